@@ -0,0 +1,212 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+//! Parser micro-benchmarks.
+//!
+//! Normalization and parsing run once per line, so a small slowdown is
+//! multiplied by tens of millions of lines. Each benchmark processes a
+//! synthetic but representative corpus and reports the time per line.
+//!
+//! Timings are only comparable on the same machine, so regressions are
+//! judged against a baseline measured there, e.g. from `main`:
+//!
+//! ```text
+//! git switch main && cargo bench --bench parsers -- --save-baseline main
+//! git switch -    && cargo bench --bench parsers -- --baseline main
+//! cargo bench --bench parsers -- normalize    # only benchmarks matching "normalize"
+//! ```
+
+use std::hint::black_box;
+use std::io::Write;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use logcrab::filetype::logcat::parse_logcat_line;
+use logcrab::filetype::pcap::parse_pcap_to_lines;
+use logcrab::parser::normalize_message;
+
+/// Lines (or packets) per corpus
+const CORPUS_LINES: usize = 20_000;
+
+/// Deterministic xorshift so corpora are identical between runs
+struct Rng(u64);
+
+impl Rng {
+    const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    const fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Messages mixing the variable parts normalization has to strip: numbers,
+/// hex, IPs, UUIDs, paths and quoted strings
+fn messages(rng: &mut Rng) -> Vec<String> {
+    const TEMPLATES: [&str; 8] = [
+        "Connection to {ip}:{port} established in {n} ms",
+        "ActivityManager: Start proc {n}:com.example.app/{hex} for activity",
+        "request {uuid} completed status={n} bytes={n}",
+        "wlan0: disassociated from {mac} reason={n}",
+        "Failed to open \"/data/user/{n}/cache/{hex}.tmp\": errno {n}",
+        "GC freed {n}KB, {n}% free {n}KB/{n}MB, paused {n}ms total {n}ms",
+        "binder: {n}:{n} transaction failed {n}/{n}, size {n}-{n} line {n}",
+        "Battery level changed to {n}% voltage={n}mV temp={n}",
+    ];
+    (0..CORPUS_LINES)
+        .map(|_| {
+            let mut message = rng.pick(&TEMPLATES).to_string();
+            while let Some(start) = message.find('{') {
+                let end = start + message[start..].find('}').unwrap_or(0) + 1;
+                let value = match &message[start..end] {
+                    "{ip}" => format!(
+                        "10.{}.{}.{}",
+                        rng.below(256),
+                        rng.below(256),
+                        rng.below(256)
+                    ),
+                    "{port}" => (1024 + rng.below(60_000)).to_string(),
+                    "{hex}" => format!("{:x}", rng.next()),
+                    "{uuid}" => {
+                        let (a, b) = (rng.next(), rng.next());
+                        format!(
+                            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                            a >> 32,
+                            a & 0xffff,
+                            (a >> 16) & 0xffff,
+                            b >> 48,
+                            b & 0xffff_ffff_ffff
+                        )
+                    }
+                    "{mac}" => {
+                        let m = rng.next();
+                        (0..6)
+                            .map(|i| format!("{:02x}", (m >> (i * 8)) & 0xff))
+                            .collect::<Vec<_>>()
+                            .join(":")
+                    }
+                    _ => rng.below(100_000).to_string(),
+                };
+                message.replace_range(start..end, &value);
+            }
+            message
+        })
+        .collect()
+}
+
+fn logcat_lines(rng: &mut Rng, messages: &[String]) -> Vec<String> {
+    const TAGS: [&str; 6] = [
+        "ActivityManager",
+        "wpa_supplicant",
+        "art",
+        "binder",
+        "BatteryService",
+        "okhttp",
+    ];
+    messages
+        .iter()
+        .enumerate()
+        .map(|(i, message)| {
+            let millis = i as u64 * 7;
+            format!(
+                "03-14 12:{:02}:{:02}.{:03}  {:5} {:5} {} {}: {message}",
+                millis / 60_000 % 60,
+                millis / 1000 % 60,
+                millis % 1000,
+                1000 + rng.below(30_000),
+                1000 + rng.below(30_000),
+                rng.pick(&["V", "D", "I", "W", "E"]),
+                rng.pick(&TAGS),
+            )
+        })
+        .collect()
+}
+
+/// Legacy pcap file of Ethernet/IPv4 UDP and TCP packets carrying `messages`
+fn write_pcap(rng: &mut Rng, messages: &[String], out: &mut impl Write) -> std::io::Result<()> {
+    // Global header: magic, version 2.4, zone, sigfigs, snaplen, Ethernet
+    out.write_all(&0xa1b2_c3d4_u32.to_le_bytes())?;
+    out.write_all(&2_u16.to_le_bytes())?;
+    out.write_all(&4_u16.to_le_bytes())?;
+    out.write_all(&[0; 8])?;
+    out.write_all(&65_535_u32.to_le_bytes())?;
+    out.write_all(&1_u32.to_le_bytes())?;
+
+    for (i, message) in messages.iter().enumerate() {
+        let payload = message.as_bytes();
+        let udp = rng.below(2) == 0;
+        let transport_len = if udp { 8 } else { 20 };
+        let ip_len = 20 + transport_len + payload.len();
+        let mut frame = Vec::with_capacity(14 + ip_len);
+        frame.extend_from_slice(&[0x02, 0, 0, 0, 0, 1, 0x02, 0, 0, 0, 0, 2, 0x08, 0x00]);
+        frame.extend_from_slice(&[0x45, 0]);
+        frame.extend_from_slice(&(ip_len as u16).to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0x40, 0, 64, if udp { 17 } else { 6 }, 0, 0]);
+        frame.extend_from_slice(&[10, 0, 0, 1 + rng.below(8) as u8]);
+        frame.extend_from_slice(&[10, 0, 1, 1 + rng.below(8) as u8]);
+        let src_port = 40_000 + rng.below(16) as u16;
+        let dst_port = [53_u16, 80, 443, 30_490][rng.below(4) as usize];
+        frame.extend_from_slice(&src_port.to_be_bytes());
+        frame.extend_from_slice(&dst_port.to_be_bytes());
+        if udp {
+            frame.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+            frame.extend_from_slice(&[0, 0]);
+        } else {
+            frame.extend_from_slice(&(i as u32).to_be_bytes());
+            frame.extend_from_slice(&[0, 0, 0, 0, 0x50, 0x18, 0xff, 0xff, 0, 0, 0, 0]);
+        }
+        frame.extend_from_slice(payload);
+
+        let micros = i as u64 * 250;
+        out.write_all(&((1_700_000_000 + micros / 1_000_000) as u32).to_le_bytes())?;
+        out.write_all(&((micros % 1_000_000) as u32).to_le_bytes())?;
+        out.write_all(&(frame.len() as u32).to_le_bytes())?;
+        out.write_all(&(frame.len() as u32).to_le_bytes())?;
+        out.write_all(&frame)?;
+    }
+    Ok(())
+}
+
+fn parsers(c: &mut Criterion) {
+    let mut rng = Rng(0x5eed_1234_abcd_ef01);
+    let messages = messages(&mut rng);
+    let logcat = logcat_lines(&mut rng, &messages);
+    let pcap_dir = tempfile::tempdir().expect("create temp dir");
+    let pcap_path = pcap_dir.path().join("corpus.pcap");
+    let mut pcap_file =
+        std::io::BufWriter::new(std::fs::File::create(&pcap_path).expect("create pcap"));
+    write_pcap(&mut rng, &messages, &mut pcap_file).expect("write pcap");
+    drop(pcap_file);
+
+    let mut group = c.benchmark_group("parsers");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(CORPUS_LINES as u64));
+    group.bench_function("normalize_message", |b| {
+        b.iter(|| {
+            for message in &messages {
+                black_box(normalize_message(black_box(message)));
+            }
+        });
+    });
+    group.bench_function("parse_logcat_line", |b| {
+        b.iter(|| {
+            for (i, line) in logcat.iter().enumerate() {
+                black_box(parse_logcat_line(black_box(line.clone()), i, 2026));
+            }
+        });
+    });
+    group.bench_function("parse_pcap_packets", |b| {
+        b.iter(|| black_box(parse_pcap_to_lines(&pcap_path).expect("parse corpus pcap")));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parsers);
+criterion_main!(benches);
@@ -187,6 +187,34 @@ else
 fi
 echo ""
 
+# 10b. Parser performance gate (opt-in), relative to a base revision
+# benchmarked on this machine
+if [ "${LOGCRAB_BENCH_GATE:-0}" = "1" ]; then
+    BENCH_BASE="${LOGCRAB_BENCH_BASE:-main}"
+    print_step "Comparing parser benchmarks against $BENCH_BASE..."
+    BENCH_WORKTREE=$(mktemp -d)
+    BENCH_TARGET="$(pwd)/target"
+    BENCH_LOG=$(mktemp)
+    if git worktree add --quiet --detach "$BENCH_WORKTREE" "$BENCH_BASE" \
+        && (cd "$BENCH_WORKTREE" && CARGO_TARGET_DIR="$BENCH_TARGET" \
+            cargo bench --quiet --bench parsers -- --save-baseline bench-gate > /dev/null) \
+        && cargo bench --quiet --bench parsers -- --baseline bench-gate --noise-threshold 0.15 \
+            | tee "$BENCH_LOG"; then
+        if grep -q "Performance has regressed" "$BENCH_LOG"; then
+            print_error "Parser benchmarks regressed by more than 15% (see output above)"
+            FAILED=1
+        else
+            print_success "No parser performance regressions"
+        fi
+    else
+        print_error "Could not benchmark $BENCH_BASE and the working tree"
+        FAILED=1
+    fi
+    git worktree remove --force "$BENCH_WORKTREE" > /dev/null 2>&1 || true
+    rm -f "$BENCH_LOG"
+    echo ""
+fi
+
 # 11. Generate documentation
 print_step "Checking documentation generation..."
 if cargo doc --no-deps --quiet; then
@@ -46,6 +46,10 @@ impl EntropyScorer {
 }
 
 impl AnomalyScorer for EntropyScorer {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
     fn score(&mut self, line: &LogLine) -> f64 {
         if self.sample_count == 0 {
             return 0.5; // Neutral score for first line
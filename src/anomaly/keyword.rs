@@ -1,12 +1,19 @@
 use crate::anomaly::scorer::AnomalyScorer;
 use crate::core::log_store::LogLine;
+use crate::filetype::Severity;
 use fancy_regex::Regex;
 use std::sync::LazyLock;
 
+/// Keywords that mark a line as an error (matched case-insensitively).
+///
+/// Shared with the built-in "Errors & Fatals" view so both agree on what an
+/// error is.
+pub const ERROR_KEYWORDS_PATTERN: &str =
+    r"\b(error|err|exception|fatal|critical|crash|panic|abort)\b";
+
 // Keywords that indicate potential issues (case-insensitive)
 static ERROR_KEYWORDS: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)\b(error|err|exception|fatal|critical|crash|panic|abort)\b")
-        .expect("valid regex literal")
+    Regex::new(&format!("(?i){ERROR_KEYWORDS_PATTERN}")).expect("valid regex literal")
 });
 
 static WARNING_KEYWORDS: LazyLock<Regex> = LazyLock::new(|| {
@@ -23,6 +30,18 @@ static ISSUE_KEYWORDS: LazyLock<Regex> = LazyLock::new(|| {
         .expect("valid regex literal")
 });
 
+/// Whether `line` is an error: its format states a level of `Error` or worse,
+/// or its message contains an error keyword.
+///
+/// The keyword scorer gives these lines its top score, and the built-in
+/// "Errors & Fatals" view shows them.
+#[must_use]
+pub fn is_error(line: &LogLine) -> bool {
+    line.severity
+        .is_some_and(|severity| severity <= Severity::Error)
+        || ERROR_KEYWORDS.is_match(&line.message).unwrap_or(false)
+}
+
 /// Keyword-based scorer - detects important keywords in messages
 /// Scores based on severity of detected keywords
 pub struct KeywordScorer {
@@ -37,11 +56,6 @@ impl KeywordScorer {
     fn score_message(message: &str) -> f64 {
         let mut score: f64 = 0.0;
 
-        // ERROR keywords = highest priority
-        if ERROR_KEYWORDS.is_match(message).unwrap_or(false) {
-            score = score.max(1.0);
-        }
-
         // FAILURE keywords = high priority
         if FAILURE_KEYWORDS.is_match(message).unwrap_or(false) {
             score = score.max(0.8);
@@ -59,11 +73,29 @@ impl KeywordScorer {
 
         score
     }
+
+    /// Score of a level the format states itself, on the keyword scale
+    const fn score_severity(severity: Severity) -> f64 {
+        match severity {
+            Severity::Emergency | Severity::Alert | Severity::Critical | Severity::Error => 1.0,
+            Severity::Warning => 0.6,
+            Severity::Notice | Severity::Info | Severity::Debug => 0.0,
+        }
+    }
 }
 
 impl AnomalyScorer for KeywordScorer {
+    fn name(&self) -> &'static str {
+        "keyword"
+    }
+
     fn score(&mut self, line: &LogLine) -> f64 {
-        Self::score_message(&line.message)
+        // ERROR keywords or levels = highest priority
+        if is_error(line) {
+            return 1.0;
+        }
+        let severity_score = line.severity.map_or(0.0, Self::score_severity);
+        Self::score_message(&line.message).max(severity_score)
     }
 
     fn update(&mut self, _line: &LogLine) {
@@ -1,6 +1,8 @@
 pub mod entropy;
 pub mod keyword;
 pub mod rarity;
+pub mod report;
+pub mod request;
 pub mod scorer;
 pub mod sidecar_client;
 pub mod temporal;
@@ -8,16 +10,74 @@ pub mod temporal;
 use entropy::EntropyScorer;
 use keyword::KeywordScorer;
 use rarity::RarityScorer;
+use request::RequestScorer;
 use scorer::CompositeScorer;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
 use temporal::TemporalScorer;
 
-/// Create the default anomaly scoring pipeline
+/// Lines at the start of a source that only warm up the scorers and keep a
+/// score of 0
+pub const WARMUP_LINES: usize = 10;
+
+/// Weights of the scorers in the composite score and the temporal window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScorerWeights {
+    pub rarity: f64,
+    pub temporal: f64,
+    pub entropy: f64,
+    pub keyword: f64,
+    pub request: f64,
+    /// Window of the temporal scorer's burst detection
+    pub temporal_window_secs: i64,
+}
+
+impl ScorerWeights {
+    pub const DEFAULT: Self = Self {
+        rarity: 5.0, // Rarity is most important
+        temporal: 2.0,
+        entropy: 1.5,
+        keyword: 2.0,
+        request: 2.0,
+        temporal_window_secs: 30,
+    };
+}
+
+impl Default for ScorerWeights {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Weights of newly created scorers; the headless tools keep the defaults
+static WEIGHTS: RwLock<ScorerWeights> = RwLock::new(ScorerWeights::DEFAULT);
+
+/// Set the weights scorers created afterwards use
+pub fn set_weights(weights: ScorerWeights) {
+    *WEIGHTS.write().expect("scorer weights lock poisoned") = weights;
+}
+
+/// Weights scorers are currently created with
+#[must_use]
+pub fn weights() -> ScorerWeights {
+    *WEIGHTS.read().expect("scorer weights lock poisoned")
+}
+
+/// Create the anomaly scoring pipeline with the current [`weights`]
 pub fn create_default_scorer() -> CompositeScorer {
+    let weights = weights();
     CompositeScorer::new()
-        .add_scorer(Box::new(RarityScorer::new()), 5.0) // Rarity is most important
-        .add_scorer(Box::new(TemporalScorer::new(30)), 2.0) // Temporal patterns
-        .add_scorer(Box::new(EntropyScorer::new()), 1.5) // Message entropy
-        .add_scorer(Box::new(KeywordScorer::new()), 2.0) // Keyword detection (error/warning/fail)
+        .add_scorer(Box::new(RarityScorer::new()), weights.rarity)
+        .add_scorer(
+            Box::new(TemporalScorer::new(weights.temporal_window_secs)),
+            weights.temporal,
+        )
+        .add_scorer(Box::new(EntropyScorer::new()), weights.entropy)
+        // Keyword detection (error/warning/fail)
+        .add_scorer(Box::new(KeywordScorer::new()), weights.keyword)
+        // HTTP 5xx and latency outliers
+        .add_scorer(Box::new(RequestScorer::new()), weights.request)
 }
 
 /// Normalize anomaly scores to 0-100 range
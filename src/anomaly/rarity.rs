@@ -19,6 +19,10 @@ impl RarityScorer {
 }
 
 impl AnomalyScorer for RarityScorer {
+    fn name(&self) -> &'static str {
+        "rarity"
+    }
+
     fn score(&mut self, line: &LogLine) -> f64 {
         if self.total_lines == 0 {
             return 1.0; // First line is always novel
@@ -0,0 +1,73 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::anomaly::{create_default_scorer, normalize_scores, WARMUP_LINES};
+use crate::core::log_store::LogLine;
+
+/// Anomaly score breakdown of one line, written as one NDJSON record by the
+/// score export.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreRecord {
+    pub source_file: String,
+    pub line_number: usize,
+    pub timestamp_unix_ms: i64,
+    /// Templates are numbered in order of first appearance within the source
+    pub template_id: usize,
+    pub template: String,
+    /// Each scorer's own score in [0, 1], keyed by scorer name. Empty for the
+    /// warm-up lines at the start of a source, which are not scored.
+    pub components: BTreeMap<&'static str, f64>,
+    /// Composite score normalized to [0, 100], as shown in the log table
+    pub score: f64,
+}
+
+/// Score `lines` of one source with the default pipeline, keeping every
+/// scorer's contribution and the template each line was scored as.
+///
+/// Mirrors the scoring done while loading a file, so `score` matches the
+/// score column for a source that was loaded without calibration.
+pub fn score_report(
+    source_file: &str,
+    lines: impl IntoIterator<Item = LogLine>,
+) -> Vec<ScoreRecord> {
+    profiling::scope!("score_report");
+    let mut scorer = create_default_scorer();
+    let mut template_ids: HashMap<String, usize> = HashMap::new();
+    let mut records = Vec::new();
+    let mut raw_scores = Vec::new();
+
+    for (idx, line) in lines.into_iter().enumerate() {
+        let template = line.template_key();
+        let next_id = template_ids.len();
+        let template_id = *template_ids.entry(template.clone()).or_insert(next_id);
+
+        let components = if idx < WARMUP_LINES {
+            BTreeMap::new()
+        } else {
+            let (raw, components) = scorer.score_components(&line);
+            raw_scores.push(raw);
+            components.into_iter().collect()
+        };
+        scorer.update(&line);
+
+        records.push(ScoreRecord {
+            source_file: source_file.to_string(),
+            line_number: line.line_number,
+            timestamp_unix_ms: line.timestamp_ms,
+            template_id,
+            template,
+            components,
+            score: 0.0,
+        });
+    }
+
+    for (record, score) in records
+        .iter_mut()
+        .skip(WARMUP_LINES)
+        .zip(normalize_scores(&raw_scores))
+    {
+        record.score = score;
+    }
+    records
+}
@@ -0,0 +1,77 @@
+use crate::anomaly::scorer::AnomalyScorer;
+use crate::core::log_store::LogLine;
+
+/// Latency samples needed before outliers are scored
+const MIN_LATENCY_SAMPLES: u32 = 20;
+
+/// Request scorer - flags server errors and unusually slow requests
+/// Only lines that record a served request (access logs) carry the metrics
+pub struct RequestScorer {
+    // Running mean/variance (Welford) of ln(1 + latency_ms)
+    count: u32,
+    mean: f64,
+    m2: f64,
+}
+
+impl RequestScorer {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Latencies are heavy-tailed, so outliers are judged on a log scale
+    fn log_latency(latency_ms: f64) -> f64 {
+        latency_ms.max(0.0).ln_1p()
+    }
+
+    fn latency_score(&self, latency_ms: f64) -> f64 {
+        if self.count < MIN_LATENCY_SAMPLES {
+            return 0.0;
+        }
+        let std_dev = (self.m2 / f64::from(self.count - 1)).sqrt();
+        if std_dev < 1e-9 {
+            return 0.0;
+        }
+        let z = (Self::log_latency(latency_ms) - self.mean) / std_dev;
+        // Two standard deviations above the mean starts to count, four is maximal
+        ((z - 2.0) / 2.0).clamp(0.0, 1.0)
+    }
+}
+
+impl AnomalyScorer for RequestScorer {
+    fn name(&self) -> &'static str {
+        "request"
+    }
+
+    fn score(&mut self, line: &LogLine) -> f64 {
+        let Some(request) = line.request else {
+            return 0.0;
+        };
+        let status_score = if request.status >= 500 { 1.0 } else { 0.0 };
+        let latency_score = request
+            .latency_ms
+            .map_or(0.0, |latency| self.latency_score(latency));
+        f64::max(status_score, latency_score)
+    }
+
+    fn update(&mut self, line: &LogLine) {
+        let Some(latency_ms) = line.request.and_then(|r| r.latency_ms) else {
+            return;
+        };
+        let x = Self::log_latency(latency_ms);
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / f64::from(self.count);
+        self.m2 += delta * (x - self.mean);
+    }
+}
+
+impl Default for RequestScorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
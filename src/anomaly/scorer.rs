@@ -2,6 +2,9 @@ use crate::core::log_store::LogLine;
 
 /// Trait for anomaly scoring components
 pub trait AnomalyScorer: Send {
+    /// Short identifier used when reporting this scorer's contribution.
+    fn name(&self) -> &'static str;
+
     /// Score a line before updating internal state.
     /// Returns a score in [0.0, 1.0] where higher = more anomalous.
     fn score(&mut self, line: &LogLine) -> f64;
@@ -43,6 +46,27 @@ impl CompositeScorer {
         weighted_sum / total_weight
     }
 
+    /// Score a line with every scorer, returning each scorer's own score
+    /// (in [0.0, 1.0]) by name alongside the weighted composite.
+    pub fn score_components(&mut self, line: &LogLine) -> (f64, Vec<(&'static str, f64)>) {
+        let total_weight: f64 = self.scorers.iter().map(|(_, w)| w).sum();
+        let components: Vec<_> = self
+            .scorers
+            .iter_mut()
+            .map(|(scorer, _)| (scorer.name(), scorer.score(line)))
+            .collect();
+
+        if total_weight == 0.0 {
+            return (0.0, components);
+        }
+        let weighted_sum: f64 = components
+            .iter()
+            .zip(&self.scorers)
+            .map(|((_, score), (_, weight))| score * weight)
+            .sum();
+        (weighted_sum / total_weight, components)
+    }
+
     pub fn update(&mut self, line: &LogLine) {
         for (scorer, _) in &mut self.scorers {
             scorer.update(line);
@@ -1,15 +1,15 @@
 use crate::anomaly::scorer::AnomalyScorer;
 use crate::core::log_store::LogLine;
-use chrono::{DateTime, Duration, Local};
+use chrono::Duration;
 use std::collections::{HashMap, VecDeque};
 
 /// Temporal anomaly scorer based on time windows
 pub struct TemporalScorer {
     window_duration: Duration,
-    // Track when each template was last seen
-    last_seen: HashMap<String, DateTime<Local>>,
-    // Track recent timestamps for burst detection
-    recent_timestamps: VecDeque<DateTime<Local>>,
+    // Track when each template was last seen (epoch milliseconds)
+    last_seen: HashMap<String, i64>,
+    // Track recent timestamps for burst detection (epoch milliseconds)
+    recent_timestamps: VecDeque<i64>,
 }
 
 impl TemporalScorer {
@@ -21,10 +21,10 @@ impl TemporalScorer {
         }
     }
 
-    fn clean_old_entries(&mut self, current_time: DateTime<Local>) {
+    fn clean_old_entries(&mut self, current_time: i64) {
         // Remove timestamps outside the window
         while let Some(&front_time) = self.recent_timestamps.front() {
-            if current_time - front_time > self.window_duration {
+            if current_time - front_time > self.window_duration.num_milliseconds() {
                 self.recent_timestamps.pop_front();
             } else {
                 break;
@@ -34,8 +34,12 @@ impl TemporalScorer {
 }
 
 impl AnomalyScorer for TemporalScorer {
+    fn name(&self) -> &'static str {
+        "temporal"
+    }
+
     fn score(&mut self, line: &LogLine) -> f64 {
-        let current_time = line.timestamp;
+        let current_time = line.timestamp_ms;
 
         self.clean_old_entries(current_time);
 
@@ -44,8 +48,7 @@ impl AnomalyScorer for TemporalScorer {
         // Component 1: Time since last occurrence (recency)
         let template_key = line.template_key();
         if let Some(&last_time) = self.last_seen.get(&template_key) {
-            let time_diff = current_time - last_time;
-            let time_diff_secs = time_diff.num_seconds().abs(); // Use absolute value for out-of-order logs
+            let time_diff_secs = ((current_time - last_time) / 1000).abs(); // Use absolute value for out-of-order logs
 
             // Compare absolute time difference to window
             let window_secs = self.window_duration.num_seconds().abs();
@@ -78,7 +81,7 @@ impl AnomalyScorer for TemporalScorer {
     }
 
     fn update(&mut self, line: &LogLine) {
-        let current_time = line.timestamp;
+        let current_time = line.timestamp_ms;
 
         // Update last seen time for this template
         self.last_seen.insert(line.template_key(), current_time);
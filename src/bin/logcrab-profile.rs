@@ -0,0 +1,194 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+//! `logcrab-profile` — headless load/score/filter/save run with memory stats.
+//!
+//! Drives the same loader, scorer, filter worker and `.crab` writer as the
+//! viewer on a reference log, then prints one JSON object to stdout:
+//! ```text
+//! { "file": "big.log", "lines": N,
+//!   "milestones": [ { "name": "loaded", "elapsed_ms": T, "heap_bytes": B }, ... ],
+//!   "heap": { "max_bytes": ..., "max_blocks": ..., "total_bytes": ..., "total_blocks": ..., "curr_bytes": ... },
+//!   "peak_rss_kib": K }
+//! ```
+//! so CI can track time and memory budgets across releases. Heap statistics
+//! come from DHAT, hence the binary requires the `ram-profiling` feature;
+//! build with `profiling` to also stream the scopes to Tracy.
+//!
+//! Note: like the viewer, the run reads and writes the `.crab` file next to the log.
+//!
+//! Usage: `cargo run --release --features ram-profiling --bin logcrab-profile -- big.log`
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Context as _;
+use clap::Parser;
+use serde_json::{json, Value};
+
+use logcrab::core::column_filter::ColumnFilter;
+use logcrab::core::filter_worker::FilterRequest;
+use logcrab::core::log_store::GlobalFileConfig;
+use logcrab::core::slow_filter::{self, DEFAULT_LINE_BACKTRACK_LIMIT};
+use logcrab::core::{FilterWorker, LogFileLoader, LogStore};
+use logcrab::ui::ToastManager;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+/// How often the background loader is polled for progress
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Parser, Debug)]
+#[command(name = "logcrab-profile")]
+#[command(author = "LogCrab Team")]
+#[command(version)]
+#[command(
+    about = "Load, score, filter and save a log headlessly and print timing and memory stats as JSON"
+)]
+struct Args {
+    /// Reference log file to load
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Search pattern timed through the filter worker
+    #[arg(long, default_value = "(?i)error|fail|exception")]
+    filter: String,
+
+    /// Path for the full DHAT heap profile
+    #[arg(long, value_name = "PROFILE_FILE", default_value = "dhat-heap.json")]
+    profile_output: PathBuf,
+}
+
+/// Wall-clock and heap snapshots taken between pipeline stages
+struct Milestones {
+    start: Instant,
+    entries: Vec<Value>,
+}
+
+impl Milestones {
+    fn start() -> Self {
+        Self {
+            start: Instant::now(),
+            entries: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, name: &str) {
+        let heap = dhat::HeapStats::get();
+        self.entries.push(json!({
+            "name": name,
+            "elapsed_ms": self.start.elapsed().as_millis(),
+            "heap_bytes": heap.curr_bytes,
+        }));
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let profiler = dhat::Profiler::builder()
+        .file_name(args.profile_output.clone())
+        .build();
+
+    let report = run(&args.file, &args.filter)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    // Writes the DHAT profile
+    drop(profiler);
+    Ok(())
+}
+
+fn run(path: &Path, filter: &str) -> anyhow::Result<Value> {
+    let regex = fancy_regex::Regex::new(filter).context("invalid --filter pattern")?;
+    let mut milestones = Milestones::start();
+
+    // ── Load ─────────────────────────────────────────────────────────────────
+    let toasts = ToastManager::new(egui::Context::default());
+    let toast = toasts.create_progress_toast("Loading", path.display().to_string());
+    let store = LogStore::new();
+    let (variant, _, _) = LogFileLoader::load_file(
+        path,
+        &toast,
+        &toasts.sender(),
+        &GlobalFileConfig::default(),
+        &store,
+    )
+    .with_context(|| format!("cannot open {}", path.display()))?;
+    let source_id = variant.source_id();
+    store.add_source(variant);
+    milestones.record("opened");
+
+    while !store.is_source_loaded(source_id) {
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    milestones.record("loaded");
+    let lines = store.total_lines();
+    anyhow::ensure!(lines > 0, "no log lines found in {}", path.display());
+
+    // ── Score ────────────────────────────────────────────────────────────────
+    // The loader dismisses its toast once heuristic scoring is done
+    while !toast.is_dismissed() {
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    milestones.record("scored");
+
+    // ── Filter ───────────────────────────────────────────────────────────────
+    // Measure the whole pass instead of aborting it as too slow
+    slow_filter::set_limits(DEFAULT_LINE_BACKTRACK_LIMIT, 0);
+    let worker = FilterWorker::new();
+    let (result_tx, result_rx) = channel();
+    worker.handle().send_request(FilterRequest {
+        filter_id: 0,
+        regex,
+        exclude_regex: None,
+        store: Arc::clone(&store),
+        result_tx,
+        search_text: filter.to_string(),
+        exclude_text: String::new(),
+        case_sensitive: true,
+        hide_duplicates: false,
+        column_filter: ColumnFilter::default(),
+        or_errors: false,
+        time_scope: None,
+        cancelled: Arc::new(AtomicBool::new(false)),
+    });
+    let matches = result_rx
+        .recv()
+        .context("filter worker exited")?
+        .filtered_indices
+        .len();
+    milestones.record("filtered");
+
+    // ── Session save ─────────────────────────────────────────────────────────
+    store.save_all_crab_files(&[], &[]);
+    store.flush_crab_files();
+    milestones.record("session_saved");
+
+    let heap = dhat::HeapStats::get();
+    Ok(json!({
+        "file": path.file_name().map(|name| name.to_string_lossy()),
+        "lines": lines,
+        "filter_matches": matches,
+        "milestones": milestones.entries,
+        "heap": {
+            "max_bytes": heap.max_bytes,
+            "max_blocks": heap.max_blocks,
+            "total_bytes": heap.total_bytes,
+            "total_blocks": heap.total_blocks,
+            "curr_bytes": heap.curr_bytes,
+        },
+        "peak_rss_kib": peak_rss_kib(),
+    }))
+}
+
+/// Peak resident set size of this process (Linux `VmHWM`), if available
+fn peak_rss_kib() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
@@ -16,14 +16,18 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod persist;
 pub mod session_history;
+pub mod settings_bundle;
 
+use crate::anomaly::ScorerWeights;
+use crate::core::redaction::RedactionRule;
 use crate::core::SearchRule;
+use crate::i18n::Locale;
 use crate::input::ShortcutAction;
-use fs2::FileExt;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
 
 /// DLT timestamp source configuration
@@ -36,6 +40,47 @@ pub enum DltTimestampSource {
     InferredMonotonic,
 }
 
+/// How absolute timestamps are displayed in the log table and copied
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    /// Local wall-clock time, e.g. `2026-02-11 13:45:49.663`
+    #[default]
+    Local,
+    /// The same layout converted to UTC
+    Utc,
+    /// ISO-8601 with the local offset, e.g. `2026-02-11T13:45:49.663+01:00`
+    Iso8601,
+    /// Milliseconds since the Unix epoch
+    EpochMillis,
+}
+
+impl TimestampFormat {
+    pub const ALL: [Self; 4] = [Self::Local, Self::Utc, Self::Iso8601, Self::EpochMillis];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Local => "Local",
+            Self::Utc => "UTC",
+            Self::Iso8601 => "ISO-8601",
+            Self::EpochMillis => "Epoch millis",
+        }
+    }
+
+    #[must_use]
+    pub fn format(self, timestamp: DateTime<Local>) -> String {
+        match self {
+            Self::Local => timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            Self::Utc => timestamp
+                .with_timezone(&Utc)
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
+            Self::Iso8601 => timestamp.to_rfc3339_opts(SecondsFormat::Millis, false),
+            Self::EpochMillis => timestamp.timestamp_millis().to_string(),
+        }
+    }
+}
+
 /// Current schema version. Bump this whenever the config format changes in a
 /// backwards-incompatible way. Old binaries that don't know this version will
 /// fall back to defaults on load rather than silently corrupting the file.
@@ -47,7 +92,24 @@ pub enum DltTimestampSource {
 ///         `grey_rare_ml_lines`, `sidecar_host`, `sidecar_port`, `selected_model`
 ///   v3 — added `hide_duplicates`
 ///   v4 — added `file_config.pcap` (`PcapConfig`) with `show_mac_addresses`
-pub const SCHEMA_VERSION: u32 = 4;
+///   v5 — added `quick_bookmark_template`
+///   v6 — added `max_fps`
+///   v7 — added `column_layouts`
+///   v8 — added `timestamp_format`
+///   v9 — added `max_retained_lines`
+///   v10 — added `record_activity`
+///   v11 — added `focus_follows_mouse`
+///   v12 — added `redaction_rules`
+///   v13 — added `locale`
+///   v14 — added `filter_timeout_secs` and `regex_backtrack_limit`
+///   v15 — added `shared_settings_path`
+///   v16 — added `ColumnLayout::color_components`
+///   v17 — added `show_score_legend` and `score_threshold`
+///   v18 — added `show_raw_panel`
+///   v19 — added `ColumnLayout::shown`
+///   v20 — added `show_previous_run`
+///   v21 — added `scorer_weights`
+pub const SCHEMA_VERSION: u32 = 21;
 
 /// Global user configuration stored in config directory
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +154,11 @@ pub struct GlobalConfig {
     #[serde(skip)]
     pub read_only: bool,
 
+    /// `true` when no config file existed at load time, i.e. on the very
+    /// first launch. Never persisted: the next save creates the file.
+    #[serde(skip)]
+    pub first_run: bool,
+
     /// Use LogBERT sidecar for anomaly scoring (default: false)
     #[serde(default)]
     pub use_sidecar_scoring: bool,
@@ -120,6 +187,77 @@ pub struct GlobalConfig {
     /// `None` means no model is selected; sidecar scoring will be skipped.
     #[serde(default)]
     pub selected_model: Option<String>,
+
+    /// Name template for bookmarks created with the quick bookmark shortcut
+    /// (see [`crate::core::bookmark_template`]).
+    #[serde(default = "default_quick_bookmark_template")]
+    pub quick_bookmark_template: String,
+
+    /// Frame rate cap for repaints driven by background work; 0 means unlimited.
+    #[serde(default)]
+    pub max_fps: u32,
+
+    /// Log table column layout per log format, keyed by filetype slug
+    /// (see [`crate::core::LogStore::column_layout_key`]).
+    #[serde(default)]
+    pub column_layouts: HashMap<String, ColumnLayout>,
+
+    /// Display format for absolute timestamps in the log table
+    #[serde(default)]
+    pub timestamp_format: TimestampFormat,
+
+    /// Lines kept in memory per live source before the oldest are evicted; 0 means unlimited.
+    #[serde(default)]
+    pub max_retained_lines: usize,
+
+    /// Record bookmark, filter and calibration actions in the session's activity log
+    #[serde(default)]
+    pub record_activity: bool,
+
+    /// Give keyboard focus to the pane under the mouse pointer, so navigation
+    /// keys act on the pane being looked at (default: false)
+    #[serde(default)]
+    pub focus_follows_mouse: bool,
+
+    /// Regex replacements applied to exported lines to mask personal data
+    #[serde(default = "RedactionRule::presets")]
+    pub redaction_rules: Vec<RedactionRule>,
+
+    /// Language of the user interface
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// Seconds after which a filter pass is aborted as too slow; 0 means no limit.
+    #[serde(default = "default_filter_timeout_secs")]
+    pub filter_timeout_secs: u32,
+
+    /// Backtracking steps one line may take in a search pattern before it is skipped
+    #[serde(default = "default_regex_backtrack_limit")]
+    pub regex_backtrack_limit: usize,
+
+    /// Settings bundle applied at every startup, e.g. a team-standard file on a share
+    #[serde(default)]
+    pub shared_settings_path: Option<PathBuf>,
+
+    /// Show the score color legend above the status bar (default: false)
+    #[serde(default)]
+    pub show_score_legend: bool,
+
+    /// Lines scoring below this (0–100) are dimmed in every tab; 0 dims nothing.
+    #[serde(default)]
+    pub score_threshold: f64,
+
+    /// Show the raw file lines around the selected line (default: false)
+    #[serde(default)]
+    pub show_raw_panel: bool,
+
+    /// Draw the earlier run of a log behind its histogram (default: false)
+    #[serde(default)]
+    pub show_previous_run: bool,
+
+    /// Weights of the heuristic anomaly scorers
+    #[serde(default)]
+    pub scorer_weights: ScorerWeights,
 }
 
 fn default_sidecar_host() -> String {
@@ -134,11 +272,24 @@ const fn default_grey_rare_ml_lines() -> bool {
     true
 }
 
+const fn default_filter_timeout_secs() -> u32 {
+    crate::core::slow_filter::DEFAULT_FILTER_TIMEOUT_SECS
+}
+
+const fn default_regex_backtrack_limit() -> usize {
+    crate::core::slow_filter::DEFAULT_LINE_BACKTRACK_LIMIT
+}
+
+fn default_quick_bookmark_template() -> String {
+    crate::core::bookmark_template::DEFAULT_QUICK_BOOKMARK_TEMPLATE.to_string()
+}
+
 impl Default for GlobalConfig {
     fn default() -> Self {
         Self {
             schema_version: SCHEMA_VERSION,
             read_only: false,
+            first_run: false,
             shortcuts: HashMap::new(),
             favorite_filters: Vec::new(),
             bright_mode: false,
@@ -153,10 +304,95 @@ impl Default for GlobalConfig {
             sidecar_host: default_sidecar_host(),
             sidecar_port: default_sidecar_port(),
             selected_model: None,
+            quick_bookmark_template: default_quick_bookmark_template(),
+            max_fps: 0,
+            timestamp_format: TimestampFormat::Local,
+            max_retained_lines: 0,
+            record_activity: false,
+            focus_follows_mouse: false,
+            redaction_rules: RedactionRule::presets(),
+            locale: Locale::English,
+            filter_timeout_secs: default_filter_timeout_secs(),
+            regex_backtrack_limit: default_regex_backtrack_limit(),
+            shared_settings_path: None,
+            show_score_legend: false,
+            score_threshold: 0.0,
+            show_raw_panel: false,
+            show_previous_run: false,
+            scorer_weights: ScorerWeights::DEFAULT,
+            column_layouts: HashMap::new(),
         }
     }
 }
 
+/// Name of the log table column counting each line's message template
+pub const COUNT_COLUMN: &str = "Count";
+
+/// Visibility and widths of the log table columns for one log format
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    /// Names of the hidden columns
+    #[serde(default)]
+    pub hidden: BTreeSet<String>,
+    /// Names of the opt-in columns the user turned on
+    #[serde(default)]
+    pub shown: BTreeSet<String>,
+    /// User-resized column widths by column name
+    #[serde(default)]
+    pub widths: BTreeMap<String, f32>,
+    /// Color each line's tag / APID / process by a hash of its name
+    #[serde(default)]
+    pub color_components: bool,
+}
+
+impl ColumnLayout {
+    /// Columns hidden until the user turns them on
+    pub const OPT_IN_COLUMNS: [&'static str; 1] = [COUNT_COLUMN];
+
+    #[must_use]
+    pub fn is_visible(&self, column: &str) -> bool {
+        if Self::OPT_IN_COLUMNS.contains(&column) {
+            self.shown.contains(column)
+        } else {
+            !self.hidden.contains(column)
+        }
+    }
+
+    pub fn set_visible(&mut self, column: &str, visible: bool) {
+        let (names, listed) = if Self::OPT_IN_COLUMNS.contains(&column) {
+            (&mut self.shown, visible)
+        } else {
+            (&mut self.hidden, !visible)
+        };
+        if listed {
+            names.insert(column.to_string());
+        } else {
+            names.remove(column);
+        }
+    }
+
+    /// Show every column, including the opt-in ones
+    pub fn show_all(&mut self) {
+        self.hidden.clear();
+        self.shown = Self::OPT_IN_COLUMNS
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+    }
+
+    /// Stored width of `column`, or `default` if it was never resized
+    #[must_use]
+    pub fn width(&self, column: &str, default: f32) -> f32 {
+        self.widths.get(column).copied().unwrap_or(default)
+    }
+
+    /// Remember the width of `column`, rounded to whole pixels so that
+    /// sub-pixel layout jitter doesn't count as a change.
+    pub fn set_width(&mut self, column: &str, width: f32) {
+        self.widths.insert(column.to_string(), width.round());
+    }
+}
+
 /// A favorite filter that can be quickly added to any log
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FavoriteFilter {
@@ -269,14 +505,23 @@ impl GlobalConfig {
         }
     }
 
-    /// Load global config from disk at startup.
+    /// Load global config at startup.
+    ///
+    /// The file is read once per process; later calls return the in-memory
+    /// config, which [`Self::update`] keeps current.
     ///
     /// - **Missing `schema_version`**: treated as v0, migrated to current.
     /// - **version < current**: deserialized, then migration logic runs.
     /// - **version == current**: deserialized as-is.
     /// - **version > current**: falls back to defaults with `read_only = true`
     ///   so `update()` will not overwrite the newer-version file.
+    #[must_use]
     pub fn load() -> Self {
+        persist::current()
+    }
+
+    /// Read the config file, see [`Self::load`]
+    fn read_from_disk() -> Self {
         if let Some(path) = Self::config_path() {
             if path.exists() {
                 tracing::info!("Loading global config from {}", path.display());
@@ -286,76 +531,71 @@ impl GlobalConfig {
                 }
             } else {
                 tracing::info!("No global config found, using defaults");
+                return Self {
+                    first_run: true,
+                    ..Self::default()
+                };
             }
         }
         Self::default()
     }
 
-    /// Atomically update the on-disk config.
+    /// Apply `f` to the config and save it in the background.
     ///
-    /// Acquires an exclusive advisory lock on the config file, re-reads the
-    /// current on-disk state, applies `f`, writes back, and releases the lock.
-    /// Concurrent instances will block on the lock rather than interleaving
-    /// their read-modify-write cycles.
+    /// Returns at once with the updated config so the caller can replace its
+    /// cached copy. The save follows shortly after, coalesced with further
+    /// updates, and takes over only the settings changed in this process into
+    /// the file as it is on disk then; see [`persist`].
     ///
     /// When the config is read-only (on-disk version is newer than this
-    /// binary), `f` is applied only to the in-memory state and no write
-    /// occurs, preserving the session's in-memory settings without touching
-    /// the file.
-    ///
-    /// Returns the updated config so the caller can replace its cached copy.
-    pub fn update(f: impl FnOnce(&mut GlobalConfig)) -> Result<GlobalConfig, String> {
-        let path = Self::config_path().ok_or("Could not determine config directory")?;
-
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {e}"))?;
-        }
-
-        // Open or create the file and hold an exclusive lock for the entire
-        // read-modify-write cycle.
-        let mut file = std::fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(&path)
-            .map_err(|e| format!("Failed to open config file: {e}"))?;
-
-        file.lock_exclusive()
-            .map_err(|e| format!("Failed to lock config file: {e}"))?;
-
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)
-            .map_err(|e| format!("Failed to read config file: {e}"))?;
-
-        let mut config = if contents.is_empty() {
-            Self::default()
-        } else {
-            Self::parse_contents(&contents)
-        };
+    /// binary), `f` is applied only to the in-memory state and nothing is
+    /// written.
+    pub fn update(f: impl FnOnce(&mut GlobalConfig)) -> GlobalConfig {
+        persist::update(f)
+    }
 
-        // Apply the caller's mutation. For read-only configs we still apply
-        // in-memory so the current session reflects the change.
-        f(&mut config);
+    /// Block until all updates made so far are saved, e.g. before exiting.
+    pub fn flush() {
+        persist::flush();
+    }
+}
 
-        if config.read_only {
-            tracing::warn!("Config is read-only (on-disk version is newer) — changes not persisted");
-            file.unlock().ok();
-            return Ok(config);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDateTime, TimeZone};
+
+    fn sample_timestamp() -> DateTime<Local> {
+        Utc.with_ymd_and_hms(2026, 2, 11, 12, 45, 49)
+            .single()
+            .expect("valid UTC time")
+            .with_timezone(&Local)
+            + chrono::Duration::milliseconds(663)
+    }
 
-        let json = serde_json::to_string_pretty(&config)
-            .map_err(|e| format!("Failed to serialize config: {e}"))?;
+    #[test]
+    fn test_timestamp_format_utc_and_epoch() {
+        let ts = sample_timestamp();
+        assert_eq!(TimestampFormat::Utc.format(ts), "2026-02-11 12:45:49.663");
+        assert_eq!(TimestampFormat::EpochMillis.format(ts), "1770813949663");
+    }
 
-        file.seek(SeekFrom::Start(0))
-            .map_err(|e| format!("Failed to seek config file: {e}"))?;
-        file.set_len(0)
-            .map_err(|e| format!("Failed to truncate config file: {e}"))?;
-        file.write_all(json.as_bytes())
-            .map_err(|e| format!("Failed to write config file: {e}"))?;
+    #[test]
+    fn test_timestamp_format_local_shows_wall_clock() {
+        let ts = sample_timestamp();
+        let text = TimestampFormat::Local.format(ts);
+        let parsed =
+            NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.3f").expect("local layout");
+        assert_eq!(parsed, ts.naive_local());
+    }
 
-        // Lock releases when `file` is dropped here.
-        tracing::info!("Updated global config");
-        Ok(config)
+    #[test]
+    fn test_timestamp_format_iso8601_round_trips() {
+        let ts = sample_timestamp();
+        let text = TimestampFormat::Iso8601.format(ts);
+        assert!(text.contains('T'));
+        assert!(text.contains(".663"));
+        let parsed = DateTime::parse_from_rfc3339(&text).expect("RFC 3339");
+        assert_eq!(parsed, ts);
     }
 }
@@ -0,0 +1,457 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Saving and reloading the [`GlobalConfig`] off the UI thread.
+//!
+//! [`GlobalConfig::update`] changes the in-memory config and returns at once.
+//! A writer thread saves it shortly after, so a burst of changes (toggling
+//! several favorites, resizing a column) becomes a single write. A write only
+//! takes over the settings this process changed since the file was last
+//! read, down to single map entries and list items, so settings another
+//! running instance saved in the meantime survive.
+//!
+//! [`ConfigWatcher`] reads the file again whenever it changes on disk, e.g.
+//! when another instance saved or the user edited it by hand.
+
+use super::GlobalConfig;
+use fs2::FileExt;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// How long the writer waits for further changes before saving
+const COALESCE_DELAY: Duration = Duration::from_millis(300);
+
+/// The config as this process sees it and as it was last read or written
+struct Synced {
+    current: GlobalConfig,
+    /// State of the file when it was last read or written; changes of
+    /// `current` relative to it are what a write takes over.
+    base: GlobalConfig,
+    /// `current` has changes that are not saved yet
+    dirty: bool,
+}
+
+static SYNCED: LazyLock<Mutex<Synced>> = LazyLock::new(|| {
+    let config = GlobalConfig::read_from_disk();
+    Mutex::new(Synced {
+        base: config.clone(),
+        current: config,
+        dirty: false,
+    })
+});
+
+enum Request {
+    Save,
+    /// Save now and report back once done
+    Flush(Sender<()>),
+}
+
+static WRITER: LazyLock<Sender<Request>> = LazyLock::new(|| {
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("config-writer".to_string())
+        .spawn(move || run_writer(&rx))
+        .expect("failed to spawn config writer thread");
+    tx
+});
+
+fn synced() -> MutexGuard<'static, Synced> {
+    SYNCED.lock().expect("config lock poisoned")
+}
+
+/// The in-memory config, read from disk on first use
+pub(super) fn current() -> GlobalConfig {
+    synced().current.clone()
+}
+
+/// Apply `f` in memory and schedule a save
+pub(super) fn update(f: impl FnOnce(&mut GlobalConfig)) -> GlobalConfig {
+    let mut synced = synced();
+    f(&mut synced.current);
+    if synced.current.read_only {
+        tracing::warn!("Config is read-only (on-disk version is newer) — changes not persisted");
+        return synced.current.clone();
+    }
+    // The save creates the file, so this is no longer the first run
+    synced.current.first_run = false;
+    synced.dirty = true;
+    let _ = WRITER.send(Request::Save);
+    synced.current.clone()
+}
+
+/// Block until all changes made so far are saved
+pub(super) fn flush() {
+    let (tx, rx) = mpsc::channel();
+    if WRITER.send(Request::Flush(tx)).is_ok() {
+        let _ = rx.recv();
+    }
+}
+
+fn run_writer(requests: &Receiver<Request>) {
+    while let Ok(first) = requests.recv() {
+        let mut flushed = Vec::new();
+        let mut next = Some(first);
+        // Gather the burst; a flush request ends it early
+        while let Some(request) = next {
+            next = match request {
+                Request::Save => requests.recv_timeout(COALESCE_DELAY).ok(),
+                Request::Flush(done) => {
+                    flushed.push(done);
+                    None
+                }
+            };
+        }
+        save_pending();
+        for done in flushed {
+            let _ = done.send(());
+        }
+    }
+}
+
+fn save_pending() {
+    let (base, snapshot) = {
+        let mut synced = synced();
+        if !synced.dirty {
+            return;
+        }
+        synced.dirty = false;
+        (synced.base.clone(), synced.current.clone())
+    };
+    match write_merged(&base, &snapshot) {
+        Ok(saved) => {
+            let mut synced = synced();
+            // Keep changes made while the file was written
+            synced.current = merge_changes(&snapshot, &synced.current, &saved);
+            synced.base = saved;
+            drop(synced);
+            tracing::info!("Updated global config");
+        }
+        Err(e) => tracing::error!("Failed to save config: {e}"),
+    }
+}
+
+/// Write the settings `current` changed relative to `base` into the file.
+///
+/// Holds an exclusive advisory lock on the file for the whole
+/// read-modify-write cycle, so concurrent instances do not interleave.
+/// Returns the config as written.
+fn write_merged(base: &GlobalConfig, current: &GlobalConfig) -> Result<GlobalConfig, String> {
+    let path = GlobalConfig::config_path().ok_or("Could not determine config directory")?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {e}"))?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .map_err(|e| format!("Failed to open config file: {e}"))?;
+
+    file.lock_exclusive()
+        .map_err(|e| format!("Failed to lock config file: {e}"))?;
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|e| format!("Failed to read config file: {e}"))?;
+
+    let on_disk = if contents.is_empty() {
+        GlobalConfig::default()
+    } else {
+        GlobalConfig::parse_contents(&contents)
+    };
+    let config = merge_changes(base, current, &on_disk);
+
+    if config.read_only {
+        tracing::warn!("Config is read-only (on-disk version is newer) — changes not persisted");
+        return Ok(config);
+    }
+
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {e}"))?;
+
+    file.seek(SeekFrom::Start(0))
+        .map_err(|e| format!("Failed to seek config file: {e}"))?;
+    file.set_len(0)
+        .map_err(|e| format!("Failed to truncate config file: {e}"))?;
+    file.write_all(json.as_bytes())
+        .map_err(|e| format!("Failed to write config file: {e}"))?;
+
+    // Lock releases when `file` is dropped here.
+    Ok(config)
+}
+
+/// `onto`, with every setting that differs between `base` and `changed`
+/// taken from `changed`
+fn merge_changes(base: &GlobalConfig, changed: &GlobalConfig, onto: &GlobalConfig) -> GlobalConfig {
+    let (Ok(base), Ok(changed), Ok(mut merged)) = (
+        serde_json::to_value(base),
+        serde_json::to_value(changed),
+        serde_json::to_value(onto),
+    ) else {
+        return onto.clone();
+    };
+    merge_value(&base, &changed, &mut merged);
+    match serde_json::from_value::<GlobalConfig>(merged) {
+        Ok(config) => GlobalConfig {
+            read_only: onto.read_only,
+            first_run: onto.first_run,
+            ..config
+        },
+        Err(e) => {
+            tracing::warn!("Failed to merge config changes: {e}");
+            onto.clone()
+        }
+    }
+}
+
+/// Apply the edits from `base` to `changed` to `onto`, entry by entry.
+///
+/// Maps such as the column layouts or shortcuts are merged per key, so
+/// entries the other side added or changed survive. A list the other side
+/// left alone is replaced; otherwise the items added and removed here are
+/// added to and removed from its version.
+fn merge_value(base: &Value, changed: &Value, onto: &mut Value) {
+    if base == changed {
+        return;
+    }
+    match (base, changed, &mut *onto) {
+        (Value::Object(base), Value::Object(changed), Value::Object(onto)) => {
+            for (key, value) in changed {
+                match (base.get(key), onto.get_mut(key)) {
+                    (Some(base_value), Some(onto_value)) => {
+                        merge_value(base_value, value, onto_value);
+                    }
+                    // Removed by the other side and left alone here
+                    (Some(base_value), None) if base_value == value => {}
+                    _ => {
+                        onto.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            for key in base.keys().filter(|key| !changed.contains_key(*key)) {
+                onto.remove(key);
+            }
+        }
+        (Value::Array(base), Value::Array(changed), Value::Array(onto)) if base != onto => {
+            onto.retain(|item| changed.contains(item) || !base.contains(item));
+            for item in changed {
+                if !base.contains(item) && !onto.contains(item) {
+                    onto.push(item.clone());
+                }
+            }
+        }
+        _ => onto.clone_from(changed),
+    }
+}
+
+/// Take over the file's contents after it changed on disk.
+///
+/// Unsaved changes of this process are kept on top. Returns what changed,
+/// or `None` if nothing changed for this process (e.g. after its own write).
+fn reload(path: &Path) -> Option<ConfigReload> {
+    let mut file = std::fs::File::open(path).ok()?;
+    // A writer holds the exclusive lock until the file is complete
+    file.lock_shared().ok()?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).ok()?;
+    drop(file);
+    // Half-written by an editor: wait for the next change
+    serde_json::from_str::<Value>(&contents).ok()?;
+    let on_disk = GlobalConfig::parse_contents(&contents);
+
+    let mut synced = synced();
+    let config = merge_changes(&synced.base, &synced.current, &on_disk);
+    let unchanged = serde_json::to_value(&config).ok() == serde_json::to_value(&synced.current).ok();
+    synced.base = on_disk;
+    if unchanged {
+        return None;
+    }
+    let before = std::mem::replace(&mut synced.current, config.clone());
+    drop(synced);
+    Some(ConfigReload {
+        before,
+        after: config,
+    })
+}
+
+/// Settings taken over from the config file after it changed on disk
+pub struct ConfigReload {
+    before: GlobalConfig,
+    after: GlobalConfig,
+}
+
+impl ConfigReload {
+    /// Take over the reloaded settings into `config`, a cached copy.
+    ///
+    /// Edits of the copy not handed to [`GlobalConfig::update`] yet, such as
+    /// a value still being dragged, are kept.
+    pub fn apply_to(&self, config: &mut GlobalConfig) {
+        *config = merge_changes(&self.before, &self.after, config);
+    }
+}
+
+/// Reports changes of the config file made outside this process.
+pub struct ConfigWatcher {
+    /// `None` if the platform watcher could not be created; then nothing is reported
+    _watcher: Option<RecommendedWatcher>,
+    reloaded: Receiver<ConfigReload>,
+}
+
+impl ConfigWatcher {
+    /// Create a watcher that rereads the file in the background and wakes up
+    /// the UI when its settings changed.
+    #[must_use]
+    pub fn with_repaint(ctx: egui::Context) -> Self {
+        let (tx, reloaded) = mpsc::channel();
+        let watcher = GlobalConfig::config_path().and_then(|path| {
+            // Watch the directory: editors save by replacing the file
+            let dir = path.parent()?.to_path_buf();
+            std::fs::create_dir_all(&dir).ok()?;
+            let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+                let Ok(event) = event else { return };
+                if matches!(event.kind, EventKind::Access(_))
+                    || !event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == path.file_name())
+                {
+                    return;
+                }
+                if let Some(reload) = reload(&path) {
+                    let _ = tx.send(reload);
+                    ctx.request_repaint();
+                }
+            })
+            .inspect_err(|e| tracing::warn!("Config change detection unavailable: {e}"))
+            .ok()?;
+            watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .inspect_err(|e| tracing::warn!("Cannot watch {} for changes: {e}", dir.display()))
+                .ok()?;
+            Some(watcher)
+        });
+        Self {
+            _watcher: watcher,
+            reloaded,
+        }
+    }
+
+    /// The changes reloaded since the last call, if any
+    #[must_use]
+    pub fn poll(&self) -> Option<ConfigReload> {
+        self.reloaded.try_iter().reduce(|first, last| ConfigReload {
+            before: first.before,
+            after: last.after,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_changes_from_both_sides() {
+        let base = GlobalConfig::default();
+        let mut ours = base.clone();
+        ours.bright_mode = !base.bright_mode;
+        let mut theirs = base.clone();
+        theirs.max_fps = base.max_fps + 1;
+
+        let merged = merge_changes(&base, &ours, &theirs);
+        assert_eq!(merged.bright_mode, ours.bright_mode);
+        assert_eq!(merged.max_fps, theirs.max_fps);
+
+        // Settings left alone here do not overwrite the other side
+        let merged = merge_changes(&base, &base, &theirs);
+        assert_eq!(merged.max_fps, theirs.max_fps);
+    }
+
+    #[test]
+    fn merge_keeps_entries_both_writers_added_to_a_map() {
+        use crate::config::{ColumnLayout, FavoriteFilter};
+
+        let base = GlobalConfig::default();
+        let mut ours = base.clone();
+        ours.column_layouts
+            .entry("logcat".to_string())
+            .or_insert_with(ColumnLayout::default)
+            .set_width("Tag", 120.0);
+        ours.favorite_filters
+            .push(FavoriteFilter::new("ours".to_string(), false));
+        let mut theirs = base.clone();
+        theirs
+            .column_layouts
+            .entry("dlt".to_string())
+            .or_insert_with(ColumnLayout::default)
+            .set_visible("ECU", false);
+        theirs
+            .favorite_filters
+            .push(FavoriteFilter::new("theirs".to_string(), false));
+
+        let merged = merge_changes(&base, &ours, &theirs);
+        assert_eq!(merged.column_layouts["logcat"].width("Tag", 0.0), 120.0);
+        assert!(!merged.column_layouts["dlt"].is_visible("ECU"));
+        let favorites: Vec<&str> = merged
+            .favorite_filters
+            .iter()
+            .map(FavoriteFilter::display_name)
+            .collect();
+        assert_eq!(favorites, ["theirs", "ours"]);
+
+        // The next edit on the other side builds on the merged file
+        let mut theirs_again = merged.clone();
+        theirs_again
+            .column_layouts
+            .get_mut("logcat")
+            .expect("logcat layout")
+            .set_width("Message", 400.0);
+        theirs_again.favorite_filters.remove(1);
+        let merged = merge_changes(&merged, &theirs_again, &merged);
+        assert_eq!(merged.column_layouts["logcat"].width("Tag", 0.0), 120.0);
+        assert_eq!(merged.column_layouts["logcat"].width("Message", 0.0), 400.0);
+        assert_eq!(merged.favorite_filters.len(), 1);
+    }
+
+    #[test]
+    fn reload_keeps_edits_not_saved_yet() {
+        let before = GlobalConfig::default();
+        let mut after = before.clone();
+        after.bright_mode = !before.bright_mode;
+        let reload = ConfigReload {
+            before: before.clone(),
+            after: after.clone(),
+        };
+
+        // A value still being dragged in the settings menu
+        let mut cached = before.clone();
+        cached.max_fps = before.max_fps + 10;
+        reload.apply_to(&mut cached);
+        assert_eq!(cached.bright_mode, after.bright_mode);
+        assert_eq!(cached.max_fps, before.max_fps + 10);
+    }
+}
@@ -0,0 +1,152 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Settings bundles: the shareable part of [`GlobalConfig`] in one JSON file,
+//! for handing a team-standard setup to colleagues.
+
+use super::{ColumnLayout, FavoriteFilter, GlobalConfig, TimestampFormat};
+use crate::core::log_store::GlobalFileConfig;
+use crate::core::redaction::RedactionRule;
+use crate::input::ShortcutAction;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Current version of the settings bundle format.
+///
+/// History:
+///   v1 — initial format
+pub const SETTINGS_BUNDLE_VERSION: u32 = 1;
+
+/// Shortcuts, favorites, appearance and format settings of a [`GlobalConfig`].
+///
+/// Machine-specific settings (recent directories, sidecar address, frame
+/// rate, ...) are left out. Every field is optional in the file so a bundle
+/// may carry only the settings a team wants to standardize.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub version: u32,
+    #[serde(default)]
+    pub shortcuts: HashMap<ShortcutAction, String>,
+    #[serde(default)]
+    pub favorite_filters: Vec<FavoriteFilter>,
+    #[serde(default)]
+    pub bright_mode: Option<bool>,
+    #[serde(default)]
+    pub timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    pub file_config: Option<GlobalFileConfig>,
+    #[serde(default)]
+    pub column_layouts: HashMap<String, ColumnLayout>,
+    #[serde(default)]
+    pub redaction_rules: Vec<RedactionRule>,
+    #[serde(default)]
+    pub quick_bookmark_template: Option<String>,
+}
+
+impl SettingsBundle {
+    /// Collect the shareable settings of `config`
+    #[must_use]
+    pub fn from_config(config: &GlobalConfig) -> Self {
+        Self {
+            version: SETTINGS_BUNDLE_VERSION,
+            shortcuts: config.shortcuts.clone(),
+            favorite_filters: config.favorite_filters.clone(),
+            bright_mode: Some(config.bright_mode),
+            timestamp_format: Some(config.timestamp_format),
+            file_config: Some(config.file_config.clone()),
+            column_layouts: config.column_layouts.clone(),
+            redaction_rules: config.redaction_rules.clone(),
+            quick_bookmark_template: Some(config.quick_bookmark_template.clone()),
+        }
+    }
+
+    /// Apply this bundle on top of `config`.
+    ///
+    /// Shortcuts and column layouts override the ones they name, favorites and
+    /// redaction rules are added unless already present; the remaining
+    /// settings are replaced when the bundle carries them.
+    pub fn apply_to(&self, config: &mut GlobalConfig) {
+        config.shortcuts.extend(
+            self.shortcuts
+                .iter()
+                .map(|(action, key)| (*action, key.clone())),
+        );
+        for favorite in &self.favorite_filters {
+            let known = config.favorite_filters.iter().any(|f| {
+                f.search_text == favorite.search_text && f.case_sensitive == favorite.case_sensitive
+            });
+            if !known {
+                config.favorite_filters.push(favorite.clone());
+            }
+        }
+        if let Some(bright_mode) = self.bright_mode {
+            config.bright_mode = bright_mode;
+        }
+        if let Some(timestamp_format) = self.timestamp_format {
+            config.timestamp_format = timestamp_format;
+        }
+        if let Some(file_config) = &self.file_config {
+            config.file_config = file_config.clone();
+        }
+        config.column_layouts.extend(
+            self.column_layouts
+                .iter()
+                .map(|(format, layout)| (format.clone(), layout.clone())),
+        );
+        for rule in &self.redaction_rules {
+            if !config.redaction_rules.contains(rule) {
+                config.redaction_rules.push(rule.clone());
+            }
+        }
+        if let Some(template) = &self.quick_bookmark_template {
+            config.quick_bookmark_template.clone_from(template);
+        }
+    }
+
+    /// Write the bundle to `path` as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {e}"))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write settings file: {e}"))
+    }
+
+    /// Read a bundle from `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the file cannot be read or parsed, or was
+    /// written in a newer bundle format.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read settings file: {e}"))?;
+        let bundle: Self = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse settings file: {e}"))?;
+        if bundle.version > SETTINGS_BUNDLE_VERSION {
+            return Err(format!(
+                "Settings file version {} is newer than this build supports ({SETTINGS_BUNDLE_VERSION})",
+                bundle.version
+            ));
+        }
+        Ok(bundle)
+    }
+}
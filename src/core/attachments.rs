@@ -0,0 +1,172 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Files attached to bookmarks, such as screenshots or notes.
+//!
+//! Attachments are copied into a folder next to the source's `.crab` file
+//! (`app.log.crab` → `app.log.crab-attachments/`), so they travel with the
+//! session. Bookmarks only store the file names within that folder.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Largest file accepted as an attachment
+pub const MAX_ATTACHMENT_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Folder holding the attachments of the session stored in `crab_path`
+#[must_use]
+pub fn attachments_dir(crab_path: &Path) -> PathBuf {
+    let mut name = crab_path.file_name().unwrap_or_default().to_os_string();
+    name.push("-attachments");
+    crab_path.with_file_name(name)
+}
+
+/// Copy `source` into `dir` and return the file name it was stored under.
+///
+/// # Errors
+///
+/// Returns an error if the file is larger than [`MAX_ATTACHMENT_BYTES`] or
+/// cannot be copied.
+pub fn attach_file(dir: &Path, source: &Path) -> io::Result<String> {
+    let len = fs::metadata(source)?.len();
+    if len > MAX_ATTACHMENT_BYTES {
+        return Err(too_large(len));
+    }
+    let name = source.file_name().map_or_else(
+        || "attachment".to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    fs::create_dir_all(dir)?;
+    let name = free_name(dir, &name);
+    fs::copy(source, dir.join(&name))?;
+    Ok(name)
+}
+
+/// Write `bytes` into `dir` as a file named like `name` and return the file
+/// name it was stored under.
+///
+/// # Errors
+///
+/// Returns an error if `bytes` is larger than [`MAX_ATTACHMENT_BYTES`] or
+/// cannot be written.
+pub fn attach_bytes(dir: &Path, name: &str, bytes: &[u8]) -> io::Result<String> {
+    let len = bytes.len() as u64;
+    if len > MAX_ATTACHMENT_BYTES {
+        return Err(too_large(len));
+    }
+    fs::create_dir_all(dir)?;
+    let name = free_name(dir, name);
+    fs::write(dir.join(&name), bytes)?;
+    Ok(name)
+}
+
+/// Delete the attachment `name` from `dir`, and `dir` itself once it is empty.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be deleted.
+pub fn remove_attachment(dir: &Path, name: &str) -> io::Result<()> {
+    match fs::remove_file(dir.join(name)) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    // Fails while other attachments are left, which is fine
+    let _ = fs::remove_dir(dir);
+    Ok(())
+}
+
+/// Whether the attachment can be previewed as an image
+#[must_use]
+pub fn is_image(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+}
+
+/// `name`, or `name (2)`, `name (3)`, ... before the extension if taken
+fn free_name(dir: &Path, name: &str) -> String {
+    if !dir.join(name).exists() {
+        return name.to_string();
+    }
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map_or_else(|| name.to_string(), |s| s.to_string_lossy().into_owned());
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (2..=usize::MAX)
+        .map(|n| format!("{stem} ({n}){extension}"))
+        .find(|candidate| !dir.join(candidate).exists())
+        .expect("a directory cannot hold usize::MAX files")
+}
+
+fn too_large(len: u64) -> io::Error {
+    io::Error::other(format!(
+        "{} MiB is too large for an attachment (at most {} MiB)",
+        len / (1024 * 1024),
+        MAX_ATTACHMENT_BYTES / (1024 * 1024)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachments_get_free_names_and_the_folder_is_cleaned_up() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+        let attachments = attachments_dir(&crab_path);
+        assert_eq!(attachments, dir.path().join("app.log.crab-attachments"));
+
+        let shot = dir.path().join("scope.png");
+        std::fs::write(&shot, b"png").expect("write");
+        assert_eq!(
+            attach_file(&attachments, &shot).expect("attach"),
+            "scope.png"
+        );
+        assert_eq!(
+            attach_file(&attachments, &shot).expect("attach"),
+            "scope (2).png"
+        );
+        assert_eq!(
+            attach_bytes(&attachments, "notes", b"text").expect("attach"),
+            "notes"
+        );
+        assert_eq!(
+            std::fs::read(attachments.join("scope (2).png")).expect("read"),
+            b"png"
+        );
+
+        for name in ["scope.png", "scope (2).png", "notes"] {
+            remove_attachment(&attachments, name).expect("remove");
+        }
+        assert!(!attachments.exists());
+    }
+
+    #[test]
+    fn only_png_files_are_previewed() {
+        assert!(is_image("Scope.PNG"));
+        assert!(!is_image("notes.txt"));
+        assert!(!is_image("png"));
+    }
+}
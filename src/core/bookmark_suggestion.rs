@@ -0,0 +1,158 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Bookmark suggestions at the most anomalous stretches of a session.
+//!
+//! High-scoring lines that follow each other within a gap are grouped into
+//! one cluster; the strongest clusters are offered as starting points for
+//! manual triage.
+
+use crate::core::log_store::StoreID;
+use crate::core::LogStore;
+
+/// Lines scoring below this never start or join a cluster
+pub const SUGGESTION_MIN_SCORE: f64 = 70.0;
+
+/// Run of high-scoring lines with no gap longer than the grouping window
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnomalyCluster {
+    /// Highest-scoring line of the cluster, where the bookmark goes
+    pub peak: StoreID,
+    pub peak_score: f64,
+    /// Adjusted timestamp (ms) of the first and last line
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// Number of high-scoring lines in the cluster
+    pub lines: usize,
+}
+
+/// Group `points` (line, adjusted timestamp in ms, score) into clusters and
+/// return the `limit` strongest, in timestamp order.
+///
+/// Consecutive points at most `gap_ms` apart share a cluster. Clusters rank
+/// by peak score, then by size.
+#[must_use]
+pub fn cluster_anomalies(
+    mut points: Vec<(StoreID, i64, f64)>,
+    gap_ms: i64,
+    limit: usize,
+) -> Vec<AnomalyCluster> {
+    points.sort_by_key(|&(id, ts, _)| (ts, id));
+    let mut clusters: Vec<AnomalyCluster> = Vec::new();
+    for (id, ts, score) in points {
+        match clusters.last_mut() {
+            Some(cluster) if ts - cluster.end_ms <= gap_ms => {
+                cluster.end_ms = ts;
+                cluster.lines += 1;
+                if score > cluster.peak_score {
+                    cluster.peak = id;
+                    cluster.peak_score = score;
+                }
+            }
+            _ => clusters.push(AnomalyCluster {
+                peak: id,
+                peak_score: score,
+                start_ms: ts,
+                end_ms: ts,
+                lines: 1,
+            }),
+        }
+    }
+    clusters.sort_by(|a, b| {
+        b.peak_score
+            .total_cmp(&a.peak_score)
+            .then(b.lines.cmp(&a.lines))
+    });
+    clusters.truncate(limit);
+    clusters.sort_by_key(|cluster| cluster.start_ms);
+    clusters
+}
+
+/// The `limit` strongest anomaly clusters across all sources of `store`,
+/// by ML sidecar scores if `ml` is set.
+#[must_use]
+pub fn suggest_bookmarks(
+    store: &LogStore,
+    ml: bool,
+    gap_ms: i64,
+    limit: usize,
+) -> Vec<AnomalyCluster> {
+    let points = store
+        .get_source_filenames()
+        .into_iter()
+        .flat_map(|(source_id, _)| store.lines_scored_above(source_id, SUGGESTION_MIN_SCORE, ml))
+        .filter_map(|id| {
+            let ts = store.adjusted_timestamp_ms(&id)?;
+            let (source_id, line_index) = (id.source_id(), id.line_index_within_source());
+            let line_score = if ml {
+                store.get_sidecar_score(source_id, line_index)
+            } else {
+                store.get_score(source_id, line_index)
+            };
+            Some((id, ts, line_score))
+        })
+        .collect();
+    cluster_anomalies(points, gap_ms, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(line: usize, ts: i64, score: f64) -> (StoreID, i64, f64) {
+        (StoreID::make(1, line), ts, score)
+    }
+
+    #[test]
+    fn test_lines_within_the_gap_form_one_cluster() {
+        let clusters = cluster_anomalies(
+            vec![
+                point(0, 1_000, 80.0),
+                point(1, 3_000, 95.0),
+                point(2, 5_500, 75.0),
+                point(3, 20_000, 90.0),
+            ],
+            2_500,
+            10,
+        );
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].peak, StoreID::make(1, 1));
+        assert_eq!((clusters[0].start_ms, clusters[0].end_ms), (1_000, 5_500));
+        assert_eq!(clusters[0].lines, 3);
+        assert_eq!(clusters[1].peak, StoreID::make(1, 3));
+        assert_eq!(clusters[1].lines, 1);
+    }
+
+    #[test]
+    fn test_strongest_clusters_are_kept_in_time_order() {
+        let clusters = cluster_anomalies(
+            vec![
+                point(0, 0, 75.0),
+                point(1, 10_000, 99.0),
+                point(2, 20_000, 85.0),
+                point(3, 30_000, 85.0),
+                point(4, 30_500, 71.0),
+            ],
+            1_000,
+            2,
+        );
+        let peaks: Vec<_> = clusters.iter().map(|c| c.peak).collect();
+        // The tie at 85 goes to the larger cluster
+        assert_eq!(peaks, vec![StoreID::make(1, 1), StoreID::make(1, 3)]);
+    }
+}
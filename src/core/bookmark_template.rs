@@ -0,0 +1,102 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Placeholder templates for auto-generated bookmark names.
+//!
+//! A template is plain text with `{name}` or `{name:N}` placeholders. `name`
+//! is resolved by the caller (e.g. `message`, `level`, `tag` or any parsed
+//! field of the line) and `N` truncates the value to its first `N` characters.
+//! Unknown placeholders expand to nothing.
+
+/// Template used for quick bookmarks until the user configures their own.
+pub const DEFAULT_QUICK_BOOKMARK_TEMPLATE: &str = "{level} {tag}: {message:40}";
+
+/// Expand all placeholders in `template` using `lookup`.
+///
+/// Runs of whitespace are collapsed and separators left dangling by empty
+/// placeholders (e.g. the `:` in `{tag}: ...` for a line without a tag) are
+/// trimmed from both ends.
+pub fn expand_template(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        let Some(close) = after_open.find('}') else {
+            expanded.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let placeholder = &after_open[..close];
+        let (name, max_chars) = match placeholder.split_once(':') {
+            Some((name, len)) => (name.trim(), len.trim().parse::<usize>().ok()),
+            None => (placeholder.trim(), None),
+        };
+        if let Some(value) = lookup(name) {
+            let value = value.replace(['\n', '\r'], " ");
+            match max_chars {
+                Some(max) => expanded.extend(value.chars().take(max)),
+                None => expanded.push_str(&value),
+            }
+        }
+        rest = &after_open[close + 1..];
+    }
+    expanded.push_str(rest);
+
+    expanded
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | '|' | ','))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "level" => Some("E".to_string()),
+            "tag" => Some("Wifi".to_string()),
+            "message" => Some("connection lost\nretrying in 5s".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_default_template() {
+        assert_eq!(
+            expand_template("{level} {tag}: {message:15}", lookup),
+            "E Wifi: connection lost"
+        );
+        assert_eq!(
+            expand_template("{message}", lookup),
+            "connection lost retrying in 5s"
+        );
+    }
+
+    #[test]
+    fn test_missing_placeholders_are_trimmed() {
+        assert_eq!(
+            expand_template("{pid} {missing}: {message:10}", lookup),
+            "connection"
+        );
+        assert_eq!(expand_template("note {unclosed", lookup), "note {unclosed");
+    }
+}
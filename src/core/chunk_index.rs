@@ -0,0 +1,182 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-chunk trigram bloom filters that let literal searches skip storage
+//! chunks which cannot contain the searched text.
+//!
+//! Every chunk of a source (see [`crate::core::line_buffer::CHUNK_LINES`])
+//! gets a [`TrigramBloom`] over the ASCII-lowercased display message and raw
+//! text of its lines. A search whose pattern is a plain literal only scans
+//! the chunks whose bloom holds all of the literal's trigrams. Folding case
+//! makes one index serve case-sensitive and case-insensitive searches alike;
+//! false positives only cost a scan, false negatives cannot happen.
+
+/// Number of bits per chunk bloom (256 KiB)
+const BLOOM_BITS: usize = 1 << 21;
+
+/// Chunk pruning information derived from a search, see
+/// [`crate::core::LogStore::get_matching_ids_within`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanHints {
+    /// Only lines within this span can match (adjusted epoch milliseconds,
+    /// both ends inclusive)
+    pub time_range: Option<(i64, i64)>,
+    /// Every matching line contains this text, ignoring ASCII case, in its
+    /// display message or raw text
+    pub literal: Option<String>,
+}
+
+/// Bloom filter over the ASCII-lowercased byte trigrams of a chunk's lines.
+#[derive(Clone)]
+pub struct TrigramBloom {
+    bits: Box<[u64]>,
+}
+
+impl TrigramBloom {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            bits: vec![0; BLOOM_BITS / 64].into_boxed_slice(),
+        }
+    }
+
+    /// Record every trigram of `text`.
+    pub fn insert(&mut self, text: &str) {
+        // Case-insensitive regexes match these against ASCII `k` and `s`
+        let folded;
+        let text = if text.is_ascii() {
+            text
+        } else {
+            folded = text.replace('\u{212A}', "k").replace('\u{17F}', "s");
+            &folded
+        };
+        for trigram in trigrams(text) {
+            for bit in bit_positions(trigram) {
+                self.bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+    }
+
+    /// Add everything recorded in `other`.
+    pub fn union_with(&mut self, other: &Self) {
+        for (word, other) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *word |= other;
+        }
+    }
+
+    /// `false` when no line recorded here can contain `literal`.
+    ///
+    /// Literals shorter than a trigram are never ruled out.
+    #[must_use]
+    pub fn might_contain(&self, literal: &str) -> bool {
+        trigrams(literal).all(|trigram| {
+            bit_positions(trigram)
+                .into_iter()
+                .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+        })
+    }
+}
+
+impl Default for TrigramBloom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for TrigramBloom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let set: u32 = self.bits.iter().map(|word| word.count_ones()).sum();
+        write!(f, "TrigramBloom({set} bits set)")
+    }
+}
+
+/// ASCII-lowercased byte trigrams of `text`, packed into the low 24 bits.
+fn trigrams(text: &str) -> impl Iterator<Item = u32> + '_ {
+    text.as_bytes().windows(3).map(|w| {
+        u32::from(w[0].to_ascii_lowercase()) << 16
+            | u32::from(w[1].to_ascii_lowercase()) << 8
+            | u32::from(w[2].to_ascii_lowercase())
+    })
+}
+
+/// The two bloom bits of a trigram.
+fn bit_positions(trigram: u32) -> [usize; 2] {
+    let hash = u64::from(trigram).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    [
+        (hash >> 43) as usize % BLOOM_BITS,
+        (hash >> 21) as usize % BLOOM_BITS,
+    ]
+}
+
+/// The text every match of `pattern` contains, if `pattern` is a plain
+/// literal (escaped punctuation allowed) of at least three bytes.
+///
+/// Returns `None` for anything else, including non-ASCII literals, whose
+/// case folding the bloom does not model.
+#[must_use]
+pub fn required_literal(pattern: &str) -> Option<String> {
+    let mut literal = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_punctuation() => literal.push(escaped),
+                _ => return None,
+            },
+            '.' | '^' | '$' | '|' | '?' | '*' | '+' | '(' | ')' | '[' | ']' | '{' | '}' => {
+                return None;
+            }
+            c => literal.push(c),
+        }
+    }
+    (literal.len() >= 3 && literal.is_ascii()).then_some(literal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_finds_inserted_text_ignoring_case() {
+        let mut bloom = TrigramBloom::new();
+        bloom.insert("WifiStateMachine: CONNECTED to ap");
+        assert!(bloom.might_contain("statemachine"));
+        assert!(bloom.might_contain("Connected"));
+        assert!(bloom.might_contain("ap"));
+        assert!(!bloom.might_contain("bluetooth"));
+
+        let mut other = TrigramBloom::new();
+        other.insert("bluetooth on");
+        bloom.union_with(&other);
+        assert!(bloom.might_contain("bluetooth"));
+    }
+
+    #[test]
+    fn test_required_literal_accepts_only_plain_text() {
+        assert_eq!(required_literal("timeout").as_deref(), Some("timeout"));
+        assert_eq!(
+            required_literal(r"wlan0\.conf").as_deref(),
+            Some("wlan0.conf")
+        );
+        assert_eq!(required_literal("ab"), None);
+        assert_eq!(required_literal("foo|bar"), None);
+        assert_eq!(required_literal(r"\bfoo"), None);
+        assert_eq!(required_literal("err.r"), None);
+        assert_eq!(required_literal("größe"), None);
+    }
+}
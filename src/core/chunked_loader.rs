@@ -23,6 +23,7 @@
 //! exponentially to amortise `SourceData::append_lines` merge overhead, and
 //! report progress via a [`ProgressToastHandle`].
 
+use crate::core::eta::Eta;
 use crate::core::log_store::SourceData;
 use crate::filetype::InputFileType;
 use crate::ui::ProgressToastHandle;
@@ -52,7 +53,8 @@ pub struct ChunkedLoader {
 impl ChunkedLoader {
     /// Drive `input.read()` in adaptive chunks, appending to `data_source`.
     ///
-    /// - Reports progress using `input.bytes_consumed()` divided by `file_size`.
+    /// - Reports progress using `input.bytes_consumed()` divided by `file_size`,
+    ///   with a throughput-based estimate of the remaining time.
     /// - Stops early if `data_source.is_cancelled()` is set.
     /// - Returns `true` if at least one line was loaded.
     ///
@@ -74,6 +76,7 @@ impl ChunkedLoader {
         let mut current_chunk_size = self.initial_chunk_size;
         let mut chunk_count: usize = 0;
         let start = std::time::Instant::now();
+        let eta = Eta::start();
 
         loop {
             if data_source.is_cancelled() {
@@ -113,7 +116,12 @@ impl ChunkedLoader {
             };
             toast.update(
                 progress,
-                format!("Loading {}… ({} lines)", file_name, data_source.len()),
+                format!(
+                    "Loading {}… ({} lines) {}",
+                    file_name,
+                    data_source.len(),
+                    eta.progress_text(f64::from(progress))
+                ),
             );
         }
 
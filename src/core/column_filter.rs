@@ -0,0 +1,234 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Excel-style column filters for discrete columns (source, level, tag, protocol).
+//!
+//! Values are tracked as a per-column *hidden* set so that values first seen
+//! after the filter was configured (e.g. while a file is still loading) stay
+//! visible by default.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Name of the pseudo-column that filters by source file.
+///
+/// Its values are the full paths, so two files of the same name in different
+/// directories are told apart. Show them with [`source_label`].
+pub const SOURCE_COLUMN: &str = "Source";
+
+/// Discrete columns naming the component that logged a line (logcat tag,
+/// DLT application ID, trace process), in order of preference.
+pub const COMPONENT_FACETS: [&str; 3] = ["Tag", "APID", "Process"];
+
+/// Label of a `Source` column value: the file name of the path.
+#[must_use]
+pub fn source_label(value: &str) -> &str {
+    Path::new(value)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(value)
+}
+
+/// Set of hidden values per discrete column.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnFilter {
+    hidden: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ColumnFilter {
+    /// `true` when no value of any column is hidden.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hidden.values().all(BTreeSet::is_empty)
+    }
+
+    /// `true` when at least one value of `column` is hidden.
+    #[must_use]
+    pub fn is_active(&self, column: &str) -> bool {
+        self.hidden.get(column).is_some_and(|set| !set.is_empty())
+    }
+
+    #[must_use]
+    pub fn is_hidden(&self, column: &str, value: &str) -> bool {
+        self.hidden
+            .get(column)
+            .is_some_and(|set| set.contains(value))
+    }
+
+    pub fn set_hidden(&mut self, column: &str, value: &str, hidden: bool) {
+        if hidden {
+            self.hidden
+                .entry(column.to_string())
+                .or_default()
+                .insert(value.to_string());
+        } else if let Some(set) = self.hidden.get_mut(column) {
+            set.remove(value);
+        }
+    }
+
+    /// Hidden values of `column`, sorted.
+    pub fn hidden_values(&self, column: &str) -> impl Iterator<Item = &str> {
+        self.hidden
+            .get(column)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Make every value of `column` visible again.
+    pub fn show_all(&mut self, column: &str) {
+        self.hidden.remove(column);
+    }
+
+    /// Hide every value in `values` for `column`.
+    pub fn hide_all<'a>(&mut self, column: &str, values: impl IntoIterator<Item = &'a str>) {
+        self.hidden
+            .entry(column.to_string())
+            .or_default()
+            .extend(values.into_iter().map(str::to_string));
+    }
+
+    /// Replace hidden `Source` values that are bare file names, as saved
+    /// before the column was keyed on full paths, with every path in `paths`
+    /// of that name.
+    ///
+    /// Names without an open source of that name are kept, so sources added
+    /// later are still matched. Returns `true` if anything was replaced.
+    pub fn resolve_source_names(&mut self, paths: &[String]) -> bool {
+        let Some(set) = self.hidden.get_mut(SOURCE_COLUMN) else {
+            return false;
+        };
+        let names: Vec<String> = set
+            .iter()
+            .filter(|value| {
+                Path::new(value.as_str())
+                    .parent()
+                    .is_some_and(|parent| parent.as_os_str().is_empty())
+                    && !paths.contains(value)
+            })
+            .cloned()
+            .collect();
+        let mut changed = false;
+        for name in names {
+            let mut matching = paths
+                .iter()
+                .filter(|path| source_label(path) == name)
+                .peekable();
+            if matching.peek().is_some() {
+                set.remove(&name);
+                set.extend(matching.cloned());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Check a line's source path and discrete values against the filter.
+    ///
+    /// A line without a value for a filtered column (e.g. a dmesg entry inside a
+    /// bugreport has no logcat tag) is kept.
+    #[must_use]
+    pub fn accepts(&self, source_path: &str, facets: &[(&str, String)]) -> bool {
+        !self.is_hidden(SOURCE_COLUMN, source_path)
+            && facets
+                .iter()
+                .all(|(column, value)| !self.is_hidden(column, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_filter_accepts_everything() {
+        let filter = ColumnFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.accepts("a.log", &[("Level", "E".to_string())]));
+    }
+
+    #[test]
+    fn test_hidden_values_are_rejected() {
+        let mut filter = ColumnFilter::default();
+        filter.set_hidden("Level", "D", true);
+        filter.set_hidden(SOURCE_COLUMN, "b.log", true);
+        assert!(filter.is_active("Level"));
+        assert!(!filter.accepts("a.log", &[("Level", "D".to_string())]));
+        assert!(filter.accepts("a.log", &[("Level", "E".to_string())]));
+        assert!(!filter.accepts("b.log", &[("Level", "E".to_string())]));
+        // Lines without the column are kept
+        assert!(filter.accepts("a.log", &[]));
+    }
+
+    #[test]
+    fn test_show_all_clears_column() {
+        let mut filter = ColumnFilter::default();
+        filter.hide_all("Tag", ["A", "B"]);
+        assert!(filter.is_hidden("Tag", "B"));
+        filter.set_hidden("Tag", "B", false);
+        assert!(!filter.is_hidden("Tag", "B"));
+        filter.show_all("Tag");
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn test_hidden_values_lists_one_column() {
+        let mut filter = ColumnFilter::default();
+        filter.hide_all(SOURCE_COLUMN, ["kernel.log", "app.log"]);
+        filter.set_hidden("Tag", "A", true);
+        assert_eq!(
+            filter.hidden_values(SOURCE_COLUMN).collect::<Vec<_>>(),
+            ["app.log", "kernel.log"]
+        );
+        assert_eq!(filter.hidden_values("Level").count(), 0);
+    }
+
+    #[test]
+    fn test_sources_of_the_same_name_are_filtered_apart() {
+        let mut filter = ColumnFilter::default();
+        filter.set_hidden(SOURCE_COLUMN, "/logs/a/main.log", true);
+        assert!(!filter.accepts("/logs/a/main.log", &[]));
+        assert!(filter.accepts("/logs/b/main.log", &[]));
+        assert_eq!(source_label("/logs/b/main.log"), "main.log");
+    }
+
+    #[test]
+    fn test_file_names_of_old_sessions_resolve_to_open_paths() {
+        let mut filter = ColumnFilter::default();
+        filter.hide_all(SOURCE_COLUMN, ["main.log", "radio.log", "/logs/c/kept.log"]);
+        let paths = [
+            "/logs/a/main.log".to_string(),
+            "/logs/b/main.log".to_string(),
+            "/logs/c/kept.log".to_string(),
+        ];
+        assert!(filter.resolve_source_names(&paths));
+        assert_eq!(
+            filter.hidden_values(SOURCE_COLUMN).collect::<Vec<_>>(),
+            [
+                "/logs/a/main.log",
+                "/logs/b/main.log",
+                "/logs/c/kept.log",
+                "radio.log"
+            ]
+        );
+        // Resolved once; a source opened later under the old name still matches
+        assert!(!filter.resolve_source_names(&paths));
+        assert!(filter.resolve_source_names(&["/logs/d/radio.log".to_string()]));
+        assert!(!filter.accepts("/logs/d/radio.log", &[]));
+    }
+}
@@ -0,0 +1,385 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Splitting of multi-container log output into one file per container.
+//!
+//! `docker compose logs` prefixes every line with `container | `, and
+//! `kubectl logs --prefix` with `[pod/NAME/CONTAINER] `. Such files are split
+//! into `<container>.log` files in a directory of the user's cache (see
+//! [`split_dir`]), with the prefix stripped, so each container can be loaded
+//! as its own source. The original file stays untouched and is the one that
+//! is watched and reloaded; every reload splits it again.
+
+use crate::core::decompress::LogReader;
+use crate::core::line_anchor::bytes_hash;
+use fancy_regex::Regex;
+use indexmap::IndexMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Number of leading non-empty lines inspected when detecting a prefix style.
+const SAMPLE_LINES: usize = 200;
+
+/// Bytes read from the start of a file to find those lines, as much as format
+/// detection samples.
+const SAMPLE_BYTES: u64 = 100 * 1024;
+
+/// Name given to lines that appear before the first prefixed line.
+const UNPREFIXED: &str = "unprefixed";
+
+/// `web-1  | message`, as printed by `docker compose logs`.
+///
+/// The replica suffix (`-1`, or `_1` for compose v1) is required so that
+/// `LEVEL | message` style logs are not mistaken for container output.
+static COMPOSE_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z0-9][A-Za-z0-9_.-]*[-_]\d+)\s+\| ?").expect("valid regex literal")
+});
+
+/// `[pod/web-5d8f/nginx] message`, as printed by `kubectl logs --prefix`.
+static KUBECTL_PREFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[pod/([^/\]]+)/([^\]]+)\] ").expect("valid regex literal"));
+
+/// Colour escapes that `docker compose` wraps around its prefix on a terminal.
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").expect("valid regex literal"));
+
+/// Which tool wrote the container prefixes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerPrefix {
+    Compose,
+    Kubectl,
+}
+
+impl ContainerPrefix {
+    /// Detect the prefix style used by at least 90% of `lines`.
+    #[must_use]
+    pub fn detect<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut total = 0usize;
+        let mut compose = 0usize;
+        let mut kubectl = 0usize;
+        for line in lines.into_iter().filter(|l| !l.trim().is_empty()) {
+            total += 1;
+            if Self::Kubectl.split(line).is_some() {
+                kubectl += 1;
+            } else if Self::Compose.split(line).is_some() {
+                compose += 1;
+            }
+        }
+        if total == 0 {
+            return None;
+        }
+        let threshold = total * 9 / 10;
+        if kubectl > 0 && kubectl >= threshold {
+            Some(Self::Kubectl)
+        } else if compose > 0 && compose >= threshold {
+            Some(Self::Compose)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the prefix style from `head`, the start of a file's content.
+    #[must_use]
+    pub fn detect_in(head: &[u8]) -> Option<Self> {
+        let head = String::from_utf8_lossy(head);
+        Self::detect(
+            head.lines()
+                .filter(|l| !l.trim().is_empty())
+                .take(SAMPLE_LINES),
+        )
+    }
+
+    /// The lines of the first container in `head`, unprefixed, as its file
+    /// from [`split_container_log`] would start.
+    ///
+    /// Lets the format of a container log be detected without splitting it.
+    #[must_use]
+    pub fn first_container_head(self, head: &[u8]) -> String {
+        let head = String::from_utf8_lossy(head);
+        let mut first = None;
+        let mut current = UNPREFIXED.to_string();
+        let mut out = String::new();
+        for line in head.lines() {
+            let text = match self.split(line) {
+                Some((name, rest)) => {
+                    current = name;
+                    rest
+                }
+                None => line.to_string(),
+            };
+            if first.get_or_insert_with(|| current.clone()) == &current {
+                out.push_str(&text);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Split a line into its container name and the unprefixed remainder.
+    #[must_use]
+    pub fn split(self, line: &str) -> Option<(String, String)> {
+        let line = ANSI_ESCAPE.replace_all(line, "");
+        let regex = match self {
+            Self::Compose => &*COMPOSE_PREFIX,
+            Self::Kubectl => &*KUBECTL_PREFIX,
+        };
+        let caps = regex.captures(&line).ok()??;
+        let name = match self {
+            Self::Compose => caps[1].to_string(),
+            Self::Kubectl => format!("{}_{}", &caps[1], &caps[2]),
+        };
+        let rest = line[caps[0].len()..].to_string();
+        Some((name, rest))
+    }
+}
+
+/// Replace characters that are unsafe in file names.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Detect whether `path` holds multi-container output, from the start of its
+/// content. Cheap enough to run before deciding how to load a file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or its start cannot be read.
+pub fn detect_container_log(path: &Path) -> anyhow::Result<Option<ContainerPrefix>> {
+    let sample = crate::core::decompress::read_head(path, SAMPLE_BYTES)?;
+    Ok(ContainerPrefix::detect_in(&sample))
+}
+
+/// Directory in the user's cache the per-container files of `path` go to.
+///
+/// Named after the file and a hash of its full path, so each log has its own
+/// directory and splitting it again reuses it, along with the `.crab` files
+/// of its containers. The hash is [`bytes_hash`], which stays the same
+/// across builds.
+#[must_use]
+pub fn split_dir(path: &Path) -> PathBuf {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let hash = bytes_hash(absolute.as_os_str().as_encoded_bytes());
+    let name = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("logcrab")
+        .join("containers")
+        .join(format!("{}-{hash:016x}", sanitize_file_name(&name)))
+}
+
+/// Split `path` into one file per container in `dir`, stripping the prefixes
+/// written in `prefix` style.
+///
+/// Returns the written files in order of first appearance. Lines without a
+/// prefix (e.g. wrapped continuations) stay with the preceding container.
+/// Compressed files are decompressed while reading; the split files are
+/// plain text. Container files left in `dir` by an earlier split are
+/// replaced, or removed if the container is gone.
+///
+/// # Errors
+///
+/// Returns the I/O error if `path` cannot be read or `dir` cannot be written.
+pub fn split_container_log(
+    path: &Path,
+    prefix: ContainerPrefix,
+    dir: &Path,
+) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    // Keep the sessions of the containers, only the logs are rewritten
+    for entry in std::fs::read_dir(dir)? {
+        let stale = entry?.path();
+        if stale.extension().is_some_and(|ext| ext == "log") {
+            std::fs::remove_file(&stale)?;
+        }
+    }
+    tracing::info!(
+        "Splitting {} into per-container files in {}",
+        path.display(),
+        dir.display()
+    );
+
+    let mut writers: IndexMap<String, (PathBuf, BufWriter<File>)> = IndexMap::new();
+    let mut current = UNPREFIXED.to_string();
+    let mut buf = Vec::new();
+    let mut reader = LogReader::open(path)
+        .map(BufReader::new)
+        .map_err(std::io::Error::other)?;
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches(['\n', '\r']);
+        let text = match prefix.split(line) {
+            Some((name, rest)) => {
+                current = name;
+                rest
+            }
+            None => line.to_string(),
+        };
+        if !writers.contains_key(&current) {
+            let file_path = dir.join(format!("{}.log", sanitize_file_name(&current)));
+            let writer = BufWriter::new(File::create(&file_path)?);
+            writers.insert(current.clone(), (file_path, writer));
+        }
+        let (_, writer) = writers.get_mut(&current).expect("writer inserted above");
+        writeln!(writer, "{text}")?;
+    }
+
+    let mut paths = Vec::with_capacity(writers.len());
+    for (_, (file_path, mut writer)) in writers {
+        writer.flush()?;
+        paths.push(file_path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_split_prefixes() {
+        let compose = ["web-1  | GET / 200", "\x1b[36mdb-1   |\x1b[0m ready"];
+        assert_eq!(
+            ContainerPrefix::detect(compose),
+            Some(ContainerPrefix::Compose)
+        );
+        assert_eq!(
+            ContainerPrefix::Compose.split(compose[1]),
+            Some(("db-1".to_string(), "ready".to_string()))
+        );
+
+        let kubectl = "[pod/api-7f9c/server] 2024-03-01T10:00:00.123456789Z started";
+        assert_eq!(
+            ContainerPrefix::detect([kubectl]),
+            Some(ContainerPrefix::Kubectl)
+        );
+        assert_eq!(
+            ContainerPrefix::Kubectl.split(kubectl),
+            Some((
+                "api-7f9c_server".to_string(),
+                "2024-03-01T10:00:00.123456789Z started".to_string()
+            ))
+        );
+
+        assert_eq!(
+            ContainerPrefix::detect(["2024-03-01 10:00:00 plain log line"]),
+            None
+        );
+        assert_eq!(ContainerPrefix::detect(["INFO  | service started"]), None);
+    }
+
+    #[test]
+    fn test_split_container_log() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("compose.log");
+        std::fs::write(
+            &path,
+            "web-1  | one\ndb-1   | two\n  continued\nweb-1  | three\n",
+        )
+        .expect("write");
+        let split = dir.path().join("split");
+        std::fs::create_dir(&split).expect("mkdir");
+        std::fs::write(split.join("gone-1.log"), "old\n").expect("write");
+        std::fs::write(split.join("web-1.log.crab"), "{}").expect("write");
+
+        let prefix = detect_container_log(&path)
+            .expect("detect")
+            .expect("multi-container");
+        let parts = split_container_log(&path, prefix, &split).expect("split");
+        let names: Vec<_> = parts
+            .iter()
+            .map(|p| p.file_name().expect("name").to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["web-1.log", "db-1.log"]);
+        assert_eq!(
+            std::fs::read_to_string(&parts[0]).expect("read"),
+            "one\nthree\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&parts[1]).expect("read"),
+            "two\n  continued\n"
+        );
+        assert!(!split.join("gone-1.log").exists());
+        assert!(split.join("web-1.log.crab").exists());
+        assert_eq!(
+            std::fs::read_dir(dir.path()).expect("read dir").count(),
+            2,
+            "nothing written next to the log"
+        );
+    }
+
+    #[test]
+    fn test_first_container_head() {
+        let head = b"web-1  | one\ndb-1   | two\n  continued\nweb-1  | three\n";
+        let prefix = ContainerPrefix::detect_in(head).expect("multi-container");
+        assert_eq!(prefix.first_container_head(head), "one\nthree\n");
+    }
+
+    #[test]
+    fn test_split_compressed_container_log() {
+        use std::io::Write as _;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("compose.log.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            File::create(&path).expect("create"),
+            flate2::Compression::default(),
+        );
+        encoder
+            .write_all(b"web-1  | one\ndb-1   | two\n")
+            .expect("write");
+        encoder.finish().expect("finish");
+
+        let prefix = detect_container_log(&path)
+            .expect("detect")
+            .expect("multi-container");
+        let parts = split_container_log(&path, prefix, &dir.path().join("split")).expect("split");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(std::fs::read_to_string(&parts[0]).expect("read"), "one\n");
+    }
+
+    #[test]
+    fn test_split_dir_is_per_log() {
+        assert_ne!(
+            split_dir(Path::new("/var/log/a/compose.log")),
+            split_dir(Path::new("/var/log/b/compose.log"))
+        );
+        // The name must not change between builds, or the containers' sessions are lost
+        #[cfg(unix)]
+        assert_eq!(
+            split_dir(Path::new("/var/log/a/compose.log")).file_name(),
+            Some(std::ffi::OsStr::new("compose.log-ba561ef5e439bbbd"))
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Detection of request/response correlation IDs in log messages.
+//!
+//! Services commonly tag the lines belonging to one request with a UUID or a
+//! long hex token. Such IDs typically show up in exactly two lines (request and
+//! response) close to each other, so following them is a quick way to connect
+//! the two ends without writing a regex by hand.
+
+/// Shortest plain hex token treated as an ID; shorter ones are mostly counters
+const MIN_HEX_LEN: usize = 8;
+
+/// How far (in ms) the two lines of a request/response pair may be apart
+pub const PAIR_WINDOW_MS: i64 = 60_000;
+
+/// Correlation ID candidates in `message`, in order of appearance, deduplicated.
+///
+/// Recognized are UUIDs (`8-4-4-4-12` hex digits) and hex tokens of at least
+/// [`MIN_HEX_LEN`] characters that mix digits and letters, so plain numbers
+/// and words like `deadbeef` are not mistaken for IDs.
+#[must_use]
+pub fn correlation_ids(message: &str) -> Vec<&str> {
+    let mut ids: Vec<&str> = Vec::new();
+    let mut push = |id| {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    };
+    for token in message.split(|c: char| !c.is_ascii_alphanumeric() && c != '-') {
+        if is_uuid(token) {
+            push(token);
+        } else {
+            token
+                .split('-')
+                .filter(|part| is_hex_id(part))
+                .for_each(&mut push);
+        }
+    }
+    ids
+}
+
+fn is_uuid(token: &str) -> bool {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+    let mut groups = token.split('-');
+    GROUPS.iter().all(|&len| {
+        groups
+            .next()
+            .is_some_and(|group| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()))
+    }) && groups.next().is_none()
+}
+
+fn is_hex_id(token: &str) -> bool {
+    token.len() >= MIN_HEX_LEN
+        && token.bytes().all(|b| b.is_ascii_hexdigit())
+        && token.bytes().any(|b| b.is_ascii_digit())
+        && token.bytes().any(|b| b.is_ascii_alphabetic())
+}
+
+/// The occurrence paired with `of` among the `occurrences` of an ID within
+/// [`PAIR_WINDOW_MS`] of it.
+///
+/// Only an ID found in exactly two lines forms a pair; with more it is not a
+/// request/response ID, or the pair is ambiguous.
+#[must_use]
+pub fn paired_occurrence<T: PartialEq + Copy>(occurrences: &[T], of: T) -> Option<T> {
+    match *occurrences {
+        [first, second] if first == of => Some(second),
+        [first, second] if second == of => Some(first),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_uuids_and_hex_tokens() {
+        assert_eq!(
+            correlation_ids(
+                "req 123e4567-e89b-12d3-a456-426614174000 trace=4bf92f3577b34da6 id=req-9f8e7d6c5b"
+            ),
+            vec![
+                "123e4567-e89b-12d3-a456-426614174000",
+                "4bf92f3577b34da6",
+                "9f8e7d6c5b"
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_numbers_words_and_short_tokens() {
+        assert!(correlation_ids("took 1234567890 ms, status deadbeef, id a1b2c3").is_empty());
+    }
+
+    #[test]
+    fn deduplicates_ids() {
+        assert_eq!(
+            correlation_ids("ab12cd34ef retry ab12cd34ef"),
+            vec!["ab12cd34ef"]
+        );
+    }
+
+    #[test]
+    fn pairs_only_ids_found_exactly_twice() {
+        assert_eq!(paired_occurrence(&[3, 7], 3), Some(7));
+        assert_eq!(paired_occurrence(&[3, 7], 7), Some(3));
+        assert_eq!(paired_occurrence(&[1, 4, 9], 4), None);
+        assert_eq!(paired_occurrence(&[5], 5), None);
+        assert_eq!(paired_occurrence(&[1, 2], 8), None);
+    }
+}
@@ -0,0 +1,205 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Coordination between instances that open the same log.
+//!
+//! Only the instance holding the OS lock on a `.crab` file writes it; any other
+//! instance opens the log read-only. The lock holder records itself in the
+//! advisory lock file `<name>.crab.lock`, so the others can tell the user which
+//! process has the session.
+//!
+//! A read-only source remembers a hash of the session it read. Once the lock
+//! is released it takes the file over, and comparing the hash tells whether
+//! the other instance changed the session in the meantime (see [`Takeover`]).
+
+use crate::core::session::ImportedSession;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// The instance holding the lock on a `.crab` file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub pid: u32,
+    pub host: String,
+    /// When the instance took the lock
+    pub since: DateTime<Local>,
+}
+
+impl LockOwner {
+    /// This process, taking the lock now
+    #[must_use]
+    pub fn this_process() -> Self {
+        Self {
+            pid: std::process::id(),
+            host: host_name(),
+            since: Local::now(),
+        }
+    }
+
+    fn is_this_process(&self) -> bool {
+        self.pid == std::process::id() && self.host == host_name()
+    }
+}
+
+impl std::fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PID {} on {}, since {}",
+            self.pid,
+            self.host,
+            self.since.format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
+fn host_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown host".to_string())
+}
+
+/// Path of the lock file kept next to `crab_path`
+#[must_use]
+pub fn lock_path(crab_path: &Path) -> PathBuf {
+    let mut name = crab_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    crab_path.with_file_name(name)
+}
+
+/// The instance recorded as holding the lock on `crab_path`, if any
+#[must_use]
+pub fn read_owner(crab_path: &Path) -> Option<LockOwner> {
+    let content = std::fs::read_to_string(lock_path(crab_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// This process's entry in the lock file of a `.crab` file.
+///
+/// The file is removed again when the record is dropped, unless another
+/// instance has recorded itself since.
+#[derive(Debug)]
+pub struct OwnerRecord {
+    crab_path: PathBuf,
+}
+
+impl OwnerRecord {
+    /// Record this process as the lock holder of `crab_path`.
+    ///
+    /// Call only while holding the OS lock. Failing to write the record is
+    /// logged and otherwise ignored, as it only serves to inform other
+    /// instances.
+    #[must_use]
+    pub fn write(crab_path: &Path) -> Option<Self> {
+        let json = serde_json::to_string(&LockOwner::this_process()).ok()?;
+        match std::fs::write(lock_path(crab_path), json) {
+            Ok(()) => Some(Self {
+                crab_path: crab_path.to_path_buf(),
+            }),
+            Err(e) => {
+                tracing::debug!("Cannot write lock file for {}: {e}", crab_path.display());
+                None
+            }
+        }
+    }
+}
+
+impl Drop for OwnerRecord {
+    fn drop(&mut self) {
+        if read_owner(&self.crab_path).is_some_and(|owner| owner.is_this_process()) {
+            let _ = std::fs::remove_file(lock_path(&self.crab_path));
+        }
+    }
+}
+
+/// Hash of a `.crab` file's contents, to notice changes by other instances
+#[must_use]
+pub fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A source opened read-only that now holds the lock on its `.crab` file
+#[derive(Debug)]
+pub struct Takeover {
+    pub source_id: u64,
+    pub crab_path: PathBuf,
+    /// The session as the other instance left it, if it differs from what
+    /// this source read when it was opened
+    pub changed: Option<ImportedSession>,
+    /// Changes were made here while the file was locked, so they are not
+    /// saved yet
+    pub unsaved: bool,
+}
+
+impl Takeover {
+    /// Both sides changed the session, so the user has to pick which to keep
+    #[must_use]
+    pub const fn is_conflict(&self) -> bool {
+        self.changed.is_some() && self.unsaved
+    }
+
+    /// File name of the log the session belongs to
+    #[must_use]
+    pub fn log_name(&self) -> String {
+        self.crab_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .trim_end_matches(".crab")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_record_is_removed_on_drop() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let record = OwnerRecord::write(&crab_path).expect("write lock file");
+        let owner = read_owner(&crab_path).expect("read lock file");
+        assert_eq!(owner.pid, std::process::id());
+        drop(record);
+        assert!(!lock_path(&crab_path).exists());
+
+        // A record another instance wrote in the meantime is left alone
+        let record = OwnerRecord::write(&crab_path).expect("write lock file");
+        let other = LockOwner {
+            pid: owner.pid.wrapping_add(1),
+            ..owner
+        };
+        std::fs::write(
+            lock_path(&crab_path),
+            serde_json::to_string(&other).expect("serialize"),
+        )
+        .expect("overwrite lock file");
+        drop(record);
+        assert_eq!(read_owner(&crab_path), Some(other));
+    }
+}
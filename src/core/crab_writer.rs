@@ -0,0 +1,579 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Background writer for `.crab` session files.
+//!
+//! Saving a session snapshots it on the calling thread; serialization and file
+//! I/O happen on the writer thread. Each file is replaced atomically — the JSON
+//! is written to `<name>.crab.tmp`, synced, and renamed over the `.crab` file —
+//! so a crash mid-save never leaves a truncated session behind.
+//!
+//! Bursts of saves are debounced: a file is written once no newer save for it
+//! has arrived for [`DEBOUNCE`], and at the latest [`MAX_DELAY`] after the
+//! first pending one. Only the newest snapshot per file is written.
+//!
+//! So that the debounce window cannot lose changes either, the newest
+//! snapshot is appended to `<name>.crab.journal` and synced well before the
+//! `.crab` file is written: once no newer save has arrived for
+//! [`JOURNAL_DEBOUNCE`], and at the latest [`JOURNAL_MAX_DELAY`] after the first
+//! save not journaled yet. Snapshots are serialized only when they are
+//! journaled or written, so a burst of edits to a session with many bookmarks
+//! costs one serialization, not one per edit. Each record is its byte length
+//! on one line followed by the JSON. The journal is removed once the `.crab`
+//! file holding the newest snapshot is durable; a journal found when a session
+//! is opened therefore holds changes newer than the `.crab` file (see
+//! [`read_journal`]).
+//!
+//! Records are whole snapshots, not individual changes: the newest complete
+//! record is the session, with no replay that could drift from the in-memory
+//! model. Readers of the journal (recovery on open, a second instance reading
+//! a locked session, taking over a released lock) all use that record.
+
+use crate::core::session::SessionError;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Quiet period after the last save of a file before it is written
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Longest a pending save is held back by a continuous stream of newer saves
+const MAX_DELAY: Duration = Duration::from_secs(3);
+
+/// Quiet period after the last save of a file before it is journaled
+const JOURNAL_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Longest a save goes unjournaled during a continuous stream of newer saves
+const JOURNAL_MAX_DELAY: Duration = Duration::from_millis(500);
+
+type Serializer = Box<dyn FnOnce() -> Result<String, SessionError> + Send>;
+
+/// A save of one `.crab` file as submitted
+struct CrabWrite {
+    crab_path: PathBuf,
+    /// The source's locked `.crab` handle; replaced by the new file after the rename
+    lock: Arc<Mutex<File>>,
+    serialize: Serializer,
+}
+
+/// The newest save of a file, serialized once it is journaled
+enum Snapshot {
+    Unserialized(Serializer),
+    Serialized(String),
+}
+
+/// A save waiting for its debounce to run out
+struct PendingWrite {
+    lock: Arc<Mutex<File>>,
+    snapshot: Snapshot,
+    /// First save since the file was last written
+    first: Instant,
+    /// Newest save
+    last: Instant,
+    /// First save not in the journal yet
+    unjournaled_since: Option<Instant>,
+}
+
+impl PendingWrite {
+    fn journal_due(&self) -> Option<Instant> {
+        self.unjournaled_since
+            .map(|since| (self.last + JOURNAL_DEBOUNCE).min(since + JOURNAL_MAX_DELAY))
+    }
+
+    fn write_due(&self) -> Instant {
+        (self.last + DEBOUNCE).min(self.first + MAX_DELAY)
+    }
+
+    /// The snapshot as JSON, serializing it if that has not happened yet
+    fn json(&mut self, crab_path: &Path) -> Option<&str> {
+        let snapshot = std::mem::replace(&mut self.snapshot, Snapshot::Serialized(String::new()));
+        self.snapshot = match snapshot {
+            Snapshot::Serialized(json) => Snapshot::Serialized(json),
+            Snapshot::Unserialized(serialize) => match serialize() {
+                Ok(json) => Snapshot::Serialized(json),
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to serialize .crab file {}: {e}",
+                        crab_path.display()
+                    );
+                    return None;
+                }
+            },
+        };
+        match &self.snapshot {
+            Snapshot::Serialized(json) => Some(json),
+            Snapshot::Unserialized(_) => None,
+        }
+    }
+}
+
+enum Request {
+    Write(CrabWrite),
+    /// Write everything pending now, then acknowledge
+    Flush(Sender<()>),
+}
+
+/// Owns the writer thread. Pending saves are written when it is dropped.
+pub struct CrabWriter {
+    request_tx: Option<Sender<Request>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl CrabWriter {
+    #[must_use]
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = channel();
+        let thread = thread::Builder::new()
+            .name("crab-writer".to_string())
+            .spawn(move || Self::worker_loop(&request_rx))
+            .expect("failed to spawn crab writer thread");
+        Self {
+            request_tx: Some(request_tx),
+            thread: Some(thread),
+        }
+    }
+
+    /// Queue a save of `crab_path`. `serialize` runs on the writer thread and
+    /// replaces any save of the same file that has not been written yet.
+    pub fn submit<F>(&self, crab_path: PathBuf, lock: Arc<Mutex<File>>, serialize: F)
+    where
+        F: FnOnce() -> Result<String, SessionError> + Send + 'static,
+    {
+        if let Some(tx) = &self.request_tx {
+            let _ = tx.send(Request::Write(CrabWrite {
+                crab_path,
+                lock,
+                serialize: Box::new(serialize),
+            }));
+        }
+    }
+
+    /// Block until every save submitted so far is on disk.
+    pub fn flush(&self) {
+        let Some(tx) = &self.request_tx else {
+            return;
+        };
+        let (ack_tx, ack_rx) = channel();
+        if tx.send(Request::Flush(ack_tx)).is_ok() {
+            let _ = ack_rx.recv();
+        }
+    }
+
+    fn worker_loop(request_rx: &Receiver<Request>) {
+        let mut pending: HashMap<PathBuf, PendingWrite> = HashMap::new();
+
+        loop {
+            let next_due = pending
+                .values()
+                .flat_map(|write| [write.journal_due(), Some(write.write_due())])
+                .flatten()
+                .min();
+            let request = match next_due {
+                None => request_rx.recv().ok(),
+                Some(due) => {
+                    match request_rx.recv_timeout(due.saturating_duration_since(Instant::now())) {
+                        Ok(request) => Some(request),
+                        Err(RecvTimeoutError::Timeout) => {
+                            Self::process_due(&mut pending);
+                            continue;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => None,
+                    }
+                }
+            };
+
+            match request {
+                Some(Request::Write(write)) => Self::queue(&mut pending, write),
+                Some(Request::Flush(ack)) => {
+                    Self::write_pending(&mut pending);
+                    let _ = ack.send(());
+                }
+                None => {
+                    // All senders gone: write what is left and exit
+                    Self::write_pending(&mut pending);
+                    return;
+                }
+            }
+            Self::process_due(&mut pending);
+        }
+    }
+
+    /// Make `write` the newest save of its file, replacing any older one
+    fn queue(pending: &mut HashMap<PathBuf, PendingWrite>, write: CrabWrite) {
+        let now = Instant::now();
+        let snapshot = Snapshot::Unserialized(write.serialize);
+        match pending.get_mut(&write.crab_path) {
+            Some(queued) => {
+                queued.lock = write.lock;
+                queued.snapshot = snapshot;
+                queued.last = now;
+                queued.unjournaled_since.get_or_insert(now);
+            }
+            None => {
+                pending.insert(
+                    write.crab_path,
+                    PendingWrite {
+                        lock: write.lock,
+                        snapshot,
+                        first: now,
+                        last: now,
+                        unjournaled_since: Some(now),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Write the saves whose debounce ran out and journal the ones due for it
+    fn process_due(pending: &mut HashMap<PathBuf, PendingWrite>) {
+        let now = Instant::now();
+        pending.retain(|crab_path, write| {
+            if write.write_due() <= now {
+                write_crab(crab_path, write);
+                return false;
+            }
+            if write.journal_due().is_some_and(|due| due <= now) {
+                return journal_write(crab_path, write);
+            }
+            true
+        });
+    }
+
+    fn write_pending(pending: &mut HashMap<PathBuf, PendingWrite>) {
+        for (crab_path, mut write) in pending.drain() {
+            write_crab(&crab_path, &mut write);
+        }
+    }
+}
+
+impl Default for CrabWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CrabWriter {
+    fn drop(&mut self) {
+        // Disconnect so the worker writes pending saves and exits
+        drop(self.request_tx.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Serialize a pending save and append it to the file's journal.
+///
+/// Returns `false` if serializing failed and the save is dropped.
+fn journal_write(crab_path: &Path, write: &mut PendingWrite) -> bool {
+    profiling::scope!("journal_write");
+    let lock = Arc::clone(&write.lock);
+    let Some(json) = write.json(crab_path) else {
+        return false;
+    };
+    // Held so the journal is only touched while the session is locked
+    let file = lock.lock().expect("crab mutex poisoned");
+    if let Err(e) = append_journal(crab_path, json) {
+        tracing::warn!("Failed to journal changes to {}: {e}", crab_path.display());
+    }
+    drop(file);
+    write.unjournaled_since = None;
+    true
+}
+
+fn write_crab(crab_path: &Path, write: &mut PendingWrite) {
+    profiling::scope!("write_crab");
+    let lock = Arc::clone(&write.lock);
+    let Some(json) = write.json(crab_path) else {
+        return;
+    };
+    let mut file = lock.lock().expect("crab mutex poisoned");
+    let durable = match replace_atomically(crab_path, json) {
+        Ok(new_file) => {
+            // Dropping the old handle releases the lock on the replaced inode
+            *file = new_file;
+            // The rename is only durable once the directory entry is
+            sync_parent_dir(crab_path)
+                .map_err(|e| {
+                    tracing::warn!(
+                        "Failed to sync the directory of {}, keeping its journal: {e}",
+                        crab_path.display()
+                    );
+                })
+                .is_ok()
+        }
+        Err(e) => {
+            // E.g. Windows refuses to rename over a file that is still open
+            tracing::debug!(
+                "Atomic replace of {} failed ({e}), rewriting in place",
+                crab_path.display()
+            );
+            rewrite_in_place(&mut file, json)
+                .map_err(|e| {
+                    tracing::error!("Failed to save .crab file {}: {e}", crab_path.display());
+                })
+                .is_ok()
+        }
+    };
+    if durable {
+        tracing::debug!("Saved .crab file {}", crab_path.display());
+        // Everything journaled so far is in the .crab file on disk now
+        compact_journal(crab_path);
+    }
+    drop(file);
+}
+
+/// Path of the journal kept next to `crab_path`
+#[must_use]
+pub fn journal_path(crab_path: &Path) -> PathBuf {
+    let mut name = crab_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".journal");
+    crab_path.with_file_name(name)
+}
+
+/// Append one snapshot to the journal of `crab_path` and sync it to disk.
+///
+/// # Errors
+///
+/// Returns the I/O error if the journal cannot be opened, written or synced.
+pub fn append_journal(crab_path: &Path, json: &str) -> std::io::Result<()> {
+    let mut journal = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(journal_path(crab_path))?;
+    let mut record = format!("{}\n", json.len()).into_bytes();
+    record.extend_from_slice(json.as_bytes());
+    record.push(b'\n');
+    journal.write_all(&record)?;
+    journal.sync_data()
+}
+
+/// Snapshots journaled for `crab_path` since its last successful save,
+/// oldest first. A record cut short by a crash is left out.
+#[must_use]
+pub fn read_journal(crab_path: &Path) -> Vec<String> {
+    let Ok(bytes) = std::fs::read(journal_path(crab_path)) else {
+        return Vec::new();
+    };
+    let mut records = Vec::new();
+    let mut rest = bytes.as_slice();
+    while let Some(newline) = rest.iter().position(|&b| b == b'\n') {
+        let Some(len) = std::str::from_utf8(&rest[..newline])
+            .ok()
+            .and_then(|header| header.parse::<usize>().ok())
+        else {
+            break;
+        };
+        let body = &rest[newline + 1..];
+        if body.len() <= len || body[len] != b'\n' {
+            break;
+        }
+        let Ok(json) = std::str::from_utf8(&body[..len]) else {
+            break;
+        };
+        records.push(json.to_string());
+        rest = &body[len + 1..];
+    }
+    records
+}
+
+/// Remove the journal of `crab_path` once the `.crab` file is up to date.
+fn compact_journal(crab_path: &Path) {
+    match std::fs::remove_file(journal_path(crab_path)) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!(
+            "Failed to remove the journal of {}: {e}",
+            crab_path.display()
+        ),
+    }
+}
+
+/// Write `json` to a temporary file next to `crab_path` and rename it over the
+/// `.crab` file. The temporary file is locked before the rename so the path is
+/// never left unlocked; its handle becomes the source's new lock.
+fn replace_atomically(crab_path: &Path, json: &str) -> std::io::Result<File> {
+    use fs2::FileExt;
+
+    let mut tmp_name = crab_path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = crab_path.with_file_name(tmp_name);
+
+    let mut tmp = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tmp_path)?;
+    let result = tmp
+        .try_lock_exclusive()
+        .and_then(|()| tmp.write_all(json.as_bytes()))
+        .and_then(|()| tmp.sync_all())
+        .and_then(|()| std::fs::rename(&tmp_path, crab_path));
+    match result {
+        Ok(()) => Ok(tmp),
+        Err(e) => {
+            drop(tmp);
+            let _ = std::fs::remove_file(&tmp_path);
+            Err(e)
+        }
+    }
+}
+
+fn rewrite_in_place(file: &mut File, json: &str) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(json.as_bytes())?;
+    file.sync_all()
+}
+
+/// Sync the directory holding `path`, making a rename into it durable.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()
+}
+
+/// Windows cannot open a directory as a file; `MoveFileEx` is left to the
+/// file system's own ordering there.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+const fn sync_parent_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs2::FileExt;
+
+    fn locked(path: &Path) -> Arc<Mutex<File>> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .expect("open crab file");
+        file.try_lock_exclusive().expect("lock crab file");
+        Arc::new(Mutex::new(file))
+    }
+
+    #[test]
+    fn test_burst_writes_latest_snapshot_and_keeps_lock() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+        let lock = locked(&crab_path);
+
+        let writer = CrabWriter::new();
+        for i in 0..5 {
+            writer.submit(crab_path.clone(), Arc::clone(&lock), move || {
+                Ok(format!("{{\"save\": {i}}}"))
+            });
+        }
+        writer.flush();
+
+        assert_eq!(
+            std::fs::read_to_string(&crab_path).expect("read crab file"),
+            "{\"save\": 4}"
+        );
+        assert!(!dir.path().join("app.log.crab.tmp").exists());
+        assert!(
+            !journal_path(&crab_path).exists(),
+            "compacted after the save"
+        );
+
+        // The replaced file is still locked against other instances
+        let other = File::open(&crab_path).expect("reopen crab file");
+        assert!(other.try_lock_exclusive().is_err());
+    }
+
+    #[test]
+    fn test_drop_writes_pending_saves() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+        let lock = locked(&crab_path);
+
+        let writer = CrabWriter::new();
+        writer.submit(crab_path.clone(), lock, || Ok("{}".to_string()));
+        drop(writer);
+
+        assert_eq!(
+            std::fs::read_to_string(&crab_path).expect("read crab file"),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_newest_snapshot_is_journaled_before_the_write() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+        let lock = locked(&crab_path);
+
+        let writer = CrabWriter::new();
+        for i in 0..3 {
+            writer.submit(crab_path.clone(), Arc::clone(&lock), move || {
+                Ok(format!("{{\"save\": {i}}}"))
+            });
+        }
+        let started = Instant::now();
+        while read_journal(&crab_path).is_empty() && started.elapsed() < DEBOUNCE {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(read_journal(&crab_path), vec!["{\"save\": 2}".to_string()]);
+        assert_eq!(
+            std::fs::read_to_string(&crab_path).expect("read crab file"),
+            "",
+            "still debounced"
+        );
+        writer.flush();
+        assert!(!journal_path(&crab_path).exists());
+    }
+
+    #[test]
+    fn test_journal_skips_a_torn_record() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+
+        append_journal(&crab_path, "{\"save\": 1}").expect("append");
+        append_journal(&crab_path, "{\n  \"save\": 2\n}").expect("append");
+        // A crash in the middle of the third record
+        let mut journal = OpenOptions::new()
+            .append(true)
+            .open(journal_path(&crab_path))
+            .expect("open journal");
+        journal.write_all(b"40\n{\"sa").expect("write");
+
+        assert_eq!(
+            read_journal(&crab_path),
+            vec![
+                "{\"save\": 1}".to_string(),
+                "{\n  \"save\": 2\n}".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Transparent decompression of compressed logs.
+//!
+//! Rotated logs are usually gzip, zstd or xz compressed. [`LogReader::open`]
+//! recognizes them by their magic bytes, not their extension, and decompresses
+//! while the parser reads, so every format reads them like plain files and
+//! nothing is extracted to disk.
+//!
+//! The decompressed size is not known up front, so progress is measured in
+//! compressed bytes read, which matches the file size on disk (see
+//! [`LogReader::disk_position`]).
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A compression format recognized by its magic bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    const MAGIC: [(Self, &'static [u8]); 3] = [
+        (Self::Gzip, &[0x1f, 0x8b]),
+        (Self::Zstd, &[0x28, 0xb5, 0x2f, 0xfd]),
+        (Self::Xz, &[0xfd, b'7', b'z', b'X', b'Z', 0x00]),
+    ];
+
+    /// The compression a file starting with `header` uses, if any
+    #[must_use]
+    pub fn detect(header: &[u8]) -> Option<Self> {
+        Self::MAGIC
+            .iter()
+            .find(|(_, magic)| header.starts_with(magic))
+            .map(|(compression, _)| *compression)
+    }
+
+    /// Name shown to the user
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+            Self::Xz => "xz",
+        }
+    }
+}
+
+/// Counts the bytes read through it into a shared counter
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// A log file opened for reading, decompressed on the fly if it is compressed
+pub struct LogReader {
+    inner: Box<dyn Read + Send>,
+    compression: Option<Compression>,
+    /// Bytes read from the file on disk so far, compressed or not
+    disk_read: Arc<AtomicU64>,
+}
+
+impl LogReader {
+    /// Open `path`, decompressing it if it starts with a known magic number.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or read, or if the
+    /// decompressor cannot be set up.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+        let mut file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut header = [0u8; 6];
+        let n = read_up_to(&mut file, &mut header)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let compression = Compression::detect(&header[..n]);
+        // Start over, so the first read is not cut short at the sniffed header;
+        // only streams that cannot seek get the header replayed in front
+        let file: Box<dyn Read + Send> = if file.rewind().is_ok() {
+            Box::new(file)
+        } else {
+            Box::new(std::io::Cursor::new(header[..n].to_vec()).chain(file))
+        };
+
+        let disk_read = Arc::new(AtomicU64::new(0));
+        let counted = CountingReader {
+            inner: file,
+            count: Arc::clone(&disk_read),
+        };
+        let inner: Box<dyn Read + Send> = match compression {
+            None => Box::new(counted),
+            // Rotated logs are often several gzip members appended to each other
+            Some(Compression::Gzip) => {
+                Box::new(flate2::read::MultiGzDecoder::new(BufReader::new(counted)))
+            }
+            Some(Compression::Zstd) => Box::new(
+                zstd::stream::read::Decoder::new(counted)
+                    .with_context(|| format!("Failed to decompress {}", path.display()))?,
+            ),
+            Some(Compression::Xz) => Box::new(xz2::read::XzDecoder::new_multi_decoder(
+                BufReader::new(counted),
+            )),
+        };
+        if let Some(compression) = compression {
+            tracing::info!(
+                "Decompressing {} ({}) while reading",
+                path.display(),
+                compression.name()
+            );
+        }
+        Ok(Self {
+            inner,
+            compression,
+            disk_read,
+        })
+    }
+
+    /// Wrap an uncompressed stream, e.g. the output of a child process
+    #[must_use]
+    pub fn plain(inner: Box<dyn Read + Send>) -> Self {
+        Self {
+            inner,
+            compression: None,
+            disk_read: Arc::default(),
+        }
+    }
+
+    /// The compression of the file, `None` for plain files
+    #[must_use]
+    pub const fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// Position in the file on disk once `decoded` bytes of its content were
+    /// consumed, for progress against the file size.
+    ///
+    /// Plain files are read as they are, so that is `decoded` itself; for
+    /// compressed files it is the compressed bytes read so far.
+    #[must_use]
+    pub fn disk_position(&self, decoded: u64) -> u64 {
+        if self.compression.is_some() {
+            self.disk_read.load(Ordering::Relaxed)
+        } else {
+            decoded
+        }
+    }
+
+    /// Shared count of the bytes read from the file on disk, for parsers
+    /// that take ownership of the reader. Stays 0 for [`Self::plain`] streams.
+    #[must_use]
+    pub fn disk_read(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.disk_read)
+    }
+}
+
+impl Read for LogReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+/// Up to `max` bytes from the start of `path`'s content, decompressed if needed.
+///
+/// Used to sample a file for format detection.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or its start cannot be read.
+pub fn read_head(path: &Path, max: u64) -> anyhow::Result<Vec<u8>> {
+    use anyhow::Context as _;
+    let mut head = Vec::new();
+    // A truncated compressed file still yields the content before the damage
+    if let Err(e) = LogReader::open(path)?.take(max).read_to_end(&mut head) {
+        if head.is_empty() {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    }
+    Ok(head)
+}
+
+/// Fail unless `path` is a regular file (or a link to one).
+///
+/// Format detection samples the start of a file and the parser then opens it
+/// again, so a pipe would lose the lines consumed by the sample.
+///
+/// # Errors
+///
+/// Returns an error if `path` cannot be inspected or is a pipe, socket or
+/// other stream that can only be read once.
+pub fn require_regular_file(path: &Path) -> anyhow::Result<()> {
+    use anyhow::Context as _;
+    let metadata =
+        std::fs::metadata(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    anyhow::ensure!(
+        metadata.is_file(),
+        "{} is not a regular file; save a pipe to a file first",
+        path.display()
+    );
+    Ok(())
+}
+
+/// The compression of the file at `path`, `None` for plain files.
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be opened or read.
+pub fn detect_file(path: &Path) -> std::io::Result<Option<Compression>> {
+    let mut header = [0u8; 6];
+    let n = read_up_to(&mut File::open(path)?, &mut header)?;
+    Ok(Compression::detect(&header[..n]))
+}
+
+/// Fill as much of `buf` as the file has, returning how much was read
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const CONTENT: &str = "11-20 14:23:45.123  1234  5678 I Tag: hello\n\
+                           11-20 14:23:46.123  1234  5678 E Tag: world\n";
+
+    fn read_all(path: &Path) -> (String, LogReader) {
+        let mut reader = LogReader::open(path).expect("open");
+        let mut content = String::new();
+        reader.read_to_string(&mut content).expect("read");
+        (content, reader)
+    }
+
+    #[test]
+    fn test_reads_compressed_logs_like_plain_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let plain = dir.path().join("app.log");
+        std::fs::write(&plain, CONTENT).expect("write");
+
+        // Two gzip members, as left by appending to a rotated file
+        let gzip = dir.path().join("app.log.1.gz");
+        let mut gz_bytes = Vec::new();
+        for part in [&CONTENT[..20], &CONTENT[20..]] {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(part.as_bytes()).expect("compress");
+            gz_bytes.extend(encoder.finish().expect("finish"));
+        }
+        std::fs::write(&gzip, &gz_bytes).expect("write");
+
+        let zstd_path = dir.path().join("app.log.2.zst");
+        std::fs::write(
+            &zstd_path,
+            zstd::encode_all(CONTENT.as_bytes(), 0).expect("compress"),
+        )
+        .expect("write");
+
+        let xz = dir.path().join("app.log.3.xz");
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(CONTENT.as_bytes()).expect("compress");
+        std::fs::write(&xz, encoder.finish().expect("finish")).expect("write");
+
+        for (path, compression) in [
+            (&plain, None),
+            (&gzip, Some(Compression::Gzip)),
+            (&zstd_path, Some(Compression::Zstd)),
+            (&xz, Some(Compression::Xz)),
+        ] {
+            let (content, reader) = read_all(path);
+            assert_eq!(content, CONTENT, "{}", path.display());
+            assert_eq!(reader.compression(), compression);
+            // Progress ends at the size on disk
+            let size = std::fs::metadata(path).expect("metadata").len();
+            assert_eq!(reader.disk_position(CONTENT.len() as u64), size);
+        }
+
+        let head = read_head(&gzip, 10).expect("head");
+        assert_eq!(head, &CONTENT.as_bytes()[..10]);
+    }
+
+    #[test]
+    fn test_first_read_of_a_plain_file_is_not_cut_at_the_header() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, CONTENT).expect("write");
+        let mut reader = LogReader::open(&path).expect("open");
+        let mut buf = [0u8; 32];
+        assert_eq!(reader.read(&mut buf).expect("read"), buf.len());
+    }
+
+    #[test]
+    fn test_tiny_files_are_plain() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("one.log");
+        std::fs::write(&path, "x").expect("write");
+        let (content, reader) = read_all(&path);
+        assert_eq!(content, "x");
+        assert_eq!(reader.compression(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pipes_are_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let fifo = dir.path().join("app.fifo");
+        let status = std::process::Command::new("mkfifo")
+            .arg(&fifo)
+            .status()
+            .expect("run mkfifo");
+        assert!(status.success());
+
+        assert!(require_regular_file(&fifo).is_err());
+        let plain = dir.path().join("app.log");
+        std::fs::write(&plain, CONTENT).expect("write");
+        require_regular_file(&plain).expect("regular file");
+    }
+}
@@ -0,0 +1,127 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Remaining-time estimates for long-running background jobs.
+//!
+//! Loading a large capture or scoring millions of lines takes minutes, and a
+//! bare percentage does not tell whether to wait or come back later. [`Eta`]
+//! extrapolates the throughput observed since the job started.
+
+use std::time::{Duration, Instant};
+
+/// Estimates are withheld until the job ran this long, as the first chunks are
+/// not representative (small chunk sizes, cold caches)
+const MIN_ELAPSED: Duration = Duration::from_secs(2);
+
+/// Estimates are withheld until this fraction of the job is done
+const MIN_FRACTION: f64 = 0.01;
+
+/// Throughput-based estimate of the time a job still needs
+#[derive(Debug, Clone, Copy)]
+pub struct Eta {
+    start: Instant,
+}
+
+impl Eta {
+    /// Start timing a job now
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+
+    /// Time left once `fraction` (0.0–1.0) of the job is done, assuming the
+    /// average throughput so far continues. `None` while too early to tell.
+    #[must_use]
+    pub fn remaining(&self, fraction: f64) -> Option<Duration> {
+        remaining_after(self.start.elapsed(), fraction)
+    }
+
+    /// Progress text such as `38% – about 2 min left`
+    #[must_use]
+    pub fn progress_text(&self, fraction: f64) -> String {
+        progress_text(fraction, self.remaining(fraction))
+    }
+}
+
+fn remaining_after(elapsed: Duration, fraction: f64) -> Option<Duration> {
+    if elapsed < MIN_ELAPSED || !(MIN_FRACTION..1.0).contains(&fraction) {
+        return None;
+    }
+    Some(elapsed.mul_f64((1.0 - fraction) / fraction))
+}
+
+fn progress_text(fraction: f64, remaining: Option<Duration>) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let percent = (fraction.clamp(0.0, 1.0) * 100.0).floor() as u32;
+    remaining.map_or_else(
+        || format!("{percent}%"),
+        |remaining| format!("{percent}% – {}", format_remaining(remaining)),
+    )
+}
+
+/// Coarse, human-friendly remaining time; precision is not meaningful here
+fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    match secs {
+        0..10 => "a few seconds left".to_string(),
+        10..60 => format!("about {} s left", secs.div_ceil(10) * 10),
+        60..3600 => format!("about {} min left", (secs + 30) / 60),
+        _ => format!("about {} h {} min left", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extrapolates_average_throughput() {
+        let remaining = remaining_after(Duration::from_secs(30), 0.25).unwrap();
+        assert_eq!(remaining, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn withholds_early_and_finished_estimates() {
+        assert_eq!(remaining_after(Duration::from_secs(1), 0.5), None);
+        assert_eq!(remaining_after(Duration::from_secs(60), 0.001), None);
+        assert_eq!(remaining_after(Duration::from_secs(60), 1.0), None);
+    }
+
+    #[test]
+    fn formats_progress_text() {
+        assert_eq!(
+            progress_text(0.38, Some(Duration::from_secs(125))),
+            "38% – about 2 min left"
+        );
+        assert_eq!(
+            progress_text(0.9, Some(Duration::from_secs(21))),
+            "90% – about 30 s left"
+        );
+        assert_eq!(
+            progress_text(0.99, Some(Duration::from_secs(3))),
+            "99% – a few seconds left"
+        );
+        assert_eq!(
+            progress_text(0.1, Some(Duration::from_secs(7500))),
+            "10% – about 2 h 5 min left"
+        );
+        assert_eq!(progress_text(0.05, None), "5%");
+    }
+}
@@ -0,0 +1,127 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! `field:KEY=VALUE` terms in a search text.
+//!
+//! A search may contain whitespace-separated terms such as
+//! `field:status=500` that match a line's parsed fields instead of its text.
+//! They are taken out of the search before the rest is compiled as a regex;
+//! a line matches when it matches the regex and every field term.
+
+/// Prefix that marks a field term
+const FIELD_PREFIX: &str = "field:";
+
+/// One `field:KEY=VALUE` term
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldTerm {
+    pub key: String,
+    pub value: String,
+}
+
+impl FieldTerm {
+    /// Whether `fields` holds this term's key with exactly its value.
+    ///
+    /// Keys compare ignoring case; values honour `case_sensitive`.
+    #[must_use]
+    pub fn matches<'a>(
+        &self,
+        mut fields: impl Iterator<Item = (&'a str, &'a str)>,
+        case_sensitive: bool,
+    ) -> bool {
+        fields.any(|(key, value)| {
+            key.eq_ignore_ascii_case(&self.key)
+                && if case_sensitive {
+                    value == self.value
+                } else {
+                    value.to_lowercase() == self.value.to_lowercase()
+                }
+        })
+    }
+}
+
+/// Split `search_text` into the regex part and its field terms.
+///
+/// Returns `search_text` unchanged (and no terms) when it holds no field
+/// term, so plain regexes keep their exact whitespace.
+#[must_use]
+pub fn split_field_terms(search_text: &str) -> (String, Vec<FieldTerm>) {
+    let mut terms = Vec::new();
+    let mut rest = Vec::new();
+    for word in search_text.split_whitespace() {
+        match parse_term(word) {
+            Some(term) => terms.push(term),
+            None => rest.push(word),
+        }
+    }
+    if terms.is_empty() {
+        (search_text.to_string(), terms)
+    } else {
+        (rest.join(" "), terms)
+    }
+}
+
+fn parse_term(word: &str) -> Option<FieldTerm> {
+    let prefix = word.get(..FIELD_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(FIELD_PREFIX) {
+        return None;
+    }
+    let (key, value) = word[FIELD_PREFIX.len()..].split_once('=')?;
+    (!key.is_empty()).then(|| FieldTerm {
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms_are_split_from_the_pattern() {
+        let (rest, terms) = split_field_terms("timeout field:status=500 Field:http.method=GET");
+        assert_eq!(rest, "timeout");
+        assert_eq!(
+            terms,
+            vec![
+                FieldTerm {
+                    key: "status".to_string(),
+                    value: "500".to_string(),
+                },
+                FieldTerm {
+                    key: "http.method".to_string(),
+                    value: "GET".to_string(),
+                },
+            ]
+        );
+
+        // No term: whitespace inside the regex survives
+        assert_eq!(split_field_terms("a  b").0, "a  b");
+        // Not a term: missing key or `=`
+        assert!(split_field_terms("field:=1 field:status").1.is_empty());
+    }
+
+    #[test]
+    fn term_matches_fields() {
+        let fields = [("Status", "500"), ("method", "GET")];
+        let (_, terms) = split_field_terms("field:status=500 field:method=get");
+        assert!(terms[0].matches(fields.iter().copied(), true));
+        assert!(!terms[1].matches(fields.iter().copied(), true));
+        assert!(terms[1].matches(fields.iter().copied(), false));
+        assert!(!terms[0].matches(std::iter::empty(), false));
+    }
+}
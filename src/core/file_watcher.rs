@@ -0,0 +1,117 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Detects changes to opened log files on disk.
+//!
+//! Watches the parent directory of every file rather than the file itself, so
+//! rotation (rename + recreate) and rewrites are noticed as well as appends.
+//! Events for other files in those directories (e.g. our own `.crab` saves)
+//! are ignored.
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+/// Watches a set of files and reports which of them changed since the last poll.
+pub struct FileWatcher {
+    /// `None` if the platform watcher could not be created; then nothing is reported
+    watcher: Option<RecommendedWatcher>,
+    events: Receiver<notify::Result<Event>>,
+    /// Files whose changes are reported: canonical path → path as given to `sync`
+    files: HashMap<PathBuf, PathBuf>,
+    /// Directories currently registered with `watcher`
+    dirs: HashSet<PathBuf>,
+}
+
+impl FileWatcher {
+    /// Create a watcher that wakes up the UI whenever a watched directory changes.
+    #[must_use]
+    pub fn with_repaint(ctx: egui::Context) -> Self {
+        let (tx, events) = channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+            ctx.request_repaint();
+        })
+        .inspect_err(|e| tracing::warn!("File change detection unavailable: {e}"))
+        .ok();
+        Self {
+            watcher,
+            events,
+            files: HashMap::new(),
+            dirs: HashSet::new(),
+        }
+    }
+
+    /// Watch exactly `files` from now on.
+    pub fn sync(&mut self, files: &[PathBuf]) {
+        let files: HashMap<PathBuf, PathBuf> =
+            files.iter().map(|p| (canonical(p), p.clone())).collect();
+        if files == self.files {
+            return;
+        }
+        let dirs: HashSet<PathBuf> = files
+            .keys()
+            .filter_map(|p| p.parent().map(Path::to_path_buf))
+            .collect();
+        if let Some(watcher) = &mut self.watcher {
+            for dir in self.dirs.difference(&dirs) {
+                let _ = watcher.unwatch(dir);
+            }
+            for dir in dirs.difference(&self.dirs) {
+                if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                    tracing::warn!("Cannot watch {} for changes: {e}", dir.display());
+                }
+            }
+        }
+        self.files = files;
+        self.dirs = dirs;
+    }
+
+    /// Watched files that were modified, replaced or removed since the last call.
+    ///
+    /// Paths are returned as they were passed to [`Self::sync`].
+    pub fn changed_files(&self) -> BTreeSet<PathBuf> {
+        let mut changed = BTreeSet::new();
+        while let Ok(event) = self.events.try_recv() {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("File watcher error: {e}");
+                    continue;
+                }
+            };
+            if let EventKind::Access(_) = event.kind {
+                continue;
+            }
+            changed.extend(
+                event
+                    .paths
+                    .iter()
+                    .filter_map(|p| self.files.get(p))
+                    .cloned(),
+            );
+        }
+        changed
+    }
+}
+
+/// Canonical form of `path` so it compares equal to the paths in notify events.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -23,13 +23,21 @@
 //!
 //! The worker is owned by the application and shuts down gracefully when dropped.
 
+use crate::anomaly::keyword;
+use crate::core::chunk_index::{self, ScanHints};
+use crate::core::column_filter::ColumnFilter;
+use crate::core::field_query::split_field_terms;
 use crate::core::log_store::{StoreID, StoreVersion};
 use crate::core::queue_map::QueueMap;
+use crate::core::search_state::TimeScope;
+use crate::core::slow_filter::{self, FilterTiming};
 use crate::core::LogStore;
 use fancy_regex::Regex;
-use std::sync::atomic::{AtomicBool, Ordering};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Request to compute filtered indices in background
 #[derive(Clone)]
@@ -47,6 +55,14 @@ pub struct FilterRequest {
     pub case_sensitive: bool,
     /// Whether to deduplicate exact matches (same timestamp, source, message)
     pub hide_duplicates: bool,
+    /// Values hidden via the column header dropdowns
+    pub column_filter: ColumnFilter,
+    /// Also keep the lines [`keyword::is_error`] accepts, not just regex matches
+    pub or_errors: bool,
+    /// Only keep lines within this time span
+    pub time_scope: Option<TimeScope>,
+    /// Set by the requester once the search changed, to stop this pass early
+    pub cancelled: Arc<AtomicBool>,
 }
 
 /// Result from background filtering
@@ -62,6 +78,8 @@ pub struct FilterResult {
     pub hide_duplicates: bool,
     /// The `LogStore` version these indices were computed for
     pub store_version: StoreVersion,
+    /// How long matching took, for slow filter warnings
+    pub timing: FilterTiming,
 }
 
 /// Handle to send filter requests to the background worker.
@@ -94,12 +112,24 @@ pub struct FilterWorker {
 impl FilterWorker {
     /// Create a new filter worker with a background thread.
     pub fn new() -> Self {
+        Self::spawn(None)
+    }
+
+    /// Create a filter worker that wakes up the UI whenever a result is ready.
+    ///
+    /// Lets the UI sleep between events instead of polling for results.
+    #[must_use]
+    pub fn with_repaint(ctx: egui::Context) -> Self {
+        Self::spawn(Some(ctx))
+    }
+
+    fn spawn(repaint_ctx: Option<egui::Context>) -> Self {
         let (request_tx, request_rx) = channel::<FilterRequest>();
         let is_filtering = Arc::new(AtomicBool::new(false));
         let is_filtering_copy = is_filtering.clone();
 
         let thread = std::thread::spawn(move || {
-            Self::worker_loop(&request_rx, &is_filtering_copy);
+            Self::worker_loop(&request_rx, &is_filtering_copy, repaint_ctx.as_ref());
         });
 
         Self {
@@ -118,7 +148,11 @@ impl FilterWorker {
     }
 
     /// Background worker loop that processes filter requests.
-    fn worker_loop(request_rx: &Receiver<FilterRequest>, is_filtering: &Arc<AtomicBool>) {
+    fn worker_loop(
+        request_rx: &Receiver<FilterRequest>,
+        is_filtering: &Arc<AtomicBool>,
+        repaint_ctx: Option<&egui::Context>,
+    ) {
         profiling::function_scope!();
 
         tracing::debug!("Filter worker thread started");
@@ -150,28 +184,149 @@ impl FilterWorker {
                 tracing::trace!("Processing filter request (search: '{:?}')", request.regex);
 
                 let store_version = request.store.version();
+                let started = Instant::now();
+                let deadline = slow_filter::filter_timeout().map(|timeout| started + timeout);
+                let aborted_lines = AtomicUsize::new(0);
+                let stopped = AtomicBool::new(false);
+                // A line that runs out of its backtracking budget counts as no match
+                let is_match = |regex: &Regex, text: &str| match regex.is_match(text) {
+                    Ok(matched) => matched,
+                    Err(e) => {
+                        if slow_filter::is_budget_exceeded(&e) {
+                            aborted_lines.fetch_add(1, Ordering::Relaxed);
+                        }
+                        false
+                    }
+                };
+                let (pattern, field_terms) = split_field_terms(&request.search_text);
                 // Filter lines in parallel
                 let filtered_indices = {
                     profiling::scope!("filter_lines");
 
                     // Parallel filtering with rayon
-                    request.store.get_matching_ids(|display_msg, raw| {
-                        let matches_include = request.regex.is_match(display_msg).unwrap_or(false)
-                            || request.regex.is_match(raw).unwrap_or(false);
+                    let hints = ScanHints {
+                        time_range: request.time_scope.as_ref().map(|scope| {
+                            (scope.start.timestamp_millis(), scope.end.timestamp_millis())
+                        }),
+                        literal: if request.or_errors {
+                            None
+                        } else {
+                            chunk_index::required_literal(&pattern)
+                        },
+                    };
+                    request
+                        .store
+                        .get_matching_ids_within(&hints, |display_msg, raw| {
+                            // Skip the remaining lines once cancelled or over time
+                            if stopped.load(Ordering::Relaxed) {
+                                return false;
+                            }
+                            if request.cancelled.load(Ordering::Relaxed)
+                                || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                            {
+                                stopped.store(true, Ordering::Relaxed);
+                                return false;
+                            }
 
-                        if !matches_include {
-                            return false;
-                        }
+                            // Error lines are picked by their level below
+                            let matches_include = request.or_errors
+                                || is_match(&request.regex, display_msg)
+                                || is_match(&request.regex, raw);
 
-                        // If there's an exclude pattern, check if the line matches it
-                        request.exclude_regex.as_ref().is_none_or(|exclude_regex| {
-                            let matches_exclude =
-                                exclude_regex.is_match(display_msg).unwrap_or(false)
-                                    || exclude_regex.is_match(raw).unwrap_or(false);
-                            // Return true only if it doesn't match the exclusion pattern
-                            !matches_exclude
+                            if !matches_include {
+                                return false;
+                            }
+
+                            // If there's an exclude pattern, check if the line matches it
+                            request.exclude_regex.as_ref().is_none_or(|exclude_regex| {
+                                let matches_exclude = is_match(exclude_regex, display_msg)
+                                    || is_match(exclude_regex, raw);
+                                // Return true only if it doesn't match the exclusion pattern
+                                !matches_exclude
+                            })
                         })
-                    })
+                };
+                // Keep the regex matches and the lines that state an error level
+                let filtered_indices = if request.or_errors {
+                    profiling::scope!("errors_filter");
+                    filtered_indices
+                        .into_par_iter()
+                        .filter(|id| {
+                            request.store.get_by_id(id).is_some_and(|line| {
+                                keyword::is_error(&line)
+                                    || is_match(&request.regex, &line.message)
+                                    || is_match(&request.regex, &line.raw)
+                            })
+                        })
+                        .collect()
+                } else {
+                    filtered_indices
+                };
+
+                if request.cancelled.load(Ordering::Relaxed) {
+                    // A newer request for this filter is queued; its result replaces ours
+                    tracing::trace!("Filter {filter_id} cancelled");
+                    drain_pending(&mut pending_requests);
+                    continue;
+                }
+                let timed_out = stopped.into_inner();
+                let timing = FilterTiming {
+                    elapsed: started.elapsed(),
+                    aborted_lines: aborted_lines.into_inner(),
+                    timed_out,
+                };
+                if timing.is_slow() {
+                    tracing::warn!(
+                        "Filter {filter_id} is slow: {:?}, {} lines over the backtracking budget{}",
+                        timing.elapsed,
+                        timing.aborted_lines,
+                        if timed_out { ", aborted" } else { "" },
+                    );
+                }
+                // Partial matches of an aborted pass would be misleading
+                let filtered_indices = if timed_out {
+                    Vec::new()
+                } else {
+                    filtered_indices
+                };
+
+                // Apply column filters to the regex matches
+                let filtered_indices = if request.column_filter.is_empty() {
+                    filtered_indices
+                } else {
+                    profiling::scope!("column_filter");
+                    request
+                        .store
+                        .filter_by_columns(filtered_indices, &request.column_filter)
+                };
+
+                // Keep the lines whose parsed fields match every `field:` term
+                let filtered_indices = if field_terms.is_empty() {
+                    filtered_indices
+                } else {
+                    profiling::scope!("field_filter");
+                    request.store.filter_by_fields(
+                        filtered_indices,
+                        &field_terms,
+                        request.case_sensitive,
+                    )
+                };
+
+                // Restrict to the scoped time span
+                let filtered_indices = match &request.time_scope {
+                    Some(scope) => {
+                        profiling::scope!("time_scope_filter");
+                        filtered_indices
+                            .into_iter()
+                            .filter(|id| {
+                                request
+                                    .store
+                                    .adjusted_timestamp(id)
+                                    .is_some_and(|ts| scope.contains(ts))
+                            })
+                            .collect()
+                    }
+                    None => filtered_indices,
                 };
 
                 // Apply deduplication if requested (serial pass after parallel regex filter)
@@ -213,6 +368,7 @@ impl FilterWorker {
                     case_sensitive: request.case_sensitive,
                     hide_duplicates: request.hide_duplicates,
                     store_version,
+                    timing,
                 };
 
                 // Send result back to the specific filter (ignore errors if filter is gone)
@@ -221,11 +377,18 @@ impl FilterWorker {
 
                     let _ = request.result_tx.send(result);
                 }
+                if let Some(ctx) = repaint_ctx {
+                    crate::ui::repaint::request_repaint(ctx);
+                }
 
                 // Check one more time if a newer request arrived during processing
                 drain_pending(&mut pending_requests);
             }
             is_filtering.store(false, Ordering::Relaxed);
+            // Let the status bar drop its "Filtering..." indicator
+            if let Some(ctx) = repaint_ctx {
+                ctx.request_repaint();
+            }
         }
         tracing::debug!("Filter worker thread shutting down (channel closed)");
     }
@@ -0,0 +1,78 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Hex and ASCII dump of binary records such as captured packets.
+
+use std::fmt::Write as _;
+
+/// Bytes shown per row
+const ROW_BYTES: usize = 16;
+
+/// `bytes` as rows of an offset, 16 hex bytes split into two groups of 8 and
+/// their ASCII, with `.` for unprintable bytes, like `xxd` or Wireshark:
+///
+/// ```text
+/// 0000  45 00 00 3c 1c 46 40 00  40 06 b1 e6 ac 10 0a 63  |E..<.F@.@......c|
+/// ```
+#[must_use]
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+    for (row, chunk) in bytes.chunks(ROW_BYTES).enumerate() {
+        if row > 0 {
+            dump.push('\n');
+        }
+        let _ = write!(dump, "{:04x} ", row * ROW_BYTES);
+        for i in 0..ROW_BYTES {
+            if i % 8 == 0 {
+                dump.push(' ');
+            }
+            match chunk.get(i) {
+                Some(byte) => {
+                    let _ = write!(dump, "{byte:02x} ");
+                }
+                None => dump.push_str("   "),
+            }
+        }
+        dump.push_str(" |");
+        dump.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                char::from(byte)
+            } else {
+                '.'
+            }
+        }));
+        dump.push('|');
+    }
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump_pads_last_row() {
+        let bytes: Vec<u8> = (0x41..0x41 + 18).chain([0x00, 0x7f]).collect();
+        assert_eq!(
+            hex_dump(&bytes),
+            "0000  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|\n\
+             0010  51 52 00 7f                                       |QR..|"
+        );
+        assert_eq!(hex_dump(&[]), "");
+    }
+}
@@ -41,6 +41,8 @@ pub const SCORE_BUCKETS: usize = 20;
 #[derive(Debug, Clone, Copy, Default)]
 pub struct AnomalyDistribution {
     pub buckets: [usize; SCORE_BUCKETS], // count per score bucket for a single time bucket
+    /// Highest anomaly score (0–100) of the lines in the time bucket
+    pub max_score: f64,
 }
 
 /// Request to compute histogram data in background
@@ -49,6 +51,8 @@ pub struct HistogramRequest {
     pub key: HistogramCacheKey,
     /// Whether to use ML sidecar score instead of heuristic score
     pub color_by_ml_score: bool,
+    /// Anomaly-only mode: count only lines scoring at least this (0–100)
+    pub min_anomaly_score: Option<u8>,
     /// Unique identifier for the filter this histogram belongs to
     pub filter_id: usize,
     /// The log store to read from
@@ -74,6 +78,8 @@ pub struct HistogramCacheKey {
     pub zoom_range_ms: Option<(i64, i64)>,
     /// Whether to use ML sidecar score instead of heuristic score
     pub color_by_ml_score: bool,
+    /// Anomaly-only threshold the buckets were counted with
+    pub min_anomaly_score: Option<u8>,
 }
 
 /// Result from background histogram computation
@@ -129,10 +135,20 @@ impl HistogramWorker {
     /// Create a new histogram worker with a background thread.
     #[must_use]
     pub fn new() -> Self {
+        Self::spawn(None)
+    }
+
+    /// Create a histogram worker that wakes up the UI whenever a result is ready.
+    #[must_use]
+    pub fn with_repaint(ctx: egui::Context) -> Self {
+        Self::spawn(Some(ctx))
+    }
+
+    fn spawn(repaint_ctx: Option<egui::Context>) -> Self {
         let (request_tx, request_rx) = channel::<HistogramRequest>();
 
         let thread = std::thread::spawn(move || {
-            Self::worker_loop(&request_rx);
+            Self::worker_loop(&request_rx, repaint_ctx.as_ref());
         });
 
         Self {
@@ -147,7 +163,7 @@ impl HistogramWorker {
     }
 
     /// Background worker loop that processes histogram requests.
-    fn worker_loop(request_rx: &Receiver<HistogramRequest>) {
+    fn worker_loop(request_rx: &Receiver<HistogramRequest>, repaint_ctx: Option<&egui::Context>) {
         profiling::function_scope!();
 
         tracing::debug!("Histogram worker thread started");
@@ -188,6 +204,9 @@ impl HistogramWorker {
 
                 // Send result back (ignore errors if receiver is gone)
                 let _ = result_channel.send(result);
+                if let Some(ctx) = repaint_ctx {
+                    crate::ui::repaint::request_repaint(ctx);
+                }
 
                 // Check one more time if a newer request arrived during processing
                 drain_pending(&mut pending_requests);
@@ -234,13 +253,14 @@ impl HistogramWorker {
         // Filter indices to only those within the zoom range
         let zoomed_indices: Vec<StoreID> = if request.zoom_range.is_some() {
             profiling::scope!("Histogram::filter_zoom_range");
+            let (start_ms, end_ms) = (start_time.timestamp_millis(), end_time.timestamp_millis());
             filtered_indices
                 .iter()
                 .filter(|idx| {
                     store.get_by_id(idx).is_some_and(|line| {
                         // Use adjusted timestamp for zoom filtering
-                        let ts = line.timestamp;
-                        ts >= start_time && ts <= end_time
+                        let ts = line.timestamp_ms;
+                        ts >= start_ms && ts <= end_ms
                     })
                 })
                 .copied()
@@ -249,8 +269,14 @@ impl HistogramWorker {
             filtered_indices
         };
 
-        let (buckets, anomaly_buckets) =
-            Self::create_buckets(store, &zoomed_indices, start_time, bucket_size, request.color_by_ml_score);
+        let (buckets, anomaly_buckets) = Self::create_buckets(
+            store,
+            &zoomed_indices,
+            start_time,
+            bucket_size,
+            request.color_by_ml_score,
+            request.min_anomaly_score,
+        );
 
         HistogramResult {
             cache_key: request.key.clone(),
@@ -273,11 +299,11 @@ impl HistogramWorker {
         // Use adjusted timestamps (with per-source offsets) for accurate time range
         let first_ts = filtered_indices
             .iter()
-            .find_map(|idx| store.get_by_id(idx).map(|line| line.timestamp));
+            .find_map(|idx| store.get_by_id(idx).map(|line| line.timestamp()));
         let last_ts = filtered_indices
             .iter()
             .rev()
-            .find_map(|idx| store.get_by_id(idx).map(|line| line.timestamp));
+            .find_map(|idx| store.get_by_id(idx).map(|line| line.timestamp()));
 
         match (first_ts, last_ts) {
             (Some(start), Some(end)) => Some((start, end)),
@@ -291,43 +317,48 @@ impl HistogramWorker {
         start_time: DateTime<Local>,
         bucket_size: Duration,
         color_by_ml_score: bool,
+        min_anomaly_score: Option<u8>,
     ) -> (Vec<usize>, Vec<AnomalyDistribution>) {
         profiling::scope!("Histogram::create_buckets");
         let mut buckets = vec![0usize; NUM_BUCKETS];
         let mut anomaly_distributions = vec![AnomalyDistribution::default(); NUM_BUCKETS];
+        let start_ms = start_time.timestamp_millis();
 
         // possible optimization: par_iter
         for line_idx in filtered_indices {
             if let Some(line) = store.get_by_id(line_idx) {
-                // Use adjusted timestamp (with per-source offsets) for accurate binning
-                let ts = line.timestamp;
-                let bucket_idx = Self::timestamp_to_bucket(ts, start_time, bucket_size);
-                buckets[bucket_idx] += 1;
-
                 // Use ML score if enabled and available, otherwise fall back to heuristic
                 let raw_score = if color_by_ml_score && line.sidecar_scored {
                     line.sidecar_anomaly_score
                 } else {
                     line.anomaly_score
                 };
+                if min_anomaly_score.is_some_and(|min| raw_score < f64::from(min)) {
+                    continue;
+                }
+
+                // Use adjusted timestamp (with per-source offsets) for accurate binning
+                let bucket_idx =
+                    Self::timestamp_to_bucket(line.timestamp_ms, start_ms, bucket_size);
+                buckets[bucket_idx] += 1;
+
                 let line_score = raw_score / 100.0;
                 // Determine which score bucket this falls into
                 let score_bucket =
                     ((line_score * SCORE_BUCKETS as f64).floor() as usize).min(SCORE_BUCKETS - 1);
-                anomaly_distributions[bucket_idx].buckets[score_bucket] += 1;
+                let dist = &mut anomaly_distributions[bucket_idx];
+                dist.buckets[score_bucket] += 1;
+                dist.max_score = dist.max_score.max(raw_score);
             }
         }
 
         (buckets, anomaly_distributions)
     }
 
-    fn timestamp_to_bucket(
-        ts: DateTime<Local>,
-        start_time: DateTime<Local>,
-        bucket_size: Duration,
-    ) -> usize {
-        let elapsed = ts - start_time;
-        ((elapsed.as_seconds_f64() / bucket_size.as_secs_f64()) as usize).min(NUM_BUCKETS - 1)
+    /// Bucket of a line at `ts_ms`, for a histogram starting at `start_ms` (epoch milliseconds)
+    fn timestamp_to_bucket(ts_ms: i64, start_ms: i64, bucket_size: Duration) -> usize {
+        let elapsed_secs = (ts_ms - start_ms) as f64 / 1000.0;
+        ((elapsed_secs / bucket_size.as_secs_f64()) as usize).min(NUM_BUCKETS - 1)
     }
 }
 
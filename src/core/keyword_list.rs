@@ -0,0 +1,98 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Plain-text keyword lists for bulk highlight import.
+//!
+//! One pattern per line, optionally followed by a tab and a `#RRGGBB` color.
+//! Blank lines and lines starting with `#` are skipped, so shared lists can
+//! carry comments.
+
+use egui::Color32;
+
+/// A single pattern read from a keyword list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordEntry {
+    pub pattern: String,
+    /// Color given after the tab, if any and valid.
+    pub color: Option<Color32>,
+}
+
+/// Parse a keyword list into its entries, in file order.
+///
+/// An unparsable color is ignored rather than rejecting the line, so the
+/// pattern still gets imported with a palette color.
+#[must_use]
+pub fn parse_keyword_list(text: &str) -> Vec<KeywordEntry> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (pattern, color) = match line.split_once('\t') {
+                Some((pattern, color)) => (pattern, parse_color(color.trim())),
+                None => (line, None),
+            };
+            let pattern = pattern.trim();
+            (!pattern.is_empty()).then(|| KeywordEntry {
+                pattern: pattern.to_string(),
+                color,
+            })
+        })
+        .collect()
+}
+
+fn parse_color(hex: &str) -> Option<Color32> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    Color32::from_hex(&format!("#{hex}")).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_with_and_without_color() {
+        let entries = parse_keyword_list("timeout\nOOM\t#ff0000\r\nretry\tc0c000\n");
+        assert_eq!(
+            entries,
+            vec![
+                KeywordEntry {
+                    pattern: "timeout".to_string(),
+                    color: None,
+                },
+                KeywordEntry {
+                    pattern: "OOM".to_string(),
+                    color: Some(Color32::from_rgb(255, 0, 0)),
+                },
+                KeywordEntry {
+                    pattern: "retry".to_string(),
+                    color: Some(Color32::from_rgb(192, 192, 0)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_blank_lines_and_bad_colors() {
+        let entries = parse_keyword_list("# shared list\n\n   \nfoo\tnot-a-color\n\t#00ff00\n");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].pattern, "foo");
+        assert_eq!(entries[0].color, None);
+    }
+}
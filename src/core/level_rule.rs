@@ -0,0 +1,162 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-source rules that correct the level of lines, for firmwares that log
+//! everything as INFO.
+//!
+//! A rule gives every line whose message matches its pattern a level label.
+//! The label replaces the line's value in its level column (`Level`,
+//! `Severity` or `Priority`, whichever the format has), so column filters see
+//! it, and the severity it stands for replaces the one the format stated, so
+//! the anomaly scorer does. The first matching rule wins; lines no rule
+//! matches keep their parsed level.
+
+use fancy_regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::filetype::Severity;
+
+/// Columns that hold a line's level, by format
+pub const LEVEL_COLUMNS: [&str; 3] = ["Level", "Severity", "Priority"];
+
+/// Gives lines whose message matches `pattern` the level `level`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelRule {
+    /// Regular expression searched in the line's message
+    pub pattern: String,
+    /// Level label as the format writes it, e.g. `E` for logcat
+    pub level: String,
+}
+
+/// [`LevelRule`]s with their patterns compiled
+#[derive(Debug, Default)]
+pub struct LevelRemap {
+    rules: Vec<LevelRule>,
+    /// Pattern, label and the severity the label stands for, per valid rule
+    compiled: Vec<(Regex, String, Option<Severity>)>,
+}
+
+impl LevelRemap {
+    /// Compile `rules`; rules with an invalid pattern are kept but never match
+    #[must_use]
+    pub fn new(rules: Vec<LevelRule>) -> Self {
+        let compiled = rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(regex) => Some((regex, rule.level.clone(), Severity::from_label(&rule.level))),
+                Err(e) => {
+                    tracing::warn!("Ignoring level rule /{}/: {e}", rule.pattern);
+                    None
+                }
+            })
+            .collect();
+        Self { rules, compiled }
+    }
+
+    /// The rules as given, in order
+    #[must_use]
+    pub fn rules(&self) -> &[LevelRule] {
+        &self.rules
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.compiled.is_empty()
+    }
+
+    /// Label and severity of the first rule matching `message`
+    fn matching(&self, message: &str) -> Option<(&str, Option<Severity>)> {
+        self.compiled
+            .iter()
+            .find(|(regex, _, _)| regex.is_match(message).unwrap_or(false))
+            .map(|(_, label, severity)| (label.as_str(), *severity))
+    }
+
+    /// Severity of a line with `message` whose format stated `parsed`
+    #[must_use]
+    pub fn severity(&self, message: &str, parsed: Option<Severity>) -> Option<Severity> {
+        self.matching(message)
+            .and_then(|(_, severity)| severity)
+            .or(parsed)
+    }
+
+    /// Replace the level in `facets` of a line with `message`, or add a
+    /// `Level` column if the format has none
+    pub fn remap_facets(&self, message: &str, facets: &mut Vec<(&'static str, String)>) {
+        let Some((label, _)) = self.matching(message) else {
+            return;
+        };
+        let label = label.to_string();
+        match facets
+            .iter_mut()
+            .find(|(column, _)| LEVEL_COLUMNS.contains(column))
+        {
+            Some((_, value)) => *value = label,
+            None => facets.push((LEVEL_COLUMNS[0], label)),
+        }
+    }
+}
+
+/// Whether `pattern` compiles, for validating a rule before adding it
+#[must_use]
+pub fn is_valid(pattern: &str) -> bool {
+    Regex::new(pattern).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_matching_rule_sets_level_and_severity() {
+        let remap = LevelRemap::new(vec![
+            LevelRule {
+                pattern: "[".to_string(),
+                level: "W".to_string(),
+            },
+            LevelRule {
+                pattern: "(?i)failed".to_string(),
+                level: "E".to_string(),
+            },
+            LevelRule {
+                pattern: "fail".to_string(),
+                level: "W".to_string(),
+            },
+        ]);
+        assert_eq!(remap.rules().len(), 3);
+
+        let mut facets = vec![("Level", "I".to_string()), ("Tag", "Net".to_string())];
+        remap.remap_facets("Connect FAILED", &mut facets);
+        assert_eq!(facets[0], ("Level", "E".to_string()));
+        assert_eq!(
+            remap.severity("Connect FAILED", Some(Severity::Info)),
+            Some(Severity::Error)
+        );
+
+        // Formats without a level column get one
+        let mut facets = vec![("Method", "GET".to_string())];
+        remap.remap_facets("fail", &mut facets);
+        assert_eq!(facets[1], ("Level", "W".to_string()));
+
+        // Unmatched lines keep their level
+        let mut facets = vec![("Severity", "info".to_string())];
+        remap.remap_facets("all good", &mut facets);
+        assert_eq!(facets[0].1, "info");
+        assert_eq!(remap.severity("all good", None), None);
+    }
+}
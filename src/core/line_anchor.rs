@@ -0,0 +1,153 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Content anchors that let a line be found again after its index changed.
+//!
+//! A line index is only meaningful for one particular read of a file. When the
+//! log grows, is regenerated or rotated, the same message may end up at a
+//! different index. A [`LineAnchor`] records the line's timestamp and a stable
+//! hash of its message so it can be re-located in the new content.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Timestamp and message hash identifying a log line independent of its index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LineAnchor {
+    /// Line timestamp in milliseconds since the epoch
+    pub timestamp_ms: i64,
+    /// [`content_hash`] of the line's message
+    pub content_hash: u64,
+}
+
+impl LineAnchor {
+    #[must_use]
+    pub fn new(timestamp: chrono::DateTime<chrono::Local>, message: &str) -> Self {
+        Self {
+            timestamp_ms: timestamp.timestamp_millis(),
+            content_hash: content_hash(message),
+        }
+    }
+}
+
+/// Stable 64-bit FNV-1a hash of `text`.
+///
+/// Unlike `std`'s `DefaultHasher` the result never changes between builds, so
+/// it can be persisted in `.crab` files.
+#[must_use]
+pub fn content_hash(text: &str) -> u64 {
+    bytes_hash(text.as_bytes())
+}
+
+/// [`content_hash`] of raw bytes
+#[must_use]
+pub fn bytes_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Re-locate `anchors` in a source of `len` lines.
+///
+/// Each anchor comes with the index it was last seen at, which is used as a
+/// hint: if the line there still matches nothing is scanned. Otherwise a single
+/// pass over all lines collects candidates with the same message hash and the
+/// one closest to the hint wins, preferring candidates whose timestamp matches
+/// too. Returns `None` for anchors whose message no longer occurs.
+pub fn resolve_anchors(
+    anchors: &[(usize, LineAnchor)],
+    len: usize,
+    anchor_at: impl Fn(usize) -> Option<LineAnchor>,
+) -> Vec<Option<usize>> {
+    let mut resolved: Vec<Option<usize>> = anchors
+        .iter()
+        .map(|(hint, anchor)| (anchor_at(*hint) == Some(*anchor)).then_some(*hint))
+        .collect();
+    if resolved.iter().all(Option::is_some) {
+        return resolved;
+    }
+
+    let mut candidates: HashMap<u64, Vec<(usize, i64)>> = anchors
+        .iter()
+        .zip(&resolved)
+        .filter(|(_, found)| found.is_none())
+        .map(|((_, anchor), _)| (anchor.content_hash, Vec::new()))
+        .collect();
+    for index in 0..len {
+        if let Some(line) = anchor_at(index) {
+            if let Some(list) = candidates.get_mut(&line.content_hash) {
+                list.push((index, line.timestamp_ms));
+            }
+        }
+    }
+
+    for ((hint, anchor), found) in anchors.iter().zip(resolved.iter_mut()) {
+        if found.is_some() {
+            continue;
+        }
+        let Some(list) = candidates.get(&anchor.content_hash) else {
+            continue;
+        };
+        let closest = |exact_time: bool| {
+            list.iter()
+                .filter(|(_, ts)| !exact_time || *ts == anchor.timestamp_ms)
+                .min_by_key(|(index, _)| index.abs_diff(*hint))
+                .map(|(index, _)| *index)
+        };
+        *found = closest(true).or_else(|| closest(false));
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(timestamp_ms: i64, message: &str) -> LineAnchor {
+        LineAnchor {
+            timestamp_ms,
+            content_hash: content_hash(message),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_resolve_anchors_after_lines_were_prepended() {
+        let old = [anchor(1, "boot"), anchor(2, "crash"), anchor(3, "crash")];
+        let new = [
+            anchor(0, "rotated"),
+            anchor(0, "rotated"),
+            anchor(1, "boot"),
+            anchor(2, "crash"),
+            anchor(3, "crash"),
+        ];
+        let anchors = [(0, old[0]), (2, old[2]), (1, anchor(9, "gone"))];
+
+        let resolved = resolve_anchors(&anchors, new.len(), |i| new.get(i).copied());
+
+        assert_eq!(resolved, [Some(2), Some(4), None]);
+    }
+}
@@ -0,0 +1,326 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Line storage that can drop its oldest lines without renumbering the rest.
+//!
+//! Lines live in fixed-size immutable chunks shared through `Arc`, so
+//! cloning a buffer only copies chunk pointers. Writers build an extended
+//! clone and publish it while readers keep using the one they hold.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Number of line indices covered by one chunk.
+///
+/// Chunk `id` holds lines `id * CHUNK_LINES .. (id + 1) * CHUNK_LINES`, so a
+/// chunk ID stays valid for as long as any of its lines is retained.
+pub const CHUNK_LINES: usize = 65_536;
+
+/// The lines of one chunk ID, made of the appended batches that fell into
+/// its index range. Only the newest chunk is ever replaced, by a copy with
+/// one more batch. Batches are shared; a small one is merged with the ones
+/// before it that are no larger, like the carries of a binary counter, so a
+/// chunk holds few pieces and each line is copied a logarithmic number of
+/// times even when lines arrive one by one.
+#[derive(Debug)]
+struct Chunk<T> {
+    /// Batches in order, each with the index of its first line
+    pieces: Vec<(usize, Arc<Vec<T>>)>,
+}
+
+impl<T> Chunk<T> {
+    /// One past the chunk's newest line index
+    fn end(&self) -> usize {
+        self.pieces
+            .last()
+            .map_or(0, |(start, lines)| start + lines.len())
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        let piece = self
+            .pieces
+            .partition_point(|(start, lines)| start + lines.len() <= index);
+        let (start, lines) = self.pieces.get(piece)?;
+        lines.get(index.checked_sub(*start)?)
+    }
+}
+
+/// Lines of one source, addressed by their index within the source.
+///
+/// Evicting the oldest lines leaves the indices of the retained ones
+/// unchanged, so `StoreID`s handed out earlier stay valid: `first_index()` is
+/// the oldest retained line and `len()` is one past the newest, exactly as if
+/// nothing had been dropped.
+///
+/// Cloning is cheap: chunks are shared, not copied.
+#[derive(Debug)]
+pub struct LineBuffer<T> {
+    first_index: usize,
+    /// ID of `chunks[0]`
+    first_chunk: usize,
+    /// Chunks holding at least one retained line, oldest first. The first
+    /// chunk may start before `first_index`.
+    chunks: VecDeque<Arc<Chunk<T>>>,
+}
+
+impl<T> LineBuffer<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            first_index: 0,
+            first_chunk: 0,
+            chunks: VecDeque::new(),
+        }
+    }
+
+    /// Index of the oldest retained line.
+    #[must_use]
+    pub const fn first_index(&self) -> usize {
+        self.first_index
+    }
+
+    /// Number of lines ever appended, i.e. one past the newest index.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.chunks
+            .back()
+            .map_or(self.first_index, |chunk| chunk.end())
+    }
+
+    /// Number of lines currently held.
+    #[must_use]
+    pub fn retained(&self) -> usize {
+        self.len() - self.first_index
+    }
+
+    /// Whether no lines are held.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.retained() == 0
+    }
+
+    /// Indices of the retained lines.
+    #[must_use]
+    pub fn indices(&self) -> Range<usize> {
+        self.first_index..self.len()
+    }
+
+    /// ID of the chunk holding the line at `index`.
+    #[must_use]
+    pub const fn chunk_id(index: usize) -> usize {
+        index / CHUNK_LINES
+    }
+
+    /// IDs of the chunks holding retained lines.
+    #[must_use]
+    pub fn chunk_ids(&self) -> Range<usize> {
+        self.first_chunk..self.first_chunk + self.chunks.len()
+    }
+
+    /// The line at `index`, or `None` if it was evicted or never appended.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.first_index {
+            return None;
+        }
+        self.chunks
+            .get(Self::chunk_id(index) - self.first_chunk)?
+            .get(index)
+    }
+
+    /// Append lines after the newest one, splitting them at chunk boundaries.
+    pub fn extend(&mut self, lines: impl IntoIterator<Item = T>)
+    where
+        T: Clone,
+    {
+        let mut lines: Vec<T> = lines.into_iter().collect();
+        while !lines.is_empty() {
+            let start = self.len();
+            let room = CHUNK_LINES - start % CHUNK_LINES;
+            let rest = if lines.len() > room {
+                lines.split_off(room)
+            } else {
+                Vec::new()
+            };
+            let batch = std::mem::replace(&mut lines, rest);
+            let piece = (start, Arc::new(batch));
+
+            if start.is_multiple_of(CHUNK_LINES) {
+                if self.chunks.is_empty() {
+                    self.first_chunk = Self::chunk_id(start);
+                }
+                self.chunks.push_back(Arc::new(Chunk {
+                    pieces: vec![piece],
+                }));
+            } else if let Some(last) = self.chunks.back_mut() {
+                let mut pieces = last.pieces.clone();
+                let (mut start, mut batch) = piece;
+                while let Some((tail_start, tail)) =
+                    pieces.pop_if(|(_, tail)| tail.len() <= batch.len())
+                {
+                    let mut merged = Vec::with_capacity(tail.len() + batch.len());
+                    merged.extend_from_slice(&tail);
+                    merged.extend_from_slice(&batch);
+                    start = tail_start;
+                    batch = Arc::new(merged);
+                }
+                pieces.push((start, batch));
+                *last = Arc::new(Chunk { pieces });
+            } else {
+                // Everything was evicted in the middle of a chunk
+                self.first_chunk = Self::chunk_id(start);
+                self.chunks.push_back(Arc::new(Chunk {
+                    pieces: vec![piece],
+                }));
+            }
+        }
+    }
+
+    /// Drop the oldest lines until at most `keep` remain, returning them.
+    ///
+    /// A chunk is released once none of its lines are retained; until then
+    /// readers holding an older clone keep seeing its lines.
+    pub fn evict_to(&mut self, keep: usize) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let excess = self.retained().saturating_sub(keep);
+        let new_first = self.first_index + excess;
+        let evicted = (self.first_index..new_first)
+            .filter_map(|index| self.get(index).cloned())
+            .collect();
+        while self.chunks.front().is_some_and(|c| c.end() <= new_first) {
+            self.chunks.pop_front();
+            self.first_chunk += 1;
+        }
+        self.first_index = new_first;
+        evicted
+    }
+}
+
+impl<T> Clone for LineBuffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            first_index: self.first_index,
+            first_chunk: self.first_chunk,
+            chunks: self.chunks.clone(),
+        }
+    }
+}
+
+impl<T> Default for LineBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eviction_keeps_indices_stable() {
+        let mut buffer = LineBuffer::new();
+        buffer.extend(["a", "b", "c", "d", "e"]);
+
+        assert_eq!(buffer.evict_to(2), vec!["a", "b", "c"]);
+        assert_eq!(buffer.first_index(), 3);
+        assert_eq!(buffer.len(), 5);
+        assert_eq!(buffer.retained(), 2);
+        assert_eq!(buffer.get(1), None);
+        assert_eq!(buffer.get(3), Some(&"d"));
+
+        buffer.extend(["f"]);
+        assert_eq!(buffer.indices(), 3..6);
+        assert_eq!(buffer.get(5), Some(&"f"));
+        assert_eq!(buffer.get(6), None);
+    }
+
+    #[test]
+    fn test_evict_to_more_than_retained_is_noop() {
+        let mut buffer = LineBuffer::new();
+        buffer.extend([1, 2]);
+        assert!(buffer.evict_to(10).is_empty());
+        assert_eq!(buffer.indices(), 0..2);
+    }
+
+    #[test]
+    fn test_lookup_spans_chunks() {
+        let mut buffer = LineBuffer::new();
+        buffer.extend([0, 1, 2]);
+        buffer.extend(Vec::new());
+        buffer.extend([3]);
+        buffer.extend([4, 5]);
+        assert_eq!(
+            (0..6)
+                .filter_map(|i| buffer.get(i).copied())
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5]
+        );
+
+        // Evicting into the middle of a chunk keeps its tail
+        assert_eq!(buffer.evict_to(4), vec![0, 1]);
+        assert_eq!(buffer.get(1), None);
+        assert_eq!(buffer.get(2), Some(&2));
+        assert_eq!(buffer.evict_to(0), vec![2, 3, 4, 5]);
+        assert!(buffer.is_empty());
+        buffer.extend([6]);
+        assert_eq!(buffer.indices(), 6..7);
+    }
+
+    #[test]
+    fn test_batches_are_split_at_chunk_boundaries() {
+        let mut buffer = LineBuffer::new();
+        buffer.extend(0..CHUNK_LINES - 1);
+        buffer.extend([CHUNK_LINES - 1, CHUNK_LINES, CHUNK_LINES + 1]);
+        assert_eq!(buffer.chunk_ids(), 0..2);
+        assert_eq!(LineBuffer::<usize>::chunk_id(CHUNK_LINES), 1);
+        assert_eq!(buffer.get(CHUNK_LINES - 1), Some(&(CHUNK_LINES - 1)));
+        assert_eq!(buffer.get(CHUNK_LINES + 1), Some(&(CHUNK_LINES + 1)));
+
+        // The first chunk goes once none of its lines are retained
+        buffer.evict_to(2);
+        assert_eq!(buffer.chunk_ids(), 1..2);
+        assert_eq!(buffer.get(CHUNK_LINES - 1), None);
+        assert_eq!(buffer.get(CHUNK_LINES), Some(&CHUNK_LINES));
+    }
+
+    #[test]
+    fn test_single_line_appends_keep_few_pieces() {
+        let mut buffer = LineBuffer::new();
+        for i in 0..1000 {
+            buffer.extend([i]);
+        }
+        let pieces = buffer.chunks.back().map_or(0, |chunk| chunk.pieces.len());
+        assert!(pieces <= 10, "{pieces} pieces");
+        assert!((0..1000).all(|i| buffer.get(i) == Some(&i)));
+    }
+
+    #[test]
+    fn test_clone_is_unaffected_by_later_appends() {
+        let mut buffer = LineBuffer::new();
+        buffer.extend(["a", "b"]);
+        let snapshot = buffer.clone();
+        buffer.extend(["c"]);
+        buffer.evict_to(1);
+        assert_eq!(snapshot.indices(), 0..2);
+        assert_eq!(snapshot.get(0), Some(&"a"));
+        assert_eq!(buffer.indices(), 2..3);
+    }
+}
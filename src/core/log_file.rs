@@ -19,9 +19,13 @@
 use crate::anomaly::{
     create_default_scorer, normalize_scores,
     sidecar_client::{InputLine, SidecarClient},
+    WARMUP_LINES,
 };
+use crate::core::eta::Eta;
 use crate::core::log_store::{DataSourceVariant, GlobalFileConfig, LogStore, SourceData};
+use crate::core::run_history::{file_hash, PreviousRun, RunSummary};
 use crate::core::{ChunkedLoader, SavedFilter, SavedHighlight};
+use crate::filetype::journal::JournalFileType;
 use crate::filetype::{InputFileType, LineType};
 use crate::ui::ProgressToastHandle;
 use std::path::{Path, PathBuf};
@@ -73,6 +77,42 @@ impl LogFileLoader {
         )
     }
 
+    /// Like [`Self::load_file`], but parse `path` as the format registered as
+    /// `slug` instead of detecting it.
+    pub fn load_file_as(
+        path: &Path,
+        slug: &str,
+        toast: &ProgressToastHandle,
+        warnings: &crate::ui::ToastSender,
+        file_config: &GlobalFileConfig,
+        store: &Arc<LogStore>,
+    ) -> Option<(DataSourceVariant, Vec<SavedFilter>, Vec<SavedHighlight>)> {
+        crate::core::log_store::open_source_as(slug, path, toast, warnings, file_config, store)
+    }
+
+    /// Follow the system journal live, capturing it to `capture_path`.
+    ///
+    /// The source keeps loading while `journalctl -f` runs; removing it from
+    /// the store stops `journalctl`. See [`JournalFileType::follow`].
+    pub fn follow_journal(
+        capture_path: &Path,
+        toast: &ProgressToastHandle,
+        warnings: &crate::ui::ToastSender,
+        store: &Arc<LogStore>,
+    ) -> (DataSourceVariant, Vec<SavedFilter>, Vec<SavedHighlight>) {
+        // The journal format has no settings
+        let (source, filters, highlights) = Self::load_typed(
+            capture_path.to_path_buf(),
+            toast,
+            warnings,
+            Arc::default(),
+            JournalFileType::follow,
+            store,
+        );
+        source.mark_live();
+        (source.into(), filters, highlights)
+    }
+
     /// Create a typed [`SourceData<T>`], spawn a background loading thread, and
     /// return the source before loading completes.
     ///
@@ -100,11 +140,13 @@ impl LogFileLoader {
         let source_clone = Arc::clone(&data_source);
         let store_clone = Arc::clone(store);
         let toast_clone = toast.clone();
+        let warnings_clone = warnings.clone();
         thread::spawn(move || {
             Self::background_load(
                 path.as_path(),
                 &source_clone,
                 &toast_clone,
+                &warnings_clone,
                 open_fn,
                 &store_clone,
                 source_id,
@@ -118,6 +160,7 @@ impl LogFileLoader {
         path: &Path,
         data_source: &Arc<SourceData<FT>>,
         toast: &ProgressToastHandle,
+        warnings: &crate::ui::ToastSender,
         open_fn: impl FnOnce(&Path, Arc<<FT::LineType as LineType>::FileState>) -> anyhow::Result<FT>,
         store: &Arc<LogStore>,
         source_id: u64,
@@ -140,6 +183,7 @@ impl LogFileLoader {
                 tracing::error!("Failed to open {}: {e}", path.display());
                 toast.set_error(format!("Failed to open file: {e}"));
                 toast.dismiss();
+                data_source.mark_loaded();
                 return;
             }
         };
@@ -151,8 +195,34 @@ impl LogFileLoader {
         };
 
         let load_complete = loader.run(&mut file_type, data_source, &file_name, file_size, toast);
+        if data_source.is_cancelled() {
+            // The source was removed from the store while loading
+            toast.dismiss();
+            data_source.mark_loaded();
+            return;
+        }
+        // The lines are shown already; the bookmarks follow
+        if let Err(e) = data_source.insert_pending_bookmarks() {
+            warnings.send(format!(
+                "Bookmarks of {file_name} could not be read ({e}); its session is not saved to keep them"
+            ));
+        }
+        let orphaned = data_source.reanchor_bookmarks();
+        if orphaned > 0 {
+            warnings.send(format!(
+                "{orphaned} bookmark(s) in {file_name} could not be found in the current file content"
+            ));
+        }
+        let dropped = data_source.reanchor_suppressions();
+        if dropped > 0 {
+            warnings.send(format!(
+                "{dropped} hidden line(s) in {file_name} could not be found in the current file content and are shown again"
+            ));
+        }
+        data_source.mark_loaded();
 
         if load_complete && !data_source.is_empty() {
+            Self::find_previous_run(data_source, path, store, source_id);
             Self::score_lines(data_source, path, toast, start_time, store, source_id);
         } else if data_source.is_empty() {
             toast.set_error("No log lines found in file");
@@ -160,6 +230,50 @@ impl LogFileLoader {
         toast.dismiss();
     }
 
+    /// Cache a summary of the loaded file and hand `store` the latest earlier
+    /// run of the same log, see [`crate::core::run_history`].
+    fn find_previous_run<FT>(
+        data_source: &SourceData<FT>,
+        path: &Path,
+        store: &LogStore,
+        source_id: u64,
+    ) where
+        FT: InputFileType,
+        FT::LineType: Clone,
+    {
+        let Some(history) = store.run_history() else {
+            return;
+        };
+        profiling::scope!("find_previous_run");
+        let timestamps: Vec<i64> = (data_source.first_index()..data_source.len())
+            .filter_map(|idx| data_source.adjusted_timestamp_ms(idx))
+            .collect();
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let (Some(summary), Some(&start_ms)) = (
+            RunSummary::new(&file_name, FT::SLUG, &timestamps),
+            timestamps.iter().min(),
+        ) else {
+            return;
+        };
+        let result = file_hash(path).and_then(|hash| history.record(hash, &summary));
+        match result {
+            Ok(Some(previous)) => {
+                tracing::info!(
+                    "Comparing {} with the earlier run {}",
+                    path.display(),
+                    previous.file_name
+                );
+                store.set_previous_run(PreviousRun {
+                    source_id,
+                    start_ms,
+                    summary: Arc::new(previous),
+                });
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to record the run of {}: {e}", path.display()),
+        }
+    }
+
     /// Score all lines in `data_source` and persist the results.
     ///
     /// Heuristic scoring and sidecar (ML) scoring run in parallel when the
@@ -206,6 +320,24 @@ impl LogFileLoader {
         tracing::info!("Total processing time: {:?}", start_time.elapsed());
     }
 
+    /// Run the heuristic scoring of an already loaded source again in a
+    /// background thread, e.g. after its level rules changed.
+    pub fn spawn_rescore<FT>(
+        data_source: Arc<SourceData<FT>>,
+        toast: ProgressToastHandle,
+        store: Arc<LogStore>,
+    ) where
+        FT: InputFileType + Send + 'static,
+        FT::LineType: Clone,
+    {
+        thread::spawn(move || {
+            let path = data_source.file_path().to_path_buf();
+            let source_id = data_source.source_id();
+            Self::score_heuristic(&data_source, &path, &toast, &store, source_id);
+            toast.dismiss();
+        });
+    }
+
     /// Run the local heuristic scoring pipeline.
     fn score_heuristic<FT>(
         data_source: &Arc<SourceData<FT>>,
@@ -217,21 +349,22 @@ impl LogFileLoader {
         FT: InputFileType,
         FT::LineType: Clone,
     {
-        static N_SKIP_INITIAL: usize = 10;
-
         toast.set_title("Calculating Anomaly Scores");
         toast.update(0.0, "Starting...");
 
         let score_start = std::time::Instant::now();
         let total_lines = data_source.len();
+        // Lines evicted by the retention limit are not scored
+        let first_retained = data_source.first_index();
         tracing::debug!("Starting background anomaly scoring for {total_lines} lines");
 
         let mut scorer = create_default_scorer();
         let mut raw_scores = Vec::new();
+        let eta = Eta::start();
 
         profiling::scope!("score_lines");
 
-        for idx in 0..total_lines {
+        for idx in first_retained..total_lines {
             if idx % 1000 == 0 {
                 if data_source.is_cancelled() {
                     tracing::info!("Anomaly scoring cancelled for {}", path.display());
@@ -239,7 +372,13 @@ impl LogFileLoader {
                     return;
                 }
                 let progress = idx as f32 / total_lines as f32;
-                toast.update(progress, format!("Scoring... ({idx}/{total_lines})"));
+                toast.update(
+                    progress,
+                    format!(
+                        "Scoring... ({idx}/{total_lines}) {}",
+                        eta.progress_text(f64::from(progress))
+                    ),
+                );
             }
 
             let Some(log_line) = data_source.get_as_log_line(idx) else {
@@ -248,7 +387,7 @@ impl LogFileLoader {
                 continue;
             };
 
-            if idx > N_SKIP_INITIAL - 1 {
+            if idx - first_retained >= WARMUP_LINES {
                 raw_scores.push(scorer.score(&log_line));
             }
             scorer.update(&log_line);
@@ -258,7 +397,7 @@ impl LogFileLoader {
 
         profiling::scope!("normalize_scores");
 
-        let normalized_scores = vec![0.0; N_SKIP_INITIAL]
+        let normalized_scores = vec![0.0; first_retained + WARMUP_LINES]
             .into_iter()
             .chain(normalize_scores(&raw_scores))
             .collect::<Vec<f64>>();
@@ -278,7 +417,12 @@ impl LogFileLoader {
             );
         }
 
+        // A removed source must not get its scores back
+        if data_source.is_cancelled() {
+            return;
+        }
         store.set_scores(source_id, &normalized_scores);
+        store.mark_scoring_finished(source_id, false);
 
         let score_duration = score_start.elapsed();
         tracing::info!(
@@ -376,6 +520,7 @@ impl LogFileLoader {
         // Use the streaming variant so scores appear in the UI as GPU batches complete.
         let store_cb = Arc::clone(store);
         let toast_cb = toast.clone();
+        let eta = Eta::start();
         // Incrementally-maintained score vectors — updated in-place each frame
         // rather than rebuilt from scratch.  Building 4 × 2.7M-entry vectors on
         // every frame (O(N) HashMap lookups) was the primary receive bottleneck.
@@ -388,6 +533,9 @@ impl LogFileLoader {
             &norm_versions_ref,
             &input_lines,
             &mut |new_entries, partial_result, total| {
+                if data_source.is_cancelled() {
+                    return;
+                }
                 // Patch only the lines that changed this frame.
                 for (&idx, entry) in new_entries {
                     if idx < total_lines {
@@ -400,9 +548,21 @@ impl LogFileLoader {
                 store_cb.set_sidecar_scores_with_unk(source_id, &raw_scores, &unk_flags, &rare_flags, &scored_flags);
 
                 let progress = partial_result.scored.len() as f32 / total as f32;
-                toast_cb.update(0.1 + progress * 0.9, format!("ML scoring... ({}/{})", partial_result.scored.len(), total));
+                toast_cb.update(
+                    0.1 + progress * 0.9,
+                    format!(
+                        "ML scoring... ({}/{}) {}",
+                        partial_result.scored.len(),
+                        total,
+                        eta.progress_text(f64::from(progress))
+                    ),
+                );
             },
         ) {
+            Ok(_) if data_source.is_cancelled() => {
+                tracing::info!("Sidecar scoring cancelled for {}", path.display());
+                return;
+            }
             Ok((r, session)) => {
                 store.set_explain_session(source_id, session);
                 r
@@ -439,6 +599,7 @@ impl LogFileLoader {
             path.display()
         );
         store.set_sidecar_scores_with_unk(source_id, &raw_scores, &unk_flags, &rare_flags, &scored_flags);
+        store.mark_scoring_finished(source_id, true);
         toast.update(1.0, "ML scoring done!");
     }
 }
@@ -16,28 +16,48 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::core::session::{CrabFile, SessionError, CRAB_FILE_VERSION};
+use crate::core::chunk_index::{ScanHints, TrigramBloom};
+use crate::core::column_filter::{ColumnFilter, COMPONENT_FACETS, SOURCE_COLUMN};
+use crate::core::crab_lock::{OwnerRecord, Takeover};
+use crate::core::crab_writer::CrabWriter;
+use crate::core::field_query::FieldTerm;
+use crate::core::level_rule::{LevelRemap, LevelRule, LEVEL_COLUMNS};
+use crate::core::line_anchor::{content_hash, resolve_anchors, LineAnchor};
+use crate::core::line_buffer::{LineBuffer, CHUNK_LINES};
+use crate::core::permalink::Permalink;
+use crate::core::run_history::{PreviousRun, RunHistory};
+use crate::core::segment::{self, Segment};
+use crate::core::session::{
+    Activity, ActivityEntry, CrabFile, DeferredBookmarks, ImportedSession, SessionError,
+    Suppression, TemplateFeedback, TemplateVerdict, CRAB_FILE_VERSION,
+};
 use crate::core::{SavedFilter, SavedHighlight};
 use crate::filetype::{
-    btsnoop::BtsnoopFileType, bugreport::BugreportFileType, dlt::DltFileType, dmesg::DmesgFileType,
-    generic::GenericFileType, logcat::LogcatFileType, otel::OtelFileType, pcap::PcapFileType,
+    access_log::AccessLogFileType, btsnoop::BtsnoopFileType, bugreport::BugreportFileType,
+    csv::CsvFileType, dlt::DltFileType, dmesg::DmesgFileType, generic::GenericFileType,
+    journal::JournalFileType, json::JsonFileType, logcat::LogcatFileType, otel::OtelFileType,
+    pcap::PcapFileType, syslog::SyslogFileType, trace::TraceFileType,
+};
+use crate::filetype::{
+    access_log::AccessLogLine, btsnoop::BtsnoopLogLine, bugreport::BugreportLogLine,
+    csv::CsvLogLine, dlt::DltLogLine, dmesg::DmesgLogLine, generic::GenericLogLine,
+    journal::JournalLogLine, json::JsonLogLine, logcat::LogcatLogLine, otel::OtelLogLine,
+    pcap::PcapLogLine, syslog::SyslogLogLine, trace::TraceLogLine,
 };
 use crate::filetype::{
-    btsnoop::BtsnoopLogLine, bugreport::BugreportLogLine, dlt::DltLogLine, dmesg::DmesgLogLine,
-    generic::GenericLogLine, logcat::LogcatLogLine, otel::OtelLogLine, pcap::PcapLogLine,
+    InputFileType, LineType, LogFileState, RequestMetrics, Severity, NANOS_PER_MS,
 };
-use crate::filetype::{InputFileType, LineType, LogFileState};
 use crate::ui::tabs::bookmarks_tab::BookmarkData;
 use chrono::Local;
 use egui;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{hash_map, BTreeMap, HashMap};
 use std::fs::File;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::{Arc, Mutex, RwLock};
 
 use arc_swap::ArcSwap;
@@ -47,6 +67,13 @@ use dashmap::DashMap;
 /// Source IDs are stable across the lifetime of a source, even when other sources are removed.
 static SOURCE_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+/// Bookmarks read from the `.crab` file and moved into a source at a time.
+///
+/// The background loader reads them once the lines are in, releasing the
+/// bookmarks lock between batches, so sessions with tens of thousands of
+/// bookmarks neither hold up opening the file nor stall the UI.
+const BOOKMARK_BATCH: usize = 1000;
+
 /// Lock-free storage for anomaly scores.
 ///
 /// Uses `ArcSwap` for atomic pointer swaps — readers never block, and writers
@@ -65,6 +92,9 @@ pub struct ScoreStore {
     /// Whether each line was actually present in the sidecar's scored set.
     /// `false` means the line was filtered/excluded by the backend (not in corpus).
     scored_flags: ArcSwap<Vec<bool>>,
+    /// Scores fixed by the user's template feedback, by line index. They take
+    /// precedence over `scores` and survive re-scoring.
+    overrides: ArcSwap<HashMap<usize, f64>>,
 }
 
 impl ScoreStore {
@@ -75,6 +105,7 @@ impl ScoreStore {
             unk_flags: ArcSwap::new(Arc::new(Vec::new())),
             rare_flags: ArcSwap::new(Arc::new(Vec::new())),
             scored_flags: ArcSwap::new(Arc::new(Vec::new())),
+            overrides: ArcSwap::new(Arc::new(HashMap::new())),
         }
     }
 
@@ -91,8 +122,16 @@ impl ScoreStore {
         self.scored_flags.store(Arc::new(scored_flags.to_vec()));
     }
 
+    /// Replace the scores fixed by template feedback
+    pub fn set_overrides(&self, overrides: HashMap<usize, f64>) {
+        self.overrides.store(Arc::new(overrides));
+    }
+
     /// Get the score for a specific line index. Returns 0.0 if out of bounds.
     pub fn get(&self, index: usize) -> f64 {
+        if let Some(&score) = self.overrides.load().get(&index) {
+            return score;
+        }
         let guard = self.scores.load();
         guard.get(index).copied().unwrap_or(0.0)
     }
@@ -115,6 +154,18 @@ impl ScoreStore {
         guard.get(index).copied().unwrap_or(false)
     }
 
+    /// Indices of the lines scoring above `threshold`, in line order.
+    pub fn indices_above(&self, threshold: f64) -> Vec<usize> {
+        let guard = self.scores.load();
+        let overrides = self.overrides.load();
+        guard
+            .iter()
+            .enumerate()
+            .filter(|&(index, &score)| overrides.get(&index).copied().unwrap_or(score) > threshold)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     /// Resize the internal vec to accommodate new lines (fills with 0.0).
     /// Called when lines are appended to keep scores in sync.
     pub fn resize(&self, new_len: usize) {
@@ -140,6 +191,7 @@ impl Clone for ScoreStore {
             unk_flags: ArcSwap::new(Arc::clone(&self.unk_flags.load())),
             rare_flags: ArcSwap::new(Arc::clone(&self.rare_flags.load())),
             scored_flags: ArcSwap::new(Arc::clone(&self.scored_flags.load())),
+            overrides: ArcSwap::new(Arc::clone(&self.overrides.load())),
         }
     }
 }
@@ -151,7 +203,157 @@ impl std::fmt::Debug for ScoreStore {
     }
 }
 
-/// A single log source with its lines, wrapped in `RwLock` for thread-safe access
+/// Per-column value dictionary: column name → observed value → number of lines.
+pub type FacetCounts = BTreeMap<&'static str, HashMap<String, usize>>;
+
+/// Lines of a source together with their timestamp order.
+///
+/// Published as a whole through `ArcSwap`: writers build the next snapshot
+/// and swap it in, so readers never block on the loader and always see an
+/// index that matches the lines they hold.
+#[derive(Debug)]
+struct LineSnapshot<T> {
+    /// Log lines in file order (index = `line_number` - 1, eternal even when
+    /// the oldest lines are evicted)
+    lines: LineBuffer<T>,
+    /// Indices into `lines`, sorted by timestamp for time-ordered iteration
+    by_timestamp: Arc<Vec<usize>>,
+    /// Earliest and latest adjusted timestamp (ms) of every chunk, aligned
+    /// with `lines.chunk_ids()`. Lets time-scoped searches skip whole chunks.
+    chunk_ranges: Arc<Vec<(i64, i64)>>,
+    /// Trigram bloom of every chunk's display messages and raw text, aligned
+    /// with `lines.chunk_ids()`. Lets literal searches skip whole chunks.
+    chunk_blooms: Arc<Vec<Arc<TrigramBloom>>>,
+    /// [`LogFileState::display_generation`] the blooms were built at; they
+    /// are stale once it moved.
+    bloom_generation: u64,
+    /// Lines before this index are in `chunk_blooms`. The newest chunk's
+    /// bloom is extended in batches of [`BLOOM_BATCH`] lines, so small
+    /// appends do not copy it each time.
+    bloomed_to: usize,
+}
+
+/// Lines appended to the newest chunk before its bloom is extended
+const BLOOM_BATCH: usize = 4096;
+
+impl<T> Default for LineSnapshot<T> {
+    fn default() -> Self {
+        Self {
+            lines: LineBuffer::new(),
+            by_timestamp: Arc::new(Vec::new()),
+            chunk_ranges: Arc::new(Vec::new()),
+            chunk_blooms: Arc::new(Vec::new()),
+            bloom_generation: 0,
+            bloomed_to: 0,
+        }
+    }
+}
+
+impl<T> LineSnapshot<T> {
+    /// Which chunks may hold a line matching a search with `hints`, aligned
+    /// with `lines.chunk_ids()`. `None` when the hints rule out nothing.
+    ///
+    /// The literal is ignored while the blooms predate `display_generation`.
+    fn live_chunks(&self, hints: &ScanHints, display_generation: u64) -> Option<Vec<bool>> {
+        let literal = hints
+            .literal
+            .as_deref()
+            .filter(|_| self.bloom_generation == display_generation);
+        if hints.time_range.is_none() && literal.is_none() {
+            return None;
+        }
+        let first_chunk = self.lines.chunk_ids().start;
+        let live = (0..self.chunk_ranges.len())
+            .map(|chunk| {
+                hints.time_range.is_none_or(|(start_ms, end_ms)| {
+                    let (min, max) = self.chunk_ranges[chunk];
+                    min <= end_ms && start_ms <= max
+                }) && literal.is_none_or(|literal| {
+                    let end = (first_chunk + chunk + 1) * CHUNK_LINES;
+                    end.min(self.lines.len()) > self.bloomed_to
+                        || self
+                            .chunk_blooms
+                            .get(chunk)
+                            .is_none_or(|bloom| bloom.might_contain(literal))
+                })
+            })
+            .collect();
+        Some(live)
+    }
+}
+
+/// Widen the per-chunk time ranges in `ranges` by the lines at `indices`.
+///
+/// `ranges` is aligned with `lines.chunk_ids()`; chunks not covered yet are
+/// appended.
+fn extend_chunk_ranges<T>(
+    ranges: &mut Vec<(i64, i64)>,
+    lines: &LineBuffer<T>,
+    indices: std::ops::Range<usize>,
+    key: impl Fn(usize) -> i64,
+) {
+    let first_chunk = lines.chunk_ids().start;
+    for idx in indices {
+        let ts = key(idx);
+        let chunk = LineBuffer::<T>::chunk_id(idx) - first_chunk;
+        if let Some((min, max)) = ranges.get_mut(chunk) {
+            *min = (*min).min(ts);
+            *max = (*max).max(ts);
+        } else {
+            ranges.push((ts, ts));
+        }
+    }
+}
+
+/// Add the text of the lines at `indices` to the per-chunk blooms in
+/// `blooms`.
+///
+/// `blooms` is aligned with `lines.chunk_ids()`; chunks not covered yet are
+/// appended. Touched blooms are copied, so older snapshots keep theirs.
+fn extend_chunk_blooms<T>(
+    blooms: &mut Vec<Arc<TrigramBloom>>,
+    lines: &LineBuffer<T>,
+    indices: std::ops::Range<usize>,
+    insert: impl Fn(usize, &mut TrigramBloom) + Sync,
+) {
+    profiling::scope!("extend_chunk_blooms");
+    let first_chunk = lines.chunk_ids().start;
+    let mut start = indices.start;
+    while start < indices.end {
+        let chunk_id = LineBuffer::<T>::chunk_id(start);
+        let end = indices.end.min((chunk_id + 1) * CHUNK_LINES);
+        let added = (start..end)
+            .into_par_iter()
+            .with_min_len(BLOOM_BATCH)
+            .fold(TrigramBloom::new, |mut bloom, idx| {
+                insert(idx, &mut bloom);
+                bloom
+            })
+            .reduce(TrigramBloom::new, |mut a, b| {
+                a.union_with(&b);
+                a
+            });
+        let chunk = chunk_id - first_chunk;
+        if let Some(bloom) = blooms.get_mut(chunk) {
+            Arc::make_mut(bloom).union_with(&added);
+        } else {
+            blooms.push(Arc::new(added));
+        }
+        start = end;
+    }
+}
+
+/// State of a source opened while another instance held its `.crab` lock
+struct Contention {
+    /// Hash of the `.crab` file as read when the source was opened; `None`
+    /// if it could not be read
+    seen: Option<u64>,
+    /// A save was skipped, so this source has changes the file lacks
+    unsaved: bool,
+}
+
+/// A single log source with its lines, readable without blocking while the
+/// loader appends
 pub struct SourceData<FT>
 where
     FT: InputFileType,
@@ -160,10 +362,10 @@ where
     source_id: u64,
     /// Path to the source file
     file_path: PathBuf,
-    /// Log lines in file order (index = `line_number` - 1, eternal)
-    lines: RwLock<Vec<FT::LineType>>,
-    /// Indices into `lines`, sorted by timestamp for time-ordered iteration
-    by_timestamp: RwLock<Vec<usize>>,
+    /// Lines and timestamp index, replaced as a whole on every change
+    snapshot: ArcSwap<LineSnapshot<FT::LineType>>,
+    /// Serializes writers of `snapshot`; readers never take it
+    write_lock: Mutex<()>,
     /// File type config — shared across all sources of this type (e.g. DLT timestamp source setting).
     /// Wrapped in `Arc<RwLock>` so a single instance is shared and can be mutated from the UI.
     pub config: Arc<RwLock<<FT::LineType as LineType>::Config>>,
@@ -175,17 +377,57 @@ where
     pub file_state: Arc<<FT::LineType as LineType>::FileState>,
     /// Bookmarks for this source, keyed by line index within this source
     bookmarks: RwLock<HashMap<usize, Bookmark>>,
+    /// Bookmarks of the `.crab` file not read into `bookmarks` yet, see
+    /// [`Self::insert_pending_bookmarks`]
+    pending_bookmarks: Mutex<Option<Arc<DeferredBookmarks>>>,
+    /// How many of `pending_bookmarks` are in `bookmarks` already; only
+    /// changed while holding the `bookmarks` lock
+    pending_inserted: AtomicUsize,
+    /// Set if the bookmarks of the `.crab` file could not be read; saving is
+    /// refused then, as it would drop them from the file
+    unreadable_bookmarks: AtomicBool,
+    /// Lines and templates hidden from all views, oldest first
+    suppressions: RwLock<Vec<Suppression>>,
+    /// Index of the first line and [`content_hash`] of the normalized message
+    /// of each line from there on, filled lazily for template suppressions
+    template_hashes: Mutex<(usize, Arc<Vec<u64>>)>,
+    /// Templates the user judged expected or important, oldest first
+    template_feedback: RwLock<Vec<TemplateFeedback>>,
+    /// Rules correcting the level of lines, see [`crate::core::level_rule`]
+    level_remap: RwLock<LevelRemap>,
+    /// Activity log of this source, oldest first
+    activity: RwLock<Vec<ActivityEntry>>,
+    /// Virtual segments of this source, ordered by start line
+    segments: RwLock<Vec<Segment>>,
+    /// Value dictionary of the discrete columns (see [`LineType::facets`]),
+    /// maintained incrementally in `append_lines`
+    facet_counts: RwLock<FacetCounts>,
     /// Path to the `.crab` session file (immutable after construction).
     crab_path: PathBuf,
     /// OS exclusive lock on the `.crab` session file.
     ///
-    /// `None` — lock released because the file was written by a newer `LogCrab`;
-    ///           all reads and writes are refused.
-    /// `Some(mutex)` — lock held; mutex provides `&mut File` for writes.
-    crab: Option<Mutex<File>>,
+    /// `None` — lock held by another instance (see `contention`), or released
+    ///           because the file was written by a newer `LogCrab`; all writes
+    ///           are refused.
+    /// `Some(mutex)` — lock held; mutex provides `&mut File` for writes. Shared
+    ///                with the [`CrabWriter`], which swaps in the replacement file.
+    crab: RwLock<Option<Arc<Mutex<File>>>>,
+    /// Set while another instance holds the lock, until this source takes it
+    /// over (see [`Self::take_over_crab`])
+    contention: Mutex<Option<Contention>>,
+    /// Entry in the advisory lock file while this source holds the lock
+    owner_record: Mutex<Option<OwnerRecord>>,
     version: AtomicU64,
     /// Flag to request cancellation of background loading/scoring operations
     cancel_requested: AtomicBool,
+    /// Set once the background loader has read the whole file (or given up)
+    loaded: AtomicBool,
+    /// Maximum number of lines kept in memory (0 = unlimited), see [`Self::set_retention_limit`]
+    retention_limit: AtomicUsize,
+    /// Set for endless live sources, the only ones the retention limit applies to
+    live: AtomicBool,
+    /// Reports bookmarks and suppressions dropped with evicted lines
+    warnings: crate::ui::ToastSender,
 }
 
 impl<FT: InputFileType> std::fmt::Debug for SourceData<FT> {
@@ -210,8 +452,9 @@ where
     /// highlights are returned to the caller so they never need to be stored.
     ///
     /// If the lock is already held by another instance the file is opened in
-    /// **read-only mode**: bookmarks and saved state are not loaded, and writes
-    /// are silently skipped for the lifetime of this source.
+    /// **read-only mode**: the session is read as that instance last saved it,
+    /// and writes are skipped until this source takes the lock over (see
+    /// [`Self::take_over_crab`]).
     ///
     /// Returns `(Self, saved_filters, saved_highlights)`.
     pub fn new(
@@ -226,49 +469,97 @@ where
         );
 
         let crab_path = Self::compute_crab_path(&file_path);
-        let (lock_file, maybe_crab) = Self::acquire_crab_lock(&crab_path).map_or_else(
+        let (lock_file, maybe_crab, contention) = Self::acquire_crab_lock(&crab_path).map_or_else(
             || {
                 tracing::warn!(
                     "Cannot lock {} — opening read-only (file already open in another instance)",
                     crab_path.display()
                 );
+                let owner = crate::core::crab_lock::read_owner(&crab_path)
+                    .map_or_else(String::new, |owner| format!(" ({owner})"));
                 warnings.send(format!(
-                    "'{}' is already open in another LogCrab instance — \
-                    opened read-only (bookmarks and filters not loaded)",
+                    "'{}' is already open in another LogCrab instance{owner} — \
+                    opened read-only; your changes are saved once it closes",
                     file_path
                         .file_name()
                         .unwrap_or(file_path.as_os_str())
                         .to_string_lossy()
                 ));
-                (None, None)
+                let (maybe_crab, seen) = Self::read_locked_session(&crab_path);
+                (
+                    None,
+                    maybe_crab,
+                    Some(Contention {
+                        seen,
+                        unsaved: false,
+                    }),
+                )
+            },
+            |lock_file| {
+                let (lock_file, maybe_crab) = Self::open_crab_file(lock_file, &crab_path, warnings);
+                (lock_file, maybe_crab, None)
             },
-            |lock_file| Self::open_crab_file(lock_file, &crab_path, warnings),
         );
+        let owner_record = lock_file
+            .as_ref()
+            .and_then(|_| OwnerRecord::write(&crab_path));
 
         // Consume the parsed CrabFile immediately — apply bookmarks/file_state
         // here and return filters/highlights to the caller so nothing lingers.
-        let (filters, highlights, bookmarks_vec, file_state_arc) = match maybe_crab {
+        let (
+            filters,
+            highlights,
+            bookmarks_vec,
+            pending_bookmarks,
+            file_state_arc,
+            suppressions,
+            template_feedback,
+            level_rules,
+            activity,
+            segments,
+        ) = match maybe_crab {
             Some(crab) => {
                 tracing::info!(
-                    "Loaded {} bookmarks from {}",
-                    crab.bookmarks.len(),
+                    "Loaded {} from {}",
+                    if crab.deferred_bookmarks.is_some() {
+                        "session (bookmarks follow)".to_string()
+                    } else {
+                        format!("{} bookmarks", crab.bookmarks.len())
+                    },
                     crab_path.display()
                 );
                 (
                     crab.filters,
                     crab.highlights,
                     crab.bookmarks,
+                    crab.deferred_bookmarks.map(Arc::new),
                     Arc::new(crab.file_state),
+                    crab.suppressions,
+                    crab.template_feedback,
+                    crab.level_rules,
+                    crab.activity,
+                    crab.segments,
                 )
             }
-            None => (vec![], vec![], vec![], Arc::new(Default::default())),
+            None => (
+                vec![],
+                vec![],
+                vec![],
+                None,
+                Arc::new(Default::default()),
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+            ),
         };
 
         let sd = Self {
             source_id: SOURCE_ID_COUNTER.fetch_add(1, AtomicOrdering::Relaxed),
             file_path,
-            lines: RwLock::new(Vec::new()),
-            by_timestamp: RwLock::new(Vec::new()),
+            snapshot: ArcSwap::from_pointee(LineSnapshot::default()),
+            write_lock: Mutex::new(()),
             config,
             file_state: file_state_arc,
             bookmarks: RwLock::new(
@@ -277,10 +568,26 @@ where
                     .map(|b| (b.line_index, b))
                     .collect(),
             ),
+            pending_bookmarks: Mutex::new(pending_bookmarks),
+            pending_inserted: AtomicUsize::new(0),
+            unreadable_bookmarks: AtomicBool::new(false),
+            suppressions: RwLock::new(suppressions),
+            template_hashes: Mutex::default(),
+            template_feedback: RwLock::new(template_feedback),
+            level_remap: RwLock::new(LevelRemap::new(level_rules)),
+            activity: RwLock::new(activity),
+            segments: RwLock::new(segments),
+            facet_counts: RwLock::new(FacetCounts::new()),
             crab_path,
-            crab: lock_file.map(Mutex::new),
+            crab: RwLock::new(lock_file.map(|file| Arc::new(Mutex::new(file)))),
+            contention: Mutex::new(contention),
+            owner_record: Mutex::new(owner_record),
             version: AtomicU64::new(1),
             cancel_requested: AtomicBool::new(false),
+            loaded: AtomicBool::new(false),
+            retention_limit: AtomicUsize::new(0),
+            live: AtomicBool::new(false),
+            warnings: warnings.clone(),
         };
         (sd, filters, highlights)
     }
@@ -290,14 +597,29 @@ where
     /// Returns `(Some(file), Some(data))` on success.
     /// Returns `(Some(file), None)` when the file is empty or unparseable.
     /// Returns `(None, None)` on `VersionTooNew`, releasing the OS lock.
+    ///
+    /// Files from an older format are upgraded in memory; the original is kept
+    /// as `<name>.crab.v<N>.bak` because the next save writes the new format,
+    /// which older releases of `LogCrab` cannot read.
+    ///
+    /// Changes journaled but not yet saved when the app last exited (see
+    /// [`crate::core::crab_writer`]) take precedence over the file.
     fn open_crab_file(
         file: File,
         crab_path: &Path,
         warnings: &crate::ui::ToastSender,
     ) -> (Option<File>, Option<CrabFile<FT>>) {
         let mut file = file;
+        if let Some(data) = Self::recover_journal(crab_path, warnings) {
+            return (Some(file), Some(data));
+        }
         match CrabFile::<FT>::load_from_file(&mut file) {
-            Ok(data) => (Some(file), Some(data)),
+            Ok(data) => {
+                if data.version < CRAB_FILE_VERSION {
+                    Self::backup_crab_file(&mut file, crab_path, data.version);
+                }
+                (Some(file), Some(data))
+            }
             Err(SessionError::Io(ref e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
                 (Some(file), None) // Empty .crab file (just created)
             }
@@ -329,6 +651,17 @@ where
                 // older format, silently destroying the user's calibration.
                 (None, None)
             }
+            Err(SessionError::NoUpgradePath { from }) => {
+                let msg = format!(
+                    ".crab file {} uses format v{from}, which this LogCrab cannot upgrade; \
+                     bookmarks and file state not loaded",
+                    crab_path.display()
+                );
+                tracing::warn!("{msg}");
+                warnings.send(msg);
+                // Drop `file` here so the unreadable session is not overwritten.
+                (None, None)
+            }
             Err(e) => {
                 tracing::warn!("Failed to load .crab file {}: {e}", crab_path.display());
                 (Some(file), None)
@@ -336,6 +669,54 @@ where
         }
     }
 
+    /// The newest readable snapshot in the journal of `crab_path`, if the
+    /// last session ended before saving it.
+    fn recover_journal(
+        crab_path: &Path,
+        warnings: &crate::ui::ToastSender,
+    ) -> Option<CrabFile<FT>> {
+        let data = crate::core::crab_writer::read_journal(crab_path)
+            .iter()
+            .rev()
+            .find_map(|json| CrabFile::<FT>::from_json_deferred(json).ok())?;
+        let msg = format!(
+            "Recovered unsaved bookmarks and filters of {} from its journal",
+            crab_path.display()
+        );
+        tracing::info!("{msg}");
+        warnings.send(msg);
+        Some(data)
+    }
+
+    /// Copy an outdated `.crab` file to `<name>.crab.v<version>.bak`.
+    ///
+    /// Reads through the locked handle, as other handles cannot read a locked
+    /// file on every platform.
+    fn backup_crab_file(file: &mut File, crab_path: &Path, version: u32) {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut backup_name = crab_path.file_name().unwrap_or_default().to_os_string();
+        backup_name.push(format!(".v{version}.bak"));
+        let backup_path = crab_path.with_file_name(backup_name);
+
+        let mut content = Vec::new();
+        let result = file
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| file.read_to_end(&mut content))
+            .and_then(|_| std::fs::write(&backup_path, &content));
+        match result {
+            Ok(()) => tracing::info!(
+                "Upgraded .crab file {} from v{version}, original kept as {}",
+                crab_path.display(),
+                backup_path.display()
+            ),
+            Err(e) => tracing::warn!(
+                "Failed to back up .crab file {} before upgrading: {e}",
+                crab_path.display()
+            ),
+        }
+    }
+
     /// Compute the .crab file path for a given log file path
     fn compute_crab_path(file_path: &Path) -> PathBuf {
         let mut crab_path = file_path.to_path_buf();
@@ -352,27 +733,8 @@ where
     /// Acquire an exclusive lock on the .crab file
     /// Returns None if the lock cannot be acquired (file already open in another instance)
     fn acquire_crab_lock(crab_path: &Path) -> Option<File> {
-        use fs2::FileExt;
-        use std::fs::OpenOptions;
-
-        // Open or create the .crab file
-        let file = match OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(crab_path)
-        {
-            Ok(f) => f,
-            Err(e) => {
-                tracing::error!("Cannot open .crab file {}: {e}", crab_path.display());
-                return None;
-            }
-        };
-
-        // Try to acquire exclusive lock
-        match file.try_lock_exclusive() {
-            Ok(()) => {
+        match Self::try_lock_crab(crab_path) {
+            Ok(file) => {
                 tracing::info!(
                     "Successfully acquired exclusive lock on {}",
                     crab_path.display()
@@ -389,6 +751,161 @@ where
         }
     }
 
+    /// Open or create the .crab file and try to lock it exclusively
+    ///
+    /// Saves rename a new file over the `.crab` path (see
+    /// [`crate::core::crab_writer`]), so a handle opened just before a save
+    /// can be locked once the writer lets go of the replaced file. Such a lock
+    /// guards nothing; the handle is only kept if it is still the file at
+    /// `crab_path`, otherwise the path is opened again.
+    fn try_lock_crab(crab_path: &Path) -> std::io::Result<File> {
+        use fs2::FileExt;
+
+        /// A save replacing the file on every attempt means it is in use
+        const ATTEMPTS: usize = 3;
+
+        for _ in 0..ATTEMPTS {
+            let file = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(crab_path)?;
+            file.try_lock_exclusive()?;
+            if Self::is_file_at(&file, crab_path)? {
+                return Ok(file);
+            }
+            tracing::debug!(
+                "{} was replaced while locking it, retrying",
+                crab_path.display()
+            );
+        }
+        Err(std::io::Error::new(
+            std::io::ErrorKind::WouldBlock,
+            "the .crab file is being replaced by another instance",
+        ))
+    }
+
+    /// Whether `file` is the file currently at `path`, not one renamed over
+    #[cfg(unix)]
+    fn is_file_at(file: &File, path: &Path) -> std::io::Result<bool> {
+        use std::os::unix::fs::MetadataExt;
+
+        let opened = file.metadata()?;
+        match std::fs::metadata(path) {
+            Ok(current) => Ok(opened.dev() == current.dev() && opened.ino() == current.ino()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Windows does not rename over an open file, so saves there rewrite the
+    /// locked file in place and the handle always stays the file at `path`.
+    #[cfg(not(unix))]
+    #[allow(clippy::unnecessary_wraps)]
+    fn is_file_at(_file: &File, _path: &Path) -> std::io::Result<bool> {
+        Ok(true)
+    }
+
+    /// Read the session another instance holds the lock on.
+    ///
+    /// Changes that instance journaled but has not saved yet count as its
+    /// session, as in [`Self::take_over_crab`]. Returns the parsed session, if
+    /// readable, and the hash of the content as read. Platforms that refuse
+    /// reads of a locked file give `(None, None)` unless there is a journal.
+    fn read_locked_session(crab_path: &Path) -> (Option<CrabFile<FT>>, Option<u64>) {
+        let journaled = crate::core::crab_writer::read_journal(crab_path).pop();
+        let Some(content) = journaled.or_else(|| std::fs::read_to_string(crab_path).ok()) else {
+            return (None, None);
+        };
+        let data = CrabFile::<FT>::from_json_deferred(&content)
+            .inspect_err(|e| {
+                tracing::debug!("Not loading locked {}: {e}", crab_path.display());
+            })
+            .ok();
+        (data, Some(crate::core::crab_lock::content_hash(&content)))
+    }
+
+    /// Whether another instance held the `.crab` lock when this source was
+    /// opened and has not released it yet
+    pub fn is_crab_contended(&self) -> bool {
+        self.contention
+            .lock()
+            .expect("contention lock poisoned")
+            .is_some()
+    }
+
+    /// Take over the `.crab` lock once the instance that held it released it.
+    ///
+    /// Returns `None` while the lock is still held, or if this source never
+    /// waited for it. A session the other instance left behind in its journal
+    /// (see [`crate::core::crab_writer`]) counts as its latest save. A file
+    /// written by a newer `LogCrab` is not taken over, so it stays read-only.
+    pub fn take_over_crab(&self) -> Option<Takeover> {
+        use std::io::Read;
+
+        let mut contention = self.contention.lock().expect("contention lock poisoned");
+        if contention.is_none() {
+            return None;
+        }
+        let mut file = Self::try_lock_crab(&self.crab_path).ok()?;
+        let Contention { seen, unsaved } = contention.take()?;
+        drop(contention);
+
+        let mut content = String::new();
+        if let Err(e) = file.read_to_string(&mut content) {
+            tracing::warn!("Failed to read {}: {e}", self.crab_path.display());
+        }
+        if let Some(journaled) = crate::core::crab_writer::read_journal(&self.crab_path).pop() {
+            content = journaled;
+        }
+        if let Err(
+            e @ (SessionError::VersionTooNew { .. }
+            | SessionError::StateVersionTooNew { .. }
+            | SessionError::NoUpgradePath { .. }),
+        ) = CrabFile::<FT>::from_json(&content)
+        {
+            tracing::warn!(
+                "Not taking over {}, it stays read-only: {e}",
+                self.crab_path.display()
+            );
+            return None;
+        }
+
+        let changed = (Some(crate::core::crab_lock::content_hash(&content)) != seen)
+            .then(|| ImportedSession::from_json(&content).ok())
+            .flatten();
+        tracing::info!(
+            "Took over the lock on {} (changed on disk: {}, unsaved changes: {unsaved})",
+            self.crab_path.display(),
+            changed.is_some()
+        );
+        *self.crab.write().expect("crab lock poisoned") = Some(Arc::new(Mutex::new(file)));
+        *self
+            .owner_record
+            .lock()
+            .expect("owner record lock poisoned") = OwnerRecord::write(&self.crab_path);
+        Some(Takeover {
+            source_id: self.source_id,
+            crab_path: self.crab_path.clone(),
+            changed,
+            unsaved,
+        })
+    }
+
+    /// Give up the `.crab` lock so the file can be opened again right away.
+    ///
+    /// Called when the source leaves the store, as the loader or a scorer may
+    /// hold on to it a little longer. Saves still queued keep the handle until
+    /// they are written (see [`LogStore::flush_crab_files`]).
+    pub(crate) fn release_crab(&self) {
+        *self.crab.write().expect("crab lock poisoned") = None;
+        self.owner_record
+            .lock()
+            .expect("owner record lock poisoned")
+            .take();
+    }
+
     /// Bump the version number (call after appending lines)
     fn bump_version(&self) {
         self.version.fetch_add(1, AtomicOrdering::SeqCst);
@@ -415,18 +932,245 @@ where
         self.cancel_requested.load(AtomicOrdering::SeqCst)
     }
 
+    /// Ask the background loader and scorers to stop, e.g. because the
+    /// source was removed from the store
+    pub fn request_cancel(&self) {
+        self.cancel_requested.store(true, AtomicOrdering::SeqCst);
+    }
+
+    /// Mark the background load as finished (called by the loader thread)
+    pub(crate) fn mark_loaded(&self) {
+        self.flush_blooms();
+        self.loaded.store(true, AtomicOrdering::SeqCst);
+    }
+
+    /// Whether the background loader has finished reading the file
+    pub fn is_loaded(&self) -> bool {
+        self.loaded.load(AtomicOrdering::SeqCst)
+    }
+
+    /// Mark this source as endless live source (e.g. the followed system
+    /// journal), which the retention limit applies to
+    pub(crate) fn mark_live(&self) {
+        self.live.store(true, AtomicOrdering::SeqCst);
+    }
+
+    /// Whether this is an endless live source, see [`Self::mark_live`]
+    pub fn is_live(&self) -> bool {
+        self.live.load(AtomicOrdering::SeqCst)
+    }
+
     // ========================================================================
     // Bookmark Management
     // ========================================================================
 
-    /// Add or update a bookmark for a line in this source
+    /// Add or update a bookmark for a line in this source, keeping the
+    /// category and attachments of an existing bookmark
     pub(crate) fn set_bookmark(&self, line_index: usize, name: String) {
         profiling::scope!("SourceData::bookmarks::write");
-        let bookmark = Bookmark { line_index, name };
+        let anchor = self.line_anchor(line_index);
+        let mut bookmarks = self.bookmarks.write().expect("bookmarks lock poisoned");
+        let (category, attachments) = bookmarks
+            .remove(&line_index)
+            .map(|old| (old.category, old.attachments))
+            .unwrap_or_default();
+        let bookmark = Bookmark {
+            line_index,
+            name,
+            category,
+            anchor,
+            attachments,
+            orphaned: false,
+        };
+        bookmarks.insert(line_index, bookmark);
+    }
+
+    /// Set the category of the bookmark at `line_index`
+    pub(crate) fn set_bookmark_category(&self, line_index: usize, category: String) {
+        profiling::scope!("SourceData::bookmarks::write");
+        if let Some(bookmark) = self
+            .bookmarks
+            .write()
+            .expect("bookmarks lock poisoned")
+            .get_mut(&line_index)
+        {
+            bookmark.category = category;
+        }
+    }
+
+    /// Replace the attachment list of the bookmark at `line_index`
+    pub(crate) fn set_bookmark_attachments(&self, line_index: usize, attachments: Vec<String>) {
+        profiling::scope!("SourceData::bookmarks::write");
+        if let Some(bookmark) = self
+            .bookmarks
+            .write()
+            .expect("bookmarks lock poisoned")
+            .get_mut(&line_index)
+        {
+            bookmark.attachments = attachments;
+        }
+    }
+
+    /// Folder the attachments of this source's bookmarks are stored in
+    pub fn attachments_dir(&self) -> PathBuf {
+        crate::core::attachments::attachments_dir(&self.crab_path)
+    }
+
+    /// Move bookmarks to the line matching their anchor.
+    ///
+    /// Called once the file has been fully loaded: if lines were added before
+    /// or removed since the bookmark was saved, its stored index points at a
+    /// different line. Bookmarks whose line cannot be found stay where they
+    /// are and are flagged `orphaned`. A bookmark moved onto a line that is
+    /// bookmarked already is merged into that bookmark (see
+    /// [`Bookmark::absorb`]). Returns the number of orphaned bookmarks.
+    pub(crate) fn reanchor_bookmarks(&self) -> usize {
+        profiling::scope!("SourceData::reanchor_bookmarks");
+        let anchored: Vec<(usize, LineAnchor)> = self
+            .get_bookmarks()
+            .iter()
+            .filter_map(|b| b.anchor.map(|anchor| (b.line_index, anchor)))
+            .collect();
+        if anchored.is_empty() {
+            return 0;
+        }
+        let resolved = self.resolve_anchors(&anchored);
+
+        let mut bookmarks = self.bookmarks.write().expect("bookmarks lock poisoned");
+        let mut moved = Vec::new();
+        let mut orphaned = 0;
+        for ((old_index, _), new_index) in anchored.iter().zip(resolved) {
+            match new_index {
+                Some(new_index) if new_index == *old_index => {}
+                Some(new_index) => {
+                    if let Some(mut bookmark) = bookmarks.remove(old_index) {
+                        bookmark.line_index = new_index;
+                        moved.push(bookmark);
+                    }
+                }
+                None => {
+                    if let Some(bookmark) = bookmarks.get_mut(old_index) {
+                        bookmark.orphaned = true;
+                        orphaned += 1;
+                    }
+                }
+            }
+        }
+        if !moved.is_empty() {
+            tracing::info!(
+                "Re-anchored {} bookmarks in {}",
+                moved.len(),
+                self.file_path.display()
+            );
+        }
+        for bookmark in moved {
+            match bookmarks.entry(bookmark.line_index) {
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(bookmark);
+                }
+                hash_map::Entry::Occupied(mut entry) => {
+                    tracing::info!(
+                        "Merged bookmark '{}' into '{}' at line {} of {}",
+                        bookmark.name,
+                        entry.get().name,
+                        bookmark.line_index,
+                        self.file_path.display()
+                    );
+                    entry.get_mut().absorb(bookmark);
+                }
+            }
+        }
+        drop(bookmarks);
+        orphaned
+    }
+
+    /// Move line suppressions to the line matching their anchor, like
+    /// [`Self::reanchor_bookmarks`].
+    ///
+    /// Suppressions whose line cannot be found are dropped rather than left
+    /// hiding whatever line now has their index. Returns how many were dropped.
+    pub(crate) fn reanchor_suppressions(&self) -> usize {
+        profiling::scope!("SourceData::reanchor_suppressions");
+        let anchored: Vec<(usize, LineAnchor)> = self
+            .get_suppressions()
+            .iter()
+            .filter_map(|s| match s {
+                Suppression::Line {
+                    line_index,
+                    anchor: Some(anchor),
+                } => Some((*line_index, *anchor)),
+                Suppression::Line { anchor: None, .. } | Suppression::Template { .. } => None,
+            })
+            .collect();
+        if anchored.is_empty() {
+            return 0;
+        }
+        let resolved: HashMap<(usize, LineAnchor), Option<usize>> = anchored
+            .iter()
+            .copied()
+            .zip(self.resolve_anchors(&anchored))
+            .collect();
+
+        let mut suppressions = self
+            .suppressions
+            .write()
+            .expect("suppressions lock poisoned");
+        let mut moved = 0;
+        let mut dropped = 0;
+        let mut kept: Vec<Suppression> = Vec::with_capacity(suppressions.len());
+        for mut suppression in suppressions.drain(..) {
+            if let Suppression::Line {
+                line_index,
+                anchor: Some(anchor),
+            } = &mut suppression
+            {
+                match resolved.get(&(*line_index, *anchor)).copied().flatten() {
+                    Some(new_index) if new_index == *line_index => {}
+                    Some(new_index) => {
+                        *line_index = new_index;
+                        moved += 1;
+                    }
+                    None => {
+                        dropped += 1;
+                        continue;
+                    }
+                }
+            }
+            // Two suppressions may have moved onto the same line
+            if !kept.contains(&suppression) {
+                kept.push(suppression);
+            }
+        }
+        *suppressions = kept;
+        drop(suppressions);
+        if moved + dropped > 0 {
+            tracing::info!(
+                "Re-anchored {moved} and dropped {dropped} line suppressions in {}",
+                self.file_path.display()
+            );
+            self.bump_version();
+        }
+        dropped
+    }
+
+    /// Remove and return all bookmarks of this source
+    pub(crate) fn take_bookmarks(&self) -> Vec<Bookmark> {
+        profiling::scope!("SourceData::bookmarks::write");
+        self.bookmarks
+            .write()
+            .expect("bookmarks lock poisoned")
+            .drain()
+            .map(|(_, bookmark)| bookmark)
+            .collect()
+    }
+
+    /// Insert previously taken bookmarks as they are (no re-anchoring)
+    pub(crate) fn restore_bookmarks(&self, restored: Vec<Bookmark>) {
+        profiling::scope!("SourceData::bookmarks::write");
         self.bookmarks
             .write()
             .expect("bookmarks lock poisoned")
-            .insert(line_index, bookmark);
+            .extend(restored.into_iter().map(|b| (b.line_index, b)));
     }
 
     /// Remove a bookmark from this source
@@ -457,6 +1201,52 @@ where
             .cloned()
     }
 
+    /// Read the bookmarks still pending from the `.crab` file into the
+    /// source, [`BOOKMARK_BATCH`] at a time.
+    ///
+    /// Called from the background loader. A bookmark the user set on the same
+    /// line in the meantime is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error if the bookmarks cannot be read. The source
+    /// then refuses to save its `.crab` file, so they are not overwritten.
+    pub(crate) fn insert_pending_bookmarks(&self) -> Result<(), SessionError> {
+        profiling::scope!("SourceData::insert_pending_bookmarks");
+        let pending = self
+            .pending_bookmarks
+            .lock()
+            .expect("pending bookmarks lock poisoned")
+            .clone();
+        let Some(pending) = pending else {
+            return Ok(());
+        };
+        let result = pending.load(BOOKMARK_BATCH, |batch| {
+            let mut bookmarks = self.bookmarks.write().expect("bookmarks lock poisoned");
+            self.pending_inserted
+                .fetch_add(batch.len(), AtomicOrdering::SeqCst);
+            for bookmark in batch {
+                bookmarks.entry(bookmark.line_index).or_insert(bookmark);
+            }
+            drop(bookmarks);
+            std::thread::yield_now();
+        });
+        if result.is_err() {
+            self.unreadable_bookmarks
+                .store(true, AtomicOrdering::SeqCst);
+        }
+        *self
+            .pending_bookmarks
+            .lock()
+            .expect("pending bookmarks lock poisoned") = None;
+        tracing::debug!(
+            "Read {} bookmarks of {}",
+            self.pending_inserted.load(AtomicOrdering::SeqCst),
+            self.crab_path.display()
+        );
+        result
+    }
+
     /// Get all bookmarks for this source
     pub(crate) fn get_bookmarks(&self) -> Vec<Bookmark> {
         profiling::scope!("SourceData::bookmarks::read");
@@ -468,82 +1258,475 @@ where
             .collect()
     }
 
-    /// Save bookmarks to this source's .crab file
-    /// Note: filters and highlights are passed in since they're shared across sources
-    pub fn save_crab_file(&self, filters: &[SavedFilter], highlights: &[SavedHighlight]) {
-        let Some(mutex) = &self.crab else {
-            tracing::warn!(
-                "Skipping save to {} — .crab file is from a newer version of LogCrab",
-                self.crab_path.display()
-            );
-            return;
-        };
-        let mut file = mutex.lock().expect("crab mutex poisoned");
-        let crab_data = CrabFile::<FT> {
-            version: CRAB_FILE_VERSION,
-            bookmarks: self.get_bookmarks(),
-            filters: filters.to_vec(),
-            highlights: highlights.to_vec(),
-            file_state: (*self.file_state).clone(),
-        };
-        match crab_data.save_to_file(&mut file) {
-            Ok(()) => tracing::debug!(
-                "Saved .crab file {} with {} bookmarks",
-                self.crab_path.display(),
-                crab_data.bookmarks.len()
-            ),
-            Err(e) => tracing::error!(
-                "Failed to save .crab file {}: {e}",
-                self.crab_path.display()
-            ),
+    /// Hide lines matching `suppression` from all views. No-op if already present.
+    pub(crate) fn add_suppression(&self, suppression: Suppression) {
+        let mut suppressions = self
+            .suppressions
+            .write()
+            .expect("suppressions lock poisoned");
+        if !suppressions.contains(&suppression) {
+            suppressions.push(suppression);
+            drop(suppressions);
+            self.bump_version();
         }
     }
 
-    // ========================================================================
-    // Time Synchronization
-    // ========================================================================
-
-    /// Re-sort `by_timestamp` using the current config and file-state, then bump the version.
-    ///
-    /// Call this after the shared `config` arc has been mutated externally (e.g.
-    /// `DltTimestampSource` was changed), so that timestamp ordering and dependent
-    /// filter caches are invalidated.
-    pub fn rebuild_time_index(&self) {
-        let lines = self.lines.read().expect("lines lock poisoned");
-        let config = self.config.read().expect("config lock poisoned");
-        let file_state = &*self.file_state;
-        let mut indices: Vec<usize> = (0..lines.len()).collect();
-        indices.par_sort_by_key(|&idx| lines[idx].timestamp(&config, file_state));
-        drop(lines);
-        drop(config);
-        *self
-            .by_timestamp
+    /// Show lines hidden by `suppression` again. Returns `false` if it was not present.
+    pub(crate) fn remove_suppression(&self, suppression: &Suppression) -> bool {
+        let mut suppressions = self
+            .suppressions
             .write()
-            .expect("by_timestamp lock poisoned") = indices;
-        self.bump_version();
-    }
-
-    /// Drive any open calibration window for this source (one per frame).
-    ///
-    /// The `FileState` impl writes the new offset into itself on confirm;
-    /// this method bumps the source version so dependent views invalidate.
-    /// Returns `true` when an offset was applied.
-    pub fn render_file_state(&self, ui: &egui::Ui) -> bool {
-        let changed = self.file_state.egui_render_file_state(ui);
-        if changed {
-            self.rebuild_time_index();
+            .expect("suppressions lock poisoned");
+        let before = suppressions.len();
+        suppressions.retain(|s| s != suppression);
+        let removed = suppressions.len() != before;
+        drop(suppressions);
+        if removed {
+            self.bump_version();
         }
-        changed
+        removed
     }
 
-    // ========================================================================
+    /// Get all suppressions of this source, oldest first
+    pub(crate) fn get_suppressions(&self) -> Vec<Suppression> {
+        self.suppressions
+            .read()
+            .expect("suppressions lock poisoned")
+            .clone()
+    }
+
+    /// Judge every line of `template`, or forget the verdict with `None`
+    pub(crate) fn set_template_verdict(&self, template: &str, verdict: Option<TemplateVerdict>) {
+        let mut feedback = self
+            .template_feedback
+            .write()
+            .expect("template_feedback lock poisoned");
+        feedback.retain(|f| f.template != template);
+        if let Some(verdict) = verdict {
+            feedback.push(TemplateFeedback {
+                template: template.to_string(),
+                verdict,
+            });
+        }
+    }
+
+    /// Get the template feedback of this source, oldest first
+    pub(crate) fn get_template_feedback(&self) -> Vec<TemplateFeedback> {
+        self.template_feedback
+            .read()
+            .expect("template_feedback lock poisoned")
+            .clone()
+    }
+
+    /// Scores fixed by template feedback, by line index
+    pub(crate) fn feedback_scores(&self) -> HashMap<usize, f64> {
+        let verdicts: HashMap<String, TemplateVerdict> = self
+            .get_template_feedback()
+            .into_iter()
+            .map(|f| (f.template, f.verdict))
+            .collect();
+        if verdicts.is_empty() {
+            return HashMap::new();
+        }
+        let snapshot = self.snapshot.load_full();
+        let lines = &snapshot.lines;
+        lines
+            .indices()
+            .into_par_iter()
+            .filter_map(|idx| {
+                let template = crate::parser::normalize_message(&lines.get(idx)?.message());
+                verdicts
+                    .get(&template)
+                    .map(|verdict| (idx, verdict.score()))
+            })
+            .collect()
+    }
+
+    /// Get the level rules of this source, in order
+    pub(crate) fn get_level_rules(&self) -> Vec<LevelRule> {
+        self.level_remap
+            .read()
+            .expect("level_remap lock poisoned")
+            .rules()
+            .to_vec()
+    }
+
+    /// Replace the level rules of this source.
+    ///
+    /// The values of the discrete columns are counted again and the version
+    /// is bumped, so column filters see the new levels. Scores only change
+    /// once the source is scored again.
+    pub(crate) fn set_level_rules(&self, rules: Vec<LevelRule>) {
+        let mut counts = self
+            .facet_counts
+            .write()
+            .expect("facet_counts lock poisoned");
+        let mut remap = self.level_remap.write().expect("level_remap lock poisoned");
+        *remap = LevelRemap::new(rules);
+        let snapshot = self.snapshot.load_full();
+        let lines = &snapshot.lines;
+        *counts = lines
+            .indices()
+            .into_par_iter()
+            .filter_map(|idx| lines.get(idx))
+            .fold(FacetCounts::new, |mut counts, line| {
+                for (column, value) in Self::line_facets(line, &remap) {
+                    *counts.entry(column).or_default().entry(value).or_default() += 1;
+                }
+                counts
+            })
+            .reduce(FacetCounts::new, |mut merged, counts| {
+                for (column, values) in counts {
+                    let column = merged.entry(column).or_default();
+                    for (value, count) in values {
+                        *column.entry(value).or_default() += count;
+                    }
+                }
+                merged
+            });
+        drop(remap);
+        drop(counts);
+        self.bump_version();
+    }
+
+    /// Discrete column values of `line` with its level corrected by `remap`
+    fn line_facets(line: &FT::LineType, remap: &LevelRemap) -> Vec<(&'static str, String)> {
+        let mut facets = line.facets();
+        if !remap.is_empty() {
+            remap.remap_facets(&line.message(), &mut facets);
+        }
+        facets
+    }
+
+    /// Append `entry` to the activity log
+    pub(crate) fn record_activity(&self, entry: ActivityEntry) {
+        self.activity
+            .write()
+            .expect("activity lock poisoned")
+            .push(entry);
+    }
+
+    /// Get the activity log of this source, oldest first
+    pub(crate) fn get_activity(&self) -> Vec<ActivityEntry> {
+        self.activity
+            .read()
+            .expect("activity lock poisoned")
+            .clone()
+    }
+
+    // ========================================================================
+    // Segments
+    // ========================================================================
+
+    /// Get the virtual segments of this source, ordered by start line
+    pub(crate) fn get_segments(&self) -> Vec<Segment> {
+        self.segments
+            .read()
+            .expect("segments lock poisoned")
+            .clone()
+    }
+
+    /// Replace the virtual segments of this source.
+    ///
+    /// Segments are ordered by start line; of several starting at the same
+    /// line only the first is kept. The timestamp index is re-sorted when
+    /// boundaries or offsets changed, since offsets take part in the ordering;
+    /// renaming alone leaves the version untouched.
+    pub(crate) fn set_segments(&self, mut segments: Vec<Segment>) {
+        segments.sort_by_key(|s| s.start_line);
+        segments.dedup_by_key(|s| s.start_line);
+        let timing = |segments: &[Segment]| -> Vec<(usize, i64)> {
+            segments
+                .iter()
+                .map(|s| (s.start_line, s.offset_ms))
+                .collect()
+        };
+        let visibility =
+            |segments: &[Segment]| -> Vec<bool> { segments.iter().map(|s| s.hidden).collect() };
+        let mut current = self.segments.write().expect("segments lock poisoned");
+        let retime = timing(&current) != timing(&segments);
+        let refilter = visibility(&current) != visibility(&segments);
+        *current = segments;
+        drop(current);
+        if retime {
+            self.rebuild_time_index();
+        } else if refilter {
+            self.bump_version();
+        }
+    }
+
+    /// Name of the segment the line at `line_index` belongs to
+    pub(crate) fn segment_name(&self, line_index: usize) -> Option<String> {
+        let segments = self.segments.read().expect("segments lock poisoned");
+        segment::segment_at(&segments, line_index).map(|s| s.name.clone())
+    }
+
+    /// Lines at which a new segment should start, see [`segment::find_split_points`].
+    ///
+    /// Looks at the calibrated timestamps in file order, ignoring any existing
+    /// segment offsets.
+    pub(crate) fn find_split_points(&self, gap_ms: i64) -> Vec<usize> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let config = self.config.read().expect("config lock poisoned");
+        let file_state = &*self.file_state;
+        segment::find_split_points(
+            lines
+                .indices()
+                .filter_map(|idx| Some((idx, lines.get(idx)?.timestamp_ms(&*config, file_state)))),
+            gap_ms,
+        )
+    }
+
+    /// Normalized message template of a line, as used by [`Suppression::Template`]
+    pub fn template_key(&self, line_index: usize) -> Option<String> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        lines
+            .get(line_index)
+            .map(|line| crate::parser::normalize_message(&line.message()))
+    }
+
+    /// Template hashes of all retained lines, with the index of the first of
+    /// them.
+    ///
+    /// Lines are only ever appended or evicted from the front, so earlier
+    /// hashes stay valid and only new lines are normalized.
+    fn template_hashes(&self, lines: &LineBuffer<FT::LineType>) -> (usize, Arc<Vec<u64>>) {
+        profiling::scope!("SourceData::template_hashes");
+        let indices = lines.indices();
+        let mut cache = self
+            .template_hashes
+            .lock()
+            .expect("template_hashes lock poisoned");
+        let (first_index, hashes) = &mut *cache;
+        let evicted = indices.start.saturating_sub(*first_index);
+        if evicted > 0 {
+            let hashes = Arc::make_mut(hashes);
+            hashes.drain(..evicted.min(hashes.len()));
+            *first_index = indices.start;
+        }
+        let known = *first_index + hashes.len();
+        if known < indices.end {
+            let new: Vec<u64> = (known..indices.end)
+                .into_par_iter()
+                .map(|idx| {
+                    lines.get(idx).map_or(0, |line| {
+                        content_hash(&crate::parser::normalize_message(&line.message()))
+                    })
+                })
+                .collect();
+            Arc::make_mut(hashes).extend(new);
+        }
+        let result = (*first_index, Arc::clone(hashes));
+        drop(cache);
+        result
+    }
+
+    /// Normalized message templates of all retained lines, with the index of
+    /// the first of them
+    pub fn template_keys(&self) -> (usize, Vec<String>) {
+        let snapshot = self.snapshot.load_full();
+        let lines = &snapshot.lines;
+        let indices = lines.indices();
+        let first_index = indices.start;
+        let keys = indices
+            .into_par_iter()
+            .map(|idx| {
+                lines
+                    .get(idx)
+                    .map(|line| crate::parser::normalize_message(&line.message()))
+                    .unwrap_or_default()
+            })
+            .collect();
+        (first_index, keys)
+    }
+
+    /// Save bookmarks to this source's .crab file
+    /// Note: filters and highlights are passed in since they're shared across sources
+    ///
+    /// The session is snapshotted here; `writer` serializes and writes it in
+    /// the background.
+    pub fn save_crab_file(
+        &self,
+        writer: &CrabWriter,
+        filters: &[SavedFilter],
+        highlights: &[SavedHighlight],
+    ) where
+        FT: 'static,
+    {
+        profiling::scope!("SourceData::save_crab_file");
+        let lock = self.crab.read().expect("crab lock poisoned").clone();
+        let Some(lock) = lock else {
+            if let Some(contention) = self
+                .contention
+                .lock()
+                .expect("contention lock poisoned")
+                .as_mut()
+            {
+                contention.unsaved = true;
+                tracing::debug!(
+                    "Skipping save to {} — locked by another instance",
+                    self.crab_path.display()
+                );
+            } else {
+                tracing::warn!(
+                    "Skipping save to {} — .crab file is from a newer version of LogCrab",
+                    self.crab_path.display()
+                );
+            }
+            return;
+        };
+        if self.unreadable_bookmarks.load(AtomicOrdering::SeqCst) {
+            tracing::warn!(
+                "Not saving {} — its bookmarks could not be read and would be lost",
+                self.crab_path.display()
+            );
+            return;
+        }
+        let pending = self
+            .pending_bookmarks
+            .lock()
+            .expect("pending bookmarks lock poisoned")
+            .clone();
+        let (bookmarks, inserted) = {
+            let bookmarks = self.bookmarks.read().expect("bookmarks lock poisoned");
+            (
+                bookmarks.values().cloned().collect::<Vec<_>>(),
+                self.pending_inserted.load(AtomicOrdering::SeqCst),
+            )
+        };
+        // Bookmarks from older .crab files have no anchor yet
+        let mut bookmarks: Vec<Bookmark> = bookmarks
+            .into_iter()
+            .map(|mut b| {
+                if b.anchor.is_none() {
+                    b.anchor = self.line_anchor(b.line_index);
+                }
+                b
+            })
+            .collect();
+        let mut crab_data = CrabFile::<FT> {
+            version: CRAB_FILE_VERSION,
+            bookmarks: Vec::new(),
+            filters: filters.to_vec(),
+            highlights: highlights.to_vec(),
+            file_state: (*self.file_state).clone(),
+            suppressions: self.get_suppressions(),
+            template_feedback: self.get_template_feedback(),
+            level_rules: self.get_level_rules(),
+            activity: self.get_activity(),
+            segments: self.get_segments(),
+            deferred_bookmarks: None,
+        };
+        writer.submit(self.crab_path.clone(), lock, move || {
+            // Bookmarks the loader has not read yet are saved as in the file,
+            // unless the user has set one on the same line since. Parsing
+            // them happens here, on the writer thread, once per file.
+            if let Some(pending) = pending {
+                let unread = pending.parse()?;
+                let bookmarked: std::collections::HashSet<usize> =
+                    bookmarks.iter().map(|b| b.line_index).collect();
+                bookmarks.extend(
+                    unread
+                        .iter()
+                        .skip(inserted)
+                        .filter(|b| !bookmarked.contains(&b.line_index))
+                        .cloned(),
+                );
+            }
+            crab_data.bookmarks = bookmarks;
+            crab_data.to_json()
+        });
+    }
+
+    // ========================================================================
+    // Time Synchronization
+    // ========================================================================
+
+    /// Re-sort `by_timestamp` using the current config and file-state, then bump the version.
+    ///
+    /// Call this after the shared `config` arc has been mutated externally (e.g.
+    /// `DltTimestampSource` was changed), so that timestamp ordering and dependent
+    /// filter caches are invalidated.
+    pub fn rebuild_time_index(&self) {
+        let segments = self.segments.read().expect("segments lock poisoned");
+        let config = self.config.read().expect("config lock poisoned");
+        let file_state = &*self.file_state;
+        let writer = self.write_lock.lock().expect("write lock poisoned");
+        let snapshot = self.snapshot.load_full();
+        let lines = &snapshot.lines;
+        let mut indices: Vec<usize> = lines.indices().collect();
+        let key = |idx: usize| {
+            lines.get(idx).map_or(0, |line| {
+                line.timestamp_ns(&config, file_state)
+                    .saturating_add(segment::offset_at(&segments, idx) * NANOS_PER_MS)
+            })
+        };
+        indices.par_sort_by_key(|&idx| key(idx));
+        let mut chunk_ranges = Vec::new();
+        extend_chunk_ranges(&mut chunk_ranges, lines, lines.indices(), |idx| {
+            key(idx).div_euclid(NANOS_PER_MS)
+        });
+        // The display messages may depend on the changed config as well
+        let bloom_generation = file_state.display_generation();
+        let mut chunk_blooms = Vec::new();
+        extend_chunk_blooms(&mut chunk_blooms, lines, lines.indices(), |idx, bloom| {
+            if let Some(line) = lines.get(idx) {
+                bloom.insert(&line.display_message(&config, file_state));
+                bloom.insert(&line.raw());
+            }
+        });
+        self.snapshot.store(Arc::new(LineSnapshot {
+            lines: lines.clone(),
+            by_timestamp: Arc::new(indices),
+            chunk_ranges: Arc::new(chunk_ranges),
+            chunk_blooms: Arc::new(chunk_blooms),
+            bloom_generation,
+            bloomed_to: lines.len(),
+        }));
+        drop(writer);
+        drop(config);
+        drop(segments);
+        self.bump_version();
+    }
+
+    /// Whether the file state asked for the file to be parsed again
+    /// (see [`LogFileState::take_reparse_request`])
+    pub fn take_reparse_request(&self) -> bool {
+        self.file_state.take_reparse_request()
+    }
+
+    /// Clock correction applied to this source
+    /// (see [`LogFileState::clock_offset_ms`])
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.file_state.clock_offset_ms()
+    }
+
+    /// Drive any open calibration window for this source (one per frame).
+    ///
+    /// The `FileState` impl writes the new offset into itself on confirm;
+    /// this method bumps the source version so dependent views invalidate.
+    /// Returns `true` when an offset was applied.
+    pub fn render_file_state(&self, ui: &egui::Ui) -> bool {
+        let changed = self.file_state.egui_render_file_state(ui);
+        // Display messages changed by the file state also leave the blooms stale
+        let stale = self.snapshot.load().bloom_generation != self.file_state.display_generation();
+        if changed || stale {
+            self.rebuild_time_index();
+        }
+        changed
+    }
+
+    // ========================================================================
     // Line Management
     // ========================================================================
 
     /// Append lines to this source
     ///
     /// Lines are stored in file order (append-only). Only the timestamp index
-    /// needs to be rebuilt. Heavy work is done outside the write lock.
+    /// needs to be rebuilt. Readers keep using the previous snapshot until the
+    /// new one is swapped in.
     pub fn append_lines(&self, lines: Vec<FT::LineType>) {
         if lines.is_empty() {
             return;
@@ -551,52 +1734,62 @@ where
 
         profiling::scope!("SourceData::append_lines");
 
+        {
+            profiling::scope!("SourceData::facet_counts::write");
+            let mut counts = self
+                .facet_counts
+                .write()
+                .expect("facet_counts lock poisoned");
+            let remap = self.level_remap.read().expect("level_remap lock poisoned");
+            for line in &lines {
+                for (column, value) in Self::line_facets(line, &remap) {
+                    *counts.entry(column).or_default().entry(value).or_default() += 1;
+                }
+            }
+        }
+
+        let segments = self.segments.read().expect("segments lock poisoned");
         let config = self.config.read().expect("config lock poisoned");
         let file_state = &*self.file_state;
-
-        // Append lines and capture the range of new indices atomically
-        let new_start_idx = {
-            profiling::scope!("SourceData::lines::write");
-            let mut lines_guard = self.lines.write().expect("lines lock poisoned");
-            let start_idx = lines_guard.len();
-            tracing::debug!(
-                "Appending {} lines to existing {} lines (merge overhead)",
-                lines.len(),
-                start_idx
-            );
-            lines_guard.extend(lines);
-            start_idx
+        let sort_key = |lines: &LineBuffer<FT::LineType>, idx: usize| {
+            lines.get(idx).map_or(0, |line| {
+                line.timestamp_ns(&*config, file_state)
+                    .saturating_add(segment::offset_at(&segments, idx) * NANOS_PER_MS)
+            })
         };
 
-        // Read lines and build sorted indices for the newly appended range
-        let (lines_guard, new_by_ts) = {
-            profiling::scope!("SourceData::lines::read");
-            let lines_read = self.lines.read().expect("lines lock poisoned");
+        // Build the next snapshot on top of the current one; readers keep
+        // using the current one until it is swapped in
+        let writer = self.write_lock.lock().expect("write lock poisoned");
+        let current = self.snapshot.load_full();
+        let mut lines_next = current.lines.clone();
+        let new_start_idx = lines_next.len();
+        tracing::debug!(
+            "Appending {} lines to existing {} lines (merge overhead)",
+            lines.len(),
+            new_start_idx
+        );
+        lines_next.extend(lines);
+
+        let new_by_ts = {
             profiling::scope!("sort_new_indices");
-            let mut indices: Vec<usize> = (new_start_idx..lines_read.len()).collect();
-            indices.par_sort_by_key(|&idx| lines_read[idx].timestamp(&*config, file_state));
-            (lines_read, indices)
+            let mut indices: Vec<usize> = (new_start_idx..lines_next.len()).collect();
+            indices.par_sort_by_key(|&idx| sort_key(&lines_next, idx));
+            indices
         };
 
-        // Merge into by_timestamp atomically - hold write lock during merge
-        {
-            profiling::scope!("SourceData::by_timestamp::write");
-            let mut by_ts_guard = self
-                .by_timestamp
-                .write()
-                .expect("by_timestamp lock poisoned");
-
+        let merged = {
             profiling::scope!("merge_timestamp_indices");
-            let existing_len = by_ts_guard.len();
-            let mut merged = Vec::with_capacity(existing_len + new_by_ts.len());
+            let existing = &current.by_timestamp;
+            let mut merged = Vec::with_capacity(existing.len() + new_by_ts.len());
             let mut i_exist = 0;
             let mut j_new = 0;
 
-            while i_exist < existing_len && j_new < new_by_ts.len() {
-                let ts_exist = lines_guard[by_ts_guard[i_exist]].timestamp(&*config, file_state);
-                let ts_new = lines_guard[new_by_ts[j_new]].timestamp(&*config, file_state);
+            while i_exist < existing.len() && j_new < new_by_ts.len() {
+                let ts_exist = sort_key(&lines_next, existing[i_exist]);
+                let ts_new = sort_key(&lines_next, new_by_ts[j_new]);
                 if ts_exist <= ts_new {
-                    merged.push(by_ts_guard[i_exist]);
+                    merged.push(existing[i_exist]);
                     i_exist += 1;
                 } else {
                     merged.push(new_by_ts[j_new]);
@@ -605,46 +1798,271 @@ where
             }
 
             // Append remaining elements
-            merged.extend_from_slice(&by_ts_guard[i_exist..]);
+            merged.extend_from_slice(&existing[i_exist..]);
             merged.extend_from_slice(&new_by_ts[j_new..]);
+            merged
+        };
+
+        let mut chunk_ranges = (*current.chunk_ranges).clone();
+        extend_chunk_ranges(
+            &mut chunk_ranges,
+            &lines_next,
+            new_start_idx..lines_next.len(),
+            |idx| sort_key(&lines_next, idx).div_euclid(NANOS_PER_MS),
+        );
+        let (chunk_blooms, bloom_generation, bloomed_to) =
+            Self::next_blooms(&current, &lines_next, &config, file_state, false);
 
-            *by_ts_guard = merged;
+        {
+            profiling::scope!("SourceData::snapshot::store");
+            self.snapshot.store(Arc::new(LineSnapshot {
+                lines: lines_next,
+                by_timestamp: Arc::new(merged),
+                chunk_ranges: Arc::new(chunk_ranges),
+                chunk_blooms,
+                bloom_generation,
+                bloomed_to,
+            }));
         }
 
-        drop(lines_guard);
+        drop(writer);
         drop(config);
+        drop(segments);
+        self.enforce_retention();
         self.bump_version();
     }
 
-    /// Get the number of lines
+    /// The blooms of `current` extended over the lines of `lines` they lack,
+    /// with their generation and end, see [`LineSnapshot::bloomed_to`].
+    ///
+    /// Unless `flush` is set, the newest chunk's bloom is only extended once
+    /// a batch has gathered or the chunk is full.
+    fn next_blooms(
+        current: &LineSnapshot<FT::LineType>,
+        lines: &LineBuffer<FT::LineType>,
+        config: &<FT::LineType as LineType>::Config,
+        file_state: &<FT::LineType as LineType>::FileState,
+        flush: bool,
+    ) -> (Arc<Vec<Arc<TrigramBloom>>>, u64, usize) {
+        let from = current.bloomed_to.max(lines.first_index());
+        let due = flush
+            || lines.len() - from >= BLOOM_BATCH
+            || LineBuffer::<FT::LineType>::chunk_id(from)
+                < LineBuffer::<FT::LineType>::chunk_id(lines.len());
+        if !due || from == lines.len() {
+            return (
+                Arc::clone(&current.chunk_blooms),
+                current.bloom_generation,
+                current.bloomed_to,
+            );
+        }
+        // Blooms of a fresh source are all built now; otherwise older chunks
+        // keep the generation they were built at
+        let generation = if current.chunk_blooms.is_empty() {
+            file_state.display_generation()
+        } else {
+            current.bloom_generation
+        };
+        let mut blooms = (*current.chunk_blooms).clone();
+        extend_chunk_blooms(&mut blooms, lines, from..lines.len(), |idx, bloom| {
+            if let Some(line) = lines.get(idx) {
+                bloom.insert(&line.display_message(config, file_state));
+                bloom.insert(&line.raw());
+            }
+        });
+        (Arc::new(blooms), generation, lines.len())
+    }
+
+    /// Extend the blooms over the lines still missing from them, once no
+    /// large batch is coming anymore.
+    fn flush_blooms(&self) {
+        let config = self.config.read().expect("config lock poisoned");
+        let _writer = self.write_lock.lock().expect("write lock poisoned");
+        let current = self.snapshot.load_full();
+        if current.bloomed_to >= current.lines.len() {
+            return;
+        }
+        let (chunk_blooms, bloom_generation, bloomed_to) =
+            Self::next_blooms(&current, &current.lines, &config, &self.file_state, true);
+        self.snapshot.store(Arc::new(LineSnapshot {
+            lines: current.lines.clone(),
+            by_timestamp: Arc::clone(&current.by_timestamp),
+            chunk_ranges: Arc::clone(&current.chunk_ranges),
+            chunk_blooms,
+            bloom_generation,
+            bloomed_to,
+        }));
+    }
+
+    /// Limit the number of lines kept in memory to `limit` (0 = unlimited).
+    ///
+    /// Meant for endless live sources: once the limit is exceeded the oldest
+    /// lines are evicted on the next append. Retained lines keep their index.
+    /// The store only sets it on sources marked live (see [`Self::mark_live`]).
+    pub fn set_retention_limit(&self, limit: usize) {
+        self.retention_limit.store(limit, AtomicOrdering::SeqCst);
+    }
+
+    /// Evict the oldest lines if more than the retention limit are held.
+    ///
+    /// Evicts an eighth of the limit beyond what is strictly necessary so
+    /// that a steady stream of appends does not evict on every chunk.
+    /// Bookmarks and line suppressions pointing at evicted lines are dropped
+    /// and reported to the user.
+    fn enforce_retention(&self) {
+        let limit = self.retention_limit.load(AtomicOrdering::SeqCst);
+        if limit == 0 {
+            return;
+        }
+
+        profiling::scope!("SourceData::enforce_retention");
+
+        // Prune `by_timestamp` in the same snapshot so no reader sees an
+        // index of an evicted line
+        let (evicted, first_index) = {
+            let _writer = self.write_lock.lock().expect("write lock poisoned");
+            let current = self.snapshot.load_full();
+            if current.lines.retained() <= limit {
+                return;
+            }
+            let mut lines = current.lines.clone();
+            let evicted = lines.evict_to(limit - limit / 8);
+            let first_index = lines.first_index();
+            let by_timestamp = current
+                .by_timestamp
+                .iter()
+                .copied()
+                .filter(|&idx| idx >= first_index)
+                .collect();
+            // The first retained chunk keeps its range, which may now be wider
+            // than its lines; that only makes pruning more conservative
+            let dropped_chunks = lines.chunk_ids().start - current.lines.chunk_ids().start;
+            let chunk_ranges = current
+                .chunk_ranges
+                .get(dropped_chunks..)
+                .unwrap_or_default()
+                .to_vec();
+            let chunk_blooms = current
+                .chunk_blooms
+                .get(dropped_chunks..)
+                .unwrap_or_default()
+                .to_vec();
+            self.snapshot.store(Arc::new(LineSnapshot {
+                lines,
+                by_timestamp: Arc::new(by_timestamp),
+                chunk_ranges: Arc::new(chunk_ranges),
+                chunk_blooms: Arc::new(chunk_blooms),
+                bloom_generation: current.bloom_generation,
+                bloomed_to: current.bloomed_to,
+            }));
+            (evicted, first_index)
+        };
+        tracing::debug!(
+            "Evicted {} lines from {}, oldest retained line is now {first_index}",
+            evicted.len(),
+            self.file_path.display()
+        );
+
+        {
+            let mut counts = self
+                .facet_counts
+                .write()
+                .expect("facet_counts lock poisoned");
+            let remap = self.level_remap.read().expect("level_remap lock poisoned");
+            for line in &evicted {
+                for (column, value) in Self::line_facets(line, &remap) {
+                    if let Some(values) = counts.get_mut(column) {
+                        if let Some(count) = values.get_mut(&value) {
+                            *count -= 1;
+                            if *count == 0 {
+                                values.remove(&value);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut bookmarks = self.bookmarks.write().expect("bookmarks lock poisoned");
+        let before = bookmarks.len();
+        bookmarks.retain(|&line_index, _| line_index >= first_index);
+        let dropped_bookmarks = before - bookmarks.len();
+        drop(bookmarks);
+
+        let mut suppressions = self
+            .suppressions
+            .write()
+            .expect("suppressions lock poisoned");
+        let before = suppressions.len();
+        suppressions.retain(
+            |s| !matches!(s, Suppression::Line { line_index, .. } if *line_index < first_index),
+        );
+        let dropped_suppressions = before - suppressions.len();
+        drop(suppressions);
+
+        if dropped_bookmarks + dropped_suppressions > 0 {
+            let file_name = self
+                .file_path
+                .file_name()
+                .unwrap_or(self.file_path.as_os_str())
+                .to_string_lossy();
+            tracing::info!(
+                "Dropped {dropped_bookmarks} bookmarks and {dropped_suppressions} line \
+                 suppressions on evicted lines of {}",
+                self.file_path.display()
+            );
+            self.warnings.send(format!(
+                "The oldest lines of {file_name} were dropped to stay within the retention \
+                 limit, together with {dropped_bookmarks} bookmark(s) and \
+                 {dropped_suppressions} hidden line(s) on them"
+            ));
+        }
+    }
+
+    /// Index of the oldest line still held (non-zero once lines were evicted)
+    pub fn first_index(&self) -> usize {
+        self.snapshot.load().lines.first_index()
+    }
+
+    /// Get the number of lines, including evicted ones (one past the newest index)
     pub fn len(&self) -> usize {
-        profiling::scope!("SourceData::lines::read");
-        self.lines.read().expect("lines lock poisoned").len()
+        self.snapshot.load().lines.len()
     }
 
     /// Check if this source has no lines
     pub fn is_empty(&self) -> bool {
-        profiling::scope!("SourceData::lines::read");
-        self.lines.read().expect("lines lock poisoned").is_empty()
+        self.snapshot.load().lines.is_empty()
     }
 
     /// Look up a single line and return it as the display [`LogLine`] DTO.
     ///
-    /// Acquires `lines`, `config`, and `file_state` locks exactly once so the
+    /// Reads one line snapshot and the `config` lock exactly once so the
     /// timestamp, message, and all other fields are computed under the same
     /// read epoch.  Returns `None` when `line_index` is out of range.
     #[allow(clippy::significant_drop_tightening)]
     pub fn get_as_log_line(&self, line_index: usize) -> Option<LogLine> {
         profiling::scope!("SourceData::get_as_log_line");
-        let lines = self.lines.read().expect("lines lock poisoned");
+        // Segments are locked before lines everywhere
+        let offset_ms = self.segment_offset_ms(line_index);
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
         let config = self.config.read().expect("config lock poisoned");
         let file_state = &*self.file_state;
         let line = lines.get(line_index)?;
+        let remap = self.level_remap.read().expect("level_remap lock poisoned");
+        let severity = if remap.is_empty() {
+            line.severity()
+        } else {
+            remap.severity(&line.message(), line.severity())
+        };
+        drop(remap);
         Some(LogLine {
-            timestamp: line.timestamp(&*config, file_state),
+            timestamp_ms: line.timestamp_ms(&*config, file_state) + offset_ms,
             message: line.display_message(&*config, file_state),
             raw: line.raw(),
             line_number: line.line_number(),
+            request: line.request_metrics(),
+            severity,
             anomaly_score: 0.0, // Scores are stored at LogStore level, populated by get_by_id
             sidecar_anomaly_score: 0.0,
             sidecar_score_is_unk: false,
@@ -653,6 +2071,49 @@ where
         })
     }
 
+    /// Fully-adjusted timestamp of the line at `line_index`: config-selected
+    /// clock, calibration offset and segment offset.
+    pub fn adjusted_timestamp(&self, line_index: usize) -> Option<chrono::DateTime<Local>> {
+        let offset_ms = self.segment_offset_ms(line_index);
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let config = self.config.read().expect("config lock poisoned");
+        let timestamp = lines.get(line_index)?.timestamp(&*config, &self.file_state);
+        Some(timestamp + chrono::Duration::milliseconds(offset_ms))
+    }
+
+    /// Like [`Self::adjusted_timestamp`] but in epoch nanoseconds, the cheap
+    /// key used for ordering lines across sources.
+    pub fn adjusted_timestamp_ns(&self, line_index: usize) -> Option<i64> {
+        let offset_ms = self.segment_offset_ms(line_index);
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let timestamp_ns = lines.get(line_index)?.timestamp_ns(
+            &*self.config.read().expect("config lock poisoned"),
+            &self.file_state,
+        );
+        Some(timestamp_ns.saturating_add(offset_ms * NANOS_PER_MS))
+    }
+
+    /// Like [`Self::adjusted_timestamp`] but in epoch milliseconds, for
+    /// bucketing and time windows.
+    pub fn adjusted_timestamp_ms(&self, line_index: usize) -> Option<i64> {
+        let offset_ms = self.segment_offset_ms(line_index);
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let config = self.config.read().expect("config lock poisoned");
+        let timestamp_ms = lines
+            .get(line_index)?
+            .timestamp_ms(&*config, &self.file_state);
+        Some(timestamp_ms + offset_ms)
+    }
+
+    /// Offset of the segment the line at `line_index` belongs to
+    fn segment_offset_ms(&self, line_index: usize) -> i64 {
+        let segments = self.segments.read().expect("segments lock poisoned");
+        segment::offset_at(&segments, line_index)
+    }
+
     /// Returns the canonical sidecar message and timestamp (ms) for a single line.
     ///
     /// Unlike `get_as_log_line`, this calls `LineType::message()` which returns the
@@ -663,37 +2124,105 @@ where
     ///
     /// Returns `None` when `line_index` is out of range.
     pub fn get_sidecar_message(&self, line_index: usize) -> Option<(u64, String)> {
-        let lines = self.lines.read().expect("lines lock poisoned");
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
         let config = self.config.read().expect("config lock poisoned");
         let file_state = &*self.file_state;
         let line = lines.get(line_index)?;
-        let ts_ms = line
-            .timestamp(&*config, file_state)
-            .timestamp_millis()
-            .max(0) as u64;
+        let ts_ms = line.timestamp_ms(&*config, file_state).max(0) as u64;
         Some((ts_ms, line.message()))
     }
 
+    /// Content anchor (timestamp + message hash) of a single line.
+    pub fn line_anchor(&self, line_index: usize) -> Option<LineAnchor> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let config = self.config.read().expect("config lock poisoned");
+        let line = lines.get(line_index)?;
+        Some(LineAnchor::new(
+            line.timestamp(&*config, &self.file_state),
+            &line.message(),
+        ))
+    }
+
+    /// Re-locate `(last known index, anchor)` pairs in the current lines.
+    ///
+    /// See [`resolve_anchors`]; holds one line snapshot and the `config` read
+    /// lock for the whole scan.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn resolve_anchors(&self, anchors: &[(usize, LineAnchor)]) -> Vec<Option<usize>> {
+        profiling::scope!("SourceData::resolve_anchors");
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let config = self.config.read().expect("config lock poisoned");
+        let file_state = &*self.file_state;
+        resolve_anchors(anchors, lines.len(), |index| {
+            lines
+                .get(index)
+                .map(|line| LineAnchor::new(line.timestamp(&*config, file_state), &line.message()))
+        })
+    }
+
     /// Filter lines by their *display message* and *raw* string, in timestamp order.
     ///
     /// Unlike `filter_sorted_mapped`, the predicate receives the display message produced
     /// by `display_message(config, file_state)` — which includes any active overlays such
     /// as SOME/IP SD decoded entries — and the raw string.  All config and file-state locks
     /// are acquired once for the whole scan.
-    pub fn filter_sorted_by_search<F>(&self, predicate: &F) -> Vec<usize>
+    ///
+    /// Chunks that `hints` rule out are skipped before the predicate runs;
+    /// every line of the remaining chunks is still passed on, so the
+    /// predicate alone decides what matches.
+    pub fn filter_sorted_by_search<F>(&self, predicate: &F, hints: &ScanHints) -> Vec<usize>
     where
         F: Fn(&str, &str) -> bool + Sync,
     {
         profiling::scope!("SourceData::filter_sorted_by_search");
-        let lines = self.lines.read().expect("lines lock poisoned");
+        let mut suppressed_lines = std::collections::HashSet::new();
+        let mut suppressed_templates = std::collections::HashSet::new();
+        for suppression in self.get_suppressions() {
+            match suppression {
+                Suppression::Line { line_index, .. } => {
+                    suppressed_lines.insert(line_index);
+                }
+                Suppression::Template { template } => {
+                    suppressed_templates.insert(content_hash(&template));
+                }
+            }
+        }
+        let segments = self.get_segments();
+        let any_hidden = segments.iter().any(|s| s.hidden);
+        let snapshot = self.snapshot.load_full();
+        let lines = &snapshot.lines;
+        let first_chunk = lines.chunk_ids().start;
+        let live_chunks = snapshot.live_chunks(hints, self.file_state.display_generation());
+        let (first_hashed, template_hashes) = if suppressed_templates.is_empty() {
+            (0, Arc::default())
+        } else {
+            self.template_hashes(lines)
+        };
         let config = self.config.read().expect("config lock poisoned");
         let file_state = &*self.file_state;
-        self.by_timestamp
-            .read()
-            .expect("by_timestamp lock poisoned")
+        snapshot
+            .by_timestamp
             .par_iter()
             .filter_map(|&idx| {
-                let line = &lines[idx];
+                if let Some(live) = &live_chunks {
+                    let chunk = LineBuffer::<FT::LineType>::chunk_id(idx) - first_chunk;
+                    if !live.get(chunk).copied().unwrap_or(true) {
+                        return None;
+                    }
+                }
+                let line = lines.get(idx)?;
+                if suppressed_lines.contains(&idx)
+                    || (any_hidden && segment::segment_at(&segments, idx).is_some_and(|s| s.hidden))
+                    || idx
+                        .checked_sub(first_hashed)
+                        .and_then(|i| template_hashes.get(i))
+                        .is_some_and(|hash| suppressed_templates.contains(hash))
+                {
+                    return None;
+                }
                 let display_msg = line.display_message(&*config, file_state);
                 let raw = line.raw();
                 predicate(&display_msg, &raw).then_some(idx)
@@ -701,31 +2230,87 @@ where
             .collect()
     }
 
-    /// Render format-specific context menu items for the line at `line_index`.
-    ///
-    /// Must be called inside an egui `context_menu` closure.
-    pub fn render_line_context_menu(&self, line_index: usize, ui: &mut egui::Ui) {
-        let lines = self.lines.read().expect("lines lock poisoned");
-        let config = self.config.read().expect("config lock poisoned");
-        let file_state = &*self.file_state;
-        if let Some(line) = lines.get(line_index) {
-            line.egui_render_context_menu(ui, &*config, file_state);
-        }
+    /// Parsed key/value fields of a single line (see [`LineType::fields`]).
+    pub fn get_fields(&self, line_index: usize) -> Option<Vec<(String, String)>> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        lines.get(line_index).map(LineType::fields)
     }
-}
 
-crate::register_filetypes! {
-    binary {
-        dlt:     Dlt:     DltFileType:     DltLogLine,
-        btsnoop: Btsnoop: BtsnoopFileType: BtsnoopLogLine,
+    /// Reformatted raw text of a single line (see [`LineType::pretty_raw`]).
+    pub fn get_pretty_raw(&self, line_index: usize) -> Option<String> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        lines.get(line_index).and_then(LineType::pretty_raw)
+    }
+
+    /// Captured bytes of a single line (see [`LineType::payload`]).
+    pub fn get_payload(&self, line_index: usize) -> Option<Arc<[u8]>> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        lines.get(line_index).and_then(LineType::payload)
+    }
+
+    /// Discrete column values of a single line (see [`LineType::facets`]).
+    pub fn get_facets(&self, line_index: usize) -> Option<Vec<(&'static str, String)>> {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let remap = self.level_remap.read().expect("level_remap lock poisoned");
+        lines
+            .get(line_index)
+            .map(|line| Self::line_facets(line, &remap))
+    }
+
+    /// Names of the discrete columns observed in this source so far.
+    pub fn facet_columns(&self) -> Vec<&'static str> {
+        self.facet_counts
+            .read()
+            .expect("facet_counts lock poisoned")
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// Observed values and line counts for every discrete column of this source.
+    pub fn facet_counts(&self) -> FacetCounts {
+        self.facet_counts
+            .read()
+            .expect("facet_counts lock poisoned")
+            .clone()
+    }
+
+    /// Render format-specific context menu items for the line at `line_index`.
+    ///
+    /// Must be called inside an egui `context_menu` closure.
+    pub fn render_line_context_menu(&self, line_index: usize, ui: &mut egui::Ui) {
+        let snapshot = self.snapshot.load();
+        let lines = &snapshot.lines;
+        let config = self.config.read().expect("config lock poisoned");
+        let file_state = &*self.file_state;
+        if let Some(line) = lines.get(line_index) {
+            line.egui_render_context_menu(ui, &*config, file_state);
+        }
+    }
+}
+
+crate::register_filetypes! {
+    binary {
+        dlt:     Dlt:     DltFileType:     DltLogLine,
+        btsnoop: Btsnoop: BtsnoopFileType: BtsnoopLogLine,
         pcap:    Pcap:    PcapFileType:    PcapLogLine,
     }
     text {
-        bugreport: Bugreport: BugreportFileType: BugreportLogLine,
-        logcat:    Logcat:   LogcatFileType:    LogcatLogLine,
-        dmesg:     Dmesg:    DmesgFileType:     DmesgLogLine,
-        otel:      Otel:     OtelFileType:      OtelLogLine,
-        generic:   Generic:  GenericFileType:   GenericLogLine,
+        bugreport:  Bugreport: BugreportFileType: BugreportLogLine,
+        logcat:     Logcat:    LogcatFileType:    LogcatLogLine,
+        dmesg:      Dmesg:     DmesgFileType:     DmesgLogLine,
+        syslog:     Syslog:    SyslogFileType:    SyslogLogLine,
+        otel:       Otel:      OtelFileType:      OtelLogLine,
+        trace:      Trace:     TraceFileType:     TraceLogLine,
+        journal:    Journal:   JournalFileType:   JournalLogLine,
+        json:       Json:      JsonFileType:      JsonLogLine,
+        access_log: AccessLog: AccessLogFileType: AccessLogLine,
+        csv:        Csv:       CsvFileType:       CsvLogLine,
+        generic:    Generic:   GenericFileType:   GenericLogLine,
     }
 }
 
@@ -736,14 +2321,20 @@ crate::register_filetypes! {
 /// calibration UI) goes through [`StoreID`] + [`LogStore`] methods.
 #[derive(Debug, Clone)]
 pub struct LogLine {
-    /// Fully-adjusted timestamp: config-selected clock + calibration offset.
-    pub timestamp: chrono::DateTime<chrono::Local>,
+    /// Fully-adjusted timestamp (config-selected clock + calibration offset)
+    /// in milliseconds since the Unix epoch. Use [`LogLine::timestamp`] to
+    /// format it for display.
+    pub timestamp_ms: i64,
     /// Rendered message text.
     pub message: String,
     /// Original raw source text.
     pub raw: String,
     /// 1-based line number within the source file.
     pub line_number: usize,
+    /// Request status and latency, for formats that log served requests.
+    pub request: Option<RequestMetrics>,
+    /// Severity stated by the format, e.g. a syslog priority.
+    pub severity: Option<Severity>,
     /// Anomaly score in [0, 100].
     pub anomaly_score: f64,
     /// ML sidecar anomaly score in [0, 100]. 0.0 when not available.
@@ -760,6 +2351,17 @@ pub struct LogLine {
 }
 
 impl LogLine {
+    /// The timestamp as local time, for display.
+    ///
+    /// Converting to `Local` looks up the UTC offset, so only do this for lines
+    /// that are actually shown; compare and bucket via `timestamp_ms`.
+    #[must_use]
+    pub fn timestamp(&self) -> chrono::DateTime<chrono::Local> {
+        chrono::DateTime::from_timestamp_millis(self.timestamp_ms)
+            .map(|utc| utc.with_timezone(&chrono::Local))
+            .unwrap_or_default()
+    }
+
     /// Compute the normalised template key for anomaly detection.
     /// This is computed on-demand rather than stored to avoid expensive
     /// regex normalization when not needed (e.g., histogram rendering).
@@ -791,6 +2393,23 @@ pub struct LogStore {
     /// Active explain sessions keyed by `source_id`.
     /// One session per scored file; dropped (closing the WebSocket) when the source is removed.
     explain_sessions: Mutex<HashMap<u64, crate::anomaly::sidecar_client::ExplainSession>>,
+    /// Maximum number of lines kept in memory per source (0 = unlimited).
+    /// Applied to every source, including ones added later.
+    retention_limit: AtomicUsize,
+    /// Whether analysis actions are written to the activity log
+    record_activity: AtomicBool,
+    /// Background writer for the sources' .crab files
+    crab_writer: CrabWriter,
+    /// Sources whose scoring finished since the UI last asked, with whether
+    /// the scores came from the ML sidecar (see [`Self::take_finished_scoring`])
+    finished_scoring: Mutex<Vec<(u64, bool)>>,
+    /// Where summaries of loaded files are cached; `None` records nothing
+    run_history: RwLock<Option<RunHistory>>,
+    /// Earlier run of a loaded source's log, drawn behind the histogram
+    previous_run: RwLock<Option<PreviousRun>>,
+    /// Original log of each file split off one (see
+    /// [`crate::core::container_split`]), keyed by the split file
+    split_origins: RwLock<HashMap<PathBuf, PathBuf>>,
 }
 
 impl std::fmt::Debug for LogStore {
@@ -805,6 +2424,8 @@ impl std::fmt::Debug for LogStore {
             .field("sidecar_scores_count", &self.sidecar_scores.len())
             .field("sidecar_enabled", &self.sidecar_config.read().map(|c| c.is_some()).unwrap_or(false))
             .field("explain_sessions", &self.explain_sessions.lock().map(|g| g.len()).unwrap_or(0))
+            .field("retention_limit", &self.retention_limit.load(AtomicOrdering::Relaxed))
+            .field("record_activity", &self.record_activity.load(AtomicOrdering::Relaxed))
             .finish()
     }
 }
@@ -825,6 +2446,19 @@ impl Clone for LogStore {
             ),
             // Explain sessions are live resources — clones start with no sessions.
             explain_sessions: Mutex::new(HashMap::new()),
+            retention_limit: AtomicUsize::new(self.retention_limit.load(AtomicOrdering::SeqCst)),
+            record_activity: AtomicBool::new(self.record_activity.load(AtomicOrdering::SeqCst)),
+            // Saves go through each source's shared lock, so a separate writer is safe.
+            crab_writer: CrabWriter::new(),
+            finished_scoring: Mutex::new(Vec::new()),
+            run_history: RwLock::new(self.run_history()),
+            previous_run: RwLock::new(self.previous_run()),
+            split_origins: RwLock::new(
+                self.split_origins
+                    .read()
+                    .expect("split origins lock poisoned")
+                    .clone(),
+            ),
         }
     }
 }
@@ -874,8 +2508,8 @@ impl StoreID {
     /// structural ordering to maintain a valid total order.
     pub fn cmp(&self, other: &Self, store: &LogStore) -> Ordering {
         match (
-            store.adjusted_timestamp(self),
-            store.adjusted_timestamp(other),
+            store.adjusted_timestamp_ns(self),
+            store.adjusted_timestamp_ns(other),
         ) {
             (Some(self_time), Some(other_time)) => {
                 // Both lines exist: compare by calibrated timestamp, then structurally for stability
@@ -901,6 +2535,13 @@ impl LogStore {
             sidecar_scores: DashMap::new(),
             sidecar_config: RwLock::new(None),
             explain_sessions: Mutex::new(HashMap::new()),
+            retention_limit: AtomicUsize::new(0),
+            record_activity: AtomicBool::new(false),
+            crab_writer: CrabWriter::new(),
+            finished_scoring: Mutex::new(Vec::new()),
+            run_history: RwLock::new(None),
+            previous_run: RwLock::new(None),
+            split_origins: RwLock::new(HashMap::new()),
         })
     }
 
@@ -925,6 +2566,9 @@ impl LogStore {
     pub fn add_source(self: &Arc<Self>, variant: DataSourceVariant) {
         profiling::scope!("LogStore::sources::write");
         let id = variant.source_id();
+        if variant.is_live() {
+            variant.set_retention_limit(self.retention_limit.load(AtomicOrdering::SeqCst));
+        }
         self.sources
             .write()
             .expect("sources lock poisoned")
@@ -938,6 +2582,10 @@ impl LogStore {
         let canonical_path = path.canonicalize().ok();
         let sources = self.sources.read().expect("sources lock poisoned");
         sources.values().any(|source| {
+            // Sources split off `path` count as loaded from it
+            if self.watched_path(source.file_path()) == path {
+                return true;
+            }
             // Try canonical comparison first, fall back to direct comparison
             if let Some(ref canonical) = canonical_path {
                 if let Ok(source_canonical) = source.file_path().canonicalize() {
@@ -1014,31 +2662,75 @@ impl LogStore {
     }
 
     /// Get full file paths for all loaded sources
+    ///
+    /// Sources split off one log are listed once, as that log (see
+    /// [`Self::watched_path`]).
     pub fn get_source_file_paths(&self) -> Vec<PathBuf> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let paths: IndexSet<PathBuf> = sources
+            .values()
+            .map(|source| self.watched_path(source.file_path()))
+            .collect();
+        paths.into_iter().collect()
+    }
+
+    /// Record that `part` was split off the log at `origin`.
+    pub fn set_split_origin(&self, part: PathBuf, origin: PathBuf) {
+        self.split_origins
+            .write()
+            .expect("split origins lock poisoned")
+            .insert(part, origin);
+    }
+
+    /// The file to watch and reload for a source loaded from `path`: the log
+    /// it was split off, or `path` itself.
+    pub fn watched_path(&self, path: &Path) -> PathBuf {
+        self.split_origins
+            .read()
+            .expect("split origins lock poisoned")
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Stable source IDs with the full file paths of their sources
+    pub fn get_source_ids_and_paths(&self) -> Vec<(u64, PathBuf)> {
         profiling::scope!("LogStore::sources::read");
         let sources = self.sources.read().expect("sources lock poisoned");
         sources
             .values()
-            .map(|source| source.file_path().to_path_buf())
+            .map(|source| (source.source_id(), source.file_path().to_path_buf()))
             .collect()
     }
 
     /// Remove a source by its stable source ID
     ///
     /// Note: `StoreID`s referencing the removed source will simply fail to resolve.
-    /// Other `StoreID`s remain valid since they use stable source IDs.
+    /// Other `StoreID`s remain valid since they use stable source IDs, and the
+    /// remaining sources keep their order. A loader or scorer still working on
+    /// the source is cancelled, so its lines are freed once it notices. Its
+    /// `.crab` lock is released at once; flush queued saves first (see
+    /// [`Self::flush_crab_files`]) so none of them still holds the file.
     pub fn remove_source(&self, source_id: u64) -> Option<PathBuf> {
         profiling::scope!("LogStore::sources::write");
         let mut sources = self.sources.write().expect("sources lock poisoned");
-        let removed = sources.swap_remove(&source_id)?;
-        let path = removed.file_path().to_path_buf();
+        let removed = sources.shift_remove(&source_id)?;
         drop(sources);
+        removed.request_cancel();
+        removed.release_crab();
+        let path = removed.file_path().to_path_buf();
         // Also remove scores and explain session for this source
         self.scores.remove(&source_id);
+        self.sidecar_scores.remove(&source_id);
         self.explain_sessions
             .lock()
             .expect("explain_sessions lock poisoned")
             .remove(&source_id);
+        self.previous_run
+            .write()
+            .expect("previous_run lock poisoned")
+            .take_if(|run| run.source_id == source_id);
         self.sources_version.fetch_add(1, AtomicOrdering::SeqCst);
         tracing::info!("Removed source: {}", path.display());
         Some(path)
@@ -1057,10 +2749,53 @@ impl LogStore {
             .entry(source_id)
             .or_default()
             .set_all(scores);
+        self.refresh_feedback_scores(source_id);
         // Bump version so UI knows to refresh
         self.sources_version.fetch_add(1, AtomicOrdering::SeqCst);
     }
 
+    /// Record that scoring of a source finished, so the UI can summarize it.
+    ///
+    /// `ml` tells whether the final scores came from the ML sidecar.
+    pub fn mark_scoring_finished(&self, source_id: u64, ml: bool) {
+        self.finished_scoring
+            .lock()
+            .expect("finished_scoring lock poisoned")
+            .push((source_id, ml));
+    }
+
+    /// Sources whose scoring finished since the last call, oldest first,
+    /// with whether the scores came from the ML sidecar
+    pub fn take_finished_scoring(&self) -> Vec<(u64, bool)> {
+        std::mem::take(
+            &mut *self
+                .finished_scoring
+                .lock()
+                .expect("finished_scoring lock poisoned"),
+        )
+    }
+
+    /// Lines of a source whose heuristic (or, with `ml`, sidecar) score is
+    /// above `threshold`, sorted by timestamp
+    pub fn lines_scored_above(&self, source_id: u64, threshold: f64, ml: bool) -> Vec<StoreID> {
+        profiling::scope!("LogStore::lines_scored_above");
+        let scores = if ml {
+            &self.sidecar_scores
+        } else {
+            &self.scores
+        };
+        let indices = scores
+            .get(&source_id)
+            .map(|store| store.indices_above(threshold))
+            .unwrap_or_default();
+        let mut ids: Vec<StoreID> = indices
+            .into_iter()
+            .map(|line_index| StoreID::make(source_id, line_index))
+            .collect();
+        ids.sort_by_cached_key(|id| self.adjusted_timestamp_ns(id));
+        ids
+    }
+
     /// Get the anomaly score for a specific line. Returns 0.0 if not found.
     pub fn get_score(&self, source_id: u64, line_index: usize) -> f64 {
         self.scores
@@ -1166,6 +2901,19 @@ impl LogStore {
         *self.sidecar_config.write().expect("sidecar_config lock poisoned") = Some(config);
     }
 
+    /// Limit every live source, present and future, to `limit` lines in
+    /// memory (0 = unlimited). Files are never trimmed.
+    ///
+    /// See [`SourceData::set_retention_limit`]; sources already above the limit
+    /// are trimmed on their next append.
+    pub fn set_retention_limit(&self, limit: usize) {
+        self.retention_limit.store(limit, AtomicOrdering::SeqCst);
+        let sources = self.sources.read().expect("sources lock poisoned");
+        for source in sources.values().filter(|source| source.is_live()) {
+            source.set_retention_limit(limit);
+        }
+    }
+
     /// Retrieve a clone of the sidecar scoring configuration (if set).
     pub fn sidecar_config(&self) -> Option<crate::core::log_file::ScoringConfig> {
         self.sidecar_config
@@ -1174,52 +2922,776 @@ impl LogStore {
             .clone()
     }
 
-    // ========================================================================
-    // Bookmark Management (delegates to appropriate SourceData)
-    // ========================================================================
+    // ========================================================================
+    // Bookmark Management (delegates to appropriate SourceData)
+    // ========================================================================
+
+    /// Add or update a bookmark
+    pub fn set_bookmark(&self, id: &StoreID, name: String) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        if let Some(source) = sources.get(&id.source_id) {
+            source.set_bookmark(id.line_index, name);
+        }
+    }
+
+    /// Stable IDs of the sources loaded from `path` or split off it
+    pub fn source_ids_for_path(&self, path: &Path) -> Vec<u64> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .values()
+            .filter(|source| {
+                source.file_path() == path || self.watched_path(source.file_path()) == path
+            })
+            .map(DataSourceVariant::source_id)
+            .collect()
+    }
+
+    /// Stable ID of the source loaded from `path`, if any
+    pub fn source_id_for_path(&self, path: &Path) -> Option<u64> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .values()
+            .find(|source| source.file_path() == path)
+            .map(DataSourceVariant::source_id)
+    }
+
+    /// Path of the file `source_id` was loaded from
+    pub fn source_path(&self, source_id: u64) -> Option<PathBuf> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map(|source| source.file_path().to_path_buf())
+    }
+
+    /// Number of lines loaded so far from `source_id` (0 if unknown)
+    pub fn source_len(&self, source_id: u64) -> usize {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources.get(&source_id).map_or(0, DataSourceVariant::len)
+    }
+
+    /// Format slug of the parser used for `source_id`
+    pub fn source_filetype_slug(&self, source_id: u64) -> Option<&'static str> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources.get(&source_id).map(DataSourceVariant::filetype_slug)
+    }
+
+    /// Whether `source_id` was parsed from a binary format such as pcap or DLT
+    pub fn is_binary_source(&self, source_id: u64) -> bool {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .is_some_and(DataSourceVariant::is_binary)
+    }
+
+    /// Stable IDs and file names of the sources parsed with the format `slug`
+    pub fn sources_with_filetype(&self, slug: &str) -> Vec<(u64, String)> {
+        self.get_source_filenames()
+            .into_iter()
+            .filter(|(source_id, _)| self.source_filetype_slug(*source_id) == Some(slug))
+            .collect()
+    }
+
+    /// Remove and return all bookmarks of `source_id`
+    pub fn take_bookmarks(&self, source_id: u64) -> Vec<Bookmark> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map(DataSourceVariant::take_bookmarks)
+            .unwrap_or_default()
+    }
+
+    /// Put bookmarks back into `source_id` at their stored line indices
+    pub fn restore_bookmarks(&self, source_id: u64, bookmarks: Vec<Bookmark>) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        if let Some(source) = sources.get(&source_id) {
+            source.restore_bookmarks(bookmarks);
+        }
+    }
+
+    /// Whether the background loader of `source_id` has finished
+    pub fn is_source_loaded(&self, source_id: u64) -> bool {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources.get(&source_id).is_some_and(DataSourceVariant::is_loaded)
+    }
+
+    /// Content anchor of a line (see [`LineAnchor`])
+    pub fn line_anchor(&self, id: &StoreID) -> Option<LineAnchor> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&id.source_id)
+            .and_then(|s| s.line_anchor(id.line_index))
+    }
+
+    /// Re-locate anchored lines in the source `source_id` (see [`resolve_anchors`])
+    pub fn resolve_anchors(
+        &self,
+        source_id: u64,
+        anchors: &[(usize, LineAnchor)],
+    ) -> Vec<Option<usize>> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map_or_else(|| vec![None; anchors.len()], |s| s.resolve_anchors(anchors))
+    }
+
+    /// Shareable link to a line (see [`Permalink`])
+    pub fn permalink(&self, id: &StoreID) -> Option<Permalink> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources.get(&id.source_id).and_then(|source| {
+            Some(Permalink {
+                source_key: Permalink::source_key(source.file_path()),
+                line_index: id.line_index,
+                anchor: source.line_anchor(id.line_index)?,
+            })
+        })
+    }
+
+    /// Find the line a permalink points to among the loaded sources.
+    ///
+    /// Sources whose file name matches are tried in order; within a source the
+    /// line is re-located by its anchor, so it is found even if its index moved.
+    pub fn resolve_permalink(&self, link: &Permalink) -> Option<StoreID> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .values()
+            .filter(|source| Permalink::source_key(source.file_path()) == link.source_key)
+            .find_map(|source| {
+                let line_index = source
+                    .resolve_anchors(&[(link.line_index, link.anchor)])
+                    .pop()??;
+                Some(StoreID::make(source.source_id(), line_index))
+            })
+    }
+
+    /// Hide a single line from all views
+    pub fn suppress_line(&self, id: &StoreID) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        if let Some(source) = sources.get(&id.source_id) {
+            source.add_suppression(Suppression::Line {
+                line_index: id.line_index,
+                anchor: source.line_anchor(id.line_index),
+            });
+        }
+    }
+
+    /// Normalized message templates of every source's retained lines, as
+    /// `(source_id, index of the first line, templates)`
+    pub fn all_template_keys(&self) -> Vec<(u64, usize, Vec<String>)> {
+        profiling::scope!("LogStore::all_template_keys");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .values()
+            .map(|source| {
+                let (first_index, keys) = source.template_keys();
+                (source.source_id(), first_index, keys)
+            })
+            .collect()
+    }
+
+    /// Hide every line sharing the message template of `id`, in all sources
+    pub fn suppress_template_of(&self, id: &StoreID) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let Some(template) = sources
+            .get(&id.source_id)
+            .and_then(|s| s.template_key(id.line_index))
+        else {
+            return;
+        };
+        for source in sources.values() {
+            source.add_suppression(Suppression::Template {
+                template: template.clone(),
+            });
+        }
+    }
+
+    /// Judge every line sharing the message template of `id`, in all sources.
+    ///
+    /// Expected templates score 0 and important ones 100 from now on, also
+    /// after re-scoring; `None` hands the template back to the scorer.
+    pub fn set_template_verdict(&self, id: &StoreID, verdict: Option<TemplateVerdict>) {
+        let source_ids: Vec<u64> = {
+            profiling::scope!("LogStore::sources::read");
+            let sources = self.sources.read().expect("sources lock poisoned");
+            let Some(template) = sources
+                .get(&id.source_id)
+                .and_then(|s| s.template_key(id.line_index))
+            else {
+                return;
+            };
+            for source in sources.values() {
+                source.set_template_verdict(&template, verdict);
+            }
+            sources.keys().copied().collect()
+        };
+        for source_id in source_ids {
+            self.refresh_feedback_scores(source_id);
+        }
+    }
+
+    /// Level rules of a source (see [`crate::core::level_rule`])
+    pub fn get_level_rules(&self, source_id: u64) -> Vec<LevelRule> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map(DataSourceVariant::get_level_rules)
+            .unwrap_or_default()
+    }
+
+    /// Level labels a rule for a source can assign: the values of its level
+    /// column, most severe first, or the syslog names if it has none
+    pub fn level_labels(&self, source_id: u64) -> Vec<String> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let mut counts = sources
+            .get(&source_id)
+            .map(DataSourceVariant::facet_counts)
+            .unwrap_or_default();
+        drop(sources);
+        let Some(values) = LEVEL_COLUMNS
+            .iter()
+            .find_map(|column| counts.remove(column))
+        else {
+            return [
+                Severity::Emergency,
+                Severity::Alert,
+                Severity::Critical,
+                Severity::Error,
+                Severity::Warning,
+                Severity::Notice,
+                Severity::Info,
+                Severity::Debug,
+            ]
+            .map(|severity| severity.name().to_string())
+            .to_vec();
+        };
+        let mut labels: Vec<String> = values.into_keys().collect();
+        // Unknown labels go last
+        labels.sort_by_cached_key(|label| {
+            let severity = Severity::from_label(label);
+            (severity.is_none(), severity, label.clone())
+        });
+        labels
+    }
+
+    /// Replace the level rules of a source and score its lines again, so the
+    /// scores reflect the corrected severities
+    pub fn set_level_rules(
+        self: &Arc<Self>,
+        source_id: u64,
+        rules: Vec<LevelRule>,
+        toast: crate::ui::ProgressToastHandle,
+    ) {
+        let source = {
+            profiling::scope!("LogStore::sources::read");
+            let sources = self.sources.read().expect("sources lock poisoned");
+            sources.get(&source_id).cloned()
+        };
+        let Some(source) = source else {
+            toast.dismiss();
+            return;
+        };
+        source.set_level_rules(rules);
+        self.sources_version.fetch_add(1, AtomicOrdering::SeqCst);
+        source.spawn_rescore(toast, Arc::clone(self));
+    }
+
+    /// Score `source_id` again in the background, e.g. after the scorer
+    /// weights changed. Sources still loading are skipped, they are scored
+    /// with the current weights once loaded.
+    pub fn rescore(self: &Arc<Self>, source_id: u64, toast: crate::ui::ProgressToastHandle) {
+        let source = {
+            profiling::scope!("LogStore::sources::read");
+            let sources = self.sources.read().expect("sources lock poisoned");
+            sources.get(&source_id).cloned()
+        };
+        match source {
+            Some(source) if source.is_loaded() => source.spawn_rescore(toast, Arc::clone(self)),
+            _ => toast.dismiss(),
+        }
+    }
+
+    /// The user's verdict on the message template of `id`, if any
+    pub fn template_verdict(&self, id: &StoreID) -> Option<TemplateVerdict> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let source = sources.get(&id.source_id)?;
+        let feedback = source.get_template_feedback();
+        if feedback.is_empty() {
+            return None;
+        }
+        let template = source.template_key(id.line_index)?;
+        feedback
+            .into_iter()
+            .find(|f| f.template == template)
+            .map(|f| f.verdict)
+    }
+
+    /// Recompute the scores fixed by the template feedback of `source_id`
+    fn refresh_feedback_scores(&self, source_id: u64) {
+        let overrides = {
+            profiling::scope!("LogStore::sources::read");
+            let sources = self.sources.read().expect("sources lock poisoned");
+            let Some(source) = sources.get(&source_id) else {
+                return;
+            };
+            source.feedback_scores()
+        };
+        self.scores
+            .entry(source_id)
+            .or_default()
+            .set_overrides(overrides);
+        self.sources_version.fetch_add(1, AtomicOrdering::SeqCst);
+    }
+
+    /// All suppressions of the session, oldest first.
+    ///
+    /// Line suppressions are paired with their source; a template suppressed
+    /// in several sources is listed once with `None`.
+    pub fn get_all_suppressions(&self) -> Vec<(Option<u64>, Suppression)> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let mut all: Vec<(Option<u64>, Suppression)> = Vec::new();
+        for source in sources.values() {
+            for suppression in source.get_suppressions() {
+                match suppression {
+                    Suppression::Line { .. } => all.push((Some(source.source_id()), suppression)),
+                    Suppression::Template { .. } => {
+                        if !all.iter().any(|(_, s)| *s == suppression) {
+                            all.push((None, suppression));
+                        }
+                    }
+                }
+            }
+        }
+        all
+    }
+
+    /// Undo a suppression returned by [`Self::get_all_suppressions`]
+    pub fn remove_suppression(&self, source_id: Option<u64>, suppression: &Suppression) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        for (id, source) in sources.iter() {
+            if source_id.is_none_or(|source_id| source_id == *id) {
+                source.remove_suppression(suppression);
+            }
+        }
+    }
+
+    /// Set the category of the bookmark at `id`
+    pub fn set_bookmark_category(&self, id: &StoreID, category: String) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        if let Some(source) = sources.get(&id.source_id) {
+            source.set_bookmark_category(id.line_index, category);
+        }
+    }
+
+    /// Replace the attachment list of the bookmark at `id`
+    pub fn set_bookmark_attachments(&self, id: &StoreID, attachments: Vec<String>) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        if let Some(source) = sources.get(&id.source_id) {
+            source.set_bookmark_attachments(id.line_index, attachments);
+        }
+    }
+
+    /// Folder the bookmark attachments of `source_id` are stored in
+    pub fn attachments_dir(&self, source_id: u64) -> Option<PathBuf> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map(DataSourceVariant::attachments_dir)
+    }
+
+    /// Remove a bookmark
+    pub fn remove_bookmark(&self, id: &StoreID) -> Option<Bookmark> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&id.source_id)
+            .and_then(|s| s.remove_bookmark(id.line_index))
+    }
+
+    /// Clock correction the user applied to a source, in milliseconds
+    pub fn clock_offset_ms(&self, source_id: u64) -> i64 {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map_or(0, DataSourceVariant::clock_offset_ms)
+    }
+
+    /// Virtual segments of a source, ordered by start line
+    pub fn get_segments(&self, source_id: u64) -> Vec<Segment> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map(DataSourceVariant::get_segments)
+            .unwrap_or_default()
+    }
+
+    /// Replace the virtual segments of a source (see [`SourceData::set_segments`])
+    pub fn set_segments(&self, source_id: u64, segments: Vec<Segment>) {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        if let Some(source) = sources.get(&source_id) {
+            source.set_segments(segments);
+        }
+    }
+
+    /// Name of the segment the line belongs to, if its source is segmented
+    pub fn segment_name(&self, id: &StoreID) -> Option<String> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&id.source_id)
+            .and_then(|s| s.segment_name(id.line_index))
+    }
+
+    /// Lines of a source at which a new segment should start, see
+    /// [`segment::find_split_points`]
+    pub fn find_split_points(&self, source_id: u64, gap_ms: i64) -> Vec<usize> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&source_id)
+            .map(|s| s.find_split_points(gap_ms))
+            .unwrap_or_default()
+    }
+
+    /// Drive all open calibration windows across every source (one per frame).
+    ///
+    /// Returns `true` if any source applied a new offset (caller should set `modified = true`).
+    pub fn render_file_states(&self, ui: &egui::Ui) -> bool {
+        profiling::scope!("LogStore::render_file_states");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources.values().fold(false, |acc, s| {
+            let applied = s.render_file_state(ui);
+            if applied && self.is_recording_activity() {
+                s.record_activity(ActivityEntry::now(Activity::TimeOffsetApplied));
+            }
+            applied || acc
+        })
+    }
+
+    /// A source whose file state asked to be parsed again, e.g. after the
+    /// column mapping of a CSV file changed. Further requests stay pending.
+    pub fn take_reparse_request(&self) -> Option<u64> {
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .values()
+            .find(|source| source.take_reparse_request())
+            .map(DataSourceVariant::source_id)
+    }
+
+    // ========================================================================
+    // Run History
+    // ========================================================================
+
+    /// Cache summaries of loaded files in `history` to compare later runs of
+    /// the same log against; with `None` nothing is recorded
+    pub fn set_run_history(&self, history: Option<RunHistory>) {
+        *self.run_history.write().expect("run_history lock poisoned") = history;
+    }
+
+    /// The cache summaries of loaded files go to, if any
+    pub fn run_history(&self) -> Option<RunHistory> {
+        self.run_history
+            .read()
+            .expect("run_history lock poisoned")
+            .clone()
+    }
+
+    /// Remember `run` as the earlier run to compare against, unless a source
+    /// loaded before already found one
+    pub fn set_previous_run(&self, run: PreviousRun) {
+        self.previous_run
+            .write()
+            .expect("previous_run lock poisoned")
+            .get_or_insert(run);
+    }
+
+    /// The earlier run to draw behind the histogram, if any
+    pub fn previous_run(&self) -> Option<PreviousRun> {
+        self.previous_run
+            .read()
+            .expect("previous_run lock poisoned")
+            .clone()
+    }
+
+    // ========================================================================
+    // Activity Log
+    // ========================================================================
+
+    /// Enable or disable recording of analysis actions
+    pub fn set_activity_recording(&self, enabled: bool) {
+        self.record_activity.store(enabled, AtomicOrdering::SeqCst);
+    }
+
+    /// Whether analysis actions are currently recorded
+    pub fn is_recording_activity(&self) -> bool {
+        self.record_activity.load(AtomicOrdering::SeqCst)
+    }
+
+    /// Record `action` in the activity log if recording is enabled.
+    ///
+    /// Per-line actions go to the source of the line at `id`; session-wide
+    /// actions (see [`Activity::is_session_wide`]) go to every source.
+    pub fn record_activity(&self, id: Option<&StoreID>, action: Activity) {
+        if !self.is_recording_activity() {
+            return;
+        }
+        // One timestamp for all copies so the log can tell them apart from repeats
+        let entry = ActivityEntry::now(action);
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        match id {
+            Some(id) if !entry.action.is_session_wide() => {
+                if let Some(source) = sources.get(&id.source_id) {
+                    source.record_activity(entry);
+                }
+            }
+            _ => {
+                for source in sources.values() {
+                    source.record_activity(entry.clone());
+                }
+            }
+        }
+    }
+
+    /// The activity log of the whole session, oldest first.
+    ///
+    /// Entries are paired with the name of their source; session-wide
+    /// entries, which every source keeps a copy of, are listed once with `None`.
+    pub fn get_activity_log(&self) -> Vec<(Option<String>, ActivityEntry)> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let mut log: Vec<(Option<String>, ActivityEntry)> = Vec::new();
+        for source in sources.values() {
+            let name = source
+                .file_path()
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned());
+            for entry in source.get_activity() {
+                if !entry.action.is_session_wide() {
+                    log.push((name.clone(), entry));
+                } else if !log.iter().any(|(_, e)| *e == entry) {
+                    log.push((None, entry));
+                }
+            }
+        }
+        log.sort_by_key(|(_, entry)| entry.time);
+        log
+    }
+
+    /// Render type-specific context menu items for the line at `id`.
+    ///
+    /// Returns `true` if the source was found. Must be called inside an egui
+    /// `context_menu` closure.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn render_typed_context_menu_items(&self, id: &StoreID, ui: &mut egui::Ui) -> bool {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let Some(variant) = sources.get(&id.source_id) else {
+            return false;
+        };
+        variant.render_context_menu(id.line_index, ui);
+        true
+    }
+
+    /// The component that logged a line: the first of its [`COMPONENT_FACETS`]
+    pub fn component(&self, id: &StoreID) -> Option<String> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let facets = sources.get(&id.source_id)?.get_facets(id.line_index)?;
+        COMPONENT_FACETS.iter().find_map(|component| {
+            facets
+                .iter()
+                .find(|(column, _)| column == component)
+                .map(|(_, value)| value.clone())
+        })
+    }
+
+    /// Level of a line as its format writes it (the first of its
+    /// [`LEVEL_COLUMNS`]), else the name of its severity
+    pub fn level(&self, id: &StoreID) -> Option<String> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let source = sources.get(&id.source_id)?;
+        let facets = source.get_facets(id.line_index).unwrap_or_default();
+        LEVEL_COLUMNS
+            .iter()
+            .find_map(|level| {
+                facets
+                    .iter()
+                    .find(|(column, _)| column == level)
+                    .map(|(_, value)| value.clone())
+            })
+            .or_else(|| {
+                let severity = source.get_log_line(id.line_index)?.severity?;
+                Some(severity.name().to_string())
+            })
+    }
 
-    /// Add or update a bookmark
-    pub fn set_bookmark(&self, id: &StoreID, name: String) {
+    /// Get the parsed key/value fields of a line for the detail panel.
+    ///
+    /// Returns an empty list for unknown IDs and for formats without structured fields.
+    pub fn get_fields(&self, id: &StoreID) -> Vec<(String, String)> {
         profiling::scope!("LogStore::sources::read");
         let sources = self.sources.read().expect("sources lock poisoned");
-        if let Some(source) = sources.get(&id.source_id) {
-            source.set_bookmark(id.line_index, name);
-        }
+        sources
+            .get(&id.source_id)
+            .and_then(|s| s.get_fields(id.line_index))
+            .unwrap_or_default()
     }
 
-    /// Remove a bookmark
-    pub fn remove_bookmark(&self, id: &StoreID) -> Option<Bookmark> {
+    /// Get the reformatted raw text of a line for the detail panel.
+    ///
+    /// `None` for unknown IDs and for formats that show their raw line as is.
+    pub fn get_pretty_raw(&self, id: &StoreID) -> Option<String> {
         profiling::scope!("LogStore::sources::read");
         let sources = self.sources.read().expect("sources lock poisoned");
         sources
             .get(&id.source_id)
-            .and_then(|s| s.remove_bookmark(id.line_index))
+            .and_then(|s| s.get_pretty_raw(id.line_index))
     }
 
-    /// Drive all open calibration windows across every source (one per frame).
+    /// Get the captured bytes of a line for the detail panel's hex dump.
     ///
-    /// Returns `true` if any source applied a new offset (caller should set `modified = true`).
-    pub fn render_file_states(&self, ui: &egui::Ui) -> bool {
-        profiling::scope!("LogStore::render_file_states");
+    /// `None` for unknown IDs and for text formats.
+    pub fn get_payload(&self, id: &StoreID) -> Option<Arc<[u8]>> {
+        profiling::scope!("LogStore::sources::read");
         let sources = self.sources.read().expect("sources lock poisoned");
         sources
+            .get(&id.source_id)
+            .and_then(|s| s.get_payload(id.line_index))
+    }
+
+    /// Names of all discrete columns offered by the loaded sources, plus `Source`.
+    pub fn facet_columns(&self) -> Vec<&'static str> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let mut columns: Vec<&'static str> = sources
             .values()
-            .fold(false, |acc, s| s.render_file_state(ui) || acc)
+            .flat_map(DataSourceVariant::facet_columns)
+            .collect();
+        columns.sort_unstable();
+        columns.dedup();
+        columns.insert(0, SOURCE_COLUMN);
+        columns
     }
 
-    /// Render type-specific context menu items for the line at `id`.
+    /// Key under which the log table column layout is stored in
+    /// [`crate::config::GlobalConfig::column_layouts`].
     ///
-    /// Returns `true` if the source was found. Must be called inside an egui
-    /// `context_menu` closure.
-    #[allow(clippy::significant_drop_tightening)]
-    pub fn render_typed_context_menu_items(&self, id: &StoreID, ui: &mut egui::Ui) -> bool {
+    /// The filetype slug when all sources share one format, `"mixed"` otherwise
+    /// (also for an empty store).
+    pub fn column_layout_key(&self) -> &'static str {
         profiling::scope!("LogStore::sources::read");
         let sources = self.sources.read().expect("sources lock poisoned");
-        let Some(variant) = sources.get(&id.source_id) else {
-            return false;
+        let mut slugs = sources.values().map(DataSourceVariant::filetype_slug);
+        let Some(first) = slugs.next() else {
+            return "mixed";
         };
-        variant.render_context_menu(id.line_index, ui);
-        true
+        if slugs.all(|slug| slug == first) {
+            first
+        } else {
+            "mixed"
+        }
+    }
+
+    /// Observed values of a discrete column with their line counts, most frequent first.
+    ///
+    /// The `Source` column lists the paths of the loaded source files.
+    pub fn facet_values(&self, column: &str) -> Vec<(String, usize)> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let mut merged: HashMap<String, usize> = HashMap::new();
+        for source in sources.values() {
+            if column == SOURCE_COLUMN {
+                let path = source.file_path().to_string_lossy().into_owned();
+                *merged.entry(path).or_default() += source.len();
+            } else if let Some(values) = source.facet_counts().remove(column) {
+                for (value, count) in values {
+                    *merged.entry(value).or_default() += count;
+                }
+            }
+        }
+        let mut values: Vec<(String, usize)> = merged.into_iter().collect();
+        values.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        values
+    }
+
+    /// Keep only the IDs whose discrete column values pass `filter`.
+    ///
+    /// Order is preserved, so timestamp-sorted input stays sorted.
+    pub fn filter_by_columns(&self, ids: Vec<StoreID>, filter: &ColumnFilter) -> Vec<StoreID> {
+        profiling::scope!("LogStore::filter_by_columns");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        let paths: HashMap<u64, String> = sources
+            .values()
+            .map(|source| {
+                let path = source.file_path().to_string_lossy().into_owned();
+                (source.source_id(), path)
+            })
+            .collect();
+        ids.into_par_iter()
+            .filter(|id| {
+                let (Some(source), Some(path)) =
+                    (sources.get(&id.source_id), paths.get(&id.source_id))
+                else {
+                    return false;
+                };
+                let facets = source.get_facets(id.line_index).unwrap_or_default();
+                filter.accepts(path, &facets)
+            })
+            .collect()
+    }
+
+    /// Keep the lines whose parsed fields or discrete columns match all `terms`.
+    pub fn filter_by_fields(
+        &self,
+        ids: Vec<StoreID>,
+        terms: &[FieldTerm],
+        case_sensitive: bool,
+    ) -> Vec<StoreID> {
+        profiling::scope!("LogStore::filter_by_fields");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        ids.into_par_iter()
+            .filter(|id| {
+                let Some(source) = sources.get(&id.source_id) else {
+                    return false;
+                };
+                let fields = source.get_fields(id.line_index).unwrap_or_default();
+                let facets = source.get_facets(id.line_index).unwrap_or_default();
+                terms.iter().all(|term| {
+                    let pairs = fields
+                        .iter()
+                        .map(|(key, value)| (key.as_str(), value.as_str()))
+                        .chain(facets.iter().map(|(key, value)| (*key, value.as_str())));
+                    term.matches(pairs, case_sensitive)
+                })
+            })
+            .collect()
     }
 
     /// Check if a line has a bookmark
@@ -1241,6 +3713,9 @@ impl LogStore {
             .map(|b| BookmarkData {
                 store_id: *id,
                 name: b.name,
+                category: b.category,
+                attachments: b.attachments,
+                orphaned: b.orphaned,
             })
     }
 
@@ -1262,21 +3737,51 @@ impl LogStore {
                             line_index: bookmark.line_index,
                         },
                         name: bookmark.name,
+                        category: bookmark.category,
+                        attachments: bookmark.attachments,
+                        orphaned: bookmark.orphaned,
                     })
             })
             .collect()
     }
 
     /// Save all sources' .crab files
+    ///
+    /// Returns once the sessions are snapshotted; the files are written in the
+    /// background (see [`Self::flush_crab_files`]).
     pub fn save_all_crab_files(&self, filters: &[SavedFilter], highlights: &[SavedHighlight]) {
         profiling::scope!("LogStore::save_all_crab_files");
         profiling::scope!("LogStore::sources::read");
         let sources = self.sources.read().expect("sources lock poisoned");
         for source in sources.values() {
-            source.save_crab_file(filters, highlights);
+            source.save_crab_file(&self.crab_writer, filters, highlights);
         }
     }
 
+    /// Whether a source waits for another instance to release its .crab file
+    pub fn has_contended_crab_files(&self) -> bool {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources.values().any(DataSourceVariant::is_crab_contended)
+    }
+
+    /// Take over the .crab files other instances released since they were
+    /// opened read-only here (see [`SourceData::take_over_crab`])
+    pub fn take_over_crab_files(&self) -> Vec<Takeover> {
+        profiling::scope!("LogStore::sources::read");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .values()
+            .filter_map(DataSourceVariant::take_over_crab)
+            .collect()
+    }
+
+    /// Block until every queued .crab save is on disk
+    pub fn flush_crab_files(&self) {
+        profiling::scope!("LogStore::flush_crab_files");
+        self.crab_writer.flush();
+    }
+
     // ========================================================================
     // Line Queries
     // ========================================================================
@@ -1286,6 +3791,20 @@ impl LogStore {
     /// Uses the pre-sorted `by_timestamp` index within each source, then merges.
     /// Returns `StoreIDs` for matching lines, sorted by timestamp.
     pub fn get_matching_ids<F>(&self, predicate: F) -> Vec<StoreID>
+    where
+        F: Fn(&str, &str) -> bool + Sync,
+    {
+        self.get_matching_ids_within(&ScanHints::default(), predicate)
+    }
+
+    /// Like [`Self::get_matching_ids`], but skips the storage chunks of every
+    /// source that `hints` rule out: chunks holding no line within the time
+    /// range, or whose trigram bloom cannot contain the literal.
+    ///
+    /// Pruning works per chunk, so lines just outside the time range may
+    /// still be returned; callers needing an exact range check the
+    /// timestamps. The literal must be implied by `predicate`.
+    pub fn get_matching_ids_within<F>(&self, hints: &ScanHints, predicate: F) -> Vec<StoreID>
     where
         F: Fn(&str, &str) -> bool + Sync,
     {
@@ -1301,7 +3820,7 @@ impl LogStore {
                 .map(|source| {
                     let source_id = source.source_id();
                     source
-                        .filter_sorted_by_search(&predicate)
+                        .filter_sorted_by_search(&predicate, hints)
                         .into_iter()
                         .map(|line_index| StoreID {
                             source_id,
@@ -1333,13 +3852,12 @@ impl LogStore {
         let mut iters: Vec<_> = sources.into_iter().map(IntoIterator::into_iter).collect();
 
         // Use a min-heap: (timestamp, source_idx, store_id) - Reverse for min-heap behavior
-        let mut heap: BinaryHeap<Reverse<(chrono::DateTime<Local>, usize, StoreID)>> =
-            BinaryHeap::new();
+        let mut heap: BinaryHeap<Reverse<(i64, usize, StoreID)>> = BinaryHeap::new();
 
         // Initialize heap with first element from each non-empty source
         for (src_idx, iter) in iters.iter_mut().enumerate() {
             if let Some((id, adjusted_time)) =
-                iter.find_map(|id| self.adjusted_timestamp(&id).map(|time| (id, time)))
+                iter.find_map(|id| self.adjusted_timestamp_ns(&id).map(|time| (id, time)))
             {
                 heap.push(Reverse((adjusted_time, src_idx, id)));
             }
@@ -1351,7 +3869,7 @@ impl LogStore {
 
             // Push the next element from this source onto the heap
             if let Some((next_id, adjusted_time)) =
-                iters[src_idx].find_map(|id| self.adjusted_timestamp(&id).map(|time| (id, time)))
+                iters[src_idx].find_map(|id| self.adjusted_timestamp_ns(&id).map(|time| (id, time)))
             {
                 heap.push(Reverse((adjusted_time, src_idx, next_id)));
             }
@@ -1375,14 +3893,38 @@ impl LogStore {
             .and_then(|s| s.adjusted_timestamp(id.line_index))
     }
 
-    /// Find the position of the line closest to a target timestamp in a sorted list.
-    /// Returns the index position within `filtered_indices`.
+    /// Get the fully-calibrated timestamp for the line identified by `id`, in
+    /// epoch nanoseconds.
+    ///
+    /// The ordering key for lines across sources; cheaper than
+    /// [`Self::adjusted_timestamp`] because no local time is constructed.
+    pub fn adjusted_timestamp_ns(&self, id: &StoreID) -> Option<i64> {
+        profiling::scope!("LogStore::adjusted_timestamp_ns");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&id.source_id)
+            .and_then(|s| s.adjusted_timestamp_ns(id.line_index))
+    }
+
+    /// Like [`Self::adjusted_timestamp_ns`] in epoch milliseconds, for
+    /// bucketing and time windows.
+    pub fn adjusted_timestamp_ms(&self, id: &StoreID) -> Option<i64> {
+        profiling::scope!("LogStore::adjusted_timestamp_ms");
+        let sources = self.sources.read().expect("sources lock poisoned");
+        sources
+            .get(&id.source_id)
+            .and_then(|s| s.adjusted_timestamp_ms(id.line_index))
+    }
+
+    /// Find the position of the line closest to a target timestamp (epoch
+    /// milliseconds) in a sorted list. Returns the index position within
+    /// `filtered_indices`.
     ///
     /// Assumes `filtered_indices` are sorted by timestamp.
     pub fn find_closest_line_position_by_time(
         &self,
         filtered_indices: &[StoreID],
-        target_time: chrono::DateTime<Local>,
+        target_ms: i64,
     ) -> Option<usize> {
         profiling::scope!("LogStore::find_closest_line_position_by_time");
         if filtered_indices.is_empty() {
@@ -1391,8 +3933,8 @@ impl LogStore {
 
         // Binary search to find insertion point
         let idx = filtered_indices.partition_point(|line_idx| {
-            self.adjusted_timestamp(line_idx)
-                .is_some_and(|ts| ts < target_time)
+            self.adjusted_timestamp_ms(line_idx)
+                .is_some_and(|ts| ts < target_ms)
         });
 
         // Compare neighbors around the insertion point to find the closest
@@ -1400,11 +3942,11 @@ impl LogStore {
             0 => Some(0),
             i if i >= filtered_indices.len() => Some(filtered_indices.len() - 1),
             i => {
-                let before_ts = self.adjusted_timestamp(&filtered_indices[i - 1])?;
-                let after_ts = self.adjusted_timestamp(&filtered_indices[i])?;
+                let before_ts = self.adjusted_timestamp_ms(&filtered_indices[i - 1])?;
+                let after_ts = self.adjusted_timestamp_ms(&filtered_indices[i])?;
 
-                let dist_before = (target_time - before_ts).abs();
-                let dist_after = (after_ts - target_time).abs();
+                let dist_before = (target_ms - before_ts).abs();
+                let dist_after = (after_ts - target_ms).abs();
 
                 if dist_before <= dist_after {
                     Some(i - 1)
@@ -1468,4 +4010,1098 @@ pub struct Bookmark {
     /// Line index within the source (not a global `StoreID`)
     pub line_index: usize,
     pub name: String,
+    /// Category the bookmark was filed under; empty for none
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    /// Timestamp and message hash of the bookmarked line, used to find it
+    /// again when the file was regrown, truncated or rotated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<LineAnchor>,
+    /// File names of attached screenshots or notes, see [`crate::core::attachments`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+    /// Set when `anchor` could not be matched on load
+    #[serde(skip)]
+    pub orphaned: bool,
+}
+
+impl Bookmark {
+    /// Fold `other`, which ended up on the same line, into this bookmark.
+    ///
+    /// Different names are joined as `"<self> / <other>"` and the attachments
+    /// combined, so neither annotation is lost.
+    pub(crate) fn absorb(&mut self, other: Self) {
+        if self.name.is_empty() {
+            self.name = other.name;
+        } else if !other.name.is_empty() && other.name != self.name {
+            self.name = format!("{} / {}", self.name, other.name);
+        }
+        if self.category.is_empty() {
+            self.category = other.category;
+        }
+        for attachment in other.attachments {
+            if !self.attachments.contains(&attachment) {
+                self.attachments.push(attachment);
+            }
+        }
+        if self.anchor.is_none() {
+            self.anchor = other.anchor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::LogFileLoader;
+    use crate::ui::ToastManager;
+
+    /// Load `paths` into a fresh store and wait until every file is read
+    fn load(paths: &[PathBuf]) -> (Arc<LogStore>, Vec<u64>) {
+        let toasts = ToastManager::new(egui::Context::default());
+        let store = LogStore::new();
+        let ids: Vec<u64> = paths
+            .iter()
+            .map(|path| {
+                let toast = toasts.create_progress_toast("Loading", path.display().to_string());
+                let (variant, _, _) = LogFileLoader::load_file(
+                    path,
+                    &toast,
+                    &toasts.sender(),
+                    &GlobalFileConfig::default(),
+                    &store,
+                )
+                .expect("file should open");
+                let id = variant.source_id();
+                store.add_source(variant);
+                id
+            })
+            .collect();
+        while !ids.iter().all(|id| store.is_source_loaded(*id)) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        (store, ids)
+    }
+
+    #[test]
+    fn test_remove_source_keeps_other_sources() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths: Vec<PathBuf> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(format!("{name}.log"));
+                std::fs::write(
+                    &path,
+                    format!("11-20 14:23:45.123  1234  5678 I Tag: line from {name}\n"),
+                )
+                .expect("write");
+                path
+            })
+            .collect();
+        let (store, ids) = load(&paths);
+        let kept = StoreID::make(ids[2], 0);
+        store.set_bookmark(&kept, "kept".to_string());
+
+        assert_eq!(store.remove_source(ids[0]), Some(paths[0].clone()));
+        assert!(store.remove_source(ids[0]).is_none());
+
+        // The remaining sources keep their order and their IDs
+        assert_eq!(
+            store.get_source_file_paths(),
+            vec![paths[1].clone(), paths[2].clone()]
+        );
+        assert!(store.get_by_id(&StoreID::make(ids[0], 0)).is_none());
+        assert!(store
+            .get_by_id(&kept)
+            .is_some_and(|line| line.message.ends_with("line from c")));
+        assert_eq!(store.get_all_bookmarks().len(), 1);
+    }
+
+    #[test]
+    fn test_template_counts_span_sources() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let a = dir.path().join("a.log");
+        std::fs::write(
+            &a,
+            "11-20 14:23:45.123  1234  5678 I Tag: retry 1 of 3\n\
+             11-20 14:23:46.123  1234  5678 E Tag: disk failure\n",
+        )
+        .expect("write");
+        let b = dir.path().join("b.log");
+        std::fs::write(&b, "11-20 14:23:47.123  1234  5678 I Tag: retry 2 of 3\n").expect("write");
+        let (store, ids) = load(&[a, b]);
+
+        let counts = crate::core::template_counts::TemplateCounts::compute(&store);
+        assert_eq!(counts.store_version, store.version());
+        assert_eq!(counts.count(&StoreID::make(ids[0], 0)), Some(2));
+        assert_eq!(counts.count(&StoreID::make(ids[0], 1)), Some(1));
+        assert_eq!(counts.count(&StoreID::make(ids[1], 0)), Some(2));
+        assert_eq!(counts.count(&StoreID::make(ids[1], 1)), None);
+    }
+
+    #[test]
+    fn test_journaled_changes_survive_a_lost_save() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let content = "11-20 14:23:45.123  1234  5678 I Tag: hello\n";
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, content).expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let (store, ids) = load(&[path]);
+        store.set_bookmark(&StoreID::make(ids[0], 0), "journaled".to_string());
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        assert!(!crate::core::crab_writer::journal_path(&crab_path).exists());
+        let saved = std::fs::read_to_string(&crab_path).expect("read crab file");
+
+        // A copy of the log whose app died after journaling the bookmark but
+        // before its (empty) .crab file was written
+        let copy = dir.path().join("copy.log");
+        std::fs::write(&copy, content).expect("write");
+        let copy_crab = dir.path().join("copy.log.crab");
+        std::fs::write(&copy_crab, "").expect("write");
+        crate::core::crab_writer::append_journal(&copy_crab, &saved).expect("journal");
+
+        let (store, _) = load(&[copy]);
+        let bookmarks = store.get_all_bookmarks();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].name, "journaled");
+    }
+
+    #[test]
+    fn test_large_bookmark_sets_load_in_batches() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let (store, ids) = load(std::slice::from_ref(&path));
+        store.set_bookmark(&StoreID::make(ids[0], 0), "bookmark".to_string());
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+
+        // A copy whose script bookmarked many more lines than a single batch
+        let copy = dir.path().join("copy.log");
+        std::fs::copy(&path, &copy).expect("copy");
+        let copy_crab = dir.path().join("copy.log.crab");
+        let count = BOOKMARK_BATCH * 2 + 500;
+        let mut session: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&crab_path).expect("read crab file"))
+                .expect("parse crab file");
+        let bookmarks: Vec<serde_json::Value> = (0..count)
+            .map(|i| serde_json::json!({ "line_index": i, "name": format!("line {i}") }))
+            .collect();
+        session["bookmarks"] = serde_json::Value::Array(bookmarks);
+        std::fs::write(&copy_crab, session.to_string()).expect("write");
+
+        // Opening the file leaves the bookmarks for the loader to read
+        let toasts = ToastManager::new(egui::Context::default());
+        let (source, _, _) = SourceData::<crate::filetype::logcat::LogcatFileType>::new(
+            copy.clone(),
+            Arc::default(),
+            &toasts.sender(),
+        );
+        assert!(source.get_bookmarks().is_empty());
+        source.set_bookmark(0, "set while loading".to_string());
+        source
+            .insert_pending_bookmarks()
+            .expect("bookmarks should load");
+        assert_eq!(source.get_bookmarks().len(), count);
+        assert_eq!(
+            source.get_bookmark(0).map(|b| b.name),
+            Some("set while loading".to_string())
+        );
+        drop(source);
+
+        let (store, _) = load(&[copy]);
+        assert_eq!(store.get_all_bookmarks().len(), count);
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&copy_crab).expect("read crab file"))
+                .expect("parse crab file");
+        assert_eq!(saved["bookmarks"].as_array().map(Vec::len), Some(count));
+    }
+
+    #[test]
+    fn test_bookmark_category_survives_renames_and_reloads() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+
+        let (store, ids) = load(std::slice::from_ref(&path));
+        let id = StoreID::make(ids[0], 0);
+        store.set_bookmark(&id, "first look".to_string());
+        store.set_bookmark_category(&id, "noise".to_string());
+        store.set_bookmark(&id, "second look".to_string());
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        drop(store);
+
+        let (store, ids) = load(std::slice::from_ref(&path));
+        let bookmark = store
+            .get_bookmark(&StoreID::make(ids[0], 0))
+            .expect("bookmark");
+        assert_eq!(bookmark.name, "second look");
+        assert_eq!(bookmark.category, "noise");
+    }
+
+    #[test]
+    fn test_reanchored_bookmark_merges_into_bookmark_on_its_line() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        let line = |s: u32, msg: &str| format!("11-20 14:23:{s:02}.123  1234  5678 I Tag: {msg}\n");
+        std::fs::write(
+            &path,
+            [line(1, "boot"), line(2, "crash"), line(3, "end")].concat(),
+        )
+        .expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let (store, ids) = load(std::slice::from_ref(&path));
+        store.set_bookmark(&StoreID::make(ids[0], 1), "moved".to_string());
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        drop(store);
+
+        // A bookmark from an older release, without anchor, on the first line
+        let mut session: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&crab_path).expect("read crab file"))
+                .expect("parse crab file");
+        session["bookmarks"]
+            .as_array_mut()
+            .expect("bookmarks")
+            .push(serde_json::json!({
+                "line_index": 0,
+                "name": "kept",
+                "attachments": ["shot.png"]
+            }));
+        std::fs::write(&crab_path, session.to_string()).expect("write");
+
+        // The first line is gone, so the anchored bookmark moves onto line 0
+        std::fs::write(&path, [line(2, "crash"), line(3, "end")].concat()).expect("write");
+        let (store, ids) = load(std::slice::from_ref(&path));
+        let bookmarks = store.get_all_bookmarks();
+        assert_eq!(bookmarks.len(), 1);
+        let bookmark = store
+            .get_bookmark(&StoreID::make(ids[0], 0))
+            .expect("merged bookmark");
+        assert_eq!(bookmark.name, "kept / moved");
+        assert_eq!(bookmark.attachments, vec!["shot.png".to_string()]);
+    }
+
+    #[test]
+    fn test_line_suppressions_follow_their_line() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        let line = |s: u32, msg: &str| format!("11-20 14:23:{s:02}.123  1234  5678 I Tag: {msg}\n");
+        std::fs::write(
+            &path,
+            [line(1, "boot"), line(2, "noise"), line(3, "spam"), line(4, "end")].concat(),
+        )
+        .expect("write");
+
+        let (store, ids) = load(std::slice::from_ref(&path));
+        store.suppress_line(&StoreID::make(ids[0], 1));
+        store.suppress_line(&StoreID::make(ids[0], 2));
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        drop(store);
+
+        // A line was prepended and the spam is gone
+        std::fs::write(
+            &path,
+            [line(0, "start"), line(1, "boot"), line(2, "noise"), line(4, "end")].concat(),
+        )
+        .expect("write");
+        let (store, _) = load(std::slice::from_ref(&path));
+        let suppressed: Vec<usize> = store
+            .get_all_suppressions()
+            .into_iter()
+            .filter_map(|(_, s)| match s {
+                Suppression::Line { line_index, .. } => Some(line_index),
+                Suppression::Template { .. } => None,
+            })
+            .collect();
+        assert_eq!(suppressed, vec![2]);
+        let visible: Vec<usize> = store
+            .get_matching_ids(|_, _| true)
+            .iter()
+            .map(StoreID::line_index_within_source)
+            .collect();
+        assert_eq!(visible, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_retention_limit_only_trims_live_sources() {
+        use crate::filetype::logcat::LogcatFileType;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        let content: String = (0..20)
+            .map(|i| format!("11-20 14:23:{i:02}.123  1234  5678 I Tag: line {i}\n"))
+            .collect();
+        std::fs::write(&path, content).expect("write");
+
+        // Files keep all their lines
+        let toasts = ToastManager::new(egui::Context::default());
+        let store = LogStore::new();
+        store.set_retention_limit(8);
+        let toast = toasts.create_progress_toast("Loading", "app.log");
+        let (variant, _, _) = LogFileLoader::load_file(
+            &path,
+            &toast,
+            &toasts.sender(),
+            &GlobalFileConfig::default(),
+            &store,
+        )
+        .expect("file should open");
+        let id = variant.source_id();
+        store.add_source(variant);
+        while !store.is_source_loaded(id) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert!(store.get_by_id(&StoreID::make(id, 0)).is_some());
+        store.remove_source(id);
+
+        // Live sources drop their oldest lines with the bookmarks on them
+        let (source, _, _) =
+            SourceData::<LogcatFileType>::new(path.clone(), Arc::default(), &toasts.sender());
+        let mut input =
+            LogcatFileType::open(&path, Default::default(), Arc::clone(&source.file_state))
+                .expect("open");
+        let lines = input.read(20).expect("read");
+        source.set_bookmark(0, "evicted".to_string());
+        source.set_bookmark(15, "kept".to_string());
+        source.add_suppression(Suppression::Line {
+            line_index: 1,
+            anchor: None,
+        });
+        source.mark_live();
+        source.set_retention_limit(8);
+        source.append_lines(lines);
+        assert_eq!(source.first_index(), 13);
+        assert!(source.get_bookmark(0).is_none());
+        assert!(source.get_bookmark(15).is_some());
+        assert!(source.get_suppressions().is_empty());
+        assert!(source.get_as_log_line(3).is_none());
+    }
+
+    #[test]
+    fn test_unreadable_bookmarks_are_not_overwritten() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let (store, _) = load(std::slice::from_ref(&path));
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        drop(store);
+
+        let mut session: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&crab_path).expect("read crab file"))
+                .expect("parse crab file");
+        session["bookmarks"] = serde_json::json!([
+            { "line_index": 0, "name": "fine" },
+            { "line_index": "not a number", "name": "broken" }
+        ]);
+        let written = session.to_string();
+        std::fs::write(&crab_path, &written).expect("write");
+
+        let (store, _) = load(std::slice::from_ref(&path));
+        store.save_all_crab_files(&[], &[]);
+        store.flush_crab_files();
+        assert_eq!(
+            std::fs::read_to_string(&crab_path).expect("read crab file"),
+            written
+        );
+    }
+
+    #[test]
+    fn test_second_instance_reads_journaled_changes_of_the_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let (first, first_ids) = load(std::slice::from_ref(&path));
+        first.set_bookmark(&StoreID::make(first_ids[0], 0), "journaled".to_string());
+        first.save_all_crab_files(&[], &[]);
+        first.flush_crab_files();
+        let saved = std::fs::read_to_string(&crab_path).expect("read crab file");
+
+        // The first instance journaled the bookmark but has not written its
+        // .crab file yet
+        std::fs::write(&crab_path, "").expect("write");
+        crate::core::crab_writer::append_journal(&crab_path, &saved).expect("journal");
+
+        let (second, _) = load(std::slice::from_ref(&path));
+        assert!(second.has_contended_crab_files());
+        let bookmarks = second.get_all_bookmarks();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].name, "journaled");
+    }
+
+    #[test]
+    fn test_second_instance_takes_over_released_session() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let (first, first_ids) = load(std::slice::from_ref(&path));
+        assert!(crate::core::crab_lock::lock_path(&crab_path).exists());
+        let (second, second_ids) = load(std::slice::from_ref(&path));
+        assert!(second.has_contended_crab_files());
+
+        // Both change the session while the first one holds the lock
+        first.set_bookmark(&StoreID::make(first_ids[0], 0), "first".to_string());
+        first.save_all_crab_files(&[], &[]);
+        first.flush_crab_files();
+        second.set_bookmark(&StoreID::make(second_ids[0], 0), "second".to_string());
+        second.save_all_crab_files(&[], &[]);
+        second.flush_crab_files();
+        assert!(second.take_over_crab_files().is_empty(), "still locked");
+
+        // Scoring may hold the first source for a moment after it is dropped
+        drop(first);
+        let mut takeovers = Vec::new();
+        for _ in 0..500 {
+            takeovers = second.take_over_crab_files();
+            if !takeovers.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(takeovers.len(), 1);
+        assert!(takeovers[0].is_conflict());
+        let changed = takeovers[0].changed.as_ref().expect("changed on disk");
+        assert_eq!(changed.bookmarks[0].name, "first");
+        assert!(!second.has_contended_crab_files());
+
+        second.save_all_crab_files(&[], &[]);
+        second.flush_crab_files();
+        let saved = std::fs::read_to_string(&crab_path).expect("read crab file");
+        assert!(saved.contains("\"second\""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lock_on_a_replaced_crab_file_is_not_kept() {
+        use crate::filetype::logcat::LogcatFileType;
+        use fs2::FileExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crab_path = dir.path().join("app.log.crab");
+        std::fs::write(&crab_path, "{}").expect("write");
+        // Opened just before a save renames a new file over the path
+        let stale = File::open(&crab_path).expect("open");
+        let tmp_path = dir.path().join("app.log.crab.tmp");
+        std::fs::write(&tmp_path, "{}").expect("write");
+        std::fs::rename(&tmp_path, &crab_path).expect("rename");
+        stale.try_lock_exclusive().expect("lock the replaced file");
+
+        assert!(!SourceData::<LogcatFileType>::is_file_at(&stale, &crab_path).expect("stat"));
+        let locked = SourceData::<LogcatFileType>::try_lock_crab(&crab_path).expect("lock");
+        assert!(SourceData::<LogcatFileType>::is_file_at(&locked, &crab_path).expect("stat"));
+    }
+
+    #[test]
+    fn test_containers_are_watched_through_their_log() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let origin = dir.path().join("compose.log");
+        std::fs::write(&origin, "web-1  | one\ndb-1   | two\n").expect("write");
+        let prefix = crate::core::container_split::detect_container_log(&origin)
+            .expect("detect")
+            .expect("multi-container");
+        let parts = crate::core::container_split::split_container_log(
+            &origin,
+            prefix,
+            &dir.path().join("split"),
+        )
+        .expect("split");
+
+        let (store, ids) = load(&parts);
+        for part in &parts {
+            store.set_split_origin(part.clone(), origin.clone());
+        }
+
+        assert_eq!(store.get_source_file_paths(), vec![origin.clone()]);
+        assert_eq!(store.source_ids_for_path(&origin), ids);
+        assert!(store.contains_file(&origin));
+        assert_eq!(store.source_path(ids[1]), Some(parts[1].clone()));
+    }
+
+    #[test]
+    fn test_segments_offset_and_hide_their_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("reboots.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:00.000  1234  5678 I Tag: first boot a\n\
+             11-20 14:00:01.000  1234  5678 I Tag: first boot b\n\
+             11-20 13:00:00.000  1234  5678 I Tag: second boot a\n\
+             11-20 13:00:01.000  1234  5678 I Tag: second boot b\n",
+        )
+        .expect("write");
+        let (store, ids) = load(&[path]);
+        let source_id = ids[0];
+        let order = |store: &LogStore| -> Vec<usize> {
+            store
+                .get_matching_ids(|_, _| true)
+                .iter()
+                .map(StoreID::line_index_within_source)
+                .collect()
+        };
+        assert_eq!(order(&store), vec![2, 3, 0, 1]);
+
+        // The clock reset starts the second segment
+        assert_eq!(store.find_split_points(source_id, 0), vec![2]);
+        let mut second = Segment::new("Boot 2".to_string(), 2);
+        second.offset_ms = 2 * 60 * 60 * 1000;
+        store.set_segments(
+            source_id,
+            vec![second, Segment::new("Boot 1".to_string(), 0)],
+        );
+        assert_eq!(order(&store), vec![0, 1, 2, 3]);
+        assert_eq!(
+            store.segment_name(&StoreID::make(source_id, 3)).as_deref(),
+            Some("Boot 2")
+        );
+        let shifted = store.adjusted_timestamp_ms(&StoreID::make(source_id, 2));
+        let unshifted = store.adjusted_timestamp_ms(&StoreID::make(source_id, 0));
+        assert_eq!(shifted, unshifted.map(|ts| ts + 1000 * 60 * 60));
+
+        // Hiding a segment only removes its own lines
+        let mut segments = store.get_segments(source_id);
+        assert_eq!(segments[0].name, "Boot 1");
+        segments[0].hidden = true;
+        store.set_segments(source_id, segments);
+        assert_eq!(order(&store), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_lines_scored_above_are_in_timestamp_order() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("scored.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:02.000  1234  5678 I Tag: late\n\
+             11-20 14:00:00.000  1234  5678 I Tag: early\n\
+             11-20 14:00:01.000  1234  5678 I Tag: calm\n",
+        )
+        .expect("write");
+        let (store, ids) = load(&[path]);
+        let source_id = ids[0];
+        // Let the heuristic scoring of the load finish first
+        while !store.take_finished_scoring().contains(&(source_id, false)) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        store.set_scores(source_id, &[95.0, 91.0, 40.0]);
+        let lines: Vec<usize> = store
+            .lines_scored_above(source_id, 90.0, false)
+            .iter()
+            .map(StoreID::line_index_within_source)
+            .collect();
+        assert_eq!(lines, vec![1, 0]);
+        assert!(store.lines_scored_above(source_id, 90.0, true).is_empty());
+    }
+
+    #[test]
+    fn test_sources_interleave_within_a_millisecond() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let write = |name: &str, first_us: u32| {
+            let path = dir.path().join(format!("{name}.log"));
+            let log: String = (0..10)
+                .map(|i| format!("[    1.{:06}] {name} {i}\n", first_us + 2 * i))
+                .collect();
+            std::fs::write(&path, log).expect("write");
+            path
+        };
+        let (store, ids) = load(&[write("a", 0), write("b", 1)]);
+
+        // Every line lies within the same millisecond; only the microseconds
+        // tell the sources apart
+        let merged: Vec<u64> = store
+            .get_matching_ids(|_, _| true)
+            .iter()
+            .map(|id| id.source_id)
+            .collect();
+        let alternating: Vec<u64> = (0..20).map(|i| ids[i % 2]).collect();
+        assert_eq!(merged, alternating);
+    }
+
+    #[test]
+    fn test_template_verdicts_override_scores() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("feedback.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:00.000  1234  5678 I Tag: heartbeat 1\n\
+             11-20 14:00:01.000  1234  5678 I Tag: heartbeat 2\n\
+             11-20 14:00:02.000  1234  5678 I Tag: disk full\n",
+        )
+        .expect("write");
+        let (store, ids) = load(&[path]);
+        let source_id = ids[0];
+        while !store.take_finished_scoring().contains(&(source_id, false)) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        let heartbeat = StoreID::make(source_id, 0);
+        let disk_full = StoreID::make(source_id, 2);
+
+        store.set_template_verdict(&heartbeat, Some(TemplateVerdict::Expected));
+        store.set_template_verdict(&disk_full, Some(TemplateVerdict::Important));
+        // Verdicts outlive re-scoring
+        store.set_scores(source_id, &[80.0, 70.0, 10.0]);
+        assert_eq!(
+            store.template_verdict(&StoreID::make(source_id, 1)),
+            Some(TemplateVerdict::Expected)
+        );
+        let above = |threshold| -> Vec<usize> {
+            store
+                .lines_scored_above(source_id, threshold, false)
+                .iter()
+                .map(StoreID::line_index_within_source)
+                .collect()
+        };
+        assert_eq!(above(5.0), vec![2]);
+
+        store.set_template_verdict(&heartbeat, None);
+        assert_eq!(store.template_verdict(&heartbeat), None);
+        assert_eq!(above(50.0), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_level_rules_correct_level_column_and_severity() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("levels.log");
+        // Logcat is only detected from ten matching lines on
+        let mut content: String = (0..9)
+            .map(|i| format!("11-20 14:00:0{i}.000  1234  5678 I Tag: link up\n"))
+            .collect();
+        content.push_str("11-20 14:00:09.000  1234  5678 I Tag: flash write failed\n");
+        std::fs::write(&path, content).expect("write");
+        let (store, ids) = load(&[path]);
+        let source_id = ids[0];
+        while !store.take_finished_scoring().contains(&(source_id, false)) {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(store.level_labels(source_id), vec!["I"]);
+
+        let rules = vec![LevelRule {
+            pattern: "failed$".to_string(),
+            level: "E".to_string(),
+        }];
+        let toasts = ToastManager::new(egui::Context::default());
+        let toast = toasts.create_progress_toast("Rescoring", "");
+        store.set_level_rules(source_id, rules.clone(), toast);
+        assert_eq!(store.get_level_rules(source_id), rules);
+        assert_eq!(
+            store.facet_values("Level"),
+            vec![("I".to_string(), 9), ("E".to_string(), 1)]
+        );
+        let failed = store.get_by_id(&StoreID::make(source_id, 9)).expect("line");
+        assert_eq!(failed.severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_scan_prints_matching_lines_as_json() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("scan.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:00.000  1234  5678 I Tag: link up\n\
+             11-20 14:00:01.000  1234  5678 E Tag: Flash write failed\n\
+             11-20 14:00:02.000  1234  5678 E Tag: flash retry failed\n",
+        )
+        .expect("write");
+        let options = crate::export::ScanOptions {
+            filter: Some(fancy_regex::Regex::new("(?i)flash").expect("regex")),
+            exclude: Some(fancy_regex::Regex::new("retry").expect("regex")),
+            json: true,
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        assert_eq!(scan_dispatch(&path, &options, &mut out).expect("scan"), 1);
+        let record: serde_json::Value = serde_json::from_slice(&out).expect("one JSON record");
+        assert_eq!(record["line_number"], 2);
+        assert!(record["message"]
+            .as_str()
+            .is_some_and(|m| m.ends_with("Tag: Flash write failed")));
+    }
+
+    #[test]
+    fn test_time_scoped_search_skips_chunks_outside_the_range() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("scoped.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:00.000  1234  5678 I Tag: a\n\
+             11-20 14:00:05.000  1234  5678 I Tag: b\n",
+        )
+        .expect("write");
+        let (store, ids) = load(&[path]);
+        let ts = |idx| {
+            store
+                .adjusted_timestamp_ms(&StoreID::make(ids[0], idx))
+                .expect("line exists")
+        };
+        let (first, last) = (ts(0), ts(1));
+
+        let within = |range| {
+            let hints = ScanHints {
+                time_range: Some(range),
+                literal: None,
+            };
+            store.get_matching_ids_within(&hints, |_, _| true).len()
+        };
+        assert_eq!(within((first, last)), 2);
+        assert_eq!(within((last, last + 1000)), 2, "the chunk overlaps");
+        assert_eq!(within((first - 2000, first - 1000)), 0);
+        assert_eq!(within((last + 1, last + 1000)), 0);
+    }
+
+    #[test]
+    fn test_literal_search_skips_chunks_without_the_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("literal.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:00.000  1234  5678 I Tag: Connected to AP\n\
+             11-20 14:00:05.000  1234  5678 I Tag: scan done\n",
+        )
+        .expect("write");
+        let (store, _) = load(&[path]);
+
+        let count = |literal: &str| {
+            let hints = ScanHints {
+                time_range: None,
+                literal: Some(literal.to_string()),
+            };
+            store.get_matching_ids_within(&hints, |_, _| true).len()
+        };
+        assert_eq!(count("connected"), 2, "the chunk may contain the text");
+        assert_eq!(count("SCAN"), 2);
+        assert_eq!(count("bluetooth"), 0);
+    }
+
+    #[test]
+    fn test_single_line_appends_share_the_bloom_until_a_batch_is_due() {
+        use crate::filetype::logcat::LogcatFileType;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("follow.log");
+        std::fs::write(
+            &path,
+            "11-20 14:00:00.000  1234  5678 I Tag: Connected to AP\n\
+             11-20 14:00:05.000  1234  5678 I Tag: scan done\n",
+        )
+        .expect("write");
+        let toasts = ToastManager::new(egui::Context::default());
+        let (source, _, _) =
+            SourceData::<LogcatFileType>::new(path.clone(), Arc::default(), &toasts.sender());
+        let mut input =
+            LogcatFileType::open(&path, Default::default(), Arc::clone(&source.file_state))
+                .expect("open");
+        let lines = input.read(2).expect("read");
+        let count = |literal: &str| {
+            let hints = ScanHints {
+                time_range: None,
+                literal: Some(literal.to_string()),
+            };
+            source.filter_sorted_by_search(&|_, _| true, &hints).len()
+        };
+
+        source.append_lines(lines[..1].to_vec());
+        let bloom = Arc::clone(&source.snapshot.load().chunk_blooms);
+        source.append_lines(lines[1..].to_vec());
+        assert!(Arc::ptr_eq(&bloom, &source.snapshot.load().chunk_blooms));
+        assert_eq!(count("bluetooth"), 2, "lines not in the bloom are searched");
+
+        source.mark_loaded();
+        assert_eq!(count("bluetooth"), 0);
+        assert_eq!(count("scan"), 2);
+    }
+
+    #[test]
+    fn test_literal_search_follows_toggled_someip_sd_decoding() {
+        // One UDP packet to a multicast group off the SD port, carrying an
+        // empty SOME/IP-SD message: decoded only once toggled on
+        let mut sd = vec![0xff, 0xff, 0x81, 0x00, 0, 0, 0, 20, 0, 0, 0, 1, 1, 1, 2, 0];
+        sd.extend([0xc0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let mut udp = vec![0x77, 0x25, 0x77, 0x25];
+        udp.extend(u16::try_from(8 + sd.len()).expect("fits").to_be_bytes());
+        udp.extend([0, 0]);
+        udp.extend(&sd);
+        let mut ip = vec![0x45, 0];
+        ip.extend(u16::try_from(20 + udp.len()).expect("fits").to_be_bytes());
+        ip.extend([0, 0, 0, 0, 1, 17, 0, 0, 10, 0, 0, 1, 239, 0, 0, 1]);
+        ip.extend(&udp);
+        let mut frame = vec![0x01, 0, 0x5e, 0, 0, 1, 2, 0, 0, 0, 0, 1, 0x08, 0];
+        frame.extend(&ip);
+        let frame_len = u32::try_from(frame.len()).expect("fits").to_le_bytes();
+        let mut pcap = vec![0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0];
+        pcap.extend([0; 8]);
+        pcap.extend(65535u32.to_le_bytes());
+        pcap.extend(1u32.to_le_bytes());
+        pcap.extend(1_700_000_000u32.to_le_bytes());
+        pcap.extend(0u32.to_le_bytes());
+        pcap.extend(frame_len);
+        pcap.extend(frame_len);
+        pcap.extend(&frame);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("sd.pcap");
+        std::fs::write(&path, pcap).expect("write");
+        let (store, ids) = load(&[path]);
+        let Some(DataSourceVariant::Pcap(source)) = store
+            .sources
+            .read()
+            .expect("sources lock poisoned")
+            .get(&ids[0])
+            .cloned()
+        else {
+            panic!("pcap source");
+        };
+
+        let count = || {
+            let hints = ScanHints {
+                time_range: None,
+                literal: Some("SOME/IP-SD".to_string()),
+            };
+            store
+                .get_matching_ids_within(&hints, |display, _| display.contains("SOME/IP-SD"))
+                .len()
+        };
+        assert_eq!(count(), 0);
+        assert!(source
+            .file_state
+            .toggle_someip_sd("239.0.0.1:30501".to_string()));
+        assert_eq!(count(), 1, "the stale bloom must not hide the decoded line");
+        source.rebuild_time_index();
+        assert_eq!(count(), 1);
+    }
+
+    /// Write `log` to a file in `dir`
+    fn write_synthetic(dir: &Path, log: &crate::core::synthetic::SyntheticLog) -> PathBuf {
+        let path = dir.join("synthetic.log");
+        let mut file = std::io::BufWriter::new(File::create(&path).expect("create"));
+        log.write(&mut file).expect("write");
+        std::io::Write::flush(&mut file).expect("flush");
+        path
+    }
+
+    #[test]
+    fn test_synthetic_anomalies_score_highest() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = crate::core::synthetic::SyntheticLog {
+            lines: 2000,
+            anomalies: 4,
+            bursts: 1,
+            burst_lines: 300,
+            ..Default::default()
+        };
+        let path = write_synthetic(dir.path(), &log);
+
+        let mut out = Vec::new();
+        export_scores_dispatch(&path, &mut out).expect("export scores");
+        let mut records: Vec<(f64, u64)> = out
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_slice(line).expect("record");
+                (
+                    record["score"].as_f64().expect("score"),
+                    record["line_number"].as_u64().expect("line number"),
+                )
+            })
+            .collect();
+        assert_eq!(records.len(), log.lines);
+        records.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mut top: Vec<u64> = records[..log.anomalies].iter().map(|r| r.1).collect();
+        top.sort_unstable();
+        let anomalies: Vec<u64> = log.anomaly_lines().iter().map(|&i| i as u64 + 1).collect();
+        assert_eq!(top, anomalies);
+    }
+
+    #[test]
+    fn test_synthetic_burst_fills_one_histogram_bucket() {
+        use crate::core::histogram_worker::{
+            HistogramCacheKey, HistogramRequest, HistogramWorker, NUM_BUCKETS,
+        };
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = crate::core::synthetic::SyntheticLog {
+            lines: 2000,
+            anomalies: 0,
+            bursts: 1,
+            burst_lines: 300,
+            ..Default::default()
+        };
+        let (store, _) = load(&[write_synthetic(dir.path(), &log)]);
+        let hints = ScanHints {
+            time_range: None,
+            literal: None,
+        };
+
+        let worker = HistogramWorker::new();
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        worker.handle().send_request(HistogramRequest {
+            key: HistogramCacheKey {
+                store_version: store.version(),
+                search_str: String::new(),
+                exclude_str: String::new(),
+                case_sensitive: false,
+                zoom_range_ms: None,
+                color_by_ml_score: false,
+                min_anomaly_score: None,
+            },
+            color_by_ml_score: false,
+            min_anomaly_score: None,
+            filter_id: 0,
+            store: Arc::clone(&store),
+            filtered_indices: store.get_matching_ids_within(&hints, |_, _| true),
+            zoom_range: None,
+            result_tx,
+        });
+        let result = result_rx
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("histogram result");
+        let buckets = result.data.expect("histogram data").buckets;
+        assert_eq!(buckets.len(), NUM_BUCKETS);
+        assert_eq!(buckets.iter().sum::<usize>(), log.lines);
+
+        // The burst takes 0.3 s in the middle of a log of about 170 s, which
+        // is at most two buckets; the others hold about 17 lines each
+        let fullest = buckets.iter().copied().max().unwrap_or_default();
+        assert!(fullest >= log.burst_lines / 2, "{buckets:?}");
+        assert!(
+            (0..NUM_BUCKETS)
+                .filter(|&i| buckets[i] > 50)
+                .all(|i| (45..55).contains(&i)),
+            "{buckets:?}"
+        );
+        assert!(buckets[45..55].iter().sum::<usize>() >= log.burst_lines);
+    }
+
+    #[test]
+    fn test_suppressed_template_disappears_from_filter_tab() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        let content: String = ["connect 1 failed", "boot done", "connect 22 failed"]
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| format!("11-20 14:23:{i:02}.123  1234  5678 I Tag: {msg}\n"))
+            .collect();
+        std::fs::write(&path, content).expect("write");
+        let (store, ids) = load(std::slice::from_ref(&path));
+
+        let worker = crate::core::filter_worker::FilterWorker::new();
+        let mut search = crate::core::SearchState::new();
+        let mut filter = || {
+            search.ensure_cache_valid(&store, &worker.handle());
+            let start = std::time::Instant::now();
+            while !search.check_filter_results() {
+                assert!(start.elapsed().as_secs() < 10, "filter timed out");
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+            search
+                .get_filtered_indices_cached()
+                .iter()
+                .map(StoreID::line_index_within_source)
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(filter(), vec![0, 1, 2]);
+
+        store.suppress_template_of(&StoreID::make(ids[0], 0));
+        assert_eq!(filter(), vec![1]);
+
+        let (source_id, template) = store
+            .get_all_suppressions()
+            .pop()
+            .expect("template suppression");
+        store.remove_suppression(source_id, &template);
+        assert_eq!(filter(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_or_errors_search_follows_levels_and_keywords() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let logcat = dir.path().join("app.log");
+        let content: String = ["I Tag: boot done", "E Tag: disk gone", "I Tag: app crash"]
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| format!("11-20 14:23:{i:02}.123  1234  5678 {msg}\n"))
+            .collect();
+        std::fs::write(&logcat, content).expect("write");
+        let syslog = dir.path().join("messages");
+        std::fs::write(
+            &syslog,
+            "<6>Nov 20 14:24:00 host app: link up\n<3>Nov 20 14:24:01 host app: link down\n",
+        )
+        .expect("write");
+        let (store, ids) = load(&[logcat, syslog]);
+
+        let worker = crate::core::filter_worker::FilterWorker::new();
+        let mut search = crate::core::SearchState::new();
+        search.search_text = r"\b\d+\s+\d+\s+[EF]\s".to_string();
+        search.or_errors = true;
+        search.ensure_cache_valid(&store, &worker.handle());
+        let start = std::time::Instant::now();
+        while !search.check_filter_results() {
+            assert!(start.elapsed().as_secs() < 10, "filter timed out");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(
+            *search.get_filtered_indices_cached(),
+            vec![
+                StoreID::make(ids[0], 1),
+                StoreID::make(ids[0], 2),
+                StoreID::make(ids[1], 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_synthetic_filter_finds_every_line_of_a_template() {
+        use crate::core::synthetic::LineKind;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = crate::core::synthetic::SyntheticLog {
+            lines: 3000,
+            anomalies: 8,
+            ..Default::default()
+        };
+        let (store, _) = load(&[write_synthetic(dir.path(), &log)]);
+
+        // "Service" is in the tags of two templates and in one anomaly message
+        let mut search = crate::core::SearchState::new();
+        search.search_text = "service".to_string();
+        search.exclude_text = "Battery".to_string();
+        let worker = crate::core::filter_worker::FilterWorker::new();
+        search.ensure_cache_valid(&store, &worker.handle());
+        let start = std::time::Instant::now();
+        while !search.check_filter_results() {
+            assert!(start.elapsed().as_secs() < 10, "filter timed out");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let found: Vec<usize> = search
+            .get_filtered_indices_cached()
+            .iter()
+            .map(StoreID::line_index_within_source)
+            .collect();
+        let expected: Vec<usize> = log
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| match line.kind {
+                LineKind::Template(index) => index == 7,
+                LineKind::Anomaly(n) => n % 4 == 2,
+            })
+            .map(|(i, _)| i)
+            .collect();
+        assert!(expected.len() > 20);
+        assert_eq!(found, expected);
+    }
 }
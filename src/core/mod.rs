@@ -1,21 +1,53 @@
 // pub mod async_cache;
+pub mod attachments;
+pub mod bookmark_suggestion;
+pub mod bookmark_template;
+pub mod chunk_index;
 pub mod chunked_loader;
+pub mod column_filter;
+pub mod container_split;
+pub mod correlation_id;
+pub mod crab_lock;
+pub mod crab_writer;
+pub mod decompress;
+pub mod eta;
+pub mod field_query;
+pub mod file_watcher;
 pub mod filter_worker;
+pub mod hex_dump;
 pub mod histogram_worker;
+pub mod keyword_list;
+pub mod level_rule;
+pub mod line_anchor;
+pub mod line_buffer;
 pub mod log_file;
 pub mod log_store;
+pub mod net_endpoint;
+pub mod permalink;
+pub mod raw_lines;
 mod queue_map;
+pub mod redaction;
+pub mod rotated_logs;
+pub mod run_history;
 pub mod search_rule;
 pub mod search_state;
+pub mod segment;
 pub mod session;
+pub mod shell_command;
+pub mod slow_filter;
+pub mod synthetic;
+pub mod table_copy;
+pub mod template_counts;
+pub mod text_arena;
 // pub mod task_worker;
 
 // pub use async_cache::AsyncCache;
 pub use chunked_loader::ChunkedLoader;
+pub use column_filter::ColumnFilter;
 pub use filter_worker::{FilterWorker, FilterWorkerHandle};
 pub use log_file::{LogFileLoader, ScoringConfig};
 pub use log_store::LogStore;
 pub use search_rule::SearchRule;
 pub use search_state::SearchState;
-pub use session::{CrabFilters, SavedFilter, SavedHighlight, SavedSearch};
+pub use session::{CrabFilters, CrabProject, SavedFilter, SavedHighlight, SavedSearch};
 // pub use task_worker::{TaskWorker, TaskWorkerHandle};
@@ -0,0 +1,161 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Detection of network endpoints (`IP:port`, URLs) in log messages.
+//!
+//! Application logs often name the peer of a connection. When a packet
+//! capture of the same run is loaded, such an endpoint is enough to find the
+//! packets of that flow, whose messages read `TCP 10.0.0.1:443 → ...`.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// An IP address and port named in a log message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoint {
+    pub addr: IpAddr,
+    pub port: u16,
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.addr {
+            IpAddr::V4(addr) => write!(f, "{addr}:{}", self.port),
+            IpAddr::V6(addr) => write!(f, "[{addr}]:{}", self.port),
+        }
+    }
+}
+
+impl Endpoint {
+    /// Regex matching packet messages from or to this endpoint.
+    ///
+    /// Packet messages write IPv6 addresses without brackets, so the address
+    /// and port are only delimited by the surrounding characters.
+    #[must_use]
+    pub fn packet_pattern(&self) -> String {
+        format!(
+            r"(?<![\w.:]){}:{}(?![\d:])",
+            fancy_regex::escape(&self.addr.to_string()),
+            self.port
+        )
+    }
+}
+
+/// Endpoints named in `message`, in order of appearance, deduplicated.
+///
+/// Recognized are `IPv4:port`, `[IPv6]:port` and URLs whose host is an IP
+/// address; a URL without a port gets its scheme's default port. Bare
+/// addresses are ignored, since version numbers like `1.2.3.4` look the same.
+#[must_use]
+pub fn endpoints(message: &str) -> Vec<Endpoint> {
+    let mut found: Vec<Endpoint> = Vec::new();
+    let tokens = message.split(|c: char| c.is_whitespace() || "=,;\"'()<>".contains(c));
+    for endpoint in tokens.filter_map(parse_endpoint) {
+        if !found.contains(&endpoint) {
+            found.push(endpoint);
+        }
+    }
+    found
+}
+
+fn parse_endpoint(token: &str) -> Option<Endpoint> {
+    let (scheme, rest) = token
+        .split_once("://")
+        .map_or((None, token), |(scheme, rest)| (Some(scheme), rest));
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host_port)| host_port)
+        .trim_end_matches(['.', ':']);
+
+    let (addr, port) = if let Some(bracketed) = host_port.strip_prefix('[') {
+        let (addr, port) = bracketed.split_once(']')?;
+        (
+            IpAddr::V6(addr.parse::<Ipv6Addr>().ok()?),
+            port.strip_prefix(':'),
+        )
+    } else {
+        let (addr, port) = host_port
+            .split_once(':')
+            .map_or((host_port, None), |(addr, port)| (addr, Some(port)));
+        (IpAddr::V4(addr.parse::<Ipv4Addr>().ok()?), port)
+    };
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port(scheme?)?,
+    };
+    Some(Endpoint { addr, port })
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        "mqtt" => Some(1883),
+        "mqtts" => Some(8883),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shown(message: &str) -> Vec<String> {
+        endpoints(message).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn finds_addresses_with_ports_and_urls() {
+        assert_eq!(
+            shown("connect to 10.0.0.7:8080 failed, retrying http://192.168.1.2/api?x=1"),
+            vec!["10.0.0.7:8080", "192.168.1.2:80"]
+        );
+        assert_eq!(
+            shown("peer=[fe80::1]:5353 url=wss://user@[2001:db8::2]:9000/ws"),
+            vec!["[fe80::1]:5353", "[2001:db8::2]:9000"]
+        );
+    }
+
+    #[test]
+    fn ignores_bare_addresses_hostnames_and_duplicates() {
+        assert!(
+            endpoints("version 1.2.3.4, host example.com:443, https://example.com/").is_empty()
+        );
+        assert_eq!(shown("10.0.0.1:53 then 10.0.0.1:53."), vec!["10.0.0.1:53"]);
+    }
+
+    #[test]
+    fn packet_pattern_matches_only_the_exact_endpoint() {
+        let pattern = |text: &str| {
+            let endpoint = endpoints(text)[0];
+            fancy_regex::Regex::new(&endpoint.packet_pattern()).unwrap()
+        };
+        let v4 = pattern("10.0.0.1:80");
+        assert!(v4
+            .is_match("TCP 10.0.0.1:80 → 10.0.0.2:5000 [SYN]")
+            .unwrap());
+        assert!(!v4.is_match("TCP 10.0.0.1:8080 → 10.0.0.2:5000").unwrap());
+        assert!(!v4.is_match("TCP 110.0.0.1:80 → 10.0.0.2:5000").unwrap());
+
+        let v6 = pattern("[fe80::1]:443");
+        assert!(v6.is_match("TCP fe80::2:5000 → fe80::1:443 [ACK]").unwrap());
+        assert!(!v6.is_match("TCP fe80::11:443 → fe80::2:5000").unwrap());
+    }
+}
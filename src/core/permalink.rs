@@ -0,0 +1,138 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Shareable links to a single log line.
+//!
+//! A [`Permalink`] names a line by the file it came from and its position, so
+//! it can be pasted into another `LogCrab` instance that has the same files
+//! loaded. Files are identified by a hash of their file name rather than the
+//! full path, which usually differs between machines. A [`LineAnchor`] is
+//! embedded as well so the line is still found when the file was re-read and
+//! its index shifted.
+//!
+//! The text form is `logcrab:<file hash>:<line>:<message hash>:<timestamp ms>`.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::core::line_anchor::{content_hash, LineAnchor};
+
+const SCHEME: &str = "logcrab";
+
+/// A copyable reference to one line of one source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permalink {
+    /// [`Permalink::source_key`] of the file the line belongs to
+    pub source_key: u32,
+    /// 0-based line index within the file, used as a hint when resolving
+    pub line_index: usize,
+    pub anchor: LineAnchor,
+}
+
+impl Permalink {
+    /// Short, machine-independent key of a source file, derived from its name
+    #[must_use]
+    pub fn source_key(path: &Path) -> u32 {
+        let name = path
+            .file_name()
+            .map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy());
+        #[allow(clippy::cast_possible_truncation)]
+        let key = content_hash(&name) as u32;
+        key
+    }
+
+    /// Parse the text form, ignoring surrounding whitespace.
+    ///
+    /// Returns `None` if `text` is not a `LogCrab` permalink.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().split(':');
+        if parts.next()? != SCHEME {
+            return None;
+        }
+        let source_key = u32::from_str_radix(parts.next()?, 16).ok()?;
+        let line_index = parts.next()?.parse().ok()?;
+        let content_hash = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let timestamp_ms = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            source_key,
+            line_index,
+            anchor: LineAnchor {
+                timestamp_ms,
+                content_hash,
+            },
+        })
+    }
+}
+
+impl fmt::Display for Permalink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{SCHEME}:{:08x}:{}:{:016x}:{}",
+            self.source_key, self.line_index, self.anchor.content_hash, self.anchor.timestamp_ms
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permalink() -> Permalink {
+        Permalink {
+            source_key: Permalink::source_key(Path::new("/var/log/syslog")),
+            line_index: 1234,
+            anchor: LineAnchor {
+                timestamp_ms: -5_000,
+                content_hash: content_hash("connection reset"),
+            },
+        }
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let link = permalink();
+        assert_eq!(Permalink::parse(&link.to_string()), Some(link));
+        assert_eq!(Permalink::parse(&format!("  {link}\n")), Some(link));
+    }
+
+    #[test]
+    fn source_key_ignores_directory() {
+        assert_eq!(
+            Permalink::source_key(Path::new("/var/log/syslog")),
+            Permalink::source_key(Path::new("C:\\logs/syslog"))
+        );
+        assert_ne!(
+            Permalink::source_key(Path::new("/var/log/syslog")),
+            Permalink::source_key(Path::new("/var/log/messages"))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_text() {
+        let text = permalink().to_string();
+        assert_eq!(Permalink::parse(&text.replacen("logcrab", "http", 1)), None);
+        assert_eq!(Permalink::parse(&format!("{text}:1")), None);
+        assert_eq!(Permalink::parse("logcrab:zz:1:0:0"), None);
+        assert_eq!(Permalink::parse(""), None);
+    }
+}
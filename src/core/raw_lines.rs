@@ -0,0 +1,224 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Reading the raw lines of a text file straight from disk.
+//!
+//! The parsed store only holds what the parser made of a line. When a parser
+//! mangles or splits records, the bytes as they are in the file are needed.
+//! [`RawLineIndex`] remembers the byte offset of every [`STRIDE`]th line, so
+//! lines around any line number are found with one seek and a short scan.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Distance in lines between two remembered offsets
+const STRIDE: usize = 1024;
+
+/// One line as stored in the file, including its line terminator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawLine {
+    /// 1-based line number
+    pub number: usize,
+    /// Byte offset of the line's first byte
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+}
+
+/// Sparse line-offset index of a text file
+#[derive(Debug)]
+pub struct RawLineIndex {
+    path: PathBuf,
+    /// File length the index was built for
+    len: u64,
+    /// Byte offset of line `k * STRIDE + 1`
+    checkpoints: Vec<u64>,
+}
+
+impl RawLineIndex {
+    /// Scan `path` once and remember where every [`STRIDE`]th line starts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file cannot be read, and an error of kind
+    /// [`io::ErrorKind::Unsupported`] for compressed files, whose lines have
+    /// no byte offset on disk.
+    pub fn build(path: &Path) -> io::Result<Self> {
+        profiling::scope!("RawLineIndex::build");
+        if let Some(compression) = crate::core::decompress::detect_file(path)? {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                compression.name(),
+            ));
+        }
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut checkpoints = vec![0];
+        let mut offset = 0_u64;
+        let mut line = 0_usize;
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf)?;
+            if read == 0 {
+                break;
+            }
+            offset += read as u64;
+            line += 1;
+            if line.is_multiple_of(STRIDE) {
+                checkpoints.push(offset);
+            }
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            len: offset,
+            checkpoints,
+        })
+    }
+
+    /// File the index was built for
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file changed length since the index was built
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        !std::fs::metadata(&self.path).is_ok_and(|meta| meta.len() == self.len)
+    }
+
+    /// Lines `number - context ..= number + context` (1-based), as far as
+    /// they exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file cannot be read.
+    pub fn read_around(&self, number: usize, context: usize) -> io::Result<Vec<RawLine>> {
+        let first = number.saturating_sub(context).max(1);
+        let last = number.saturating_add(context);
+        let checkpoint = ((first - 1) / STRIDE).min(self.checkpoints.len() - 1);
+
+        let mut file = File::open(&self.path)?;
+        let mut offset = self.checkpoints[checkpoint];
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = BufReader::new(file);
+
+        let mut lines = Vec::new();
+        let mut current = checkpoint * STRIDE + 1;
+        while current <= last {
+            let mut bytes = Vec::new();
+            let read = reader.read_until(b'\n', &mut bytes)?;
+            if read == 0 {
+                break;
+            }
+            if current >= first {
+                lines.push(RawLine {
+                    number: current,
+                    offset,
+                    bytes,
+                });
+            }
+            offset += read as u64;
+            current += 1;
+        }
+        Ok(lines)
+    }
+}
+
+/// `bytes` as printable text: invalid UTF-8 and control characters are shown
+/// as `\xNN` escapes, line terminators as `\r` and `\n`.
+#[must_use]
+pub fn escape_raw(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\r' => text.push_str("\\r"),
+                '\n' => text.push_str("\\n"),
+                '\t' => text.push('\t'),
+                c if c.is_control() => {
+                    let _ = write!(text, "\\x{:02X}", u32::from(c));
+                }
+                c => text.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            let _ = write!(text, "\\x{byte:02X}");
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_lines_around_a_line_across_checkpoints() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("big.log");
+        let content = (1..=3000)
+            .map(|n| format!("line {n}\r\n"))
+            .collect::<Vec<_>>()
+            .concat();
+        std::fs::write(&path, &content).expect("write");
+
+        let index = RawLineIndex::build(&path).expect("index");
+        let lines = index.read_around(STRIDE + 1, 2).expect("read");
+        let numbers: Vec<usize> = lines.iter().map(|l| l.number).collect();
+        assert_eq!(
+            numbers,
+            vec![STRIDE - 1, STRIDE, STRIDE + 1, STRIDE + 2, STRIDE + 3]
+        );
+        assert_eq!(
+            lines[2].bytes,
+            format!("line {}\r\n", STRIDE + 1).into_bytes()
+        );
+        let offset = content.find(&format!("line {}\r\n", STRIDE + 1)).unwrap();
+        assert_eq!(lines[2].offset, offset as u64);
+
+        assert_eq!(index.read_around(2999, 5).expect("read").len(), 7);
+        assert!(!index.is_stale());
+    }
+
+    #[test]
+    fn refuses_compressed_files() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&path).expect("create"),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(b"line 1\nline 2\n").expect("write");
+        encoder.finish().expect("finish");
+
+        let err = RawLineIndex::build(&path).expect_err("compressed");
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn escapes_terminators_control_bytes_and_invalid_utf8() {
+        assert_eq!(
+            escape_raw(b"a\tb\x1b[0m\xffc\r\n"),
+            "a\tb\\x1B[0m\\xFFc\\r\\n"
+        );
+    }
+}
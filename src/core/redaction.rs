@@ -0,0 +1,167 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Redaction of exported log text.
+//!
+//! Before logs leave the machine, user-defined [`RedactionRule`]s (regex →
+//! replacement) mask personal data such as e-mail addresses or IMEIs. The
+//! rules live in the global config so they apply to every export.
+
+use std::borrow::Cow;
+
+use fancy_regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A regex whose matches are replaced in exported text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionRule {
+    pub pattern: String,
+    /// Replacement text; `$1`, `${name}` refer to capture groups
+    pub replacement: String,
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+impl RedactionRule {
+    #[must_use]
+    pub fn new(pattern: impl Into<String>, replacement: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            replacement: replacement.into(),
+            enabled: true,
+        }
+    }
+
+    /// Common PII patterns offered out of the box, disabled until the user opts in
+    #[must_use]
+    pub fn presets() -> Vec<Self> {
+        [
+            (r"[\w.+-]+@[\w-]+(?:\.[\w-]+)+", "<email>"),
+            (r"(?<!\d)\d{15}(?!\d)", "<imei>"),
+            (r"(?<![\d.])(?:\d{1,3}\.){3}\d{1,3}(?![\d.])", "<ip>"),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| Self {
+            enabled: false,
+            ..Self::new(pattern, replacement)
+        })
+        .collect()
+    }
+}
+
+/// The enabled rules of a rule list, compiled
+#[derive(Debug, Default)]
+pub struct Redactor {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Redactor {
+    /// Compile the enabled `rules`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming the first enabled rule whose pattern is invalid.
+    pub fn new(rules: &[RedactionRule]) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled && !rule.pattern.is_empty())
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|regex| (regex, rule.replacement.clone()))
+                    .map_err(|e| format!("Invalid redaction pattern '{}': {e}", rule.pattern))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Whether no rule is active, so [`Self::apply`] never changes anything
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Apply every rule in order to `text`.
+    ///
+    /// If a pattern fails at match time (e.g. the backtrack limit is hit), the
+    /// whole text is replaced rather than risking an unredacted export.
+    #[must_use]
+    pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut text = Cow::Borrowed(text);
+        for (regex, replacement) in &self.rules {
+            match regex.try_replacen(&text, 0, replacement.as_str()) {
+                Ok(Cow::Borrowed(_)) => {}
+                Ok(Cow::Owned(redacted)) => text = Cow::Owned(redacted),
+                Err(e) => {
+                    tracing::warn!("Redaction pattern failed, masking whole line: {e}");
+                    text = Cow::Owned(replacement.clone());
+                }
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_presets() -> Vec<RedactionRule> {
+        RedactionRule::presets()
+            .into_iter()
+            .map(|rule| RedactionRule {
+                enabled: true,
+                ..rule
+            })
+            .collect()
+    }
+
+    #[test]
+    fn masks_presets_when_enabled() {
+        let redactor = Redactor::new(&enabled_presets()).unwrap();
+        assert_eq!(
+            redactor.apply("user jane.doe@example.com on 10.0.0.12 imei=356938035643809"),
+            "user <email> on <ip> imei=<imei>"
+        );
+    }
+
+    #[test]
+    fn disabled_rules_change_nothing() {
+        let redactor = Redactor::new(&RedactionRule::presets()).unwrap();
+        assert!(redactor.is_empty());
+        assert!(matches!(
+            redactor.apply("jane.doe@example.com"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn replacement_can_use_capture_groups() {
+        let redactor =
+            Redactor::new(&[RedactionRule::new(r"token=(\w{4})\w+", "token=$1…")]).unwrap();
+        assert_eq!(
+            redactor.apply("auth token=abcd1234efgh ok"),
+            "auth token=abcd… ok"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_pattern() {
+        let err = Redactor::new(&[RedactionRule::new("(unclosed", "x")]).unwrap_err();
+        assert!(err.contains("(unclosed"));
+    }
+}
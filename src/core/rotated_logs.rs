@@ -0,0 +1,155 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Listing the logs of a folder in the order they were written.
+//!
+//! logrotate and most loggers keep the current log as `app.log` and move the
+//! older ones to `app.log.1`, `app.log.2.gz`, ... (higher is older) or, with
+//! `dateext`, to `app.log-20240131.gz`. [`rotated_logs`] groups a folder's
+//! files by the log they belong to and orders each group from the oldest file
+//! to the current one.
+
+use std::cmp::Reverse;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the compressed files [`crate::core::decompress`] reads
+const COMPRESSED_EXTENSIONS: [&str; 3] = [".gz", ".zst", ".xz"];
+
+/// Where a file stands in the rotation of its log, oldest first
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Generation {
+    /// `app.log.N`, where a higher `N` is older
+    Numbered(Reverse<u64>),
+    /// `app.log-YYYYMMDD`, in the order of its date
+    Dated(String),
+    /// `app.log`, the file still being written
+    Current,
+}
+
+/// Name of the log a file belongs to and its place in that log's rotation
+fn rotation_key(name: &str) -> (String, Generation) {
+    let name = COMPRESSED_EXTENSIONS
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+    if let Some((base, index)) = name.rsplit_once('.') {
+        if !base.is_empty() && is_number(index) {
+            if let Ok(index) = index.parse() {
+                return (base.to_string(), Generation::Numbered(Reverse(index)));
+            }
+        }
+    }
+    if let Some((base, date)) = name.rsplit_once('-') {
+        if !base.is_empty() && date.len() >= 8 && is_number(date) {
+            return (base.to_string(), Generation::Dated(date.to_string()));
+        }
+    }
+    (name.to_string(), Generation::Current)
+}
+
+/// Name of the log `file_name` is a rotation of, e.g. `app.log` for
+/// `app.log.2.gz` or `app.log-20240131`
+#[must_use]
+pub fn rotation_base(file_name: &str) -> String {
+    rotation_key(file_name).0
+}
+
+/// Dotfiles and our own files (sessions with their journals and lock
+/// files, projects, exported filters) are not logs
+#[must_use]
+pub fn is_hidden_or_session(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy())
+        .is_some_and(|n| {
+            n.starts_with('.')
+                || [
+                    ".crab",
+                    ".crab.journal",
+                    ".crab.lock",
+                    ".crabproj",
+                    ".crab-filters",
+                ]
+                .iter()
+                .any(|ext| n.ends_with(ext))
+        })
+}
+
+/// The log files directly inside `dir`, grouped by the log they belong to and
+/// within each log ordered from the oldest rotated file to the current one.
+///
+/// # Errors
+///
+/// Returns an error if `dir` cannot be read.
+pub fn rotated_logs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<((String, Generation), PathBuf)> = std::fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && !is_hidden_or_session(path))
+        .map(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            (rotation_key(&name), path)
+        })
+        .collect();
+    files.sort();
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotated_logs_are_ordered_oldest_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for name in [
+            "app.log",
+            "app.log.1",
+            "app.log.2.gz",
+            "app.log.10.gz",
+            "app.log.crab",
+            ".hidden",
+            "kern.log",
+            "kern.log-20240102",
+            "kern.log-20240101.xz",
+        ] {
+            std::fs::write(dir.path().join(name), "").expect("write");
+        }
+        std::fs::create_dir(dir.path().join("app.log.crab-attachments")).expect("mkdir");
+
+        let names: Vec<String> = rotated_logs(dir.path())
+            .expect("list")
+            .iter()
+            .map(|p| p.file_name().expect("name").to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "app.log.10.gz",
+                "app.log.2.gz",
+                "app.log.1",
+                "app.log",
+                "kern.log-20240101.xz",
+                "kern.log-20240102",
+                "kern.log",
+            ]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Line rates of earlier runs of a log, to compare the current one against.
+//!
+//! Logs of the same program are often compared run by run: the boot log of
+//! this build against the one of the last build, today's test run against
+//! yesterday's. Once a file is loaded, a [`RunSummary`] of how many lines it
+//! logged over its duration is cached under a hash of the file. Loading
+//! another file of the same format and name pattern (`boot-1234.log` after
+//! `boot-1233.log`) brings up the most recently loaded summary of a different
+//! file, which the histogram draws as a ghost behind its bars.
+
+use crate::core::line_anchor::{bytes_hash, content_hash};
+use crate::core::rotated_logs::rotation_base;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read as _};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Number of equal time slices a run is summarized in
+const SUMMARY_BUCKETS: usize = 200;
+
+/// Summaries kept per name pattern; older ones are removed
+const KEPT_PER_PATTERN: usize = 20;
+
+/// Bytes from the start of a file that go into its [`file_hash`]
+const HASHED_BYTES: u64 = 1 << 20;
+
+/// Line counts of one loaded file over its duration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub file_name: String,
+    /// Slug of the format the file was parsed as
+    pub filetype: String,
+    /// Time from the first to the last line
+    pub duration_ms: i64,
+    /// Lines per equal slice of the duration
+    pub buckets: Vec<u64>,
+    /// When the file was loaded
+    pub loaded: DateTime<Local>,
+}
+
+impl RunSummary {
+    /// Summarize a file from the timestamps of its lines; `None` without lines
+    #[must_use]
+    pub fn new(file_name: &str, filetype: &str, timestamps_ms: &[i64]) -> Option<Self> {
+        let start = *timestamps_ms.iter().min()?;
+        let end = *timestamps_ms.iter().max()?;
+        // A run within one millisecond still needs a duration to divide
+        let duration_ms = (end - start).max(1);
+        let mut buckets = vec![0; SUMMARY_BUCKETS];
+        for &ts in timestamps_ms {
+            let slice = (ts - start) as f64 / duration_ms as f64 * SUMMARY_BUCKETS as f64;
+            buckets[(slice as usize).min(SUMMARY_BUCKETS - 1)] += 1;
+        }
+        Some(Self {
+            file_name: file_name.to_string(),
+            filetype: filetype.to_string(),
+            duration_ms,
+            buckets,
+            loaded: Local::now(),
+        })
+    }
+
+    /// Lines logged from `from_ms` to `to_ms` after the start of the run,
+    /// taking the lines of a slice as spread evenly over it
+    #[must_use]
+    pub fn lines_between(&self, from_ms: f64, to_ms: f64) -> f64 {
+        if self.buckets.is_empty() {
+            return 0.0;
+        }
+        let slice_ms = self.duration_ms as f64 / self.buckets.len() as f64;
+        let first = (from_ms / slice_ms).floor().max(0.0) as usize;
+        let mut lines = 0.0;
+        for (i, &count) in self.buckets.iter().enumerate().skip(first) {
+            let slice_start = i as f64 * slice_ms;
+            if slice_start >= to_ms {
+                break;
+            }
+            let overlap = (slice_start + slice_ms).min(to_ms) - slice_start.max(from_ms);
+            if overlap > 0.0 {
+                lines += count as f64 * overlap / slice_ms;
+            }
+        }
+        lines
+    }
+
+    /// Key shared by the runs of one log: the format and the file name without
+    /// its rotation suffix, with every number replaced by `#`
+    fn pattern(&self) -> String {
+        let mut pattern = format!("{}:", self.filetype);
+        let mut in_number = false;
+        for c in rotation_base(&self.file_name).chars() {
+            if !c.is_ascii_digit() {
+                pattern.push(c);
+            } else if !in_number {
+                pattern.push('#');
+            }
+            in_number = c.is_ascii_digit();
+        }
+        pattern
+    }
+}
+
+/// An earlier run matched to a loaded source
+#[derive(Debug, Clone)]
+pub struct PreviousRun {
+    pub source_id: u64,
+    /// Time of the source's first line, which the earlier run is aligned to
+    pub start_ms: i64,
+    pub summary: Arc<RunSummary>,
+}
+
+impl PreviousRun {
+    /// Lines the earlier run logged in each of `count` histogram buckets of
+    /// `bucket_ms` starting at `view_start_ms`
+    #[must_use]
+    pub fn resample(&self, view_start_ms: i64, bucket_ms: f64, count: usize) -> Vec<f64> {
+        let offset_ms = (view_start_ms - self.start_ms) as f64;
+        (0..count)
+            .map(|i| {
+                let from_ms = (i as f64).mul_add(bucket_ms, offset_ms);
+                self.summary.lines_between(from_ms, from_ms + bucket_ms)
+            })
+            .collect()
+    }
+}
+
+/// Hash identifying a file by its size and first megabyte, so it stays cheap
+/// for large logs.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn file_hash(path: &Path) -> io::Result<u64> {
+    let file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut bytes = Vec::new();
+    file.take(HASHED_BYTES).read_to_end(&mut bytes)?;
+    bytes.extend_from_slice(&len.to_le_bytes());
+    Ok(bytes_hash(&bytes))
+}
+
+/// Cache of [`RunSummary`]s with one JSON file per loaded file, named after
+/// the hashes of its name pattern and of the file
+#[derive(Debug, Clone)]
+pub struct RunHistory {
+    dir: PathBuf,
+}
+
+impl RunHistory {
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The cache in the user's cache directory
+    #[must_use]
+    pub fn in_cache_dir() -> Option<Self> {
+        Some(Self::new(dirs::cache_dir()?.join("logcrab").join("runs")))
+    }
+
+    /// Cache `summary` of the file hashed `file_hash` and return the summary
+    /// of the most recently loaded other file with the same name pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be created or written.
+    pub fn record(&self, file_hash: u64, summary: &RunSummary) -> io::Result<Option<RunSummary>> {
+        std::fs::create_dir_all(&self.dir)?;
+        let prefix = format!("{:016x}-", content_hash(&summary.pattern()));
+        let own_name = format!("{prefix}{file_hash:016x}.json");
+
+        let mut others: Vec<(PathBuf, RunSummary)> = std::fs::read_dir(&self.dir)?
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with(&prefix) && name != own_name
+            })
+            .filter_map(|entry| {
+                let json = std::fs::read_to_string(entry.path()).ok()?;
+                Some((entry.path(), serde_json::from_str(&json).ok()?))
+            })
+            .collect();
+        others.sort_by_key(|(_, other)| std::cmp::Reverse(other.loaded));
+
+        let json = serde_json::to_string(summary).map_err(io::Error::other)?;
+        std::fs::write(self.dir.join(&own_name), json)?;
+        // The file just written counts towards the kept summaries
+        for (path, _) in others.iter().skip(KEPT_PER_PATTERN - 1) {
+            let _ = std::fs::remove_file(path);
+        }
+        Ok(others.into_iter().next().map(|(_, previous)| previous))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_between_spreads_slices_evenly() {
+        let summary =
+            RunSummary::new("boot.log", "generic", &[0, 100, 150, 199, 200]).expect("summary");
+        assert_eq!(summary.duration_ms, 200);
+        assert_eq!(summary.buckets.iter().sum::<u64>(), 5);
+        // Slices are 1 ms wide here
+        assert!((summary.lines_between(0.0, 1.0) - 1.0).abs() < 1e-9);
+        assert!((summary.lines_between(99.5, 100.5) - 0.5).abs() < 1e-9);
+        assert!((summary.lines_between(-50.0, 1000.0) - 5.0).abs() < 1e-9);
+        assert!(summary.lines_between(300.0, 400.0).abs() < 1e-9);
+
+        let previous = PreviousRun {
+            source_id: 0,
+            start_ms: 1_000,
+            summary: Arc::new(summary),
+        };
+        let resampled = previous.resample(1_000, 100.0, 3);
+        assert!((resampled[0] - 1.0).abs() < 1e-9);
+        assert!((resampled[1] - 4.0).abs() < 1e-9);
+        assert!(resampled[2].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_record_returns_latest_other_run_of_same_pattern() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let history = RunHistory::new(dir.path().to_path_buf());
+        let summary = |name: &str| RunSummary::new(name, "generic", &[0, 1000]).expect("summary");
+
+        let first = summary("boot-1233.log");
+        assert_eq!(history.record(1, &first).expect("record"), None);
+        // Another log does not count as an earlier run
+        assert_eq!(
+            history.record(2, &summary("kernel.log")).expect("record"),
+            None
+        );
+        let second = summary("boot-1234.log");
+        assert_eq!(
+            history.record(3, &second).expect("record"),
+            Some(first.clone())
+        );
+        // Opening the same file again still compares against the other one
+        let reopened = summary("boot-1234.log");
+        assert_eq!(history.record(3, &reopened).expect("record"), Some(first));
+        assert_eq!(
+            history
+                .record(4, &summary("boot-1235.log.1.gz"))
+                .expect("record"),
+            Some(reopened)
+        );
+    }
+}
@@ -26,6 +26,9 @@ use egui::Color32;
 
 use crate::core::{SavedSearch, SearchState};
 
+/// Maximum number of characters of a title derived from the search pattern.
+const AUTO_TITLE_MAX_CHARS: usize = 10;
+
 /// A colored search rule that can filter/highlight log lines.
 ///
 /// This is the shared foundation for both filter tabs and highlight rules.
@@ -64,12 +67,108 @@ impl SearchRule {
         self.search.id()
     }
 
+    /// The user-given name, or a short title derived from the search pattern.
+    ///
+    /// An empty pattern matches every line and is shown as "everything".
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        if !self.name.is_empty() {
+            return self.name.clone();
+        }
+        if self.search.search_text.is_empty() {
+            return "everything".to_string();
+        }
+        let mut title = title_from_pattern(&self.search.search_text);
+        if title.is_empty() {
+            // Nothing but metacharacters, e.g. `.*`
+            title.clone_from(&self.search.search_text);
+        }
+        if title.chars().count() > AUTO_TITLE_MAX_CHARS {
+            let head: String = title.chars().take(AUTO_TITLE_MAX_CHARS - 1).collect();
+            format!("{}…", head.trim_end())
+        } else {
+            title
+        }
+    }
+
     /// Check if this rule matches a favorite filter's search criteria.
     pub fn matches_search(&self, search_text: &str, case_sensitive: bool) -> bool {
         self.search.search_text == search_text && self.search.case_sensitive == case_sensitive
     }
 }
 
+/// Patterns of a pasted list: one per non-empty line, trimmed and deduplicated.
+#[must_use]
+pub fn pasted_patterns(text: &str) -> Vec<&str> {
+    let mut patterns: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if !patterns.contains(&line) {
+            patterns.push(line);
+        }
+    }
+    patterns
+}
+
+/// Regex matching any of `literals` verbatim, added as further alternatives
+/// to `existing` (grouped so its own alternation keeps its meaning).
+#[must_use]
+pub fn or_pattern(existing: &str, literals: &[&str]) -> String {
+    let escaped = literals.iter().map(|literal| fancy_regex::escape(literal));
+    if existing.is_empty() {
+        escaped.collect::<Vec<_>>().join("|")
+    } else {
+        std::iter::once(format!("(?:{existing})").into())
+            .chain(escaped)
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+}
+
+/// Strip regex syntax from `pattern`, leaving its literal words.
+///
+/// Escaped punctuation keeps the character (`\.` → `.`), class escapes and
+/// anchors such as `\d` or `\b` are dropped, quantifier braces and inline
+/// flags (`(?i)`) are removed, and alternatives are joined by commas.
+fn title_from_pattern(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_alphanumeric() => out.push(' '),
+                Some(escaped) => out.push(escaped),
+                None => {}
+            },
+            '(' if chars.peek() == Some(&'?') => {
+                // Inline flags or non-capturing group: skip up to ':' or ')'
+                for flag in chars.by_ref() {
+                    if flag == ':' || flag == ')' {
+                        break;
+                    }
+                }
+                out.push(' ');
+            }
+            '{' => {
+                for quantifier in chars.by_ref() {
+                    if quantifier == '}' {
+                        break;
+                    }
+                }
+                out.push(' ');
+            }
+            '|' => out.push_str(", "),
+            '^' | '$' | '(' | ')' | '[' | ']' | '*' | '+' | '?' | '.' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" ,", ",")
+        .trim_matches(|c| c == ',' || c == ' ')
+        .to_string()
+}
+
 // ============================================================================
 // Conversion traits for session persistence
 // ============================================================================
@@ -96,6 +195,54 @@ impl From<&SearchRule> for SavedSearch {
             color: rule.color,
             enabled: rule.enabled,
             show_in_histogram: rule.show_in_histogram,
+            alert_threshold: None,
+            group: None,
+            compact: false,
+            hidden_sources: Vec::new(),
+            histogram_height: None,
+            histogram_collapsed: false,
+            filter_bar_collapsed: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_from_pattern_strips_metacharacters() {
+        assert_eq!(title_from_pattern(r"^ERROR\s+\d{3}$"), "ERROR");
+        assert_eq!(title_from_pattern(r"(?i)crash|panic"), "crash, panic");
+        assert_eq!(title_from_pattern(r"foo\.bar.*baz"), "foo.bar baz");
+        assert_eq!(title_from_pattern(r"(?:wifi|bt) state"), "wifi, bt state");
+        assert_eq!(title_from_pattern(".*"), "");
+    }
+
+    #[test]
+    fn test_display_name_keeps_user_name() {
+        let mut rule = SearchRule::new(String::new(), Color32::RED);
+        assert_eq!(rule.display_name(), "everything");
+        rule.search.search_text = r"\bdisconnect(ed)?\b".to_string();
+        assert_eq!(rule.display_name(), "disconnec…");
+        rule.search.search_text = ".*".to_string();
+        assert_eq!(rule.display_name(), ".*");
+        rule.name = "Network".to_string();
+        assert_eq!(rule.display_name(), "Network");
+    }
+
+    #[test]
+    fn test_pasted_patterns_skips_blank_and_duplicate_lines() {
+        assert_eq!(
+            pasted_patterns("  wlan0 down\r\n\nBT off\nwlan0 down\n"),
+            vec!["wlan0 down", "BT off"]
+        );
+        assert_eq!(pasted_patterns("single"), vec!["single"]);
+    }
+
+    #[test]
+    fn test_or_pattern_escapes_and_groups() {
+        assert_eq!(or_pattern("", &["a.b", "c(d)"]), r"a\.b|c\(d\)");
+        assert_eq!(or_pattern("x|y", &["z+"]), r"(?:x|y)|z\+");
+    }
+}
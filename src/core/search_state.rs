@@ -21,17 +21,39 @@
 //! This module provides the core regex-based search functionality
 //! with background filtering support via the global filter worker.
 
+use crate::core::column_filter::ColumnFilter;
+use crate::core::field_query::split_field_terms;
 use crate::core::filter_worker::{FilterRequest, FilterResult, FilterWorkerHandle};
 use crate::core::log_store::{StoreID, StoreVersion};
+use crate::core::slow_filter::{self, FilterTiming};
 use crate::core::LogStore;
+use chrono::{DateTime, Local};
 use fancy_regex::{Error, Regex};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
 
 /// Global counter for assigning unique search IDs
 static NEXT_SEARCH_ID: AtomicUsize = AtomicUsize::new(0);
 
+/// Time span a search is restricted to, e.g. the lines between two bookmarks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeScope {
+    /// Shown in the scope dropdown
+    pub label: String,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl TimeScope {
+    /// `true` when `time` lies within the scope, both ends inclusive.
+    #[must_use]
+    pub fn contains(&self, time: DateTime<Local>) -> bool {
+        self.start <= time && time <= self.end
+    }
+}
+
 /// Core search state shared between filters and highlights.
 ///
 /// Handles regex compilation, background filtering, and result caching.
@@ -46,8 +68,17 @@ pub struct SearchState {
     pub case_sensitive: bool,
     /// Whether to deduplicate exact matches (same timestamp, source, message)
     pub hide_duplicates: bool,
+    /// Values hidden via the column header dropdowns
+    pub column_filter: ColumnFilter,
+    /// Also match the lines that state an error level or name an error
+    pub or_errors: bool,
+    /// Only lines within this time span match
+    pub time_scope: Option<TimeScope>,
     /// Cached indices of matching lines (Arc allows cheap cloning)
     filtered_indices: Arc<Vec<StoreID>>,
+    /// Last line looked up by [`Self::find_closest_row_position_in_cache`]
+    /// and its row position; cleared whenever new results arrive
+    closest_row_cache: Cell<Option<(StoreID, Option<usize>)>>,
 
     /// What we last requested from the worker (optimistic tracking)
     last_requested_version: StoreVersion,
@@ -55,6 +86,9 @@ pub struct SearchState {
     last_requested_exclude: String,
     last_requested_case: bool,
     last_requested_dedup: bool,
+    last_requested_columns: ColumnFilter,
+    last_requested_or_errors: bool,
+    last_requested_scope: Option<TimeScope>,
 
     /// What the current `filtered_indices` was actually computed for
     /// (only updated when results are received)
@@ -63,6 +97,12 @@ pub struct SearchState {
     indices_computed_for_case: bool,
     indices_computed_for_dedup: bool,
     indices_computed_for_version: StoreVersion,
+    /// How long the pass that produced `filtered_indices` took
+    last_timing: FilterTiming,
+    /// Cancels the most recently requested pass once the search changes
+    in_flight_cancel: Arc<AtomicBool>,
+    /// Re-run a pass that timed out even though the search is unchanged
+    retry_requested: bool,
 
     /// Channel for receiving background filter results
     filter_result_rx: Receiver<FilterResult>,
@@ -84,14 +124,24 @@ impl SearchState {
             last_requested_exclude: String::new(),
             case_sensitive: false,
             hide_duplicates: false,
+            column_filter: ColumnFilter::default(),
+            or_errors: false,
             last_requested_case: false,
             last_requested_dedup: false,
+            last_requested_columns: ColumnFilter::default(),
+            last_requested_or_errors: false,
+            time_scope: None,
+            last_requested_scope: None,
             indices_computed_for_text: String::new(),
             indices_computed_for_exclude: String::new(),
             indices_computed_for_case: false,
             indices_computed_for_dedup: false,
             indices_computed_for_version: StoreVersion::default(),
+            last_timing: FilterTiming::default(),
+            in_flight_cancel: Arc::new(AtomicBool::new(false)),
+            retry_requested: false,
             filtered_indices: Arc::new(Vec::new()),
+            closest_row_cache: Cell::new(None),
             last_requested_version: StoreVersion::default(),
             filter_result_rx: result_rx,
             filter_result_tx: result_tx,
@@ -112,13 +162,15 @@ impl SearchState {
         Arc::clone(&self.filtered_indices)
     }
 
+    /// Regex for the search text, without its `field:KEY=VALUE` terms.
     pub fn get_regex(&self) -> Result<Regex, Box<Error>> {
+        let (search_text, _) = split_field_terms(&self.search_text);
         let pattern = if self.case_sensitive {
-            &self.search_text
+            search_text
         } else {
-            &format!("(?i){}", self.search_text)
+            format!("(?i){search_text}")
         };
-        Regex::new(pattern).map_err(Box::new)
+        slow_filter::compile_bounded(&pattern)
     }
 
     pub fn get_exclude_regex(&self) -> Result<Option<Regex>, Box<Error>> {
@@ -130,7 +182,7 @@ impl SearchState {
         } else {
             &format!("(?i){}", self.exclude_text)
         };
-        Regex::new(pattern).map(Some).map_err(Box::new)
+        slow_filter::compile_bounded(pattern).map(Some)
     }
 
     /// Request a background filter update for the given store.
@@ -155,6 +207,10 @@ impl SearchState {
                 exclude_text: self.exclude_text.clone(),
                 case_sensitive: self.case_sensitive,
                 hide_duplicates: self.hide_duplicates,
+                column_filter: self.column_filter.clone(),
+                or_errors: self.or_errors,
+                time_scope: self.time_scope.clone(),
+                cancelled: Arc::clone(&self.in_flight_cancel),
             };
 
             worker.send_request(request);
@@ -168,12 +224,14 @@ impl SearchState {
         let mut got_any = false;
         while let Ok(result) = self.filter_result_rx.try_recv() {
             self.filtered_indices = result.filtered_indices;
+            self.closest_row_cache.set(None);
             // Track what these indices were computed for (from the result, not cached_for)
             self.indices_computed_for_text = result.search_text;
             self.indices_computed_for_exclude = result.exclude_text;
             self.indices_computed_for_case = result.case_sensitive;
             self.indices_computed_for_dedup = result.hide_duplicates;
             self.indices_computed_for_version = result.store_version;
+            self.last_timing = result.timing;
             got_any = true;
         }
         if got_any {
@@ -196,29 +254,80 @@ impl SearchState {
         )
     }
 
+    /// Timing of the filter pass behind the current results.
+    pub const fn last_timing(&self) -> FilterTiming {
+        self.last_timing
+    }
+
+    /// Run a pass that was aborted for being too slow again, e.g. after the
+    /// timeout was raised.
+    pub const fn retry(&mut self) {
+        self.retry_requested = true;
+    }
+
     /// Check if cache is valid for the given store version, request update if not.
+    ///
+    /// A pass that changed search parameters cancels the one still running.
+    /// Once a pass timed out, new lines alone don't trigger another attempt,
+    /// so a live log doesn't keep the worker busy with a hopeless pattern.
     pub fn ensure_cache_valid(&mut self, store: &Arc<LogStore>, worker: &FilterWorkerHandle) {
-        if self.last_requested_version != store.version()
-            || self.last_requested_text != self.search_text
+        let search_changed = self.last_requested_text != self.search_text
             || self.last_requested_exclude != self.exclude_text
             || self.last_requested_case != self.case_sensitive
             || self.last_requested_dedup != self.hide_duplicates
-        {
-            self.request_filter_update(Arc::clone(store), worker);
-            self.last_requested_version = store.version();
-            self.last_requested_text = self.search_text.clone();
-            self.last_requested_exclude = self.exclude_text.clone();
-            self.last_requested_case = self.case_sensitive;
-            self.last_requested_dedup = self.hide_duplicates;
+            || self.last_requested_columns != self.column_filter
+            || self.last_requested_or_errors != self.or_errors
+            || self.last_requested_scope != self.time_scope;
+        let store_changed = self.last_requested_version != store.version();
+        if search_changed {
+            self.in_flight_cancel.store(true, Ordering::Relaxed);
+            self.in_flight_cancel = Arc::new(AtomicBool::new(false));
+        } else if !self.retry_requested && (!store_changed || self.last_timing.timed_out) {
+            return;
         }
+        self.retry_requested = false;
+        self.request_filter_update(Arc::clone(store), worker);
+        self.last_requested_version = store.version();
+        self.last_requested_text = self.search_text.clone();
+        self.last_requested_exclude = self.exclude_text.clone();
+        self.last_requested_case = self.case_sensitive;
+        self.last_requested_dedup = self.hide_duplicates;
+        self.last_requested_columns.clone_from(&self.column_filter);
+        self.last_requested_or_errors = self.or_errors;
+        self.last_requested_scope.clone_from(&self.time_scope);
     }
 
     /// Find the row position of the closest line in filtered results to the target.
     /// Returns the index within the filtered list (for scrolling to that row).
+    ///
+    /// The answer for the most recent target is cached until new results
+    /// arrive, so repeated lookups of the same selection are free.
     pub fn find_closest_row_position_in_cache(
         &self,
         target: StoreID,
         store: &Arc<LogStore>,
+    ) -> Option<usize> {
+        if let Some((cached_target, position)) = self.closest_row_cache.get() {
+            if cached_target == target {
+                return position;
+            }
+        }
+        let position = self.compute_closest_row_position(target, store);
+        self.closest_row_cache.set(Some((target, position)));
+        position
+    }
+
+    /// Record that `target` sits at `position` in the filtered results, e.g.
+    /// after the selection was moved to a known row.
+    pub fn remember_row_position(&self, target: StoreID, position: usize) {
+        debug_assert_eq!(self.filtered_indices.get(position), Some(&target));
+        self.closest_row_cache.set(Some((target, Some(position))));
+    }
+
+    fn compute_closest_row_position(
+        &self,
+        target: StoreID,
+        store: &Arc<LogStore>,
     ) -> Option<usize> {
         profiling::scope!("find_closest_row_position");
         let indices = {
@@ -230,12 +339,12 @@ impl SearchState {
         }
 
         // Get target timestamp
-        let target_time = store.adjusted_timestamp(&target)?;
+        let target_time = store.adjusted_timestamp_ms(&target)?;
 
         // Binary search to find the first line with timestamp >= target_time
         let idx = indices.partition_point(|line_idx| {
             store
-                .adjusted_timestamp(line_idx)
+                .adjusted_timestamp_ms(line_idx)
                 .is_some_and(|ts| ts < target_time)
         });
 
@@ -249,7 +358,7 @@ impl SearchState {
         let mut range_start = idx;
         while range_start > 0 {
             let prev_idx = range_start - 1;
-            if let Some(ts) = store.adjusted_timestamp(&indices[prev_idx]) {
+            if let Some(ts) = store.adjusted_timestamp_ms(&indices[prev_idx]) {
                 if ts == target_time {
                     range_start = prev_idx;
                     if indices[prev_idx] == target {
@@ -266,7 +375,7 @@ impl SearchState {
         // Scan forwards to find exact target or determine the range end
         let mut pos = range_start;
         while pos < indices.len() {
-            if let Some(ts) = store.adjusted_timestamp(&indices[pos]) {
+            if let Some(ts) = store.adjusted_timestamp_ms(&indices[pos]) {
                 match ts.cmp(&target_time) {
                     std::cmp::Ordering::Equal => {
                         if indices[pos] == target {
@@ -339,6 +448,21 @@ mod tests {
         assert!(state.get_exclude_regex().is_err());
     }
 
+    #[test]
+    fn test_time_scope_contains_is_inclusive() {
+        let start = Local::now();
+        let end = start + chrono::Duration::seconds(10);
+        let scope = TimeScope {
+            label: "crash window".to_string(),
+            start,
+            end,
+        };
+        assert!(scope.contains(start));
+        assert!(scope.contains(end));
+        assert!(!scope.contains(end + chrono::Duration::milliseconds(1)));
+        assert!(!scope.contains(start - chrono::Duration::milliseconds(1)));
+    }
+
     #[test]
     fn test_check_filter_results_drains_channel() {
         let mut state = SearchState::new();
@@ -350,7 +474,9 @@ mod tests {
             search_text: "first".to_string(),
             exclude_text: String::new(),
             case_sensitive: false,
+            hide_duplicates: false,
             store_version: StoreVersion::default(),
+            timing: FilterTiming::default(),
         })
         .expect("Failed to send FilterResult for 'first'");
 
@@ -359,7 +485,9 @@ mod tests {
             search_text: "second".to_string(),
             exclude_text: String::new(),
             case_sensitive: false,
+            hide_duplicates: false,
             store_version: StoreVersion::default(),
+            timing: FilterTiming::default(),
         })
         .expect("Failed to send FilterResult for 'second'");
 
@@ -371,4 +499,65 @@ mod tests {
         // Second call should return false (channel drained)
         assert!(!state.check_filter_results());
     }
+
+    #[test]
+    fn test_closest_row_cache_is_cleared_by_new_results() {
+        let mut state = SearchState::new();
+        let store = LogStore::new();
+        let line = StoreID::make(0, 3);
+
+        state.filtered_indices = Arc::new(vec![StoreID::make(0, 1), line]);
+        state.remember_row_position(line, 1);
+        assert_eq!(
+            state.find_closest_row_position_in_cache(line, &store),
+            Some(1)
+        );
+
+        state
+            .filter_result_tx
+            .send(FilterResult {
+                filtered_indices: Arc::new(vec![line]),
+                search_text: String::new(),
+                exclude_text: String::new(),
+                case_sensitive: false,
+                hide_duplicates: false,
+                store_version: StoreVersion::default(),
+                timing: FilterTiming::default(),
+            })
+            .expect("Failed to send FilterResult");
+        assert!(state.check_filter_results());
+        // The store has no such line, so a fresh lookup finds nothing
+        assert_eq!(state.find_closest_row_position_in_cache(line, &store), None);
+    }
+
+    #[test]
+    fn test_timed_out_search_waits_for_change_or_retry() {
+        let worker = crate::core::FilterWorker::new();
+        let store = Arc::new(LogStore::new());
+        let mut state = SearchState::new();
+        state.search_text = "(a|aa)+\\1b".to_string();
+        state.ensure_cache_valid(&store, &worker.handle());
+        let first_pass = Arc::clone(&state.in_flight_cancel);
+
+        // Pretend the pass timed out and new lines arrived since
+        state.last_timing.timed_out = true;
+        let old_version = StoreVersion {
+            sources: 99,
+            lines: 0,
+        };
+        state.last_requested_version = old_version;
+        state.ensure_cache_valid(&store, &worker.handle());
+        assert_eq!(state.last_requested_version, old_version);
+
+        state.retry();
+        state.ensure_cache_valid(&store, &worker.handle());
+        assert_eq!(state.last_requested_version, store.version());
+        assert!(!first_pass.load(Ordering::Relaxed));
+
+        // Editing the pattern cancels the running pass
+        state.search_text = "error".to_string();
+        state.ensure_cache_valid(&store, &worker.handle());
+        assert!(first_pass.load(Ordering::Relaxed));
+        assert_eq!(state.last_requested_text, "error");
+    }
 }
@@ -0,0 +1,140 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Virtual segments: named slices of one source, such as one per reboot or
+//! per test iteration.
+//!
+//! A segment starts at `start_line` and runs up to the start of the next one,
+//! so a list of start lines is all that is stored — the lines themselves are
+//! never copied. Lines before the first segment belong to no segment. Each
+//! segment can be hidden and shifted in time independently of the rest of its
+//! source.
+
+use serde::{Deserialize, Serialize};
+
+/// A named slice of a source, see the [module docs](self)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub name: String,
+    /// Index of the first line of the segment within its source
+    pub start_line: usize,
+    /// Added to the timestamps of the segment's lines, on top of the source's
+    /// own calibration
+    #[serde(default)]
+    pub offset_ms: i64,
+    /// Whether the segment's lines are hidden from every view
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl Segment {
+    /// A visible segment without time offset
+    #[must_use]
+    pub const fn new(name: String, start_line: usize) -> Self {
+        Self {
+            name,
+            start_line,
+            offset_ms: 0,
+            hidden: false,
+        }
+    }
+}
+
+/// The segment `line_index` belongs to, given segments sorted by `start_line`
+#[must_use]
+pub fn segment_at(segments: &[Segment], line_index: usize) -> Option<&Segment> {
+    let next = segments.partition_point(|s| s.start_line <= line_index);
+    next.checked_sub(1).map(|i| &segments[i])
+}
+
+/// Time offset of the segment `line_index` belongs to (0 outside any segment)
+#[must_use]
+pub fn offset_at(segments: &[Segment], line_index: usize) -> i64 {
+    segment_at(segments, line_index).map_or(0, |s| s.offset_ms)
+}
+
+/// Line indices at which a new segment should start.
+///
+/// `timestamps` are `(line index, epoch ms)` pairs in file order. A segment
+/// starts wherever the clock jumps backwards — a reboot or a new capture
+/// resets it — or, when `gap_ms` is non-zero, skips ahead by more than
+/// `gap_ms`. The first line never counts as a split point.
+#[must_use]
+pub fn find_split_points(
+    timestamps: impl IntoIterator<Item = (usize, i64)>,
+    gap_ms: i64,
+) -> Vec<usize> {
+    let mut splits = Vec::new();
+    let mut previous: Option<i64> = None;
+    for (line_index, ts) in timestamps {
+        if let Some(prev) = previous {
+            if ts < prev || (gap_ms > 0 && ts - prev > gap_ms) {
+                splits.push(line_index);
+            }
+        }
+        previous = Some(ts);
+    }
+    splits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(name: &str, start_line: usize) -> Segment {
+        Segment::new(name.to_string(), start_line)
+    }
+
+    #[test]
+    fn test_segment_at_picks_the_last_segment_starting_at_or_before_the_line() {
+        let segments = [segment("Boot 1", 10), segment("Boot 2", 20)];
+        assert_eq!(segment_at(&segments, 5), None);
+        assert_eq!(
+            segment_at(&segments, 10).map(|s| s.name.as_str()),
+            Some("Boot 1")
+        );
+        assert_eq!(
+            segment_at(&segments, 19).map(|s| s.name.as_str()),
+            Some("Boot 1")
+        );
+        assert_eq!(
+            segment_at(&segments, 20).map(|s| s.name.as_str()),
+            Some("Boot 2")
+        );
+        assert_eq!(
+            segment_at(&segments, 1000).map(|s| s.name.as_str()),
+            Some("Boot 2")
+        );
+        assert_eq!(segment_at(&[], 0), None);
+    }
+
+    #[test]
+    fn test_find_split_points_at_clock_resets_and_gaps() {
+        let timestamps = [
+            (0, 1_000),
+            (1, 2_000),
+            (2, 500),
+            (3, 600),
+            (4, 60_600),
+            (5, 60_700),
+        ];
+        assert_eq!(find_split_points(timestamps, 0), vec![2]);
+        assert_eq!(find_split_points(timestamps, 30_000), vec![2, 4]);
+        assert!(find_split_points([], 1).is_empty());
+    }
+}
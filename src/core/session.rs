@@ -16,17 +16,21 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
-//! Session persistence for `.crab` and `.crab-filters` files.
+//! Session persistence for `.crab`, `.crab-filters` and `.crabproj` files.
 //!
 //! This module handles serialization and deserialization of session data,
 //! including filters, highlights, and bookmarks.
 
+use chrono::{DateTime, Local};
 use egui::Color32;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::core::level_rule::LevelRule;
+use crate::core::line_anchor::LineAnchor;
 use crate::core::log_store::Bookmark;
+use crate::core::segment::Segment;
 
 /// Current version of the .crab file format.
 ///
@@ -40,12 +44,18 @@ use crate::core::log_store::Bookmark;
 ///       on save), so a global bump is required.
 pub const CRAB_FILE_VERSION: u32 = 4;
 
-/// Last legacy format version; files with version ≤ this are parsed as [`CrabFileV2`]
-const CRAB_FILE_V2: u32 = 2;
+/// Oldest .crab format version that can still be upgraded; older files fail to load
+const OLDEST_CRAB_FILE_VERSION: u32 = 1;
 
 /// Current version of the .crab-filters file format
 pub const CRAB_FILTERS_VERSION: u32 = 1;
 
+/// Current version of the .crabproj file format
+pub const CRAB_PROJECT_VERSION: u32 = 1;
+
+/// File extension of project files
+pub const PROJECT_EXTENSION: &str = "crabproj";
+
 // ============================================================================
 // Color Serialization
 // ============================================================================
@@ -132,6 +142,27 @@ pub struct SavedSearch {
     pub enabled: bool,
     #[serde(default)]
     pub show_in_histogram: bool,
+    /// Filters only: flag the tab when the match count exceeds this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert_threshold: Option<usize>,
+    /// Filters only: named group the tab is organized under (e.g. "Network")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Filters only: show the tab's lines in compact rows
+    #[serde(default)]
+    pub compact: bool,
+    /// Filters only: paths of the sources excluded from the tab
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_sources: Vec<String>,
+    /// Filters only: histogram height in pixels, if resized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub histogram_height: Option<f32>,
+    /// Filters only: histogram collapsed to one row
+    #[serde(default)]
+    pub histogram_collapsed: bool,
+    /// Filters only: filter bar collapsed to one row
+    #[serde(default)]
+    pub filter_bar_collapsed: bool,
 }
 
 /// Type alias for backwards compatibility - filters use `SavedSearch`
@@ -144,23 +175,6 @@ pub type SavedHighlight = SavedSearch;
 // File Formats
 // ============================================================================
 
-/// `.crab` v2 file format — legacy format used only for migration.
-///
-/// v2 stored the time offset as a flat `time_offset_ms` field instead of a
-/// typed `file_state`. This struct is never written; it exists solely so that
-/// old files can be deserialized and then converted via [`CrabFile::migrate_from_v2`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct CrabFileV2 {
-    #[serde(default = "default_version")]
-    version: u32,
-    bookmarks: Vec<Bookmark>,
-    filters: Vec<SavedFilter>,
-    #[serde(default)]
-    highlights: Vec<SavedHighlight>,
-    #[serde(default)]
-    time_offset_ms: i64,
-}
-
 /// `.crab` file format (v3+) — stores per-source session data.
 ///
 /// Generic over `FT: InputFileType`. The `file_state` is stored in JSON under
@@ -170,9 +184,13 @@ struct CrabFileV2 {
 /// `FT::SLUG` is provided by the `HasSlug` impl generated by `register_filetypes!`.
 ///
 /// Version history:
-/// - v2: flat `time_offset_ms: i64` for source time calibration (see [`CrabFileV2`])
+/// - v2: flat `time_offset_ms: i64` for source time calibration
 /// - v3: `file_state` stored under `FT::SLUG` (typed per-source state)
 ///
+/// Older files are upgraded step by step on load, see [`CrabFile::upgrade_step`].
+/// After loading, `version` still holds the version found on disk, so callers
+/// can tell that a file was upgraded; saving always writes [`CRAB_FILE_VERSION`].
+///
 /// Individual filetype states may carry their own `version` field inside the
 /// slug object to version their schema independently (see e.g. `BugreportFileState`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -191,62 +209,213 @@ pub struct CrabFile<FT: crate::filetype::InputFileType> {
     /// Per-source persistent state. Stored in JSON under `FT::SLUG`.
     #[serde(default)]
     pub file_state: <FT::LineType as crate::filetype::LineType>::FileState,
+    /// Lines and templates hidden from every view, oldest first.
+    #[serde(default)]
+    pub suppressions: Vec<Suppression>,
+    /// Templates the user judged expected or important, oldest first.
+    #[serde(default)]
+    pub template_feedback: Vec<TemplateFeedback>,
+    /// Rules correcting the level of lines; the first matching one applies.
+    #[serde(default)]
+    pub level_rules: Vec<LevelRule>,
+    /// Analysis actions taken in this session, oldest first.
+    #[serde(default)]
+    pub activity: Vec<ActivityEntry>,
+    /// Virtual segments of the source, ordered by start line.
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+    /// Bookmarks still in JSON form when read by [`Self::from_json_deferred`];
+    /// `bookmarks` is empty then.
+    #[serde(skip)]
+    pub deferred_bookmarks: Option<DeferredBookmarks>,
 }
 
-impl<FT: crate::filetype::InputFileType> CrabFile<FT> {
-    /// Migrate a v2 session into the current v3 format.
-    fn migrate_from_v2(v2: CrabFileV2) -> Self {
-        use crate::filetype::LineType as _;
-        tracing::info!(
-            "Migrating .crab file from v{} to v{}",
-            v2.version,
-            CRAB_FILE_VERSION
-        );
+/// The `bookmarks` array of a `.crab` file, kept as JSON text.
+///
+/// Scripts can leave tens of thousands of bookmarks in a session; reading them
+/// is left to the background loader so opening the file isn't held up.
+#[derive(Debug, Clone)]
+pub struct DeferredBookmarks {
+    raw: Box<serde_json::value::RawValue>,
+    /// Result of the first successful [`Self::parse`]
+    parsed: std::sync::OnceLock<std::sync::Arc<Vec<Bookmark>>>,
+}
+
+impl DeferredBookmarks {
+    const fn new(raw: Box<serde_json::value::RawValue>) -> Self {
         Self {
-            version: CRAB_FILE_VERSION,
-            bookmarks: v2.bookmarks,
-            filters: v2.filters,
-            highlights: v2.highlights,
-            file_state: FT::LineType::file_state_from_v2(v2.time_offset_ms),
+            raw,
+            parsed: std::sync::OnceLock::new(),
         }
     }
 
-    /// Load a session from an already-open file handle.
+    /// Deserialize all bookmarks at once; later calls reuse the result.
     ///
-    /// Files with version ≤ v2 are deserialized as [`CrabFileV2`] and migrated;
-    /// v3+ files have their `FT::SLUG` key remapped to `file_state` before
-    /// deserialization.
-    pub fn load_from_file(file: &mut std::fs::File) -> Result<Self, SessionError> {
-        use std::io::{Read, Seek, SeekFrom};
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not a list of bookmarks.
+    pub fn parse(&self) -> Result<std::sync::Arc<Vec<Bookmark>>, SessionError> {
+        if let Some(parsed) = self.parsed.get() {
+            return Ok(std::sync::Arc::clone(parsed));
+        }
+        let parsed: Vec<Bookmark> =
+            serde_json::from_str(self.raw.get()).map_err(SessionError::Parse)?;
+        Ok(std::sync::Arc::clone(
+            self.parsed.get_or_init(|| std::sync::Arc::new(parsed)),
+        ))
+    }
 
-        file.seek(SeekFrom::Start(0)).map_err(SessionError::Io)?;
-        let mut content = String::new();
-        file.read_to_string(&mut content)
-            .map_err(SessionError::Io)?;
+    /// Deserialize the bookmarks while reading them, handing them to `insert`
+    /// `batch` at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not a list of bookmarks; the batches
+    /// before the error have been handed over.
+    pub fn load(
+        &self,
+        batch: usize,
+        insert: impl FnMut(Vec<Bookmark>),
+    ) -> Result<(), SessionError> {
+        use serde::de::DeserializeSeed as _;
+        let mut deserializer = serde_json::Deserializer::from_str(self.raw.get());
+        BookmarkBatches {
+            batch: batch.max(1),
+            insert,
+        }
+        .deserialize(&mut deserializer)
+        .and_then(|()| deserializer.end())
+        .map_err(SessionError::Parse)
+    }
+}
+
+/// Sequence visitor behind [`DeferredBookmarks::load`]
+struct BookmarkBatches<F> {
+    batch: usize,
+    insert: F,
+}
+
+impl<'de, F: FnMut(Vec<Bookmark>)> serde::de::DeserializeSeed<'de> for BookmarkBatches<F> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F: FnMut(Vec<Bookmark>)> serde::de::Visitor<'de> for BookmarkBatches<F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a list of bookmarks")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let mut batch = Vec::with_capacity(self.batch);
+        while let Some(bookmark) = seq.next_element()? {
+            batch.push(bookmark);
+            if batch.len() == self.batch {
+                (self.insert)(std::mem::replace(
+                    &mut batch,
+                    Vec::with_capacity(self.batch),
+                ));
+            }
+        }
+        if !batch.is_empty() {
+            (self.insert)(batch);
+        }
+        Ok(())
+    }
+}
 
-        let mut value: serde_json::Value =
-            serde_json::from_str(&content).map_err(SessionError::Parse)?;
+impl<FT: crate::filetype::InputFileType> CrabFile<FT> {
+    /// Upgrade raw `.crab` JSON from `version` to `version + 1`.
+    ///
+    /// Every format change that bumps [`CRAB_FILE_VERSION`] adds an arm here,
+    /// so that sessions written by any older release keep loading. Steps only
+    /// touch the JSON; typed deserialization happens once all steps have run.
+    fn upgrade_step(
+        version: u32,
+        obj: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), SessionError> {
+        use crate::filetype::LineType as _;
+        match version {
+            // v1 files only lack the `version` key
+            1 => Ok(()),
+            // v3: the flat time offset moves into the typed state under `FT::SLUG`
+            2 => {
+                let time_offset_ms = obj
+                    .remove("time_offset_ms")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0);
+                let file_state = FT::LineType::file_state_from_v2(time_offset_ms);
+                let state = serde_json::to_value(file_state).map_err(SessionError::Serialize)?;
+                obj.insert(FT::SLUG.to_string(), state);
+                Ok(())
+            }
+            // v4: per-filetype `state_version`; states version their own fields
+            3 => Ok(()),
+            _ => Err(SessionError::NoUpgradePath { from: version }),
+        }
+    }
 
+    /// Parse `.crab` JSON, upgrading files written by older versions.
+    ///
+    /// Files newer than [`CRAB_FILE_VERSION`] are refused with
+    /// [`SessionError::VersionTooNew`]; the `FT::SLUG` key is remapped to
+    /// `file_state` before deserialization.
+    pub fn from_json(content: &str) -> Result<Self, SessionError> {
+        Self::from_value(serde_json::from_str(content).map_err(SessionError::Parse)?)
+    }
+
+    /// Parse `.crab` JSON like [`Self::from_json`], but leave the bookmarks
+    /// unparsed in `deferred_bookmarks`.
+    pub fn from_json_deferred(content: &str) -> Result<Self, SessionError> {
+        // Only the top level is parsed here; each other key is small
+        let mut fields: std::collections::BTreeMap<String, Box<serde_json::value::RawValue>> =
+            serde_json::from_str(content).map_err(SessionError::Parse)?;
+        let bookmarks = fields.remove("bookmarks");
+        let mut obj = serde_json::Map::new();
+        for (key, raw) in fields {
+            let value = serde_json::from_str(raw.get()).map_err(SessionError::Parse)?;
+            obj.insert(key, value);
+        }
+        if bookmarks.is_some() {
+            obj.insert(
+                "bookmarks".to_string(),
+                serde_json::Value::Array(Vec::new()),
+            );
+        }
+        let mut crab = Self::from_value(serde_json::Value::Object(obj))?;
+        crab.deferred_bookmarks = bookmarks.map(DeferredBookmarks::new);
+        Ok(crab)
+    }
+
+    fn from_value(mut value: serde_json::Value) -> Result<Self, SessionError> {
         let version = value
             .get("version")
             .and_then(serde_json::Value::as_u64)
             .unwrap_or(1) as u32;
 
-        // v2 and older: use the legacy parser and migrate up.
-        if version <= CRAB_FILE_V2 {
-            let v2: CrabFileV2 = serde_json::from_value(value).map_err(SessionError::Parse)?;
-            return Ok(Self::migrate_from_v2(v2));
-        }
-
         if version > CRAB_FILE_VERSION {
             return Err(SessionError::VersionTooNew {
                 found: version,
                 supported: CRAB_FILE_VERSION,
             });
         }
+        if version < OLDEST_CRAB_FILE_VERSION {
+            return Err(SessionError::NoUpgradePath { from: version });
+        }
 
-        // v3+: remap the per-format slug key to the canonical `file_state` key.
         if let Some(obj) = value.as_object_mut() {
+            if version < CRAB_FILE_VERSION {
+                tracing::info!("Upgrading .crab file from v{version} to v{CRAB_FILE_VERSION}");
+            }
+            for step in version..CRAB_FILE_VERSION {
+                Self::upgrade_step(step, obj)?;
+            }
+
+            // Remap the per-format slug key to the canonical `file_state` key.
             if let Some(slug_state) = obj.remove(FT::SLUG) {
                 // Check the per-filetype state_version before handing to serde,
                 // so "too new" reaches the caller as a typed error with a good message
@@ -273,12 +442,25 @@ impl<FT: crate::filetype::InputFileType> CrabFile<FT> {
         serde_json::from_value(value).map_err(SessionError::Parse)
     }
 
-    /// Save the session to an already-open file handle.
+    /// Load a session from an already-open file handle, leaving its
+    /// bookmarks deferred (see [`Self::from_json_deferred`]).
+    pub fn load_from_file(file: &mut std::fs::File) -> Result<Self, SessionError> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        file.seek(SeekFrom::Start(0)).map_err(SessionError::Io)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(SessionError::Io)?;
+        Self::from_json_deferred(&content)
+    }
+
+    /// Serialize the session to the `.crab` JSON text.
     ///
     /// Serializes `file_state` under `FT::SLUG` rather than `"file_state"`.
-    pub fn save_to_file(&self, file: &mut std::fs::File) -> Result<(), SessionError> {
-        use std::io::{Seek, SeekFrom, Write};
-
+    /// Writing is left to [`crate::core::crab_writer`], which replaces the
+    /// file atomically off the UI thread.
+    pub fn to_json(&self) -> Result<String, SessionError> {
+        profiling::scope!("CrabFile::to_json");
         let mut value = serde_json::to_value(self).map_err(SessionError::Serialize)?;
 
         if let Some(obj) = value.as_object_mut() {
@@ -287,17 +469,220 @@ impl<FT: crate::filetype::InputFileType> CrabFile<FT> {
             }
         }
 
-        let json = serde_json::to_string_pretty(&value).map_err(SessionError::Serialize)?;
+        serde_json::to_string_pretty(&value).map_err(SessionError::Serialize)
+    }
+}
 
-        file.set_len(0).map_err(SessionError::Io)?;
-        file.seek(SeekFrom::Start(0)).map_err(SessionError::Io)?;
-        file.write_all(json.as_bytes()).map_err(SessionError::Io)?;
-        file.flush().map_err(SessionError::Io)?;
+/// A line or message template the user hid from the session.
+///
+/// Suppressed lines disappear from every filter tab and histogram until the
+/// suppression is removed again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Suppression {
+    /// A single line, by its index within the source
+    Line {
+        line_index: usize,
+        /// Timestamp and message hash of the line, used to find it again
+        /// when the file was regrown, truncated or rotated
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor: Option<LineAnchor>,
+    },
+    /// Every line whose normalized message equals `template`
+    Template { template: String },
+}
 
-        Ok(())
+/// How the user judged a message template, overriding the anomaly score of
+/// its lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateVerdict {
+    /// Normal for this system; its lines never stand out
+    Expected,
+    /// Always worth a look, however often it occurs
+    Important,
+}
+
+impl TemplateVerdict {
+    /// Heuristic score given to every line of a template with this verdict
+    #[must_use]
+    pub const fn score(self) -> f64 {
+        match self {
+            Self::Expected => 0.0,
+            Self::Important => 100.0,
+        }
+    }
+}
+
+/// The user's verdict on every line whose normalized message equals `template`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateFeedback {
+    pub template: String,
+    pub verdict: TemplateVerdict,
+}
+
+/// An analysis action the user took, recorded in the session's activity log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activity {
+    BookmarkAdded {
+        line_number: usize,
+        name: String,
+    },
+    BookmarkRenamed {
+        line_number: usize,
+        name: String,
+    },
+    BookmarkRemoved {
+        line_number: usize,
+    },
+    /// A filter tab committed a new search pattern
+    FilterChanged {
+        pattern: String,
+    },
+    /// A time calibration was applied to the source
+    TimeOffsetApplied,
+}
+
+impl Activity {
+    /// Whether the action concerns the whole session rather than one source.
+    ///
+    /// Session-wide actions are recorded in every source's `.crab` file.
+    #[must_use]
+    pub const fn is_session_wide(&self) -> bool {
+        matches!(self, Self::FilterChanged { .. })
+    }
+}
+
+impl std::fmt::Display for Activity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BookmarkAdded { line_number, name } if name.is_empty() => {
+                write!(f, "Bookmarked line {line_number}")
+            }
+            Self::BookmarkAdded { line_number, name } => {
+                write!(f, "Bookmarked line {line_number} as \"{name}\"")
+            }
+            Self::BookmarkRenamed { line_number, name } => {
+                write!(f, "Renamed bookmark on line {line_number} to \"{name}\"")
+            }
+            Self::BookmarkRemoved { line_number } => {
+                write!(f, "Removed bookmark on line {line_number}")
+            }
+            Self::FilterChanged { pattern } => write!(f, "Filtered for \"{pattern}\""),
+            Self::TimeOffsetApplied => write!(f, "Applied time calibration"),
+        }
+    }
+}
+
+/// A timestamped entry of the activity log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// Wall-clock time the action was taken
+    pub time: DateTime<Local>,
+    pub action: Activity,
+}
+
+impl ActivityEntry {
+    /// Record `action` as taken now
+    #[must_use]
+    pub fn now(action: Activity) -> Self {
+        Self {
+            time: Local::now(),
+            action,
+        }
     }
 }
 
+/// Format-independent view of someone else's `.crab` file.
+///
+/// Used when importing a teammate's session into an already open log. Only the
+/// shared parts (bookmarks, filters, highlights) are read; per-source
+/// `file_state` such as time calibration stays with the local session.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ImportedSession {
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    pub filters: Vec<SavedFilter>,
+    #[serde(default)]
+    pub highlights: Vec<SavedHighlight>,
+}
+
+impl ImportedSession {
+    /// Load the shareable parts of a `.crab` file of any supported version.
+    pub fn load(path: &Path) -> Result<Self, SessionError> {
+        let content = fs::read_to_string(path).map_err(SessionError::Io)?;
+        Self::from_json(&content)
+    }
+
+    /// Parse the shareable parts of `.crab` file contents.
+    pub fn from_json(content: &str) -> Result<Self, SessionError> {
+        let value: serde_json::Value =
+            serde_json::from_str(content).map_err(SessionError::Parse)?;
+
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(1) as u32;
+        if version > CRAB_FILE_VERSION {
+            return Err(SessionError::VersionTooNew {
+                found: version,
+                supported: CRAB_FILE_VERSION,
+            });
+        }
+
+        serde_json::from_value(value).map_err(SessionError::Parse)
+    }
+}
+
+/// Result of merging imported bookmarks into the local ones of a source
+#[derive(Debug, Default)]
+pub struct BookmarkMerge {
+    /// Bookmarks to write (new lines and renamed conflicts)
+    pub bookmarks: Vec<Bookmark>,
+    /// Number of bookmarks on lines that had none locally
+    pub added: usize,
+    /// Number of lines bookmarked on both sides under different names
+    pub renamed: usize,
+}
+
+/// Union `theirs` into `mine`.
+///
+/// Lines only bookmarked in `theirs` are added. When both sides bookmarked the
+/// same line under different names, the local bookmark is renamed to
+/// `"<mine> / <theirs>"` so neither annotation is lost.
+#[must_use]
+pub fn merge_bookmarks(mine: &[Bookmark], theirs: &[Bookmark]) -> BookmarkMerge {
+    let mut merge = BookmarkMerge::default();
+    for their in theirs {
+        match mine.iter().find(|b| b.line_index == their.line_index) {
+            None => {
+                merge.bookmarks.push(their.clone());
+                merge.added += 1;
+            }
+            Some(my) if their.name.is_empty() || my.name == their.name => {}
+            Some(my) => {
+                let name = if my.name.is_empty() {
+                    their.name.clone()
+                } else {
+                    format!("{} / {}", my.name, their.name)
+                };
+                merge.bookmarks.push(Bookmark {
+                    line_index: their.line_index,
+                    name,
+                    category: my.category.clone(),
+                    anchor: their.anchor,
+                    attachments: Vec::new(),
+                    orphaned: false,
+                });
+                merge.renamed += 1;
+            }
+        }
+    }
+    merge
+}
+
 /// .crab-filters file format - stores only filters for import/export
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrabFilters {
@@ -326,12 +711,103 @@ impl CrabFilters {
 
     /// Save filters to a .crab-filters file
     pub fn save(&self, path: &Path) -> Result<(), SessionError> {
+        profiling::scope!("CrabFilters::save");
         let json = serde_json::to_string_pretty(self).map_err(SessionError::Serialize)?;
         fs::write(path, json).map_err(SessionError::Io)?;
         Ok(())
     }
 }
 
+/// .crabproj file format - the set of logs making up one investigation.
+///
+/// Only the file list and the shared filters/highlights live here; time
+/// offsets and bookmarks stay in each log's own `.crab` file, which is picked
+/// up again when the file is opened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrabProject {
+    /// File format version for future compatibility
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Log files, relative to the project file where possible
+    pub files: Vec<PathBuf>,
+    #[serde(default)]
+    pub filters: Vec<SavedFilter>,
+    #[serde(default)]
+    pub highlights: Vec<SavedHighlight>,
+}
+
+impl CrabProject {
+    /// Load a project, resolving relative file paths against its directory
+    pub fn load(path: &Path) -> Result<Self, SessionError> {
+        let content = fs::read_to_string(path).map_err(SessionError::Io)?;
+        let mut project: Self = serde_json::from_str(&content).map_err(SessionError::Parse)?;
+
+        if project.version > CRAB_PROJECT_VERSION {
+            return Err(SessionError::VersionTooNew {
+                found: project.version,
+                supported: CRAB_PROJECT_VERSION,
+            });
+        }
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        for file in &mut project.files {
+            if file.is_relative() {
+                *file = base.join(&*file);
+            }
+        }
+        // Hidden sources name the project's files the same way; other
+        // relative entries are bare file names of older sessions
+        let files = &project.files;
+        for filter in &mut project.filters {
+            for source in &mut filter.hidden_sources {
+                let joined = base.join(&*source);
+                if Path::new(source).is_relative() && files.contains(&joined) {
+                    *source = joined.to_string_lossy().into_owned();
+                }
+            }
+        }
+        Ok(project)
+    }
+
+    /// Save the project, storing file paths relative to its directory so the
+    /// project and its logs can be moved or shared together
+    pub fn save(&self, path: &Path) -> Result<(), SessionError> {
+        profiling::scope!("CrabProject::save");
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let relative = Self {
+            files: self
+                .files
+                .iter()
+                .map(|file| {
+                    file.strip_prefix(base)
+                        .map_or_else(|_| file.clone(), Path::to_path_buf)
+                })
+                .collect(),
+            filters: self
+                .filters
+                .iter()
+                .map(|filter| SavedFilter {
+                    hidden_sources: filter
+                        .hidden_sources
+                        .iter()
+                        .map(|source| {
+                            Path::new(source).strip_prefix(base).map_or_else(
+                                |_| source.clone(),
+                                |relative| relative.to_string_lossy().into_owned(),
+                            )
+                        })
+                        .collect(),
+                    ..filter.clone()
+                })
+                .collect(),
+            ..self.clone()
+        };
+        let json = serde_json::to_string_pretty(&relative).map_err(SessionError::Serialize)?;
+        fs::write(path, json).map_err(SessionError::Io)?;
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Error Handling
 // ============================================================================
@@ -355,6 +831,11 @@ pub enum SessionError {
         found: u32,
         supported: u32,
     },
+    /// No upgrade step from this .crab version exists; it predates every
+    /// format this build can read.
+    NoUpgradePath {
+        from: u32,
+    },
 }
 
 impl std::fmt::Display for SessionError {
@@ -371,6 +852,9 @@ impl std::fmt::Display for SessionError {
                 f,
                 "{slug} state version {found} is newer than supported version {supported}"
             ),
+            Self::NoUpgradePath { from } => {
+                write!(f, ".crab file version {from} cannot be upgraded")
+            }
         }
     }
 }
@@ -383,6 +867,235 @@ impl std::error::Error for SessionError {
             Self::Serialize(e) => Some(e),
             Self::VersionTooNew { .. } => None,
             Self::StateVersionTooNew { .. } => None,
+            Self::NoUpgradePath { .. } => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmark(line_index: usize, name: &str) -> Bookmark {
+        Bookmark {
+            line_index,
+            name: name.to_string(),
+            category: String::new(),
+            anchor: None,
+            attachments: Vec::new(),
+            orphaned: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_bookmarks_unions_and_renames_conflicts() {
+        let mine = [bookmark(1, "crash"), bookmark(2, "same"), bookmark(3, "")];
+        let theirs = [
+            bookmark(1, "oom"),
+            bookmark(2, "same"),
+            bookmark(3, "reboot"),
+            bookmark(4, "new"),
+        ];
+
+        let merge = merge_bookmarks(&mine, &theirs);
+
+        assert_eq!(merge.added, 1);
+        assert_eq!(merge.renamed, 2);
+        let names: Vec<_> = merge
+            .bookmarks
+            .iter()
+            .map(|b| (b.line_index, b.name.as_str()))
+            .collect();
+        assert_eq!(names, [(1, "crash / oom"), (3, "reboot"), (4, "new")]);
+    }
+
+    type GenericCrab = CrabFile<crate::filetype::generic::GenericFileType>;
+
+    #[test]
+    fn test_every_old_version_has_upgrade_step() {
+        for version in OLDEST_CRAB_FILE_VERSION..CRAB_FILE_VERSION {
+            let mut obj = serde_json::Map::new();
+            assert!(
+                GenericCrab::upgrade_step(version, &mut obj).is_ok(),
+                "no upgrade step from .crab v{version}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_upgrade_v2_time_offset() {
+        let v2 = r#"{"version": 2, "bookmarks": [{"line_index": 4, "name": "boot"}],
+                     "filters": [], "time_offset_ms": 1500}"#;
+        let crab = GenericCrab::from_json(v2).expect("v2 file loads");
+        assert_eq!(crab.version, 2);
+        assert_eq!(crab.bookmarks[0].name, "boot");
+        assert_eq!(crab.file_state.time_offset_ms(), 1500);
+
+        // Without a version key the file is v1, which has the same layout
+        let v1 = r#"{"bookmarks": [], "filters": [], "time_offset_ms": -20}"#;
+        let crab = GenericCrab::from_json(v1).expect("v1 file loads");
+        assert_eq!(crab.file_state.time_offset_ms(), -20);
+    }
+
+    #[test]
+    fn test_deferred_bookmarks_are_read_in_batches() {
+        let v2 = r#"{"version": 2, "filters": [], "time_offset_ms": 1500, "bookmarks": [
+                     {"line_index": 0, "name": "a"}, {"line_index": 1, "name": "b"},
+                     {"line_index": 2, "name": "c"}, {"line_index": 3, "name": "d"},
+                     {"line_index": 4, "name": "e"}]}"#;
+        let crab = GenericCrab::from_json_deferred(v2).expect("v2 file loads");
+        assert!(crab.bookmarks.is_empty());
+        let deferred = crab.deferred_bookmarks.expect("bookmarks are deferred");
+
+        let mut batches = Vec::new();
+        deferred
+            .load(2, |batch| {
+                batches.push(batch.iter().map(|b| b.name.clone()).collect::<Vec<_>>());
+            })
+            .expect("bookmarks load");
+        assert_eq!(batches, [vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+        assert_eq!(deferred.parse().expect("bookmarks parse").len(), 5);
+    }
+
+    #[test]
+    fn test_newer_version_is_refused() {
+        let json = format!(
+            r#"{{"version": {}, "bookmarks": [], "filters": []}}"#,
+            CRAB_FILE_VERSION + 1
+        );
+        assert!(matches!(
+            GenericCrab::from_json(&json),
+            Err(SessionError::VersionTooNew { found, supported })
+                if found == CRAB_FILE_VERSION + 1 && supported == CRAB_FILE_VERSION
+        ));
+        assert!(matches!(
+            GenericCrab::from_json(r#"{"version": 0, "bookmarks": [], "filters": []}"#),
+            Err(SessionError::NoUpgradePath { from: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_suppression_json_shape() {
+        let suppressions = vec![
+            Suppression::Line {
+                line_index: 7,
+                anchor: None,
+            },
+            Suppression::Template {
+                template: "connect <NUM> failed".to_string(),
+            },
+        ];
+        let json = serde_json::to_string(&suppressions).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"line":{"line_index":7}},{"template":{"template":"connect <NUM> failed"}}]"#
+        );
+        let parsed: Vec<Suppression> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, suppressions);
+    }
+
+    #[test]
+    fn test_template_feedback_json_shape() {
+        let crab = GenericCrab::from_json(r#"{"version": 4, "bookmarks": [], "filters": []}"#)
+            .expect("parse");
+        assert!(crab.template_feedback.is_empty());
+
+        let feedback = TemplateFeedback {
+            template: "heartbeat <NUM>".to_string(),
+            verdict: TemplateVerdict::Expected,
+        };
+        let json = serde_json::to_string(&feedback).unwrap();
+        assert_eq!(
+            json,
+            r#"{"template":"heartbeat <NUM>","verdict":"expected"}"#
+        );
+    }
+
+    #[test]
+    fn test_activity_json_shape() {
+        let actions = vec![
+            Activity::BookmarkRemoved { line_number: 3 },
+            Activity::TimeOffsetApplied,
+        ];
+        let json = serde_json::to_string(&actions).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"bookmark_removed":{"line_number":3}},"time_offset_applied"]"#
+        );
+        let parsed: Vec<Activity> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, actions);
+    }
+
+    #[test]
+    fn test_activity_description() {
+        let added = |name: &str| Activity::BookmarkAdded {
+            line_number: 12,
+            name: name.to_string(),
+        };
+        assert_eq!(added("").to_string(), "Bookmarked line 12");
+        assert_eq!(added("boot").to_string(), r#"Bookmarked line 12 as "boot""#);
+    }
+
+    #[test]
+    fn test_project_stores_paths_relative_to_itself() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let inside = dir.path().join("logs").join("app.log");
+        let outside = PathBuf::from("/var/log/syslog");
+        let project_path = dir.path().join("case.crabproj");
+
+        let project = CrabProject {
+            version: CRAB_PROJECT_VERSION,
+            files: vec![inside.clone(), outside.clone()],
+            filters: Vec::new(),
+            highlights: Vec::new(),
+        };
+        project.save(&project_path).unwrap();
+
+        let json = fs::read_to_string(&project_path).unwrap();
+        assert!(!json.contains(&*dir.path().to_string_lossy()));
+
+        let loaded = CrabProject::load(&project_path).unwrap();
+        assert_eq!(loaded.files, vec![inside, outside]);
+    }
+
+    #[test]
+    fn test_project_hidden_sources_move_with_the_project() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let inside = dir.path().join("logs").join("app.log");
+        let project_path = dir.path().join("case.crabproj");
+        let project = CrabProject {
+            version: CRAB_PROJECT_VERSION,
+            files: vec![inside.clone()],
+            filters: vec![SavedFilter {
+                hidden_sources: vec![
+                    inside.to_string_lossy().into_owned(),
+                    "/var/log/syslog".to_string(),
+                    "radio.log".to_string(),
+                ],
+                ..SavedFilter::from(&crate::core::SearchRule::new(
+                    "app".to_string(),
+                    Color32::RED,
+                ))
+            }],
+            highlights: Vec::new(),
+        };
+        project.save(&project_path).expect("save");
+
+        let json = fs::read_to_string(&project_path).expect("read");
+        assert!(!json.contains(&*dir.path().to_string_lossy()));
+
+        // Moving the project with its logs keeps the same sources hidden
+        let moved = tempfile::tempdir().expect("tempdir");
+        let moved_path = moved.path().join("case.crabproj");
+        fs::rename(&project_path, &moved_path).expect("move");
+        let loaded = CrabProject::load(&moved_path).expect("load");
+        assert_eq!(
+            loaded.filters[0].hidden_sources,
+            [
+                moved.path().join("logs").join("app.log").to_string_lossy(),
+                "/var/log/syslog".into(),
+                "radio.log".into(),
+            ]
+        );
+    }
+}
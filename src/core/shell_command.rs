@@ -0,0 +1,175 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Shell commands reproducing a filter on the raw files.
+//!
+//! A finding made in the GUI often has to be checked again on a server where
+//! only the log files exist. [`shell_command`] turns a filter's search and
+//! exclude patterns into an `rg` or `grep` pipeline over the source files.
+//! Field terms (`field:status=500`) have no equivalent on raw lines and are left
+//! out; the caller tells the user so.
+
+use crate::core::field_query::split_field_terms;
+use std::path::{Path, PathBuf};
+
+/// The tool a command is built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrepTool {
+    Ripgrep,
+    Grep,
+}
+
+impl GrepTool {
+    pub const ALL: [Self; 2] = [Self::Ripgrep, Self::Grep];
+
+    /// Name shown to the user
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ripgrep => "rg",
+            Self::Grep => "grep -P",
+        }
+    }
+
+    /// The invocation matching `pattern`, or the lines not matching it
+    fn invocation(self, pattern: &str, case_sensitive: bool, invert: bool) -> Vec<String> {
+        // Lookarounds and backreferences are beyond ripgrep's default engine.
+        // POSIX ERE lacks far more (`\d`, `(?:…)`, `(?i)`, lazy quantifiers),
+        // so grep always gets Perl syntax, like the filters use.
+        let pcre = ["(?=", "(?!", "(?<=", "(?<!", "\\1", "\\2", "\\3"]
+            .iter()
+            .any(|feature| pattern.contains(feature));
+        let mut args = match (self, pcre) {
+            (Self::Ripgrep, false) => vec!["rg".to_string()],
+            (Self::Ripgrep, true) => vec!["rg".to_string(), "-P".to_string()],
+            (Self::Grep, _) => vec!["grep".to_string(), "-P".to_string()],
+        };
+        if invert {
+            args.push("-v".to_string());
+        }
+        if !case_sensitive {
+            args.push("-i".to_string());
+        } else if self == Self::Ripgrep {
+            // Override a smart-case setting in the user's ripgrep config
+            args.push("-s".to_string());
+        }
+        args.push("-e".to_string());
+        args.push(shell_quote(pattern));
+        args
+    }
+}
+
+/// Whether `path` is a compressed log, which only `rg -z` searches as text
+fn is_compressed(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        ["gz", "zst", "xz"]
+            .iter()
+            .any(|c| ext.eq_ignore_ascii_case(c))
+    })
+}
+
+/// `s` quoted for a POSIX shell, left as it is if it needs no quoting
+#[must_use]
+pub fn shell_quote(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=+,@%".contains(c));
+    if plain {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
+}
+
+/// A `tool` command printing the lines of `paths` that match `search_text`
+/// and not `exclude_text`, the way a filter with these settings shows them.
+///
+/// An exclude pattern is applied by piping into a second, inverted `tool`.
+#[must_use]
+pub fn shell_command(
+    tool: GrepTool,
+    search_text: &str,
+    exclude_text: &str,
+    case_sensitive: bool,
+    paths: &[PathBuf],
+) -> String {
+    let (search_text, _) = split_field_terms(search_text);
+    let mut stages = Vec::new();
+    let mut first = if search_text.is_empty() && !exclude_text.is_empty() {
+        tool.invocation(exclude_text, case_sensitive, true)
+    } else {
+        let excluded =
+            (!exclude_text.is_empty()).then(|| tool.invocation(exclude_text, case_sensitive, true));
+        stages.extend(excluded);
+        tool.invocation(&search_text, case_sensitive, false)
+    };
+    if tool == GrepTool::Ripgrep && paths.iter().any(|path| is_compressed(path)) {
+        first.insert(1, "-z".to_string());
+    }
+    first.extend(
+        paths
+            .iter()
+            .map(|path| shell_quote(&path.to_string_lossy())),
+    );
+    stages.insert(0, first);
+    stages
+        .iter()
+        .map(|stage| stage.join(" "))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_command_reproduces_filter() {
+        let paths = [
+            PathBuf::from("/var/log/app.log"),
+            PathBuf::from("/var/log/it's old.log.1.gz"),
+        ];
+        assert_eq!(
+            shell_command(GrepTool::Ripgrep, "error|fail", "", false, &paths[..1]),
+            "rg -i -e 'error|fail' /var/log/app.log"
+        );
+        assert_eq!(
+            shell_command(GrepTool::Ripgrep, "Timeout", "retry", true, &paths),
+            "rg -z -s -e Timeout /var/log/app.log '/var/log/it'\\''s old.log.1.gz' \
+             | rg -v -s -e retry"
+        );
+        assert_eq!(
+            shell_command(
+                GrepTool::Grep,
+                "field:level=E conn (?!ok)",
+                "",
+                false,
+                &paths[..1]
+            ),
+            "grep -P -i -e 'conn (?!ok)' /var/log/app.log"
+        );
+        assert_eq!(
+            shell_command(GrepTool::Grep, "", "debug", true, &paths[..1]),
+            "grep -P -v -e debug /var/log/app.log"
+        );
+        assert_eq!(
+            shell_command(GrepTool::Grep, r"\d+ (?:a|b)", "", true, &paths[..1]),
+            r"grep -P -e '\d+ (?:a|b)' /var/log/app.log"
+        );
+    }
+}
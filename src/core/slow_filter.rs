@@ -0,0 +1,228 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Detection of filters that are too slow to evaluate comfortably.
+//!
+//! The filter worker times every pass and counts lines whose match was cut
+//! short by the per-line backtracking budget. When either exceeds its limit
+//! the filter bar shows a warning with the measured time and a hint on how to
+//! rewrite the pattern.
+//!
+//! Both limits are configurable (see [`set_limits`]): the per-line budget
+//! applies to every compiled search pattern, and a pass running past the
+//! overall timeout is stopped and reported as aborted instead of leaving the
+//! tab filtering indefinitely.
+
+use fancy_regex::{Error, Regex, RegexBuilder, RuntimeError};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// A filter pass taking longer than this is reported as slow
+pub const SLOW_FILTER_BUDGET: Duration = Duration::from_millis(500);
+
+/// Default number of backtracking steps a single line may use before its
+/// match is given up.
+///
+/// Bounds the time one line can take, so a catastrophically backtracking
+/// pattern degrades to skipped lines instead of a hung worker.
+pub const DEFAULT_LINE_BACKTRACK_LIMIT: usize = 100_000;
+
+/// Default time after which a filter pass is aborted
+pub const DEFAULT_FILTER_TIMEOUT_SECS: u32 = 10;
+
+static LINE_BACKTRACK_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_LINE_BACKTRACK_LIMIT);
+static FILTER_TIMEOUT_SECS: AtomicU32 = AtomicU32::new(DEFAULT_FILTER_TIMEOUT_SECS);
+
+/// Set the per-line backtracking budget and the overall filter timeout
+/// (0 = no timeout). Patterns compiled afterwards use the new budget.
+pub fn set_limits(line_backtrack_limit: usize, filter_timeout_secs: u32) {
+    LINE_BACKTRACK_LIMIT.store(line_backtrack_limit.max(1), Ordering::Relaxed);
+    FILTER_TIMEOUT_SECS.store(filter_timeout_secs, Ordering::Relaxed);
+}
+
+/// Backtracking steps a single line may currently use.
+#[must_use]
+pub fn line_backtrack_limit() -> usize {
+    LINE_BACKTRACK_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Time after which a filter pass is aborted, if any.
+#[must_use]
+pub fn filter_timeout() -> Option<Duration> {
+    match FILTER_TIMEOUT_SECS.load(Ordering::Relaxed) {
+        0 => None,
+        secs => Some(Duration::from_secs(u64::from(secs))),
+    }
+}
+
+/// Compile a user pattern with the per-line backtracking budget applied.
+///
+/// # Errors
+///
+/// Returns the parse error if `pattern` is not a valid regex.
+pub fn compile_bounded(pattern: &str) -> Result<Regex, Box<Error>> {
+    RegexBuilder::new(pattern)
+        .backtrack_limit(line_backtrack_limit())
+        .build()
+        .map_err(Box::new)
+}
+
+/// `true` when a match error means the line ran out of its budget.
+#[must_use]
+pub const fn is_budget_exceeded(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::RuntimeError(RuntimeError::BacktrackLimitExceeded | RuntimeError::StackOverflow)
+    )
+}
+
+/// How long one filter pass took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilterTiming {
+    /// Wall-clock time spent matching lines
+    pub elapsed: Duration,
+    /// Lines whose match was abandoned because they exceeded the per-line
+    /// backtracking budget; they are treated as non-matching
+    pub aborted_lines: usize,
+    /// The pass hit [`filter_timeout`] and was stopped without results
+    pub timed_out: bool,
+}
+
+impl FilterTiming {
+    /// `true` when the pass was over budget or had to give up on lines.
+    #[must_use]
+    pub const fn is_slow(&self) -> bool {
+        self.timed_out
+            || self.aborted_lines > 0
+            || self.elapsed.as_millis() > SLOW_FILTER_BUDGET.as_millis()
+    }
+}
+
+/// Likely reason a pattern is slow, used to suggest a rewrite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlowPatternHint {
+    /// A quantified group that itself contains a quantifier, e.g. `(a+)+`
+    NestedQuantifier,
+    /// Several unbounded wildcards that can split the line many ways, e.g. `.*a.*b.*`
+    StackedWildcards,
+    /// Lookaround or backreferences force the backtracking engine
+    Backtracking,
+    /// The pattern starts with `.*`, which filters don't need
+    LeadingWildcard,
+    /// No specific cause found
+    General,
+}
+
+static NESTED_QUANTIFIER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\((?:[^()\\]|\\.)*[+*}](?:[^()\\]|\\.)*\)[+*{]").expect("valid regex literal")
+});
+static BACKTRACKING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\(\?<?[=!]|\\[1-9]|\\k<").expect("valid regex literal"));
+
+impl SlowPatternHint {
+    /// Guess why `pattern` is slow.
+    #[must_use]
+    pub fn for_pattern(pattern: &str) -> Self {
+        let pattern = pattern.strip_prefix("(?i)").unwrap_or(pattern);
+        if NESTED_QUANTIFIER.is_match(pattern).unwrap_or(false) {
+            Self::NestedQuantifier
+        } else if BACKTRACKING.is_match(pattern).unwrap_or(false) {
+            Self::Backtracking
+        } else if pattern.matches(".*").count() + pattern.matches(".+").count() >= 3 {
+            Self::StackedWildcards
+        } else if pattern.starts_with(".*") || pattern.starts_with(".+") {
+            Self::LeadingWildcard
+        } else {
+            Self::General
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hint_for_pattern() {
+        assert_eq!(
+            SlowPatternHint::for_pattern("(a+)+b"),
+            SlowPatternHint::NestedQuantifier
+        );
+        assert_eq!(
+            SlowPatternHint::for_pattern(r"(\w*\s?){3,}x"),
+            SlowPatternHint::NestedQuantifier
+        );
+        assert_eq!(
+            SlowPatternHint::for_pattern(r"(?i)(\w+) \1"),
+            SlowPatternHint::Backtracking
+        );
+        assert_eq!(
+            SlowPatternHint::for_pattern("foo(?!bar)"),
+            SlowPatternHint::Backtracking
+        );
+        assert_eq!(
+            SlowPatternHint::for_pattern(".*a.*b.*c"),
+            SlowPatternHint::StackedWildcards
+        );
+        assert_eq!(
+            SlowPatternHint::for_pattern(".*error"),
+            SlowPatternHint::LeadingWildcard
+        );
+        assert_eq!(
+            SlowPatternHint::for_pattern("error"),
+            SlowPatternHint::General
+        );
+        // Escaped parentheses are not groups
+        assert_eq!(
+            SlowPatternHint::for_pattern(r"\(x+\)+"),
+            SlowPatternHint::General
+        );
+    }
+
+    #[test]
+    fn test_budget_exceeded_is_reported() {
+        let regex = compile_bounded(r"^(a|aa)+\1b").expect("valid pattern");
+        let line = "a".repeat(40);
+        let error = regex.is_match(&line).expect_err("should exceed the budget");
+        assert!(is_budget_exceeded(&error));
+        // Harmless patterns are unaffected
+        let regex = compile_bounded("(?i)error").expect("valid pattern");
+        assert_eq!(regex.is_match("ERROR: boom").ok(), Some(true));
+    }
+
+    #[test]
+    fn test_timing_is_slow() {
+        assert!(!FilterTiming::default().is_slow());
+        assert!(FilterTiming {
+            elapsed: SLOW_FILTER_BUDGET * 2,
+            ..FilterTiming::default()
+        }
+        .is_slow());
+        assert!(FilterTiming {
+            aborted_lines: 1,
+            ..FilterTiming::default()
+        }
+        .is_slow());
+        assert!(FilterTiming {
+            timed_out: true,
+            ..FilterTiming::default()
+        }
+        .is_slow());
+    }
+}
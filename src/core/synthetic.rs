@@ -0,0 +1,306 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Synthetic logcat logs for tests and demos.
+//!
+//! A [`SyntheticLog`] writes Android logcat lines from a set of message
+//! templates, timed by a [`FakeClock`] and driven by a seeded generator: the
+//! same options always give the same bytes. Anomalies (error lines of a
+//! template found nowhere else) and bursts (lines a millisecond apart) are
+//! injected at known lines, so tests of the scorer, the histogram and the
+//! filters know what to expect where. `logcrab generate` writes one to a file.
+
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use std::io::{self, Write};
+use std::ops::Range;
+
+/// Messages of the regular lines, as `LEVEL Tag: text`. They avoid the words
+/// the keyword scorer looks for, so only injected anomalies are errors.
+pub const DEFAULT_TEMPLATES: [&str; 8] = [
+    "I ActivityManager: Start proc {n}:com.example.app for activity",
+    "D WifiStateMachine: RSSI changed to -{n} dBm",
+    "I PowerManager: Screen brightness set to {n}",
+    "D NetworkController: Received {n} bytes on wlan0",
+    "I BatteryService: Battery level {n}%",
+    "D InputDispatcher: Delivering touch to window {n}",
+    "I AudioFlinger: Stream {n} started",
+    "D SensorService: Accelerometer sample {n}",
+];
+
+/// Messages of the injected anomalies, used in turn
+const ANOMALY_TEMPLATES: [&str; 4] = [
+    "E StorageManager: Filesystem error on block {n}, remounting read-only",
+    "F libc: Fatal signal 11 (SIGSEGV), fault addr 0x{n}",
+    "E Watchdog: Service {n} crashed, restarting",
+    "E kernel: Out of memory: killed process {n}",
+];
+
+/// Placeholder in a template replaced by a random number
+const NUMBER_PLACEHOLDER: &str = "{n}";
+
+/// Clock that only moves when told to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FakeClock {
+    now: NaiveDateTime,
+}
+
+impl FakeClock {
+    #[must_use]
+    pub const fn new(start: NaiveDateTime) -> Self {
+        Self { now: start }
+    }
+
+    #[must_use]
+    pub const fn now(&self) -> NaiveDateTime {
+        self.now
+    }
+
+    /// Move the clock forward by `ms` milliseconds; it stays put on overflow
+    pub fn advance(&mut self, ms: u64) {
+        let step = i64::try_from(ms).ok().and_then(TimeDelta::try_milliseconds);
+        if let Some(now) = step.and_then(|step| self.now.checked_add_signed(step)) {
+            self.now = now;
+        }
+    }
+}
+
+/// Deterministic xorshift, so a seed always gives the same log
+struct Rng(u64);
+
+impl Rng {
+    const fn new(seed: u64) -> Self {
+        // xorshift never leaves the zero state
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    const fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// What a generated line is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// A regular line of the template at this index
+    Template(usize),
+    /// The n-th injected anomaly
+    Anomaly(usize),
+}
+
+/// One generated line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticLine {
+    pub time: NaiveDateTime,
+    pub kind: LineKind,
+    /// The line as written, without its newline
+    pub text: String,
+}
+
+/// Options of a synthetic log
+#[derive(Debug, Clone)]
+pub struct SyntheticLog {
+    pub seed: u64,
+    /// Number of lines, anomalies and bursts included
+    pub lines: usize,
+    /// Time of the first line
+    pub start: NaiveDateTime,
+    /// Mean time between two lines outside of bursts; each gap is drawn
+    /// from half to one and a half of it
+    pub interval_ms: u64,
+    /// Messages of the regular lines as `LEVEL Tag: text`, where each `{n}`
+    /// becomes a random number. Earlier templates are logged more often.
+    /// Empty for [`DEFAULT_TEMPLATES`].
+    pub templates: Vec<String>,
+    /// Number of error lines spread evenly over the log
+    pub anomalies: usize,
+    /// Number of bursts spread evenly over the log
+    pub bursts: usize,
+    /// Lines per burst, logged one millisecond apart
+    pub burst_lines: usize,
+}
+
+impl Default for SyntheticLog {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            lines: 10_000,
+            start: NaiveDate::from_ymd_opt(2024, 6, 15)
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .expect("valid start time"),
+            interval_ms: 100,
+            templates: Vec::new(),
+            anomalies: 5,
+            bursts: 0,
+            burst_lines: 200,
+        }
+    }
+}
+
+impl SyntheticLog {
+    /// Indices of the injected anomalies, ascending
+    #[must_use]
+    pub fn anomaly_lines(&self) -> Vec<usize> {
+        (0..self.anomalies)
+            .map(|i| (i + 1) * self.lines / (self.anomalies + 1))
+            .collect()
+    }
+
+    /// Line ranges of the bursts, each centered in its share of the log
+    #[must_use]
+    pub fn burst_ranges(&self) -> Vec<Range<usize>> {
+        (0..self.bursts)
+            .map(|i| {
+                let center = (2 * i + 1) * self.lines / (2 * self.bursts);
+                let start = center.saturating_sub(self.burst_lines / 2);
+                start..(start + self.burst_lines).min(self.lines)
+            })
+            .collect()
+    }
+
+    /// The lines of the log in order.
+    ///
+    /// The first lines go through the templates once, so every regular
+    /// message is known before the scorer's warm-up ends.
+    pub fn lines(&self) -> impl Iterator<Item = SyntheticLine> + '_ {
+        let templates: Vec<&str> = if self.templates.is_empty() {
+            DEFAULT_TEMPLATES.to_vec()
+        } else {
+            self.templates.iter().map(String::as_str).collect()
+        };
+        let anomalies = self.anomaly_lines();
+        let bursts = self.burst_ranges();
+        let in_burst = move |i: usize| bursts.iter().any(|burst| burst.contains(&i));
+        let mut rng = Rng::new(self.seed);
+        let mut clock = FakeClock::new(self.start);
+
+        (0..self.lines).map(move |i| {
+            if i > 0 {
+                let step = if in_burst(i) && in_burst(i - 1) {
+                    1
+                } else {
+                    self.interval_ms / 2 + rng.below(self.interval_ms + 1)
+                };
+                clock.advance(step);
+            }
+            let (kind, template, pid) = anomalies.binary_search(&i).map_or_else(
+                |_| {
+                    let index = if i < templates.len() {
+                        i
+                    } else {
+                        // Skewed towards the first templates
+                        let bound = rng.below(templates.len() as u64) + 1;
+                        rng.below(bound) as usize
+                    };
+                    (
+                        LineKind::Template(index),
+                        templates[index],
+                        1000 + 37 * index,
+                    )
+                },
+                |n| {
+                    (
+                        LineKind::Anomaly(n),
+                        ANOMALY_TEMPLATES[n % ANOMALY_TEMPLATES.len()],
+                        666,
+                    )
+                },
+            );
+
+            let mut message = String::new();
+            for (n, part) in template.split(NUMBER_PLACEHOLDER).enumerate() {
+                if n > 0 {
+                    message.push_str(&rng.below(10_000).to_string());
+                }
+                message.push_str(part);
+            }
+            let tid = pid + rng.below(4) as usize;
+            SyntheticLine {
+                time: clock.now(),
+                kind,
+                text: format!(
+                    "{} {pid:>5} {tid:>5} {message}",
+                    clock.now().format("%m-%d %H:%M:%S%.3f")
+                ),
+            }
+        })
+    }
+
+    /// Write the log as text, one line each.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails.
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        for line in self.lines() {
+            writeln!(out, "{}", line.text)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_options_give_the_same_log() {
+        let log = SyntheticLog {
+            lines: 500,
+            anomalies: 2,
+            bursts: 2,
+            burst_lines: 50,
+            ..Default::default()
+        };
+        let lines: Vec<SyntheticLine> = log.lines().collect();
+        assert_eq!(lines, log.lines().collect::<Vec<_>>());
+        assert_ne!(
+            lines,
+            SyntheticLog {
+                seed: 2,
+                ..log.clone()
+            }
+            .lines()
+            .collect::<Vec<_>>()
+        );
+
+        assert_eq!(lines[0].time, log.start);
+        assert_eq!(lines[0].kind, LineKind::Template(0));
+        assert!(lines[0].text.starts_with("06-15 12:00:00.000  1000  100"));
+        assert_eq!(log.anomaly_lines(), [166, 333]);
+        assert_eq!(lines[166].kind, LineKind::Anomaly(0));
+        assert!(lines[333].text.contains(" F libc: Fatal signal 11"));
+
+        // Burst lines are a millisecond apart, all others further
+        assert_eq!(log.burst_ranges(), [100..150, 350..400]);
+        for i in 1..lines.len() {
+            let gap = (lines[i].time - lines[i - 1].time).num_milliseconds();
+            if (101..150).contains(&i) || (351..400).contains(&i) {
+                assert_eq!(gap, 1, "line {i}");
+            } else {
+                assert!((50..=150).contains(&gap), "line {i}: {gap} ms");
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Clipboard formats for copying several log lines at once.
+//!
+//! Lines pasted into a ticket or wiki read best as a table. [`copy_rows`]
+//! renders the lines of a range selection as plain text, as CSV or as a
+//! Markdown table with the timestamp, level, source and message of each line.
+
+/// How copied lines are laid out on the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// The raw lines as they appear in their files
+    PlainText,
+    Csv,
+    Markdown,
+}
+
+impl CopyFormat {
+    pub const ALL: [Self; 3] = [Self::PlainText, Self::Csv, Self::Markdown];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::PlainText => "Plain Text",
+            Self::Csv => "CSV",
+            Self::Markdown => "Markdown Table",
+        }
+    }
+}
+
+/// One line to copy, with its columns already formatted for display
+#[derive(Debug, Clone, Default)]
+pub struct CopiedRow {
+    pub timestamp: String,
+    pub level: String,
+    pub source: String,
+    pub message: String,
+    /// The line as it appears in its file, for [`CopyFormat::PlainText`]
+    pub raw: String,
+}
+
+const HEADER: [&str; 4] = ["Timestamp", "Level", "Source", "Message"];
+
+/// `rows` laid out in `format`
+#[must_use]
+pub fn copy_rows(format: CopyFormat, rows: &[CopiedRow]) -> String {
+    match format {
+        CopyFormat::PlainText => rows
+            .iter()
+            .map(|row| row.raw.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        CopyFormat::Csv => csv_table(rows),
+        CopyFormat::Markdown => markdown_table(rows),
+    }
+}
+
+fn csv_table(rows: &[CopiedRow]) -> String {
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    let written = writer.write_record(HEADER).is_ok()
+        && rows.iter().all(|row| {
+            writer
+                .write_record([&row.timestamp, &row.level, &row.source, &row.message])
+                .is_ok()
+        });
+    if !written {
+        return String::new();
+    }
+    String::from_utf8_lossy(&writer.into_inner().unwrap_or_default()).into_owned()
+}
+
+/// A cell that keeps the table intact: pipes escaped, line breaks as `<br>`
+fn markdown_cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+fn markdown_table(rows: &[CopiedRow]) -> String {
+    let mut lines = vec![
+        format!("| {} |", HEADER.join(" | ")),
+        format!("|{}", " --- |".repeat(HEADER.len())),
+    ];
+    lines.extend(rows.iter().map(|row| {
+        let cells = [&row.timestamp, &row.level, &row.source, &row.message];
+        let cells: Vec<String> = cells.iter().map(|cell| markdown_cell(cell)).collect();
+        format!("| {} |", cells.join(" | "))
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_rows_as_csv_and_markdown() {
+        let rows = [
+            CopiedRow {
+                timestamp: "2026-02-11 13:45:49.663".to_string(),
+                level: "E".to_string(),
+                source: "app.log".to_string(),
+                message: "write failed: a|b, \"disk\"".to_string(),
+                raw: "raw one".to_string(),
+            },
+            CopiedRow {
+                message: "two\nlines".to_string(),
+                raw: "raw two".to_string(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(copy_rows(CopyFormat::PlainText, &rows), "raw one\nraw two");
+        assert_eq!(
+            copy_rows(CopyFormat::Csv, &rows),
+            "Timestamp,Level,Source,Message\n\
+             2026-02-11 13:45:49.663,E,app.log,\"write failed: a|b, \"\"disk\"\"\"\n\
+             ,,,\"two\nlines\"\n"
+        );
+        assert_eq!(
+            copy_rows(CopyFormat::Markdown, &rows),
+            "| Timestamp | Level | Source | Message |\n\
+             | --- | --- | --- | --- |\n\
+             | 2026-02-11 13:45:49.663 | E | app.log | write failed: a\\|b, \"disk\" |\n\
+             |  |  |  | two<br>lines |"
+        );
+    }
+}
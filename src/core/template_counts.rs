@@ -0,0 +1,130 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! How often each line's message template occurs in the whole store.
+//!
+//! Backs the optional `Count` column of the log table: a line whose
+//! template is seen once or twice among millions stands out without
+//! opening any other view. Counts are computed in the background, once per
+//! [`StoreVersion`].
+
+use crate::core::log_store::{StoreID, StoreVersion};
+use crate::core::LogStore;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+
+/// Template occurrence count of every line, for one store version
+#[derive(Debug, Default)]
+pub struct TemplateCounts {
+    pub store_version: StoreVersion,
+    /// Per source: index of its first retained line and the counts from there on
+    per_source: HashMap<u64, (usize, Vec<u32>)>,
+}
+
+impl TemplateCounts {
+    /// Count the templates of all lines in `store`
+    #[must_use]
+    pub fn compute(store: &LogStore) -> Self {
+        profiling::scope!("TemplateCounts::compute");
+        let store_version = store.version();
+        let templates = store.all_template_keys();
+        let mut totals: HashMap<&str, u32> = HashMap::new();
+        for (_, _, keys) in &templates {
+            for key in keys {
+                *totals.entry(key.as_str()).or_default() += 1;
+            }
+        }
+        let per_source = templates
+            .iter()
+            .map(|(source_id, first_index, keys)| {
+                let counts = keys.iter().map(|key| totals[key.as_str()]).collect();
+                (*source_id, (*first_index, counts))
+            })
+            .collect();
+        Self {
+            store_version,
+            per_source,
+        }
+    }
+
+    /// How many lines share the template of `id`, if it was counted
+    #[must_use]
+    pub fn count(&self, id: &StoreID) -> Option<u32> {
+        let (first_index, counts) = self.per_source.get(&id.source_id())?;
+        let offset = id.line_index_within_source().checked_sub(*first_index)?;
+        counts.get(offset).copied()
+    }
+}
+
+/// Latest [`TemplateCounts`] of a store, refreshed in the background
+#[derive(Default)]
+pub struct TemplateCountCache {
+    counts: Option<Arc<TemplateCounts>>,
+    /// Channel of the computation in flight
+    pending: Option<Receiver<TemplateCounts>>,
+}
+
+impl TemplateCountCache {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            counts: None,
+            pending: None,
+        }
+    }
+
+    /// The latest finished counts, starting a new computation if the store
+    /// changed since.
+    ///
+    /// At most one computation runs at a time; while a file is still loading
+    /// the counts are refreshed each time the previous computation finishes.
+    pub fn request(
+        &mut self,
+        store: &Arc<LogStore>,
+        ctx: &egui::Context,
+    ) -> Option<Arc<TemplateCounts>> {
+        if let Some(rx) = &self.pending {
+            match rx.try_recv() {
+                Ok(counts) => {
+                    self.counts = Some(Arc::new(counts));
+                    self.pending = None;
+                }
+                Err(TryRecvError::Empty) => return self.counts.clone(),
+                Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+        let version = store.version();
+        if !self
+            .counts
+            .as_ref()
+            .is_some_and(|counts| counts.store_version == version)
+        {
+            let (tx, rx) = mpsc::channel();
+            let store = Arc::clone(store);
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                // The session may have been closed; nobody to deliver to then.
+                let _ = tx.send(TemplateCounts::compute(&store));
+                ctx.request_repaint();
+            });
+            self.pending = Some(rx);
+        }
+        self.counts.clone()
+    }
+}
@@ -0,0 +1,154 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Shared text storage for parsed lines.
+//!
+//! Giving every parsed line its own `String`s costs one or more heap
+//! allocations per line and fragments the heap across millions of lines.
+//! Readers instead append the raw text of a whole chunk to a [`TextArena`],
+//! freeze it into one shared buffer and hand each line an [`ArenaStr`]: the
+//! buffer plus the line's byte range. Parts of a line that are substrings of
+//! its raw text (message, tag) are kept as offsets into that range.
+
+use std::fmt;
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
+/// Append-only text buffer for the lines of one chunk
+#[derive(Default)]
+pub struct TextArena {
+    buf: String,
+}
+
+impl TextArena {
+    #[must_use]
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            buf: String::with_capacity(bytes),
+        }
+    }
+
+    /// Append `text`, returning its byte range within the arena
+    pub fn push(&mut self, text: &str) -> Range<usize> {
+        let start = self.buf.len();
+        self.buf.push_str(text);
+        start..self.buf.len()
+    }
+
+    /// Freeze the arena into the buffer the pushed ranges refer to
+    #[must_use]
+    pub fn freeze(self) -> Arc<str> {
+        Arc::from(self.buf)
+    }
+}
+
+/// A string slice of a shared arena buffer
+///
+/// Cloning shares the buffer; the buffer is freed once every line of its
+/// chunk has been dropped.
+#[derive(Clone)]
+pub struct ArenaStr {
+    buf: Arc<str>,
+    start: usize,
+    end: usize,
+}
+
+impl ArenaStr {
+    /// Slice `range` of a frozen arena buffer
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or not on `char` boundaries.
+    #[must_use]
+    pub fn new(buf: Arc<str>, range: Range<usize>) -> Self {
+        assert!(
+            buf.get(range.clone()).is_some(),
+            "arena range {range:?} is not a valid slice"
+        );
+        Self {
+            buf,
+            start: range.start,
+            end: range.end,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf[self.start..self.end]
+    }
+}
+
+impl From<String> for ArenaStr {
+    /// A single line that was not read as part of a chunk
+    fn from(text: String) -> Self {
+        let end = text.len();
+        Self {
+            buf: Arc::from(text),
+            start: 0,
+            end,
+        }
+    }
+}
+
+impl Deref for ArenaStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for ArenaStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArenaStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_share_one_buffer() {
+        let mut arena = TextArena::default();
+        let first = arena.push("first line");
+        let second = arena.push("zweite Zeile ✓");
+        let buf = arena.freeze();
+        let a = ArenaStr::new(Arc::clone(&buf), first);
+        let b = ArenaStr::new(Arc::clone(&buf), second);
+        assert_eq!(a.as_str(), "first line");
+        assert_eq!(&*b, "zweite Zeile ✓");
+        assert_eq!(Arc::strong_count(&buf), 3);
+        drop(a);
+        drop(b);
+        assert_eq!(Arc::strong_count(&buf), 1);
+    }
+
+    #[test]
+    fn test_from_string() {
+        let text = ArenaStr::from("standalone".to_string());
+        assert_eq!(text.as_str(), "standalone");
+        assert_eq!(format!("{text:?}"), "\"standalone\"");
+    }
+}
@@ -1,16 +1,23 @@
 // LogCrab - GPL-3.0-or-later
 // Copyright (C) 2026 Daniel Freiermuth
 
-//! Export primitives shared by `logcrab-export` and the macro-generated
-//! `export_dispatch` function in `core::log_store`.
+//! Export primitives shared by the headless tools.
+//!
+//! Used by `logcrab-export`, `logcrab convert`, `logcrab scan`,
+//! `logcrab --detect`, `logcrab --export-scores`, the folder analysis window
+//! and the macro-generated `*_dispatch` functions in `core::log_store`.
 
 use std::io::Write;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use anyhow::Context as _;
+use chrono::{DateTime, Datelike, Local};
 use serde::Serialize;
 
+use crate::anomaly::keyword::ERROR_KEYWORDS_PATTERN;
+use crate::anomaly::report::{score_report, ScoreRecord};
+use crate::core::log_store::{LogLine, LogStore, StoreID};
 use crate::filetype::{InputFileType, LineType};
 
 /// One NDJSON record emitted by `logcrab-export`.
@@ -25,14 +32,231 @@ pub struct ExportRecord<'a> {
 
 const EXPORT_CHUNK: usize = 4096;
 
-/// Read all lines from `path` using file type `FT` and write them as NDJSON.
+/// Error keywords of the keyword scorer, counted by [`summarize_typed`]
+static ERROR_REGEX: LazyLock<fancy_regex::Regex> = LazyLock::new(|| {
+    fancy_regex::Regex::new(&format!("(?i){ERROR_KEYWORDS_PATTERN}")).expect("valid regex literal")
+});
+
+/// Receives the concrete file type detected by
+/// [`crate::core::log_store::visit_detected`].
+///
+/// Multi-container logs are visited once per container, see
+/// [`crate::core::container_split`].
+pub trait FileTypeVisitor {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()>;
+
+    /// Called before the file of each container of a multi-container log is
+    /// visited.
+    fn begin_container(&mut self, _name: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Visitor writing every line as an NDJSON [`ExportRecord`].
+pub struct NdjsonExport<'w, W: Write> {
+    pub out: &'w mut W,
+}
+
+impl<W: Write> FileTypeVisitor for NdjsonExport<'_, W> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        export_typed::<FT>(path, filetype, self.out)
+    }
+}
+
+/// Visitor writing every line as `timestamp message` text.
+pub struct TextConvert<'w, W: Write> {
+    pub out: &'w mut W,
+}
+
+impl<W: Write> FileTypeVisitor for TextConvert<'_, W> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        convert_typed::<FT>(path, filetype, self.out)
+    }
+}
+
+/// Visitor writing a short summary of the detected format (`logcrab --detect`).
+pub struct DetectReport<'w, W: Write> {
+    pub out: &'w mut W,
+}
+
+impl<W: Write> FileTypeVisitor for DetectReport<'_, W> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        detect_typed::<FT>(path, filetype, self.out)
+    }
+
+    fn begin_container(&mut self, name: &str) -> anyhow::Result<()> {
+        writeln!(self.out, "container: {name}").context("write error")
+    }
+}
+
+/// Visitor recording the display name of the detected format.
+pub struct FormatName<'a> {
+    pub out: &'a mut Option<&'static str>,
+}
+
+impl FileTypeVisitor for FormatName<'_> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        _path: &Path,
+        _filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        // The first container names a multi-container log
+        self.out.get_or_insert(FT::DISPLAY_NAME);
+        Ok(())
+    }
+}
+
+/// Visitor writing the anomaly score breakdown of every line as NDJSON.
+pub struct ScoreExport<'w, W: Write> {
+    pub out: &'w mut W,
+}
+
+impl<W: Write> FileTypeVisitor for ScoreExport<'_, W> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        export_scores_typed::<FT>(path, filetype, self.out)
+    }
+}
+
+/// Visitor writing the lines of a file that match a [`ScanOptions`]
+/// (`logcrab scan`), storing how many did in `matched`.
+pub struct Scan<'w, W: Write> {
+    pub options: &'w ScanOptions,
+    pub out: &'w mut W,
+    pub matched: &'w mut usize,
+}
+
+impl<W: Write> FileTypeVisitor for Scan<'_, W> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        *self.matched += scan_typed::<FT>(path, filetype, self.options, self.out)?;
+        Ok(())
+    }
+}
+
+/// Visitor scoring a file and storing its [`FileSummary`] in `out`.
+pub struct Summarize<'s> {
+    pub out: &'s mut Option<FileSummary>,
+}
+
+impl FileTypeVisitor for Summarize<'_> {
+    fn visit<FT: InputFileType>(
+        &mut self,
+        path: &Path,
+        filetype: &'static str,
+    ) -> anyhow::Result<()> {
+        let summary = summarize_typed::<FT>(path, filetype)?;
+        *self.out = Some(match self.out.take() {
+            Some(containers) => containers.merge(&summary),
+            None => summary,
+        });
+        Ok(())
+    }
+}
+
+/// Which lines `logcrab scan` prints and how.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Lines must match in their message or raw text, like a filter tab
+    pub filter: Option<fancy_regex::Regex>,
+    /// Lines matching this in their message or raw text are dropped
+    pub exclude: Option<fancy_regex::Regex>,
+    /// Lowest anomaly score in [0, 100] a line needs
+    pub min_score: f64,
+    /// One NDJSON [`ScanRecord`] per line instead of text
+    pub json: bool,
+    /// Prefix text lines with the file name, as when scanning several files
+    pub with_file_name: bool,
+}
+
+/// One NDJSON record printed by `logcrab scan --format json`.
+#[derive(Serialize)]
+pub struct ScanRecord<'a> {
+    pub source_file: &'a str,
+    pub line_number: usize,
+    pub timestamp_unix_ms: i64,
+    /// Composite score normalized to [0, 100], as shown in the log table
+    pub score: f64,
+    pub message: &'a str,
+}
+
+/// Headline numbers of one scored file, used to rank a folder of logs.
+#[derive(Debug, Clone)]
+pub struct FileSummary {
+    pub filetype: &'static str,
+    pub lines: usize,
+    /// Lines matching the error keywords of the keyword scorer
+    pub errors: usize,
+    /// Highest anomaly score in [0, 100]
+    pub max_score: f64,
+    pub first_timestamp_ms: Option<i64>,
+    pub last_timestamp_ms: Option<i64>,
+}
+
+impl FileSummary {
+    /// Time between the first and last line, if any line was parsed.
+    #[must_use]
+    pub fn duration_ms(&self) -> Option<i64> {
+        Some(self.last_timestamp_ms? - self.first_timestamp_ms?)
+    }
+
+    /// Summary of two files taken together, e.g. the containers of one log.
+    /// The format is the first one's.
+    #[must_use]
+    pub fn merge(self, other: &Self) -> Self {
+        Self {
+            filetype: self.filetype,
+            lines: self.lines + other.lines,
+            errors: self.errors + other.errors,
+            max_score: self.max_score.max(other.max_score),
+            first_timestamp_ms: self
+                .first_timestamp_ms
+                .into_iter()
+                .chain(other.first_timestamp_ms)
+                .min(),
+            last_timestamp_ms: self
+                .last_timestamp_ms
+                .into_iter()
+                .chain(other.last_timestamp_ms)
+                .max(),
+        }
+    }
+}
+
+/// Read `path` with file type `FT`, calling `f` for every chunk of lines.
 ///
 /// Config and file-state are both `Default`, so timestamps are raw and
-/// uncalibrated — honouring the stability invariant on [`LineType::timestamp`].
-pub fn export_typed<FT: InputFileType>(
+/// uncalibrated.
+fn for_each_chunk<FT: InputFileType>(
     path: &Path,
     filetype: &str,
-    out: &mut impl Write,
+    mut f: impl FnMut(
+        &[FT::LineType],
+        &<FT::LineType as LineType>::Config,
+        &<FT::LineType as LineType>::FileState,
+    ) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
     let config = <<FT as InputFileType>::LineType as LineType>::Config::default();
     let file_state =
@@ -41,22 +265,35 @@ pub fn export_typed<FT: InputFileType>(
     let mut reader = FT::open(path, config.clone(), Arc::clone(&file_state))
         .with_context(|| format!("failed to open {} as {filetype}", path.display()))?;
 
-    let source_file = path
-        .file_name()
-        .unwrap_or_else(|| path.as_os_str())
-        .to_string_lossy();
-
     loop {
         let lines = reader
             .read(EXPORT_CHUNK)
             .with_context(|| format!("read error in {}", path.display()))?;
 
         if lines.is_empty() {
-            break;
+            return Ok(());
         }
+        f(&lines, &config, &file_state)?;
+    }
+}
+
+/// Read all lines from `path` using file type `FT` and write them as NDJSON.
+///
+/// Config and file-state are both `Default`, so timestamps are raw and
+/// uncalibrated — honouring the stability invariant on [`LineType::timestamp`].
+pub fn export_typed<FT: InputFileType>(
+    path: &Path,
+    filetype: &str,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let source_file = path
+        .file_name()
+        .unwrap_or_else(|| path.as_os_str())
+        .to_string_lossy();
 
-        for line in &lines {
-            let ts = line.timestamp(&config, &file_state);
+    for_each_chunk::<FT>(path, filetype, |lines, config, file_state| {
+        for line in lines {
+            let ts = line.timestamp(config, file_state);
             let record = ExportRecord {
                 line_number: line.line_number(),
                 timestamp_unix_ms: ts.timestamp_millis(),
@@ -67,7 +304,355 @@ pub fn export_typed<FT: InputFileType>(
             serde_json::to_writer(&mut *out, &record).context("failed to serialize record")?;
             writeln!(out).context("write error")?;
         }
+        Ok(())
+    })
+}
+
+/// Read all lines from `path` using file type `FT` and write them as text.
+///
+/// Each line is the timestamp followed by the decoded message, i.e. the same
+/// strings the log table shows, so decoded binary formats can be fed to
+/// `grep` and friends. Line breaks inside a message (e.g. a quoted CSV cell)
+/// are written as `\n` and `\r`, keeping one output line per log line.
+pub fn convert_typed<FT: InputFileType>(
+    path: &Path,
+    filetype: &str,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    for_each_chunk::<FT>(path, filetype, |lines, config, file_state| {
+        for line in lines {
+            let ts = line.timestamp(config, file_state);
+            let message = line.display_message(config, file_state);
+            writeln!(
+                out,
+                "{} {}",
+                ts.format("%Y-%m-%d %H:%M:%S%.3f"),
+                escape_line_breaks(&message)
+            )
+            .context("write error")?;
+        }
+        Ok(())
+    })
+}
+
+/// `message` with `\n` and `\r` spelled out, borrowed if it has neither
+fn escape_line_breaks(message: &str) -> std::borrow::Cow<'_, str> {
+    if message.contains(['\n', '\r']) {
+        message.replace('\n', "\\n").replace('\r', "\\r").into()
+    } else {
+        message.into()
     }
+}
+
+/// Read all lines from `path` using file type `FT` and write a summary: the
+/// format, the year timestamps were placed in, line counts and time range.
+///
+/// Fails when not a single line could be parsed, so scripts can treat the
+/// exit status as "this tool understands the file".
+pub fn detect_typed<FT: InputFileType>(
+    path: &Path,
+    filetype: &str,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut parsed = 0usize;
+    let mut highest_line_number = 0usize;
+    let mut range: Option<(DateTime<Local>, DateTime<Local>)> = None;
+
+    for_each_chunk::<FT>(path, filetype, |lines, config, file_state| {
+        for line in lines {
+            parsed += 1;
+            highest_line_number = highest_line_number.max(line.line_number());
+            let ts = line.timestamp(config, file_state);
+            range = Some(range.map_or((ts, ts), |(first, last)| (first.min(ts), last.max(ts))));
+        }
+        Ok(())
+    })?;
+
+    let Some((first, last)) = range else {
+        anyhow::bail!(
+            "no lines of {} could be parsed as {filetype}",
+            path.display()
+        );
+    };
+    // Text readers count every source line, binary ones only records
+    let skipped = highest_line_number.saturating_sub(parsed);
 
+    writeln!(out, "format: {filetype}").context("write error")?;
+    writeln!(out, "year: {}", first.year()).context("write error")?;
+    writeln!(out, "parsed lines: {parsed}").context("write error")?;
+    writeln!(out, "skipped lines: {skipped}").context("write error")?;
+    writeln!(
+        out,
+        "first timestamp: {}",
+        first.format("%Y-%m-%d %H:%M:%S%.3f")
+    )
+    .context("write error")?;
+    writeln!(
+        out,
+        "last timestamp: {}",
+        last.format("%Y-%m-%d %H:%M:%S%.3f")
+    )
+    .context("write error")?;
     Ok(())
 }
+
+/// Read all lines from `path` using file type `FT`, score them and write one
+/// NDJSON [`ScoreRecord`] per line.
+pub fn export_scores_typed<FT: InputFileType>(
+    path: &Path,
+    filetype: &str,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let source_file = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+
+    let lines = read_log_lines::<FT>(path, filetype)?;
+    write_score_records(&score_report(&source_file, lines), out)
+}
+
+/// Read all lines from `path` using file type `FT`, score them and write
+/// those matching `options`. Returns the number of lines written.
+///
+/// Filtering matches a filter tab: a line is kept if its message or raw text
+/// matches `options.filter` and neither matches `options.exclude`.
+pub fn scan_typed<FT: InputFileType>(
+    path: &Path,
+    filetype: &str,
+    options: &ScanOptions,
+    out: &mut impl Write,
+) -> anyhow::Result<usize> {
+    let source_file = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    let is_match = |regex: &fancy_regex::Regex, line: &LogLine| {
+        regex.is_match(&line.message).unwrap_or(false) || regex.is_match(&line.raw).unwrap_or(false)
+    };
+
+    let lines = read_log_lines::<FT>(path, filetype)?;
+    // Scoring consumes the lines; records come back one per line, in order
+    let kept: Vec<Option<(DateTime<Local>, String)>> = lines
+        .iter()
+        .map(|line| {
+            let keep = options.filter.as_ref().is_none_or(|r| is_match(r, line))
+                && !options.exclude.as_ref().is_some_and(|r| is_match(r, line));
+            keep.then(|| (line.timestamp(), line.message.clone()))
+        })
+        .collect();
+    let records = score_report(&source_file, lines);
+
+    let mut written = 0;
+    for (record, kept) in records.iter().zip(&kept) {
+        let Some((ts, message)) = kept else {
+            continue;
+        };
+        if record.score < options.min_score {
+            continue;
+        }
+        if options.json {
+            let record = ScanRecord {
+                source_file: &source_file,
+                line_number: record.line_number,
+                timestamp_unix_ms: record.timestamp_unix_ms,
+                score: record.score,
+                message,
+            };
+            serde_json::to_writer(&mut *out, &record).context("failed to serialize record")?;
+            writeln!(out).context("write error")?;
+        } else {
+            if options.with_file_name {
+                write!(out, "{source_file}:").context("write error")?;
+            }
+            writeln!(
+                out,
+                "{} {:5.1} {message}",
+                ts.format("%Y-%m-%d %H:%M:%S%.3f"),
+                record.score
+            )
+            .context("write error")?;
+        }
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Read all lines from `path` using file type `FT` as unscored [`LogLine`]s.
+fn read_log_lines<FT: InputFileType>(path: &Path, filetype: &str) -> anyhow::Result<Vec<LogLine>> {
+    let mut lines = Vec::new();
+    for_each_chunk::<FT>(path, filetype, |chunk, config, file_state| {
+        lines.extend(chunk.iter().map(|line| LogLine {
+            timestamp_ms: line.timestamp_ms(config, file_state),
+            message: line.display_message(config, file_state),
+            raw: line.raw(),
+            line_number: line.line_number(),
+            request: line.request_metrics(),
+            severity: line.severity(),
+            anomaly_score: 0.0,
+            sidecar_anomaly_score: 0.0,
+            sidecar_score_is_unk: false,
+            sidecar_score_is_rare: false,
+            sidecar_scored: false,
+        }));
+        Ok(())
+    })?;
+    Ok(lines)
+}
+
+/// Read all lines from `path` using file type `FT`, score them and count
+/// errors.
+///
+/// Scores match [`export_scores_typed`], so the summary agrees with the
+/// score column of a source loaded without calibration.
+pub fn summarize_typed<FT: InputFileType>(
+    path: &Path,
+    filetype: &'static str,
+) -> anyhow::Result<FileSummary> {
+    let mut errors = 0;
+    let mut lines = Vec::new();
+    for_each_chunk::<FT>(path, filetype, |chunk, config, file_state| {
+        for line in chunk {
+            let message = line.display_message(config, file_state);
+            if ERROR_REGEX.is_match(&message).unwrap_or(false) {
+                errors += 1;
+            }
+            lines.push(LogLine {
+                timestamp_ms: line.timestamp_ms(config, file_state),
+                message,
+                raw: line.raw(),
+                line_number: line.line_number(),
+                request: line.request_metrics(),
+                severity: line.severity(),
+                anomaly_score: 0.0,
+                sidecar_anomaly_score: 0.0,
+                sidecar_score_is_unk: false,
+                sidecar_score_is_rare: false,
+                sidecar_scored: false,
+            });
+        }
+        Ok(())
+    })?;
+
+    let first_timestamp_ms = lines.iter().map(|l| l.timestamp_ms).min();
+    let last_timestamp_ms = lines.iter().map(|l| l.timestamp_ms).max();
+    let line_count = lines.len();
+    let source_file = path
+        .file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    let max_score = score_report(&source_file, lines)
+        .iter()
+        .map(|r| r.score)
+        .fold(0.0, f64::max);
+
+    Ok(FileSummary {
+        filetype,
+        lines: line_count,
+        errors,
+        max_score,
+        first_timestamp_ms,
+        last_timestamp_ms,
+    })
+}
+
+/// Write `records` as NDJSON, one record per line.
+pub fn write_score_records(records: &[ScoreRecord], out: &mut impl Write) -> anyhow::Result<()> {
+    for record in records {
+        serde_json::to_writer(&mut *out, record).context("failed to serialize record")?;
+        writeln!(out).context("write error")?;
+    }
+    Ok(())
+}
+
+/// Score every source loaded in `store` and write the breakdown as NDJSON.
+///
+/// Unlike the headless export, timestamps include any calibration applied in
+/// the session. Returns the number of lines written.
+pub fn export_store_scores(store: &LogStore, out: &mut impl Write) -> anyhow::Result<usize> {
+    let mut written = 0;
+    for (source_id, filename) in store.get_source_filenames() {
+        let lines = (0..store.source_len(source_id))
+            .filter_map(|idx| store.get_by_id(&StoreID::make(source_id, idx)));
+        let records = score_report(&filename, lines);
+        write_score_records(&records, out)?;
+        written += records.len();
+    }
+    out.flush().context("write error")?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filetype::generic::GenericFileType;
+
+    #[test]
+    fn test_detect_typed_reports_range_and_counts() {
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        tmp.write_all(
+            b"2025-11-20 14:23:45.123 first\n\
+              2025-11-20 14:25:00.000 second\n\
+              2025-11-20 14:24:10.500 third\n",
+        )
+        .expect("write");
+
+        let mut out = Vec::new();
+        detect_typed::<GenericFileType>(tmp.path(), "generic", &mut out).expect("detect");
+        assert_eq!(
+            String::from_utf8(out).expect("utf-8"),
+            "format: generic\n\
+             year: 2025\n\
+             parsed lines: 3\n\
+             skipped lines: 0\n\
+             first timestamp: 2025-11-20 14:23:45.123\n\
+             last timestamp: 2025-11-20 14:25:00.000\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_typed_keeps_multi_line_cells_on_one_line() {
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        tmp.write_all(
+            b"timestamp,level,message\n\
+              2025-01-01 10:00:00,INFO,\"first\r\nsecond\"\n\
+              2025-01-01 10:00:01,WARN,plain\n",
+        )
+        .expect("write");
+
+        let mut out = Vec::new();
+        convert_typed::<crate::filetype::csv::CsvFileType>(tmp.path(), "csv", &mut out)
+            .expect("convert");
+        let out = String::from_utf8(out).expect("utf8");
+        assert_eq!(
+            out,
+            "2025-01-01 10:00:00.000 first\\r\\nsecond\n\
+             2025-01-01 10:00:01.000 plain\n"
+        );
+    }
+
+    #[test]
+    fn test_detect_typed_fails_without_lines() {
+        let tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        let mut out = Vec::new();
+        assert!(detect_typed::<GenericFileType>(tmp.path(), "generic", &mut out).is_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_format_name_of_a_container_log_does_not_split_it() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("compose.log");
+        std::fs::write(
+            &path,
+            "web-1  | Feb  5 17:32:18 web01 sshd[4242]: Accepted publickey\n\
+             db-1   | 2025-11-20 14:23:45.123 ready\n\
+             web-1  | Feb  5 17:32:19 web01 CRON[77]: (root) CMD (run-parts)\n",
+        )
+        .expect("write");
+
+        let format = crate::core::log_store::detect_format_name(&path).expect("detect");
+        assert_eq!(format, "Syslog");
+        assert!(!crate::core::container_split::split_dir(&path).exists());
+    }
+}
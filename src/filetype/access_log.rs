@@ -0,0 +1,583 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use egui::Ui;
+use fancy_regex::Regex;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use crate::core::decompress::LogReader;
+use crate::filetype::{
+    epoch_ns, local_time, InputFileType, LineType, RequestMetrics, TextFileType, NANOS_PER_MS,
+};
+
+// ============================================================================
+// Line parsing
+// ============================================================================
+
+/// NCSA common/combined log format, optionally followed by a request time:
+/// `host ident user [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.0" 200 2326 "referer" "agent" 0.042`
+static NCSA_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(\S+) (\S+) (\S+) \[([^\]]+)\] "((?:[^"\\]|\\.)*)" (\d{3}|-) (\d+|-)(?: "((?:[^"\\]|\\.)*)" "((?:[^"\\]|\\.)*)")?(?:\s+(\d+(?:\.\d+)?))?\s*$"#,
+    )
+    .expect("valid regex literal")
+});
+
+/// Fields of one NCSA line, borrowed from the raw text.
+struct NcsaRecord<'a> {
+    client: &'a str,
+    ident: &'a str,
+    user: &'a str,
+    time: &'a str,
+    request: &'a str,
+    status: &'a str,
+    bytes: &'a str,
+    referer: Option<&'a str>,
+    user_agent: Option<&'a str>,
+    request_time: Option<&'a str>,
+}
+
+impl<'a> NcsaRecord<'a> {
+    fn parse(raw: &'a str) -> Option<Self> {
+        let caps = NCSA_LINE.captures(raw).ok()??;
+        let group = |i: usize| caps.get(i).map(|m| m.as_str());
+        Some(Self {
+            client: group(1)?,
+            ident: group(2)?,
+            user: group(3)?,
+            time: group(4)?,
+            request: group(5)?,
+            status: group(6)?,
+            bytes: group(7)?,
+            referer: group(8),
+            user_agent: group(9),
+            request_time: group(10),
+        })
+    }
+
+    fn timestamp_ns(&self) -> Option<i64> {
+        DateTime::parse_from_str(self.time, "%d/%b/%Y:%H:%M:%S %z")
+            .ok()
+            .map(|dt| epoch_ns(&dt))
+    }
+
+    /// Split the quoted request line into method, path and protocol.
+    fn request_parts(&self) -> (&'a str, &'a str, &'a str) {
+        let mut parts = self.request.splitn(3, ' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(path), protocol) => (method, path, protocol.unwrap_or("")),
+            _ => ("-", self.request, ""),
+        }
+    }
+
+    /// Trailing request time in milliseconds.
+    ///
+    /// A fractional value is read as seconds (nginx `$request_time`), an
+    /// integer as microseconds (Apache `%D`).
+    fn latency_ms(&self) -> Option<f64> {
+        let value = self.request_time?;
+        let number: f64 = value.parse().ok()?;
+        Some(if value.contains('.') {
+            number * 1000.0
+        } else {
+            number / 1000.0
+        })
+    }
+}
+
+/// Look up a W3C extended log column by name in a parsed line.
+fn w3c_value<'a>(columns: &[String], values: &[&'a str], name: &str) -> Option<&'a str> {
+    columns
+        .iter()
+        .position(|c| c.eq_ignore_ascii_case(name))
+        .and_then(|i| values.get(i).copied())
+        .filter(|v| *v != "-")
+}
+
+/// W3C extended log lines carry their date and time in UTC.
+fn w3c_timestamp_ns(columns: &[String], values: &[&str]) -> Option<i64> {
+    let date = w3c_value(columns, values, "date")?;
+    let time = w3c_value(columns, values, "time")?;
+    NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|naive| epoch_ns(&naive.and_utc()))
+}
+
+/// Method, path and status shared by both flavours of access log.
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    status: Option<u16>,
+    latency_ms: Option<f64>,
+}
+
+impl Request {
+    fn from_ncsa(record: &NcsaRecord<'_>) -> Self {
+        let (method, path, _) = record.request_parts();
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            status: record.status.parse().ok(),
+            latency_ms: record.latency_ms(),
+        }
+    }
+
+    /// IIS writes `time-taken` in milliseconds.
+    fn from_w3c(columns: &[String], values: &[&str]) -> Option<Self> {
+        let method = w3c_value(columns, values, "cs-method");
+        let path = w3c_value(columns, values, "cs-uri-stem");
+        let status = w3c_value(columns, values, "sc-status");
+        if method.is_none() && path.is_none() && status.is_none() {
+            return None;
+        }
+        Some(Self {
+            method: method.unwrap_or("-").to_string(),
+            path: path.unwrap_or("-").to_string(),
+            status: status.and_then(|s| s.parse().ok()),
+            latency_ms: w3c_value(columns, values, "time-taken").and_then(|v| v.parse().ok()),
+        })
+    }
+}
+
+// ============================================================================
+// AccessLogLine
+// ============================================================================
+
+/// A single request from a web server access log (NCSA or W3C extended).
+#[derive(Debug, Clone)]
+pub struct AccessLogLine {
+    raw_line: String,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    /// `None` for lines that could not be parsed as a request.
+    request: Option<Request>,
+    /// W3C `#Fields:` columns in effect for this line; `None` for NCSA lines.
+    columns: Option<Arc<[String]>>,
+    pub line_number: usize,
+}
+
+impl AccessLogLine {
+    fn parse(
+        raw_line: String,
+        line_number: usize,
+        columns: Option<&Arc<[String]>>,
+        fallback_ns: i64,
+    ) -> Self {
+        let (timestamp_ns, request) = columns.map_or_else(
+            || {
+                NcsaRecord::parse(&raw_line).map_or((None, None), |record| {
+                    (record.timestamp_ns(), Some(Request::from_ncsa(&record)))
+                })
+            },
+            |columns| {
+                let values: Vec<&str> = raw_line.split_whitespace().collect();
+                (
+                    w3c_timestamp_ns(columns, &values),
+                    Request::from_w3c(columns, &values),
+                )
+            },
+        );
+        Self {
+            timestamp_ns: timestamp_ns.unwrap_or(fallback_ns),
+            request,
+            columns: columns.cloned(),
+            raw_line,
+            line_number,
+        }
+    }
+
+    fn message_text(&self) -> String {
+        self.request.as_ref().map_or_else(
+            || self.raw_line.clone(),
+            |request| {
+                let status = request
+                    .status
+                    .map_or_else(|| "-".to_string(), |s| s.to_string());
+                format!("{} {} {status}", request.method, request.path)
+            },
+        )
+    }
+}
+
+// ============================================================================
+// AccessLogFileState
+// ============================================================================
+
+pub type AccessLogFileState = crate::filetype::SimpleFileState;
+
+// ============================================================================
+// LineType implementation
+// ============================================================================
+
+impl LineType for AccessLogLine {
+    type Config = ();
+    type FileState = AccessLogFileState;
+
+    fn file_state_from_v2(time_offset_ms: i64) -> AccessLogFileState {
+        let s = AccessLogFileState::default();
+        s.set_time_offset_ms(time_offset_ms);
+        s
+    }
+
+    fn timestamp(&self, config: &(), file_state: &AccessLogFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &AccessLogFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
+    }
+
+    fn message(&self) -> String {
+        self.message_text()
+    }
+
+    fn display_message(&self, _config: &(), file_state: &AccessLogFileState) -> String {
+        let offset_ms = file_state.time_offset_ms();
+        if offset_ms != 0 {
+            format!(
+                "[{}] {}",
+                crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
+                self.message_text()
+            )
+        } else {
+            self.message_text()
+        }
+    }
+
+    fn raw(&self) -> String {
+        self.raw_line.clone()
+    }
+
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        let Some(request) = &self.request else {
+            return Vec::new();
+        };
+        let mut fields = vec![
+            ("Method".to_string(), request.method.clone()),
+            ("Path".to_string(), request.path.clone()),
+        ];
+        if let Some(status) = request.status {
+            fields.push(("Status".to_string(), status.to_string()));
+        }
+        if let Some(latency) = request.latency_ms {
+            fields.push(("Latency (ms)".to_string(), format!("{latency:.3}")));
+        }
+
+        if let Some(columns) = &self.columns {
+            const MAPPED: &[&str] = &[
+                "date",
+                "time",
+                "cs-method",
+                "cs-uri-stem",
+                "sc-status",
+                "time-taken",
+            ];
+            let values = self.raw_line.split_whitespace();
+            fields.extend(
+                columns
+                    .iter()
+                    .zip(values)
+                    .filter(|(name, value)| {
+                        *value != "-" && !MAPPED.iter().any(|m| name.eq_ignore_ascii_case(m))
+                    })
+                    .map(|(name, value)| (name.clone(), value.replace('+', " "))),
+            );
+        } else if let Some(record) = NcsaRecord::parse(&self.raw_line) {
+            let (_, _, protocol) = record.request_parts();
+            let optional = [
+                ("Client", Some(record.client)),
+                ("Ident", Some(record.ident)),
+                ("User", Some(record.user)),
+                ("Protocol", Some(protocol)),
+                ("Bytes", Some(record.bytes)),
+                ("Referer", record.referer),
+                ("User-Agent", record.user_agent),
+            ];
+            fields.extend(
+                optional
+                    .into_iter()
+                    .filter_map(|(name, value)| value.map(|v| (name, v)))
+                    .filter(|(_, value)| !value.is_empty() && *value != "-")
+                    .map(|(name, value)| (name.to_string(), value.to_string())),
+            );
+        }
+        fields
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        let Some(request) = &self.request else {
+            return Vec::new();
+        };
+        let mut facets = vec![("Method", request.method.clone())];
+        if let Some(status) = request.status {
+            facets.push(("Status", status.to_string()));
+        }
+        facets
+    }
+
+    fn request_metrics(&self) -> Option<RequestMetrics> {
+        let request = self.request.as_ref()?;
+        Some(RequestMetrics {
+            status: request.status?,
+            latency_ms: request.latency_ms,
+        })
+    }
+
+    fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &AccessLogFileState) {
+        if ui.button("⏱ Calibrate Time Here").clicked() {
+            let raw_time = local_time(self.timestamp_ns);
+            let display_time =
+                raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
+            *file_state
+                .calibration
+                .lock()
+                .expect("calibration lock poisoned") = Some((
+                raw_time,
+                crate::filetype::CalibrationWindow::new(
+                    display_time,
+                    false,
+                    Some(display_time),
+                    raw_time,
+                ),
+            ));
+            ui.close();
+        }
+    }
+}
+
+// ============================================================================
+// AccessLogFileType (InputFileType + TextFileType)
+// ============================================================================
+
+/// Stateful reader for web server access logs.
+///
+/// Handles the NCSA common/combined format written by Apache and nginx, and
+/// the W3C extended format written by IIS, whose `#Fields:` directive names
+/// the columns of the lines that follow it. Other `#` directives are skipped.
+/// Lines that cannot be parsed are kept with the previous line's timestamp.
+pub struct AccessLogFileType {
+    reader: BufReader<LogReader>,
+    line_number: usize,
+    bytes_read: u64,
+    columns: Option<Arc<[String]>>,
+    last_timestamp_ns: i64,
+}
+
+impl InputFileType for AccessLogFileType {
+    type LineType = AccessLogLine;
+
+    const DISPLAY_NAME: &'static str = "Web Server Access Log";
+    const FILE_EXTENSIONS: &'static [&'static str] = &["log", "txt"];
+
+    fn open(
+        path: &Path,
+        _config: (),
+        _file_state: std::sync::Arc<AccessLogFileState>,
+    ) -> anyhow::Result<Self> {
+        let file = LogReader::open(path)?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            line_number: 0,
+            bytes_read: 0,
+            columns: None,
+            last_timestamp_ns: 0,
+        })
+    }
+
+    fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
+        let mut result = Vec::with_capacity(lines_to_read);
+        let mut buf = Vec::new();
+        while result.len() < lines_to_read {
+            buf.clear();
+            match self.reader.read_until(b'\n', &mut buf) {
+                Ok(0) => break, // EOF
+                Ok(n) => {
+                    self.bytes_read += n as u64;
+                    self.line_number += 1;
+                    let line_str = String::from_utf8_lossy(&buf);
+                    let raw = line_str.trim_end_matches(['\n', '\r']);
+                    if raw.trim().is_empty() {
+                        continue;
+                    }
+                    if let Some(directive) = raw.strip_prefix('#') {
+                        if let Some(fields) = directive.strip_prefix("Fields:") {
+                            self.columns =
+                                Some(fields.split_whitespace().map(String::from).collect());
+                        }
+                        continue;
+                    }
+                    let line = AccessLogLine::parse(
+                        raw.to_string(),
+                        self.line_number,
+                        self.columns.as_ref(),
+                        self.last_timestamp_ns,
+                    );
+                    self.last_timestamp_ns = line.timestamp_ns;
+                    result.push(line);
+                }
+                Err(e) => return Err(anyhow::anyhow!("Read error: {e}")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn bytes_consumed(&self) -> u64 {
+        self.reader.get_ref().disk_position(self.bytes_read)
+    }
+}
+
+impl TextFileType for AccessLogFileType {
+    /// Returns `true` for a W3C directive header, or when every complete line
+    /// among the first few is an NCSA access log line.
+    fn looks_like(file: &mut dyn std::io::Read) -> bool {
+        let mut buf = [0u8; 4096];
+        let n = file.read(&mut buf).unwrap_or(0);
+        let sample = String::from_utf8_lossy(&buf[..n]);
+        if ["#Software:", "#Version:", "#Fields:"]
+            .iter()
+            .any(|directive| sample.starts_with(directive))
+        {
+            return true;
+        }
+
+        // Drop a trailing partial line when the sample filled the buffer.
+        let complete = if n == buf.len() {
+            sample.rsplit_once('\n').map_or("", |(head, _)| head)
+        } else {
+            &sample
+        };
+        let lines: Vec<&str> = complete
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .take(5)
+            .collect();
+        !lines.is_empty()
+            && lines.iter().all(|l| {
+                NCSA_LINE
+                    .is_match(l.trim_end_matches('\r'))
+                    .unwrap_or(false)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const COMBINED: &str = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08 [en] (Win98; I ;Nav)""#;
+
+    const W3C: &str = "#Software: Microsoft Internet Information Services 10.0
+#Version: 1.0
+#Date: 2024-03-01 10:00:00
+#Fields: date time s-ip cs-method cs-uri-stem cs-uri-query s-port cs-username c-ip cs(User-Agent) sc-status sc-substatus sc-win32-status time-taken
+2024-03-01 10:00:01 10.0.0.1 GET /default.htm - 80 - 192.168.1.5 Mozilla/5.0+(Windows) 200 0 0 15
+2024-03-01 10:00:02 10.0.0.1 POST /api/orders id=7 443 - 192.168.1.6 curl/8.0 503 0 0 2048
+";
+
+    fn read_all(contents: &str) -> Vec<AccessLogLine> {
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        tmp.write_all(contents.as_bytes()).expect("write");
+        let file_state = Arc::new(AccessLogFileState::default());
+        let mut ft = AccessLogFileType::open(tmp.path(), (), file_state).expect("open");
+        ft.read(100).expect("read")
+    }
+
+    #[test]
+    fn test_looks_like() {
+        assert!(AccessLogFileType::looks_like(&mut std::io::Cursor::new(
+            COMBINED
+        )));
+        assert!(AccessLogFileType::looks_like(&mut std::io::Cursor::new(
+            W3C
+        )));
+        assert!(!AccessLogFileType::looks_like(&mut std::io::Cursor::new(
+            "2025-01-01 12:00:00 INFO some log line"
+        )));
+    }
+
+    #[test]
+    fn test_parse_combined() {
+        let log = format!("{COMBINED}\n");
+        let lines = read_all(&log);
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line.message(), "GET /apache_pb.gif 200");
+        assert_eq!(
+            local_time(line.timestamp_ns),
+            DateTime::parse_from_rfc3339("2000-10-10T13:55:36-07:00").expect("valid")
+        );
+        assert_eq!(
+            line.request_metrics(),
+            Some(RequestMetrics {
+                status: 200,
+                latency_ms: None
+            })
+        );
+        let fields = line.fields();
+        assert!(fields.contains(&("User".to_string(), "frank".to_string())));
+        assert!(fields.contains(&("Protocol".to_string(), "HTTP/1.0".to_string())));
+        assert!(fields.contains(&(
+            "Referer".to_string(),
+            "http://www.example.com/start.html".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_ncsa_request_time() {
+        let nginx = r#"10.0.0.2 - - [01/Mar/2024:10:00:00 +0000] "GET /slow HTTP/1.1" 504 0 "-" "curl/8.0" 1.250"#;
+        let apache =
+            r#"10.0.0.2 - - [01/Mar/2024:10:00:00 +0000] "GET /fast HTTP/1.1" 200 12 42000"#;
+        let lines = read_all(&format!("{nginx}\n{apache}\n"));
+        assert_eq!(
+            lines[0].request_metrics().and_then(|m| m.latency_ms),
+            Some(1250.0)
+        );
+        assert_eq!(
+            lines[1].request_metrics().and_then(|m| m.latency_ms),
+            Some(42.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_w3c() {
+        let lines = read_all(W3C);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].message(), "POST /api/orders 503");
+        assert_eq!(lines[1].line_number, 6);
+        assert_eq!(
+            local_time(lines[1].timestamp_ns),
+            DateTime::parse_from_rfc3339("2024-03-01T10:00:02Z").expect("valid")
+        );
+        assert_eq!(
+            lines[1].request_metrics(),
+            Some(RequestMetrics {
+                status: 503,
+                latency_ms: Some(2048.0)
+            })
+        );
+        let fields = lines[0].fields();
+        assert!(fields.contains(&(
+            "cs(User-Agent)".to_string(),
+            "Mozilla/5.0 (Windows)".to_string()
+        )));
+        assert!(!fields.iter().any(|(name, _)| name == "cs-uri-query"));
+    }
+
+    #[test]
+    fn test_unparsed_line_keeps_previous_timestamp() {
+        let lines = read_all(&format!("{COMBINED}\ngarbage line\n"));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].message(), "garbage line");
+        assert_eq!(lines[1].timestamp_ns, lines[0].timestamp_ns);
+        assert!(lines[1].request_metrics().is_none());
+    }
+}
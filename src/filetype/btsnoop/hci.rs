@@ -1,7 +1,7 @@
 // LogCrab - GPL-3.0-or-later
 // Copyright (C) 2026 Daniel Freiermuth
 
-use chrono::{DateTime, Local};
+use crate::filetype::local_time;
 
 // ============================================================================
 // HciPacketInfo
@@ -10,8 +10,8 @@ use chrono::{DateTime, Local};
 /// Represents a parsed HCI packet for display
 #[derive(Debug, Clone)]
 pub struct HciPacketInfo {
-    /// Packet timestamp
-    pub timestamp: DateTime<Local>,
+    /// Packet timestamp in epoch nanoseconds
+    pub timestamp_ns: i64,
     /// HCI packet type (Command, Event, ACL Data, SCO Data, etc.)
     pub packet_type: String,
     /// Direction (Sent/Received or Host→Controller/Controller→Host)
@@ -33,7 +33,7 @@ impl HciPacketInfo {
     pub fn format_raw(&self) -> String {
         format!(
             "[{}] {} {} {} Length={}",
-            self.timestamp.format("%H:%M:%S%.6f"),
+            local_time(self.timestamp_ns).format("%H:%M:%S%.6f"),
             self.packet_type,
             self.direction,
             self.info,
@@ -48,7 +48,7 @@ impl HciPacketInfo {
 
 pub(super) fn parse_hci_packet(
     packet: &btsnoop::Packet,
-    timestamp: DateTime<Local>,
+    timestamp_ns: i64,
 ) -> Option<HciPacketInfo> {
     profiling::scope!("parse_hci_packet");
 
@@ -65,7 +65,7 @@ pub(super) fn parse_hci_packet(
     let (packet_type, info) = parse_hci_type_and_info(data);
 
     Some(HciPacketInfo {
-        timestamp,
+        timestamp_ns,
         packet_type,
         direction: direction.to_string(),
         length: packet.header.original_length,
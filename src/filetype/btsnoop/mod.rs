@@ -6,13 +6,14 @@ mod hci;
 mod hfp;
 mod rfcomm;
 
-use chrono::{DateTime, Local, TimeDelta};
+use chrono::{DateTime, Local};
 use egui::Ui;
-use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+use std::sync::Arc;
 
-use crate::filetype::{BinaryFileType, InputFileType, LineType};
+use crate::core::decompress::LogReader;
+use crate::filetype::{local_time, BinaryFileType, InputFileType, LineType, NANOS_PER_MS};
 
 pub use hci::HciPacketInfo;
 
@@ -25,14 +26,18 @@ pub use hci::HciPacketInfo;
 pub struct BtsnoopLogLine {
     /// Parsed HCI packet information
     pub hci_info: HciPacketInfo,
+    /// Captured bytes of the HCI packet, without the `BTSnoop` record header
+    pub data: Arc<[u8]>,
     /// Original packet number in source file
     pub line_number: usize,
 }
 
 impl BtsnoopLogLine {
-    pub const fn new(hci_info: HciPacketInfo, line_number: usize) -> Self {
+    #[must_use]
+    pub const fn new(hci_info: HciPacketInfo, data: Arc<[u8]>, line_number: usize) -> Self {
         Self {
             hci_info,
+            data,
             line_number,
         }
     }
@@ -60,8 +65,12 @@ impl LineType for BtsnoopLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &(), file_state: &BtsnoopFileState) -> DateTime<Local> {
-        self.hci_info.timestamp + chrono::Duration::milliseconds(file_state.time_offset_ms())
+    fn timestamp(&self, config: &(), file_state: &BtsnoopFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &BtsnoopFileState) -> i64 {
+        self.hci_info.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
     }
 
     fn message(&self) -> String {
@@ -89,9 +98,13 @@ impl LineType for BtsnoopLogLine {
         self.line_number
     }
 
+    fn payload(&self) -> Option<Arc<[u8]>> {
+        Some(Arc::clone(&self.data))
+    }
+
     fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &BtsnoopFileState) {
         if ui.button("⏱ Calibrate Time Here").clicked() {
-            let raw_time = self.hci_info.timestamp;
+            let raw_time = local_time(self.hci_info.timestamp_ns);
             let display_time =
                 raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
             *file_state
@@ -128,6 +141,7 @@ pub struct BtsnoopFileType {
 impl InputFileType for BtsnoopFileType {
     type LineType = BtsnoopLogLine;
 
+    const DISPLAY_NAME: &'static str = "Bluetooth HCI Snoop";
     const FILE_EXTENSIONS: &'static [&'static str] = &["log", "btsnoop"];
 
     fn open(
@@ -179,10 +193,9 @@ fn parse_btsnoop_to_lines<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<Btsnoop
     let path = path.as_ref();
     tracing::info!("Starting btsnoop parsing: {}", path.display());
 
-    let mut file = File::open(path)
-        .with_context(|| format!("Failed to open btsnoop file: {}", path.display()))?;
     let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
+    LogReader::open(path)?
+        .read_to_end(&mut buffer)
         .with_context(|| format!("Failed to read btsnoop file: {}", path.display()))?;
 
     let btsnoop_file = btsnoop::parse_btsnoop_file(&buffer)
@@ -193,19 +206,18 @@ fn parse_btsnoop_to_lines<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<Btsnoop
 
     for packet in &btsnoop_file.packets {
         let duration_since_unix = packet.header.timestamp();
-        let Some(timestamp) = TimeDelta::from_std(duration_since_unix)
-            .ok()
-            .and_then(|delta| {
-                DateTime::from_timestamp(0, 0).map(|epoch| (epoch + delta).with_timezone(&Local))
-            })
-        else {
+        let Ok(timestamp_ns) = i64::try_from(duration_since_unix.as_nanos()) else {
             tracing::warn!("Failed to convert packet timestamp at line {line_number}, skipping");
             line_number += 1;
             continue;
         };
 
-        if let Some(hci_info) = hci::parse_hci_packet(packet, timestamp) {
-            lines.push(BtsnoopLogLine::new(hci_info, line_number));
+        if let Some(hci_info) = hci::parse_hci_packet(packet, timestamp_ns) {
+            lines.push(BtsnoopLogLine::new(
+                hci_info,
+                Arc::from(&packet.packet_data[..]),
+                line_number,
+            ));
         }
         line_number += 1;
     }
@@ -4,15 +4,17 @@
 use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
 use egui::Ui;
 use fancy_regex::Regex;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::{LazyLock, Mutex};
 
 use super::dmesg::{parse_dmesg_line, DmesgLogLine};
 use super::logcat::{parse_logcat_line, LogcatLogLine};
-use crate::filetype::{CalibrationState, InputFileType, LineType, LogFileState, TextFileType};
+use crate::core::decompress::{read_head, LogReader};
+use crate::filetype::{
+    local_time, CalibrationState, InputFileType, LineType, LogFileState, TextFileType, NANOS_PER_MS,
+};
 
 // ============================================================================
 // Bugreport parsing utilities
@@ -303,6 +305,12 @@ impl LogFileState for BugreportFileState {
 
         logcat_changed || dmesg_changed
     }
+
+    /// Only the logcat side counts: the dmesg offset is the device boot time,
+    /// not a correction.
+    fn clock_offset_ms(&self) -> i64 {
+        self.logcat_offset_ms()
+    }
 }
 
 // ============================================================================
@@ -328,15 +336,17 @@ impl LineType for BugreportLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &(), file_state: &BugreportFileState) -> DateTime<Local> {
+    fn timestamp(&self, config: &(), file_state: &BugreportFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &BugreportFileState) -> i64 {
         match self {
             BugreportLogLine::Logcat(l) => {
-                l.timestamp
-                    + chrono::Duration::milliseconds(file_state.logcat_offset_ms())
+                l.timestamp_ns + file_state.logcat_offset_ms() * NANOS_PER_MS
             }
             BugreportLogLine::Dmesg(l) => {
-                l.timestamp
-                    + chrono::Duration::milliseconds(file_state.dmesg_offset_ms())
+                l.timestamp_ns + file_state.dmesg_offset_ms() * NANOS_PER_MS
             }
         }
     }
@@ -385,6 +395,26 @@ impl LineType for BugreportLogLine {
         }
     }
 
+    fn fields(&self) -> Vec<(String, String)> {
+        match self {
+            BugreportLogLine::Logcat(l) => l.fields(),
+            BugreportLogLine::Dmesg(l) => l.fields(),
+        }
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        let kind = match self {
+            BugreportLogLine::Logcat(_) => "logcat",
+            BugreportLogLine::Dmesg(_) => "dmesg",
+        };
+        let mut facets = vec![("Buffer", kind.to_string())];
+        match self {
+            BugreportLogLine::Logcat(l) => facets.extend(l.facets()),
+            BugreportLogLine::Dmesg(l) => facets.extend(l.facets()),
+        }
+        facets
+    }
+
     fn egui_render_context_menu(
         &self,
         ui: &mut Ui,
@@ -394,7 +424,7 @@ impl LineType for BugreportLogLine {
         match self {
             BugreportLogLine::Logcat(line) => {
                 if ui.button("⏱ Calibrate Logcat Time Here").clicked() {
-                    let raw_time = line.timestamp;
+                    let raw_time = local_time(line.timestamp_ns);
                     let display_time = raw_time
                         + chrono::Duration::milliseconds(file_state.logcat_offset_ms());
                     *file_state
@@ -414,7 +444,7 @@ impl LineType for BugreportLogLine {
             }
             BugreportLogLine::Dmesg(line) => {
                 if ui.button("⏱ Calibrate Dmesg Time Here").clicked() {
-                    let raw_time = line.timestamp;
+                    let raw_time = local_time(line.timestamp_ns);
                     let display_time = raw_time
                         + chrono::Duration::milliseconds(file_state.dmesg_offset_ms());
                     *file_state
@@ -452,7 +482,7 @@ impl LineType for BugreportLogLine {
 /// Must be registered **before** [`super::logcat::LogcatFileType`] so that
 /// `looks_like` is checked first (bugreport ⊂ logcat pattern-space).
 pub struct BugreportFileType {
-    reader: BufReader<File>,
+    reader: BufReader<LogReader>,
     year: i32,
     line_number: usize,
     bytes_read: u64,
@@ -478,15 +508,10 @@ impl BugreportFileType {
         path: &Path,
         file_state: &BugreportFileState,
     ) -> anyhow::Result<Self> {
-        use anyhow::Context as _;
-        let mut file =
-            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
-
         // Read enough to capture the dumpstate header AND the Uptime line which
         // may come after several long bootconfig lines (empirically ~8 KB is safe).
-        let mut preview_buf = [0u8; 8192];
-        let preview_n = file.read(&mut preview_buf).unwrap_or(0);
-        let preview = String::from_utf8_lossy(&preview_buf[..preview_n]);
+        let preview_buf = read_head(path, 8192).unwrap_or_default();
+        let preview = String::from_utf8_lossy(&preview_buf);
 
         let (year, boot_time_ms) = detect_header_info(&preview).unwrap_or_else(|| {
             tracing::warn!(
@@ -502,11 +527,9 @@ impl BugreportFileType {
             file_state.init_dmesg_offset_if_zero(boot_time_ms);
         }
 
-        file.seek(SeekFrom::Start(0))
-            .with_context(|| format!("Failed to seek {}", path.display()))?;
-
+        // A compressed file cannot seek back, so it is read again from the start
         Ok(Self {
-            reader: BufReader::new(file),
+            reader: BufReader::new(LogReader::open(path)?),
             year,
             line_number: 0,
             bytes_read: 0,
@@ -520,6 +543,7 @@ impl BugreportFileType {
 impl InputFileType for BugreportFileType {
     type LineType = BugreportLogLine;
 
+    const DISPLAY_NAME: &'static str = "Android Bugreport";
     const FILE_EXTENSIONS: &'static [&'static str] = &["txt", "zip"];
 
     /// Version 2: shares logcat's `message()` semantics (`"TAG: text"`).
@@ -612,7 +636,7 @@ impl InputFileType for BugreportFileType {
     }
 
     fn bytes_consumed(&self) -> u64 {
-        self.bytes_read
+        self.reader.get_ref().disk_position(self.bytes_read)
     }
 }
 
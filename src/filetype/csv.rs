@@ -0,0 +1,715 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+use chrono::{DateTime, Local};
+use egui::Ui;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::core::decompress::{read_head, LogReader};
+use crate::filetype::{
+    local_time, InputFileType, LineType, LogFileState, SimpleFileState, TextFileType, NANOS_PER_MS,
+};
+
+/// Delimiters tried when sniffing a file, in order of preference
+const DELIMITERS: [u8; 3] = [b',', b'\t', b';'];
+
+/// Lowercased header names recognised as the timestamp, level and message column
+const TIMESTAMP_HEADERS: &[&str] = &[
+    "timestamp",
+    "time",
+    "datetime",
+    "date",
+    "ts",
+    "@timestamp",
+    "time_stamp",
+];
+const LEVEL_HEADERS: &[&str] = &[
+    "level",
+    "severity",
+    "loglevel",
+    "log_level",
+    "priority",
+    "lvl",
+];
+const MESSAGE_HEADERS: &[&str] = &[
+    "message",
+    "msg",
+    "text",
+    "description",
+    "event",
+    "log",
+    "body",
+];
+
+// ============================================================================
+// CsvMapping
+// ============================================================================
+
+/// Which columns of a CSV file hold the timestamp, level and message.
+///
+/// Every column not mapped to the timestamp or message is exposed as a field
+/// of the line. Persisted in the `.crab` file together with the header it was
+/// made for, so a file whose header changed gets a fresh guess.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CsvMapping {
+    pub header: Vec<String>,
+    pub timestamp: Option<usize>,
+    pub level: Option<usize>,
+    /// When unset, the message is built from all unmapped columns
+    pub message: Option<usize>,
+}
+
+impl CsvMapping {
+    /// Guess the mapping from the header names, falling back to the first
+    /// column of `first_record` that holds a timestamp.
+    #[must_use]
+    pub fn guess(header: Vec<String>, first_record: Option<&csv::StringRecord>) -> Self {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.contains(&h.trim().to_lowercase().as_str()))
+        };
+        let timestamp = find(TIMESTAMP_HEADERS).or_else(|| {
+            first_record.and_then(|record| {
+                record
+                    .iter()
+                    .position(|cell| parse_timestamp_ns(cell).is_some())
+            })
+        });
+        let level = find(LEVEL_HEADERS);
+        let message = find(MESSAGE_HEADERS);
+        Self {
+            header,
+            timestamp,
+            level,
+            message,
+        }
+    }
+
+    /// Header name of column `index`
+    fn column_name(&self, index: usize) -> String {
+        self.header
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("column {}", index + 1))
+    }
+
+    /// Build the parts of a line from the cells of one record
+    fn split<'r>(
+        &self,
+        record: &'r csv::StringRecord,
+    ) -> (Option<&'r str>, Option<String>, String) {
+        let timestamp = self.timestamp.and_then(|i| record.get(i));
+        let level = self
+            .level
+            .and_then(|i| record.get(i))
+            .filter(|level| !level.is_empty())
+            .map(str::to_string);
+        let message = self.message.and_then(|i| record.get(i)).map_or_else(
+            || {
+                record
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| Some(*i) != self.timestamp && Some(*i) != self.level)
+                    .map(|(i, cell)| format!("{}={cell}", self.column_name(i)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            },
+            str::to_string,
+        );
+        (timestamp, level, message)
+    }
+}
+
+/// Parse a timestamp cell: any format the generic parser knows, or Unix
+/// epoch seconds, milliseconds or microseconds, as epoch nanoseconds.
+pub(crate) fn parse_timestamp_ns(cell: &str) -> Option<i64> {
+    let cell = cell.trim();
+    if let Some((timestamp_ns, _)) = crate::filetype::generic::parse_generic_parts(cell) {
+        return Some(timestamp_ns);
+    }
+    if cell.contains('.') {
+        let secs = cell.parse::<f64>().ok().filter(|secs| *secs >= 1e9)?;
+        #[allow(clippy::cast_possible_truncation)]
+        return ((secs * 1e6) as i64).checked_mul(1_000);
+    }
+    let value = cell.parse::<i64>().ok()?;
+    match value {
+        1_000_000_000_000_000.. => value.checked_mul(1_000),
+        1_000_000_000_000.. => value.checked_mul(NANOS_PER_MS),
+        1_000_000_000.. => value.checked_mul(1_000_000_000),
+        _ => None,
+    }
+}
+
+/// Shape shared by all lines of one file
+#[derive(Debug)]
+pub struct CsvLayout {
+    delimiter: u8,
+    mapping: CsvMapping,
+}
+
+// ============================================================================
+// CsvLogLine
+// ============================================================================
+
+/// One record of a CSV/TSV event log
+#[derive(Debug, Clone)]
+pub struct CsvLogLine {
+    raw_line: String,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    message_text: String,
+    level: Option<String>,
+    /// Cells of the record as read, for [`LineType::fields`]
+    cells: csv::StringRecord,
+    pub line_number: usize,
+    layout: Arc<CsvLayout>,
+}
+
+// ============================================================================
+// CsvFileState
+// ============================================================================
+
+/// Per-source state: time offset, the column mapping and the mapping dialog.
+#[derive(Default)]
+pub struct CsvFileState {
+    inner: SimpleFileState,
+    mapping: Mutex<Option<CsvMapping>>,
+    /// Mapping being edited in the open dialog (transient)
+    mapping_window: Mutex<Option<CsvMapping>>,
+    /// Set when a new mapping was applied and the file must be parsed again
+    reparse_requested: AtomicBool,
+}
+
+impl CsvFileState {
+    /// Read the current time offset in milliseconds.
+    #[inline]
+    pub fn time_offset_ms(&self) -> i64 {
+        self.inner.time_offset_ms()
+    }
+
+    /// Set the time offset in milliseconds.
+    #[inline]
+    pub fn set_time_offset_ms(&self, v: i64) {
+        self.inner.set_time_offset_ms(v);
+    }
+
+    /// The mapping in use, if the file has been opened
+    pub fn mapping(&self) -> Option<CsvMapping> {
+        self.mapping.lock().expect("mapping lock poisoned").clone()
+    }
+
+    /// Open the column mapping dialog
+    pub fn open_mapping_window(&self) {
+        *self
+            .mapping_window
+            .lock()
+            .expect("mapping_window lock poisoned") = self.mapping();
+    }
+}
+
+impl std::fmt::Debug for CsvFileState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CsvFileState")
+            .field("time_offset_ms", &self.time_offset_ms())
+            .field("mapping", &self.mapping())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for CsvFileState {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            mapping: Mutex::new(self.mapping()),
+            mapping_window: Mutex::new(None), // the dialog is transient
+            reparse_requested: AtomicBool::new(false),
+        }
+    }
+}
+
+impl serde::Serialize for CsvFileState {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = s.serialize_struct("CsvFileState", 2)?;
+        state.serialize_field("time_offset_ms", &self.time_offset_ms())?;
+        state.serialize_field("mapping", &self.mapping())?;
+        state.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CsvFileState {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Helper {
+            #[serde(default)]
+            time_offset_ms: i64,
+            #[serde(default)]
+            mapping: Option<CsvMapping>,
+        }
+        let h = Helper::deserialize(d)?;
+        let state = Self {
+            mapping: Mutex::new(h.mapping),
+            ..Self::default()
+        };
+        state.set_time_offset_ms(h.time_offset_ms);
+        Ok(state)
+    }
+}
+
+impl LogFileState for CsvFileState {
+    fn egui_render_file_state(&self, ui: &egui::Ui) -> bool {
+        let mut window = self
+            .mapping_window
+            .lock()
+            .expect("mapping_window lock poisoned");
+        if let Some(draft) = window.as_mut() {
+            match render_mapping_window(ui, draft) {
+                Ok(Some(())) => {
+                    let mapping = window.take();
+                    if mapping != self.mapping() {
+                        *self.mapping.lock().expect("mapping lock poisoned") = mapping;
+                        self.reparse_requested.store(true, Ordering::SeqCst);
+                    }
+                }
+                Ok(None) => {}
+                Err(()) => *window = None,
+            }
+        }
+        drop(window);
+        self.inner.egui_render_file_state(ui)
+    }
+
+    fn take_reparse_request(&self) -> bool {
+        self.reparse_requested.swap(false, Ordering::SeqCst)
+    }
+
+    fn clock_offset_ms(&self) -> i64 {
+        self.time_offset_ms()
+    }
+}
+
+/// Render the column mapping dialog.
+///
+/// Returns `Ok(Some(()))` on apply, `Ok(None)` while open and `Err(())` on cancel.
+fn render_mapping_window(ui: &egui::Ui, draft: &mut CsvMapping) -> Result<Option<()>, ()> {
+    let mut result = Ok(None);
+    egui::Window::new("🗂 Map CSV Columns")
+        .collapsible(false)
+        .resizable(false)
+        .show(ui.ctx(), |ui| {
+            let header = draft.header.clone();
+            egui::Grid::new("csv_mapping_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    for (label, column, none_label) in [
+                        ("Timestamp:", &mut draft.timestamp, "(none)"),
+                        ("Level:", &mut draft.level, "(none)"),
+                        ("Message:", &mut draft.message, "(all other columns)"),
+                    ] {
+                        ui.label(label);
+                        let selected = column
+                            .and_then(|i| header.get(i))
+                            .map_or(none_label, String::as_str);
+                        egui::ComboBox::from_id_salt(label)
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(column, None, none_label);
+                                for (i, name) in header.iter().enumerate() {
+                                    ui.selectable_value(column, Some(i), name);
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+            ui.label(
+                egui::RichText::new(
+                    "Unmapped columns are shown as fields of each line. \
+                     Applying re-parses the file.",
+                )
+                .weak(),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    result = Ok(Some(()));
+                }
+                if ui.button("Cancel").clicked() {
+                    result = Err(());
+                }
+            });
+        });
+    result
+}
+
+// ============================================================================
+// LineType implementation
+// ============================================================================
+
+impl LineType for CsvLogLine {
+    type Config = ();
+    type FileState = CsvFileState;
+
+    fn file_state_from_v2(time_offset_ms: i64) -> CsvFileState {
+        let s = CsvFileState::default();
+        s.set_time_offset_ms(time_offset_ms);
+        s
+    }
+
+    fn timestamp(&self, config: &(), file_state: &CsvFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &CsvFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
+    }
+
+    fn message(&self) -> String {
+        self.message_text.clone()
+    }
+
+    fn display_message(&self, _config: &(), file_state: &CsvFileState) -> String {
+        let offset_ms = file_state.time_offset_ms();
+        if offset_ms != 0 {
+            format!(
+                "[{}] {}",
+                crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
+                self.message_text
+            )
+        } else {
+            self.message_text.clone()
+        }
+    }
+
+    fn raw(&self) -> String {
+        self.raw_line.clone()
+    }
+
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        let mapping = &self.layout.mapping;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != mapping.timestamp && Some(*i) != mapping.message)
+            .map(|(i, cell)| (mapping.column_name(i), cell.to_string()))
+            .collect()
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        self.level
+            .as_ref()
+            .map(|level| vec![("Level", level.clone())])
+            .unwrap_or_default()
+    }
+
+    fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &CsvFileState) {
+        if ui.button("⏱ Calibrate Time Here").clicked() {
+            let raw_time = local_time(self.timestamp_ns);
+            let display_time =
+                raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
+            *file_state
+                .inner
+                .calibration
+                .lock()
+                .expect("calibration lock poisoned") = Some((
+                raw_time,
+                crate::filetype::CalibrationWindow::new(
+                    display_time,
+                    false,
+                    Some(display_time),
+                    raw_time,
+                ),
+            ));
+            ui.close();
+        }
+        if ui.button("🗂 Map CSV Columns…").clicked() {
+            file_state.open_mapping_window();
+            ui.close();
+        }
+    }
+}
+
+// ============================================================================
+// CsvFileType (InputFileType + TextFileType)
+// ============================================================================
+
+/// Stateful reader for CSV and TSV event logs with a header row.
+///
+/// The delimiter is sniffed from the start of the file. Records whose
+/// timestamp cannot be parsed inherit the timestamp of the previous record.
+pub struct CsvFileType {
+    reader: csv::Reader<BufReader<LogReader>>,
+    layout: Arc<CsvLayout>,
+    last_timestamp_ns: i64,
+}
+
+impl InputFileType for CsvFileType {
+    type LineType = CsvLogLine;
+
+    const DISPLAY_NAME: &'static str = "CSV Log";
+    const FILE_EXTENSIONS: &'static [&'static str] = &["csv", "tsv"];
+
+    /// Open the file, reusing the mapping saved in `file_state` if the header
+    /// still matches and guessing a new one otherwise.
+    fn open(path: &Path, _config: (), file_state: Arc<CsvFileState>) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+        let sample = read_head(path, 4096)?;
+        let delimiter = sniff_delimiter(&sample).unwrap_or(b',');
+
+        let file = LogReader::open(path)?;
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_reader(BufReader::new(file));
+        let header: Vec<String> = reader
+            .headers()
+            .context("Failed to read CSV header")?
+            .iter()
+            .map(str::to_string)
+            .collect();
+
+        let saved = file_state.mapping().filter(|m| m.header == header);
+        let mapping = saved.unwrap_or_else(|| {
+            let first_record = csv::ReaderBuilder::new()
+                .flexible(true)
+                .delimiter(delimiter)
+                .from_reader(sample.as_slice())
+                .records()
+                .next()
+                .and_then(Result::ok);
+            let mapping = CsvMapping::guess(header, first_record.as_ref());
+            if mapping.timestamp.is_none() {
+                tracing::info!(
+                    "No timestamp column found in {}, asking for a column mapping",
+                    path.display()
+                );
+                *file_state
+                    .mapping_window
+                    .lock()
+                    .expect("mapping_window lock poisoned") = Some(mapping.clone());
+            }
+            mapping
+        });
+        *file_state.mapping.lock().expect("mapping lock poisoned") = Some(mapping.clone());
+
+        Ok(Self {
+            reader,
+            layout: Arc::new(CsvLayout { delimiter, mapping }),
+            last_timestamp_ns: 0,
+        })
+    }
+
+    fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
+        let mut result = Vec::with_capacity(lines_to_read);
+        let mut record = csv::StringRecord::new();
+        while result.len() < lines_to_read {
+            match self.reader.read_record(&mut record) {
+                Ok(false) => break, // EOF
+                Ok(true) => {
+                    let line_number = record
+                        .position()
+                        .map_or(result.len() + 2, |p| p.line() as usize);
+                    let (timestamp, level, message_text) = self.layout.mapping.split(&record);
+                    if let Some(timestamp_ns) = timestamp.and_then(parse_timestamp_ns) {
+                        self.last_timestamp_ns = timestamp_ns;
+                    }
+                    result.push(CsvLogLine {
+                        raw_line: raw_record(&record, self.layout.delimiter),
+                        timestamp_ns: self.last_timestamp_ns,
+                        message_text,
+                        level,
+                        cells: record.clone(),
+                        line_number,
+                        layout: Arc::clone(&self.layout),
+                    });
+                }
+                Err(e) => return Err(anyhow::anyhow!("CSV read error: {e}")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn bytes_consumed(&self) -> u64 {
+        self.reader
+            .get_ref()
+            .get_ref()
+            .disk_position(self.reader.position().byte())
+    }
+}
+
+/// Write `record` back as one CSV line, quoting cells where needed
+fn raw_record(record: &csv::StringRecord, delimiter: u8) -> String {
+    let delimiter = char::from(delimiter);
+    let mut raw = String::with_capacity(record.as_slice().len() + record.len());
+    for (i, cell) in record.iter().enumerate() {
+        if i > 0 {
+            raw.push(delimiter);
+        }
+        if cell.contains([delimiter, '"', '\n', '\r']) {
+            raw.push('"');
+            raw.push_str(&cell.replace('"', "\"\""));
+            raw.push('"');
+        } else {
+            raw.push_str(cell);
+        }
+    }
+    raw
+}
+
+/// Find the delimiter under which `sample` reads as a table: a header of at
+/// least three non-numeric names and data rows with the same column count.
+fn sniff_delimiter(sample: &[u8]) -> Option<u8> {
+    // Only complete lines, the sample may end mid-record
+    let end = sample.iter().rposition(|&b| b == b'\n')?;
+    let sample = &sample[..end];
+    DELIMITERS.into_iter().find(|&delimiter| {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_reader(sample);
+        let mut records = reader.records();
+        let Some(Ok(header)) = records.next() else {
+            return false;
+        };
+        let header_ok = header.len() >= 3
+            && header.iter().all(|name| {
+                let name = name.trim();
+                !name.is_empty() && name.len() <= 64 && name.parse::<f64>().is_err()
+            });
+        let mut rows = 0;
+        let rows_ok = records.all(|record| {
+            rows += 1;
+            record.is_ok_and(|r| r.len() == header.len())
+        });
+        header_ok && rows_ok && rows > 0
+    })
+}
+
+impl TextFileType for CsvFileType {
+    /// Returns `true` if the start of the file reads as a delimited table with a header row.
+    fn looks_like(file: &mut dyn std::io::Read) -> bool {
+        let mut buf = [0u8; 4096];
+        let n = file.read(&mut buf).unwrap_or(0);
+        sniff_delimiter(&buf[..n]).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_CSV: &str = "\
+time,severity,component,message
+2025-03-01 10:00:00.000,INFO,bench,\"power on, rail ok\"
+2025-03-01 10:00:01.500,ERROR,dut,voltage drop
+";
+
+    fn read_all(contents: &str, file_state: Arc<CsvFileState>) -> Vec<CsvLogLine> {
+        use std::io::Write;
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        tmp.write_all(contents.as_bytes()).expect("write");
+        let mut ft = CsvFileType::open(tmp.path(), (), file_state).expect("open");
+        ft.read(100).expect("read")
+    }
+
+    #[test]
+    fn test_looks_like_csv() {
+        let mut cursor = std::io::Cursor::new(SAMPLE_CSV);
+        assert!(CsvFileType::looks_like(&mut cursor));
+        let mut tsv = std::io::Cursor::new("ts\tlevel\tmsg\n1700000000\tW\thello\n");
+        assert!(CsvFileType::looks_like(&mut tsv));
+    }
+
+    #[test]
+    fn test_looks_like_rejects_plain_text() {
+        let mut cursor = std::io::Cursor::new(
+            "2025-01-01 10:00:00 INFO a, b, c\n2025-01-01 10:00:01 INFO started\n",
+        );
+        assert!(!CsvFileType::looks_like(&mut cursor));
+    }
+
+    #[test]
+    fn test_parse_with_guessed_mapping() {
+        let file_state = Arc::new(CsvFileState::default());
+        let lines = read_all(SAMPLE_CSV, Arc::clone(&file_state));
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].message_text, "power on, rail ok");
+        assert_eq!(
+            lines[0].raw(),
+            "2025-03-01 10:00:00.000,INFO,bench,\"power on, rail ok\""
+        );
+        assert_eq!(lines[0].line_number, 2);
+        assert_eq!(lines[1].level.as_deref(), Some("ERROR"));
+        assert_eq!(
+            lines[1].timestamp_ns - lines[0].timestamp_ns,
+            1500 * NANOS_PER_MS
+        );
+        assert_eq!(
+            lines[0].fields(),
+            [
+                ("severity".to_string(), "INFO".to_string()),
+                ("component".to_string(), "bench".to_string()),
+            ]
+        );
+        let mapping = file_state.mapping().expect("mapping stored");
+        assert_eq!(
+            (mapping.timestamp, mapping.level, mapping.message),
+            (Some(0), Some(1), Some(3))
+        );
+    }
+
+    #[test]
+    fn test_saved_mapping_is_used() {
+        let file_state = CsvFileState::default();
+        *file_state.mapping.lock().unwrap() = Some(CsvMapping {
+            header: ["time", "severity", "component", "message"]
+                .map(String::from)
+                .to_vec(),
+            timestamp: Some(0),
+            level: None,
+            message: None,
+        });
+        let lines = read_all(SAMPLE_CSV, Arc::new(file_state));
+        assert_eq!(
+            lines[1].message_text,
+            "severity=ERROR component=dut message=voltage drop"
+        );
+    }
+
+    #[test]
+    fn test_parse_epoch_timestamps() {
+        let secs = parse_timestamp_ns("1700000000").unwrap();
+        assert_eq!(parse_timestamp_ns("1700000000000"), Some(secs));
+        assert_eq!(parse_timestamp_ns("1700000000000000"), Some(secs));
+        assert_eq!(
+            parse_timestamp_ns("1700000000.250").unwrap() - secs,
+            250 * NANOS_PER_MS
+        );
+        assert_eq!(parse_timestamp_ns("42"), None);
+    }
+
+    #[test]
+    fn test_file_state_roundtrip() {
+        let file_state = CsvFileState::default();
+        file_state.set_time_offset_ms(250);
+        *file_state.mapping.lock().unwrap() = Some(CsvMapping {
+            header: vec!["t".to_string()],
+            timestamp: Some(0),
+            ..CsvMapping::default()
+        });
+        let json = serde_json::to_string(&file_state).unwrap();
+        let parsed: CsvFileState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.time_offset_ms(), 250);
+        assert_eq!(parsed.mapping(), file_state.mapping());
+    }
+}
@@ -5,15 +5,17 @@ use chrono::{DateTime, Local};
 use dashmap::DashMap;
 use dlt_core::read::{read_message, DltMessageReader};
 use egui::Ui;
-use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::BufReader;
 use std::path::Path;
 use std::sync::{
     atomic::{AtomicI64, AtomicU64, Ordering},
     Arc, Mutex,
 };
 
-use crate::filetype::{BinaryFileType, EguiConfig, InputFileType, LineType};
+use crate::core::decompress::LogReader;
+use crate::filetype::{
+    epoch_ns, local_time, BinaryFileType, EguiConfig, InputFileType, LineType, NANOS_PER_MS,
+};
 use crate::parser::format_time_diff;
 
 // ============================================================================
@@ -25,8 +27,8 @@ use crate::parser::format_time_diff;
 pub struct DltLogLine {
     /// Parsed DLT message structure
     pub dlt_message: dlt_core::dlt::Message,
-    /// Storage header wall-clock timestamp (always available)
-    pub storage_time: DateTime<Local>,
+    /// Storage header wall-clock timestamp in epoch nanoseconds (always available)
+    pub storage_time_ns: i64,
     /// Header timestamp in microseconds (time since boot).
     /// `None` when the DLT message has no header timestamp field.
     pub header_timestamp_us: Option<i64>,
@@ -41,7 +43,7 @@ pub struct DltLogLine {
 impl DltLogLine {
     pub const fn new(
         dlt_message: dlt_core::dlt::Message,
-        storage_time: DateTime<Local>,
+        storage_time_ns: i64,
         header_timestamp_us: Option<i64>,
         ecu_id: String,
         app_id: String,
@@ -49,7 +51,7 @@ impl DltLogLine {
     ) -> Self {
         Self {
             dlt_message,
-            storage_time,
+            storage_time_ns,
             header_timestamp_us,
             ecu_id,
             app_id,
@@ -91,23 +93,7 @@ impl DltLogLine {
                 let formatted_args: Vec<String> = args
                     .iter()
                     .map(|arg| {
-                        let val_str = match &arg.value {
-                            dlt_core::dlt::Value::StringVal(s) => s.clone(),
-                            dlt_core::dlt::Value::U32(v) => format!("{v}"),
-                            dlt_core::dlt::Value::U64(v) => format!("{v}"),
-                            dlt_core::dlt::Value::U8(v) => format!("{v}"),
-                            dlt_core::dlt::Value::U16(v) => format!("{v}"),
-                            dlt_core::dlt::Value::I32(v) => format!("{v}"),
-                            dlt_core::dlt::Value::I64(v) => format!("{v}"),
-                            dlt_core::dlt::Value::I8(v) => format!("{v}"),
-                            dlt_core::dlt::Value::I16(v) => format!("{v}"),
-                            dlt_core::dlt::Value::F32(v) => format!("{v}"),
-                            dlt_core::dlt::Value::F64(v) => format!("{v}"),
-                            dlt_core::dlt::Value::Bool(v) => format!("{v}"),
-                            dlt_core::dlt::Value::U128(v) => format!("{v}"),
-                            dlt_core::dlt::Value::I128(v) => format!("{v}"),
-                            dlt_core::dlt::Value::Raw(bytes) => format!("{bytes:02x?}"),
-                        };
+                        let val_str = format_dlt_value(&arg.value);
                         arg.name
                             .as_ref()
                             .map(|name| format!("{name}: {val_str}"))
@@ -126,6 +112,12 @@ impl DltLogLine {
         format!("{ecu_header} {session_id} {app_id} {ctx_id} {message_type} {payload}")
     }
 
+    /// Storage header wall-clock timestamp in local time.
+    #[must_use]
+    pub fn storage_time(&self) -> DateTime<Local> {
+        local_time(self.storage_time_ns)
+    }
+
     /// Returns the `[<storage_time> (<diff>) <storage_ecu>]` prefix for inferred-monotonic mode.
     fn format_time_prefix(&self, inferred_time: DateTime<Local>) -> String {
         let storage_ecu = self
@@ -133,8 +125,31 @@ impl DltLogLine {
             .storage_header
             .as_ref()
             .map_or("", |sh| sh.ecu_id.as_str());
-        let diff_str = format_time_diff(self.storage_time.signed_duration_since(inferred_time));
-        format!("[{} ({diff_str}) {storage_ecu}]", self.storage_time)
+        let storage_time = self.storage_time();
+        let diff_str = format_time_diff(storage_time.signed_duration_since(inferred_time));
+        format!("[{storage_time} ({diff_str}) {storage_ecu}]")
+    }
+}
+
+/// Format a single verbose DLT argument value.
+fn format_dlt_value(value: &dlt_core::dlt::Value) -> String {
+    use dlt_core::dlt::Value;
+    match value {
+        Value::StringVal(s) => s.clone(),
+        Value::U32(v) => format!("{v}"),
+        Value::U64(v) => format!("{v}"),
+        Value::U8(v) => format!("{v}"),
+        Value::U16(v) => format!("{v}"),
+        Value::I32(v) => format!("{v}"),
+        Value::I64(v) => format!("{v}"),
+        Value::I8(v) => format!("{v}"),
+        Value::I16(v) => format!("{v}"),
+        Value::F32(v) => format!("{v}"),
+        Value::F64(v) => format!("{v}"),
+        Value::Bool(v) => format!("{v}"),
+        Value::U128(v) => format!("{v}"),
+        Value::I128(v) => format!("{v}"),
+        Value::Raw(bytes) => format!("{bytes:02x?}"),
     }
 }
 
@@ -327,21 +342,28 @@ impl LineType for DltLogLine {
         config: &crate::config::DltTimestampSource,
         file_state: &DltFileState,
     ) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(
+        &self,
+        config: &crate::config::DltTimestampSource,
+        file_state: &DltFileState,
+    ) -> i64 {
         use crate::config::DltTimestampSource;
+        let storage_ns = self.storage_time_ns + file_state.storage_offset_ms() * NANOS_PER_MS;
         match config {
             DltTimestampSource::InferredMonotonic => {
                 if let Some(header_us) = self.header_timestamp_us {
                     let key = (self.ecu_id.clone(), self.app_id.clone());
                     if let Some(boot_time) = file_state.boot_times.get(&key) {
-                        return *boot_time + chrono::TimeDelta::microseconds(header_us);
+                        return epoch_ns(&*boot_time) + header_us * 1_000;
                     }
                 }
                 // Fallback: no boot_time for this app yet
-                self.storage_time + chrono::Duration::milliseconds(file_state.storage_offset_ms())
-            }
-            DltTimestampSource::StorageTime => {
-                self.storage_time + chrono::Duration::milliseconds(file_state.storage_offset_ms())
+                storage_ns
             }
+            DltTimestampSource::StorageTime => storage_ns,
         }
     }
 
@@ -387,6 +409,80 @@ impl LineType for DltLogLine {
         self.line_number
     }
 
+    fn fields(&self) -> Vec<(String, String)> {
+        use dlt_core::dlt::PayloadContent;
+
+        let msg = &self.dlt_message;
+        let mut fields = Vec::new();
+        if let Some(ref storage) = msg.storage_header {
+            fields.push(("Storage ECU".to_string(), storage.ecu_id.clone()));
+        }
+        fields.push((
+            "Storage Time".to_string(),
+            self.storage_time()
+                .format("%Y-%m-%d %H:%M:%S%.6f")
+                .to_string(),
+        ));
+        if let Some(ref ecu) = msg.header.ecu_id {
+            fields.push(("ECU".to_string(), ecu.clone()));
+        }
+        if let Some(session_id) = msg.header.session_id {
+            fields.push(("Session ID".to_string(), session_id.to_string()));
+        }
+        if let Some(header_us) = self.header_timestamp_us {
+            fields.push((
+                "Header Timestamp".to_string(),
+                format!("{:.4}s", header_us as f64 / 1_000_000.0),
+            ));
+        }
+        fields.push((
+            "Counter".to_string(),
+            msg.header.message_counter.to_string(),
+        ));
+        if let Some(ref ext) = msg.extended_header {
+            fields.push(("APID".to_string(), ext.application_id.clone()));
+            fields.push(("CTID".to_string(), ext.context_id.clone()));
+            fields.push(("Type".to_string(), format!("{:?}", ext.message_type)));
+            fields.push(("Verbose".to_string(), ext.verbose.to_string()));
+        }
+        match &msg.payload {
+            PayloadContent::Verbose(args) => {
+                for (i, arg) in args.iter().enumerate() {
+                    let name = arg
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("Arg {i}"));
+                    let mut value = format_dlt_value(&arg.value);
+                    if let Some(ref unit) = arg.unit {
+                        value = format!("{value} {unit}");
+                    }
+                    fields.push((name, value));
+                }
+            }
+            PayloadContent::NonVerbose(id, bytes) => {
+                fields.push(("Message ID".to_string(), id.to_string()));
+                fields.push(("Payload".to_string(), format!("{bytes:02x?}")));
+            }
+            PayloadContent::ControlMsg(ctrl, bytes) => {
+                fields.push(("Control".to_string(), format!("{ctrl:?}")));
+                fields.push(("Payload".to_string(), format!("{bytes:02x?}")));
+            }
+            PayloadContent::NetworkTrace(traces) => {
+                fields.push(("Network Traces".to_string(), traces.len().to_string()));
+            }
+        }
+        fields
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        let mut facets = vec![("ECU", self.ecu_id.clone()), ("APID", self.app_id.clone())];
+        if let Some(ref ext) = self.dlt_message.extended_header {
+            facets.push(("CTID", ext.context_id.clone()));
+            facets.push(("Type", format!("{:?}", ext.message_type)));
+        }
+        facets
+    }
+
     fn egui_render_context_menu(
         &self,
         ui: &mut Ui,
@@ -399,20 +495,18 @@ impl LineType for DltLogLine {
             let is_inferred = matches!(config, DltTimestampSource::InferredMonotonic)
                 && self.header_timestamp_us.is_some();
 
+            let storage_time = self.storage_time();
             // Current display time: inferred if available, otherwise storage.
             let current_time = if is_inferred {
                 let header_us = self
                     .header_timestamp_us
                     .expect("header_timestamp_us is Some when is_inferred");
                 let key = (self.ecu_id.clone(), self.app_id.clone());
-                file_state
-                    .boot_times
-                    .get(&key)
-                    .map_or(self.storage_time, |bt| {
-                        *bt + chrono::TimeDelta::microseconds(header_us)
-                    })
+                file_state.boot_times.get(&key).map_or(storage_time, |bt| {
+                    *bt + chrono::TimeDelta::microseconds(header_us)
+                })
             } else {
-                self.storage_time + chrono::Duration::milliseconds(file_state.storage_offset_ms())
+                storage_time + chrono::Duration::milliseconds(file_state.storage_offset_ms())
             };
 
             *file_state
@@ -423,12 +517,12 @@ impl LineType for DltLogLine {
                 app_id: self.app_id.clone(),
                 header_timestamp_us: self.header_timestamp_us.unwrap_or(0),
                 is_inferred,
-                storage_time: self.storage_time,
+                storage_time,
                 window: crate::filetype::CalibrationWindow::new(
                     current_time,
                     is_inferred,
                     Some(current_time),
-                    self.storage_time,
+                    storage_time,
                 ),
             });
             ui.close();
@@ -478,46 +572,24 @@ impl crate::filetype::LogFileState for DltFileState {
             }
         }
     }
+
+    /// Storage-time offset; per-app boot times are not corrections.
+    fn clock_offset_ms(&self) -> i64 {
+        self.storage_offset_ms()
+    }
 }
 
 // ============================================================================
 // DltFileType (InputFileType + BinaryFileType)
 // ============================================================================
 
-/// Minimal `Read` wrapper that counts bytes consumed, used for `ChunkedLoader` progress.
-struct ByteCountReader<R> {
-    inner: R,
-    count: Arc<AtomicU64>,
-}
-
-impl<R: Read> ByteCountReader<R> {
-    fn new(inner: R) -> Self {
-        Self {
-            inner,
-            count: Arc::new(AtomicU64::new(0)),
-        }
-    }
-
-    fn bytes_read_arc(&self) -> Arc<AtomicU64> {
-        Arc::clone(&self.count)
-    }
-}
-
-impl<R: Read> Read for ByteCountReader<R> {
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let n = self.inner.read(buf)?;
-        self.count.fetch_add(n as u64, Ordering::Relaxed);
-        Ok(n)
-    }
-}
-
 /// Stateful streaming reader for AUTOSAR Diagnostic Log and Trace (`.dlt`) files.
 ///
 /// Holds a clone of the `Arc<DashMap>` from [`DltFileState::boot_times`] so that
 /// each `read(n)` call can write newly discovered `(ECU, App)` boot-times directly
 /// into the shared map — no lock acquisition, no end-of-chunk batch flush.
 pub struct DltFileType {
-    reader: DltMessageReader<ByteCountReader<BufReader<File>>>,
+    reader: DltMessageReader<BufReader<LogReader>>,
     /// Shared boot-time map — same `Arc` as `DltFileState::boot_times`.
     boot_times: Arc<DashMap<(String, String), DateTime<Local>>>,
     bytes_read_rc: Arc<AtomicU64>,
@@ -527,6 +599,7 @@ pub struct DltFileType {
 impl InputFileType for DltFileType {
     type LineType = DltLogLine;
 
+    const DISPLAY_NAME: &'static str = "AUTOSAR DLT";
     const FILE_EXTENSIONS: &'static [&'static str] = &["dlt"];
 
     /// Open a DLT file for pull-based reading.
@@ -535,15 +608,13 @@ impl InputFileType for DltFileType {
         _config: crate::config::DltTimestampSource,
         file_state: Arc<DltFileState>,
     ) -> anyhow::Result<Self> {
-        use anyhow::Context as _;
         // Clone the boot_times Arc so read() can write into it without
         // ever touching the outer Arc<DltFileState>.
         let boot_times = Arc::clone(&file_state.boot_times);
-        let file =
-            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
-        let inner = ByteCountReader::new(BufReader::new(file));
-        let bytes_read_rc = inner.bytes_read_arc();
-        let reader = DltMessageReader::new(inner, true);
+        let file = LogReader::open(path)?;
+        // Bytes read from disk (compressed, if the capture is) drive the progress
+        let bytes_read_rc = file.disk_read();
+        let reader = DltMessageReader::new(BufReader::new(file), true);
         Ok(Self {
             reader,
             boot_times,
@@ -570,7 +641,7 @@ impl InputFileType for DltFileType {
                             // First-seen wins; persisted calibration loaded at open time is
                             // already present and or_insert_with leaves it untouched.
                             self.boot_times.entry(key).or_insert_with(|| {
-                                line.storage_time - chrono::TimeDelta::microseconds(header_us)
+                                line.storage_time() - chrono::TimeDelta::microseconds(header_us)
                             });
                         }
                         result.push(line);
@@ -603,21 +674,15 @@ impl BinaryFileType for DltFileType {
 // DLT parsing utilities (moved from parser/dlt.rs)
 // ============================================================================
 
-pub fn storage_time_to_datetime(
-    storage_time: &dlt_core::dlt::DltTimeStamp,
-) -> Option<DateTime<Local>> {
-    use chrono::TimeZone;
-    Local
-        .timestamp_opt(
-            i64::from(storage_time.seconds),
-            storage_time.microseconds * 1000,
-        )
-        .single()
+/// Storage header time in epoch nanoseconds.
+#[must_use]
+pub fn storage_time_to_ns(storage_time: &dlt_core::dlt::DltTimeStamp) -> i64 {
+    i64::from(storage_time.seconds) * 1_000_000_000 + i64::from(storage_time.microseconds) * 1_000
 }
 
 /// Convert a `dlt_core::dlt::Message` to `DltLogLine`.
 pub fn convert_dlt_message(msg: &dlt_core::dlt::Message, line_number: usize) -> Option<DltLogLine> {
-    let storage_time = storage_time_to_datetime(&msg.storage_header.as_ref()?.timestamp)?;
+    let storage_time_ns = storage_time_to_ns(&msg.storage_header.as_ref()?.timestamp);
 
     if msg.header.ecu_id.is_none() {
         tracing::warn!("DLT message missing ECU ID for line {line_number}");
@@ -640,7 +705,7 @@ pub fn convert_dlt_message(msg: &dlt_core::dlt::Message, line_number: usize) ->
 
     Some(DltLogLine::new(
         msg.clone(),
-        storage_time,
+        storage_time_ns,
         header_timestamp_us,
         ecu_id,
         app_id,
@@ -1,14 +1,14 @@
 // LogCrab - GPL-3.0-or-later
 // Copyright (C) 2026 Daniel Freiermuth
 
-use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono::{DateTime, Local};
 use egui::Ui;
-use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::sync::LazyLock;
 
-use crate::filetype::{InputFileType, LineType, TextFileType};
+use crate::core::decompress::LogReader;
+use crate::filetype::{local_time, InputFileType, LineType, TextFileType, NANOS_PER_MS};
 
 // ============================================================================
 // DmesgLogLine
@@ -16,16 +16,16 @@ use crate::filetype::{InputFileType, LineType, TextFileType};
 
 /// Dmesg kernel log line: `[SECONDS.MICROSECONDS] message`
 ///
-/// The timestamp is seconds since system boot. It is stored as a
-/// `DateTime<Local>` anchored at the Unix epoch so that relative ordering and
+/// The timestamp is seconds since system boot. It is stored as epoch
+/// nanoseconds anchored at the Unix epoch so that relative ordering and
 /// calibration both work correctly — the absolute wall-clock value is
 /// meaningless until the user calibrates the source.
 #[derive(Debug, Clone)]
 pub struct DmesgLogLine {
     /// Original raw line from file
     raw_line: String,
-    /// Parsed timestamp (epoch + boot-relative duration)
-    pub timestamp: DateTime<Local>,
+    /// Parsed timestamp (epoch + boot-relative duration) in nanoseconds
+    pub timestamp_ns: i64,
     /// Message portion (everything after `[SECONDS.MICROSECONDS] `)
     message_text: String,
     /// Original line number in source file
@@ -35,13 +35,13 @@ pub struct DmesgLogLine {
 impl DmesgLogLine {
     pub const fn new(
         raw_line: String,
-        timestamp: DateTime<Local>,
+        timestamp_ns: i64,
         message_text: String,
         line_number: usize,
     ) -> Self {
         Self {
             raw_line,
-            timestamp,
+            timestamp_ns,
             message_text,
             line_number,
         }
@@ -78,8 +78,12 @@ impl LineType for DmesgLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &(), file_state: &DmesgFileState) -> DateTime<Local> {
-        self.timestamp + chrono::Duration::milliseconds(file_state.time_offset_ms())
+    fn timestamp(&self, config: &(), file_state: &DmesgFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &DmesgFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
     }
 
     fn message(&self) -> String {
@@ -109,7 +113,7 @@ impl LineType for DmesgLogLine {
 
     fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &DmesgFileState) {
         if ui.button("⏱ Calibrate Time Here").clicked() {
-            let raw_time = self.timestamp;
+            let raw_time = local_time(self.timestamp_ns);
             let display_time =
                 raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
             *file_state
@@ -140,7 +144,7 @@ impl LineType for DmesgLogLine {
 /// timestamp header are treated as continuations and appended (with `\n`) to
 /// the most-recently-seen timestamped entry.
 pub struct DmesgFileType {
-    reader: BufReader<File>,
+    reader: BufReader<LogReader>,
     line_number: usize,
     bytes_read: u64,
     /// Last parsed entry, held back until we know it has no more continuations.
@@ -150,6 +154,7 @@ pub struct DmesgFileType {
 impl InputFileType for DmesgFileType {
     type LineType = DmesgLogLine;
 
+    const DISPLAY_NAME: &'static str = "Kernel Log (dmesg)";
     const FILE_EXTENSIONS: &'static [&'static str] = &["log", "txt"];
 
     fn open(
@@ -157,9 +162,7 @@ impl InputFileType for DmesgFileType {
         _config: (),
         _file_state: std::sync::Arc<DmesgFileState>,
     ) -> anyhow::Result<Self> {
-        use anyhow::Context as _;
-        let file =
-            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let file = LogReader::open(path)?;
         Ok(Self {
             reader: BufReader::new(file),
             line_number: 0,
@@ -215,7 +218,7 @@ impl InputFileType for DmesgFileType {
     }
 
     fn bytes_consumed(&self) -> u64 {
-        self.bytes_read
+        self.reader.get_ref().disk_position(self.bytes_read)
     }
 }
 
@@ -271,15 +274,10 @@ pub fn parse_dmesg_line(raw: String, line_number: usize) -> Option<DmesgLogLine>
     let secs: i64 = caps[1].parse().ok()?;
     let micros: i64 = caps[2].parse().ok()?;
     let message = caps[3].to_string();
-    let total_micros = secs * 1_000_000 + micros;
-    let timestamp = Utc
-        .timestamp_opt(
-            total_micros / 1_000_000,
-            ((total_micros % 1_000_000) * 1_000) as u32,
-        )
-        .single()?
-        .with_timezone(&Local);
-    Some(DmesgLogLine::new(raw, timestamp, message, line_number))
+    let timestamp_ns = secs
+        .checked_mul(1_000_000_000)?
+        .checked_add(micros * 1_000)?;
+    Some(DmesgLogLine::new(raw, timestamp_ns, message, line_number))
 }
 
 #[cfg(test)]
@@ -291,7 +289,7 @@ mod tests {
         let raw = "[    0.000000] Initializing cgroup subsys cpuset".to_string();
         let line = parse_dmesg_line(raw, 1).expect("should parse dmesg line");
         assert_eq!(line.message_text, "Initializing cgroup subsys cpuset");
-        assert_eq!(line.timestamp.timestamp_micros(), 0);
+        assert_eq!(line.timestamp_ns / 1_000, 0);
     }
 
     #[test]
@@ -300,10 +298,7 @@ mod tests {
         let line = parse_dmesg_line(raw, 2).expect("should parse dmesg line");
         assert_eq!(line.message_text, "init: service 'foo' requested start");
         // 42798 seconds + 603585 microseconds
-        assert_eq!(
-            line.timestamp.timestamp_micros(),
-            42798 * 1_000_000 + 603_585
-        );
+        assert_eq!(line.timestamp_ns / 1_000, 42798 * 1_000_000 + 603_585);
     }
 
     #[test]
@@ -331,10 +326,7 @@ mod tests {
             line.message_text,
             "init: Untracked pid 22963 exited with status 0"
         );
-        assert_eq!(
-            line.timestamp.timestamp_micros(),
-            1_400 * 1_000_000 + 67_717
-        );
+        assert_eq!(line.timestamp_ns / 1_000, 1_400 * 1_000_000 + 67_717);
         assert!(is_dmesg_line(
             "<14>[ 1400.067717][    T1] init: some message"
         ));
@@ -348,7 +340,7 @@ mod tests {
         tmp.write_all(content.as_bytes()).expect("write");
         let path = tmp.path().to_owned();
         let ft = DmesgFileType {
-            reader: BufReader::new(File::open(&path).expect("open")),
+            reader: BufReader::new(LogReader::open(&path).expect("open")),
             line_number: 0,
             bytes_read: 0,
             pending: None,
@@ -1,15 +1,18 @@
 // LogCrab - GPL-3.0-or-later
 // Copyright (C) 2026 Daniel Freiermuth
 
-use chrono::{DateTime, Local, TimeZone};
+use chrono::{DateTime, Local};
 use egui::Ui;
 use fancy_regex::Regex;
-use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
-use crate::filetype::{InputFileType, LineType, TextFileType};
+use crate::core::decompress::LogReader;
+use crate::core::text_arena::{ArenaStr, TextArena};
+use crate::filetype::{
+    epoch_ns, local_naive_ns, local_time, InputFileType, LineType, TextFileType, NANOS_PER_MS,
+};
 
 // ============================================================================
 // GenericLogLine
@@ -18,29 +21,21 @@ use crate::filetype::{InputFileType, LineType, TextFileType};
 /// Generic text-based log line with timestamp
 #[derive(Debug, Clone)]
 pub struct GenericLogLine {
-    /// Original raw line from file
-    raw_line: String,
-    /// Parsed timestamp
-    pub timestamp: DateTime<Local>,
-    /// Message portion (everything after timestamp, or whole line if no timestamp)
-    message_text: String,
+    /// Original raw line from file, usually shared with the rest of its chunk
+    raw_line: ArenaStr,
+    /// Parsed timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    /// Start of the message portion within `raw_line`; see [`Self::message_text`]
+    message_start: u32,
     /// Original line number in source file
     pub line_number: usize,
 }
 
 impl GenericLogLine {
-    pub const fn new(
-        raw_line: String,
-        timestamp: DateTime<Local>,
-        message_text: String,
-        line_number: usize,
-    ) -> Self {
-        Self {
-            raw_line,
-            timestamp,
-            message_text,
-            line_number,
-        }
+    /// Message portion (everything after timestamp, or whole line if nothing follows it)
+    #[must_use]
+    pub fn message_text(&self) -> &str {
+        &self.raw_line[self.message_start as usize..]
     }
 }
 
@@ -66,12 +61,16 @@ impl LineType for GenericLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &(), file_state: &GenericFileState) -> DateTime<Local> {
-        self.timestamp + chrono::Duration::milliseconds(file_state.time_offset_ms())
+    fn timestamp(&self, config: &(), file_state: &GenericFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &GenericFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
     }
 
     fn message(&self) -> String {
-        self.message_text.clone()
+        self.message_text().to_string()
     }
 
     fn display_message(&self, _config: &(), file_state: &GenericFileState) -> String {
@@ -80,15 +79,15 @@ impl LineType for GenericLogLine {
             format!(
                 "[{}] {}",
                 crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
-                self.message_text
+                self.message_text()
             )
         } else {
-            self.message_text.clone()
+            self.message_text().to_string()
         }
     }
 
     fn raw(&self) -> String {
-        self.raw_line.clone()
+        self.raw_line.to_string()
     }
 
     fn line_number(&self) -> usize {
@@ -97,7 +96,7 @@ impl LineType for GenericLogLine {
 
     fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &GenericFileState) {
         if ui.button("⏱ Calibrate Time Here").clicked() {
-            let raw_time = self.timestamp;
+            let raw_time = local_time(self.timestamp_ns);
             let display_time =
                 raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
             *file_state
@@ -126,7 +125,7 @@ impl LineType for GenericLogLine {
 /// **Must be the last text type in the registry** — its `looks_like` always
 /// returns `true`, acting as the catch-all fallback.
 pub struct GenericFileType {
-    reader: BufReader<File>,
+    reader: BufReader<LogReader>,
     line_number: usize,
     bytes_read: u64,
 }
@@ -134,6 +133,7 @@ pub struct GenericFileType {
 impl InputFileType for GenericFileType {
     type LineType = GenericLogLine;
 
+    const DISPLAY_NAME: &'static str = "Text Log";
     const FILE_EXTENSIONS: &'static [&'static str] = &["txt", "log"];
 
     /// Open a generic text log file for pull-based reading.
@@ -142,9 +142,7 @@ impl InputFileType for GenericFileType {
         _config: (),
         _file_state: std::sync::Arc<GenericFileState>,
     ) -> anyhow::Result<Self> {
-        use anyhow::Context as _;
-        let file =
-            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let file = LogReader::open(path)?;
         Ok(Self {
             reader: BufReader::new(file),
             line_number: 0,
@@ -152,10 +150,12 @@ impl InputFileType for GenericFileType {
         })
     }
 
+    /// Lines read in one call share a single [`TextArena`] buffer for their text.
     fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
-        let mut result = Vec::with_capacity(lines_to_read);
+        let mut parsed = Vec::with_capacity(lines_to_read);
+        let mut arena = TextArena::default();
         let mut buf = Vec::new();
-        while result.len() < lines_to_read {
+        while parsed.len() < lines_to_read {
             buf.clear();
             match self.reader.read_until(b'\n', &mut buf) {
                 Ok(0) => break, // EOF
@@ -163,7 +163,7 @@ impl InputFileType for GenericFileType {
                     self.bytes_read += n as u64;
                     self.line_number += 1;
                     let line_str = String::from_utf8_lossy(&buf);
-                    let raw = line_str.trim_end_matches(['\n', '\r']).to_string();
+                    let raw = line_str.trim_end_matches(['\n', '\r']);
                     if matches!(line_str, std::borrow::Cow::Owned(_)) {
                         tracing::warn!(
                             "Line {}: {} contains invalid UTF-8 bytes; replacement characters inserted",
@@ -171,18 +171,29 @@ impl InputFileType for GenericFileType {
                             raw
                         );
                     }
-                    if let Some(line) = parse_generic_line(raw, self.line_number) {
-                        result.push(line);
+                    if let Some((timestamp, message_start)) = parse_generic_parts(raw) {
+                        parsed.push((arena.push(raw), timestamp, message_start, self.line_number));
                     }
                 }
                 Err(e) => return Err(anyhow::anyhow!("Read error: {e}")),
             }
         }
-        Ok(result)
+        let text = arena.freeze();
+        Ok(parsed
+            .into_iter()
+            .map(
+                |(range, timestamp_ns, message_start, line_number)| GenericLogLine {
+                    raw_line: ArenaStr::new(Arc::clone(&text), range),
+                    timestamp_ns,
+                    message_start,
+                    line_number,
+                },
+            )
+            .collect())
     }
 
     fn bytes_consumed(&self) -> u64 {
-        self.bytes_read
+        self.reader.get_ref().disk_position(self.bytes_read)
     }
 }
 
@@ -198,7 +209,7 @@ impl TextFileType for GenericFileType {
 // ============================================================================
 
 static ISO_TIMESTAMP: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^(\d{4}-\d{2}-\d{2}[T\s]\d{2}:\d{2}:\d{2}(?:\.\d{3})?(?:Z|[+-]\d{2}:?\d{2})?)")
+    Regex::new(r"^(\d{4}-\d{2}-\d{2}[T\s]\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:?\d{2})?)")
         .expect("valid regex literal")
 });
 static HYPHENATED_TIMESTAMP: LazyLock<Regex> = LazyLock::new(|| {
@@ -226,18 +237,31 @@ static BRACKETED_CTIME_TIMESTAMP: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 /// Parse a single line and return the concrete `GenericLogLine` if it has a recognised timestamp.
+#[must_use]
 pub fn parse_generic_line(raw: String, line_number: usize) -> Option<GenericLogLine> {
+    let (timestamp_ns, message_start) = parse_generic_parts(&raw)?;
+    Some(GenericLogLine {
+        raw_line: ArenaStr::from(raw),
+        timestamp_ns,
+        message_start,
+        line_number,
+    })
+}
+
+/// Parse the timestamp of a line and locate the message after it, as a byte
+/// offset into `raw`, without copying text.
+pub(crate) fn parse_generic_parts(raw: &str) -> Option<(i64, u32)> {
     let mut timestamp = None;
-    let mut remaining = raw.as_str();
+    let mut remaining = raw;
 
     if let Ok(Some(caps)) = SLASH_TIMESTAMP.captures(remaining) {
         if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y/%m/%d %H:%M:%S%.f") {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y/%m/%d %H:%M:%S")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = HYPHENATED_TIMESTAMP.captures(remaining) {
@@ -247,12 +271,12 @@ pub fn parse_generic_line(raw: String, line_number: usize) -> Option<GenericLogL
         if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%d %H:%M:%S%.f")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%d %H:%M:%S")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = ISO_TIMESTAMP.captures(remaining) {
@@ -274,34 +298,34 @@ pub fn parse_generic_line(raw: String, line_number: usize) -> Option<GenericLogL
             },
         );
         if let Ok(dt) = DateTime::parse_from_rfc3339(&normalized_ts) {
-            timestamp = Some(dt.with_timezone(&Local));
+            timestamp = Some(epoch_ns(&dt));
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
-            chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y-%m-%d %H:%M:%S%.3f")
+            chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y-%m-%d %H:%M:%S%.f")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y-%m-%d %H:%M:%S")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = BRACKETED_TIMESTAMP.captures(remaining) {
         if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y-%m-%d %H:%M:%S%.3f")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&caps[1], "%Y-%m-%d %H:%M:%S")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = BRACKETED_CTIME_TIMESTAMP.captures(remaining) {
         // e.g. [Sat Mar  7 11:53:27 2026]
         if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(&caps[1], "%a %b %e %H:%M:%S %Y") {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = LOGCAT_TIMESTAMP_GENERIC.captures(remaining) {
@@ -309,39 +333,40 @@ pub fn parse_generic_line(raw: String, line_number: usize) -> Option<GenericLogL
         if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&timestamp_str, "%Y-%m-%d %H:%M:%S%.3f")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = SYSLOG_TIMESTAMP.captures(remaining) {
         let ts_str = format!("1970 {}", &caps[1]);
         if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(&ts_str, "%Y %b %d %H:%M:%S%.3f") {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&ts_str, "%Y %b %d %H:%M:%S")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     } else if let Ok(Some(caps)) = TIME_ONLY_TIMESTAMP.captures(remaining) {
         let ts_str = format!("1970-01-01 {}", &caps[1]);
         if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(&ts_str, "%Y-%m-%d %H:%M:%S%.f") {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         } else if let Ok(naive) =
             chrono::NaiveDateTime::parse_from_str(&ts_str, "%Y-%m-%d %H:%M:%S")
         {
-            timestamp = Local.from_local_datetime(&naive).single();
+            timestamp = local_naive_ns(&naive);
             remaining = remaining[caps[0].len()..].trim_start();
         }
     }
 
-    let message = if remaining.is_empty() {
-        raw.clone()
+    // `remaining` is always a suffix of `raw`
+    let message_start = if remaining.is_empty() {
+        0
     } else {
-        remaining.to_string()
+        raw.len() - remaining.len()
     };
-    timestamp.map(|ts| GenericLogLine::new(raw, ts, message, line_number))
+    Some((timestamp?, u32::try_from(message_start).ok()?))
 }
 
 #[cfg(test)]
@@ -352,7 +377,7 @@ mod tests {
     fn test_iso_timestamp() {
         let raw = "2025-11-20T14:23:45.123Z ERROR Connection failed".to_string();
         let line = parse_generic_line(raw, 1).expect("should parse ISO timestamp");
-        assert_eq!(line.message_text, "ERROR Connection failed");
+        assert_eq!(line.message_text(), "ERROR Connection failed");
     }
 
     #[test]
@@ -361,11 +386,13 @@ mod tests {
             .to_string();
         let line = parse_generic_line(raw, 1).expect("should parse hyphenated timestamp");
         assert_eq!(
-            line.message_text,
+            line.message_text(),
             ", [402.037] ,cnss: fatal: SMMU fault happened with IOVA 0x0"
         );
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "2025-11-26 09:58:05"
         );
     }
@@ -374,9 +401,11 @@ mod tests {
     fn test_syslog_format() {
         let raw = "Nov 20 14:23:45 INFO Application started".to_string();
         let line = parse_generic_line(raw, 1).expect("should parse syslog format");
-        assert_eq!(line.message_text, "INFO Application started");
+        assert_eq!(line.message_text(), "INFO Application started");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "1970-11-20 14:23:45"
         );
     }
@@ -386,9 +415,11 @@ mod tests {
         let raw = "2025-11-20 14:23:45.123 ERROR Connection failed".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("Should parse ISO timestamp with space and milliseconds");
-        assert_eq!(line.message_text, "ERROR Connection failed");
+        assert_eq!(line.message_text(), "ERROR Connection failed");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
             "2025-11-20 14:23:45.123"
         );
     }
@@ -398,9 +429,11 @@ mod tests {
         let raw = "2025-11-20 14:23:45 WARN Timeout occurred".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("Should parse ISO timestamp with space, no milliseconds");
-        assert_eq!(line.message_text, "WARN Timeout occurred");
+        assert_eq!(line.message_text(), "WARN Timeout occurred");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "2025-11-20 14:23:45"
         );
     }
@@ -410,9 +443,11 @@ mod tests {
         let raw = "[2025-11-20 14:23:45.123] DEBUG Processing request".to_string();
         let line =
             parse_generic_line(raw, 1).expect("Should parse bracketed timestamp with milliseconds");
-        assert_eq!(line.message_text, "DEBUG Processing request");
+        assert_eq!(line.message_text(), "DEBUG Processing request");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
             "2025-11-20 14:23:45.123"
         );
     }
@@ -422,9 +457,11 @@ mod tests {
         let raw = "[2025-11-20 14:23:45] INFO Service started".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("Should parse bracketed timestamp without milliseconds");
-        assert_eq!(line.message_text, "INFO Service started");
+        assert_eq!(line.message_text(), "INFO Service started");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "2025-11-20 14:23:45"
         );
     }
@@ -435,11 +472,13 @@ mod tests {
             "[Sat Mar  7 11:53:27 2026] kernel: usb 1-1: new high-speed USB device".to_string();
         let line = parse_generic_line(raw, 1).expect("should parse bracketed ctime timestamp");
         assert_eq!(
-            line.message_text,
+            line.message_text(),
             "kernel: usb 1-1: new high-speed USB device"
         );
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "2026-03-07 11:53:27"
         );
     }
@@ -448,9 +487,11 @@ mod tests {
     fn test_logcat_timestamp_format() {
         let raw = "11-20 14:23:45.123 E/ActivityManager: Process crashed".to_string();
         let line = parse_generic_line(raw, 1).expect("Should parse logcat timestamp format");
-        assert_eq!(line.message_text, "E/ActivityManager: Process crashed");
+        assert_eq!(line.message_text(), "E/ActivityManager: Process crashed");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
             "1970-11-20 14:23:45.123"
         );
     }
@@ -463,11 +504,13 @@ mod tests {
         let line =
             parse_generic_line(raw, 1).expect("Should parse syslog timestamp with milliseconds");
         assert_eq!(
-            line.message_text,
+            line.message_text(),
             "qcgpio[gpio_drv.c:1222]: dalcfg_query_item_name gpio_driver done"
         );
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
             "1970-02-03 23:26:34.864"
         );
     }
@@ -477,7 +520,7 @@ mod tests {
         let raw = "2025-11-20T14:23:45+05:30 INFO Server running".to_string();
         let line =
             parse_generic_line(raw, 1).expect("Should parse ISO timestamp with timezone offset");
-        assert_eq!(line.message_text, "INFO Server running");
+        assert_eq!(line.message_text(), "INFO Server running");
     }
 
     #[test]
@@ -485,8 +528,23 @@ mod tests {
         let raw = "2026-02-05T09:20:23.638+01:00 INFO Server started".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("Should parse ISO timestamp with milliseconds and timezone offset");
-        assert_eq!(line.message_text, "INFO Server started");
-        assert_eq!(line.timestamp.format("%Y-%m-%d").to_string(), "2026-02-05");
+        assert_eq!(line.message_text(), "INFO Server started");
+        assert_eq!(
+            local_time(line.timestamp_ns).format("%Y-%m-%d").to_string(),
+            "2026-02-05"
+        );
+    }
+
+    #[test]
+    fn test_iso_timestamp_with_nanoseconds() {
+        // `kubectl logs --timestamps`
+        let raw = "2024-03-01T10:00:00.123456789Z GET /healthz".to_string();
+        let line = parse_generic_line(raw, 1).expect("Should parse RFC 3339 nanoseconds");
+        assert_eq!(line.message_text(), "GET /healthz");
+        assert_eq!(
+            local_time(line.timestamp_ns).timestamp_subsec_nanos(),
+            123_456_789
+        );
     }
 
     #[test]
@@ -494,8 +552,11 @@ mod tests {
         let raw = "2026-02-05T09:20:23+0100 INFO Application started".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("Should parse ISO timestamp with timezone offset without colon");
-        assert_eq!(line.message_text, "INFO Application started");
-        assert_eq!(line.timestamp.format("%Y-%m-%d").to_string(), "2026-02-05");
+        assert_eq!(line.message_text(), "INFO Application started");
+        assert_eq!(
+            local_time(line.timestamp_ns).format("%Y-%m-%d").to_string(),
+            "2026-02-05"
+        );
     }
 
     #[test]
@@ -503,7 +564,7 @@ mod tests {
         let raw = "2026-02-10T15:30:00-0500 WARN Connection timeout".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("Should parse ISO timestamp with negative timezone offset without colon");
-        assert_eq!(line.message_text, "WARN Connection timeout");
+        assert_eq!(line.message_text(), "WARN Connection timeout");
     }
 
     #[test]
@@ -511,9 +572,11 @@ mod tests {
         let raw = "2026/03/09 01:20:14.942857 INFO Something happened".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("should parse slash-separated timestamp with microseconds");
-        assert_eq!(line.message_text, "INFO Something happened");
+        assert_eq!(line.message_text(), "INFO Something happened");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "2026-03-09 01:20:14"
         );
     }
@@ -523,9 +586,11 @@ mod tests {
         let raw = "2026/03/09 01:20:14 DEBUG No fractions".to_string();
         let line = parse_generic_line(raw, 1)
             .expect("should parse slash-separated timestamp without fraction");
-        assert_eq!(line.message_text, "DEBUG No fractions");
+        assert_eq!(line.message_text(), "DEBUG No fractions");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "2026-03-09 01:20:14"
         );
     }
@@ -534,9 +599,11 @@ mod tests {
     fn test_time_only_with_milliseconds() {
         let raw = "01:34:00.178 INFO Something happened".to_string();
         let line = parse_generic_line(raw, 1).expect("should parse time-only timestamp");
-        assert_eq!(line.message_text, "INFO Something happened");
+        assert_eq!(line.message_text(), "INFO Something happened");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S%.3f")
+                .to_string(),
             "1970-01-01 01:34:00.178"
         );
     }
@@ -546,9 +613,11 @@ mod tests {
         let raw = "01:34:00 DEBUG No fractions".to_string();
         let line =
             parse_generic_line(raw, 1).expect("should parse time-only timestamp without fraction");
-        assert_eq!(line.message_text, "DEBUG No fractions");
+        assert_eq!(line.message_text(), "DEBUG No fractions");
         assert_eq!(
-            line.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
             "1970-01-01 01:34:00"
         );
     }
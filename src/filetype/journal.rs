@@ -0,0 +1,567 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+use chrono::{DateTime, Local};
+use egui::Ui;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+use crate::core::decompress::LogReader;
+use crate::filetype::{local_time, InputFileType, LineType, Severity, TextFileType, NANOS_PER_MS};
+
+/// Entries of history `journalctl -f` prints before following
+const FOLLOW_BACKLOG: usize = 1000;
+
+/// Columns taken from entry fields besides the priority, each from the
+/// first of its fields the entry has
+const FACET_FIELDS: [(&str, &[&str]); 3] = [
+    ("Unit", &["_SYSTEMD_UNIT", "UNIT", "_SYSTEMD_USER_UNIT"]),
+    ("Identifier", &["SYSLOG_IDENTIFIER", "_COMM"]),
+    ("Host", &["_HOSTNAME"]),
+];
+
+// ============================================================================
+// JournalLogLine
+// ============================================================================
+
+/// One journal entry, from `journalctl -o export` or `journalctl -o json`
+#[derive(Debug, Clone)]
+pub struct JournalLogLine {
+    /// Fields of the entry in their original order, without the
+    /// `__`-prefixed address fields (cursor, timestamps, sequence numbers)
+    entry_fields: Vec<(String, String)>,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    /// `PRIORITY` field, the syslog severity of the entry
+    pub priority: Option<Severity>,
+    message_text: String,
+    /// Number of the entry's first line in the file
+    pub line_number: usize,
+}
+
+impl JournalLogLine {
+    /// Build an entry from its fields; `None` without a `__REALTIME_TIMESTAMP`.
+    fn from_fields(fields: Vec<(String, String)>, line_number: usize) -> Option<Self> {
+        let realtime_us: i64 = fields
+            .iter()
+            .find(|(key, _)| key == "__REALTIME_TIMESTAMP")?
+            .1
+            .parse()
+            .ok()?;
+        let timestamp_ns = realtime_us.checked_mul(1_000)?;
+        let entry_fields: Vec<(String, String)> = fields
+            .into_iter()
+            .filter(|(key, _)| !key.starts_with("__"))
+            .collect();
+        let mut line = Self {
+            entry_fields,
+            timestamp_ns,
+            priority: None,
+            message_text: String::new(),
+            line_number,
+        };
+        line.priority = line
+            .field("PRIORITY")
+            .and_then(|p| p.parse::<u8>().ok())
+            .map(Severity::from_code);
+        line.message_text = line.field("MESSAGE").unwrap_or_default().to_string();
+        Some(line)
+    }
+
+    /// Parse one line of `journalctl -o json` output
+    fn from_json(raw: &str, line_number: usize) -> Option<Self> {
+        let Value::Object(object) = serde_json::from_str::<Value>(raw).ok()? else {
+            return None;
+        };
+        let fields = object
+            .into_iter()
+            .filter_map(|(key, value)| json_field_value(&value).map(|value| (key, value)))
+            .collect();
+        Self::from_fields(fields, line_number)
+    }
+
+    /// First value of field `key`
+    fn field(&self, key: &str) -> Option<&str> {
+        self.entry_fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// First of `keys` that the entry has
+    fn first_field(&self, keys: &[&str]) -> Option<&str> {
+        keys.iter().find_map(|key| self.field(key))
+    }
+}
+
+/// Text of a JSON field value: a string, an array of bytes (binary data) or
+/// an array of values for a field given several times. `null` marks a value
+/// too large for `journalctl` to print.
+fn json_field_value(value: &Value) -> Option<String> {
+    if let Some(text) = value.as_str() {
+        return Some(text.to_string());
+    }
+    let items = value.as_array()?;
+    let bytes: Option<Vec<u8>> = items
+        .iter()
+        .map(|item| item.as_u64().and_then(|b| u8::try_from(b).ok()))
+        .collect();
+    Some(bytes.map_or_else(
+        || {
+            items
+                .iter()
+                .filter_map(json_field_value)
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+        |bytes| String::from_utf8_lossy(&bytes).into_owned(),
+    ))
+}
+
+// ============================================================================
+// JournalFileState
+// ============================================================================
+
+pub type JournalFileState = crate::filetype::SimpleFileState;
+
+// ============================================================================
+// LineType implementation
+// ============================================================================
+
+impl LineType for JournalLogLine {
+    type Config = ();
+    type FileState = JournalFileState;
+
+    fn file_state_from_v2(time_offset_ms: i64) -> JournalFileState {
+        let s = JournalFileState::default();
+        s.set_time_offset_ms(time_offset_ms);
+        s
+    }
+
+    fn timestamp(&self, config: &(), file_state: &JournalFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &JournalFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
+    }
+
+    /// `identifier[pid]: message`, as `journalctl` prints it
+    fn message(&self) -> String {
+        let identifier = self.first_field(FACET_FIELDS[1].1);
+        match (identifier, self.field("_PID")) {
+            (Some(ident), Some(pid)) => format!("{ident}[{pid}]: {}", self.message_text),
+            (Some(ident), None) => format!("{ident}: {}", self.message_text),
+            (None, _) => self.message_text.clone(),
+        }
+    }
+
+    fn display_message(&self, _config: &(), file_state: &JournalFileState) -> String {
+        let offset_ms = file_state.time_offset_ms();
+        if offset_ms != 0 {
+            format!(
+                "[{}] {}",
+                crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
+                self.message()
+            )
+        } else {
+            self.message()
+        }
+    }
+
+    /// The entry's fields as `KEY=VALUE` lines
+    fn raw(&self) -> String {
+        self.entry_fields
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        self.entry_fields
+            .iter()
+            .filter(|(key, _)| key != "MESSAGE")
+            .cloned()
+            .collect()
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        let mut facets: Vec<(&'static str, String)> = self
+            .priority
+            .map(|p| ("Priority", p.name().to_string()))
+            .into_iter()
+            .collect();
+        facets.extend(FACET_FIELDS.iter().filter_map(|(column, keys)| {
+            self.first_field(keys)
+                .map(|value| (*column, value.to_string()))
+        }));
+        facets
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.priority
+    }
+
+    fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &JournalFileState) {
+        if ui.button("⏱ Calibrate Time Here").clicked() {
+            let raw_time = local_time(self.timestamp_ns);
+            let display_time =
+                raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
+            *file_state
+                .calibration
+                .lock()
+                .expect("calibration lock poisoned") = Some((
+                raw_time,
+                crate::filetype::CalibrationWindow::new(
+                    display_time,
+                    false,
+                    Some(display_time),
+                    raw_time,
+                ),
+            ));
+            ui.close();
+        }
+    }
+}
+
+// ============================================================================
+// JournalFileType (InputFileType + TextFileType)
+// ============================================================================
+
+/// Output format of `journalctl` being read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// `-o export`: `KEY=VALUE` lines, entries separated by an empty line
+    Export,
+    /// `-o json`: one object per line
+    Json,
+}
+
+/// Fields of an export entry and the number of its first line
+type ExportEntry = (Vec<(String, String)>, usize);
+
+/// Copies everything read from `inner` into `copy`
+struct Tee<R> {
+    inner: R,
+    copy: File,
+}
+
+impl<R: Read> Read for Tee<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.copy.write_all(&buf[..n])?;
+        Ok(n)
+    }
+}
+
+/// Stateful reader for `journalctl` output, from a file or a running
+/// `journalctl -f`.
+pub struct JournalFileType {
+    reader: BufReader<LogReader>,
+    format: Format,
+    /// The followed `journalctl`, killed when reading stops
+    child: Option<Child>,
+    line_number: usize,
+    bytes_read: u64,
+}
+
+impl JournalFileType {
+    /// Follow the system journal live.
+    ///
+    /// Spawns `journalctl -f -o export`, starting with the latest
+    /// [`FOLLOW_BACKLOG`] entries, and copies its output to `path` so the
+    /// source can be reopened and saved like a file. Reading blocks until new
+    /// entries arrive and ends when `journalctl` exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be created or `journalctl` cannot be
+    /// started.
+    pub fn follow(
+        path: &Path,
+        _file_state: std::sync::Arc<JournalFileState>,
+    ) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+        let copy =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut child = Command::new("journalctl")
+            .args(["--follow", "--output=export"])
+            .arg(format!("--lines={FOLLOW_BACKLOG}"))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start journalctl")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("journalctl has no output pipe")?;
+        Ok(Self {
+            reader: BufReader::new(LogReader::plain(Box::new(Tee {
+                inner: stdout,
+                copy,
+            }))),
+            format: Format::Export,
+            child: Some(child),
+            line_number: 0,
+            bytes_read: 0,
+        })
+    }
+
+    /// File to capture a live journal in, named after the current time
+    #[must_use]
+    pub fn capture_path() -> Option<PathBuf> {
+        let dir = dirs::cache_dir()?.join("logcrab").join("journal");
+        std::fs::create_dir_all(&dir).ok()?;
+        Some(dir.join(format!(
+            "journal-{}.export",
+            Local::now().format("%Y%m%d-%H%M%S")
+        )))
+    }
+
+    /// Read one line including its `\n`; `None` at the end of input
+    fn read_line(&mut self, buf: &mut Vec<u8>) -> std::io::Result<Option<()>> {
+        buf.clear();
+        let n = self.reader.read_until(b'\n', buf)?;
+        if n == 0 {
+            return Ok(None);
+        }
+        self.bytes_read += n as u64;
+        self.line_number += 1;
+        Ok(Some(()))
+    }
+
+    /// Fields of the next export entry with the number of its first line;
+    /// `None` at the end of input
+    fn read_export_entry(&mut self) -> std::io::Result<Option<ExportEntry>> {
+        let mut fields = Vec::new();
+        let mut first_line = 0;
+        let mut buf = Vec::new();
+        while self.read_line(&mut buf)?.is_some() {
+            let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+            if line.is_empty() {
+                if fields.is_empty() {
+                    continue;
+                }
+                return Ok(Some((fields, first_line)));
+            }
+            if fields.is_empty() {
+                first_line = self.line_number;
+            }
+            if let Some(eq) = line.iter().position(|&b| b == b'=') {
+                fields.push((
+                    String::from_utf8_lossy(&line[..eq]).into_owned(),
+                    String::from_utf8_lossy(&line[eq + 1..]).into_owned(),
+                ));
+            } else {
+                // Binary field: name, little-endian length, data, newline
+                let key = String::from_utf8_lossy(line).into_owned();
+                let mut len = [0u8; 8];
+                self.reader.read_exact(&mut len)?;
+                let len =
+                    usize::try_from(u64::from_le_bytes(len)).map_err(std::io::Error::other)?;
+                let mut data = vec![0u8; len + 1];
+                self.reader.read_exact(&mut data)?;
+                data.pop();
+                self.bytes_read += 8 + len as u64 + 1;
+                // The data's lines and the terminating newline
+                self.line_number += data.split(|&b| b == b'\n').count();
+                fields.push((key, String::from_utf8_lossy(&data).into_owned()));
+            }
+        }
+        Ok((!fields.is_empty()).then_some((fields, first_line)))
+    }
+
+    /// The next entry; `None` at the end of input
+    fn read_entry(&mut self) -> std::io::Result<Option<JournalLogLine>> {
+        loop {
+            let line = match self.format {
+                Format::Export => {
+                    let Some((fields, line_number)) = self.read_export_entry()? else {
+                        return Ok(None);
+                    };
+                    JournalLogLine::from_fields(fields, line_number)
+                }
+                Format::Json => {
+                    let mut buf = Vec::new();
+                    if self.read_line(&mut buf)?.is_none() {
+                        return Ok(None);
+                    }
+                    JournalLogLine::from_json(
+                        String::from_utf8_lossy(&buf).trim(),
+                        self.line_number,
+                    )
+                }
+            };
+            // Entries without a timestamp cannot be placed; skip them
+            if line.is_some() {
+                return Ok(line);
+            }
+        }
+    }
+}
+
+impl Drop for JournalFileType {
+    fn drop(&mut self) {
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl InputFileType for JournalFileType {
+    type LineType = JournalLogLine;
+
+    const DISPLAY_NAME: &'static str = "systemd Journal Export";
+    const FILE_EXTENSIONS: &'static [&'static str] = &["export", "json"];
+
+    fn open(
+        path: &Path,
+        _config: (),
+        _file_state: std::sync::Arc<JournalFileState>,
+    ) -> anyhow::Result<Self> {
+        use anyhow::Context as _;
+        let mut reader = BufReader::new(LogReader::open(path)?);
+        let starts_with_brace = reader
+            .fill_buf()
+            .context("Failed to read journal")?
+            .iter()
+            .find(|b| !b.is_ascii_whitespace())
+            == Some(&b'{');
+        Ok(Self {
+            reader,
+            format: if starts_with_brace {
+                Format::Json
+            } else {
+                Format::Export
+            },
+            child: None,
+            line_number: 0,
+            bytes_read: 0,
+        })
+    }
+
+    fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
+        let mut result = Vec::with_capacity(lines_to_read.min(4096));
+        while result.len() < lines_to_read {
+            // A followed journal hands out what has arrived instead of
+            // waiting for a full chunk
+            if self.child.is_some() && !result.is_empty() && self.reader.buffer().is_empty() {
+                break;
+            }
+            match self.read_entry() {
+                Ok(Some(line)) => result.push(line),
+                Ok(None) => break,
+                Err(e) => return Err(anyhow::anyhow!("Read error: {e}")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn bytes_consumed(&self) -> u64 {
+        self.reader.get_ref().disk_position(self.bytes_read)
+    }
+}
+
+impl TextFileType for JournalFileType {
+    /// Returns `true` when the file starts like `journalctl -o export` or
+    /// `journalctl -o json` output.
+    fn looks_like(file: &mut dyn std::io::Read) -> bool {
+        let mut buf = [0u8; 4096];
+        let n = file.read(&mut buf).unwrap_or(0);
+        let sample = String::from_utf8_lossy(&buf[..n]);
+        let Some(first) = sample.lines().find(|l| !l.trim().is_empty()) else {
+            return false;
+        };
+        first.starts_with("__CURSOR=")
+            || first.starts_with("__REALTIME_TIMESTAMP=")
+            || (first.trim_start().starts_with('{') && first.contains("\"__REALTIME_TIMESTAMP\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn reader(text: &[u8], format: Format) -> JournalFileType {
+        JournalFileType {
+            reader: BufReader::new(LogReader::plain(Box::new(Cursor::new(text.to_vec())))),
+            format,
+            child: None,
+            line_number: 0,
+            bytes_read: 0,
+        }
+    }
+
+    #[test]
+    fn test_export_entries() {
+        let mut export = b"__CURSOR=s=1\n__REALTIME_TIMESTAMP=1700000000123456\n\
+            PRIORITY=3\n_SYSTEMD_UNIT=nginx.service\nSYSLOG_IDENTIFIER=nginx\n_PID=42\n\
+            _HOSTNAME=web01\nMESSAGE=bind() failed\n\n\
+            __REALTIME_TIMESTAMP=1700000001000000\nMESSAGE\n"
+            .to_vec();
+        export.extend_from_slice(&7u64.to_le_bytes());
+        export.extend_from_slice(b"two\nrow\n\n");
+
+        let lines = reader(&export, Format::Export).read(10).expect("read");
+        assert_eq!(lines.len(), 2);
+        let first = &lines[0];
+        assert_eq!(first.timestamp_ns, 1_700_000_000_123_456_000);
+        assert_eq!(first.priority, Some(Severity::Error));
+        assert_eq!(first.message(), "nginx[42]: bind() failed");
+        assert_eq!(first.line_number, 1);
+        assert_eq!(
+            first.facets(),
+            vec![
+                ("Priority", "err".to_string()),
+                ("Unit", "nginx.service".to_string()),
+                ("Identifier", "nginx".to_string()),
+                ("Host", "web01".to_string()),
+            ]
+        );
+        assert!(!first.fields().iter().any(|(key, _)| key.starts_with("__")));
+        // Binary field data may hold newlines
+        assert_eq!(lines[1].message(), "two\nrow");
+        assert_eq!(lines[1].line_number, 10);
+    }
+
+    #[test]
+    fn test_json_entries() {
+        let json = br#"{"__CURSOR":"s=1","__REALTIME_TIMESTAMP":"1700000000000000","PRIORITY":"6","UNIT":"sshd.service","MESSAGE":[104,105]}
+{"__REALTIME_TIMESTAMP":"1700000002000000","MESSAGE":"plain","TAG":["a","b"]}
+"#;
+        let lines = reader(json, Format::Json).read(10).expect("read");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].message(), "hi");
+        assert_eq!(lines[0].priority, Some(Severity::Info));
+        assert_eq!(
+            lines[0].first_field(FACET_FIELDS[0].1),
+            Some("sshd.service")
+        );
+        assert_eq!(lines[1].field("TAG"), Some("a\nb"));
+        assert_eq!(lines[1].line_number, 2);
+    }
+
+    #[test]
+    fn test_looks_like() {
+        assert!(JournalFileType::looks_like(&mut Cursor::new(
+            "__CURSOR=s=1\n__REALTIME_TIMESTAMP=1\n"
+        )));
+        assert!(JournalFileType::looks_like(&mut Cursor::new(
+            "{\"__CURSOR\":\"s=1\",\"__REALTIME_TIMESTAMP\":\"1\"}\n"
+        )));
+        assert!(!JournalFileType::looks_like(&mut Cursor::new(
+            "{\"time\":\"2025-01-01T00:00:00Z\",\"msg\":\"hi\"}\n"
+        )));
+    }
+}
@@ -0,0 +1,460 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+use chrono::{DateTime, Local};
+use egui::Ui;
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::core::decompress::LogReader;
+use crate::filetype::{local_time, InputFileType, LineType, TextFileType, NANOS_PER_MS};
+
+/// Lowercased keys recognised as the timestamp, level and message of a record
+const TIMESTAMP_KEYS: &[&str] = &[
+    "timestamp",
+    "@timestamp",
+    "time",
+    "ts",
+    "datetime",
+    "date",
+    "t",
+];
+const LEVEL_KEYS: &[&str] = &[
+    "level",
+    "severity",
+    "loglevel",
+    "log_level",
+    "levelname",
+    "lvl",
+];
+const MESSAGE_KEYS: &[&str] = &["message", "msg", "text", "log", "event", "body"];
+
+// ============================================================================
+// JsonRecord
+// ============================================================================
+
+/// Top-level members of one JSON object, in the order they appear in the line.
+///
+/// `serde_json` sorts object keys; the record keeps the file's order so the
+/// fields and the pretty-printed object read like the original.
+struct JsonRecord(Vec<(String, Value)>);
+
+impl<'de> serde::Deserialize<'de> for JsonRecord {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct RecordVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for RecordVisitor {
+            type Value = JsonRecord;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<JsonRecord, A::Error> {
+                let mut members = Vec::with_capacity(map.size_hint().unwrap_or(8));
+                while let Some(member) = map.next_entry()? {
+                    members.push(member);
+                }
+                Ok(JsonRecord(members))
+            }
+        }
+
+        d.deserialize_map(RecordVisitor)
+    }
+}
+
+impl serde::Serialize for JsonRecord {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = s.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl JsonRecord {
+    fn parse(raw: &str) -> Option<Self> {
+        serde_json::from_str(raw).ok()
+    }
+
+    /// Position of the first member whose key is one of `names`
+    fn find(&self, names: &[&str]) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|(key, _)| names.contains(&key.to_lowercase().as_str()))
+    }
+
+    /// Positions of the timestamp, level and message members
+    fn roles(&self) -> [Option<usize>; 3] {
+        [
+            self.find(TIMESTAMP_KEYS),
+            self.find(LEVEL_KEYS),
+            self.find(MESSAGE_KEYS),
+        ]
+    }
+
+    /// All members except the timestamp and message, nested objects
+    /// flattened to dotted keys (`http.status`)
+    fn fields(&self) -> Vec<(String, String)> {
+        let [timestamp, _, message] = self.roles();
+        self.members_except(&[timestamp, message])
+    }
+
+    /// Flattened members, leaving out those at the `skipped` positions
+    fn members_except(&self, skipped: &[Option<usize>]) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if !skipped.contains(&Some(i)) {
+                flatten(key, value, &mut fields);
+            }
+        }
+        fields
+    }
+}
+
+/// Append `value` under `key` to `fields`, descending into objects
+fn flatten(key: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    match value.as_object() {
+        Some(members) if !members.is_empty() => {
+            for (member, value) in members {
+                flatten(&format!("{key}.{member}"), value, fields);
+            }
+        }
+        _ => fields.push((key.to_string(), value_text(value))),
+    }
+}
+
+/// A JSON value as plain text: strings without quotes, everything else as JSON
+fn value_text(value: &Value) -> String {
+    value
+        .as_str()
+        .map_or_else(|| value.to_string(), str::to_string)
+}
+
+/// Parse a timestamp member: a date string or Unix epoch seconds,
+/// milliseconds or microseconds, as epoch nanoseconds.
+fn parse_timestamp_ns(value: &Value) -> Option<i64> {
+    crate::filetype::csv::parse_timestamp_ns(&value_text(value))
+}
+
+// ============================================================================
+// JsonLogLine
+// ============================================================================
+
+/// One record of a JSON-lines log (one object per line, as written by most
+/// structured loggers)
+#[derive(Debug, Clone)]
+pub struct JsonLogLine {
+    raw_line: String,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    message_text: String,
+    level: Option<String>,
+    pub line_number: usize,
+}
+
+impl JsonLogLine {
+    /// Parse one line. Lines that are not a JSON object are kept as their
+    /// raw text with `fallback_ns`.
+    fn parse(raw_line: String, line_number: usize, fallback_ns: i64) -> Self {
+        let Some(record) = JsonRecord::parse(&raw_line) else {
+            return Self {
+                message_text: raw_line.clone(),
+                raw_line,
+                timestamp_ns: fallback_ns,
+                level: None,
+                line_number,
+            };
+        };
+        let [timestamp, level, message] = record.roles();
+        let member = |i: Option<usize>| i.map(|i| &record.0[i].1);
+        let message_text = member(message).map_or_else(
+            || {
+                record
+                    .members_except(&[timestamp, level, message])
+                    .into_iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            },
+            value_text,
+        );
+        Self {
+            timestamp_ns: member(timestamp)
+                .and_then(parse_timestamp_ns)
+                .unwrap_or(fallback_ns),
+            level: member(level).map(value_text).filter(|l| !l.is_empty()),
+            message_text,
+            raw_line,
+            line_number,
+        }
+    }
+}
+
+// ============================================================================
+// JsonFileState
+// ============================================================================
+
+pub type JsonFileState = crate::filetype::SimpleFileState;
+
+// ============================================================================
+// LineType implementation
+// ============================================================================
+
+impl LineType for JsonLogLine {
+    type Config = ();
+    type FileState = JsonFileState;
+
+    fn file_state_from_v2(time_offset_ms: i64) -> JsonFileState {
+        let s = JsonFileState::default();
+        s.set_time_offset_ms(time_offset_ms);
+        s
+    }
+
+    fn timestamp(&self, config: &(), file_state: &JsonFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &JsonFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
+    }
+
+    fn message(&self) -> String {
+        self.message_text.clone()
+    }
+
+    fn display_message(&self, _config: &(), file_state: &JsonFileState) -> String {
+        let offset_ms = file_state.time_offset_ms();
+        if offset_ms != 0 {
+            format!(
+                "[{}] {}",
+                crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
+                self.message_text
+            )
+        } else {
+            self.message_text.clone()
+        }
+    }
+
+    fn raw(&self) -> String {
+        self.raw_line.clone()
+    }
+
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        JsonRecord::parse(&self.raw_line)
+            .map(|record| record.fields())
+            .unwrap_or_default()
+    }
+
+    fn pretty_raw(&self) -> Option<String> {
+        let record = JsonRecord::parse(&self.raw_line)?;
+        serde_json::to_string_pretty(&record).ok()
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        self.level
+            .as_ref()
+            .map(|level| vec![("Level", level.clone())])
+            .unwrap_or_default()
+    }
+
+    fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &JsonFileState) {
+        if ui.button("⏱ Calibrate Time Here").clicked() {
+            let raw_time = local_time(self.timestamp_ns);
+            let display_time =
+                raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
+            *file_state
+                .calibration
+                .lock()
+                .expect("calibration lock poisoned") = Some((
+                raw_time,
+                crate::filetype::CalibrationWindow::new(
+                    display_time,
+                    false,
+                    Some(display_time),
+                    raw_time,
+                ),
+            ));
+            ui.close();
+        }
+    }
+}
+
+// ============================================================================
+// JsonFileType (InputFileType + TextFileType)
+// ============================================================================
+
+/// Stateful reader for JSON-lines logs.
+///
+/// The timestamp, level and message are taken from the first member with a
+/// well-known name (`time`, `level`, `msg`, ...); all other members become
+/// fields of the line. Records without a message show their fields instead.
+/// Lines without a parseable timestamp keep the previous line's timestamp.
+pub struct JsonFileType {
+    reader: BufReader<LogReader>,
+    line_number: usize,
+    bytes_read: u64,
+    last_timestamp_ns: i64,
+}
+
+impl InputFileType for JsonFileType {
+    type LineType = JsonLogLine;
+
+    const DISPLAY_NAME: &'static str = "JSON Lines Log";
+    const FILE_EXTENSIONS: &'static [&'static str] = &["jsonl", "ndjson", "json", "log"];
+
+    fn open(
+        path: &Path,
+        _config: (),
+        _file_state: std::sync::Arc<JsonFileState>,
+    ) -> anyhow::Result<Self> {
+        let file = LogReader::open(path)?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            line_number: 0,
+            bytes_read: 0,
+            last_timestamp_ns: 0,
+        })
+    }
+
+    fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
+        let mut result = Vec::with_capacity(lines_to_read);
+        let mut buf = Vec::new();
+        while result.len() < lines_to_read {
+            buf.clear();
+            match self.reader.read_until(b'\n', &mut buf) {
+                Ok(0) => break, // EOF
+                Ok(n) => {
+                    self.bytes_read += n as u64;
+                    self.line_number += 1;
+                    let line_str = String::from_utf8_lossy(&buf);
+                    let raw = line_str.trim_end_matches(['\n', '\r']);
+                    if raw.trim().is_empty() {
+                        continue;
+                    }
+                    let line = JsonLogLine::parse(
+                        raw.to_string(),
+                        self.line_number,
+                        self.last_timestamp_ns,
+                    );
+                    self.last_timestamp_ns = line.timestamp_ns;
+                    result.push(line);
+                }
+                Err(e) => return Err(anyhow::anyhow!("Read error: {e}")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn bytes_consumed(&self) -> u64 {
+        self.reader.get_ref().disk_position(self.bytes_read)
+    }
+}
+
+impl TextFileType for JsonFileType {
+    /// Returns `true` when every complete line among the first few is a JSON
+    /// object with a timestamp or message member.
+    fn looks_like(file: &mut dyn std::io::Read) -> bool {
+        let mut buf = [0u8; 4096];
+        let n = file.read(&mut buf).unwrap_or(0);
+        let sample = String::from_utf8_lossy(&buf[..n]);
+
+        // Drop a trailing partial line when the sample filled the buffer.
+        let complete = if n == buf.len() {
+            sample.rsplit_once('\n').map_or("", |(head, _)| head)
+        } else {
+            &sample
+        };
+        let lines: Vec<&str> = complete
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .take(5)
+            .collect();
+        !lines.is_empty()
+            && lines.iter().all(|l| {
+                JsonRecord::parse(l).is_some_and(|record| {
+                    let [timestamp, _, message] = record.roles();
+                    timestamp.is_some() || message.is_some()
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_JSONL: &str = r#"{"time":"2025-03-01T10:00:00.000Z","level":"info","msg":"request served","http":{"path":"/api","status":200},"took_ms":12}
+{"time":"2025-03-01T10:00:01.500Z","level":"error","msg":"upstream timeout","http":{"path":"/api","status":504}}
+not json at all
+{"ts":1740823202,"component":"db","pool":{}}
+"#;
+
+    fn read_all(contents: &str) -> Vec<JsonLogLine> {
+        use std::io::Write;
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        tmp.write_all(contents.as_bytes()).expect("write");
+        let mut ft = JsonFileType::open(tmp.path(), (), std::sync::Arc::default()).expect("open");
+        ft.read(100).expect("read")
+    }
+
+    #[test]
+    fn test_looks_like() {
+        let mut cursor =
+            std::io::Cursor::new(SAMPLE_JSONL.lines().take(2).collect::<Vec<_>>().join("\n"));
+        assert!(JsonFileType::looks_like(&mut cursor));
+        let mut plain = std::io::Cursor::new("2025-01-01 10:00:00 INFO {\"a\":1}\n");
+        assert!(!JsonFileType::looks_like(&mut plain));
+        let mut config = std::io::Cursor::new("{\"name\":\"x\",\"version\":3}\n");
+        assert!(!JsonFileType::looks_like(&mut config));
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let lines = read_all(SAMPLE_JSONL);
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(lines[0].message_text, "request served");
+        assert_eq!(lines[0].level.as_deref(), Some("info"));
+        assert_eq!(
+            lines[1].timestamp_ns - lines[0].timestamp_ns,
+            1500 * NANOS_PER_MS
+        );
+        assert_eq!(
+            lines[1].fields(),
+            [
+                ("level".to_string(), "error".to_string()),
+                ("http.path".to_string(), "/api".to_string()),
+                ("http.status".to_string(), "504".to_string()),
+            ]
+        );
+
+        // Unparsed lines keep the previous timestamp
+        assert_eq!(lines[2].message_text, "not json at all");
+        assert_eq!(lines[2].timestamp_ns, lines[1].timestamp_ns);
+        assert!(lines[2].fields().is_empty());
+
+        // Without a message, the remaining members are shown
+        assert_eq!(lines[3].message_text, "component=db pool={}");
+        assert_eq!(lines[3].timestamp_ns, 1_740_823_202 * 1_000_000_000);
+    }
+
+    #[test]
+    fn test_pretty_raw_keeps_member_order() {
+        let lines = read_all(SAMPLE_JSONL);
+        let pretty = lines[1].pretty_raw().expect("json line");
+        assert!(pretty.starts_with("{\n  \"time\": \"2025-03-01T10:00:01.500Z\",\n  \"level\""));
+        assert!(pretty.contains("\"status\": 504"));
+        assert_eq!(lines[2].pretty_raw(), None);
+    }
+}
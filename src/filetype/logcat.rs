@@ -4,12 +4,15 @@
 use chrono::{DateTime, Datelike, Local};
 use egui::Ui;
 use fancy_regex::Regex;
-use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
-use crate::filetype::{InputFileType, LineType, TextFileType};
+use crate::core::decompress::LogReader;
+use crate::core::text_arena::{ArenaStr, TextArena};
+use crate::filetype::{
+    local_naive_ns, local_time, InputFileType, LineType, TextFileType, NANOS_PER_MS,
+};
 
 // ============================================================================
 // LogcatLogLine
@@ -18,43 +21,66 @@ use crate::filetype::{InputFileType, LineType, TextFileType};
 /// Android Logcat format: MM-DD HH:MM:SS.mmm PID TID LEVEL TAG: message
 #[derive(Debug, Clone)]
 pub struct LogcatLogLine {
-    /// Original raw line from file
-    raw_line: String,
-    /// Parsed timestamp
-    pub timestamp: DateTime<Local>,
-    /// Full message portion (everything after timestamp: PID TID LEVEL TAG: text).
-    /// Used for UI display via [`LineType::display_message`].
-    message_text: String,
-    /// Canonical "TAG: text" portion, stripped of PID/TID/LEVEL prefix.
-    /// Falls back to `message_text` when the level marker cannot be located.
-    /// Used for anomaly scoring, export, and scoring frames via [`LineType::message`].
-    tag_message: String,
+    /// Original raw line from file, usually shared with the rest of its chunk
+    raw_line: ArenaStr,
+    /// Parsed timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    /// Start of the message portion within `raw_line`; see [`Self::message_text`]
+    message_start: u32,
+    /// Start of the "TAG: text" portion within `raw_line`; see [`Self::tag_message`]
+    tag_start: u32,
     /// Original line number in source file
     pub line_number: usize,
 }
 
+/// Where the parts of a logcat line start, as byte offsets into its raw text.
+/// Both parts run to the end of the line.
+#[derive(Clone, Copy)]
+struct LogcatParts {
+    timestamp_ns: i64,
+    message_start: u32,
+    tag_start: u32,
+}
+
 impl LogcatLogLine {
-    #[must_use]
-    pub fn new(
-        raw_line: String,
-        timestamp: DateTime<Local>,
-        message_text: String,
-        line_number: usize,
-    ) -> Self {
-        let tag_message = extract_tag_message(&message_text)
-            .unwrap_or_else(|| message_text.clone());
+    const fn from_parts(raw_line: ArenaStr, parts: LogcatParts, line_number: usize) -> Self {
         Self {
             raw_line,
-            timestamp,
-            message_text,
-            tag_message,
+            timestamp_ns: parts.timestamp_ns,
+            message_start: parts.message_start,
+            tag_start: parts.tag_start,
             line_number,
         }
     }
 
-    /// Full message portion including PID/TID/LEVEL prefix.
+    /// Full message portion (everything after timestamp: PID TID LEVEL TAG: text).
+    /// Used for UI display via [`LineType::display_message`].
+    #[must_use]
     pub fn message_text(&self) -> &str {
-        &self.message_text
+        &self.raw_line[self.message_start as usize..]
+    }
+
+    /// Canonical "TAG: text" portion, stripped of PID/TID/LEVEL prefix.
+    /// Falls back to [`Self::message_text`] when the level marker cannot be located.
+    /// Used for anomaly scoring, export, and scoring frames via [`LineType::message`].
+    #[must_use]
+    pub fn tag_message(&self) -> &str {
+        &self.raw_line[self.tag_start as usize..]
+    }
+
+    /// Single-letter log level (`V`/`D`/`I`/`W`/`E`/`F`/`S`), if present.
+    #[must_use]
+    pub fn level(&self) -> Option<&str> {
+        let tokens: Vec<&str> = self.message_text().split_whitespace().take(5).collect();
+        level_token_position(&tokens).map(|pos| tokens[pos])
+    }
+
+    /// Log tag (the part of `tag_message` before the first `:`), if present.
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.tag_message()
+            .split_once(':')
+            .map(|(tag, _)| tag.trim())
     }
 }
 
@@ -80,12 +106,16 @@ impl LineType for LogcatLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &(), file_state: &LogcatFileState) -> DateTime<Local> {
-        self.timestamp + chrono::Duration::milliseconds(file_state.time_offset_ms())
+    fn timestamp(&self, config: &(), file_state: &LogcatFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &LogcatFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
     }
 
     fn message(&self) -> String {
-        self.tag_message.clone()
+        self.tag_message().to_string()
     }
 
     fn display_message(&self, _config: &(), file_state: &LogcatFileState) -> String {
@@ -94,24 +124,61 @@ impl LineType for LogcatLogLine {
             format!(
                 "[{}] {}",
                 crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
-                self.message_text
+                self.message_text()
             )
         } else {
-            self.message_text.clone()
+            self.message_text().to_string()
         }
     }
 
     fn raw(&self) -> String {
-        self.raw_line.clone()
+        self.raw_line.to_string()
     }
 
     fn line_number(&self) -> usize {
         self.line_number
     }
 
+    fn fields(&self) -> Vec<(String, String)> {
+        let tokens: Vec<&str> = self.message_text().split_whitespace().collect();
+        let Some(level_pos) = level_token_position(&tokens) else {
+            return Vec::new();
+        };
+        let names: &[&str] = match level_pos {
+            1 => &["PID"],
+            2 => &["PID", "TID"],
+            3 => &["User", "PID", "TID"],
+            _ => return Vec::new(),
+        };
+        let mut fields: Vec<(String, String)> = names
+            .iter()
+            .zip(&tokens)
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect();
+        fields.push(("Level".to_string(), tokens[level_pos].to_string()));
+        if let Some(tag) = self.tag() {
+            fields.push(("Tag".to_string(), tag.to_string()));
+        }
+        if let Some((_, text)) = self.tag_message().split_once(':') {
+            fields.push(("Text".to_string(), text.trim_start().to_string()));
+        }
+        fields
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        let mut facets = Vec::new();
+        if let Some(level) = self.level() {
+            facets.push(("Level", level.to_string()));
+        }
+        if let Some(tag) = self.tag() {
+            facets.push(("Tag", tag.to_string()));
+        }
+        facets
+    }
+
     fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &LogcatFileState) {
         if ui.button("⏱ Calibrate Time Here").clicked() {
-            let raw_time = self.timestamp;
+            let raw_time = local_time(self.timestamp_ns);
             let display_time =
                 raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
             *file_state
@@ -140,7 +207,7 @@ impl LineType for LogcatLogLine {
 /// Must be registered **after** [`super::bugreport::BugreportFileType`] — bugreports also match
 /// logcat lines, so bugreport wins when checked first.
 pub struct LogcatFileType {
-    reader: BufReader<File>,
+    reader: BufReader<LogReader>,
     year: i32,
     line_number: usize,
     bytes_read: u64,
@@ -149,6 +216,7 @@ pub struct LogcatFileType {
 impl InputFileType for LogcatFileType {
     type LineType = LogcatLogLine;
 
+    const DISPLAY_NAME: &'static str = "Android Logcat";
     const FILE_EXTENSIONS: &'static [&'static str] = &["txt", "log"];
 
     /// Version 2: `message()` returns `"TAG: text"` instead of the full
@@ -163,10 +231,8 @@ impl InputFileType for LogcatFileType {
         _config: (),
         _file_state: std::sync::Arc<LogcatFileState>,
     ) -> anyhow::Result<Self> {
-        use anyhow::Context as _;
         let year = chrono::Local::now().year();
-        let file =
-            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let file = LogReader::open(path)?;
         Ok(Self {
             reader: BufReader::new(file),
             year,
@@ -175,10 +241,13 @@ impl InputFileType for LogcatFileType {
         })
     }
 
+    /// The raw text of all lines read in one call shares a single [`TextArena`]
+    /// buffer, so a chunk costs a handful of allocations instead of several per line.
     fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
-        let mut result = Vec::with_capacity(lines_to_read);
+        let mut parsed = Vec::with_capacity(lines_to_read);
+        let mut arena = TextArena::default();
         let mut buf = Vec::new();
-        while result.len() < lines_to_read {
+        while parsed.len() < lines_to_read {
             buf.clear();
             match self.reader.read_until(b'\n', &mut buf) {
                 Ok(0) => break,
@@ -186,7 +255,7 @@ impl InputFileType for LogcatFileType {
                     self.bytes_read += n as u64;
                     self.line_number += 1;
                     let line_str = String::from_utf8_lossy(&buf);
-                    let raw = line_str.trim_end_matches(['\n', '\r']).to_string();
+                    let raw = line_str.trim_end_matches(['\n', '\r']);
                     if std::str::from_utf8(&buf).is_err() {
                         tracing::warn!(
                             "Invalid UTF-8 at line {}:{}; replacing broken bytes with U+FFFD",
@@ -194,8 +263,8 @@ impl InputFileType for LogcatFileType {
                             raw
                         );
                     }
-                    if let Some(line) = parse_logcat_line(raw, self.line_number, self.year) {
-                        result.push(line);
+                    if let Some(parts) = parse_logcat_parts(raw, self.year) {
+                        parsed.push((arena.push(raw), parts, self.line_number));
                     } else {
                         tracing::warn!(
                             "Failed to parse line {}: '{}'",
@@ -207,11 +276,21 @@ impl InputFileType for LogcatFileType {
                 Err(e) => return Err(anyhow::anyhow!("Read error: {e}")),
             }
         }
-        Ok(result)
+        let text = arena.freeze();
+        Ok(parsed
+            .into_iter()
+            .map(|(range, parts, line_number)| {
+                LogcatLogLine::from_parts(
+                    ArenaStr::new(Arc::clone(&text), range),
+                    parts,
+                    line_number,
+                )
+            })
+            .collect())
     }
 
     fn bytes_consumed(&self) -> u64 {
-        self.bytes_read
+        self.reader.get_ref().disk_position(self.bytes_read)
     }
 }
 
@@ -248,6 +327,15 @@ pub fn is_logcat_line(line: &str) -> bool {
     LOGCAT_TIMESTAMP.is_match(line).unwrap_or(false)
 }
 
+/// Position of the single-letter level token in a whitespace-split
+/// `PID TID LEVEL TAG: text` prefix. The level must follow at least one token.
+fn level_token_position(tokens: &[&str]) -> Option<usize> {
+    tokens.iter().skip(1).position(|t| {
+        t.len() == 1 && matches!(t.as_bytes()[0], b'V' | b'D' | b'I' | b'W' | b'E' | b'F' | b'S')
+    })
+    .map(|pos| pos + 1)
+}
+
 /// Extract the `TAG: message` portion from the part of the logcat line that
 /// follows the timestamp (i.e. from `message_text`).
 ///
@@ -259,7 +347,13 @@ pub fn is_logcat_line(line: &str) -> bool {
 ///
 /// Returns `None` when no level marker is found; callers fall back to the full
 /// `message_text` in that case.
+#[must_use]
 pub fn extract_tag_message(text: &str) -> Option<String> {
+    tag_message_offset(text).map(|start| text[start..].to_string())
+}
+
+/// Byte offset of the `TAG: message` portion within `text`; see [`extract_tag_message`].
+fn tag_message_offset(text: &str) -> Option<usize> {
     let bytes = text.as_bytes();
     let n = bytes.len();
     let mut i = 0;
@@ -295,7 +389,7 @@ pub fn extract_tag_message(text: &str) -> Option<String> {
                 i += 1;
             }
             if i < n {
-                return Some(text[i..].to_string());
+                return Some(i);
             }
         }
 
@@ -311,28 +405,41 @@ pub fn extract_tag_message(text: &str) -> Option<String> {
 }
 
 /// Parse a single logcat line and return the concrete `LogcatLogLine`.
+#[must_use]
 pub fn parse_logcat_line(raw: String, line_number: usize, year: i32) -> Option<LogcatLogLine> {
-    if let Ok(Some(caps)) = LOGCAT_TIMESTAMP.captures(&raw) {
-        let message = caps[2].to_string();
-        return parse_logcat_timestamp(&caps[1], year)
-            .map(|ts| LogcatLogLine::new(raw, ts, message, line_number));
-    }
-    None
+    let parts = parse_logcat_parts(&raw, year)?;
+    Some(LogcatLogLine::from_parts(
+        ArenaStr::from(raw),
+        parts,
+        line_number,
+    ))
+}
+
+/// Parse the timestamp of a logcat line and locate its message parts, without copying text.
+fn parse_logcat_parts(raw: &str, year: i32) -> Option<LogcatParts> {
+    let caps = LOGCAT_TIMESTAMP.captures(raw).ok()??;
+    let timestamp_ns = parse_logcat_timestamp(&caps[1], year)?;
+    let message = caps.get(2)?;
+    let message_start = message.start();
+    let tag_start = message_start + tag_message_offset(message.as_str()).unwrap_or(0);
+    Some(LogcatParts {
+        timestamp_ns,
+        message_start: u32::try_from(message_start).ok()?,
+        tag_start: u32::try_from(tag_start).ok()?,
+    })
 }
 
-fn parse_logcat_timestamp(s: &str, year: i32) -> Option<DateTime<Local>> {
+fn parse_logcat_timestamp(s: &str, year: i32) -> Option<i64> {
     let timestamp_str = format!("{year}-{s}");
-    if let Ok(naive) =
-        chrono::NaiveDateTime::parse_from_str(&timestamp_str, "%Y-%m-%d %H:%M:%S%.3f")
-    {
-        return naive.and_local_timezone(Local).single();
-    }
-    None
+    let naive =
+        chrono::NaiveDateTime::parse_from_str(&timestamp_str, "%Y-%m-%d %H:%M:%S%.3f").ok()?;
+    local_naive_ns(&naive)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::filetype::epoch_ns;
     use chrono::Datelike;
 
     #[test]
@@ -342,7 +449,7 @@ mod tests {
         let line = parse_logcat_line(raw, 1, 2024).expect("should parse logcat line");
         // message_text: full portion after timestamp (PID TID L TAG: text)
         assert_eq!(
-            line.message_text,
+            line.message_text(),
             "1234  5678 I ActivityManager: Start proc com.example.app"
         );
         // message() / tag_message: canonical TAG: text only
@@ -352,6 +459,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_threadtime_fields() {
+        let raw = "11-20 14:23:45.123  1234  5678 I ActivityManager: Start proc com.example.app"
+            .to_string();
+        let line = parse_logcat_line(raw, 1, 2024).expect("should parse logcat line");
+        let fields = line.fields();
+        let get = |key: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("PID"), Some("1234"));
+        assert_eq!(get("TID"), Some("5678"));
+        assert_eq!(get("Level"), Some("I"));
+        assert_eq!(get("Tag"), Some("ActivityManager"));
+        assert_eq!(get("Text"), Some("Start proc com.example.app"));
+    }
+
     #[test]
     fn test_threadtime_with_process_name() {
         let raw =
@@ -359,7 +485,7 @@ mod tests {
                 .to_string();
         let line = parse_logcat_line(raw, 1, 2024).expect("should parse logcat line");
         assert_eq!(
-            line.message_text,
+            line.message_text(),
             "root     8     8 I CAM_INFO: CAM-ICP: cam_icp_mgr_process_dbg_buf"
         );
         assert_eq!(
@@ -373,7 +499,7 @@ mod tests {
         // Lines without a recognisable level marker fall back to full message_text.
         let raw = "11-20 14:23:45.123 Some message without tag".to_string();
         let line = parse_logcat_line(raw, 1, 2024).expect("should parse logcat line");
-        assert_eq!(line.message_text, "Some message without tag");
+        assert_eq!(line.message_text(), "Some message without tag");
         assert_eq!(line.message(), "Some message without tag");
     }
 
@@ -381,7 +507,47 @@ mod tests {
     fn test_parse_with_detected_year() {
         let raw = "11-20 14:23:45.123 Test message".to_string();
         let line = parse_logcat_line(raw, 1, 2023).expect("should parse logcat line");
-        assert!(line.timestamp.year() == 2023);
+        assert!(local_time(line.timestamp_ns).year() == 2023);
+    }
+
+    #[test]
+    fn test_timestamp_ns_matches_calibrated_timestamp() {
+        let raw = "11-20 14:23:45.123 Test message".to_string();
+        let line = parse_logcat_line(raw, 1, 2023).expect("should parse logcat line");
+        let file_state = LogcatFileState::default();
+        file_state.set_time_offset_ms(-1_500);
+        assert_eq!(
+            line.timestamp_ns(&(), &file_state),
+            epoch_ns(&line.timestamp(&(), &file_state))
+        );
+        assert_eq!(
+            line.timestamp_ms(&(), &file_state),
+            line.timestamp_ns / NANOS_PER_MS - 1_500
+        );
+    }
+
+    #[test]
+    fn test_read_shares_chunk_text() {
+        use std::io::Write as _;
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmpfile");
+        tmp.write_all(
+            b"11-20 14:23:45.123  1234  5678 I ActivityManager: Start proc\r\n\
+              not a logcat line\n\
+              11-20 14:23:46.000  1234  5678 W Wifi: scan failed\n",
+        )
+        .expect("write");
+        let file_state = Arc::new(LogcatFileState::default());
+        let mut ft = LogcatFileType::open(tmp.path(), (), file_state).expect("open");
+        let lines = ft.read(100).expect("read");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0].raw(),
+            "11-20 14:23:45.123  1234  5678 I ActivityManager: Start proc"
+        );
+        assert_eq!(lines[0].message(), "ActivityManager: Start proc");
+        assert_eq!(lines[1].message_text(), "1234  5678 W Wifi: scan failed");
+        assert_eq!(lines[1].tag(), Some("Wifi"));
+        assert_eq!(lines[1].line_number, 3);
     }
 
     #[test]
@@ -392,6 +558,15 @@ mod tests {
         assert!(!is_logcat_line("just some text"));
     }
 
+    #[test]
+    fn test_bundled_sample_log_is_logcat() {
+        let sample = include_str!("../../assets/sample.log");
+        assert!(LogcatFileType::looks_like(&mut sample.as_bytes()));
+        assert!(sample
+            .lines()
+            .all(|line| parse_logcat_line(line.to_string(), 1, 2026).is_some()));
+    }
+
     #[test]
     fn test_extract_tag_message() {
         assert_eq!(
@@ -1,20 +1,64 @@
 use egui::Ui;
 
+pub mod access_log;
 pub mod btsnoop;
 pub mod bugreport;
 pub mod calibration_window;
+pub mod csv;
 pub mod dlt;
 pub mod dmesg;
 pub mod generic;
+pub mod journal;
+pub mod json;
 pub mod logcat;
 pub mod otel;
 pub mod pcap;
 pub mod registry_macro;
 pub mod simple_file_state;
+pub mod syslog;
+pub mod trace;
 
 pub use calibration_window::CalibrationWindow;
 pub use simple_file_state::SimpleFileState;
 
+// ============================================================================
+// Epoch timestamps
+// ============================================================================
+
+/// Nanoseconds per millisecond, for converting [`LineType::timestamp_ns`]
+pub const NANOS_PER_MS: i64 = 1_000_000;
+
+/// Nanoseconds since the Unix epoch of `time`, the form line types store
+/// their timestamps in. Saturates outside the years 1677 to 2262.
+#[must_use]
+pub fn epoch_ns<Tz: chrono::TimeZone>(time: &chrono::DateTime<Tz>) -> i64 {
+    time.timestamp_nanos_opt().unwrap_or_else(|| {
+        if time.timestamp() < 0 {
+            i64::MIN
+        } else {
+            i64::MAX
+        }
+    })
+}
+
+/// [`epoch_ns`] of a wall-clock time read from a log, taken as local time.
+///
+/// `None` if that time does not exist or is ambiguous locally (around DST
+/// changes).
+#[must_use]
+pub fn local_naive_ns(naive: &chrono::NaiveDateTime) -> Option<i64> {
+    naive
+        .and_local_timezone(chrono::Local)
+        .single()
+        .map(|time| epoch_ns(&time))
+}
+
+/// Local time of a stored epoch timestamp, see [`epoch_ns`]
+#[must_use]
+pub fn local_time(epoch_ns: i64) -> chrono::DateTime<chrono::Local> {
+    chrono::DateTime::from_timestamp_nanos(epoch_ns).with_timezone(&chrono::Local)
+}
+
 // ============================================================================
 // CalibrationState — typed alias used by every FileState
 // ============================================================================
@@ -80,6 +124,29 @@ pub trait LogFileState: Send + Sync {
     fn egui_render_file_state(&self, _ui: &egui::Ui) -> bool {
         false
     }
+
+    /// Whether the file must be parsed again because this state changed in a
+    /// way that affects parsing (e.g. a new CSV column mapping).
+    ///
+    /// Polled once per frame; returns `true` once per request. Default: never.
+    fn take_reparse_request(&self) -> bool {
+        false
+    }
+
+    /// Clock correction the user applied to this source, in milliseconds.
+    ///
+    /// Shown in the status bar so skewed sources stand out. Default: 0.
+    fn clock_offset_ms(&self) -> i64 {
+        0
+    }
+
+    /// Counter that moves whenever display messages may change while the
+    /// lines stay the same (e.g. a decoding was switched on).
+    ///
+    /// Indexes built from display messages are stale once it moved. Default: 0.
+    fn display_generation(&self) -> u64 {
+        0
+    }
 }
 
 /// Blanket impl so that `()` (used as `FileState` by the legacy Mixed source)
@@ -107,6 +174,86 @@ pub trait EguiConfig {
 /// Blanket impl for `()` — no settings to show.
 impl EguiConfig for () {}
 
+// ============================================================================
+// RequestMetrics — per-request numbers fed into anomaly scoring
+// ============================================================================
+
+/// Outcome of a single served request, for formats that log one line per
+/// request (e.g. web server access logs).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RequestMetrics {
+    /// HTTP status code.
+    pub status: u16,
+    /// Time taken to serve the request, in milliseconds, when logged.
+    pub latency_ms: Option<f64>,
+}
+
+// ============================================================================
+// Severity — level stated by the format itself, fed into anomaly scoring
+// ============================================================================
+
+/// Syslog severity levels (RFC 5424), most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Emergency,
+    Alert,
+    Critical,
+    Error,
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+
+impl Severity {
+    /// Severity of a syslog priority's lower three bits
+    #[must_use]
+    pub const fn from_code(code: u8) -> Self {
+        match code & 7 {
+            0 => Self::Emergency,
+            1 => Self::Alert,
+            2 => Self::Critical,
+            3 => Self::Error,
+            4 => Self::Warning,
+            5 => Self::Notice,
+            6 => Self::Info,
+            _ => Self::Debug,
+        }
+    }
+
+    /// Severity a format's level label stands for, e.g. `E`, `err`, `ERROR`
+    /// or `Fatal`; `None` for labels it does not know
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.to_ascii_lowercase().as_str() {
+            "emerg" | "emergency" | "panic" => Some(Self::Emergency),
+            "a" | "alert" => Some(Self::Alert),
+            "c" | "crit" | "critical" | "f" | "fatal" => Some(Self::Critical),
+            "e" | "err" | "error" => Some(Self::Error),
+            "w" | "warn" | "warning" => Some(Self::Warning),
+            "n" | "notice" => Some(Self::Notice),
+            "i" | "info" | "information" => Some(Self::Info),
+            "v" | "verbose" | "d" | "debug" | "t" | "trace" => Some(Self::Debug),
+            _ => None,
+        }
+    }
+
+    /// Keyword as used by syslog tools (`err`, `warning`, ...)
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Emergency => "emerg",
+            Self::Alert => "alert",
+            Self::Critical => "crit",
+            Self::Error => "err",
+            Self::Warning => "warning",
+            Self::Notice => "notice",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        }
+    }
+}
+
 /// Filetype trait infrastructure for logcrab
 pub trait LineType: std::fmt::Debug + Send + Sync {
     /// Per-type global user-controlled settings shared across all sources of this type
@@ -156,6 +303,24 @@ pub trait LineType: std::fmt::Debug + Send + Sync {
         file_state: &Self::FileState,
     ) -> chrono::DateTime<chrono::Local>;
 
+    /// The calibrated timestamp of this line as nanoseconds since the Unix epoch.
+    ///
+    /// Same instant as [`timestamp`](Self::timestamp) at full precision. This
+    /// is the key the store sorts and merges by, so packets captured within
+    /// the same millisecond keep their order. Implementations add their offset
+    /// to the stored epoch value instead of going through `DateTime<Local>`,
+    /// which looks up the local UTC offset on every call.
+    fn timestamp_ns(&self, config: &Self::Config, file_state: &Self::FileState) -> i64 {
+        epoch_ns(&self.timestamp(config, file_state))
+    }
+
+    /// [`timestamp_ns`](Self::timestamp_ns) in whole milliseconds, for
+    /// bucketing and time windows.
+    fn timestamp_ms(&self, config: &Self::Config, file_state: &Self::FileState) -> i64 {
+        self.timestamp_ns(config, file_state)
+            .div_euclid(NANOS_PER_MS)
+    }
+
     /// Get the formatted message (may be constructed lazily).
     ///
     /// Returns the raw log message without any display decorations and without
@@ -185,6 +350,56 @@ pub trait LineType: std::fmt::Debug + Send + Sync {
     /// Get the original line number in the source file
     fn line_number(&self) -> usize;
 
+    /// Parsed key/value fields of this line, in display order.
+    ///
+    /// Shown in the line detail panel, where each value can be copied or used
+    /// as a filter. Structured formats return their decoded header and payload
+    /// fields (e.g. DLT ECU/APID, logcat PID/TAG, TCP seq/ack). The default
+    /// returns nothing, which leaves only the generic timestamp/message rows.
+    fn fields(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// The raw line reformatted for reading, for formats that store a compact
+    /// structure per line (e.g. one JSON object).
+    ///
+    /// Offered in the line detail panel next to the field table. Default: `None`.
+    fn pretty_raw(&self) -> Option<String> {
+        None
+    }
+
+    /// The captured bytes of a binary record (e.g. a network or HCI packet).
+    ///
+    /// Shown as a hex dump in the line detail panel. Default: `None`.
+    fn payload(&self) -> Option<std::sync::Arc<[u8]>> {
+        None
+    }
+
+    /// Discrete, low-cardinality values of this line (e.g. level, tag, protocol).
+    ///
+    /// Each `(column, value)` pair is counted into the per-source value dictionary
+    /// that backs the column filter dropdowns. Evaluated for every line at load
+    /// time and while column filtering, so keep it cheap. Default: none.
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Status and latency of the request this line records, if it records one.
+    ///
+    /// Read by the anomaly scorer to flag server errors and slow requests.
+    /// Default: `None`.
+    fn request_metrics(&self) -> Option<RequestMetrics> {
+        None
+    }
+
+    /// Severity the line states in a dedicated field (e.g. a syslog priority).
+    ///
+    /// Read by the keyword scorer, so an error is scored as one even when its
+    /// text has no error keyword. Default: `None`.
+    fn severity(&self) -> Option<Severity> {
+        None
+    }
+
     /// Render format-specific context menu items for a single log line.
     ///
     /// Called inside an egui context menu. Implementations write into
@@ -210,6 +425,10 @@ pub trait InputFileType: HasSlug {
     /// Log line type for this filetype
     type LineType: LineType;
 
+    /// Human-readable format name, shown as the file dialog filter label and as
+    /// the detected format of dropped and command-line files.
+    const DISPLAY_NAME: &'static str;
+
     /// File extensions supported by this format (hint for file dialog only — not used
     /// for detection; multiple types may share extensions).
     const FILE_EXTENSIONS: &'static [&'static str];
@@ -4,12 +4,12 @@
 use chrono::{DateTime, Local};
 use egui::Ui;
 use opentelemetry_proto::tonic::{common::v1::any_value::Value as OTelValue, logs::v1::LogsData};
-use std::fs::{metadata, File};
+use std::fs::metadata;
 use std::io::Read;
 use std::path::Path;
-use std::time::UNIX_EPOCH;
 
-use crate::filetype::{InputFileType, LineType, TextFileType};
+use crate::core::decompress::LogReader;
+use crate::filetype::{local_time, InputFileType, LineType, TextFileType, NANOS_PER_MS};
 
 // ============================================================================
 // OtelLogLine
@@ -19,7 +19,8 @@ use crate::filetype::{InputFileType, LineType, TextFileType};
 #[derive(Debug, Clone)]
 pub struct OtelLogLine {
     raw_line: String,
-    pub timestamp: DateTime<Local>,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
     message_text: String,
     pub line_number: usize,
 }
@@ -44,8 +45,12 @@ impl LineType for OtelLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &(), file_state: &OtelFileState) -> DateTime<Local> {
-        self.timestamp + chrono::Duration::milliseconds(file_state.time_offset_ms())
+    fn timestamp(&self, config: &(), file_state: &OtelFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &OtelFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
     }
 
     fn message(&self) -> String {
@@ -73,9 +78,17 @@ impl LineType for OtelLogLine {
         self.line_number
     }
 
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&self.raw_line) {
+            flatten_json("", &value, &mut fields);
+        }
+        fields
+    }
+
     fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &OtelFileState) {
         if ui.button("⏱ Calibrate Time Here").clicked() {
-            let raw_time = self.timestamp;
+            let raw_time = local_time(self.timestamp_ns);
             let display_time =
                 raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
             *file_state
@@ -95,6 +108,32 @@ impl LineType for OtelLogLine {
     }
 }
 
+/// Flatten a JSON value into dotted `key.path = value` pairs for the detail panel.
+fn flatten_json(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_json(&path, child, out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                flatten_json(&format!("{prefix}[{i}]"), child, out);
+            }
+        }
+        serde_json::Value::String(s) => out.push((prefix.to_string(), s.clone())),
+        serde_json::Value::Null => out.push((prefix.to_string(), "null".to_string())),
+        serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {
+            out.push((prefix.to_string(), value.to_string()));
+        }
+    }
+}
+
 // ============================================================================
 // OtelFileType (InputFileType + TextFileType)
 // ============================================================================
@@ -114,6 +153,7 @@ pub struct OtelFileType {
 impl InputFileType for OtelFileType {
     type LineType = OtelLogLine;
 
+    const DISPLAY_NAME: &'static str = "OpenTelemetry JSON";
     const FILE_EXTENSIONS: &'static [&'static str] = &["json"];
 
     fn open(
@@ -125,10 +165,9 @@ impl InputFileType for OtelFileType {
             .map_err(|e| anyhow::anyhow!("Failed to stat {}: {e}", path.display()))?;
         let file_size = metadata.len();
 
-        let mut file = File::open(path)
-            .map_err(|e| anyhow::anyhow!("Failed to open {}: {e}", path.display()))?;
         let mut contents = String::new();
-        file.read_to_string(&mut contents)
+        LogReader::open(path)?
+            .read_to_string(&mut contents)
             .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))?;
 
         let logs_data: LogsData = serde_json::from_str(&contents)
@@ -161,23 +200,14 @@ impl InputFileType for OtelFileType {
                         log_record.observed_time_unix_nano
                     };
 
-                    let timestamp = if nanos > 0 {
-                        // not completely correct, but a data 200 years in the future is a bug
-                        // TODO: error variants to surface this
-                        let secs = i64::try_from(nanos / 1_000_000_000).unwrap_or_else(|_| {
-                            tracing::warn!("Timestamp in log record is too large: {nanos} nanoseconds since epoch");
-                            i64::MAX
-                        });
-                        let subsec_nanos = (nanos % 1_000_000_000) as u32;
-                        DateTime::from_timestamp(secs, subsec_nanos)
-                            .map_or_else(|| {
-                                tracing::warn!("Invalid timestamp in log record: {nanos} nanoseconds since epoch");
-                                UNIX_EPOCH.into()
-                            }, |dt| dt.with_timezone(&Local))
-                    } else {
-                        // Into local time
-                        UNIX_EPOCH.into()
-                    };
+                    // not completely correct, but a data 200 years in the future is a bug
+                    // TODO: error variants to surface this
+                    let timestamp_ns = i64::try_from(nanos).unwrap_or_else(|_| {
+                        tracing::warn!(
+                            "Invalid timestamp in log record: {nanos} nanoseconds since epoch"
+                        );
+                        0
+                    });
 
                     let body = log_record.body.as_ref().map_or_else(String::new, |v| {
                         use opentelemetry_proto::tonic::common::v1::any_value::Value;
@@ -205,7 +235,7 @@ impl InputFileType for OtelFileType {
 
                     records.push(OtelLogLine {
                         raw_line: raw,
-                        timestamp,
+                        timestamp_ns,
                         message_text: message,
                         line_number,
                     });
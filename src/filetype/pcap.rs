@@ -1,16 +1,19 @@
 // LogCrab - GPL-3.0-or-later
 // Copyright (C) 2026 Daniel Freiermuth
 
-use chrono::{DateTime, Local, TimeZone};
+use chrono::{DateTime, Local};
 use egui::Ui;
 use pcap_parser::traits::PcapReaderIterator;
 use pcap_parser::{LegacyPcapReader, PcapBlockOwned, PcapError, PcapNGReader};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
+use std::sync::Arc;
 
-use crate::filetype::{BinaryFileType, InputFileType, LineType};
+use crate::core::decompress::LogReader;
+use crate::filetype::{
+    epoch_ns, local_time, BinaryFileType, InputFileType, LineType, NANOS_PER_MS,
+};
 
 // ============================================================================
 // PcapLogLine
@@ -21,14 +24,18 @@ use crate::filetype::{BinaryFileType, InputFileType, LineType};
 pub struct PcapLogLine {
     /// Parsed packet information
     pub packet_info: PacketInfo,
+    /// Captured bytes of the packet, from the link layer header on
+    pub data: Arc<[u8]>,
     /// Original packet number in source file
     pub line_number: usize,
 }
 
 impl PcapLogLine {
-    pub const fn new(packet_info: PacketInfo, line_number: usize) -> Self {
+    #[must_use]
+    pub const fn new(packet_info: PacketInfo, data: Arc<[u8]>, line_number: usize) -> Self {
         Self {
             packet_info,
+            data,
             line_number,
         }
     }
@@ -50,6 +57,8 @@ pub struct PcapFileState {
     someip_sd_decodings: std::sync::Mutex<HashSet<String>>,
     /// Known SOME/IP endpoints discovered from SD messages (format: "TCP:ip:port" or "UDP:ip:port")
     someip_known_endpoints: std::sync::Mutex<HashSet<String>>,
+    /// Bumped whenever the state above changes how messages are displayed
+    display_generation: std::sync::atomic::AtomicU64,
 }
 
 impl PcapFileState {
@@ -79,13 +88,16 @@ impl PcapFileState {
             .someip_sd_decodings
             .lock()
             .expect("someip_sd_decodings lock poisoned");
-        if decodings.contains(&key) {
+        let active = if decodings.contains(&key) {
             decodings.remove(&key);
             false
         } else {
             decodings.insert(key);
             true
-        }
+        };
+        self.display_generation
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        active
     }
 
     /// Add discovered SOME/IP endpoints from SD messages
@@ -94,8 +106,13 @@ impl PcapFileState {
             .someip_known_endpoints
             .lock()
             .expect("someip_known_endpoints lock poisoned");
+        let mut added = false;
         for ep in endpoints {
-            known.insert(ep.key());
+            added |= known.insert(ep.key());
+        }
+        if added {
+            self.display_generation
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         }
     }
 
@@ -114,6 +131,7 @@ impl Default for PcapFileState {
             inner: crate::filetype::SimpleFileState::default(),
             someip_sd_decodings: std::sync::Mutex::new(HashSet::new()),
             someip_known_endpoints: std::sync::Mutex::new(HashSet::new()),
+            display_generation: std::sync::atomic::AtomicU64::new(0),
         }
     }
 }
@@ -134,6 +152,10 @@ impl Clone for PcapFileState {
                     .expect("someip_known_endpoints lock poisoned")
                     .clone(),
             ),
+            display_generation: std::sync::atomic::AtomicU64::new(
+                self.display_generation
+                    .load(std::sync::atomic::Ordering::SeqCst),
+            ),
         }
     }
 }
@@ -182,6 +204,7 @@ impl<'de> serde::Deserialize<'de> for PcapFileState {
             },
             someip_sd_decodings: std::sync::Mutex::new(h.someip_sd_decodings.into_iter().collect()),
             someip_known_endpoints: std::sync::Mutex::new(h.someip_known_endpoints.into_iter().collect()),
+            display_generation: std::sync::atomic::AtomicU64::new(0),
         })
     }
 }
@@ -190,6 +213,15 @@ impl crate::filetype::LogFileState for PcapFileState {
     fn egui_render_file_state(&self, ui: &egui::Ui) -> bool {
         self.inner.egui_render_file_state(ui)
     }
+
+    fn clock_offset_ms(&self) -> i64 {
+        self.time_offset_ms()
+    }
+
+    fn display_generation(&self) -> u64 {
+        self.display_generation
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
 }
 
 // ============================================================================
@@ -227,8 +259,12 @@ impl LineType for PcapLogLine {
         s
     }
 
-    fn timestamp(&self, _config: &PcapConfig, file_state: &PcapFileState) -> DateTime<Local> {
-        self.packet_info.timestamp + chrono::Duration::milliseconds(file_state.time_offset_ms())
+    fn timestamp(&self, config: &PcapConfig, file_state: &PcapFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &PcapConfig, file_state: &PcapFileState) -> i64 {
+        self.packet_info.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
     }
 
     fn message(&self) -> String {
@@ -328,9 +364,56 @@ impl LineType for PcapLogLine {
         self.line_number
     }
 
+    fn fields(&self) -> Vec<(String, String)> {
+        let pi = &self.packet_info;
+        let mut fields = vec![("Protocol".to_string(), pi.protocol.clone())];
+        fields.push(("Source".to_string(), pi.src_addr.clone()));
+        if let Some(port) = pi.src_port {
+            fields.push(("Source Port".to_string(), port.to_string()));
+        }
+        fields.push(("Destination".to_string(), pi.dst_addr.clone()));
+        if let Some(port) = pi.dst_port {
+            fields.push(("Destination Port".to_string(), port.to_string()));
+        }
+        if let Some(ref mac) = pi.src_mac {
+            fields.push(("Source MAC".to_string(), mac.clone()));
+        }
+        if let Some(ref mac) = pi.dst_mac {
+            fields.push(("Destination MAC".to_string(), mac.clone()));
+        }
+        if let Some(vlan) = pi.vlan_id {
+            fields.push(("VLAN".to_string(), vlan.to_string()));
+        }
+        fields.push(("Length".to_string(), pi.length.to_string()));
+        if let Some(ref tcp) = pi.tcp_details {
+            fields.push(("TCP Flags".to_string(), format_tcp_flags(tcp.flags)));
+            fields.push(("Seq".to_string(), tcp.seq.to_string()));
+            if tcp.flags & 0x10 != 0 {
+                fields.push(("Ack".to_string(), tcp.ack.to_string()));
+            }
+            fields.push(("Window".to_string(), tcp.window.to_string()));
+            fields.push(("Payload Length".to_string(), tcp.payload_len.to_string()));
+        }
+        if !pi.info.is_empty() {
+            fields.push(("Info".to_string(), pi.info.clone()));
+        }
+        if pi.is_abnormal {
+            fields.push(("Abnormal".to_string(), "true".to_string()));
+        }
+        fields
+    }
+
+    fn payload(&self) -> Option<Arc<[u8]>> {
+        Some(Arc::clone(&self.data))
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        vec![("Protocol", self.packet_info.protocol.clone())]
+    }
+
     fn egui_render_context_menu(&self, ui: &mut Ui, _config: &PcapConfig, file_state: &PcapFileState) {
         if ui.button("⏱ Calibrate Time Here").clicked() {
-            let raw_time = self.packet_info.timestamp;
+            let raw_time = local_time(self.packet_info.timestamp_ns);
             let display_time =
                 raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
             *file_state
@@ -383,6 +466,7 @@ pub struct PcapFileType {
 impl InputFileType for PcapFileType {
     type LineType = PcapLogLine;
 
+    const DISPLAY_NAME: &'static str = "Packet Capture";
     const FILE_EXTENSIONS: &'static [&'static str] = &["pcap", "pcapng", "cap"];
 
     /// Open a pcap/pcapng file for pull-based reading.
@@ -438,7 +522,8 @@ impl BinaryFileType for PcapFileType {
 /// Represents a parsed network packet for display
 #[derive(Debug, Clone)]
 pub struct PacketInfo {
-    pub timestamp: DateTime<Local>,
+    /// Capture time in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
     pub src_addr: String,
     pub src_port: Option<u16>,
     pub dst_addr: String,
@@ -559,7 +644,7 @@ impl PacketInfo {
             || {
                 format!(
                     "[{}] {} {} \u{2192} {}{} {} Length={}{}",
-                    self.timestamp.format("%H:%M:%S%.6f"),
+                    local_time(self.timestamp_ns).format("%H:%M:%S%.6f"),
                     self.protocol,
                     src,
                     dst,
@@ -585,7 +670,7 @@ impl PacketInfo {
                 };
                 format!(
                     "[{}] {} {} \u{2192} {}{} {} {}{}{}{}{}",
-                    self.timestamp.format("%H:%M:%S%.6f"),
+                    local_time(self.timestamp_ns).format("%H:%M:%S%.6f"),
                     self.protocol,
                     src,
                     dst,
@@ -1082,7 +1167,7 @@ fn pre_discover_someip_endpoints(lines: &[PcapLogLine], file_state: &PcapFileSta
 // Packet parsing helpers
 // ============================================================================
 
-fn parse_packet_data(data: &[u8], timestamp: DateTime<Local>) -> Option<PacketInfo> {
+fn parse_packet_data(data: &[u8], timestamp_ns: i64) -> Option<PacketInfo> {
     profiling::scope!("parse_packet_data");
     if data.len() < 14 {
         return None;
@@ -1101,19 +1186,19 @@ fn parse_packet_data(data: &[u8], timestamp: DateTime<Local>) -> Option<PacketIn
     let payload = &data[payload_offset..];
     match ethertype {
         0x0800 => {
-            let mut pi = parse_ipv4_packet(payload, timestamp, vlan_id)?;
+            let mut pi = parse_ipv4_packet(payload, timestamp_ns, vlan_id)?;
             pi.src_mac = Some(src_mac);
             pi.dst_mac = Some(dst_mac);
             Some(pi)
         }
         0x86DD => {
-            let mut pi = parse_ipv6_packet(payload, timestamp, vlan_id)?;
+            let mut pi = parse_ipv6_packet(payload, timestamp_ns, vlan_id)?;
             pi.src_mac = Some(src_mac);
             pi.dst_mac = Some(dst_mac);
             Some(pi)
         }
         0x0806 => Some(PacketInfo {
-            timestamp,
+            timestamp_ns,
             // For ARP the "addresses" are the MACs themselves; no separate MAC field.
             src_addr: src_mac,
             src_port: None,
@@ -1130,7 +1215,7 @@ fn parse_packet_data(data: &[u8], timestamp: DateTime<Local>) -> Option<PacketIn
             transport_payload: None,
         }),
         _ => Some(PacketInfo {
-            timestamp,
+            timestamp_ns,
             src_addr: src_mac,
             src_port: None,
             dst_addr: dst_mac,
@@ -1159,11 +1244,7 @@ fn format_mac(bytes: &[u8]) -> String {
     }
 }
 
-fn parse_ipv4_packet(
-    data: &[u8],
-    timestamp: DateTime<Local>,
-    vlan_id: Option<u16>,
-) -> Option<PacketInfo> {
+fn parse_ipv4_packet(data: &[u8], timestamp_ns: i64, vlan_id: Option<u16>) -> Option<PacketInfo> {
     profiling::scope!("parse_ipv4_packet");
     if data.len() < 20 {
         return None;
@@ -1207,7 +1288,7 @@ fn parse_ipv4_packet(
         .as_ref()
         .is_some_and(|tcp| tcp.flags & 0x04 != 0);
     Some(PacketInfo {
-        timestamp,
+        timestamp_ns,
         src_addr: src_ip,
         src_port,
         dst_addr: dst_ip,
@@ -1224,11 +1305,7 @@ fn parse_ipv4_packet(
     })
 }
 
-fn parse_ipv6_packet(
-    data: &[u8],
-    timestamp: DateTime<Local>,
-    vlan_id: Option<u16>,
-) -> Option<PacketInfo> {
+fn parse_ipv6_packet(data: &[u8], timestamp_ns: i64, vlan_id: Option<u16>) -> Option<PacketInfo> {
     profiling::scope!("parse_ipv6_packet");
     if data.len() < 40 {
         return None;
@@ -1261,7 +1338,7 @@ fn parse_ipv6_packet(
         .as_ref()
         .is_some_and(|tcp| tcp.flags & 0x04 != 0);
     Some(PacketInfo {
-        timestamp,
+        timestamp_ns,
         src_addr: src_ip,
         src_port,
         dst_addr: dst_ip,
@@ -1374,8 +1451,10 @@ fn parse_icmp_info(data: &[u8]) -> String {
     }
 }
 
-fn pcap_ts_to_datetime(sec: u32, usec: u32) -> Option<DateTime<Local>> {
-    Local.timestamp_opt(i64::from(sec), usec * 1000).single()
+/// Legacy pcap record time in epoch nanoseconds; `ns_per_tick` is 1000 for
+/// microsecond captures and 1 for nanosecond captures.
+fn pcap_ts_to_ns(sec: u32, frac: u32, ns_per_tick: i64) -> i64 {
+    i64::from(sec) * 1_000_000_000 + i64::from(frac) * ns_per_tick
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1387,8 +1466,7 @@ enum PcapFormat {
 fn detect_pcap_format(path: &Path) -> anyhow::Result<PcapFormat> {
     use anyhow::Context as _;
     use std::io::Read;
-    let mut file =
-        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let mut file = LogReader::open(path)?;
     let mut magic = [0u8; 4];
     file.read_exact(&mut magic)
         .context("Failed to read magic")?;
@@ -1418,25 +1496,31 @@ pub fn parse_pcap_to_lines<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<PcapLo
 
 fn parse_legacy_pcap_to_lines(path: &Path) -> anyhow::Result<Vec<PcapLogLine>> {
     profiling::scope!("parse_legacy_pcap_to_lines");
-    use anyhow::Context as _;
     tracing::info!("Starting legacy pcap parsing: {}", path.display());
-    let file = File::open(path)
-        .with_context(|| format!("Failed to open pcap file: {}", path.display()))?;
-    let reader = BufReader::new(file);
+    let reader = BufReader::new(LogReader::open(path)?);
     let mut pcap_reader = LegacyPcapReader::new(65536, reader)
         .map_err(|e| anyhow::anyhow!("Failed to create pcap reader: {e:?}"))?;
     let mut lines = Vec::new();
     let mut line_number = 1usize;
     let mut flow_tracker = TcpFlowTracker::new();
+    let mut ns_per_tick = 1_000;
     loop {
         match pcap_reader.next() {
             Ok((offset, block)) => {
+                if let PcapBlockOwned::LegacyHeader(header) = &block {
+                    if header.is_nanosecond_precision() {
+                        ns_per_tick = 1;
+                    }
+                }
                 if let PcapBlockOwned::Legacy(packet) = block {
-                    let timestamp = pcap_ts_to_datetime(packet.ts_sec, packet.ts_usec)
-                        .unwrap_or_else(Local::now);
-                    if let Some(mut packet_info) = parse_packet_data(packet.data, timestamp) {
+                    let timestamp_ns = pcap_ts_to_ns(packet.ts_sec, packet.ts_usec, ns_per_tick);
+                    if let Some(mut packet_info) = parse_packet_data(packet.data, timestamp_ns) {
                         flow_tracker.analyze_packet(&mut packet_info);
-                        lines.push(PcapLogLine::new(packet_info, line_number));
+                        lines.push(PcapLogLine::new(
+                            packet_info,
+                            packet.data.into(),
+                            line_number,
+                        ));
                         line_number += 1;
                     }
                 }
@@ -1463,11 +1547,8 @@ fn parse_legacy_pcap_to_lines(path: &Path) -> anyhow::Result<Vec<PcapLogLine>> {
 
 fn parse_pcapng_to_lines(path: &Path) -> anyhow::Result<Vec<PcapLogLine>> {
     profiling::scope!("parse_pcapng_to_lines");
-    use anyhow::Context as _;
     tracing::info!("Starting pcapng parsing: {}", path.display());
-    let file = File::open(path)
-        .with_context(|| format!("Failed to open pcapng file: {}", path.display()))?;
-    let reader = BufReader::new(file);
+    let reader = BufReader::new(LogReader::open(path)?);
     let mut pcap_reader = PcapNGReader::new(65536, reader)
         .map_err(|e| anyhow::anyhow!("Failed to create pcapng reader: {e:?}"))?;
     let mut lines = Vec::new();
@@ -1494,21 +1575,22 @@ fn parse_pcapng_to_lines(path: &Path) -> anyhow::Result<Vec<PcapLogLine>> {
                         let ts_raw = (u64::from(epb.ts_high) << 32) | u64::from(epb.ts_low);
                         let sec = ts_raw / if_tsresol;
                         let nsec = ((ts_raw % if_tsresol) * 1_000_000_000) / if_tsresol;
-                        let timestamp = Local
-                            .timestamp_opt(sec.cast_signed(), nsec as u32)
-                            .single()
-                            .unwrap_or_else(Local::now);
-                        if let Some(mut packet_info) = parse_packet_data(epb.data, timestamp) {
+                        let timestamp_ns = i64::try_from(sec)
+                            .ok()
+                            .and_then(|sec| sec.checked_mul(1_000_000_000))
+                            .and_then(|ns| ns.checked_add(nsec.cast_signed()))
+                            .unwrap_or_else(|| epoch_ns(&Local::now()));
+                        if let Some(mut packet_info) = parse_packet_data(epb.data, timestamp_ns) {
                             flow_tracker.analyze_packet(&mut packet_info);
-                            lines.push(PcapLogLine::new(packet_info, line_number));
+                            lines.push(PcapLogLine::new(packet_info, epb.data.into(), line_number));
                             line_number += 1;
                         }
                     }
                     PcapBlockOwned::NG(pcap_parser::Block::SimplePacket(spb)) => {
-                        let timestamp = Local::now();
-                        if let Some(mut packet_info) = parse_packet_data(spb.data, timestamp) {
+                        let timestamp_ns = epoch_ns(&Local::now());
+                        if let Some(mut packet_info) = parse_packet_data(spb.data, timestamp_ns) {
                             flow_tracker.analyze_packet(&mut packet_info);
-                            lines.push(PcapLogLine::new(packet_info, line_number));
+                            lines.push(PcapLogLine::new(packet_info, spb.data.into(), line_number));
                             line_number += 1;
                         }
                     }
@@ -33,8 +33,14 @@
 //! - All [`DataSourceVariant`] dispatch methods.
 //! - [`AsTypedSource<T>`] and `From<Arc<SourceData<T>>>` impls for each arm.
 //! - [`all_file_extensions()`] – deduplicated list of all file extensions.
+//! - [`file_type_filters()`] – per-type display name and extensions for the file dialog.
+//! - [`visit_detected()`], [`export_dispatch()`], [`convert_dispatch()`],
+//!   [`scan_dispatch()`] – headless detection for the command-line tools.
+//! - [`detect_format_name()`] – display name of the type detection picks for a path.
 //! - [`try_open_binary()`] – reads the file header, matches magic bytes, and opens the source.
 //! - [`open_text_source()`] – runs `looks_like()` on a sample and opens the source.
+//! - [`parser_choices()`], [`open_source_as()`] – open a source with a user-chosen
+//!   type, bypassing detection.
 //! - Compile-time assertions: each binary type has ≥1 magic pattern and no two patterns
 //!   across all binary types are byte-prefix of one another.
 
@@ -203,6 +209,23 @@ macro_rules! register_filetypes {
             exts
         }
 
+        /// Returns `(display name, extensions)` for every registered type, in
+        /// registration order.
+        ///
+        /// Used to build one file dialog filter per format.
+        pub fn file_type_filters() -> Vec<(&'static str, &'static [&'static str])> {
+            vec![
+                $((
+                    <$b_ftype as $crate::filetype::InputFileType>::DISPLAY_NAME,
+                    <$b_ftype as $crate::filetype::InputFileType>::FILE_EXTENSIONS,
+                ),)*
+                $((
+                    <$t_ftype as $crate::filetype::InputFileType>::DISPLAY_NAME,
+                    <$t_ftype as $crate::filetype::InputFileType>::FILE_EXTENSIONS,
+                ),)*
+            ]
+        }
+
         /// Returns the normalisation version for every registered filetype, keyed by slug.
         ///
         /// Sent in the `normalization_versions` field of the WebSocket `start` frame so
@@ -231,16 +254,20 @@ macro_rules! register_filetypes {
             file_config: &GlobalFileConfig,
             store: &::std::sync::Arc<$crate::core::log_store::LogStore>,
         ) -> ::std::option::Option<(DataSourceVariant, Vec<$crate::core::SavedFilter>, Vec<$crate::core::SavedHighlight>)> {
-            use ::std::io::Read as _;
-            let mut file = ::std::fs::File::open(path).ok()?;
-            let mut header = [0u8; 16];
-            let n = file.read(&mut header).ok().filter(|&n| n >= 4)?;
-            let header = &header[..n];
+            // Compressed files are matched by the magic of their content
+            let header = $crate::core::decompress::read_head(path, 16)
+                .ok()
+                .filter(|h| h.len() >= 4)?;
             $(
                 if <$b_ftype as $crate::filetype::BinaryFileType>::MAGIC_BYTES
                     .iter()
                     .any(|p| header.starts_with(p))
                 {
+                    tracing::info!(
+                        "Opening {} with detected format {}",
+                        path.display(),
+                        <$b_ftype as $crate::filetype::InputFileType>::DISPLAY_NAME,
+                    );
                     let config_val = file_config.$b_slug.clone();
                     let arc_config = ::std::sync::Arc::new(::std::sync::RwLock::new(config_val.clone()));
                     let (source, filters, highlights) = $crate::core::log_file::LogFileLoader::load_typed(
@@ -258,6 +285,68 @@ macro_rules! register_filetypes {
             None
         }
 
+        /// Slug and display name of every registered type, in registration order.
+        pub fn parser_choices() -> Vec<(&'static str, &'static str)> {
+            vec![
+                $((
+                    <$b_ftype as $crate::filetype::HasSlug>::SLUG,
+                    <$b_ftype as $crate::filetype::InputFileType>::DISPLAY_NAME,
+                ),)*
+                $((
+                    <$t_ftype as $crate::filetype::HasSlug>::SLUG,
+                    <$t_ftype as $crate::filetype::InputFileType>::DISPLAY_NAME,
+                ),)*
+            ]
+        }
+
+        /// Open `path` with the type registered as `slug`, skipping detection.
+        ///
+        /// Used when detection picked the wrong format. Returns `None` for an
+        /// unknown slug; a file the forced parser cannot read fails in the
+        /// background loader like any other unreadable file.
+        pub fn open_source_as(
+            slug: &str,
+            path: &::std::path::Path,
+            toast: &$crate::ui::ProgressToastHandle,
+            warnings: &$crate::ui::ToastSender,
+            file_config: &GlobalFileConfig,
+            store: &::std::sync::Arc<$crate::core::log_store::LogStore>,
+        ) -> ::std::option::Option<(DataSourceVariant, Vec<$crate::core::SavedFilter>, Vec<$crate::core::SavedHighlight>)> {
+            tracing::info!("Opening {} with forced format {slug}", path.display());
+            $(
+                if slug == <$b_ftype as $crate::filetype::HasSlug>::SLUG {
+                    let config_val = file_config.$b_slug.clone();
+                    let arc_config = ::std::sync::Arc::new(::std::sync::RwLock::new(config_val.clone()));
+                    let (source, filters, highlights) = $crate::core::log_file::LogFileLoader::load_typed(
+                        path.to_path_buf(),
+                        toast,
+                        warnings,
+                        arc_config,
+                        move |p, fs| <$b_ftype as $crate::filetype::InputFileType>::open(p, config_val, fs),
+                        store,
+                    );
+                    return Some((source.into(), filters, highlights));
+                }
+            )*
+            $(
+                if slug == <$t_ftype as $crate::filetype::HasSlug>::SLUG {
+                    let config_val = file_config.$t_slug.clone();
+                    let arc_config = ::std::sync::Arc::new(::std::sync::RwLock::new(config_val.clone()));
+                    let (source, filters, highlights) = $crate::core::log_file::LogFileLoader::load_typed(
+                        path.to_path_buf(),
+                        toast,
+                        warnings,
+                        arc_config,
+                        move |p, fs| <$t_ftype as $crate::filetype::InputFileType>::open(p, config_val, fs),
+                        store,
+                    );
+                    return Some((source.into(), filters, highlights));
+                }
+            )*
+            tracing::error!("open_source_as: unknown format '{slug}'");
+            None
+        }
+
         /// Returns `None` when the file cannot be opened for sampling.
         pub fn open_text_source(
             path: &::std::path::Path,
@@ -266,22 +355,24 @@ macro_rules! register_filetypes {
             file_config: &GlobalFileConfig,
             store: &::std::sync::Arc<$crate::core::log_store::LogStore>,
         ) -> ::std::option::Option<(DataSourceVariant, Vec<$crate::core::SavedFilter>, Vec<$crate::core::SavedHighlight>)> {
-            use ::std::io::Read as _;
-            const MAX_SAMPLE_BYTES: usize = 100 * 1024;
-            let mut sample = ::std::vec::Vec::with_capacity(MAX_SAMPLE_BYTES);
-            match ::std::fs::File::open(path) {
-                Ok(f) => { let _ = f.take(MAX_SAMPLE_BYTES as u64).read_to_end(&mut sample); }
+            const MAX_SAMPLE_BYTES: u64 = 100 * 1024;
+            let sample = match $crate::core::decompress::read_head(path, MAX_SAMPLE_BYTES) {
+                Ok(sample) => sample,
                 Err(e) => {
                     tracing::error!("Cannot open file for format detection: {e}");
                     warnings.send(format!("Cannot open file: {e}"));
                     return None;
                 }
-            }
+            };
             $(
                 if <$t_ftype as $crate::filetype::TextFileType>::looks_like(
                     &mut ::std::io::Cursor::new(&sample),
                 ) {
-                    tracing::info!("Opening {} with detected format {}", path.display(), stringify!($t_ftype));
+                    tracing::info!(
+                        "Opening {} with detected format {}",
+                        path.display(),
+                        <$t_ftype as $crate::filetype::InputFileType>::DISPLAY_NAME,
+                    );
                     let config_val = file_config.$t_slug.clone();
                     let arc_config = ::std::sync::Arc::new(::std::sync::RwLock::new(config_val.clone()));
                     let (source, filters, highlights) = $crate::core::log_file::LogFileLoader::load_typed(
@@ -300,7 +391,7 @@ macro_rules! register_filetypes {
             None
         }
 
-        /// Detect the file type of `path` and export all lines as NDJSON to `out`.
+        /// Detect the file type of `path` and hand the concrete type to `visitor`.
         ///
         /// Mirrors the detection logic of [`try_open_binary`] and
         /// [`open_text_source`] but has no UI dependencies — suitable for CLI
@@ -308,62 +399,183 @@ macro_rules! register_filetypes {
         /// bytes; text types are then matched in registration order by content
         /// sampling. The last text type must be a catch-all (e.g. `generic`).
         ///
-        /// Timestamps are raw and uncalibrated (config and file-state are both
-        /// `Default`), honouring the stability invariant on
-        /// [`$crate::filetype::LineType::timestamp`].
-        pub fn export_dispatch(
+        /// Multi-container logs are split like the UI splits them (see
+        /// [`$crate::core::container_split`]) and each container is detected and
+        /// visited on its own.
+        pub fn visit_detected<V: $crate::export::FileTypeVisitor>(
             path: &::std::path::Path,
-            out: &mut impl ::std::io::Write,
+            mut visitor: V,
         ) -> ::anyhow::Result<()> {
             use ::anyhow::Context as _;
-            use ::std::io::Read as _;
 
+            // Detection reads the head, then the parser opens the path again
+            $crate::core::decompress::require_regular_file(path)?;
+
+            let Some(prefix) = $crate::core::container_split::detect_container_log(path)? else {
+                return visit_detected_file(path, &mut visitor);
+            };
+            let parts = $crate::core::container_split::split_container_log(
+                path,
+                prefix,
+                &$crate::core::container_split::split_dir(path),
+            )
+            .with_context(|| format!("cannot split the containers of {}", path.display()))?;
+            for part in parts {
+                let name = part.file_stem().unwrap_or_default().to_string_lossy();
+                visitor.begin_container(&name)?;
+                visit_detected_file(&part, &mut visitor)?;
+            }
+            Ok(())
+        }
+
+        /// [`visit_detected`] for a file that is not split any further.
+        fn visit_detected_file<V: $crate::export::FileTypeVisitor>(
+            path: &::std::path::Path,
+            visitor: &mut V,
+        ) -> ::anyhow::Result<()> {
+            use ::anyhow::Context as _;
+
+            // Compressed files are matched by the magic of their content
+            let sample = $crate::core::decompress::read_head(path, MAX_SAMPLE_BYTES)
+                .with_context(|| format!("cannot sample {}", path.display()))?;
+            visit_detected_sample(path, &sample, visitor)
+        }
+
+        /// Bytes of a file sampled for detection.
+        const MAX_SAMPLE_BYTES: u64 = 100 * 1024;
+
+        /// Detect the file type from `sample`, the start of `path`'s content,
+        /// and visit it.
+        fn visit_detected_sample<V: $crate::export::FileTypeVisitor>(
+            path: &::std::path::Path,
+            sample: &[u8],
+            visitor: &mut V,
+        ) -> ::anyhow::Result<()> {
             // ── Binary: magic-byte detection ─────────────────────────────────
-            let mut header = [0u8; 16];
-            let n = ::std::fs::File::open(path)
-                .with_context(|| format!("cannot open {}", path.display()))?
-                .read(&mut header)
-                .with_context(|| format!("cannot read header of {}", path.display()))?;
-            let header = &header[..n];
-
-            if n >= 4 {
+            if sample.len() >= 4 {
                 $(
                     if <$b_ftype as $crate::filetype::BinaryFileType>::MAGIC_BYTES
                         .iter()
-                        .any(|p| header.starts_with(p))
+                        .any(|p| sample.starts_with(p))
                     {
-                        return $crate::export::export_typed::<$b_ftype>(
+                        return visitor.visit::<$b_ftype>(
                             path,
                             <$b_ftype as $crate::filetype::HasSlug>::SLUG,
-                            out,
                         );
                     }
                 )*
             }
 
             // ── Text: content sampling ────────────────────────────────────────
-            const MAX_SAMPLE_BYTES: u64 = 100 * 1024;
-            let mut sample = ::std::vec::Vec::new();
-            ::std::fs::File::open(path)
-                .with_context(|| format!("cannot open {}", path.display()))?
-                .take(MAX_SAMPLE_BYTES)
-                .read_to_end(&mut sample)
-                .with_context(|| format!("cannot sample {}", path.display()))?;
-
             $(
                 if <$t_ftype as $crate::filetype::TextFileType>::looks_like(
-                    &mut ::std::io::Cursor::new(&sample),
+                    &mut ::std::io::Cursor::new(sample),
                 ) {
-                    return $crate::export::export_typed::<$t_ftype>(
+                    return visitor.visit::<$t_ftype>(
                         path,
                         <$t_ftype as $crate::filetype::HasSlug>::SLUG,
-                        out,
                     );
                 }
             )*
 
             // Should never be reached if the last text type is a catch-all.
-            ::anyhow::bail!("export_dispatch: no file type matched for {}", path.display())
+            ::anyhow::bail!("visit_detected: no file type matched for {}", path.display())
+        }
+
+        /// Display name of the file type [`visit_detected`] picks for `path`.
+        ///
+        /// Runs the same detection as opening the file, so the name shown for a
+        /// dropped or command-line file is the parser it will actually get. A
+        /// multi-container log is not split: the format of its first container
+        /// is detected from the file's head, so this stays cheap enough to run
+        /// while a file is dragged over the window.
+        ///
+        /// # Errors
+        ///
+        /// Fails when the file cannot be read.
+        pub fn detect_format_name(path: &::std::path::Path) -> ::anyhow::Result<&'static str> {
+            use ::anyhow::Context as _;
+
+            $crate::core::decompress::require_regular_file(path)?;
+            let sample = $crate::core::decompress::read_head(path, MAX_SAMPLE_BYTES)
+                .with_context(|| format!("cannot sample {}", path.display()))?;
+            let prefix = $crate::core::container_split::ContainerPrefix::detect_in(&sample);
+            let sample = match prefix {
+                Some(prefix) => prefix.first_container_head(&sample).into_bytes(),
+                None => sample,
+            };
+            let mut out = None;
+            let mut visitor = $crate::export::FormatName { out: &mut out };
+            visit_detected_sample(path, &sample, &mut visitor)?;
+            out.ok_or_else(|| ::anyhow::anyhow!("no format for {}", path.display()))
+        }
+
+        /// Detect the file type of `path` and export all lines as NDJSON to `out`.
+        ///
+        /// Timestamps are raw and uncalibrated (config and file-state are both
+        /// `Default`), honouring the stability invariant on
+        /// [`$crate::filetype::LineType::timestamp`].
+        pub fn export_dispatch(
+            path: &::std::path::Path,
+            out: &mut impl ::std::io::Write,
+        ) -> ::anyhow::Result<()> {
+            visit_detected(path, $crate::export::NdjsonExport { out })
+        }
+
+        /// Detect the file type of `path` and write its lines as plain text to `out`,
+        /// formatted the way the log table shows them.
+        pub fn convert_dispatch(
+            path: &::std::path::Path,
+            out: &mut impl ::std::io::Write,
+        ) -> ::anyhow::Result<()> {
+            visit_detected(path, $crate::export::TextConvert { out })
+        }
+
+        /// Detect the file type of `path` and write a short summary (format,
+        /// year, line counts, time range) to `out`.
+        pub fn detect_dispatch(
+            path: &::std::path::Path,
+            out: &mut impl ::std::io::Write,
+        ) -> ::anyhow::Result<()> {
+            visit_detected(path, $crate::export::DetectReport { out })
+        }
+
+        /// Detect the file type of `path`, score it and write every line's
+        /// anomaly score breakdown as NDJSON to `out`.
+        pub fn export_scores_dispatch(
+            path: &::std::path::Path,
+            out: &mut impl ::std::io::Write,
+        ) -> ::anyhow::Result<()> {
+            visit_detected(path, $crate::export::ScoreExport { out })
+        }
+
+        /// Detect the file type of `path`, score it and write the lines matching
+        /// `options` to `out`. Returns the number of lines written.
+        pub fn scan_dispatch(
+            path: &::std::path::Path,
+            options: &$crate::export::ScanOptions,
+            out: &mut impl ::std::io::Write,
+        ) -> ::anyhow::Result<usize> {
+            let mut matched = 0;
+            visit_detected(
+                path,
+                $crate::export::Scan {
+                    options,
+                    out,
+                    matched: &mut matched,
+                },
+            )?;
+            Ok(matched)
+        }
+
+        /// Detect the file type of `path`, score it and return its headline
+        /// numbers (line and error counts, max score, time range).
+        pub fn summarize_dispatch(
+            path: &::std::path::Path,
+        ) -> ::anyhow::Result<$crate::export::FileSummary> {
+            let mut out = None;
+            visit_detected(path, $crate::export::Summarize { out: &mut out })?;
+            out.ok_or_else(|| ::anyhow::anyhow!("no summary for {}", path.display()))
         }
 
         // ── DataSourceVariant ────────────────────────────────────────────────────
@@ -411,6 +623,118 @@ macro_rules! register_filetypes {
                 }
             }
 
+            /// Whether this source was parsed from a binary format
+            pub const fn is_binary(&self) -> bool {
+                match self {
+                    $( Self::$b_arm(_) => true, )*
+                    $( Self::$t_arm(_) => false, )*
+                }
+            }
+
+            pub fn is_loaded(&self) -> bool {
+                match self {
+                    $( Self::$b_arm(s) => s.is_loaded(), )*
+                    $( Self::$t_arm(s) => s.is_loaded(), )*
+                }
+            }
+
+            pub fn record_activity(&self, entry: $crate::core::session::ActivityEntry) {
+                match self {
+                    $( Self::$b_arm(s) => s.record_activity(entry), )*
+                    $( Self::$t_arm(s) => s.record_activity(entry), )*
+                }
+            }
+
+            pub fn get_activity(&self) -> Vec<$crate::core::session::ActivityEntry> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_activity(), )*
+                    $( Self::$t_arm(s) => s.get_activity(), )*
+                }
+            }
+
+            pub fn take_reparse_request(&self) -> bool {
+                match self {
+                    $( Self::$b_arm(s) => s.take_reparse_request(), )*
+                    $( Self::$t_arm(s) => s.take_reparse_request(), )*
+                }
+            }
+
+            pub fn clock_offset_ms(&self) -> i64 {
+                match self {
+                    $( Self::$b_arm(s) => s.clock_offset_ms(), )*
+                    $( Self::$t_arm(s) => s.clock_offset_ms(), )*
+                }
+            }
+
+            pub fn request_cancel(&self) {
+                match self {
+                    $( Self::$b_arm(s) => s.request_cancel(), )*
+                    $( Self::$t_arm(s) => s.request_cancel(), )*
+                }
+            }
+
+            pub fn get_segments(&self) -> Vec<$crate::core::segment::Segment> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_segments(), )*
+                    $( Self::$t_arm(s) => s.get_segments(), )*
+                }
+            }
+
+            pub fn set_segments(&self, segments: Vec<$crate::core::segment::Segment>) {
+                match self {
+                    $( Self::$b_arm(s) => s.set_segments(segments), )*
+                    $( Self::$t_arm(s) => s.set_segments(segments), )*
+                }
+            }
+
+            pub fn segment_name(&self, line_index: usize) -> Option<String> {
+                match self {
+                    $( Self::$b_arm(s) => s.segment_name(line_index), )*
+                    $( Self::$t_arm(s) => s.segment_name(line_index), )*
+                }
+            }
+
+            pub fn find_split_points(&self, gap_ms: i64) -> Vec<usize> {
+                match self {
+                    $( Self::$b_arm(s) => s.find_split_points(gap_ms), )*
+                    $( Self::$t_arm(s) => s.find_split_points(gap_ms), )*
+                }
+            }
+
+            pub fn is_live(&self) -> bool {
+                match self {
+                    $( Self::$b_arm(s) => s.is_live(), )*
+                    $( Self::$t_arm(s) => s.is_live(), )*
+                }
+            }
+
+            pub fn set_retention_limit(&self, limit: usize) {
+                match self {
+                    $( Self::$b_arm(s) => s.set_retention_limit(limit), )*
+                    $( Self::$t_arm(s) => s.set_retention_limit(limit), )*
+                }
+            }
+
+            pub fn line_anchor(
+                &self,
+                line_index: usize,
+            ) -> Option<$crate::core::line_anchor::LineAnchor> {
+                match self {
+                    $( Self::$b_arm(s) => s.line_anchor(line_index), )*
+                    $( Self::$t_arm(s) => s.line_anchor(line_index), )*
+                }
+            }
+
+            pub fn resolve_anchors(
+                &self,
+                anchors: &[(usize, $crate::core::line_anchor::LineAnchor)],
+            ) -> Vec<Option<usize>> {
+                match self {
+                    $( Self::$b_arm(s) => s.resolve_anchors(anchors), )*
+                    $( Self::$t_arm(s) => s.resolve_anchors(anchors), )*
+                }
+            }
+
             pub fn has_bookmark(&self, line_index: usize) -> bool {
                 match self {
                     $( Self::$b_arm(s) => s.has_bookmark(line_index), )*
@@ -439,6 +763,41 @@ macro_rules! register_filetypes {
                 }
             }
 
+            pub fn set_bookmark_category(&self, line_index: usize, category: String) {
+                match self {
+                    $( Self::$b_arm(s) => s.set_bookmark_category(line_index, category), )*
+                    $( Self::$t_arm(s) => s.set_bookmark_category(line_index, category), )*
+                }
+            }
+
+            pub fn set_bookmark_attachments(&self, line_index: usize, attachments: Vec<String>) {
+                match self {
+                    $( Self::$b_arm(s) => s.set_bookmark_attachments(line_index, attachments), )*
+                    $( Self::$t_arm(s) => s.set_bookmark_attachments(line_index, attachments), )*
+                }
+            }
+
+            pub fn attachments_dir(&self) -> ::std::path::PathBuf {
+                match self {
+                    $( Self::$b_arm(s) => s.attachments_dir(), )*
+                    $( Self::$t_arm(s) => s.attachments_dir(), )*
+                }
+            }
+
+            pub fn take_bookmarks(&self) -> Vec<Bookmark> {
+                match self {
+                    $( Self::$b_arm(s) => s.take_bookmarks(), )*
+                    $( Self::$t_arm(s) => s.take_bookmarks(), )*
+                }
+            }
+
+            pub fn restore_bookmarks(&self, bookmarks: Vec<Bookmark>) {
+                match self {
+                    $( Self::$b_arm(s) => s.restore_bookmarks(bookmarks), )*
+                    $( Self::$t_arm(s) => s.restore_bookmarks(bookmarks), )*
+                }
+            }
+
             pub fn remove_bookmark(&self, line_index: usize) -> Option<Bookmark> {
                 match self {
                     $( Self::$b_arm(s) => s.remove_bookmark(line_index), )*
@@ -446,14 +805,126 @@ macro_rules! register_filetypes {
                 }
             }
 
+            pub fn add_suppression(&self, suppression: $crate::core::session::Suppression) {
+                match self {
+                    $( Self::$b_arm(s) => s.add_suppression(suppression), )*
+                    $( Self::$t_arm(s) => s.add_suppression(suppression), )*
+                }
+            }
+
+            pub fn remove_suppression(&self, suppression: &$crate::core::session::Suppression) -> bool {
+                match self {
+                    $( Self::$b_arm(s) => s.remove_suppression(suppression), )*
+                    $( Self::$t_arm(s) => s.remove_suppression(suppression), )*
+                }
+            }
+
+            pub fn get_suppressions(&self) -> Vec<$crate::core::session::Suppression> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_suppressions(), )*
+                    $( Self::$t_arm(s) => s.get_suppressions(), )*
+                }
+            }
+
+            pub fn set_template_verdict(
+                &self,
+                template: &str,
+                verdict: Option<$crate::core::session::TemplateVerdict>,
+            ) {
+                match self {
+                    $( Self::$b_arm(s) => s.set_template_verdict(template, verdict), )*
+                    $( Self::$t_arm(s) => s.set_template_verdict(template, verdict), )*
+                }
+            }
+
+            pub fn get_template_feedback(&self) -> Vec<$crate::core::session::TemplateFeedback> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_template_feedback(), )*
+                    $( Self::$t_arm(s) => s.get_template_feedback(), )*
+                }
+            }
+
+            pub fn get_level_rules(&self) -> Vec<$crate::core::level_rule::LevelRule> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_level_rules(), )*
+                    $( Self::$t_arm(s) => s.get_level_rules(), )*
+                }
+            }
+
+            pub fn set_level_rules(&self, rules: Vec<$crate::core::level_rule::LevelRule>) {
+                match self {
+                    $( Self::$b_arm(s) => s.set_level_rules(rules), )*
+                    $( Self::$t_arm(s) => s.set_level_rules(rules), )*
+                }
+            }
+
+            /// Run the heuristic scoring of this source again in the background.
+            pub fn spawn_rescore(
+                &self,
+                toast: $crate::ui::ProgressToastHandle,
+                store: ::std::sync::Arc<LogStore>,
+            ) {
+                match self {
+                    $( Self::$b_arm(s) => $crate::core::LogFileLoader::spawn_rescore(
+                        ::std::sync::Arc::clone(s), toast, store,
+                    ), )*
+                    $( Self::$t_arm(s) => $crate::core::LogFileLoader::spawn_rescore(
+                        ::std::sync::Arc::clone(s), toast, store,
+                    ), )*
+                }
+            }
+
+            pub fn feedback_scores(&self) -> std::collections::HashMap<usize, f64> {
+                match self {
+                    $( Self::$b_arm(s) => s.feedback_scores(), )*
+                    $( Self::$t_arm(s) => s.feedback_scores(), )*
+                }
+            }
+
+            pub fn template_key(&self, line_index: usize) -> Option<String> {
+                match self {
+                    $( Self::$b_arm(s) => s.template_key(line_index), )*
+                    $( Self::$t_arm(s) => s.template_key(line_index), )*
+                }
+            }
+
+            pub fn template_keys(&self) -> (usize, Vec<String>) {
+                match self {
+                    $( Self::$b_arm(s) => s.template_keys(), )*
+                    $( Self::$t_arm(s) => s.template_keys(), )*
+                }
+            }
+
             pub fn save_crab_file(
                 &self,
+                writer: &$crate::core::crab_writer::CrabWriter,
                 filters: &[$crate::core::SavedFilter],
                 highlights: &[$crate::core::SavedHighlight],
             ) {
                 match self {
-                    $( Self::$b_arm(s) => s.save_crab_file(filters, highlights), )*
-                    $( Self::$t_arm(s) => s.save_crab_file(filters, highlights), )*
+                    $( Self::$b_arm(s) => s.save_crab_file(writer, filters, highlights), )*
+                    $( Self::$t_arm(s) => s.save_crab_file(writer, filters, highlights), )*
+                }
+            }
+
+            pub fn is_crab_contended(&self) -> bool {
+                match self {
+                    $( Self::$b_arm(s) => s.is_crab_contended(), )*
+                    $( Self::$t_arm(s) => s.is_crab_contended(), )*
+                }
+            }
+
+            pub fn release_crab(&self) {
+                match self {
+                    $( Self::$b_arm(s) => s.release_crab(), )*
+                    $( Self::$t_arm(s) => s.release_crab(), )*
+                }
+            }
+
+            pub fn take_over_crab(&self) -> Option<$crate::core::crab_lock::Takeover> {
+                match self {
+                    $( Self::$b_arm(s) => s.take_over_crab(), )*
+                    $( Self::$t_arm(s) => s.take_over_crab(), )*
                 }
             }
 
@@ -490,26 +961,81 @@ macro_rules! register_filetypes {
                 }
             }
 
-            /// Get the fully-calibrated timestamp for the line at `line_index`.
+            /// Parsed key/value fields for the line at `line_index`.
+            pub fn get_fields(&self, line_index: usize) -> Option<Vec<(String, String)>> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_fields(line_index), )*
+                    $( Self::$t_arm(s) => s.get_fields(line_index), )*
+                }
+            }
+
+            /// Reformatted raw text for the line at `line_index`.
+            pub fn get_pretty_raw(&self, line_index: usize) -> Option<String> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_pretty_raw(line_index), )*
+                    $( Self::$t_arm(s) => s.get_pretty_raw(line_index), )*
+                }
+            }
+
+            /// Captured bytes of the line at `line_index`.
+            pub fn get_payload(&self, line_index: usize) -> Option<std::sync::Arc<[u8]>> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_payload(line_index), )*
+                    $( Self::$t_arm(s) => s.get_payload(line_index), )*
+                }
+            }
+
+            /// Discrete column values for the line at `line_index`.
+            pub fn get_facets(&self, line_index: usize) -> Option<Vec<(&'static str, String)>> {
+                match self {
+                    $( Self::$b_arm(s) => s.get_facets(line_index), )*
+                    $( Self::$t_arm(s) => s.get_facets(line_index), )*
+                }
+            }
+
+            /// Names of the discrete columns observed in this source.
+            pub fn facet_columns(&self) -> Vec<&'static str> {
+                match self {
+                    $( Self::$b_arm(s) => s.facet_columns(), )*
+                    $( Self::$t_arm(s) => s.facet_columns(), )*
+                }
+            }
+
+            /// Value dictionary of the discrete columns of this source.
+            pub fn facet_counts(&self) -> $crate::core::log_store::FacetCounts {
+                match self {
+                    $( Self::$b_arm(s) => s.facet_counts(), )*
+                    $( Self::$t_arm(s) => s.facet_counts(), )*
+                }
+            }
+
+            /// Get the fully-adjusted timestamp for the line at `line_index`.
             ///
-            /// Locks `config` and `file_state` and calls `LineType::timestamp()`, so
-            /// both config-driven source selection (e.g. DLT ECU/session clock) and the
-            /// per-source calibration offset are applied. Returns `None` if the line
-            /// index is out of bounds.
+            /// Config-driven clock selection (e.g. DLT ECU/session clock), the
+            /// per-source calibration offset and the offset of the line's segment
+            /// are applied. Returns `None` if the line index is out of bounds.
             pub fn adjusted_timestamp(&self, line_index: usize) -> Option<::chrono::DateTime<::chrono::Local>> {
                 match self {
-                    $( Self::$b_arm(s) => {
-                        let lines = s.lines.read().expect("lines lock poisoned");
-                        let config = s.config.read().expect("config lock poisoned");
-                        let file_state = &*s.file_state;
-                        lines.get(line_index).map(|l| l.timestamp(&*config, file_state))
-                    } )*
-                    $( Self::$t_arm(s) => {
-                        let lines = s.lines.read().expect("lines lock poisoned");
-                        let config = s.config.read().expect("config lock poisoned");
-                        let file_state = &*s.file_state;
-                        lines.get(line_index).map(|l| l.timestamp(&*config, file_state))
-                    } )*
+                    $( Self::$b_arm(s) => s.adjusted_timestamp(line_index), )*
+                    $( Self::$t_arm(s) => s.adjusted_timestamp(line_index), )*
+                }
+            }
+
+            /// Calibrated timestamp of the line at `line_index` in epoch nanoseconds,
+            /// the key used for ordering lines across sources.
+            pub fn adjusted_timestamp_ns(&self, line_index: usize) -> Option<i64> {
+                match self {
+                    $( Self::$b_arm(s) => s.adjusted_timestamp_ns(line_index), )*
+                    $( Self::$t_arm(s) => s.adjusted_timestamp_ns(line_index), )*
+                }
+            }
+
+            /// Like [`Self::adjusted_timestamp_ns`] in epoch milliseconds, for
+            /// bucketing and time windows.
+            pub fn adjusted_timestamp_ms(&self, line_index: usize) -> Option<i64> {
+                match self {
+                    $( Self::$b_arm(s) => s.adjusted_timestamp_ms(line_index), )*
+                    $( Self::$t_arm(s) => s.adjusted_timestamp_ms(line_index), )*
                 }
             }
 
@@ -540,13 +1066,18 @@ macro_rules! register_filetypes {
             ///
             /// Predicate receives `(display_message, raw)` — the display message includes
             /// any active per-source overlays (e.g. SOME/IP SD decoding for PCAP).
-            pub fn filter_sorted_by_search<F>(&self, predicate: &F) -> Vec<usize>
+            /// Chunks ruled out by `hints` are skipped.
+            pub fn filter_sorted_by_search<F>(
+                &self,
+                predicate: &F,
+                hints: &$crate::core::chunk_index::ScanHints,
+            ) -> Vec<usize>
             where
                 F: Fn(&str, &str) -> bool + Sync,
             {
                 match self {
-                    $( Self::$b_arm(s) => s.filter_sorted_by_search(predicate), )*
-                    $( Self::$t_arm(s) => s.filter_sorted_by_search(predicate), )*
+                    $( Self::$b_arm(s) => s.filter_sorted_by_search(predicate, hints), )*
+                    $( Self::$t_arm(s) => s.filter_sorted_by_search(predicate, hints), )*
                 }
             }
         }
@@ -86,4 +86,8 @@ impl LogFileState for SimpleFileState {
             true
         })
     }
+
+    fn clock_offset_ms(&self) -> i64 {
+        self.time_offset_ms()
+    }
 }
@@ -0,0 +1,631 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use egui::Ui;
+use fancy_regex::Regex;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::core::decompress::LogReader;
+use crate::filetype::{
+    epoch_ns, local_time, InputFileType, LineType, Severity, TextFileType, NANOS_PER_MS,
+};
+
+// ============================================================================
+// Line parsing
+// ============================================================================
+
+/// RFC 5424: `<PRI>VERSION TIMESTAMP HOSTNAME APP-NAME PROCID MSGID SD [MSG]`
+static RFC5424_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^<(\d{1,3})>\d{1,2} (\S+) (\S+) (\S+) (\S+) (\S+) ?(.*)$")
+        .expect("valid regex literal")
+});
+
+/// RFC 3164 as written by syslog daemons, priority optional:
+/// `<PRI>Oct 11 22:14:15 host tag[pid]: msg`, also with an RFC 3339
+/// timestamp as rsyslog writes by default.
+static RFC3164_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:<(\d{1,3})>)?([A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}|\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})) (\S+) ([^\s:\[]+)(?:\[([^\]]*)\])?: ?(.*)$",
+    )
+    .expect("valid regex literal")
+});
+
+/// Facility keywords by code (RFC 5424, section 6.2.1)
+const FACILITIES: [&str; 24] = [
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron", "authpriv",
+    "ftp", "ntp", "audit", "alert", "clock", "local0", "local1", "local2", "local3", "local4",
+    "local5", "local6", "local7",
+];
+
+/// Split a `<PRI>` value into facility code and severity
+fn split_priority(pri: &str) -> Option<(u8, Severity)> {
+    let pri: u8 = pri.parse().ok().filter(|pri| *pri < 192)?;
+    Some((pri >> 3, Severity::from_code(pri)))
+}
+
+/// `None` for the RFC 5424 nil value `-`
+fn non_nil(value: &str) -> Option<String> {
+    (value != "-").then(|| value.to_string())
+}
+
+/// Split RFC 5424 structured data off the start of `rest`.
+///
+/// Returns the structured data (`None` for `-`) and the message after it.
+fn split_structured_data(rest: &str) -> Option<(Option<&str>, &str)> {
+    if let Some(msg) = rest.strip_prefix('-') {
+        return Some((None, msg.strip_prefix(' ').unwrap_or(msg)));
+    }
+    // One or more `[id param="value"...]` elements; `\]` and `\"` are escaped
+    let mut end = 0;
+    let bytes = rest.as_bytes();
+    while bytes.get(end) == Some(&b'[') {
+        let mut in_quotes = false;
+        let mut i = end + 1;
+        loop {
+            match bytes.get(i)? {
+                b'\\' => i += 1,
+                b'"' => in_quotes = !in_quotes,
+                b']' if !in_quotes => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        end = i + 1;
+    }
+    if end == 0 {
+        return None;
+    }
+    let msg = &rest[end..];
+    Some((Some(&rest[..end]), msg.strip_prefix(' ').unwrap_or(msg)))
+}
+
+/// Where the year of a BSD timestamp comes from
+#[derive(Debug, Clone, Copy)]
+pub enum BsdYear {
+    /// The latest time the line can be from (the file's modification time):
+    /// its year, or the one before if the date would lie after it, as for a
+    /// log spanning New Year.
+    NotAfter(DateTime<Local>),
+    /// The time of a nearby line that carries a year: the year placing the
+    /// date closest to it.
+    Near(DateTime<Local>),
+}
+
+/// Timestamp of a BSD syslog line, which has no year.
+fn parse_bsd_timestamp(text: &str, year: BsdYear) -> Option<i64> {
+    let at_year = |year: i32| {
+        NaiveDateTime::parse_from_str(&format!("{year} {text}"), "%Y %b %e %H:%M:%S")
+            .ok()
+            .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+            .map(|time| epoch_ns(&time))
+    };
+    match year {
+        BsdYear::NotAfter(reference) => {
+            let timestamp = at_year(reference.year())?;
+            if timestamp > epoch_ns(&(reference + chrono::Duration::days(1))) {
+                at_year(reference.year() - 1)
+            } else {
+                Some(timestamp)
+            }
+        }
+        BsdYear::Near(nearby) => {
+            let nearby_ns = epoch_ns(&nearby);
+            (nearby.year() - 1..=nearby.year() + 1)
+                .filter_map(at_year)
+                .min_by_key(|timestamp| timestamp.abs_diff(nearby_ns))
+        }
+    }
+}
+
+// ============================================================================
+// SyslogLogLine
+// ============================================================================
+
+/// One line of a syslog file, classic (RFC 3164) or structured (RFC 5424)
+#[derive(Debug, Clone)]
+pub struct SyslogLogLine {
+    raw_line: String,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    /// Facility code of the priority, when the line carries one
+    pub facility: Option<u8>,
+    /// Severity of the priority, when the line carries one
+    pub severity: Option<Severity>,
+    pub hostname: Option<String>,
+    pub app_name: Option<String>,
+    proc_id: Option<String>,
+    msg_id: Option<String>,
+    structured_data: Option<String>,
+    message_text: String,
+    pub line_number: usize,
+    /// Whether the timestamp carries its own year (RFC 5424 and RFC 3339)
+    dated: bool,
+}
+
+impl SyslogLogLine {
+    /// Parse a syslog line; `None` if it is in neither format.
+    ///
+    /// `year` supplies the year of BSD timestamps.
+    #[must_use]
+    pub fn parse(raw: &str, line_number: usize, year: BsdYear) -> Option<Self> {
+        Self::parse_rfc5424(raw, line_number)
+            .or_else(|| Self::parse_rfc3164(raw, line_number, year))
+    }
+
+    fn parse_rfc5424(raw: &str, line_number: usize) -> Option<Self> {
+        let caps = RFC5424_LINE.captures(raw).ok()??;
+        let (facility, severity) = split_priority(&caps[1])?;
+        let timestamp_ns = epoch_ns(&DateTime::parse_from_rfc3339(&caps[2]).ok()?);
+        let (structured_data, msg) = split_structured_data(caps.get(7)?.as_str())?;
+        Some(Self {
+            raw_line: raw.to_string(),
+            timestamp_ns,
+            facility: Some(facility),
+            severity: Some(severity),
+            hostname: non_nil(&caps[3]),
+            app_name: non_nil(&caps[4]),
+            proc_id: non_nil(&caps[5]),
+            msg_id: non_nil(&caps[6]),
+            structured_data: structured_data.map(str::to_string),
+            // A UTF-8 message may start with a byte order mark
+            message_text: msg.trim_start_matches('\u{feff}').to_string(),
+            line_number,
+            dated: true,
+        })
+    }
+
+    fn parse_rfc3164(raw: &str, line_number: usize, year: BsdYear) -> Option<Self> {
+        let caps = RFC3164_LINE.captures(raw).ok()??;
+        let priority = match caps.get(1) {
+            Some(pri) => Some(split_priority(pri.as_str())?),
+            None => None,
+        };
+        let time = &caps[2];
+        let dated = time.contains('T');
+        let timestamp_ns = if dated {
+            epoch_ns(&DateTime::parse_from_rfc3339(time).ok()?)
+        } else {
+            parse_bsd_timestamp(time, year)?
+        };
+        Some(Self {
+            raw_line: raw.to_string(),
+            timestamp_ns,
+            facility: priority.map(|(facility, _)| facility),
+            severity: priority.map(|(_, severity)| severity),
+            hostname: Some(caps[3].to_string()),
+            app_name: Some(caps[4].to_string()),
+            proc_id: caps.get(5).map(|m| m.as_str().to_string()),
+            msg_id: None,
+            structured_data: None,
+            message_text: caps[6].to_string(),
+            line_number,
+            dated,
+        })
+    }
+
+    /// A line that is not syslog (e.g. a wrapped continuation), kept as is
+    fn unparsed(raw: String, line_number: usize, timestamp_ns: i64) -> Self {
+        Self {
+            message_text: raw.clone(),
+            raw_line: raw,
+            timestamp_ns,
+            facility: None,
+            severity: None,
+            hostname: None,
+            app_name: None,
+            proc_id: None,
+            msg_id: None,
+            structured_data: None,
+            line_number,
+            dated: false,
+        }
+    }
+
+    /// Facility keyword (`auth`, `local3`, ...)
+    fn facility_name(&self) -> Option<&'static str> {
+        self.facility
+            .and_then(|facility| FACILITIES.get(usize::from(facility)).copied())
+    }
+}
+
+// ============================================================================
+// SyslogFileState
+// ============================================================================
+
+pub type SyslogFileState = crate::filetype::SimpleFileState;
+
+// ============================================================================
+// LineType implementation
+// ============================================================================
+
+impl LineType for SyslogLogLine {
+    type Config = ();
+    type FileState = SyslogFileState;
+
+    fn file_state_from_v2(time_offset_ms: i64) -> SyslogFileState {
+        let s = SyslogFileState::default();
+        s.set_time_offset_ms(time_offset_ms);
+        s
+    }
+
+    fn timestamp(&self, config: &(), file_state: &SyslogFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &SyslogFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
+    }
+
+    /// `app[pid]: message`, like the line reads in a classic syslog file
+    fn message(&self) -> String {
+        match (&self.app_name, &self.proc_id) {
+            (Some(app), Some(pid)) => format!("{app}[{pid}]: {}", self.message_text),
+            (Some(app), None) => format!("{app}: {}", self.message_text),
+            (None, _) => self.message_text.clone(),
+        }
+    }
+
+    fn display_message(&self, _config: &(), file_state: &SyslogFileState) -> String {
+        let offset_ms = file_state.time_offset_ms();
+        if offset_ms != 0 {
+            format!(
+                "[{}] {}",
+                crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
+                self.message()
+            )
+        } else {
+            self.message()
+        }
+    }
+
+    fn raw(&self) -> String {
+        self.raw_line.clone()
+    }
+
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        [
+            ("Facility", self.facility_name().map(str::to_string)),
+            ("Severity", self.severity.map(|s| s.name().to_string())),
+            ("Hostname", self.hostname.clone()),
+            ("App", self.app_name.clone()),
+            ("PID", self.proc_id.clone()),
+            ("Message ID", self.msg_id.clone()),
+            ("Structured Data", self.structured_data.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key.to_string(), value)))
+        .collect()
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        [
+            ("Severity", self.severity.map(Severity::name)),
+            ("Facility", self.facility_name()),
+            ("Host", self.hostname.as_deref()),
+            ("App", self.app_name.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(column, value)| value.map(|value| (column, value.to_string())))
+        .collect()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &SyslogFileState) {
+        if ui.button("⏱ Calibrate Time Here").clicked() {
+            let raw_time = local_time(self.timestamp_ns);
+            let display_time =
+                raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
+            *file_state
+                .calibration
+                .lock()
+                .expect("calibration lock poisoned") = Some((
+                raw_time,
+                crate::filetype::CalibrationWindow::new(
+                    display_time,
+                    false,
+                    Some(display_time),
+                    raw_time,
+                ),
+            ));
+            ui.close();
+        }
+    }
+}
+
+// ============================================================================
+// SyslogFileType (InputFileType + TextFileType)
+// ============================================================================
+
+/// Stateful reader for syslog files.
+///
+/// BSD timestamps carry no year; it is taken from the closest preceding line
+/// that has one (or, before the first, from the first in the file's head), and
+/// from the file's modification time when no line has one. Lines in neither
+/// syslog format keep the previous line's timestamp.
+pub struct SyslogFileType {
+    reader: BufReader<LogReader>,
+    line_number: usize,
+    bytes_read: u64,
+    last_timestamp_ns: i64,
+    /// Latest time the file's lines can be from
+    reference: DateTime<Local>,
+    /// Time of the latest line carrying a year
+    dated: Option<DateTime<Local>>,
+}
+
+/// Bytes sampled for a line carrying a year before reading starts
+const DATED_SAMPLE_BYTES: u64 = 100 * 1024;
+
+impl SyslogFileType {
+    fn bsd_year(&self) -> BsdYear {
+        self.dated
+            .map_or(BsdYear::NotAfter(self.reference), BsdYear::Near)
+    }
+}
+
+impl InputFileType for SyslogFileType {
+    type LineType = SyslogLogLine;
+
+    const DISPLAY_NAME: &'static str = "Syslog";
+    const FILE_EXTENSIONS: &'static [&'static str] = &["log", "syslog", "messages"];
+
+    fn open(
+        path: &Path,
+        _config: (),
+        _file_state: std::sync::Arc<SyslogFileState>,
+    ) -> anyhow::Result<Self> {
+        let reference = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_or_else(|_| Local::now(), DateTime::<Local>::from);
+        let head = crate::core::decompress::read_head(path, DATED_SAMPLE_BYTES)?;
+        let dated = String::from_utf8_lossy(&head)
+            .lines()
+            .filter_map(|line| SyslogLogLine::parse(line, 0, BsdYear::NotAfter(reference)))
+            .find(|line| line.dated)
+            .map(|line| local_time(line.timestamp_ns));
+        let file = LogReader::open(path)?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            line_number: 0,
+            bytes_read: 0,
+            last_timestamp_ns: 0,
+            reference,
+            dated,
+        })
+    }
+
+    fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
+        let mut result = Vec::with_capacity(lines_to_read);
+        let mut buf = Vec::new();
+        while result.len() < lines_to_read {
+            buf.clear();
+            match self.reader.read_until(b'\n', &mut buf) {
+                Ok(0) => break, // EOF
+                Ok(n) => {
+                    self.bytes_read += n as u64;
+                    self.line_number += 1;
+                    let line_str = String::from_utf8_lossy(&buf);
+                    let raw = line_str.trim_end_matches(['\n', '\r']);
+                    if raw.trim().is_empty() {
+                        continue;
+                    }
+                    let line = SyslogLogLine::parse(raw, self.line_number, self.bsd_year())
+                        .unwrap_or_else(|| {
+                            SyslogLogLine::unparsed(
+                                raw.to_string(),
+                                self.line_number,
+                                self.last_timestamp_ns,
+                            )
+                        });
+                    if line.dated {
+                        self.dated = Some(local_time(line.timestamp_ns));
+                    }
+                    self.last_timestamp_ns = line.timestamp_ns;
+                    result.push(line);
+                }
+                Err(e) => return Err(anyhow::anyhow!("Read error: {e}")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn bytes_consumed(&self) -> u64 {
+        self.reader.get_ref().disk_position(self.bytes_read)
+    }
+}
+
+impl TextFileType for SyslogFileType {
+    /// Returns `true` when nearly all of the first complete lines are syslog
+    /// lines in either format.
+    fn looks_like(file: &mut dyn std::io::Read) -> bool {
+        let mut buf = [0u8; 4096];
+        let n = file.read(&mut buf).unwrap_or(0);
+        let sample = String::from_utf8_lossy(&buf[..n]);
+
+        // Drop a trailing partial line when the sample filled the buffer.
+        let complete = if n == buf.len() {
+            sample.rsplit_once('\n').map_or("", |(head, _)| head)
+        } else {
+            &sample
+        };
+        let year = BsdYear::NotAfter(Local::now());
+        let mut total = 0;
+        let mut matching = 0;
+        for line in complete.lines().filter(|l| !l.trim().is_empty()).take(20) {
+            total += 1;
+            if SyslogLogLine::parse(line, 0, year).is_some() {
+                matching += 1;
+            }
+        }
+        matching > 0 && matching * 10 >= total * 9
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference() -> BsdYear {
+        BsdYear::NotAfter(
+            Local
+                .with_ymd_and_hms(2025, 3, 1, 12, 0, 0)
+                .single()
+                .expect("valid date"),
+        )
+    }
+
+    #[test]
+    fn test_rfc5424() {
+        let line = SyslogLogLine::parse(
+            "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed on /dev/pts/8",
+            1,
+            reference(),
+        )
+        .expect("rfc 5424 line");
+        assert_eq!(line.severity, Some(Severity::Critical));
+        assert_eq!(line.facility_name(), Some("auth"));
+        assert_eq!(line.hostname.as_deref(), Some("mymachine.example.com"));
+        assert_eq!(line.app_name.as_deref(), Some("su"));
+        assert_eq!(line.proc_id, None);
+        assert_eq!(line.msg_id.as_deref(), Some("ID47"));
+        assert_eq!(line.message(), "su: 'su root' failed on /dev/pts/8");
+        assert_eq!(
+            local_time(line.timestamp_ns)
+                .with_timezone(&chrono::Utc)
+                .to_rfc3339(),
+            "2003-10-11T22:14:15.003+00:00"
+        );
+
+        let line = SyslogLogLine::parse(
+            r#"<165>1 2003-10-11T22:14:15.003Z host evntslog 42 ID47 [exampleSDID@32473 iut="3" eventID="1011" note="a \"]\" here"][meta x="1"] An application event"#,
+            2,
+            reference(),
+        )
+        .expect("structured data");
+        assert_eq!(line.severity, Some(Severity::Notice));
+        assert_eq!(line.facility_name(), Some("local4"));
+        assert_eq!(line.message_text, "An application event");
+        assert!(line
+            .structured_data
+            .as_deref()
+            .is_some_and(|sd| sd.ends_with("[meta x=\"1\"]")));
+    }
+
+    #[test]
+    fn test_rfc3164() {
+        let line = SyslogLogLine::parse(
+            "<13>Feb  5 17:32:18 web01 sshd[4242]: Failed password for root",
+            1,
+            reference(),
+        )
+        .expect("rfc 3164 line");
+        assert_eq!(line.severity, Some(Severity::Notice));
+        assert_eq!(line.facility_name(), Some("user"));
+        assert_eq!(line.hostname.as_deref(), Some("web01"));
+        assert_eq!(line.message(), "sshd[4242]: Failed password for root");
+        assert_eq!(
+            local_time(line.timestamp_ns)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            "2025-02-05 17:32:18"
+        );
+
+        // Files written by the daemon: no priority; December lies in the year before
+        let line = SyslogLogLine::parse(
+            "Dec 31 23:59:59 web01 kernel: [ 1.234] usb 1-1: new device",
+            2,
+            reference(),
+        )
+        .expect("file line");
+        assert_eq!(line.severity, None);
+        assert_eq!(local_time(line.timestamp_ns).year(), 2024);
+        assert_eq!(line.message(), "kernel: [ 1.234] usb 1-1: new device");
+
+        let line = SyslogLogLine::parse(
+            "2025-03-01T10:00:00.123456+01:00 web01 systemd[1]: Started Session 7.",
+            3,
+            reference(),
+        )
+        .expect("rsyslog high-precision line");
+        assert_eq!(line.proc_id.as_deref(), Some("1"));
+        assert!(SyslogLogLine::parse("2025-03-01 10:00:00 INFO started", 4, reference()).is_none());
+    }
+
+    #[test]
+    fn test_looks_like() {
+        let mut syslog = std::io::Cursor::new(
+            "Feb  5 17:32:18 web01 sshd[4242]: Accepted publickey\n\
+             Feb  5 17:32:19 web01 CRON[77]: (root) CMD (run-parts)\n",
+        );
+        assert!(SyslogFileType::looks_like(&mut syslog));
+        let mut generic =
+            std::io::Cursor::new("2025-11-20T14:23:45.123Z ERROR Connection failed\n");
+        assert!(!SyslogFileType::looks_like(&mut generic));
+    }
+
+    #[test]
+    fn test_severity_and_facets() {
+        let line = SyslogLogLine::parse(
+            "<11>Feb  5 17:32:18 db01 postgres[9]: disk full",
+            1,
+            reference(),
+        )
+        .expect("line");
+        assert_eq!(LineType::severity(&line), Some(Severity::Error));
+        assert_eq!(
+            line.facets(),
+            vec![
+                ("Severity", "err".to_string()),
+                ("Facility", "user".to_string()),
+                ("Host", "db01".to_string()),
+                ("App", "postgres".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bsd_lines_take_the_year_of_dated_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mixed.log");
+        std::fs::write(
+            &path,
+            "<13>Oct 10 23:59:00 web01 cron[1]: before\n\
+             <34>1 2003-10-11T22:14:15.003Z host su - ID47 - 'su root' failed\n\
+             <13>Oct 12 08:00:00 web01 sshd[2]: after\n",
+        )
+        .expect("write log");
+        let mut file =
+            SyslogFileType::open(&path, (), std::sync::Arc::new(SyslogFileState::default()))
+                .expect("open");
+        let lines = file.read(10).expect("read");
+        let years: Vec<_> = lines
+            .iter()
+            .map(|line| local_time(line.timestamp_ns).year())
+            .collect();
+        assert_eq!(years, vec![2003, 2003, 2003]);
+
+        // Across New Year, the closest year wins
+        let line = SyslogLogLine::parse(
+            "Jan  1 00:00:05 web01 cron[1]: happy new year",
+            1,
+            BsdYear::Near(
+                Local
+                    .with_ymd_and_hms(2003, 12, 31, 23, 59, 0)
+                    .single()
+                    .expect("valid date"),
+            ),
+        )
+        .expect("line");
+        assert_eq!(local_time(line.timestamp_ns).year(), 2004);
+    }
+}
@@ -0,0 +1,520 @@
+// LogCrab - GPL-3.0-or-later
+// Copyright (C) 2026 Daniel Freiermuth
+
+use chrono::{DateTime, Local};
+use egui::Ui;
+use fancy_regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::metadata;
+use std::io::Read;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::core::decompress::LogReader;
+use crate::filetype::{local_time, InputFileType, LineType, TextFileType, NANOS_PER_MS};
+
+// ============================================================================
+// TraceLogLine
+// ============================================================================
+
+/// A single event from a Chrome trace or a systrace/ftrace text export.
+///
+/// Trace timestamps come from a monotonic clock (microseconds in Chrome JSON,
+/// seconds in ftrace text). Like dmesg, they are anchored at the Unix epoch so
+/// that ordering and calibration work; the source needs to be calibrated
+/// against a device log before the wall-clock values mean anything.
+#[derive(Debug, Clone)]
+pub struct TraceLogLine {
+    raw_line: String,
+    /// Timestamp in epoch nanoseconds, see [`crate::filetype::epoch_ns`]
+    pub timestamp_ns: i64,
+    message_text: String,
+    /// Decoded event fields for the detail panel.
+    fields: Vec<(String, String)>,
+    /// Discrete values for the column filters.
+    facets: Vec<(&'static str, String)>,
+    pub line_number: usize,
+}
+
+// ============================================================================
+// TraceFileState
+// ============================================================================
+
+pub type TraceFileState = crate::filetype::SimpleFileState;
+
+// ============================================================================
+// LineType implementation
+// ============================================================================
+
+impl LineType for TraceLogLine {
+    type Config = ();
+    type FileState = TraceFileState;
+
+    fn file_state_from_v2(time_offset_ms: i64) -> TraceFileState {
+        let s = TraceFileState::default();
+        s.set_time_offset_ms(time_offset_ms);
+        s
+    }
+
+    fn timestamp(&self, config: &(), file_state: &TraceFileState) -> DateTime<Local> {
+        local_time(self.timestamp_ns(config, file_state))
+    }
+
+    fn timestamp_ns(&self, _config: &(), file_state: &TraceFileState) -> i64 {
+        self.timestamp_ns + file_state.time_offset_ms() * NANOS_PER_MS
+    }
+
+    fn message(&self) -> String {
+        self.message_text.clone()
+    }
+
+    fn display_message(&self, _config: &(), file_state: &TraceFileState) -> String {
+        let offset_ms = file_state.time_offset_ms();
+        if offset_ms != 0 {
+            format!(
+                "[{}] {}",
+                crate::parser::format_time_diff(chrono::Duration::milliseconds(offset_ms)),
+                self.message_text
+            )
+        } else {
+            self.message_text.clone()
+        }
+    }
+
+    fn raw(&self) -> String {
+        self.raw_line.clone()
+    }
+
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        self.fields.clone()
+    }
+
+    fn facets(&self) -> Vec<(&'static str, String)> {
+        self.facets.clone()
+    }
+
+    fn egui_render_context_menu(&self, ui: &mut Ui, _config: &(), file_state: &TraceFileState) {
+        if ui.button("⏱ Calibrate Time Here").clicked() {
+            let raw_time = local_time(self.timestamp_ns);
+            let display_time =
+                raw_time + chrono::Duration::milliseconds(file_state.time_offset_ms());
+            *file_state
+                .calibration
+                .lock()
+                .expect("calibration lock poisoned") = Some((
+                raw_time,
+                crate::filetype::CalibrationWindow::new(
+                    display_time,
+                    false,
+                    Some(display_time),
+                    raw_time,
+                ),
+            ));
+            ui.close();
+        }
+    }
+}
+
+/// Monotonic microseconds anchored at the Unix epoch, as epoch nanoseconds.
+#[allow(clippy::cast_possible_truncation)]
+fn timestamp_ns_from_micros(micros: f64) -> i64 {
+    (micros * 1000.0).round() as i64
+}
+
+fn format_duration_us(micros: f64) -> String {
+    format!("{:.3} ms", micros / 1000.0)
+}
+
+// ============================================================================
+// Chrome trace events
+// ============================================================================
+
+/// Render a JSON scalar without quotes around strings.
+fn value_text(value: &Value) -> String {
+    value
+        .as_str()
+        .map_or_else(|| value.to_string(), str::to_string)
+}
+
+/// Process and thread names announced by `"ph": "M"` metadata events.
+#[derive(Default)]
+struct TraceNames {
+    processes: HashMap<String, String>,
+    threads: HashMap<(String, String), String>,
+}
+
+impl TraceNames {
+    fn collect(events: &[Value]) -> Self {
+        let mut names = Self::default();
+        for event in events.iter().filter(|e| e["ph"] == "M") {
+            let Some(name) = event["args"]["name"].as_str() else {
+                continue;
+            };
+            let pid = value_text(&event["pid"]);
+            match event["name"].as_str() {
+                Some("process_name") => {
+                    names.processes.insert(pid, name.to_string());
+                }
+                Some("thread_name") => {
+                    let tid = value_text(&event["tid"]);
+                    names.threads.insert((pid, tid), name.to_string());
+                }
+                _ => {}
+            }
+        }
+        names
+    }
+}
+
+/// Converts Chrome trace events into lines, pairing `B`/`E` duration events
+/// per thread so the end line can report the slice duration.
+struct ChromeTraceConverter {
+    names: TraceNames,
+    /// Open `B` events per (pid, tid): name and start timestamp (µs).
+    open_slices: HashMap<(String, String), Vec<(String, f64)>>,
+}
+
+impl ChromeTraceConverter {
+    fn convert(&mut self, event: &Value, line_number: usize) -> Option<TraceLogLine> {
+        let ph = event["ph"].as_str().unwrap_or("");
+        if ph == "M" {
+            return None;
+        }
+        let ts = event["ts"].as_f64()?;
+        let pid = value_text(&event["pid"]);
+        let tid = value_text(&event["tid"]);
+        let mut name = event["name"].as_str().unwrap_or("").to_string();
+        let category = event["cat"].as_str().unwrap_or("");
+
+        let slice_key = (pid.clone(), tid.clone());
+        let body = match ph {
+            "X" => event["dur"].as_f64().map_or_else(
+                || name.clone(),
+                |dur| format!("{name} ({})", format_duration_us(dur)),
+            ),
+            "B" => {
+                self.open_slices
+                    .entry(slice_key.clone())
+                    .or_default()
+                    .push((name.clone(), ts));
+                format!("{name} begin")
+            }
+            "E" => {
+                let opened = self
+                    .open_slices
+                    .get_mut(&slice_key)
+                    .and_then(std::vec::Vec::pop);
+                match opened {
+                    Some((begin_name, begin_ts)) => {
+                        if name.is_empty() {
+                            name = begin_name;
+                        }
+                        format!("{name} end ({})", format_duration_us(ts - begin_ts))
+                    }
+                    None => format!("{name} end"),
+                }
+            }
+            "C" => {
+                let values = event["args"].as_object().map_or_else(String::new, |args| {
+                    args.iter()
+                        .map(|(k, v)| format!("{k}={}", value_text(v)))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                });
+                format!("{name} {values}")
+            }
+            "i" | "I" | "n" | "R" => name.clone(),
+            "b" | "S" => format!("{name} async begin"),
+            "e" | "F" => format!("{name} async end"),
+            other => format!("{name} ({other})"),
+        };
+
+        let process = self.names.processes.get(&pid).cloned();
+        let thread = self.names.threads.get(&slice_key).cloned();
+        let thread_label = thread.clone().unwrap_or_else(|| format!("{pid}/{tid}"));
+
+        let mut fields = vec![
+            ("Phase".to_string(), ph.to_string()),
+            ("Name".to_string(), name),
+            ("PID".to_string(), pid),
+            ("TID".to_string(), tid),
+        ];
+        if !category.is_empty() {
+            fields.push(("Category".to_string(), category.to_string()));
+        }
+        if let Some(process) = &process {
+            fields.push(("Process".to_string(), process.clone()));
+        }
+        if let Some(thread) = &thread {
+            fields.push(("Thread".to_string(), thread.clone()));
+        }
+        if let Some(dur) = event["dur"].as_f64() {
+            fields.push(("Duration".to_string(), format_duration_us(dur)));
+        }
+        if let Some(args) = event["args"].as_object() {
+            fields.extend(
+                args.iter()
+                    .map(|(k, v)| (format!("args.{k}"), value_text(v))),
+            );
+        }
+
+        let mut facets = vec![("Thread", thread_label.clone())];
+        if !category.is_empty() {
+            facets.push(("Category", category.to_string()));
+        }
+        if let Some(process) = process {
+            facets.push(("Process", process));
+        }
+
+        Some(TraceLogLine {
+            raw_line: event.to_string(),
+            timestamp_ns: timestamp_ns_from_micros(ts),
+            message_text: format!("{thread_label}: {body}"),
+            fields,
+            facets,
+            line_number,
+        })
+    }
+}
+
+// ============================================================================
+// systrace / ftrace text
+// ============================================================================
+
+/// `TASK-PID (TGID) [CPU] FLAGS TIMESTAMP: EVENT: ARGS`; TGID and FLAGS are optional.
+static FTRACE_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(.+?)-(\d+)\s+(?:\(\s*[\d-]+\)\s+)?\[(\d+)\]\s+(?:\S{4,5}\s+)?(\d+\.\d+):\s+([^:\s]+):\s?(.*)$",
+    )
+    .expect("valid regex literal")
+});
+
+fn parse_ftrace_line(raw: &str, line_number: usize) -> Option<TraceLogLine> {
+    let caps = FTRACE_LINE.captures(raw).ok()??;
+    let group = |i: usize| caps.get(i).map_or("", |m| m.as_str());
+    let (task, pid, cpu, event, args) = (group(1), group(2), group(3), group(5), group(6));
+    let seconds: f64 = group(4).parse().ok()?;
+    let thread_label = format!("{task}-{pid}");
+
+    Some(TraceLogLine {
+        raw_line: raw.to_string(),
+        timestamp_ns: timestamp_ns_from_micros(seconds * 1_000_000.0),
+        message_text: format!("{thread_label}: {event}: {args}"),
+        fields: vec![
+            ("Task".to_string(), task.to_string()),
+            ("PID".to_string(), pid.to_string()),
+            ("CPU".to_string(), cpu.to_string()),
+            ("Event".to_string(), event.to_string()),
+            ("Args".to_string(), args.to_string()),
+        ],
+        facets: vec![
+            ("Thread", thread_label),
+            ("Event", event.to_string()),
+            ("CPU", cpu.to_string()),
+        ],
+        line_number,
+    })
+}
+
+fn parse_ftrace_text(text: &str, first_line_number: usize, out: &mut Vec<TraceLogLine>) {
+    for (i, raw) in text.lines().enumerate() {
+        if raw.starts_with('#') {
+            continue;
+        }
+        if let Some(line) = parse_ftrace_line(raw, first_line_number + i) {
+            out.push(line);
+        }
+    }
+}
+
+// ============================================================================
+// TraceFileType (InputFileType + TextFileType)
+// ============================================================================
+
+/// Reader for app and system traces.
+///
+/// Accepts Chrome trace event JSON as written by `chrome://tracing` and by
+/// Perfetto's JSON export — either `{"traceEvents": [...]}` or a bare event
+/// array — and systrace/ftrace text as written by `traceconv systrace`.
+/// Ftrace text embedded in a Chrome trace's `systemTraceEvents` is imported
+/// alongside the JSON events. Metadata events only name processes and threads
+/// and produce no lines.
+pub struct TraceFileType {
+    records: std::vec::IntoIter<TraceLogLine>,
+    file_size: u64,
+    bytes_read: u64,
+}
+
+impl TraceFileType {
+    fn parse(contents: &str) -> anyhow::Result<Vec<TraceLogLine>> {
+        let mut records = Vec::new();
+        let trimmed = contents.trim_start();
+        if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+            parse_ftrace_text(contents, 1, &mut records);
+            return Ok(records);
+        }
+
+        let root: Value = serde_json::from_str(contents)
+            .map_err(|e| anyhow::anyhow!("Failed to parse trace JSON: {e}"))?;
+        let events = match &root {
+            Value::Array(events) => events.as_slice(),
+            Value::Object(map) => map
+                .get("traceEvents")
+                .and_then(Value::as_array)
+                .map_or(&[][..], Vec::as_slice),
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => &[][..],
+        };
+
+        let mut converter = ChromeTraceConverter {
+            names: TraceNames::collect(events),
+            open_slices: HashMap::new(),
+        };
+        records.extend(
+            events
+                .iter()
+                .enumerate()
+                .filter_map(|(i, event)| converter.convert(event, i + 1)),
+        );
+        if let Some(systrace) = root["systemTraceEvents"].as_str() {
+            parse_ftrace_text(systrace, events.len() + 1, &mut records);
+        }
+        Ok(records)
+    }
+}
+
+impl InputFileType for TraceFileType {
+    type LineType = TraceLogLine;
+
+    const DISPLAY_NAME: &'static str = "Chrome/Perfetto Trace";
+    const FILE_EXTENSIONS: &'static [&'static str] = &["json", "trace", "txt"];
+
+    fn open(
+        path: &Path,
+        _config: (),
+        _file_state: std::sync::Arc<TraceFileState>,
+    ) -> anyhow::Result<Self> {
+        let file_size = metadata(path)
+            .map_err(|e| anyhow::anyhow!("Failed to stat {}: {e}", path.display()))?
+            .len();
+        let mut contents = String::new();
+        LogReader::open(path)?
+            .read_to_string(&mut contents)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))?;
+
+        Ok(Self {
+            records: Self::parse(&contents)?.into_iter(),
+            file_size,
+            bytes_read: 0,
+        })
+    }
+
+    fn read(&mut self, lines_to_read: usize) -> anyhow::Result<Vec<Self::LineType>> {
+        let batch: Vec<_> = self.records.by_ref().take(lines_to_read).collect();
+        if batch.is_empty() {
+            self.bytes_read = self.file_size;
+        } else {
+            let remaining = self.records.len();
+            let total = remaining + batch.len();
+            if total > 0 {
+                self.bytes_read =
+                    self.file_size - (self.file_size * remaining as u64 / total as u64);
+            }
+        }
+        Ok(batch)
+    }
+
+    fn bytes_consumed(&self) -> u64 {
+        self.bytes_read
+    }
+}
+
+impl TextFileType for TraceFileType {
+    /// Returns `true` for Chrome trace JSON (a `traceEvents` key, or an event
+    /// array with `ph` and `ts` keys) and for ftrace text with a `# tracer:`
+    /// header.
+    fn looks_like(file: &mut dyn std::io::Read) -> bool {
+        let mut buf = [0u8; 4096];
+        let n = file.read(&mut buf).unwrap_or(0);
+        let sample = String::from_utf8_lossy(&buf[..n]);
+        let trimmed = sample.trim_start();
+        if trimmed.starts_with('{') && sample.contains("\"traceEvents\"") {
+            return true;
+        }
+        if trimmed.starts_with('[') {
+            return sample.contains("\"ph\"") && sample.contains("\"ts\"");
+        }
+        trimmed.starts_with("# tracer:")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHROME_TRACE: &str = r##"{
+        "traceEvents": [
+            {"name": "thread_name", "ph": "M", "pid": 1, "tid": 2, "args": {"name": "RenderThread"}},
+            {"name": "draw", "cat": "gfx", "ph": "X", "ts": 1000, "dur": 2500, "pid": 1, "tid": 2},
+            {"name": "load", "cat": "io", "ph": "B", "ts": 2000, "pid": 1, "tid": 3},
+            {"ph": "E", "ts": 7000, "pid": 1, "tid": 3},
+            {"name": "heap", "ph": "C", "ts": 8000, "pid": 1, "tid": 3, "args": {"used": 42}}
+        ],
+        "systemTraceEvents": "# tracer: nop\n          <idle>-0     (-----) [001] d..2  0.009000: sched_switch: prev_comm=swapper\n"
+    }"##;
+
+    #[test]
+    fn test_looks_like() {
+        assert!(TraceFileType::looks_like(&mut std::io::Cursor::new(
+            CHROME_TRACE
+        )));
+        assert!(TraceFileType::looks_like(&mut std::io::Cursor::new(
+            r#"[{"name": "a", "ph": "i", "ts": 1, "pid": 1, "tid": 1}]"#
+        )));
+        assert!(TraceFileType::looks_like(&mut std::io::Cursor::new(
+            "# tracer: nop\n#\n"
+        )));
+        assert!(!TraceFileType::looks_like(&mut std::io::Cursor::new(
+            r#"{"resourceLogs": []}"#
+        )));
+    }
+
+    #[test]
+    fn test_parse_chrome_trace() {
+        let lines = TraceFileType::parse(CHROME_TRACE).expect("parse");
+        let messages: Vec<_> = lines.iter().map(LineType::message).collect();
+        assert_eq!(
+            messages,
+            [
+                "RenderThread: draw (2.500 ms)",
+                "1/3: load begin",
+                "1/3: load end (5.000 ms)",
+                "1/3: heap used=42",
+                "<idle>-0: sched_switch: prev_comm=swapper",
+            ]
+        );
+        assert_eq!(lines[0].line_number, 2);
+        assert_eq!(lines[0].timestamp_ns, 1_000_000);
+        assert_eq!(lines[4].timestamp_ns, 9_000_000);
+        assert!(lines[0].facets().contains(&("Category", "gfx".to_string())));
+    }
+
+    #[test]
+    fn test_parse_ftrace_text() {
+        let text = "# tracer: nop\n#\n  surfaceflinger-612   [002] ...1  1234.500000: tracing_mark_write: B|612|onMessageReceived\n";
+        let lines = TraceFileType::parse(text).expect("parse");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].line_number, 3);
+        assert_eq!(
+            lines[0].message(),
+            "surfaceflinger-612: tracing_mark_write: B|612|onMessageReceived"
+        );
+        assert!(lines[0]
+            .fields()
+            .contains(&("CPU".to_string(), "002".to_string())));
+    }
+}
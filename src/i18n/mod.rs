@@ -0,0 +1,293 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! String catalog for translated UI text
+//!
+//! UI strings are written in English and wrapped in [`tr!`](crate::tr). The
+//! English text doubles as the catalog key, so English needs no catalog and a
+//! string without a translation falls back to English instead of showing a
+//! key. Catalogs live in `assets/i18n/<code>.json` and map English text to
+//! the translation.
+//!
+//! Placeholders are written as `{name}` and filled in by `tr!`:
+//!
+//! ```ignore
+//! ui.label(tr!("{count} lines", count = n));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::LazyLock;
+
+/// Language of the user interface
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    /// Name of the language in that language, as shown in the language menu
+    #[must_use]
+    pub const fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::German,
+            _ => Self::English,
+        }
+    }
+
+    /// Translation catalog, or `None` for English
+    fn catalog(self) -> Option<&'static HashMap<String, String>> {
+        match self {
+            Self::English => None,
+            Self::German => Some(&GERMAN),
+        }
+    }
+}
+
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(Locale::English as u8);
+
+static GERMAN: LazyLock<HashMap<String, String>> =
+    LazyLock::new(|| parse_catalog(include_str!("../../assets/i18n/de.json")));
+
+fn parse_catalog(json: &str) -> HashMap<String, String> {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        tracing::error!("Invalid translation catalog: {e}");
+        HashMap::new()
+    })
+}
+
+/// Switch the language used by [`tr!`](crate::tr) from the next frame on
+pub fn set_locale(locale: Locale) {
+    CURRENT_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// The language currently used by [`tr!`](crate::tr)
+#[must_use]
+pub fn locale() -> Locale {
+    Locale::from_u8(CURRENT_LOCALE.load(Ordering::Relaxed))
+}
+
+/// Translate `text` into the current language, falling back to `text`
+#[must_use]
+pub fn translate(text: &'static str) -> &'static str {
+    locale()
+        .catalog()
+        .and_then(|catalog| catalog.get(text))
+        .map_or(text, String::as_str)
+}
+
+/// Replace each `{name}` in `template` with its value
+///
+/// The template is scanned once from left to right, so braces inside a
+/// value (a path, a JSON snippet) are never taken for placeholders.
+#[must_use]
+pub fn fill_placeholders(template: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    use std::fmt::Write;
+
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (value, close))
+        });
+        if let Some((value, close)) = value {
+            let _ = write!(text, "{value}");
+            rest = &after[close + 1..];
+        } else {
+            text.push('{');
+            rest = after;
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Translate a UI string literal into the current language
+///
+/// `tr!("Open")` yields a `&'static str`. With arguments, the placeholders are
+/// filled in and a `String` is returned; like `format!`, a bare name stands
+/// for a variable of that name: `tr!("{n} lines in {file}", n = count, file)`.
+#[macro_export]
+macro_rules! tr {
+    ($text:literal) => {
+        $crate::i18n::translate($text)
+    };
+    ($text:literal, $($name:ident $(= $value:expr)?),+ $(,)?) => {{
+        // The `{name}` placeholders are filled at runtime, not by `format!`
+        #[allow(clippy::literal_string_with_formatting_args)]
+        let template = $crate::i18n::translate($text);
+        $crate::i18n::fill_placeholders(
+            template,
+            &[$((
+                stringify!($name),
+                &$crate::__tr_value!($name $(, $value)?) as &dyn std::fmt::Display,
+            )),+],
+        )
+    }};
+}
+
+/// Value of one `tr!` argument; not meant to be used directly
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tr_value {
+    ($name:ident) => {
+        $name
+    };
+    ($name:ident, $value:expr) => {
+        $value
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fancy_regex::Regex;
+
+    /// Placeholder names used in `text`, sorted
+    fn placeholders(text: &str) -> Vec<String> {
+        let re = Regex::new(r"\{(\w+)\}").expect("valid regex literal");
+        let mut names: Vec<String> = re
+            .captures_iter(text)
+            .filter_map(Result::ok)
+            .map(|c| c[1].to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Resolve the escapes used in UI string literals
+    fn unescape(literal: &str) -> String {
+        let mut text = String::with_capacity(literal.len());
+        let mut chars = literal.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('t') => text.push('\t'),
+                // Line continuation: skip the newline and the next line's indentation
+                Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+                Some(other) => text.push(other),
+                None => {}
+            }
+        }
+        text
+    }
+
+    /// Every string literal wrapped in `tr!` below `dir`
+    fn tr_literals(dir: &std::path::Path, out: &mut Vec<String>) {
+        let re = Regex::new(r#"(?s)\btr!\(\s*"((?:[^"\\]|\\.)*)""#).expect("valid regex literal");
+        for entry in std::fs::read_dir(dir).expect("readable source dir") {
+            let path = entry.expect("readable dir entry").path();
+            if path.ends_with("i18n") {
+                // Skip the usage examples in this module's docs
+            } else if path.is_dir() {
+                tr_literals(&path, out);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                let source = std::fs::read_to_string(&path).expect("readable source file");
+                out.extend(
+                    re.captures_iter(&source)
+                        .filter_map(Result::ok)
+                        .map(|c| unescape(&c[1])),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_falls_back_to_english() {
+        assert_eq!(Locale::English.catalog(), None);
+        assert_eq!(translate("no such catalog entry"), "no such catalog entry");
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(
+            fill_placeholders(
+                "{count} lines in {file}",
+                &[("count", &3), ("file", &"a.log")]
+            ),
+            "3 lines in a.log"
+        );
+    }
+
+    #[test]
+    fn test_fill_placeholders_leaves_braces_in_values_alone() {
+        assert_eq!(
+            fill_placeholders(
+                "Failed to load {path}: {e}",
+                &[("path", &"{e}/x.log"), ("e", &"bad {path}")]
+            ),
+            "Failed to load {e}/x.log: bad {path}"
+        );
+        assert_eq!(
+            fill_placeholders("{ {unknown} {n}}", &[("n", &1)]),
+            "{ {unknown} 1}"
+        );
+    }
+
+    #[test]
+    fn test_german_catalog_keeps_placeholders() {
+        assert!(!GERMAN.is_empty());
+        for (english, german) in GERMAN.iter() {
+            assert!(!german.is_empty(), "empty translation for {english:?}");
+            assert_eq!(
+                placeholders(english),
+                placeholders(german),
+                "in {english:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_ui_string_has_german_translation() {
+        let mut literals = Vec::new();
+        tr_literals(
+            &std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut literals,
+        );
+        let missing: Vec<&String> = literals
+            .iter()
+            .filter(|text| !GERMAN.contains_key(*text))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "missing German translations: {missing:#?}"
+        );
+    }
+}
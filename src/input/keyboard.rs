@@ -252,6 +252,7 @@ pub enum ShortcutAction {
     MoveUp,
     MoveDown,
     ToggleBookmark,
+    QuickBookmark,
     FocusSearch,
     NewFilterTab,
     NewBookmarksTab,
@@ -261,6 +262,7 @@ pub enum ShortcutAction {
     PageUp,
     PageDown,
     OpenFile,
+    ReloadFiles,
     FocusPaneLeft,
     FocusPaneDown,
     FocusPaneUp,
@@ -268,6 +270,9 @@ pub enum ShortcutAction {
     CycleTab,
     ReverseCycleTab,
     RenameFilter,
+    NextSameTemplate,
+    PreviousSameTemplate,
+    ErrorsView,
 }
 
 impl ShortcutAction {
@@ -277,6 +282,7 @@ impl ShortcutAction {
             Self::MoveUp,
             Self::MoveDown,
             Self::ToggleBookmark,
+            Self::QuickBookmark,
             Self::FocusSearch,
             Self::NewFilterTab,
             Self::NewBookmarksTab,
@@ -286,6 +292,7 @@ impl ShortcutAction {
             Self::PageUp,
             Self::PageDown,
             Self::OpenFile,
+            Self::ReloadFiles,
             Self::FocusPaneLeft,
             Self::FocusPaneDown,
             Self::FocusPaneUp,
@@ -293,6 +300,9 @@ impl ShortcutAction {
             Self::CycleTab,
             Self::ReverseCycleTab,
             Self::RenameFilter,
+            Self::NextSameTemplate,
+            Self::PreviousSameTemplate,
+            Self::ErrorsView,
         ]
     }
 
@@ -301,6 +311,7 @@ impl ShortcutAction {
             Self::MoveUp => "Move Selection Up",
             Self::MoveDown => "Move Selection Down",
             Self::ToggleBookmark => "Toggle Bookmark",
+            Self::QuickBookmark => "Quick Bookmark",
             Self::FocusSearch => "Focus Search Input",
             Self::NewFilterTab => "New Filter Tab",
             Self::NewBookmarksTab => "New Bookmarks Tab",
@@ -310,6 +321,7 @@ impl ShortcutAction {
             Self::PageUp => "Page Up",
             Self::PageDown => "Page Down",
             Self::OpenFile => "Open File",
+            Self::ReloadFiles => "Reload Files",
             Self::FocusPaneLeft => "Focus Pane Left",
             Self::FocusPaneDown => "Focus Pane Down",
             Self::FocusPaneUp => "Focus Pane Up",
@@ -317,6 +329,9 @@ impl ShortcutAction {
             Self::CycleTab => "Cycle to Next Tab",
             Self::ReverseCycleTab => "Cycle to Previous Tab",
             Self::RenameFilter => "Rename Filter",
+            Self::NextSameTemplate => "Next Same Message",
+            Self::PreviousSameTemplate => "Previous Same Message",
+            Self::ErrorsView => "Errors & Fatals View",
         }
     }
 
@@ -325,6 +340,7 @@ impl ShortcutAction {
             Self::MoveUp => "Move to the previous log line in the active view",
             Self::MoveDown => "Move to the next log line in the active view",
             Self::ToggleBookmark => "Add or remove a bookmark on the selected line",
+            Self::QuickBookmark => "Bookmark the selected line, named from the quick bookmark template",
             Self::FocusSearch => "Jump to the search input field (filter tabs only). Press Enter to return focus to logs.",
             Self::NewFilterTab => "Create a new filter tab with search focused",
             Self::NewBookmarksTab => "Create a new bookmarks tab next to the current tab",
@@ -334,6 +350,7 @@ impl ShortcutAction {
             Self::PageUp => "Jump up by one page of log lines",
             Self::PageDown => "Jump down by one page of log lines",
             Self::OpenFile => "Open a file dialog to load a new log file",
+            Self::ReloadFiles => "Re-read all open files from disk, keeping filters, bookmarks and selection",
             Self::FocusPaneLeft => "Move focus to the pane on the left (Vim-style: Shift+H)",
             Self::FocusPaneDown => "Move focus to the pane below (Vim-style: Shift+J)",
             Self::FocusPaneUp => "Move focus to the pane above (Vim-style: Shift+K)",
@@ -341,6 +358,9 @@ impl ShortcutAction {
             Self::CycleTab => "Cycle to the next tab in the active pane",
             Self::ReverseCycleTab => "Cycle to the previous tab in the active pane",
             Self::RenameFilter => "Open rename dialog for the current filter tab",
+            Self::NextSameTemplate => "Jump to the next line with the same message template as the selected one (Vim-style: *)",
+            Self::PreviousSameTemplate => "Jump to the previous line with the same message template as the selected one (Vim-style: #)",
+            Self::ErrorsView => "Open or focus the built-in tab showing error/fatal lines (by log level or error keywords)",
         }
     }
 
@@ -349,6 +369,7 @@ impl ShortcutAction {
             Self::MoveUp => "k",
             Self::MoveDown => "j",
             Self::ToggleBookmark => "Space",
+            Self::QuickBookmark => "m", // Vim-style mark
             Self::FocusSearch => "Ctrl+l",
             Self::NewFilterTab => "Ctrl+t",
             Self::NewBookmarksTab => "Ctrl+b",
@@ -358,6 +379,7 @@ impl ShortcutAction {
             Self::PageUp => "PageUp",
             Self::PageDown => "PageDown",
             Self::OpenFile => "Ctrl+o",
+            Self::ReloadFiles => "\u{E005}", // F5
             Self::FocusPaneLeft => "H", // Uppercase letters for Vim-style pane navigation
             Self::FocusPaneDown => "J",
             Self::FocusPaneUp => "K",
@@ -365,6 +387,10 @@ impl ShortcutAction {
             Self::CycleTab => "Ctrl+Tab",
             Self::ReverseCycleTab => "Ctrl+Shift+Tab",
             Self::RenameFilter => "\u{E002}", // F2
+            // egui has no key for '*' and '#', so use Vim's search-next keys instead
+            Self::NextSameTemplate => "n",
+            Self::PreviousSameTemplate => "N",
+            Self::ErrorsView => "Ctrl+e",
         }
     }
 }
@@ -19,6 +19,7 @@ pub mod config;
 pub mod core;
 pub mod export;
 pub mod filetype;
+pub mod i18n;
 pub mod input;
 pub mod parser;
 pub mod ui;
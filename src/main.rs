@@ -14,10 +14,11 @@
 ///
 /// You should have received a copy of the GNU General Public License
 /// along with this program.  If not, see <https://www.gnu.org/licenses/>.
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use egui::IconData;
 use logcrab::ui::app::LogCrabApp;
-use std::path::PathBuf;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "ram-profiling")]
 #[global_allocator]
@@ -28,11 +29,25 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 #[command(author = "LogCrab Team")]
 #[command(version = "0.1.0")]
 #[command(about = "Analyze log files with anomaly detection and pattern matching", long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path(s) to log file(s) to open
     #[arg(value_name = "FILE")]
     files: Vec<PathBuf>,
 
+    /// Print the detected format, year, line counts and time range of FILE
+    /// and exit without opening the UI (non-zero exit if nothing parses)
+    #[arg(long, value_name = "FILE")]
+    detect: Option<PathBuf>,
+
+    /// Score FILE and print each line's anomaly score breakdown (template,
+    /// per-scorer components, composite score) as NDJSON, without opening the UI
+    #[arg(long, value_name = "FILE")]
+    export_scores: Option<PathBuf>,
+
     /// Path for the DHAT heap profiling output (only used when built with --features ram-profiling)
     #[cfg(feature = "ram-profiling")]
     #[arg(
@@ -43,7 +58,313 @@ struct Args {
     profile_output: PathBuf,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Decode a log file without opening the UI and write its lines as text
+    ///
+    /// Each output line is the timestamp followed by the message as shown in
+    /// the log table, so pcap, btsnoop and DLT files can be searched with grep.
+    Convert {
+        /// Log file to convert (format is auto-detected)
+        #[arg(value_name = "INPUT")]
+        input: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
+    },
+    /// Score log files without opening the UI and print the lines that match
+    ///
+    /// Uses the parsers and anomaly scoring of the viewer, so CI jobs flag the
+    /// same lines a filter tab would show. Exits with 0 if a line was printed,
+    /// 1 if none was and 2 on errors, like grep.
+    Scan {
+        /// Log file(s) to scan (format is auto-detected)
+        #[arg(value_name = "FILE", required = true)]
+        files: Vec<PathBuf>,
+
+        /// Only print lines matching REGEX in their message or raw text
+        #[arg(short, long, value_name = "REGEX")]
+        filter: Option<String>,
+
+        /// Leave out lines matching REGEX in their message or raw text
+        #[arg(short, long, value_name = "REGEX")]
+        exclude: Option<String>,
+
+        /// Match the regexes case-sensitively instead of ignoring case
+        #[arg(short = 's', long)]
+        case_sensitive: bool,
+
+        /// Only print lines with at least this anomaly score (0-100)
+        #[arg(long, value_name = "SCORE", default_value_t = 0.0)]
+        min_score: f64,
+
+        /// Print `timestamp score message` text or one JSON object per line
+        #[arg(long, value_enum, default_value_t = ScanFormat::Text)]
+        format: ScanFormat,
+    },
+    /// Write a synthetic logcat log with injected anomalies and bursts
+    ///
+    /// The same options always give the same log, for tests of the scorer,
+    /// histogram and filters and for demos.
+    #[command(hide = true)]
+    Generate {
+        /// Output file (default: stdout)
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
+
+        /// Number of lines, anomalies and bursts included
+        #[arg(long, default_value_t = 10_000)]
+        lines: usize,
+
+        /// Seed of the generator
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+
+        /// Time of the first line, e.g. 2024-06-15T12:00:00
+        #[arg(long, value_name = "TIME")]
+        start: Option<chrono::NaiveDateTime>,
+
+        /// Mean milliseconds between two lines outside of bursts
+        #[arg(long, value_name = "MS", default_value_t = 100)]
+        interval: u64,
+
+        /// Message of the regular lines as `LEVEL Tag: text`, `{n}` becoming
+        /// a random number (repeatable; earlier ones are logged more often)
+        #[arg(long = "template", value_name = "TEMPLATE")]
+        templates: Vec<String>,
+
+        /// Number of error lines spread evenly over the log
+        #[arg(long, default_value_t = 5)]
+        anomalies: usize,
+
+        /// Number of bursts spread evenly over the log
+        #[arg(long, default_value_t = 0)]
+        bursts: usize,
+
+        /// Lines per burst, logged one millisecond apart
+        #[arg(long, default_value_t = 200)]
+        burst_lines: usize,
+    },
+}
+
+/// Output format of `logcrab scan`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ScanFormat {
+    Text,
+    Json,
+}
+
+/// Whether `e` only means the reader of the output went away, e.g. `| head`
+fn is_broken_pipe(e: &anyhow::Error) -> bool {
+    e.chain().any(|cause| {
+        let kind = cause
+            .downcast_ref::<std::io::Error>()
+            .map(std::io::Error::kind)
+            .or_else(|| {
+                cause
+                    .downcast_ref::<serde_json::Error>()
+                    .and_then(serde_json::Error::io_error_kind)
+            });
+        kind == Some(std::io::ErrorKind::BrokenPipe)
+    })
+}
+
+/// Treat a closed output as the normal end of a command
+fn ignore_broken_pipe(result: anyhow::Result<()>) -> anyhow::Result<()> {
+    match result {
+        Err(e) if is_broken_pipe(&e) => Ok(()),
+        result => result,
+    }
+}
+
+/// Run `logcrab convert`
+fn convert(input: &Path, output: Option<&Path>) -> anyhow::Result<()> {
+    let mut out: BufWriter<Box<dyn Write>> = match output {
+        Some(path) => BufWriter::new(Box::new(std::fs::File::create(path)?)),
+        None => BufWriter::new(Box::new(std::io::stdout().lock())),
+    };
+    ignore_broken_pipe(
+        logcrab::core::log_store::convert_dispatch(input, &mut out).and_then(|()| Ok(out.flush()?)),
+    )
+}
+
+/// Compile a `logcrab scan` regex the way the filter bar does
+fn scan_regex(pattern: &str, case_sensitive: bool) -> anyhow::Result<fancy_regex::Regex> {
+    let pattern = if case_sensitive {
+        pattern.to_string()
+    } else {
+        format!("(?i){pattern}")
+    };
+    Ok(logcrab::core::slow_filter::compile_bounded(&pattern)?)
+}
+
+/// Run `logcrab scan`, returning whether any line was printed
+fn scan(
+    files: &[PathBuf],
+    filter: Option<&str>,
+    exclude: Option<&str>,
+    case_sensitive: bool,
+    min_score: f64,
+    format: ScanFormat,
+) -> anyhow::Result<bool> {
+    use anyhow::Context as _;
+    let options = logcrab::export::ScanOptions {
+        filter: filter
+            .map(|p| scan_regex(p, case_sensitive))
+            .transpose()
+            .context("invalid --filter")?,
+        exclude: exclude
+            .map(|p| scan_regex(p, case_sensitive))
+            .transpose()
+            .context("invalid --exclude")?,
+        min_score,
+        json: format == ScanFormat::Json,
+        with_file_name: files.len() > 1,
+    };
+
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let mut matched = 0;
+    let mut had_error = false;
+    for path in files {
+        match logcrab::core::log_store::scan_dispatch(path, &options, &mut out) {
+            Ok(n) => matched += n,
+            // Lines were printed before the reader went away
+            Err(e) if is_broken_pipe(&e) => return Ok(true),
+            Err(e) => {
+                eprintln!("logcrab scan: {}: {e:#}", path.display());
+                had_error = true;
+            }
+        }
+    }
+    if let Err(e) = out.flush() {
+        if e.kind() == std::io::ErrorKind::BrokenPipe {
+            return Ok(true);
+        }
+        return Err(e.into());
+    }
+    if had_error {
+        anyhow::bail!("not every file could be scanned");
+    }
+    Ok(matched > 0)
+}
+
+/// Run `logcrab generate`
+fn generate(
+    log: &logcrab::core::synthetic::SyntheticLog,
+    output: Option<&Path>,
+) -> anyhow::Result<()> {
+    let mut out: BufWriter<Box<dyn Write>> = match output {
+        Some(path) => BufWriter::new(Box::new(std::fs::File::create(path)?)),
+        None => BufWriter::new(Box::new(std::io::stdout().lock())),
+    };
+    ignore_broken_pipe(
+        log.write(&mut out)
+            .and_then(|()| out.flush())
+            .map_err(Into::into),
+    )
+}
+
+/// Run `logcrab --detect`
+fn detect(input: &Path) -> anyhow::Result<()> {
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    ignore_broken_pipe(
+        logcrab::core::log_store::detect_dispatch(input, &mut out).and_then(|()| Ok(out.flush()?)),
+    )
+}
+
+/// Run `logcrab --export-scores`
+fn export_scores(input: &Path) -> anyhow::Result<()> {
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    ignore_broken_pipe(
+        logcrab::core::log_store::export_scores_dispatch(input, &mut out)
+            .and_then(|()| Ok(out.flush()?)),
+    )
+}
+
 fn main() -> eframe::Result<()> {
+    let args = Args::parse();
+
+    match args.command {
+        Some(Command::Convert { input, output }) => {
+            if let Err(e) = convert(&input, output.as_deref()) {
+                eprintln!("logcrab convert: {}: {e:#}", input.display());
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Command::Scan {
+            files,
+            filter,
+            exclude,
+            case_sensitive,
+            min_score,
+            format,
+        }) => match scan(
+            &files,
+            filter.as_deref(),
+            exclude.as_deref(),
+            case_sensitive,
+            min_score,
+            format,
+        ) {
+            Ok(true) => Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("logcrab scan: {e:#}");
+                std::process::exit(2);
+            }
+        },
+        Some(Command::Generate {
+            output,
+            lines,
+            seed,
+            start,
+            interval,
+            templates,
+            anomalies,
+            bursts,
+            burst_lines,
+        }) => {
+            let defaults = logcrab::core::synthetic::SyntheticLog::default();
+            let log = logcrab::core::synthetic::SyntheticLog {
+                seed,
+                lines,
+                start: start.unwrap_or(defaults.start),
+                interval_ms: interval,
+                templates,
+                anomalies,
+                bursts,
+                burst_lines,
+            };
+            if let Err(e) = generate(&log, output.as_deref()) {
+                eprintln!("logcrab generate: {e:#}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        None => {
+            if let Some(input) = &args.detect {
+                if let Err(e) = detect(input) {
+                    eprintln!("logcrab --detect: {}: {e:#}", input.display());
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            if let Some(input) = &args.export_scores {
+                if let Err(e) = export_scores(input) {
+                    eprintln!("logcrab --export-scores: {}: {e:#}", input.display());
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            run_gui(args)
+        }
+    }
+}
+
+/// Open the viewer with the files given on the command line
+fn run_gui(args: Args) -> eframe::Result<()> {
     println!(
         r#"
                     %@@@@@@@                                           -@@@@@@@
@@ -104,13 +425,12 @@ fn main() -> eframe::Result<()> {
 
     #[cfg(feature = "ram-profiling")]
     let _profiler = {
-        let args_early = Args::parse();
         tracing::info!(
             "RAM profiling enabled, output: {}",
-            args_early.profile_output.display()
+            args.profile_output.display()
         );
         dhat::Profiler::builder()
-            .file_name(args_early.profile_output)
+            .file_name(args.profile_output.clone())
             .build()
     };
 
@@ -119,12 +439,13 @@ fn main() -> eframe::Result<()> {
         tracing::info!("CPU profiling enabled with Tracy - run Tracy profiler to connect");
     }
 
-    let args = Args::parse();
-
     if !args.files.is_empty() {
         tracing::info!("Opening {} file(s) from command line", args.files.len());
         for file in &args.files {
-            tracing::info!("  - {}", file.display());
+            match logcrab::core::log_store::detect_format_name(file) {
+                Ok(format) => tracing::info!("  - {} ({format})", file.display()),
+                Err(_) => tracing::info!("  - {}", file.display()),
+            }
         }
     }
 
@@ -143,9 +464,12 @@ fn main() -> eframe::Result<()> {
         ..Default::default()
     };
 
-    eframe::run_native(
+    let result = eframe::run_native(
         "LogCrab - Log Anomaly Explorer",
         native_options,
         Box::new(move |cc| Ok(Box::new(LogCrabApp::new(cc, args.files)))),
-    )
+    );
+    // Config changes are saved in the background; don't lose the last ones
+    logcrab::config::GlobalConfig::flush();
+    result
 }
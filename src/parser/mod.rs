@@ -118,6 +118,32 @@ pub fn normalize_message(message: &str) -> String {
     normalized.trim().to_string()
 }
 
+/// Placeholders inserted by [`normalize_message`], plus the collapsed whitespace
+static TEMPLATE_TOKEN_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<UUID>|<URL>|<HEX>|<NUM>| ").expect("valid regex literal"));
+
+/// Build a regex matching the messages whose [`normalize_message`] result is `template`.
+///
+/// Templates are lowercased, so the pattern is meant for case-insensitive matching.
+pub fn template_to_pattern(template: &str) -> String {
+    let mut pattern = String::with_capacity(template.len() * 2);
+    let mut literal_start = 0;
+    for token in TEMPLATE_TOKEN_PATTERN.find_iter(template).flatten() {
+        let literal = &template[literal_start..token.start()];
+        pattern.push_str(&fancy_regex::escape(literal));
+        pattern.push_str(match token.as_str() {
+            "<UUID>" => r"[0-9a-f]{8}(?:-[0-9a-f]{4}){3}-[0-9a-f]{12}",
+            "<URL>" => r"https?://\S+",
+            "<HEX>" => r"(?:0x[0-9a-f]+|[0-9a-f]{8,})",
+            "<NUM>" => r"\d+",
+            _ => r"\s+",
+        });
+        literal_start = token.end();
+    }
+    pattern.push_str(&fancy_regex::escape(&template[literal_start..]));
+    pattern
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +171,19 @@ mod tests {
         let normalized = normalize_message(msg);
         assert!(normalized.contains("<URL>"));
     }
+
+    #[test]
+    fn test_template_to_pattern_matches_original() {
+        let msg = "Retry 3 of 5 for [job-7] at 0xdeadbeef";
+        let template = normalize_message(msg);
+        let regex = Regex::new(&format!("(?i){}", template_to_pattern(&template)))
+            .expect("template pattern is a valid regex");
+        assert!(regex.is_match(msg).expect("match should not error"));
+        assert!(regex
+            .is_match("retry 10 of 12 for [job-9] at 0x1234")
+            .expect("match should not error"));
+        assert!(!regex
+            .is_match("Retry 3 for [job-7] at 0xdeadbeef")
+            .expect("match should not error"));
+    }
 }
@@ -1,16 +1,36 @@
 use super::windows;
 use super::ToastManager;
 
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::anomaly;
+use crate::config::persist::{ConfigReload, ConfigWatcher};
 use crate::config::session_history::{RecordedSession, SessionHistory};
-use crate::config::GlobalConfig;
+use crate::config::settings_bundle::SettingsBundle;
+use crate::config::{GlobalConfig, TimestampFormat};
+use crate::core::container_split::{self, ContainerPrefix};
+use crate::core::crab_lock::Takeover;
+use crate::core::file_watcher::FileWatcher;
 use crate::core::histogram_worker::HistogramWorker;
-use crate::core::log_store::all_file_extensions;
+use crate::core::level_rule::{self, LevelRule};
+use crate::core::log_store::{
+    all_file_extensions, detect_format_name, file_type_filters, parser_choices,
+};
+use crate::core::permalink::Permalink;
+use crate::core::run_history::RunHistory;
+use crate::core::segment::Segment;
+use crate::core::session::{ImportedSession, Suppression, PROJECT_EXTENSION};
+use crate::core::slow_filter;
 use crate::core::ScoringConfig;
-use crate::core::{FilterWorker, LogStore};
+use crate::core::{CrabProject, FilterWorker, LogStore};
+use crate::filetype::journal::JournalFileType;
+use crate::i18n::{self, Locale};
 use crate::input::{KeyboardBindings, ShortcutAction};
+use crate::parser::format_time_diff;
+use crate::tr;
+use crate::ui::raw_panel::RawSourcePanel;
 use crate::ui::tabs::{BookmarksView, HighlightsView};
 use crate::ui::CrabSession;
 use egui::text::LayoutJob;
@@ -43,9 +63,27 @@ pub struct LogCrabApp {
     /// Whether to show the about window
     show_about_window: bool,
 
+    /// Whether to show the activity log window
+    show_activity_log: bool,
+
     /// Sidecar settings window (None when closed)
     sidecar_settings_window: Option<windows::SidecarSettingsWindow>,
 
+    /// Anomaly scorer weights window (None when closed)
+    scorer_settings_window: Option<windows::ScorerSettingsWindow>,
+
+    /// Go to permalink dialog (None when closed)
+    go_to_permalink_window: Option<windows::GoToPermalinkWindow>,
+
+    /// Ranked overview of a folder of logs (None when closed)
+    folder_analysis_window: Option<windows::FolderAnalysisWindow>,
+
+    /// First-run onboarding overlay (None once dismissed)
+    onboarding_window: Option<windows::OnboardingWindow>,
+
+    /// Raw file lines around the selection, shown with `show_raw_panel`
+    raw_panel: RawSourcePanel,
+
     /// Global configuration (shortcuts, favorites, etc.)
     global_config: GlobalConfig,
 
@@ -61,6 +99,23 @@ pub struct LogCrabApp {
     /// Pending source removal (index of source to remove)
     pending_source_removal: Option<u64>,
 
+    /// Pending re-parse of a source with a user-chosen format slug
+    pending_reparse: Option<(u64, &'static str)>,
+
+    /// Watches the session's files for changes on disk
+    file_watcher: FileWatcher,
+    /// Picks up config changes saved by other instances or by hand
+    config_watcher: ConfigWatcher,
+
+    /// Files changed on disk since they were loaded, shown in a banner
+    changed_files: BTreeSet<PathBuf>,
+
+    /// Files reloaded automatically whenever they change on disk
+    followed_files: HashSet<PathBuf>,
+
+    /// Capture files a live source is writing; their changes are expected
+    live_files: HashSet<PathBuf>,
+
     /// Toast notification manager
     toast_manager: ToastManager,
 
@@ -71,6 +126,33 @@ pub struct LogCrabApp {
     /// to one or more previous sessions, we show a dialog to let them choose.
     /// Contains (files_being_opened, matching_sessions).
     pending_session_offer: Option<PendingSessionOffer>,
+
+    /// Pending "merge session?" dialog for a `.crab` file that belongs to
+    /// someone else's copy of an already open log.
+    pending_session_import: Option<PendingSessionImport>,
+
+    /// Sessions another instance changed while this one had unsaved changes
+    /// to them, asked about one at a time
+    crab_conflicts: Vec<Takeover>,
+
+    /// Last check whether other instances released the `.crab` files this one
+    /// opened read-only
+    last_crab_lock_poll: Option<std::time::Instant>,
+
+    /// Last title sent to the window. Sending a viewport command requests a
+    /// repaint, so the title is only re-sent when it actually changes.
+    window_title: String,
+
+    /// Multi-container logs being split on a background thread
+    container_splits: Vec<ContainerSplit>,
+}
+
+/// A multi-container log being split, see [`LogCrabApp::poll_container_splits`]
+struct ContainerSplit {
+    /// The log as opened by the user
+    origin: PathBuf,
+    /// The per-container files, once written
+    result: std::sync::mpsc::Receiver<std::io::Result<Vec<PathBuf>>>,
 }
 
 /// State for the "restore session?" dialog
@@ -81,6 +163,27 @@ struct PendingSessionOffer {
     matching_sessions: Vec<RecordedSession>,
 }
 
+/// State for the "merge session?" dialog
+struct PendingSessionImport {
+    /// The `.crab` file being imported
+    path: PathBuf,
+    /// Its bookmarks, filters and highlights
+    imported: ImportedSession,
+    /// Source that receives the bookmarks
+    target_source: Option<u64>,
+}
+
+/// How to resolve a session both this and another instance changed
+#[derive(Clone, Copy)]
+enum CrabConflictChoice {
+    /// Take the other instance's session
+    Reload,
+    /// Keep this instance's session
+    Overwrite,
+    /// Combine both
+    Merge,
+}
+
 /// Action chosen in the session offer dialog
 enum SessionOfferAction {
     JustTheFiles,
@@ -90,7 +193,7 @@ enum SessionOfferAction {
 
 impl LogCrabApp {
     /// Update the window title based on open files
-    fn update_window_title(&self, ctx: &egui::Context) {
+    fn update_window_title(&mut self, ctx: &egui::Context) {
         let title = self.session.as_ref().map_or_else(
             || "LogCrab".to_string(),
             |session| {
@@ -104,12 +207,21 @@ impl LogCrabApp {
                 }
             },
         );
-        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+        if title != self.window_title {
+            self.window_title.clone_from(&title);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+        }
     }
 
     pub fn new(cc: &eframe::CreationContext<'_>, files: Vec<PathBuf>) -> Self {
         // Load global configuration
         let global_config = GlobalConfig::load();
+        i18n::set_locale(global_config.locale);
+        slow_filter::set_limits(
+            global_config.regex_backtrack_limit,
+            global_config.filter_timeout_secs,
+        );
+        anomaly::set_weights(global_config.scorer_weights);
 
         // Apply saved theme
         if global_config.bright_mode {
@@ -123,21 +235,41 @@ impl LogCrabApp {
 
         let mut app = Self {
             session: None,
-            filter_worker: FilterWorker::new(),
-            histogram_worker: HistogramWorker::new(),
+            filter_worker: FilterWorker::with_repaint(cc.egui_ctx.clone()),
+            histogram_worker: HistogramWorker::with_repaint(cc.egui_ctx.clone()),
             show_anomaly_explanation: false,
             show_shortcuts_window: false,
             show_about_window: false,
+            show_activity_log: false,
             sidecar_settings_window: None,
+            scorer_settings_window: None,
+            go_to_permalink_window: None,
+            folder_analysis_window: None,
+            onboarding_window: global_config
+                .first_run
+                .then(windows::OnboardingWindow::default),
+            raw_panel: RawSourcePanel::default(),
             shortcut_bindings: KeyboardBindings::load(&global_config),
             global_config,
             pending_rebind: None,
             pending_drop_files: Vec::new(),
             pending_source_removal: None,
+            pending_reparse: None,
+            file_watcher: FileWatcher::with_repaint(cc.egui_ctx.clone()),
+            config_watcher: ConfigWatcher::with_repaint(cc.egui_ctx.clone()),
+            changed_files: BTreeSet::new(),
+            followed_files: HashSet::new(),
+            live_files: HashSet::new(),
             toast_manager: ToastManager::new(cc.egui_ctx.clone()),
             session_history,
             pending_session_offer: None,
+            pending_session_import: None,
+            crab_conflicts: Vec::new(),
+            last_crab_lock_poll: None,
+            window_title: String::new(),
+            container_splits: Vec::new(),
         };
+        app.apply_shared_settings(&cc.egui_ctx);
 
         // Load initial files if provided via command line
         if !files.is_empty() {
@@ -147,7 +279,7 @@ impl LogCrabApp {
                     app.add_file_to_session(file);
                 } else {
                     app.toast_manager
-                        .show_error(format!("File not found: {}", file.display()));
+                        .show_error(tr!("File not found: {path}", path = file.display()));
                 }
             }
         }
@@ -162,6 +294,9 @@ impl LogCrabApp {
         let store = LogStore::new();
         // Push sidecar scoring config so background loading threads can use it
         self.apply_sidecar_config_to_store(&store);
+        store.set_retention_limit(self.global_config.max_retained_lines);
+        store.set_activity_recording(self.global_config.record_activity);
+        store.set_run_history(RunHistory::in_cache_dir());
         let mut session = CrabSession::new(
             store,
             self.filter_worker.handle(),
@@ -171,6 +306,8 @@ impl LogCrabApp {
         // uploads) can surface success/error notifications without blocking the UI.
         session.state.toast_sender = Some(self.toast_manager.sender());
         self.session = Some(session);
+        // Containers of logs opened in the previous session stay out of this one
+        self.container_splits.clear();
     }
 
     /// Save the current session's file set into the session history
@@ -227,7 +364,7 @@ impl LogCrabApp {
                 self.add_file_to_session(file);
             } else {
                 self.toast_manager
-                    .show_error(format!("File not found: {}", file.display()));
+                    .show_error(tr!("File not found: {path}", path = file.display()));
             }
         }
     }
@@ -253,52 +390,667 @@ impl LogCrabApp {
         if let Some(ref mut session) = self.session {
             // Check if this is a .crab session file
             if path.to_string_lossy().ends_with(".crab") {
+                let crab_path = path.clone();
                 path = PathBuf::from(path.to_string_lossy().trim_end_matches(".crab"));
 
-                if path.exists() {
+                // A log of that name open from elsewhere already has its own
+                // session, so merge into it instead of opening a second copy
+                let store = &session.state.store;
+                let log_name = path.file_name().map(|n| n.to_string_lossy().into_owned());
+                let open_elsewhere = store.get_source_filenames().into_iter().any(|(id, name)| {
+                    Some(name) == log_name && store.source_path(id).as_deref() != Some(&*path)
+                });
+
+                if open_elsewhere {
+                    self.offer_session_import(crab_path);
+                    return;
+                } else if path.exists() {
                     tracing::info!("Loading log file from .crab session: {}", path.display());
+                } else if self.offer_session_import(crab_path) {
+                    return;
                 } else {
-                    let err_msg = format!("File not found: {}", path.display());
+                    let err_msg = tr!("File not found: {path}", path = path.display());
                     tracing::error!("{err_msg}");
                     self.toast_manager.show_error(err_msg);
                     return;
                 }
             }
-            let file_name = path
-                .file_name()
-                .map_or_else(|| "file".to_string(), |n| n.to_string_lossy().to_string());
+            match container_split::detect_container_log(&path) {
+                Ok(Some(prefix)) => {
+                    let splitting = self.container_splits.iter().any(|s| s.origin == path);
+                    if splitting || session.state.store.contains_file(&path) {
+                        tracing::info!("Skipping already loaded file: {}", path.display());
+                    } else {
+                        self.start_container_split(path, prefix);
+                    }
+                    return;
+                }
+                Ok(None) => {}
+                // Reported when the file is opened
+                Err(e) => tracing::debug!("Cannot sample {}: {e}", path.display()),
+            }
+            self.load_into_session(&path);
+        }
+    }
+
+    /// Load `path` as one source of the current session
+    fn load_into_session(&mut self, path: &std::path::Path) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        let file_name = path
+            .file_name()
+            .map_or_else(|| "file".to_string(), |n| n.to_string_lossy().to_string());
+        let toast_handle = self
+            .toast_manager
+            .create_progress_toast(file_name, tr!("Starting..."));
+        let warnings = self.toast_manager.sender();
+
+        session.add_file(
+            path,
+            &toast_handle,
+            &warnings,
+            &self.global_config.file_config,
+        );
+    }
+
+    /// Split a multi-container log into the cache on a background thread.
+    /// Its containers are added once that is done, see
+    /// [`Self::poll_container_splits`].
+    fn start_container_split(&mut self, path: PathBuf, prefix: ContainerPrefix) {
+        let file_name = path
+            .file_name()
+            .map_or_else(|| "file".to_string(), |n| n.to_string_lossy().to_string());
+        let toast = self
+            .toast_manager
+            .create_progress_toast(file_name, tr!("Splitting containers..."));
+        let (result_tx, result) = std::sync::mpsc::channel();
+        let origin = path.clone();
+        std::thread::spawn(move || {
+            let dir = container_split::split_dir(&origin);
+            let _ = result_tx.send(container_split::split_container_log(&origin, prefix, &dir));
+            toast.dismiss();
+        });
+        self.container_splits.push(ContainerSplit {
+            origin: path,
+            result,
+        });
+    }
+
+    /// Add the containers of the logs whose split has finished.
+    ///
+    /// The containers are watched and reloaded through the original log (see
+    /// [`LogStore::watched_path`]). A log that cannot be split is loaded as is.
+    fn poll_container_splits(&mut self) {
+        let mut finished = Vec::new();
+        self.container_splits
+            .retain(|split| match split.result.try_recv() {
+                Ok(result) => {
+                    finished.push((split.origin.clone(), result));
+                    false
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => true,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => false,
+            });
+        for (origin, result) in finished {
+            match result {
+                Ok(parts) => {
+                    tracing::info!(
+                        "Loading {} containers from {}",
+                        parts.len(),
+                        origin.display()
+                    );
+                    for part in parts {
+                        if let Some(session) = &self.session {
+                            session
+                                .state
+                                .store
+                                .set_split_origin(part.clone(), origin.clone());
+                        }
+                        self.load_into_session(&part);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to split containers in {}: {e}", origin.display());
+                    self.toast_manager.show_error(tr!(
+                        "Failed to split containers in {path}: {e}",
+                        path = origin.display(),
+                        e
+                    ));
+                    self.load_into_session(&origin);
+                }
+            }
+        }
+    }
+
+    /// Stream the system journal into the session, starting one if needed
+    fn follow_journal(&mut self) {
+        let Some(capture_path) = JournalFileType::capture_path() else {
+            self.toast_manager
+                .show_error(tr!("Cannot create a file to capture the journal in"));
+            return;
+        };
+        if self.session.is_none() {
+            self.start_new_session();
+        }
+        if let Some(ref mut session) = self.session {
             let toast_handle = self
                 .toast_manager
-                .create_progress_toast(file_name, "Starting...");
+                .create_progress_toast(tr!("System Journal"), tr!("Starting..."));
             let warnings = self.toast_manager.sender();
+            session.follow_journal(&capture_path, &toast_handle, &warnings);
+            self.live_files.insert(capture_path);
+        }
+    }
 
-            session.add_file(
-                &path,
-                &toast_handle,
-                &warnings,
-                &self.global_config.file_config,
-            );
+    /// Load a `.crab` file for merging into the current session.
+    ///
+    /// The target source is pre-selected by matching the log file name the
+    /// `.crab` belongs to (or the only open source). Returns `true` if the
+    /// merge dialog will be shown.
+    fn offer_session_import(&mut self, path: PathBuf) -> bool {
+        let Some(ref session) = self.session else {
+            return false;
+        };
+        let sources = session.state.store.get_source_filenames();
+        if sources.is_empty() {
+            return false;
         }
+
+        let imported = match ImportedSession::load(&path) {
+            Ok(imported) => imported,
+            Err(e) => {
+                tracing::error!("Failed to load session {}: {e}", path.display());
+                self.toast_manager.show_error(tr!(
+                    "Failed to load {path}: {e}",
+                    path = path.display(),
+                    e
+                ));
+                return false;
+            }
+        };
+
+        let log_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().trim_end_matches(".crab").to_string());
+        let target_source = sources
+            .iter()
+            .find(|(_, name)| Some(name) == log_name.as_ref())
+            .or_else(|| (sources.len() == 1).then(|| &sources[0]))
+            .map(|(id, _)| *id);
+
+        self.pending_session_import = Some(PendingSessionImport {
+            path,
+            imported,
+            target_source,
+        });
+        true
     }
 
-    /// Show file dialog and load selected file
-    fn open_file_dialog(&mut self) {
+    /// Show file dialog and import a `.crab` file into the current session
+    fn import_session_dialog(&mut self) {
         let mut dialog = rfd::FileDialog::new()
-            .add_filter("Log Files", &all_file_extensions())
+            .add_filter("Crab Session", &["crab"])
+            .add_filter("All Files", &["*"]);
+
+        if let Some(ref dir) = self.global_config.last_log_directory {
+            dialog = dialog.set_directory(dir);
+        }
+
+        if let Some(path) = dialog.pick_file() {
+            self.offer_session_import(path);
+        }
+    }
+
+    /// Ask for a destination and write the anomaly score breakdown of every
+    /// loaded source as NDJSON, scoring on a background thread.
+    fn export_scores_dialog(&self) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Export Anomaly Scores"))
+            .add_filter("NDJSON", &["ndjson", "jsonl"])
+            .set_file_name("anomaly_scores.ndjson")
+            .save_file()
+        else {
+            return;
+        };
+
+        let store = Arc::clone(&session.state.store);
+        let toast = self
+            .toast_manager
+            .create_progress_toast(tr!("Exporting Anomaly Scores"), tr!("Scoring..."));
+        let sender = self.toast_manager.sender();
+        std::thread::spawn(move || {
+            let result = std::fs::File::create(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|file| {
+                    crate::export::export_store_scores(&store, &mut std::io::BufWriter::new(file))
+                });
+            match result {
+                Ok(count) => sender.send_success(tr!(
+                    "Exported scores of {count} lines to {path}",
+                    count,
+                    path = path.display()
+                )),
+                Err(e) => sender.send(tr!(
+                    "Failed to export anomaly scores: {e}",
+                    e = format!("{e:#}")
+                )),
+            }
+            toast.dismiss();
+        });
+    }
+
+    /// Ask for a destination and write the shareable settings as one JSON bundle
+    fn export_settings_dialog(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Export Settings"))
+            .add_filter("JSON", &["json"])
+            .set_file_name("logcrab-settings.json")
+            .save_file()
+        else {
+            return;
+        };
+        match SettingsBundle::from_config(&self.global_config).save(&path) {
+            Ok(()) => self
+                .toast_manager
+                .show_success(tr!("Exported settings to {path}", path = path.display())),
+            Err(e) => self
+                .toast_manager
+                .show_error(tr!("Failed to export settings: {e}", e)),
+        }
+    }
+
+    /// Pick a settings bundle and apply it once
+    fn import_settings_dialog(&mut self, ctx: &egui::Context) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Import Settings"))
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        match SettingsBundle::load(&path) {
+            Ok(bundle) => {
+                self.apply_settings_bundle(ctx, &bundle);
+                self.toast_manager
+                    .show_success(tr!("Imported settings from {path}", path = path.display()));
+            }
+            Err(e) => self
+                .toast_manager
+                .show_error(tr!("Failed to import settings: {e}", e)),
+        }
+    }
+
+    /// Ask for a destination and save the open files and filters as a project
+    fn save_project_dialog(&mut self) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Save Project"))
+            .add_filter("LogCrab Project", &[PROJECT_EXTENSION])
+            .set_file_name(format!("investigation.{PROJECT_EXTENSION}"))
+            .save_file()
+        else {
+            return;
+        };
+        match session.to_project().save(&path) {
+            Ok(()) => self
+                .toast_manager
+                .show_success(tr!("Saved project to {path}", path = path.display())),
+            Err(e) => self
+                .toast_manager
+                .show_error(tr!("Failed to save project: {e}", e)),
+        }
+    }
+
+    /// Pick a project file and open it
+    fn open_project_dialog(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Open Project"))
+            .add_filter("LogCrab Project", &[PROJECT_EXTENSION])
+            .pick_file()
+        else {
+            return;
+        };
+        self.open_project(&path);
+    }
+
+    /// Open every file of a project as a new session and restore its filters.
+    ///
+    /// Offsets and bookmarks come back from each file's own .crab.
+    fn open_project(&mut self, path: &std::path::Path) {
+        let project = match CrabProject::load(path) {
+            Ok(project) => project,
+            Err(e) => {
+                self.toast_manager
+                    .show_error(tr!("Failed to open project: {e}", e));
+                return;
+            }
+        };
+        tracing::info!(
+            "Opening project {} with {} files",
+            path.display(),
+            project.files.len()
+        );
+        self.open_files_as_new_session(project.files.clone());
+        if let Some(session) = &mut self.session {
+            session.apply_project(&project);
+        }
+    }
+
+    /// Pick a settings bundle that is applied now and at every startup
+    fn choose_shared_settings_dialog(&mut self, ctx: &egui::Context) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Shared Settings File"))
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        let shared = Some(path);
+        self.global_config = GlobalConfig::update(|c| c.shared_settings_path.clone_from(&shared));
+        self.apply_shared_settings(ctx);
+    }
+
+    /// Apply the shared settings file, if one is configured
+    fn apply_shared_settings(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.global_config.shared_settings_path.clone() else {
+            return;
+        };
+        match SettingsBundle::load(&path) {
+            Ok(bundle) => self.apply_settings_bundle(ctx, &bundle),
+            Err(e) => self.toast_manager.show_error(tr!(
+                "Shared settings from {path} not applied: {e}",
+                path = path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Store `bundle` on top of the current config and bring the theme,
+    /// shortcuts and open sources in line with it
+    fn apply_settings_bundle(&mut self, ctx: &egui::Context, bundle: &SettingsBundle) {
+        self.global_config = GlobalConfig::update(|c| bundle.apply_to(c));
+        if self.global_config.bright_mode {
+            ctx.set_visuals(egui::Visuals::light());
+        } else {
+            ctx.set_visuals(egui::Visuals::dark());
+        }
+        self.shortcut_bindings = KeyboardBindings::load(&self.global_config);
+        if let Some(ref session) = self.session {
+            session
+                .state
+                .store
+                .rebuild_all_time_indices(&self.global_config.file_config);
+        }
+    }
+
+    /// Take over a config saved by another instance or edited by hand
+    fn apply_reloaded_config(&mut self, ctx: &egui::Context, reload: &ConfigReload) {
+        tracing::info!("Config file changed on disk, applying it");
+        let file_config_before = serde_json::to_value(&self.global_config.file_config).ok();
+        reload.apply_to(&mut self.global_config);
+        let file_config_changed =
+            serde_json::to_value(&self.global_config.file_config).ok() != file_config_before;
+        i18n::set_locale(self.global_config.locale);
+        slow_filter::set_limits(
+            self.global_config.regex_backtrack_limit,
+            self.global_config.filter_timeout_secs,
+        );
+        anomaly::set_weights(self.global_config.scorer_weights);
+        if self.global_config.bright_mode {
+            ctx.set_visuals(egui::Visuals::light());
+        } else {
+            ctx.set_visuals(egui::Visuals::dark());
+        }
+        self.shortcut_bindings = KeyboardBindings::load(&self.global_config);
+        if file_config_changed {
+            if let Some(ref session) = self.session {
+                session
+                    .state
+                    .store
+                    .rebuild_all_time_indices(&self.global_config.file_config);
+            }
+        }
+    }
+
+    /// Pick up file changes on disk: reload followed files, queue the rest
+    /// for the change banner.
+    fn poll_file_changes(&mut self) {
+        let Some(session) = &self.session else {
+            self.changed_files.clear();
+            return;
+        };
+        let store = Arc::clone(&session.state.store);
+        let mut paths = store.get_source_file_paths();
+        // Not a source until its split is done, but followed all the same
+        paths.extend(self.container_splits.iter().map(|s| s.origin.clone()));
+        self.file_watcher.sync(&paths);
+        self.changed_files.extend(self.file_watcher.changed_files());
+        self.live_files.retain(|p| paths.contains(p));
+        self.changed_files
+            .retain(|p| paths.contains(p) && !self.live_files.contains(p));
+        self.followed_files.retain(|p| paths.contains(p));
+
+        // Followed files reload as soon as their previous load has finished
+        let due: Vec<PathBuf> = self
+            .changed_files
+            .iter()
+            .filter(|p| self.followed_files.contains(*p))
+            .filter(|p| {
+                let ids = store.source_ids_for_path(p);
+                !ids.is_empty() && ids.iter().all(|id| store.is_source_loaded(*id))
+            })
+            .cloned()
+            .collect();
+        if !due.is_empty() {
+            for path in &due {
+                self.changed_files.remove(path);
+            }
+            self.reload_files(due);
+        }
+    }
+
+    /// Take over the `.crab` files other instances have released.
+    ///
+    /// Unsaved changes are saved and changes of the other instance are taken
+    /// over; only when both sides changed the session is the user asked.
+    fn poll_crab_locks(&mut self, ctx: &egui::Context) {
+        const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+        let Some(session) = self.session.as_mut() else {
+            self.crab_conflicts.clear();
+            return;
+        };
+        let store = Arc::clone(&session.state.store);
+        if !store.has_contended_crab_files() {
+            return;
+        }
+        ctx.request_repaint_after(POLL_INTERVAL);
+        if self
+            .last_crab_lock_poll
+            .is_some_and(|last| last.elapsed() < POLL_INTERVAL)
+        {
+            return;
+        }
+        self.last_crab_lock_poll = Some(std::time::Instant::now());
+
+        for takeover in store.take_over_crab_files() {
+            if takeover.is_conflict() {
+                self.crab_conflicts.push(takeover);
+                continue;
+            }
+            if let Some(on_disk) = &takeover.changed {
+                session.reload_session(takeover.source_id, on_disk);
+            } else if takeover.unsaved {
+                session.save_crab_file();
+            }
+            self.toast_manager.show_success(tr!(
+                "{file} was closed in the other LogCrab instance — its session is saved here again",
+                file = takeover.log_name()
+            ));
+        }
+    }
+
+    /// Non-modal banner listing files that changed on disk
+    fn render_file_change_banner(&mut self, ui: &mut egui::Ui) {
+        let pending: Vec<PathBuf> = self
+            .changed_files
+            .iter()
+            .filter(|p| !self.followed_files.contains(*p))
+            .cloned()
+            .collect();
+        ui.horizontal(|ui| {
+            let names = pending
+                .iter()
+                .map(|p| p.file_name().unwrap_or(p.as_os_str()).to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui.colored_label(
+                Color32::from_rgb(230, 180, 40),
+                tr!("⚠ Changed on disk: {names}", names),
+            )
+            .on_hover_text(tr!("The displayed lines may be stale"));
+            if ui
+                .button(tr!("⟳ Reload"))
+                .on_hover_text(tr!("Read the changed files again"))
+                .clicked()
+            {
+                for path in &pending {
+                    self.changed_files.remove(path);
+                }
+                self.reload_files(pending.clone());
+            }
+            if ui
+                .button(tr!("Follow"))
+                .on_hover_text(tr!("Reload these files automatically whenever they change"))
+                .clicked()
+            {
+                // Picked up by the next poll, which also does the first reload
+                self.followed_files.extend(pending.iter().cloned());
+            }
+            if ui.button(tr!("Dismiss")).clicked() {
+                for path in &pending {
+                    self.changed_files.remove(path);
+                }
+            }
+        });
+    }
+
+    /// Re-read the given files of the current session from disk
+    fn reload_files(&mut self, paths: Vec<PathBuf>) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        let paths = session.begin_reload_of(paths);
+        tracing::info!("Reloading {} changed file(s)", paths.len());
+        for path in paths {
+            self.add_file_to_session(path);
+        }
+    }
+
+    /// Re-read all files of the current session from disk
+    fn reload_session_files(&mut self) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        let paths = session.begin_reload();
+        self.changed_files.clear();
+        tracing::info!("Reloading {} file(s)", paths.len());
+        for path in paths {
+            self.add_file_to_session(path);
+        }
+    }
+
+    /// File dialog for log files with one filter per registered format.
+    ///
+    /// "All Supported Logs" comes first so it is the pre-selected filter.
+    fn log_file_dialog(&self) -> rfd::FileDialog {
+        let mut dialog =
+            rfd::FileDialog::new().add_filter("All Supported Logs", &all_file_extensions());
+        for (name, extensions) in file_type_filters() {
+            dialog = dialog.add_filter(name, extensions);
+        }
+        dialog = dialog
+            .add_filter("Crab Session", &["crab"])
             .add_filter("All Files", &["*"]);
 
         if let Some(ref dir) = self.global_config.last_log_directory {
             dialog = dialog.set_directory(dir);
         }
+        dialog
+    }
+
+    /// Show file dialog and load selected file
+    /// Ask for a folder and start ranking the logs in it
+    fn analyze_folder_dialog(&mut self, ctx: &egui::Context) {
+        let mut dialog = rfd::FileDialog::new().set_title(tr!("Analyze Folder"));
+        if let Some(ref dir) = self.global_config.last_log_directory {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(dir) = dialog.pick_folder() else {
+            return;
+        };
+        match windows::FolderAnalysisWindow::start(dir.clone(), ctx) {
+            Ok(window) => self.folder_analysis_window = Some(window),
+            Err(e) => {
+                tracing::error!("Failed to read {}: {e}", dir.display());
+                self.toast_manager.show_error(tr!(
+                    "Failed to read {path}: {e}",
+                    path = dir.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    /// Ask for a folder and open all logs in it, rotated files included,
+    /// as one session merged by timestamp
+    fn open_folder_dialog(&mut self) {
+        let mut dialog = rfd::FileDialog::new().set_title(tr!("Open Folder"));
+        if let Some(ref dir) = self.global_config.last_log_directory {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(dir) = dialog.pick_folder() else {
+            return;
+        };
+        let paths = match crate::core::rotated_logs::rotated_logs(&dir) {
+            Ok(paths) => paths,
+            Err(e) => {
+                tracing::error!("Failed to read {}: {e}", dir.display());
+                self.toast_manager.show_error(tr!(
+                    "Failed to read {path}: {e}",
+                    path = dir.display(),
+                    e
+                ));
+                return;
+            }
+        };
+        if paths.is_empty() {
+            self.toast_manager
+                .show_error(tr!("No log files in {path}", path = dir.display()));
+            return;
+        }
+        tracing::info!("Opening {} log files from {}", paths.len(), dir.display());
+        self.global_config = GlobalConfig::update(|c| c.last_log_directory = Some(dir));
+
+        if !self.check_session_offer(paths.clone()) {
+            self.open_files_as_new_session(paths);
+        }
+    }
+
+    fn open_file_dialog(&mut self) {
+        let dialog = self.log_file_dialog();
 
         if let Some(paths) = dialog.pick_files() {
             if let Some(first) = paths.first() {
                 if let Some(parent) = first.parent() {
                     let dir = parent.to_path_buf();
-                    match GlobalConfig::update(|c| c.last_log_directory = Some(dir)) {
-                        Ok(updated) => self.global_config = updated,
-                        Err(e) => tracing::error!("Failed to update config: {e}"),
-                    }
+                    self.global_config = GlobalConfig::update(|c| c.last_log_directory = Some(dir));
                 }
             }
 
@@ -311,23 +1063,14 @@ impl LogCrabApp {
 
     /// Show file dialog and add selected file(s) to the current workspace
     fn add_file_dialog(&mut self) {
-        let mut dialog = rfd::FileDialog::new()
-            .add_filter("Log Files", &all_file_extensions())
-            .add_filter("All Files", &["*"]);
-
-        if let Some(ref dir) = self.global_config.last_log_directory {
-            dialog = dialog.set_directory(dir);
-        }
+        let dialog = self.log_file_dialog();
 
         if let Some(paths) = dialog.pick_files() {
             // Remember the directory from the first file
             if let Some(first) = paths.first() {
                 if let Some(parent) = first.parent() {
                     let dir = parent.to_path_buf();
-                    match GlobalConfig::update(|c| c.last_log_directory = Some(dir)) {
-                        Ok(updated) => self.global_config = updated,
-                        Err(e) => tracing::error!("Failed to update config: {e}"),
-                    }
+                    self.global_config = GlobalConfig::update(|c| c.last_log_directory = Some(dir));
                 }
             }
 
@@ -344,6 +1087,7 @@ impl LogCrabApp {
     /// - If no session exists, first log file is loaded as main file
     /// - If session exists, additional log files are added to the workspace
     /// - All .crab-filters files are imported
+    /// - A dropped .crabproj replaces everything else with the project
     fn process_dropped_files(&mut self, files: Vec<PathBuf>) {
         let mut log_files: Vec<PathBuf> = Vec::new();
         let mut filter_files: Vec<PathBuf> = Vec::new();
@@ -351,7 +1095,10 @@ impl LogCrabApp {
         for path in files {
             let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
-            if ext == "crab-filters" {
+            if ext == PROJECT_EXTENSION {
+                self.open_project(&path);
+                return;
+            } else if ext == "crab-filters" {
                 filter_files.push(path);
             } else {
                 log_files.push(path);
@@ -385,12 +1132,13 @@ impl LogCrabApp {
                                 "Failed to import filters from {}: {e}",
                                 path.display()
                             );
-                            self.toast_manager.show_error(format!(
-                                "Failed to import {}: {e}",
-                                path.file_name().map_or_else(
+                            self.toast_manager.show_error(tr!(
+                                "Failed to import {file}: {e}",
+                                file = path.file_name().map_or_else(
                                     || "filters".to_string(),
                                     |n| n.to_string_lossy().to_string()
-                                )
+                                ),
+                                e
                             ));
                         }
                     }
@@ -400,28 +1148,111 @@ impl LogCrabApp {
                     "Cannot import filter files - no log file is open. Open a log file first."
                 );
                 self.toast_manager
-                    .show_error("Cannot import filters - open a log file first");
+                    .show_error(tr!("Cannot import filters - open a log file first"));
             }
         }
     }
 
     /// Render top menu bar
     fn render_menu_bar(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        ui.menu_button("File", |ui| {
-            if ui.button("Open Log File...").clicked() {
+        ui.menu_button(tr!("File"), |ui| {
+            if ui.button(tr!("Open Log File...")).clicked() {
                 self.open_file_dialog();
                 ui.close();
             }
 
-            if self.session.is_some() && ui.button("Add File to session...").clicked() {
+            if ui
+                .button(tr!("Open Folder..."))
+                .on_hover_text(tr!(
+                    "Open every log in a folder, e.g. app.log with its rotated app.log.1 and \
+                     app.log.2.gz, as one session merged by timestamp"
+                ))
+                .clicked()
+            {
+                self.open_folder_dialog();
+                ui.close();
+            }
+
+            if self.session.is_some()
+                && ui
+                    .button(tr!("Add Log File to Session..."))
+                    .on_hover_text(tr!(
+                        "Load more log files into this session; their lines are merged by \
+                         timestamp and the Source column tells them apart"
+                    ))
+                    .clicked()
+            {
                 self.add_file_dialog();
                 ui.close();
             }
 
+            if cfg!(target_os = "linux")
+                && ui
+                    .button(tr!("Follow System Journal"))
+                    .on_hover_text(tr!(
+                        "Stream new entries of the systemd journal into the session as they \
+                         are logged; remove the source to stop"
+                    ))
+                    .clicked()
+            {
+                self.follow_journal();
+                ui.close();
+            }
+
+            if ui
+                .button(tr!("Open Project..."))
+                .on_hover_text(tr!("Reopen a saved set of log files with its filters"))
+                .clicked()
+            {
+                self.open_project_dialog();
+                ui.close();
+            }
+
+            if self.session.is_some()
+                && ui
+                    .button(tr!("Save Project..."))
+                    .on_hover_text(tr!(
+                        "Save the open log files, filters and highlights as a .crabproj file; \
+                         offsets and bookmarks stay in each file's .crab"
+                    ))
+                    .clicked()
+            {
+                self.save_project_dialog();
+                ui.close();
+            }
+
+            if ui
+                .button(tr!("Analyze Folder..."))
+                .on_hover_text(tr!(
+                    "Score every log in a folder and rank them by their most anomalous line"
+                ))
+                .clicked()
+            {
+                self.analyze_folder_dialog(ctx);
+                ui.close();
+            }
+
+            if self.session.is_some() && ui.button(tr!("Reload Files")).clicked() {
+                self.reload_session_files();
+                ui.close();
+            }
+
+            if self.session.is_some()
+                && ui
+                    .button(tr!("Merge Session (.crab)..."))
+                    .on_hover_text(tr!(
+                        "Merge bookmarks, filters and highlights from someone else's .crab file"
+                    ))
+                    .clicked()
+            {
+                self.import_session_dialog();
+                ui.close();
+            }
+
             // Recent sessions submenu
             if !self.session_history.sessions.is_empty() {
                 let mut restore_idx: Option<usize> = None;
-                ui.menu_button("Recent Sessions", |ui| {
+                ui.menu_button(tr!("Recent Sessions"), |ui| {
                     for (idx, session) in self.session_history.sessions.iter().enumerate() {
                         let label = session.display_label();
                         let time_str = session.last_used.format("%Y-%m-%d %H:%M").to_string();
@@ -448,25 +1279,96 @@ impl LogCrabApp {
                 }
             }
 
-            // Show submenu to remove individual files
+            // Quick actions for each loaded file
+            let mut reload_path = None;
+            let mut segments_changed = false;
+            let mut new_level_rules = None;
             if let Some(ref session) = self.session {
                 let filenames = session.state.store.get_source_filenames();
                 if !filenames.is_empty() {
-                    ui.menu_button("Remove File from session", |ui| {
-                        for (source_id, filename) in filenames {
-                            if ui.button(&filename).clicked() {
-                                self.pending_source_removal = Some(source_id);
-                                ui.close();
-                            }
+                    ui.menu_button(tr!("Sources"), |ui| {
+                        for (source_id, filename) in &filenames {
+                            let Some(path) = session.state.store.source_path(*source_id) else {
+                                continue;
+                            };
+                            ui.menu_button(filename, |ui| {
+                                if ui.button(tr!("📂 Reveal in file manager")).clicked() {
+                                    if let Err(e) = reveal_in_file_manager(&path) {
+                                        tracing::error!("Failed to reveal {}: {e}", path.display());
+                                    }
+                                    ui.close();
+                                }
+                                if ui.button(tr!("📋 Copy path")).clicked() {
+                                    ui.ctx().copy_text(path.display().to_string());
+                                    ui.close();
+                                }
+                                if ui.button(tr!("🔄 Reload")).clicked() {
+                                    reload_path = Some(path.clone());
+                                    ui.close();
+                                }
+                                let current = session.state.store.source_filetype_slug(*source_id);
+                                ui.menu_button(tr!("Reparse as…"), |ui| {
+                                    for (slug, name) in parser_choices() {
+                                        if ui.radio(current == Some(slug), name).clicked()
+                                            && current != Some(slug)
+                                        {
+                                            self.pending_reparse = Some((*source_id, slug));
+                                            ui.close();
+                                        }
+                                    }
+                                });
+                                ui.menu_button(tr!("✂ Segments"), |ui| {
+                                    segments_changed |=
+                                        render_segments_menu(ui, &session.state.store, *source_id);
+                                });
+                                ui.menu_button(tr!("🎚 Level Rules"), |ui| {
+                                    if let Some(rules) = render_level_rules_menu(
+                                        ui,
+                                        &session.state.store,
+                                        *source_id,
+                                    ) {
+                                        new_level_rules = Some((*source_id, rules));
+                                    }
+                                });
+                                ui.separator();
+                                if ui
+                                    .button(tr!("🗑 Remove source"))
+                                    .on_hover_text(tr!("Drop this file's lines from the session; \
+                                         the other files stay loaded"))
+                                    .clicked()
+                                {
+                                    self.pending_source_removal = Some(*source_id);
+                                    ui.close();
+                                }
+                            })
+                            .response
+                            .on_hover_text(path.display().to_string());
                         }
                     });
                 }
             }
+            if let Some(path) = reload_path {
+                self.reload_files(vec![path]);
+            }
+            if segments_changed {
+                if let Some(session) = &mut self.session {
+                    session.state.modified = true;
+                }
+            }
+            if let Some((source_id, rules)) = new_level_rules {
+                if let Some(session) = &mut self.session {
+                    let toast = self
+                        .toast_manager
+                        .create_progress_toast(tr!("Applying Level Rules"), tr!("Starting..."));
+                    session.state.store.set_level_rules(source_id, rules, toast);
+                    session.state.modified = true;
+                }
+            }
 
             ui.separator();
 
             if let Some(ref mut log_view) = &mut self.session {
-                if ui.button("Export Filters...").clicked() {
+                if ui.button(tr!("Export Filters...")).clicked() {
                     let mut dialog = rfd::FileDialog::new()
                         .add_filter("Crab Filters", &["crab-filters"])
                         .add_filter("All Files", &["*"])
@@ -479,10 +1381,8 @@ impl LogCrabApp {
                     if let Some(path) = dialog.save_file() {
                         if let Some(parent) = path.parent() {
                             let dir = parent.to_path_buf();
-                            match GlobalConfig::update(|c| c.last_filters_directory = Some(dir)) {
-                                Ok(updated) => self.global_config = updated,
-                                Err(e) => tracing::error!("Failed to update config: {e}"),
-                            }
+                            self.global_config =
+                                GlobalConfig::update(|c| c.last_filters_directory = Some(dir));
                         }
                         match log_view.export_filters(&path) {
                             Ok(()) => tracing::info!("Filters exported successfully"),
@@ -491,7 +1391,7 @@ impl LogCrabApp {
                     }
                     ui.close();
                 }
-                if ui.button("Import Filters...").clicked() {
+                if ui.button(tr!("Import Filters...")).clicked() {
                     let mut dialog = rfd::FileDialog::new()
                         .add_filter("Crab Filters", &["crab-filters"])
                         .add_filter("All Files", &["*"]);
@@ -505,10 +1405,8 @@ impl LogCrabApp {
                         if let Some(first) = paths.first() {
                             if let Some(parent) = first.parent() {
                                 let dir = parent.to_path_buf();
-                                match GlobalConfig::update(|c| c.last_filters_directory = Some(dir)) {
-                                    Ok(updated) => self.global_config = updated,
-                                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                                }
+                                self.global_config =
+                                    GlobalConfig::update(|c| c.last_filters_directory = Some(dir));
                             }
                         }
                         for path in paths {
@@ -531,7 +1429,27 @@ impl LogCrabApp {
                 ui.separator();
             }
 
-            if ui.button("Sidecar Settings...").clicked() {
+            if self.session.is_some()
+                && ui
+                    .button(tr!("Export Anomaly Scores..."))
+                    .on_hover_text(tr!(
+                        "Write each line's template, per-scorer components and composite \
+                         score as NDJSON"
+                    ))
+                    .clicked()
+            {
+                self.export_scores_dialog();
+                ui.close();
+            }
+
+            if ui.button(tr!("Anomaly Scorer Settings...")).clicked() {
+                self.scorer_settings_window = Some(windows::ScorerSettingsWindow::new(
+                    self.global_config.scorer_weights,
+                ));
+                ui.close();
+            }
+
+            if ui.button(tr!("Sidecar Settings...")).clicked() {
                 self.sidecar_settings_window =
                     Some(windows::SidecarSettingsWindow::open_with_config(
                         &self.global_config,
@@ -539,55 +1457,138 @@ impl LogCrabApp {
                 ui.close();
             }
 
+            ui.menu_button(tr!("Settings Bundle"), |ui| {
+                if ui
+                    .button(tr!("Export Settings..."))
+                    .on_hover_text(tr!(
+                        "Write shortcuts, favorites, theme, format settings, column layouts \
+                         and redaction rules to one JSON file"
+                    ))
+                    .clicked()
+                {
+                    self.export_settings_dialog();
+                    ui.close();
+                }
+                if ui.button(tr!("Import Settings...")).clicked() {
+                    self.import_settings_dialog(ctx);
+                    ui.close();
+                }
+
+                ui.separator();
+
+                let shared = self
+                    .global_config
+                    .shared_settings_path
+                    .as_ref()
+                    .map_or_else(
+                        || tr!("Shared file: none").to_owned(),
+                        |path| tr!("Shared file: {path}", path = path.display()),
+                    );
+                ui.label(egui::RichText::new(shared).weak());
+                if ui
+                    .button(tr!("Use Shared Settings File..."))
+                    .on_hover_text(tr!(
+                        "Apply a settings file, e.g. a team standard on a network share, \
+                         now and at every start"
+                    ))
+                    .clicked()
+                {
+                    self.choose_shared_settings_dialog(ctx);
+                    ui.close();
+                }
+                if self.global_config.shared_settings_path.is_some()
+                    && ui.button(tr!("Stop Using Shared File")).clicked()
+                {
+                    self.global_config = GlobalConfig::update(|c| c.shared_settings_path = None);
+                    ui.close();
+                }
+            });
+
             ui.separator();
 
-            if ui.button("Quit").clicked() {
+            if ui.button(tr!("Quit")).clicked() {
                 ctx.send_viewport_cmd(egui::ViewportCommand::Close);
             }
         });
 
-        ui.menu_button("View", |ui| {
+        ui.menu_button(tr!("View"), |ui| {
             if let Some(ref mut log_view) = &mut self.session {
-                if ui.button("Add Filter Tab").clicked() {
+                if ui.button(tr!("Add Filter Tab")).clicked() {
                     log_view.add_filter_view(false, None);
                     ui.close();
                 }
 
-                if ui.button("Add Bookmarks Tab").clicked() {
+                if ui.button(tr!("Add Bookmarks Tab")).clicked() {
                     log_view
                         .dock_state
                         .push_to_focused_leaf(Box::new(BookmarksView::default()));
                     ui.close();
                 }
 
-                if ui.button("Add Highlights Tab").clicked() {
+                if ui.button(tr!("Add Highlights Tab")).clicked() {
                     log_view
                         .dock_state
                         .push_to_focused_leaf(Box::new(HighlightsView::new()));
                     ui.close();
                 }
 
+                if ui.button(tr!("Errors & Fatals")).clicked() {
+                    log_view.open_errors_view();
+                    ui.close();
+                }
+
+                if ui
+                    .button(tr!("Suggest Bookmarks…"))
+                    .on_hover_text(tr!("Propose bookmarks at the most anomalous stretches of the logs"))
+                    .clicked()
+                {
+                    log_view.suggest_bookmarks(self.global_config.color_by_ml_score);
+                    ui.close();
+                }
+
+                if ui.button(tr!("Go to Permalink…")).clicked() {
+                    self.go_to_permalink_window = Some(windows::GoToPermalinkWindow::default());
+                    ui.close();
+                }
+
+                if ui.button(tr!("Activity Log")).clicked() {
+                    self.show_activity_log = true;
+                    ui.close();
+                }
+
                 ui.separator();
             }
 
+            if ui
+                .checkbox(&mut self.global_config.record_activity, tr!("Record Activity Log"))
+                .on_hover_text(
+                    tr!("Keep a timestamped log of bookmarks, filters and time calibrations \
+                     in the .crab file, to reconstruct how a finding was made"),
+                )
+                .changed()
+            {
+                let new_val = self.global_config.record_activity;
+                self.global_config = GlobalConfig::update(|c| c.record_activity = new_val);
+                if let Some(ref session) = self.session {
+                    session.state.store.set_activity_recording(new_val);
+                }
+            }
+
             if ui
                 .checkbox(
                     &mut self.global_config.show_bookmarks_in_timeline,
-                    "Show Bookmarks in Timeline",
+                    tr!("Show Bookmarks in Timeline"),
                 )
                 .changed()
             {
                 let new_val = self.global_config.show_bookmarks_in_timeline;
-                match GlobalConfig::update(|c| c.show_bookmarks_in_timeline = new_val) {
-                    Ok(updated) => self.global_config = updated,
-                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                }
+                self.global_config = GlobalConfig::update(|c| c.show_bookmarks_in_timeline = new_val);
             }
 
             ui.separator();
 
             if ui
-                .checkbox(&mut self.global_config.bright_mode, "Bright Mode")
+                .checkbox(&mut self.global_config.bright_mode, tr!("Bright Mode"))
                 .changed()
             {
                 // Apply theme change
@@ -597,20 +1598,14 @@ impl LogCrabApp {
                     ctx.set_visuals(egui::Visuals::dark());
                 }
                 let new_val = self.global_config.bright_mode;
-                match GlobalConfig::update(|c| c.bright_mode = new_val) {
-                    Ok(updated) => self.global_config = updated,
-                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                }
+                self.global_config = GlobalConfig::update(|c| c.bright_mode = new_val);
             }
 
             ui.separator();
 
             if self.global_config.file_config.render(ui) {
                 let new_fc = self.global_config.file_config.clone();
-                match GlobalConfig::update(|c| c.file_config = new_fc) {
-                    Ok(updated) => self.global_config = updated,
-                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                }
+                self.global_config = GlobalConfig::update(|c| c.file_config = new_fc);
                 if let Some(ref mut session) = self.session {
                     session
                         .state
@@ -624,102 +1619,494 @@ impl LogCrabApp {
             if ui
                 .checkbox(
                     &mut self.global_config.use_sidecar_scoring,
-                    "Enable ML Scoring",
+                    tr!("Enable ML Scoring"),
                 )
-                .on_hover_text("Send log lines to the sidecar server for ML-based anomaly scoring")
+                .on_hover_text(tr!("Send log lines to the sidecar server for ML-based anomaly scoring"))
                 .changed()
             {
                 let new_val = self.global_config.use_sidecar_scoring;
-                match GlobalConfig::update(|c| c.use_sidecar_scoring = new_val) {
-                    Ok(updated) => self.global_config = updated,
-                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                }
+                self.global_config = GlobalConfig::update(|c| c.use_sidecar_scoring = new_val);
             }
 
             if ui
                 .checkbox(
                     &mut self.global_config.color_by_ml_score,
-                    "Color by ML Score",
+                    tr!("Color by ML Score"),
                 )
-                .on_hover_text("Color log lines by ML anomaly score instead of local heuristic scorer")
+                .on_hover_text(tr!("Color log lines by ML anomaly score instead of local heuristic scorer"))
                 .changed()
             {
                 let new_val = self.global_config.color_by_ml_score;
-                match GlobalConfig::update(|c| c.color_by_ml_score = new_val) {
-                    Ok(updated) => self.global_config = updated,
-                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                }
+                self.global_config = GlobalConfig::update(|c| c.color_by_ml_score = new_val);
             }
 
             if self.global_config.color_by_ml_score {
                 if ui
                     .checkbox(
                         &mut self.global_config.grey_rare_ml_lines,
-                        "Grey out rare lines",
+                        tr!("Grey out rare lines"),
                     )
-                    .on_hover_text("Show RARE-flagged lines in grey instead of their scored color (rare = in-corpus but seen < min_count times in training)")
+                    .on_hover_text(tr!("Show RARE-flagged lines in grey instead of their scored color (rare = in-corpus but seen < min_count times in training)"))
                     .changed()
                 {
                     let new_val = self.global_config.grey_rare_ml_lines;
-                    match GlobalConfig::update(|c| c.grey_rare_ml_lines = new_val) {
-                        Ok(updated) => self.global_config = updated,
-                        Err(e) => tracing::error!("Failed to update config: {e}"),
-                    }
+                    self.global_config = GlobalConfig::update(|c| c.grey_rare_ml_lines = new_val);
                 }
             }
 
+            if ui
+                .checkbox(
+                    &mut self.global_config.show_score_legend,
+                    tr!("Show Score Legend"),
+                )
+                .on_hover_text(tr!("Show the score colors and a threshold to dim unremarkable lines"))
+                .changed()
+            {
+                let new_val = self.global_config.show_score_legend;
+                self.global_config = GlobalConfig::update(|c| c.show_score_legend = new_val);
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.global_config.show_raw_panel,
+                    tr!("Show Raw Source Panel"),
+                )
+                .on_hover_text(tr!(
+                    "Show the lines around the selected line exactly as they are in the file, \
+                     read from disk rather than from the parsed log"
+                ))
+                .changed()
+            {
+                let new_val = self.global_config.show_raw_panel;
+                self.global_config = GlobalConfig::update(|c| c.show_raw_panel = new_val);
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.global_config.show_previous_run,
+                    tr!("Compare with Previous Run"),
+                )
+                .on_hover_text(tr!(
+                    "Draw the line rate of the last loaded file with the same format and name \
+                     pattern (e.g. boot-1233.log for boot-1234.log) as a faint outline over \
+                     the histogram of tabs without a filter"
+                ))
+                .changed()
+            {
+                let new_val = self.global_config.show_previous_run;
+                self.global_config = GlobalConfig::update(|c| c.show_previous_run = new_val);
+            }
+
             ui.separator();
 
             if ui
                 .checkbox(
                     &mut self.global_config.hide_duplicates,
-                    "Hide Duplicate Lines",
+                    tr!("Hide Duplicate Lines"),
                 )
-                .on_hover_text("Hide exact duplicate log lines (same timestamp, source, and message)")
+                .on_hover_text(tr!("Hide exact duplicate log lines (same timestamp, source, and message)"))
                 .changed()
             {
                 let new_val = self.global_config.hide_duplicates;
-                match GlobalConfig::update(|c| c.hide_duplicates = new_val) {
-                    Ok(updated) => self.global_config = updated,
-                    Err(e) => tracing::error!("Failed to update config: {e}"),
-                }
+                self.global_config = GlobalConfig::update(|c| c.hide_duplicates = new_val);
             }
+
+            if ui
+                .checkbox(
+                    &mut self.global_config.focus_follows_mouse,
+                    tr!("Focus Follows Mouse"),
+                )
+                .on_hover_text(tr!("Keyboard navigation acts on the pane under the mouse pointer"))
+                .changed()
+            {
+                let new_val = self.global_config.focus_follows_mouse;
+                self.global_config = GlobalConfig::update(|c| c.focus_follows_mouse = new_val);
+            }
+
+            ui.menu_button(tr!("Timestamp Format"), |ui| {
+                for format in TimestampFormat::ALL {
+                    if ui
+                        .radio_value(&mut self.global_config.timestamp_format, format, format.label())
+                        .changed()
+                    {
+                        self.global_config = GlobalConfig::update(|c| c.timestamp_format = format);
+                    }
+                }
+            });
+
+            ui.menu_button(tr!("Language"), |ui| {
+                for locale in Locale::ALL {
+                    if ui
+                        .radio_value(&mut self.global_config.locale, locale, locale.native_name())
+                        .changed()
+                    {
+                        i18n::set_locale(locale);
+                        self.global_config = GlobalConfig::update(|c| c.locale = locale);
+                    }
+                }
+            });
+
+            ui.separator();
+
+            ui.menu_button(tr!("Performance"), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Max FPS:"));
+                    let response = ui
+                        .add(
+                            egui::DragValue::new(&mut self.global_config.max_fps)
+                                .range(0..=240)
+                                .custom_formatter(|v, _| {
+                                    if v < 1.0 {
+                                        tr!("Unlimited").to_string()
+                                    } else {
+                                        format!("{v:.0}")
+                                    }
+                                }),
+                        )
+                        .on_hover_text(
+                            tr!("Upper limit for the frame rate while background work \
+                             (loading, filtering) updates the window. Input is always \
+                             handled right away. 0 = unlimited. \
+                             An idle window is never repainted."),
+                        );
+                    // Persist once the drag ends rather than on every step
+                    if (response.changed() && !response.dragged()) || response.drag_stopped() {
+                        let new_val = self.global_config.max_fps;
+                        self.global_config = GlobalConfig::update(|c| c.max_fps = new_val);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Max retained lines:"));
+                    let response = ui
+                        .add(
+                            egui::DragValue::new(&mut self.global_config.max_retained_lines)
+                                .range(0..=100_000_000)
+                                .speed(10_000)
+                                .custom_formatter(|v, _| {
+                                    if v < 1.0 {
+                                        tr!("Unlimited").to_string()
+                                    } else {
+                                        format!("{v:.0}")
+                                    }
+                                }),
+                        )
+                        .on_hover_text(
+                            tr!("Number of lines kept in memory per live source, such as the \
+                             followed system journal. Once exceeded, the oldest lines are \
+                             dropped together with their bookmarks. Files are never trimmed. \
+                             0 = unlimited."),
+                        );
+                    if (response.changed() && !response.dragged()) || response.drag_stopped() {
+                        let new_val = self.global_config.max_retained_lines;
+                        self.global_config = GlobalConfig::update(|c| c.max_retained_lines = new_val);
+                        if let Some(ref session) = self.session {
+                            session.state.store.set_retention_limit(new_val);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Filter timeout:"));
+                    let response = ui
+                        .add(
+                            egui::DragValue::new(&mut self.global_config.filter_timeout_secs)
+                                .range(0..=600)
+                                .custom_formatter(|v, _| {
+                                    if v < 1.0 {
+                                        tr!("Unlimited").to_string()
+                                    } else {
+                                        format!("{v:.0} s")
+                                    }
+                                }),
+                        )
+                        .on_hover_text(tr!(
+                            "A filter still running after this long is aborted and \
+                             marked as too slow. 0 = unlimited."
+                        ));
+                    if (response.changed() && !response.dragged()) || response.drag_stopped() {
+                        self.apply_filter_limits();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Regex step budget per line:"));
+                    let response = ui
+                        .add(
+                            egui::DragValue::new(&mut self.global_config.regex_backtrack_limit)
+                                .range(1_000..=100_000_000)
+                                .speed(1_000),
+                        )
+                        .on_hover_text(tr!(
+                            "Backtracking steps a search pattern may take on one line. \
+                             Lines exceeding it are skipped instead of stalling the filter."
+                        ));
+                    if (response.changed() && !response.dragged()) || response.drag_stopped() {
+                        self.apply_filter_limits();
+                    }
+                });
+            });
         });
 
-        ui.menu_button("Help", |ui| {
-            if ui.button("Anomaly Score Calculation").clicked() {
+        ui.menu_button(tr!("Help"), |ui| {
+            if ui.button(tr!("Anomaly Score Calculation")).clicked() {
                 self.show_anomaly_explanation = true;
                 ui.close();
             }
-            if ui.button("Keyboard Shortcuts").clicked() {
+            if ui.button(tr!("Keyboard Shortcuts")).clicked() {
                 self.show_shortcuts_window = true;
                 ui.close();
             }
             ui.separator();
-            if ui.button("About LogCrab").clicked() {
+            if ui.button(tr!("About LogCrab")).clicked() {
                 self.show_about_window = true;
                 ui.close();
             }
         });
     }
 
+    /// Score every loaded source again with the current scorer weights
+    fn rescore_all_sources(&self) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let store = &session.state.store;
+        for (source_id, filename) in store.get_source_filenames() {
+            let toast = self
+                .toast_manager
+                .create_progress_toast(tr!("Rescoring {filename}", filename), tr!("Starting..."));
+            store.rescore(source_id, toast);
+        }
+    }
+
+    /// Persist the filter timeout and regex step budget and apply them to
+    /// subsequent filter passes
+    fn apply_filter_limits(&mut self) {
+        let timeout = self.global_config.filter_timeout_secs;
+        let backtrack_limit = self.global_config.regex_backtrack_limit;
+        slow_filter::set_limits(backtrack_limit, timeout);
+        self.global_config = GlobalConfig::update(|c| {
+            c.filter_timeout_secs = timeout;
+            c.regex_backtrack_limit = backtrack_limit;
+        });
+    }
+
     /// Render bottom status panel
-    fn render_status_panel(&self, ui: &mut egui::Ui) {
+    fn render_status_panel(&mut self, ui: &mut egui::Ui) {
+        let timestamp_format = self.global_config.timestamp_format;
         ui.horizontal(|ui| {
-            // Show filtering indicator if any filter is currently processing
-            if self
-                .filter_worker
-                .handle()
-                .is_filtering
-                .load(std::sync::atomic::Ordering::Relaxed)
-            {
-                ui.separator();
-                ui.spinner();
-                ui.label("Filtering...");
+            if let Some(session) = &mut self.session {
+                Self::render_line_count_menu(ui, session);
+                Self::render_match_count_menu(ui, session);
+                Self::render_selection_menu(ui, session, timestamp_format);
+                Self::render_offsets_menu(ui, session);
+                Self::render_suppressions_menu(ui, session);
+            }
+            self.render_jobs_menu(ui);
+        });
+    }
+
+    /// Status bar total line count, broken down per source in its menu
+    fn render_line_count_menu(ui: &mut egui::Ui, session: &CrabSession) {
+        let store = &session.state.store;
+        ui.menu_button(
+            tr!("📄 {count} lines", count = store.total_lines()),
+            |ui| {
+                for (source_id, source) in store.get_source_filenames() {
+                    let lines = store.source_len(source_id);
+                    if store.is_source_loaded(source_id) {
+                        ui.label(tr!("{source}: {lines} lines", source, lines));
+                    } else {
+                        ui.label(tr!("{source}: {lines} lines (loading)", source, lines));
+                    }
+                }
+            },
+        );
+    }
+
+    /// Status bar match count of the focused filter tab, with a menu to
+    /// switch to any other filter tab
+    fn render_match_count_menu(ui: &mut egui::Ui, session: &mut CrabSession) {
+        let Some(matches) = session.focused_match_count() else {
+            return;
+        };
+        ui.separator();
+        let mut focus = None;
+        ui.menu_button(tr!("🔍 {matches} matches", matches), |ui| {
+            for (uuid, tab, count) in session.filter_match_counts() {
+                if ui.button(tr!("{tab}: {count}", tab, count)).clicked() {
+                    focus = Some(uuid);
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text(tr!("Lines matching the focused tab; click to switch tabs"));
+        if let Some(uuid) = focus {
+            session.focus_filter_tab(uuid);
+        }
+    }
+
+    /// Status bar timestamp and source of the selected line, with copy and
+    /// calibration actions
+    fn render_selection_menu(
+        ui: &mut egui::Ui,
+        session: &CrabSession,
+        timestamp_format: TimestampFormat,
+    ) {
+        let store = &session.state.store;
+        let Some(id) = session.state.selected_line_index else {
+            return;
+        };
+        let Some(timestamp) = store.adjusted_timestamp(&id) else {
+            return;
+        };
+        let time = timestamp_format.format(timestamp);
+        let mut source = store.get_source_name(&id).unwrap_or_default();
+        if let Some(segment) = store.segment_name(&id) {
+            source = format!("{source} › {segment}");
+        }
+        ui.separator();
+        ui.menu_button(
+            format!("📍 {time} · {source}:{}", id.line_index_within_source() + 1),
+            |ui| {
+                if ui.button(tr!("📋 Copy Timestamp")).clicked() {
+                    ui.ctx().copy_text(time.clone());
+                    ui.close();
+                }
+                // Calibration entries of the source's file type
+                store.render_typed_context_menu_items(&id, ui);
+            },
+        )
+        .response
+        .on_hover_text(tr!(
+            "Selected line; click to copy its timestamp or calibrate its clock"
+        ));
+    }
+
+    /// Status bar count of clock corrections in effect, listing the total
+    /// offset of every corrected source and segment in its menu
+    fn render_offsets_menu(ui: &mut egui::Ui, session: &CrabSession) {
+        let store = &session.state.store;
+        let mut offsets = Vec::new();
+        for (source_id, source) in store.get_source_filenames() {
+            let clock_ms = store.clock_offset_ms(source_id);
+            if clock_ms != 0 {
+                offsets.push((source.clone(), clock_ms));
+            }
+            for segment in store.get_segments(source_id) {
+                if segment.offset_ms != 0 {
+                    offsets.push((
+                        format!("{source} › {}", segment.name),
+                        clock_ms + segment.offset_ms,
+                    ));
+                }
+            }
+        }
+        if offsets.is_empty() {
+            return;
+        }
+        ui.separator();
+        ui.menu_button(tr!("🕐 {count} offsets", count = offsets.len()), |ui| {
+            for (label, offset_ms) in &offsets {
+                let offset = format_time_diff(chrono::Duration::milliseconds(*offset_ms));
+                ui.label(format!("{label}: {offset}"));
+            }
+            ui.separator();
+            ui.label(
+                egui::RichText::new(tr!(
+                    "Calibrate from a line's context menu; segment offsets are under File → Sources"
+                ))
+                .weak(),
+            );
+        });
+    }
+
+    /// Status bar spinner while loading files or filtering, listing every
+    /// running job in its menu
+    fn render_jobs_menu(&self, ui: &mut egui::Ui) {
+        let mut jobs = Vec::new();
+        if self
+            .filter_worker
+            .handle()
+            .is_filtering
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            jobs.push(tr!("Filtering...").to_owned());
+        }
+        if let Some(session) = &self.session {
+            let store = &session.state.store;
+            for (source_id, file) in store.get_source_filenames() {
+                if !store.is_source_loaded(source_id) {
+                    jobs.push(tr!("Loading {file}", file));
+                }
+            }
+        }
+        let label = match jobs.as_slice() {
+            [] => return,
+            [job] => job.clone(),
+            _ => tr!("{count} background jobs", count = jobs.len()),
+        };
+        ui.separator();
+        ui.spinner();
+        ui.menu_button(label, |ui| {
+            for job in &jobs {
+                ui.label(job);
             }
         });
     }
 
+    /// Status bar counter of suppressed lines/templates with an undo list
+    fn render_suppressions_menu(ui: &mut egui::Ui, session: &mut CrabSession) {
+        let store = &session.state.store;
+        let suppressions = store.get_all_suppressions();
+        if suppressions.is_empty() {
+            return;
+        }
+        let source_names: HashMap<u64, String> = store.get_source_filenames().into_iter().collect();
+        ui.menu_button(
+            tr!("🚫 {count} suppressed", count = suppressions.len()),
+            |ui| {
+                let mut restore = None;
+                // Newest first, so the top entry undoes the last suppression
+                for (index, (source_id, suppression)) in suppressions.iter().enumerate().rev() {
+                    ui.horizontal(|ui| {
+                        if ui.button("↺").on_hover_text(tr!("Show again")).clicked() {
+                            restore = Some(index);
+                        }
+                        match suppression {
+                            Suppression::Line { line_index, .. } => {
+                                let source = source_id
+                                    .and_then(|id| source_names.get(&id))
+                                    .map_or("?", String::as_str);
+                                ui.label(tr!(
+                                    "Line {line} of {source}",
+                                    line = line_index + 1,
+                                    source
+                                ));
+                            }
+                            Suppression::Template { template } => {
+                                let short: String = template.chars().take(60).collect();
+                                ui.label(tr!("Template: {template}", template = short))
+                                    .on_hover_text(template);
+                            }
+                        }
+                    });
+                }
+                ui.separator();
+                if ui.button(tr!("Show All Again")).clicked() {
+                    for (source_id, suppression) in &suppressions {
+                        store.remove_suppression(*source_id, suppression);
+                    }
+                    session.state.modified = true;
+                    ui.close();
+                }
+                if let Some(index) = restore {
+                    let (source_id, suppression) = &suppressions[index];
+                    store.remove_suppression(*source_id, suppression);
+                    session.state.modified = true;
+                }
+            },
+        );
+    }
+
     /// Render central content area with dock layout
     fn render_central_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         profiling::scope!("central_panel");
@@ -747,10 +2134,10 @@ impl LogCrabApp {
     fn render_welcome_screen(&mut self, ui: &mut egui::Ui) {
         ui.vertical_centered(|ui| {
             ui.add_space(60.0);
-            ui.heading("Welcome to LogCrab 🦀");
+            ui.heading(tr!("Welcome to LogCrab 🦀"));
             ui.add_space(20.0);
 
-            if ui.button("Open Log File").clicked() {
+            if ui.button(tr!("Open Log File")).clicked() {
                 self.open_file_dialog();
             }
 
@@ -760,7 +2147,7 @@ impl LogCrabApp {
             if !self.session_history.sessions.is_empty() {
                 ui.separator();
                 ui.add_space(10.0);
-                ui.label(egui::RichText::new("Previous Sessions").strong());
+                ui.label(egui::RichText::new(tr!("Previous Sessions")).strong());
                 ui.add_space(8.0);
 
                 let mut session_to_restore: Option<usize> = None;
@@ -785,7 +2172,10 @@ impl LogCrabApp {
 
                                 if ui
                                     .button(&label)
-                                    .on_hover_text(format!("{tooltip}\n\nLast used: {time_str}"))
+                                    .on_hover_text(format!(
+                                        "{tooltip}\n\n{}",
+                                        tr!("Last used: {time}", time = time_str)
+                                    ))
                                     .clicked()
                                 {
                                     session_to_restore = Some(idx);
@@ -795,7 +2185,7 @@ impl LogCrabApp {
 
                                 if ui
                                     .small_button("✕")
-                                    .on_hover_text("Remove from history")
+                                    .on_hover_text(tr!("Remove from history"))
                                     .clicked()
                                 {
                                     session_to_remove = Some(idx);
@@ -817,6 +2207,38 @@ impl LogCrabApp {
         });
     }
 
+    /// Close the onboarding overlay and carry out the chosen action
+    fn finish_onboarding(&mut self, action: &windows::OnboardingAction) {
+        // Saving creates the config file, so the overlay is not shown again
+        self.global_config = GlobalConfig::update(|_| {});
+
+        match action {
+            windows::OnboardingAction::OpenSample => {
+                match windows::onboarding::write_sample_log() {
+                    Ok(path) => self.open_files_as_new_session(vec![path]),
+                    Err(e) => self
+                        .toast_manager
+                        .show_error(tr!("Failed to write sample log: {e}", e)),
+                }
+            }
+            windows::OnboardingAction::OpenFile => self.open_file_dialog(),
+            windows::OnboardingAction::Dismiss => {}
+        }
+    }
+
+    /// Select the line a permalink points to, or report why it cannot be found
+    fn go_to_permalink(&mut self, link: &Permalink) {
+        let Some(ref mut session) = self.session else {
+            return;
+        };
+        match session.state.store.resolve_permalink(link) {
+            Some(id) => session.state.selected_line_index = Some(id),
+            None => self
+                .toast_manager
+                .show_error(tr!("Permalink target not found - is the same file loaded?")),
+        }
+    }
+
     /// Render the "Restore previous session?" dialog window
     fn render_session_offer_dialog(&mut self, ctx: &egui::Context) {
         let Some(ref offer) = self.pending_session_offer else {
@@ -825,7 +2247,7 @@ impl LogCrabApp {
 
         let mut action: Option<SessionOfferAction> = None;
 
-        egui::Window::new("Restore Previous Session?")
+        egui::Window::new(tr!("Restore Previous Session?"))
             .collapsible(false)
             .resizable(true)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -837,14 +2259,18 @@ impl LogCrabApp {
                     .map(|n| n.to_string_lossy().into_owned())
                     .collect::<Vec<_>>()
                     .join(", ");
-                ui.label(format!(
-                    "The file(s) you selected ({file_label}) appeared in previous sessions."
+                ui.label(tr!(
+                    "The file(s) you selected ({files}) appeared in previous sessions.",
+                    files = file_label
                 ));
-                ui.label("Would you like to restore one of those sessions?");
+                ui.label(tr!("Would you like to restore one of those sessions?"));
                 ui.add_space(10.0);
 
                 // Option: open just the requested files
-                if ui.button(format!("Open only: {file_label}")).clicked() {
+                if ui
+                    .button(tr!("Open only: {files}", files = file_label))
+                    .clicked()
+                {
                     action = Some(SessionOfferAction::JustTheFiles);
                 }
 
@@ -864,7 +2290,11 @@ impl LogCrabApp {
                         .join("\n");
 
                     if ui
-                        .button(format!("Restore session: {label}  ({time_str})"))
+                        .button(tr!(
+                            "Restore session: {session}  ({time})",
+                            session = label,
+                            time = time_str
+                        ))
                         .on_hover_text(tooltip)
                         .clicked()
                     {
@@ -873,7 +2303,7 @@ impl LogCrabApp {
                 }
 
                 ui.add_space(6.0);
-                if ui.button("Cancel").clicked() {
+                if ui.button(tr!("Cancel")).clicked() {
                     action = Some(SessionOfferAction::Cancel);
                 }
             });
@@ -900,6 +2330,180 @@ impl LogCrabApp {
         }
     }
 
+    /// Render the "Merge session?" dialog for an imported `.crab` file
+    fn render_session_import_dialog(&mut self, ctx: &egui::Context) {
+        let Some(ref mut import) = self.pending_session_import else {
+            return;
+        };
+        let Some(ref session) = self.session else {
+            self.pending_session_import = None;
+            return;
+        };
+        let sources = session.state.store.get_source_filenames();
+
+        let mut merge = false;
+        let mut cancel = false;
+
+        egui::Window::new(tr!("Merge Session?"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let file_label = import
+                    .path
+                    .file_name()
+                    .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+                ui.label(tr!(
+                    "{file} contains {bookmarks} bookmarks, {filters} filters and {highlights} highlights.",
+                    file = file_label,
+                    bookmarks = import.imported.bookmarks.len(),
+                    filters = import.imported.filters.len(),
+                    highlights = import.imported.highlights.len(),
+                ));
+                ui.label(
+                    tr!("Merging keeps your own session: bookmarks are combined (conflicting names \
+                     are joined) and new filters are added as extra tabs."),
+                );
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Apply bookmarks to:"));
+                    let selected_name = import
+                        .target_source
+                        .and_then(|id| sources.iter().find(|(sid, _)| *sid == id))
+                        .map_or_else(|| tr!("Select a file…"), |(_, name)| name.as_str());
+                    egui::ComboBox::from_id_salt("session_import_target")
+                        .selected_text(selected_name)
+                        .show_ui(ui, |ui| {
+                            for (id, name) in &sources {
+                                ui.selectable_value(&mut import.target_source, Some(*id), name);
+                            }
+                        });
+                });
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(import.target_source.is_some(), egui::Button::new(tr!("Merge")))
+                        .clicked()
+                    {
+                        merge = true;
+                    }
+                    if ui.button(tr!("Cancel")).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if cancel {
+            self.pending_session_import = None;
+        } else if merge {
+            let Some(import) = self.pending_session_import.take() else {
+                return;
+            };
+            if let (Some(session), Some(source_id)) = (self.session.as_mut(), import.target_source)
+            {
+                let summary = session.merge_session(source_id, &import.imported);
+                self.toast_manager.show_success(tr!(
+                    "Merged session: {added} bookmarks added, {renamed} renamed, {filters} filters, {highlights} highlights",
+                    added = summary.bookmarks_added,
+                    renamed = summary.bookmarks_renamed,
+                    filters = summary.filters_added,
+                    highlights = summary.highlights_added,
+                ));
+            }
+        }
+    }
+
+    /// Render the "reload / overwrite / merge" dialog for the first session
+    /// both this and another instance changed
+    fn render_crab_conflict_dialog(&mut self, ctx: &egui::Context) {
+        let Some(conflict) = self.crab_conflicts.first() else {
+            return;
+        };
+        let Some(on_disk) = conflict.changed.clone() else {
+            self.crab_conflicts.remove(0);
+            return;
+        };
+        let file = conflict.log_name();
+
+        let mut choice = None;
+        egui::Window::new(tr!("Session Changed on Disk"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr!(
+                    "Another LogCrab instance saved the session of {file} while it was open read-only here, and both have changes.",
+                    file = file.as_str()
+                ));
+                ui.label(tr!(
+                    "The other instance saved {bookmarks} bookmarks, {filters} filters and {highlights} highlights.",
+                    bookmarks = on_disk.bookmarks.len(),
+                    filters = on_disk.filters.len(),
+                    highlights = on_disk.highlights.len(),
+                ));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr!("Reload"))
+                        .on_hover_text(tr!("Use the saved session and drop the bookmarks changed here"))
+                        .clicked()
+                    {
+                        choice = Some(CrabConflictChoice::Reload);
+                    }
+                    if ui
+                        .button(tr!("Overwrite"))
+                        .on_hover_text(tr!("Replace the saved session with this one"))
+                        .clicked()
+                    {
+                        choice = Some(CrabConflictChoice::Overwrite);
+                    }
+                    if ui
+                        .button(tr!("Merge"))
+                        .on_hover_text(tr!("Combine the bookmarks, filters and highlights of both"))
+                        .clicked()
+                    {
+                        choice = Some(CrabConflictChoice::Merge);
+                    }
+                });
+            });
+
+        let Some(choice) = choice else {
+            return;
+        };
+        let conflict = self.crab_conflicts.remove(0);
+        let Some(session) = self.session.as_mut() else {
+            return;
+        };
+        match choice {
+            CrabConflictChoice::Reload => session.reload_session(conflict.source_id, &on_disk),
+            CrabConflictChoice::Overwrite => session.save_crab_file(),
+            CrabConflictChoice::Merge => {
+                let summary = session.merge_session(conflict.source_id, &on_disk);
+                self.toast_manager.show_success(tr!(
+                    "Merged session: {added} bookmarks added, {renamed} renamed, {filters} filters, {highlights} highlights",
+                    added = summary.bookmarks_added,
+                    renamed = summary.bookmarks_renamed,
+                    filters = summary.filters_added,
+                    highlights = summary.highlights_added,
+                ));
+            }
+        }
+    }
+
+    /// Detected format of a file dragged over the window.
+    ///
+    /// Remembered per path so the header isn't re-read every frame of the drag.
+    fn dropped_file_format(ctx: &egui::Context, path: &std::path::Path) -> Option<&'static str> {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if ext == PROJECT_EXTENSION || ext == "crab-filters" {
+            return None;
+        }
+        let id = Id::new(("dropped_file_format", path));
+        ctx.data_mut(|d| *d.get_temp_mut_or_insert_with(id, || detect_format_name(path).ok()))
+    }
+
     /// Preview hovering files - shows overlay when dragging files over window
     fn preview_files_being_dropped(ctx: &egui::Context) {
         // Also guard on window focus: if the OS fails to send HoveredFileCancelled (a known
@@ -908,10 +2512,13 @@ impl LogCrabApp {
         let active = ctx.input(|i| !i.raw.hovered_files.is_empty() && i.focused);
         if active {
             let text = ctx.input(|i| {
-                let mut text = "Drop to open:\n".to_owned();
+                let mut text = format!("{}\n", tr!("Drop to open:"));
                 for file in &i.raw.hovered_files {
                     if let Some(path) = &file.path {
                         let _ = write!(text, "\n{}", path.display());
+                        if let Some(format) = Self::dropped_file_format(ctx, path) {
+                            let _ = write!(text, "  ({format})");
+                        }
                     }
                 }
                 text
@@ -958,19 +2565,17 @@ impl LogCrabApp {
             self.shortcut_bindings
                 .save_to_config(&mut self.global_config);
             let new_shortcuts = self.global_config.shortcuts.clone();
-            match GlobalConfig::update(|c| c.shortcuts = new_shortcuts) {
-                Ok(updated) => self.global_config = updated,
-                Err(e) => tracing::error!("Failed to update config: {e}"),
-            }
+            self.global_config = GlobalConfig::update(|c| c.shortcuts = new_shortcuts);
         }
 
         if let Some(ref mut log_view) = self.session {
-            log_view.process_keyboard_input(&actions);
+            log_view.process_keyboard_input(&actions, &self.global_config.quick_bookmark_template);
         }
 
         for action in actions {
             match action {
                 ShortcutAction::ToggleBookmark => {}
+                ShortcutAction::QuickBookmark => {}
                 ShortcutAction::FocusSearch => {}
                 ShortcutAction::NewFilterTab => {}
                 ShortcutAction::NewBookmarksTab => {}
@@ -984,7 +2589,13 @@ impl LogCrabApp {
                 ShortcutAction::OpenFile => {
                     self.open_file_dialog();
                 }
+                ShortcutAction::ReloadFiles => {
+                    self.reload_session_files();
+                }
                 ShortcutAction::RenameFilter => {}
+                ShortcutAction::NextSameTemplate => {}
+                ShortcutAction::PreviousSameTemplate => {}
+                ShortcutAction::ErrorsView => {}
                 ShortcutAction::MoveUp => {}
                 ShortcutAction::MoveDown => {}
                 ShortcutAction::FocusPaneLeft => {}
@@ -1010,6 +2621,8 @@ impl eframe::App for LogCrabApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         profiling::function_scope!();
 
+        crate::ui::repaint::set_max_fps(ctx, self.global_config.max_fps);
+
         // Update window title based on open files
         self.update_window_title(ctx);
 
@@ -1023,9 +2636,33 @@ impl eframe::App for LogCrabApp {
         // Process pending source removal
         if let Some(source_id) = self.pending_source_removal.take() {
             if let Some(ref mut session) = self.session {
-                // Save .crab file before removal to persist any unsaved data
-                session.save_crab_file();
-                session.state.store.remove_source(source_id);
+                session.remove_source(source_id);
+            }
+        }
+
+        self.poll_container_splits();
+        self.poll_file_changes();
+        self.poll_crab_locks(ctx);
+
+        if let Some(reload) = self.config_watcher.poll() {
+            self.apply_reloaded_config(ctx, &reload);
+        }
+
+        if self.pending_reparse.is_none() {
+            if let Some(store) = self.session.as_ref().map(|s| Arc::clone(&s.state.store)) {
+                self.pending_reparse = store
+                    .take_reparse_request()
+                    .and_then(|id| store.source_filetype_slug(id).map(|slug| (id, slug)));
+            }
+        }
+
+        if let Some((source_id, slug)) = self.pending_reparse.take() {
+            if let Some(path) = self
+                .session
+                .as_mut()
+                .and_then(|session| session.begin_reparse(source_id, slug))
+            {
+                self.add_file_to_session(path);
             }
         }
 
@@ -1038,11 +2675,40 @@ impl eframe::App for LogCrabApp {
             });
         }
 
+        if self
+            .changed_files
+            .iter()
+            .any(|p| !self.followed_files.contains(p))
+        {
+            egui::TopBottomPanel::top("file_change_banner").show(ctx, |ui| {
+                self.render_file_change_banner(ui);
+            });
+        }
+
         {
             profiling::scope!("bottom_panel");
             egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
                 self.render_status_panel(ui);
             });
+            if self.global_config.show_score_legend {
+                egui::TopBottomPanel::bottom("score_legend").show(ctx, |ui| {
+                    crate::ui::score_legend::render_score_legend(ui, &mut self.global_config);
+                });
+            }
+            if self.global_config.show_raw_panel {
+                if let Some(session) = &self.session {
+                    egui::TopBottomPanel::bottom("raw_source")
+                        .resizable(true)
+                        .show(ctx, |ui| {
+                            self.raw_panel.render(
+                                ui,
+                                &session.state.store,
+                                session.state.selected_line_index,
+                                &mut self.global_config,
+                            );
+                        });
+                }
+            }
         }
 
         {
@@ -1071,16 +2737,80 @@ impl eframe::App for LogCrabApp {
             windows::render_about_window(ctx, &mut self.show_about_window);
         }
 
+        if self.show_activity_log {
+            if let Some(ref session) = self.session {
+                windows::render_activity_log_window(
+                    ctx,
+                    &mut self.show_activity_log,
+                    &session.state.store,
+                );
+            }
+        }
+
+        if let Some(window) = self.onboarding_window.take() {
+            match window.render(ctx, &self.shortcut_bindings) {
+                None => self.onboarding_window = Some(window),
+                Some(action) => self.finish_onboarding(&action),
+            }
+        }
+
+        if let Some(mut window) = self.go_to_permalink_window.take() {
+            match window.render(ctx) {
+                Ok(None) => self.go_to_permalink_window = Some(window),
+                Ok(Some(link)) => self.go_to_permalink(&link),
+                Err(()) => {}
+            }
+        }
+
+        if let Some(mut window) = self.folder_analysis_window.take() {
+            match window.render(ctx) {
+                None => self.folder_analysis_window = Some(window),
+                Some(windows::FolderAnalysisAction::Open(path)) => {
+                    if self.session.is_some() {
+                        self.add_file_to_session(path);
+                    } else {
+                        self.open_files_as_new_session(vec![path]);
+                    }
+                    self.folder_analysis_window = Some(window);
+                }
+                Some(windows::FolderAnalysisAction::Close) => {}
+            }
+        }
+
         // Show session offer dialog
         if self.pending_session_offer.is_some() {
             self.render_session_offer_dialog(ctx);
         }
 
+        // Show session merge dialog
+        if self.pending_session_import.is_some() {
+            self.render_session_import_dialog(ctx);
+        }
+
+        // Show dialog for sessions changed by another instance
+        if !self.crab_conflicts.is_empty() {
+            self.render_crab_conflict_dialog(ctx);
+        }
+
+        if let Some(mut window) = self.scorer_settings_window.take() {
+            match window.render(ctx, self.session.is_some()) {
+                None => self.scorer_settings_window = Some(window),
+                Some(windows::ScorerSettingsAction::Save { weights, rescore }) => {
+                    anomaly::set_weights(weights);
+                    self.global_config = GlobalConfig::update(|c| c.scorer_weights = weights);
+                    if rescore {
+                        self.rescore_all_sources();
+                    }
+                }
+                Some(windows::ScorerSettingsAction::Close) => {}
+            }
+        }
+
         // Show sidecar settings window
         {
             if let Some(mut sidecar_window) = self.sidecar_settings_window.take() {
                 let mut open = true;
-                egui::Window::new("Sidecar Settings")
+                egui::Window::new(tr!("Sidecar Settings"))
                     .collapsible(false)
                     .resizable(true)
                     .open(&mut open)
@@ -1090,15 +2820,12 @@ impl eframe::App for LogCrabApp {
                             let port = self.global_config.sidecar_port;
                             let use_sidecar = self.global_config.use_sidecar_scoring;
                             let model = self.global_config.selected_model.clone();
-                            match GlobalConfig::update(|c| {
+                            self.global_config = GlobalConfig::update(|c| {
                                 c.sidecar_host = host;
                                 c.sidecar_port = port;
                                 c.use_sidecar_scoring = use_sidecar;
                                 c.selected_model = model;
-                            }) {
-                                Ok(updated) => self.global_config = updated,
-                                Err(e) => tracing::error!("Failed to update config: {e}"),
-                            }
+                            });
                             // Update store with new sidecar config
                             if let Some(ref session) = self.session {
                                 self.apply_sidecar_config_to_store(&session.state.store);
@@ -1119,11 +2846,189 @@ impl eframe::App for LogCrabApp {
     }
 }
 
+/// Segment controls of one source: split by time, and per segment visibility,
+/// name, time offset and removal. Returns `true` when the segments changed.
+fn render_segments_menu(ui: &mut egui::Ui, store: &LogStore, source_id: u64) -> bool {
+    let gap_id = Id::new("segment_split_gap_minutes");
+    let mut gap_minutes = ui.data(|d| d.get_temp::<u32>(gap_id)).unwrap_or(30);
+    let mut segments = store.get_segments(source_id);
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        ui.label(tr!("Split at clock resets and pauses over"));
+        ui.add(egui::DragValue::new(&mut gap_minutes).suffix(" min"))
+            .on_hover_text(tr!("0 splits at clock resets only"));
+    });
+    ui.data_mut(|d| d.insert_temp(gap_id, gap_minutes));
+    if ui
+        .button(tr!("✂ Split"))
+        .on_hover_text(tr!(
+            "Replace the segments of this source with one segment per \
+             boot or capture, detected from where the timestamps jump"
+        ))
+        .clicked()
+    {
+        let splits = store.find_split_points(source_id, i64::from(gap_minutes) * 60_000);
+        segments = std::iter::once(0)
+            .chain(splits)
+            .enumerate()
+            .map(|(i, start_line)| Segment::new(tr!("Segment {n}", n = i + 1), start_line))
+            .collect();
+        changed = true;
+    }
+
+    if segments.is_empty() {
+        ui.weak(tr!(
+            "No segments. Split by time, or right-click a line and choose \
+             “Start segment here”."
+        ));
+    } else {
+        ui.separator();
+        let mut removed = None;
+        for (i, segment) in segments.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let mut visible = !segment.hidden;
+                if ui
+                    .checkbox(&mut visible, "")
+                    .on_hover_text(tr!("Show the lines of this segment"))
+                    .changed()
+                {
+                    segment.hidden = !visible;
+                    changed = true;
+                }
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut segment.name).desired_width(120.0))
+                    .changed();
+                changed |= ui
+                    .add(egui::DragValue::new(&mut segment.offset_ms).suffix(" ms"))
+                    .on_hover_text(tr!("Time offset of this segment"))
+                    .changed();
+                if ui
+                    .small_button("🗑")
+                    .on_hover_text(tr!("Remove segment"))
+                    .clicked()
+                {
+                    removed = Some(i);
+                }
+            });
+        }
+        if let Some(i) = removed {
+            segments.remove(i);
+            changed = true;
+        }
+        if ui.button(tr!("Clear segments")).clicked() {
+            segments.clear();
+            changed = true;
+        }
+    }
+
+    if changed {
+        store.set_segments(source_id, segments);
+    }
+    changed
+}
+
+/// Edit the level rules of a source: the list of rules with a remove button
+/// each, and a row adding a new one. Returns the new rules once they changed.
+fn render_level_rules_menu(
+    ui: &mut egui::Ui,
+    store: &LogStore,
+    source_id: u64,
+) -> Option<Vec<LevelRule>> {
+    let draft_id = Id::new(("level_rule_draft", source_id));
+    let mut draft: LevelRule = ui.data(|d| d.get_temp(draft_id)).unwrap_or_default();
+    let mut rules = store.get_level_rules(source_id);
+    let mut changed = false;
+
+    ui.weak(tr!(
+        "Lines whose message matches a pattern get its level, for column filters \
+         and anomaly scores. The first matching rule wins."
+    ));
+    if !rules.is_empty() {
+        ui.separator();
+        let mut removed = None;
+        for (i, rule) in rules.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.monospace(&rule.pattern);
+                ui.label("→");
+                ui.strong(&rule.level);
+                if ui
+                    .small_button("🗑")
+                    .on_hover_text(tr!("Remove rule"))
+                    .clicked()
+                {
+                    removed = Some(i);
+                }
+            });
+        }
+        if let Some(i) = removed {
+            rules.remove(i);
+            changed = true;
+        }
+    }
+
+    ui.separator();
+    let labels = store.level_labels(source_id);
+    if !labels.contains(&draft.level) {
+        draft.level = labels.first().cloned().unwrap_or_default();
+    }
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut draft.pattern)
+                .hint_text(tr!("Regex"))
+                .desired_width(160.0),
+        );
+        egui::ComboBox::from_id_salt(draft_id.with("level"))
+            .selected_text(draft.level.clone())
+            .show_ui(ui, |ui| {
+                for label in &labels {
+                    ui.selectable_value(&mut draft.level, label.clone(), label);
+                }
+            });
+        let valid = !draft.pattern.is_empty() && level_rule::is_valid(&draft.pattern);
+        if ui
+            .add_enabled(valid, egui::Button::new(tr!("Add")))
+            .on_disabled_hover_text(tr!("Enter a valid regular expression"))
+            .clicked()
+        {
+            rules.push(draft.clone());
+            draft.pattern.clear();
+            changed = true;
+        }
+    });
+    ui.data_mut(|d| d.insert_temp(draft_id, draft));
+
+    changed.then_some(rules)
+}
+
+/// Open the system file manager at `path`, selecting it where supported.
+fn reveal_in_file_manager(path: &std::path::Path) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = std::process::Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    } else {
+        // xdg-open has no way to select a file, so open its directory
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(path.parent().unwrap_or(path));
+        command
+    };
+    let mut child = command.spawn()?;
+    // Reap the launcher once it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
 impl Drop for LogCrabApp {
     fn drop(&mut self) {
         // Save .crab files and record session history on exit
         if let Some(ref session) = self.session {
             session.save_crab_file();
+            session.state.store.flush_crab_files();
         }
         self.record_current_session();
     }
@@ -17,24 +17,64 @@
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::config::GlobalConfig;
+use crate::core::column_filter::SOURCE_COLUMN;
 use crate::core::histogram_worker::HistogramWorkerHandle;
-use crate::core::session::CRAB_FILTERS_VERSION;
-use crate::core::{CrabFilters, LogFileLoader, LogStore, SavedFilter, SavedHighlight, SearchRule};
+use crate::core::line_anchor::LineAnchor;
+use crate::core::log_store::{Bookmark, StoreID};
+use crate::core::net_endpoint::Endpoint;
+use crate::core::session::{
+    merge_bookmarks, ImportedSession, CRAB_FILTERS_VERSION, CRAB_PROJECT_VERSION,
+};
+use crate::core::{
+    CrabFilters, CrabProject, LogFileLoader, LogStore, SavedFilter, SavedHighlight, SearchRule,
+};
+use crate::filetype::{pcap::PcapFileType, HasSlug};
 use crate::input::ShortcutAction;
+use crate::tr;
 use crate::ui::filter_highlight::FilterHighlight;
-use crate::ui::session_state::SessionState;
+use crate::ui::session_state::{FilterGroupAction, SessionState};
 use crate::ui::tabs::filter_tab::filter_state::FilterState;
 use crate::ui::tabs::{
-    navigation, BookmarksView, FilterView, HighlightsView, LogCrabTab, LogCrabTabViewer,
-    PendingTabAdd,
+    navigation, BookmarksView, FilterView, HeatmapView, HighlightsView, LogCrabTab,
+    LogCrabTabViewer, PendingTabAdd,
+};
+use crate::ui::windows::anomaly_summary::SUMMARY_THRESHOLD;
+use crate::ui::windows::{
+    AnomalySummary, AnomalySummaryAction, BookmarkSuggestionsAction, BookmarkSuggestionsWindow,
 };
 use crate::ui::{PaneDirection, ProgressToastHandle, DEFAULT_PALETTE};
 
 use chrono::Local;
 use egui_dock::{DockArea, DockState, Node};
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Counts reported after merging an imported `.crab` into the session
+#[derive(Debug, Default)]
+pub struct SessionMergeSummary {
+    pub bookmarks_added: usize,
+    pub bookmarks_renamed: usize,
+    pub filters_added: usize,
+    pub highlights_added: usize,
+}
+
+/// Anchored selection of a source that is being reloaded
+struct PendingReload {
+    path: PathBuf,
+    /// Selected line of this source before the reload (index and anchor)
+    selection: (usize, LineAnchor),
+}
+
+/// Source being parsed again with a different format
+struct PendingReparse {
+    path: PathBuf,
+    /// Bookmarks of the source, held back until the new parse has finished
+    bookmarks: Vec<Bookmark>,
+    /// Number of lines the previous parse produced
+    line_count: usize,
+}
+
 /// Main log viewing session for an opened file.
 ///
 /// Responsibilities:
@@ -53,6 +93,21 @@ pub struct CrabSession {
 
     /// Pending tab add request (set by add button callback)
     pending_tab_add: Option<PendingTabAdd>,
+
+    /// Sources being reloaded whose selection still needs re-anchoring
+    pending_reloads: Vec<PendingReload>,
+
+    /// Format chosen by the user for a file, overriding detection on (re)load
+    parser_overrides: HashMap<PathBuf, &'static str>,
+
+    /// Sources being re-parsed whose bookmarks still need to be restored
+    pending_reparses: Vec<PendingReparse>,
+
+    /// Top anomalies of freshly scored sources, until dismissed
+    anomaly_summaries: Vec<AnomalySummary>,
+
+    /// Open "Suggest Bookmarks" checklist
+    bookmark_suggestions: Option<BookmarkSuggestionsWindow>,
 }
 
 impl CrabSession {
@@ -65,6 +120,11 @@ impl CrabSession {
             dock_state: DockState::new(Vec::new()),
             monotonic_filter_counter: 0,
             pending_tab_add: None,
+            pending_reloads: Vec::new(),
+            parser_overrides: HashMap::new(),
+            pending_reparses: Vec::new(),
+            anomaly_summaries: Vec::new(),
+            bookmark_suggestions: None,
             state: SessionState::new(store, filter_worker, histogram_worker),
         };
         cs.add_filter_view(false, None);
@@ -95,10 +155,56 @@ impl CrabSession {
         if focus_search {
             filter.focus_search_next_frame();
         }
+        filter.resolve_source_names(&self.source_paths());
         self.dock_state.push_to_focused_leaf(filter);
         self.monotonic_filter_counter += 1;
     }
 
+    /// Focus the built-in "Errors & Fatals" view, opening it if needed.
+    ///
+    /// The view does not take a palette color from the user filter counter.
+    pub fn open_errors_view(&mut self) {
+        if let Some(path) = self.dock_state.find_tab_from(|t| t.is_errors_view()) {
+            self.dock_state.set_active_tab(path);
+            self.dock_state
+                .set_focused_node_and_surface((path.0, path.1));
+        } else {
+            self.dock_state
+                .push_to_focused_leaf(Box::new(FilterView::new(FilterState::errors_view())));
+        }
+    }
+
+    /// Match count of the active tab in the focused pane, if it filters lines
+    pub fn focused_match_count(&mut self) -> Option<usize> {
+        self.dock_state
+            .find_active_focused()
+            .and_then(|(_, tab)| tab.match_count())
+    }
+
+    /// UUID, title and match count of every filter tab, in dock order
+    pub fn filter_match_counts(&mut self) -> Vec<(usize, String, usize)> {
+        self.dock_state
+            .iter_all_tabs_mut()
+            .filter_map(|(_, tab)| {
+                let uuid = tab.get_uuid()?;
+                let count = tab.match_count()?;
+                Some((uuid, tab.title().text().to_owned(), count))
+            })
+            .collect()
+    }
+
+    /// Bring the filter tab with `uuid` to the front and focus its pane
+    pub fn focus_filter_tab(&mut self, uuid: usize) {
+        if let Some(path) = self
+            .dock_state
+            .find_tab_from(|t| t.get_uuid() == Some(uuid))
+        {
+            self.dock_state.set_active_tab(path);
+            self.dock_state
+                .set_focused_node_and_surface((path.0, path.1));
+        }
+    }
+
     /// Add a file to the current session.
     ///
     /// Loads the file asynchronously and adds it as an additional source to the store.
@@ -123,11 +229,21 @@ impl CrabSession {
 
         tracing::info!("Adding file to session: {}", path.display());
 
-        let Some((variant, filters, highlights)) =
-            LogFileLoader::load_file(path, toast, warnings, file_config, &self.state.store)
-        else {
-            toast.set_error(format!("Cannot open '{}'", path.display()));
+        let loaded = match self.parser_overrides.get(path) {
+            Some(slug) => LogFileLoader::load_file_as(
+                path,
+                slug,
+                toast,
+                warnings,
+                file_config,
+                &self.state.store,
+            ),
+            None => LogFileLoader::load_file(path, toast, warnings, file_config, &self.state.store),
+        };
+        let Some((variant, filters, highlights)) = loaded else {
+            toast.set_error(tr!("Cannot open '{path}'", path = path.display()));
             toast.dismiss();
+            self.abandon_pending_reload(path, warnings);
             return;
         };
 
@@ -138,6 +254,40 @@ impl CrabSession {
         for saved_highlight in &highlights {
             self.add_highlight_if_not_exists(saved_highlight);
         }
+
+        let paths = self.source_paths();
+        for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+            if tab.resolve_source_names(&paths) {
+                self.state.modified = true;
+            }
+        }
+    }
+
+    /// Paths of the open sources, as the `Source` column lists them
+    fn source_paths(&self) -> Vec<String> {
+        self.state
+            .store
+            .get_source_ids_and_paths()
+            .into_iter()
+            .map(|(_, path)| path.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Add the live system journal to the current session, captured to
+    /// `capture_path`.
+    pub fn follow_journal(
+        &mut self,
+        capture_path: &Path,
+        toast: &ProgressToastHandle,
+        warnings: &crate::ui::ToastSender,
+    ) {
+        tracing::info!(
+            "Following the system journal into {}",
+            capture_path.display()
+        );
+        let (variant, _, _) =
+            LogFileLoader::follow_journal(capture_path, toast, warnings, &self.state.store);
+        self.state.store.add_source(variant);
     }
 
     fn add_filter_if_not_exists(&mut self, saved_filter: &SavedFilter) {
@@ -239,9 +389,333 @@ impl CrabSession {
         Ok(count)
     }
 
+    /// Snapshot the open files and shared filters/highlights as a project.
+    pub fn to_project(&self) -> CrabProject {
+        CrabProject {
+            version: CRAB_PROJECT_VERSION,
+            files: self.state.store.get_source_file_paths(),
+            filters: self
+                .dock_state
+                .iter_all_tabs()
+                .filter_map(|((_surface, _node), tab)| tab.try_into_stored_filter())
+                .collect(),
+            highlights: self.state.highlights.iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Add the project's filters and highlights that are not open yet.
+    pub fn apply_project(&mut self, project: &CrabProject) {
+        for saved_filter in &project.filters {
+            self.add_filter_if_not_exists(saved_filter);
+        }
+        for saved_highlight in &project.highlights {
+            self.add_highlight_if_not_exists(saved_highlight);
+        }
+    }
+
+    /// Merge a teammate's session into the source `source_id`.
+    ///
+    /// Bookmarks are unioned (conflicting names are combined, see
+    /// [`merge_bookmarks`]); filters and highlights whose search text is not
+    /// present yet are appended as new tabs / highlights.
+    pub fn merge_session(
+        &mut self,
+        source_id: u64,
+        imported: &ImportedSession,
+    ) -> SessionMergeSummary {
+        let mine: Vec<Bookmark> = self
+            .state
+            .get_all_bookmarks()
+            .into_iter()
+            .filter(|b| b.store_id.source_id() == source_id)
+            .map(|b| Bookmark {
+                line_index: b.store_id.line_index_within_source(),
+                name: b.name,
+                category: b.category,
+                anchor: None,
+                attachments: b.attachments,
+                orphaned: b.orphaned,
+            })
+            .collect();
+        let merge = merge_bookmarks(&mine, &imported.bookmarks);
+        for bookmark in merge.bookmarks {
+            self.state
+                .store
+                .set_bookmark(&StoreID::make(source_id, bookmark.line_index), bookmark.name);
+        }
+
+        let filters_before = self.dock_state.iter_all_tabs().count();
+        for saved_filter in &imported.filters {
+            self.add_filter_if_not_exists(saved_filter);
+        }
+        let highlights_before = self.state.highlights.len();
+        for saved_highlight in &imported.highlights {
+            self.add_highlight_if_not_exists(saved_highlight);
+        }
+
+        let summary = SessionMergeSummary {
+            bookmarks_added: merge.added,
+            bookmarks_renamed: merge.renamed,
+            filters_added: self.dock_state.iter_all_tabs().count() - filters_before,
+            highlights_added: self.state.highlights.len() - highlights_before,
+        };
+        tracing::info!("Merged imported session: {summary:?}");
+
+        self.state.modified = true;
+        self.save_crab_file();
+        summary
+    }
+
+    /// Replace the bookmarks of `source_id` with those saved by another
+    /// instance, dropping the local ones.
+    ///
+    /// Filters and highlights are shared by all sources, so the saved ones are
+    /// added where missing rather than replacing the open tabs.
+    pub fn reload_session(&mut self, source_id: u64, on_disk: &ImportedSession) {
+        let store = Arc::clone(&self.state.store);
+        store.take_bookmarks(source_id);
+        store.restore_bookmarks(source_id, on_disk.bookmarks.clone());
+        for saved_filter in &on_disk.filters {
+            self.add_filter_if_not_exists(saved_filter);
+        }
+        for saved_highlight in &on_disk.highlights {
+            self.add_highlight_if_not_exists(saved_highlight);
+        }
+        tracing::info!(
+            "Reloaded session of source {source_id}: {} bookmarks",
+            on_disk.bookmarks.len()
+        );
+
+        self.state.modified = true;
+        self.save_crab_file();
+    }
+
+    /// Drop every source so it can be read again from disk.
+    ///
+    /// Bookmarks are saved with their content anchors and re-anchored by the
+    /// loader; the selection is remembered the same way and restored once the
+    /// new content has loaded (see [`Self::finish_pending_reloads`]). Filters
+    /// and tabs are untouched. Returns the paths the caller must add again.
+    pub fn begin_reload(&mut self) -> Vec<PathBuf> {
+        let paths = self.state.store.get_source_file_paths();
+        self.begin_reload_of(paths)
+    }
+
+    /// Like [`Self::begin_reload`], but only for the sources loaded from `paths`.
+    pub fn begin_reload_of(&mut self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        let store = Arc::clone(&self.state.store);
+        // Wait for the write so the files can be reopened right away
+        self.save_crab_file();
+        store.flush_crab_files();
+        let selected = self.state.selected_line_index;
+
+        // A log split into containers reloads all of them
+        for source_id in paths
+            .iter()
+            .flat_map(|path| store.source_ids_for_path(path))
+        {
+            if let Some(id) = selected.filter(|id| id.source_id() == source_id) {
+                if let (Some(anchor), Some(path)) =
+                    (store.line_anchor(&id), store.source_path(source_id))
+                {
+                    self.pending_reloads.push(PendingReload {
+                        path,
+                        selection: (id.line_index_within_source(), anchor),
+                    });
+                }
+            }
+            store.remove_source(source_id);
+        }
+        paths
+    }
+
+    /// Forget the selection and held-back bookmarks of a reloaded or
+    /// re-parsed source that could not be opened again.
+    ///
+    /// The bookmarks were saved before the source was dropped, so they come
+    /// back from the `.crab` file once the file opens again.
+    fn abandon_pending_reload(&mut self, path: &Path, warnings: &crate::ui::ToastSender) {
+        let pending = self.pending_reloads.len() + self.pending_reparses.len();
+        self.pending_reloads.retain(|reload| reload.path != path);
+        self.pending_reparses.retain(|reparse| reparse.path != path);
+        if self.pending_reloads.len() + self.pending_reparses.len() < pending {
+            let file = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            warnings.send(tr!(
+                "'{file}' could not be opened again and was removed from the session",
+                file
+            ));
+        }
+    }
+
+    /// Drop a source so it can be parsed again as the format `slug`.
+    ///
+    /// The choice sticks for later reloads of the file. Bookmarks are held
+    /// back and restored once the new parse has finished (see
+    /// [`Self::finish_pending_reparses`]). Returns the path the caller must
+    /// add again.
+    pub fn begin_reparse(&mut self, source_id: u64, slug: &'static str) -> Option<PathBuf> {
+        let store = Arc::clone(&self.state.store);
+        let line_count = store.source_len(source_id);
+        // The bookmarks stay in the file until the new parse has finished, so
+        // closing the app in the meantime does not lose them
+        self.save_crab_file();
+        store.flush_crab_files();
+        let bookmarks = store.take_bookmarks(source_id);
+        let path = store.remove_source(source_id)?;
+        if self
+            .state
+            .selected_line_index
+            .is_some_and(|id| id.source_id() == source_id)
+        {
+            self.state.selected_line_index = None;
+        }
+        tracing::info!("Re-parsing {} as {slug}", path.display());
+        self.parser_overrides.insert(path.clone(), slug);
+        self.pending_reparses.push(PendingReparse {
+            path: path.clone(),
+            bookmarks,
+            line_count,
+        });
+        Some(path)
+    }
+
+    /// Drop a source from the session for good.
+    ///
+    /// Its `.crab` file is written first, so bookmarks come back when the file
+    /// is opened again. Lines of the other sources keep their `StoreID`s, so
+    /// their bookmarks, filters and selection stay valid.
+    pub fn remove_source(&mut self, source_id: u64) {
+        let store = Arc::clone(&self.state.store);
+        let Some(path) = store.source_path(source_id) else {
+            return;
+        };
+        // Bookmarks held back for a pending re-parse go back into the file
+        let (reparsed, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_reparses)
+            .into_iter()
+            .partition(|reparse| reparse.path == path);
+        self.pending_reparses = pending;
+        for reparse in reparsed {
+            store.restore_bookmarks(source_id, reparse.bookmarks);
+        }
+        self.pending_reloads.retain(|reload| reload.path != path);
+
+        // Wait for the write so the file can be reopened right away
+        self.save_crab_file();
+        store.flush_crab_files();
+        store.remove_source(source_id);
+        if self
+            .state
+            .selected_line_index
+            .is_some_and(|id| id.source_id() == source_id)
+        {
+            self.state.selected_line_index = None;
+        }
+    }
+
+    /// Restore bookmarks of re-parsed sources that finished loading.
+    ///
+    /// They replace the bookmarks the new parse read from the `.crab` file.
+    /// Line indices only carry over if the new parse produced as many lines
+    /// as the old one. Otherwise the bookmarks are kept but flagged orphaned,
+    /// and those past the end of the new parse are dropped and reported.
+    fn finish_pending_reparses(&mut self) {
+        if self.pending_reparses.is_empty() {
+            return;
+        }
+        let store = Arc::clone(&self.state.store);
+        let mut restored = false;
+        for reparse in std::mem::take(&mut self.pending_reparses) {
+            let Some(source_id) = store
+                .source_id_for_path(&reparse.path)
+                .filter(|id| store.is_source_loaded(*id))
+            else {
+                self.pending_reparses.push(reparse);
+                continue;
+            };
+            let new_len = store.source_len(source_id);
+            let aligned = new_len == reparse.line_count;
+            let (bookmarks, dropped): (Vec<_>, Vec<_>) = reparse
+                .bookmarks
+                .into_iter()
+                .map(|mut b| {
+                    if aligned {
+                        // Anchor again against the new content on the next save
+                        b.anchor = None;
+                        b.orphaned = false;
+                    } else {
+                        b.orphaned = true;
+                    }
+                    b
+                })
+                .partition(|b| b.line_index < new_len);
+            let kept = bookmarks.len();
+            // Read from the file again by the new parse, against foreign content
+            store.take_bookmarks(source_id);
+            store.restore_bookmarks(source_id, bookmarks);
+            let file = reparse
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            if let Some(sender) = &self.state.toast_sender {
+                if !aligned && kept > 0 {
+                    sender.send(tr!(
+                        "Line count of '{file}' changed after re-parsing; {count} bookmark(s) may point at the wrong line",
+                        file,
+                        count = kept
+                    ));
+                }
+                if !dropped.is_empty() {
+                    let names: Vec<&str> = dropped.iter().map(|b| b.name.as_str()).collect();
+                    sender.send(tr!(
+                        "Re-parsing '{file}' left {count} bookmark(s) past its end, removed: {names}",
+                        file,
+                        count = dropped.len(),
+                        names = names.join(", ")
+                    ));
+                }
+            }
+            self.state.modified = true;
+            restored = true;
+        }
+        if restored {
+            self.save_crab_file();
+        }
+    }
+
+    /// Restore the selection in reloaded sources that finished loading.
+    fn finish_pending_reloads(&mut self) {
+        if self.pending_reloads.is_empty() {
+            return;
+        }
+        let store = Arc::clone(&self.state.store);
+        for reload in std::mem::take(&mut self.pending_reloads) {
+            let Some(source_id) = store
+                .source_id_for_path(&reload.path)
+                .filter(|id| store.is_source_loaded(*id))
+            else {
+                self.pending_reloads.push(reload);
+                continue;
+            };
+            if let Some(Some(index)) = store.resolve_anchors(source_id, &[reload.selection]).first()
+            {
+                self.state.selected_line_index = Some(StoreID::make(source_id, *index));
+            }
+        }
+    }
+
     pub fn render(&mut self, ui: &mut egui::Ui, global_config: &mut GlobalConfig) {
         profiling::scope!("LogView::render");
 
+        self.finish_pending_reloads();
+        self.finish_pending_reparses();
+        self.collect_anomaly_summaries();
+
         // Collect all filter highlights from all tabs
         let mut all_filter_highlights: Vec<FilterHighlight> = {
             profiling::scope!("collect_filter_highlights");
@@ -299,16 +773,35 @@ impl CrabSession {
             for bookmark in bookmarks {
                 histogram_markers.push(crate::ui::tabs::filter_tab::HistogramMarker {
                     name: bookmark.name,
-                    color: egui::Color32::from_rgb(255, 215, 0), // Gold color
+                    color: crate::ui::tabs::filter_tab::histogram::BOOKMARK_COLOR,
                     indices: std::sync::Arc::new(vec![bookmark.store_id]),
                 });
             }
         }
 
+        let filter_groups: Vec<String> = self
+            .dock_state
+            .iter_all_tabs()
+            .filter_map(|(_, tab)| tab.group().map(str::to_string))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        if global_config.focus_follows_mouse {
+            self.focus_pane_under_pointer(ui.ctx());
+        }
+
+        // Accent the focused pane's active tab and outline the pane itself
+        let focus_accent = ui.visuals().selection.stroke.color;
+        let mut dock_style = egui_dock::Style::from_egui(ui.style());
+        dock_style.tab.focused.outline_color = focus_accent;
+        dock_style.tab.focused_with_kb_focus.outline_color = focus_accent;
+
         // Use dock area for VS Code-like draggable/tiling layout
         {
             profiling::scope!("DockArea::show");
             DockArea::new(&mut self.dock_state)
+                .style(dock_style)
                 .show_add_buttons(true)
                 .show_add_popup(true)
                 .show_inside(
@@ -319,9 +812,11 @@ impl CrabSession {
                         pending_tab_add: &mut self.pending_tab_add,
                         all_filter_highlights: &all_filter_highlights,
                         histogram_markers: &histogram_markers,
+                        filter_groups: &filter_groups,
                     },
                 );
         }
+        self.paint_focused_pane_border(ui, focus_accent);
         if self.state.modified
             && self
                 .state
@@ -348,9 +843,37 @@ impl CrabSession {
                     self.dock_state
                         .push_to_focused_leaf(Box::new(BookmarksView::default()));
                 }
+                PendingTabAdd::Heatmap => {
+                    self.dock_state
+                        .push_to_focused_leaf(Box::new(HeatmapView::default()));
+                }
             }
         }
 
+        // Zoom all filter timelines to a period picked in the heatmap
+        if let Some((start, end)) = self.state.pending_time_zoom.take() {
+            for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+                tab.zoom_to_time_range(start, end);
+            }
+        }
+
+        if let Some(action) = self.state.pending_group_action.take() {
+            match action {
+                FilterGroupAction::SetGloballyVisible { group, visible } => {
+                    for (_, tab) in self.dock_state.iter_all_tabs_mut() {
+                        if tab.group() == Some(group.as_str()) {
+                            tab.set_globally_visible(visible);
+                        }
+                    }
+                }
+                FilterGroupAction::Close { group } => {
+                    self.dock_state
+                        .retain_tabs(|t| t.group() != Some(group.as_str()));
+                }
+            }
+            self.state.modified = true;
+        }
+
         // Drive any open calibration windows for all sources (one per source per frame).
         if self.state.store.render_file_states(ui) {
             self.state.modified = true;
@@ -377,6 +900,42 @@ impl CrabSession {
             }
         }
 
+        // Handle filter creation from bookmarked lines' templates
+        if let Some(templates) = self.state.pending_template_filter.take() {
+            let color = DEFAULT_PALETTE[self.monotonic_filter_counter % DEFAULT_PALETTE.len()];
+            let mut filter_state =
+                FilterState::new(format!("{} templates", templates.len()), color);
+            filter_state.search.search_text = templates
+                .iter()
+                .map(|template| format!("(?:{})", crate::parser::template_to_pattern(template)))
+                .collect::<Vec<_>>()
+                .join("|");
+            self.add_filter_view(false, Some(filter_state));
+            self.state.modified = true;
+        }
+
+        if let Some(id) = self.state.pending_id_filter.take() {
+            let color = DEFAULT_PALETTE[self.monotonic_filter_counter % DEFAULT_PALETTE.len()];
+            let mut filter_state = FilterState::new(format!("ID {id}"), color);
+            filter_state.search.search_text = fancy_regex::escape(&id).into_owned();
+            self.add_filter_view(false, Some(filter_state));
+            self.state.modified = true;
+        }
+
+        if let Some((endpoint, origin)) = self.state.pending_packet_filter.take() {
+            self.show_packets_of(endpoint, origin);
+        }
+
+        if let Some(patterns) = self.state.pending_split_filters.take() {
+            for pattern in patterns {
+                let color = DEFAULT_PALETTE[self.monotonic_filter_counter % DEFAULT_PALETTE.len()];
+                let mut filter_state = FilterState::new(String::new(), color);
+                filter_state.search.search_text = fancy_regex::escape(&pattern).into_owned();
+                self.add_filter_view(false, Some(filter_state));
+            }
+            self.state.modified = true;
+        }
+
         // Handle filter-to-highlight conversion
         if let Some(data) = self.state.pending_filter_to_highlight.take() {
             let mut highlight = SearchRule::new(data.name, data.color);
@@ -393,14 +952,186 @@ impl CrabSession {
             self.dock_state
                 .retain_tabs(|t| t.get_uuid() != Some(data.filter_uuid));
         }
+
+        self.render_anomaly_summaries(ui.ctx());
+        self.render_bookmark_suggestions(ui.ctx());
+    }
+
+    /// Focus the packet tab for `endpoint`, opening it if needed, and select
+    /// the matching packet closest in time to the `origin` line.
+    ///
+    /// The tab shows only the packet capture sources, so the application log
+    /// lines naming the endpoint do not clutter it.
+    fn show_packets_of(&mut self, endpoint: Endpoint, origin: StoreID) {
+        let store = Arc::clone(&self.state.store);
+        let pcap_sources = store.sources_with_filetype(<PcapFileType as HasSlug>::SLUG);
+        let pattern = endpoint.packet_pattern();
+
+        if let Some(path) = self.dock_state.find_tab_from(|t| {
+            t.try_into_stored_filter()
+                .is_some_and(|saved| saved.search_text == pattern)
+        }) {
+            self.dock_state.set_active_tab(path);
+            self.dock_state
+                .set_focused_node_and_surface((path.0, path.1));
+        } else {
+            let color = DEFAULT_PALETTE[self.monotonic_filter_counter % DEFAULT_PALETTE.len()];
+            let mut filter_state = FilterState::new(format!("📡 {endpoint}"), color);
+            filter_state.search.search_text.clone_from(&pattern);
+            let other_sources: Vec<String> = store
+                .get_source_ids_and_paths()
+                .into_iter()
+                .filter(|(source_id, _)| !pcap_sources.iter().any(|(id, _)| id == source_id))
+                .map(|(_, path)| path.to_string_lossy().into_owned())
+                .collect();
+            filter_state
+                .search
+                .column_filter
+                .hide_all(SOURCE_COLUMN, other_sources.iter().map(String::as_str));
+            self.add_filter_view(false, Some(filter_state));
+            self.state.modified = true;
+        }
+
+        let (Ok(regex), Some(origin_time)) = (
+            fancy_regex::Regex::new(&pattern),
+            store.adjusted_timestamp(&origin),
+        ) else {
+            return;
+        };
+        let closest = store
+            .get_matching_ids(|message, _| regex.is_match(message).unwrap_or(false))
+            .into_iter()
+            .filter(|id| {
+                pcap_sources
+                    .iter()
+                    .any(|(source_id, _)| *source_id == id.source_id())
+            })
+            .filter_map(|id| {
+                let time = store.adjusted_timestamp(&id)?;
+                Some((id, (time - origin_time).num_milliseconds().abs()))
+            })
+            .min_by_key(|&(_, distance)| distance);
+        match closest {
+            Some((id, _)) => self.state.selected_line_index = Some(id),
+            None => {
+                if let Some(sender) = &self.state.toast_sender {
+                    sender.send(tr!("No captured packets from or to {endpoint}", endpoint));
+                }
+            }
+        }
+    }
+
+    /// Open the checklist of bookmarks suggested at the strongest anomaly
+    /// clusters, by ML sidecar scores if `ml` is set.
+    pub fn suggest_bookmarks(&mut self, ml: bool) {
+        self.bookmark_suggestions = Some(BookmarkSuggestionsWindow::new(&self.state.store, ml));
+    }
+
+    fn render_bookmark_suggestions(&mut self, ctx: &egui::Context) {
+        let Some(window) = &mut self.bookmark_suggestions else {
+            return;
+        };
+        match window.render(ctx, &self.state.store) {
+            Some(BookmarkSuggestionsAction::Jump(id)) => {
+                self.state.selected_line_index = Some(id);
+            }
+            Some(BookmarkSuggestionsAction::Accept(bookmarks)) => {
+                self.bookmark_suggestions = None;
+                for (id, name) in bookmarks {
+                    self.state.add_named_bookmark(id, name);
+                }
+            }
+            Some(BookmarkSuggestionsAction::Close) => self.bookmark_suggestions = None,
+            None => {}
+        }
+    }
+
+    /// Summarize the top anomalies of every source whose scoring just finished.
+    ///
+    /// A newer summary of the same source (e.g. once ML scores arrive)
+    /// replaces the older one.
+    fn collect_anomaly_summaries(&mut self) {
+        let store = &self.state.store;
+        for (source_id, ml) in store.take_finished_scoring() {
+            let lines = store.lines_scored_above(source_id, SUMMARY_THRESHOLD, ml);
+            let Some(first) = lines.first() else {
+                continue;
+            };
+            let source_name = store.get_source_name(first).unwrap_or_default();
+            self.anomaly_summaries.retain(|s| s.source_id != source_id);
+            self.anomaly_summaries.push(AnomalySummary {
+                source_id,
+                source_name,
+                ml,
+                lines,
+            });
+        }
     }
 
-    pub fn process_keyboard_input(&mut self, actions: &[ShortcutAction]) {
+    fn render_anomaly_summaries(&mut self, ctx: &egui::Context) {
+        let mut picked = None;
+        for (slot, summary) in self.anomaly_summaries.iter().enumerate() {
+            if let Some(action) = summary.render(ctx, slot) {
+                picked = Some((slot, action));
+            }
+        }
+        let Some((slot, action)) = picked else {
+            return;
+        };
+        match action {
+            AnomalySummaryAction::JumpToFirst => {
+                self.state.selected_line_index =
+                    self.anomaly_summaries[slot].lines.first().copied();
+            }
+            AnomalySummaryAction::OpenTab => {
+                let summary = self.anomaly_summaries.remove(slot);
+                self.open_anomalies_tab(&summary);
+            }
+            AnomalySummaryAction::Dismiss => {
+                self.anomaly_summaries.remove(slot);
+            }
+        }
+    }
+
+    /// Open a filter tab matching the message templates of the summarized lines
+    fn open_anomalies_tab(&mut self, summary: &AnomalySummary) {
+        let templates: BTreeSet<String> = summary
+            .lines
+            .iter()
+            .filter_map(|id| self.state.store.get_by_id(id))
+            .map(|line| line.template_key())
+            .collect();
+        if templates.is_empty() {
+            return;
+        }
+        let color = DEFAULT_PALETTE[self.monotonic_filter_counter % DEFAULT_PALETTE.len()];
+        let mut filter_state = FilterState::new(
+            tr!("Anomalies in {source}", source = summary.source_name),
+            color,
+        );
+        filter_state.search.search_text = templates
+            .iter()
+            .map(|template| format!("(?:{})", crate::parser::template_to_pattern(template)))
+            .collect::<Vec<_>>()
+            .join("|");
+        self.add_filter_view(false, Some(filter_state));
+        self.state.modified = true;
+    }
+
+    pub fn process_keyboard_input(
+        &mut self,
+        actions: &[ShortcutAction],
+        quick_bookmark_template: &str,
+    ) {
         profiling::function_scope!();
         // Execute all generated actions
         for action in actions {
             match action {
                 ShortcutAction::ToggleBookmark => {}
+                ShortcutAction::QuickBookmark => {
+                    self.state.quick_bookmark_selected(quick_bookmark_template);
+                    self.save_crab_file();
+                }
                 ShortcutAction::FocusSearch => {}
                 ShortcutAction::NewFilterTab => {
                     self.add_filter_view(true, None);
@@ -466,13 +1197,17 @@ impl CrabSession {
                 ShortcutAction::PageUp => {}
                 ShortcutAction::PageDown => {}
                 ShortcutAction::OpenFile => {}
+                ShortcutAction::ReloadFiles => {}
                 ShortcutAction::RenameFilter => {}
+                ShortcutAction::NextSameTemplate => {}
+                ShortcutAction::PreviousSameTemplate => {}
                 ShortcutAction::MoveUp => {}
                 ShortcutAction::MoveDown => {}
                 ShortcutAction::FocusPaneLeft => self.navigate_pane(PaneDirection::Left),
                 ShortcutAction::FocusPaneDown => self.navigate_pane(PaneDirection::Down),
                 ShortcutAction::FocusPaneUp => self.navigate_pane(PaneDirection::Up),
                 ShortcutAction::FocusPaneRight => self.navigate_pane(PaneDirection::Right),
+                ShortcutAction::ErrorsView => self.open_errors_view(),
             }
         }
 
@@ -484,6 +1219,47 @@ impl CrabSession {
         }
     }
 
+    /// Focus the pane under the mouse pointer (focus-follows-mouse mode).
+    ///
+    /// Skipped while a mouse button is held so that dragging tabs or
+    /// selecting text doesn't move the focus mid-gesture.
+    fn focus_pane_under_pointer(&mut self, ctx: &egui::Context) {
+        let (pos, button_down) = ctx.input(|i| (i.pointer.hover_pos(), i.pointer.any_down()));
+        let Some(pos) = pos.filter(|_| !button_down) else {
+            return;
+        };
+        let Some(leaf) = navigation::leaf_at(self.dock_state.main_surface(), pos) else {
+            return;
+        };
+        let main = egui_dock::SurfaceIndex::main();
+        if self.dock_state.focused_leaf() != Some((main, leaf)) {
+            self.dock_state.set_focused_node_and_surface((main, leaf));
+        }
+    }
+
+    /// Outline the focused pane so it is clear which pane keyboard navigation acts on
+    fn paint_focused_pane_border(&self, ui: &egui::Ui, color: egui::Color32) {
+        let tree = self.dock_state.main_surface();
+        // With a single pane there is nothing to tell apart
+        if tree.iter().filter(|node| node.is_leaf()).count() < 2 {
+            return;
+        }
+        let Some((surface, node)) = self.dock_state.focused_leaf() else {
+            return;
+        };
+        if surface != egui_dock::SurfaceIndex::main() {
+            return;
+        }
+        if let Some(rect) = tree[node].rect() {
+            ui.painter().rect_stroke(
+                rect,
+                0.0,
+                egui::Stroke::new(2.0, color),
+                egui::StrokeKind::Inside,
+            );
+        }
+    }
+
     fn navigate_pane(&mut self, direction: PaneDirection) {
         let tree = self.dock_state.main_surface_mut();
 
@@ -506,3 +1282,134 @@ impl Drop for CrabSession {
         self.save_crab_file();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::histogram_worker::HistogramWorker;
+    use crate::core::log_store::GlobalFileConfig;
+    use crate::core::FilterWorker;
+    use crate::ui::ToastManager;
+
+    /// Add `path` to `session` and wait until every source is read
+    fn add_and_wait(session: &mut CrabSession, toasts: &ToastManager, path: &Path) {
+        let toast = toasts.create_progress_toast("Loading", path.display().to_string());
+        session.add_file(path, &toast, &toasts.sender(), &GlobalFileConfig::default());
+        let store = Arc::clone(&session.state.store);
+        while !store
+            .get_source_ids_and_paths()
+            .iter()
+            .all(|(id, _)| store.is_source_loaded(*id))
+        {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_reloaded_source_keeps_its_session_writable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let toasts = ToastManager::new(egui::Context::default());
+        let filter_worker = FilterWorker::new();
+        let histogram_worker = HistogramWorker::new();
+        let mut session = CrabSession::new(
+            LogStore::new(),
+            filter_worker.handle(),
+            histogram_worker.handle(),
+        );
+        add_and_wait(&mut session, &toasts, &path);
+        let store = Arc::clone(&session.state.store);
+        let id = store.source_id_for_path(&path).expect("source");
+        store.set_bookmark(&StoreID::make(id, 0), "before reload".to_string());
+
+        for path in session.begin_reload_of(vec![path.clone()]) {
+            add_and_wait(&mut session, &toasts, &path);
+        }
+        assert!(!store.has_contended_crab_files());
+        let id = store.source_id_for_path(&path).expect("source");
+        let bookmarks = store.get_all_bookmarks();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].name, "before reload");
+
+        store.set_bookmark(&StoreID::make(id, 0), "after reload".to_string());
+        session.save_crab_file();
+        store.flush_crab_files();
+        let saved = std::fs::read_to_string(&crab_path).expect("read crab file");
+        assert!(saved.contains("\"after reload\""));
+    }
+
+    #[test]
+    fn test_reparse_keeps_bookmarks_saved_until_restored() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(
+            &path,
+            "11-20 14:23:45.123  1234  5678 I Tag: hello\n\
+             11-20 14:23:46.123  1234  5678 I Tag: world\n",
+        )
+        .expect("write");
+        let crab_path = dir.path().join("app.log.crab");
+
+        let toasts = ToastManager::new(egui::Context::default());
+        let filter_worker = FilterWorker::new();
+        let histogram_worker = HistogramWorker::new();
+        let mut session = CrabSession::new(
+            LogStore::new(),
+            filter_worker.handle(),
+            histogram_worker.handle(),
+        );
+        add_and_wait(&mut session, &toasts, &path);
+        let store = Arc::clone(&session.state.store);
+        let id = store.source_id_for_path(&path).expect("source");
+        store.set_bookmark(&StoreID::make(id, 1), "kept".to_string());
+
+        let path = session.begin_reparse(id, "generic").expect("reparse");
+        // Closing the app now must not lose the bookmark
+        let saved = std::fs::read_to_string(&crab_path).expect("read crab file");
+        assert!(saved.contains("\"kept\""));
+
+        add_and_wait(&mut session, &toasts, &path);
+        session.finish_pending_reparses();
+        let bookmarks = store.get_all_bookmarks();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].name, "kept");
+        assert_eq!(bookmarks[0].store_id.line_index_within_source(), 1);
+        store.flush_crab_files();
+        let saved = std::fs::read_to_string(&crab_path).expect("read crab file");
+        assert!(saved.contains("\"kept\""));
+    }
+
+    #[test]
+    fn test_failed_reload_drops_its_pending_selection() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "11-20 14:23:45.123  1234  5678 I Tag: hello\n").expect("write");
+
+        let toasts = ToastManager::new(egui::Context::default());
+        let filter_worker = FilterWorker::new();
+        let histogram_worker = HistogramWorker::new();
+        let mut session = CrabSession::new(
+            LogStore::new(),
+            filter_worker.handle(),
+            histogram_worker.handle(),
+        );
+        add_and_wait(&mut session, &toasts, &path);
+        let id = session
+            .state
+            .store
+            .source_id_for_path(&path)
+            .expect("source");
+        session.state.selected_line_index = Some(StoreID::make(id, 0));
+
+        let paths = session.begin_reload_of(vec![path.clone()]);
+        assert_eq!(session.pending_reloads.len(), 1);
+        std::fs::remove_file(&path).expect("remove");
+        for path in paths {
+            add_and_wait(&mut session, &toasts, &path);
+        }
+        assert!(session.pending_reloads.is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 pub mod app;
 pub mod filter_highlight;
 pub mod log_view;
+pub mod primary_selection;
+pub mod raw_panel;
+pub mod repaint;
+pub mod score_legend;
 pub mod session_state;
 pub mod tabs;
 pub mod toasts;
@@ -24,6 +28,26 @@ pub const DEFAULT_PALETTE: [Color32; 8] = [
     Color32::from_rgb(140, 110, 200), // Soft violet
 ];
 
+/// Color of a filter tab group, stable across sessions for the same name
+#[must_use]
+pub fn group_color(group: &str) -> Color32 {
+    let hash = group
+        .bytes()
+        .fold(0usize, |hash, b| hash.wrapping_mul(31).wrapping_add(usize::from(b)));
+    DEFAULT_PALETTE[hash % DEFAULT_PALETTE.len()]
+}
+
+/// Text color of a logging component (tag, APID, ...), stable for the same name
+#[must_use]
+pub fn component_color(component: &str, dark_mode: bool) -> Color32 {
+    let hash = component.bytes().fold(0u32, |hash, b| {
+        hash.wrapping_mul(31).wrapping_add(u32::from(b))
+    });
+    let hue = (hash % 360) as f32 / 360.0;
+    let value = if dark_mode { 0.95 } else { 0.55 };
+    egui::ecolor::Hsva::new(hue, 0.65, value, 1.0).into()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaneDirection {
     Left,
@@ -0,0 +1,61 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Linux primary selection ("select to copy, middle-click to paste").
+//!
+//! egui only talks to the regular clipboard, so the primary selection is
+//! offered through a dedicated `arboard` handle that is kept alive for the
+//! whole session: on X11 the contents are served by the owning handle.
+//! Wayland sessions reach it through `XWayland`.
+
+#[cfg(target_os = "linux")]
+use std::sync::Mutex;
+
+#[cfg(target_os = "linux")]
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+/// Offer `text` as the primary selection, so a middle click in another
+/// application pastes it.
+#[cfg(target_os = "linux")]
+pub fn set_primary_selection(text: &str) {
+    use arboard::{LinuxClipboardKind, SetExtLinux};
+
+    let mut clipboard = CLIPBOARD.lock().expect("primary selection lock poisoned");
+    if clipboard.is_none() {
+        match arboard::Clipboard::new() {
+            Ok(handle) => *clipboard = Some(handle),
+            Err(e) => {
+                tracing::warn!("Primary selection unavailable: {e}");
+                return;
+            }
+        }
+    }
+    if let Some(handle) = clipboard.as_mut() {
+        if let Err(e) = handle
+            .set()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text(text.to_owned())
+        {
+            tracing::warn!("Failed to set primary selection: {e}");
+        }
+    }
+}
+
+/// Other platforms have no primary selection.
+#[cfg(not(target_os = "linux"))]
+pub const fn set_primary_selection(_text: &str) {}
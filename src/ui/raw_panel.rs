@@ -0,0 +1,160 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Raw source panel: the lines around the selected line exactly as they are
+//! in the file, read from disk instead of the parsed store.
+
+use crate::config::GlobalConfig;
+use crate::core::log_store::StoreID;
+use crate::core::raw_lines::{escape_raw, RawLine, RawLineIndex};
+use crate::core::LogStore;
+use crate::tr;
+use egui::{RichText, Ui};
+
+/// Lines shown before and after the selected line
+const CONTEXT_LINES: usize = 5;
+
+/// State of the raw source panel; the offset index is kept for the file of
+/// the last selection.
+#[derive(Default)]
+pub struct RawSourcePanel {
+    index: Option<RawLineIndex>,
+    /// Selection the lines were read for, with the selected line's number
+    shown: Option<(StoreID, usize, Result<Vec<RawLine>, String>)>,
+}
+
+impl RawSourcePanel {
+    /// Render the panel for `selected`, reading from disk when the selection
+    /// changed.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        store: &LogStore,
+        selected: Option<StoreID>,
+        global_config: &mut GlobalConfig,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(tr!("Raw source")).strong());
+            if let Some((_, number, Ok(lines))) = &self.shown {
+                if let Some(line) = lines.iter().find(|line| line.number == *number) {
+                    ui.label(tr!(
+                        "line {number}, byte offset {offset}",
+                        number,
+                        offset = line.offset
+                    ));
+                    if ui
+                        .small_button("📋")
+                        .on_hover_text(tr!("Copy the raw line"))
+                        .clicked()
+                    {
+                        ui.ctx()
+                            .copy_text(String::from_utf8_lossy(&line.bytes).into_owned());
+                    }
+                }
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .small_button("✖")
+                    .on_hover_text(tr!("Hide the raw source panel (View menu)"))
+                    .clicked()
+                {
+                    *global_config = GlobalConfig::update(|c| c.show_raw_panel = false);
+                }
+                if ui
+                    .small_button("⟳")
+                    .on_hover_text(tr!("Read the file again, e.g. after it was rewritten"))
+                    .clicked()
+                {
+                    self.index = None;
+                    self.shown = None;
+                }
+            });
+        });
+        ui.separator();
+
+        let Some(id) = selected else {
+            ui.weak(tr!("Select a line to see it as stored in the file"));
+            return;
+        };
+        if store.is_binary_source(id.source_id()) {
+            ui.weak(tr!("Raw lines are only available for text files"));
+            return;
+        }
+        if self.shown.as_ref().is_none_or(|(shown, _, _)| *shown != id) {
+            self.shown = self.read(store, id);
+        }
+        let Some((_, number, lines)) = &self.shown else {
+            return;
+        };
+        match lines {
+            Ok(lines) => {
+                egui::ScrollArea::both()
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        for line in lines {
+                            let text = RichText::new(format!(
+                                "{:>7}  {}",
+                                line.number,
+                                escape_raw(&line.bytes)
+                            ))
+                            .monospace();
+                            let text = if line.number == *number {
+                                text.background_color(ui.visuals().selection.bg_fill)
+                            } else {
+                                text
+                            };
+                            ui.label(text);
+                        }
+                    });
+            }
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+            }
+        }
+    }
+
+    /// Read the lines around `id` from its file, (re)building the offset
+    /// index if it belongs to another file.
+    fn read(
+        &mut self,
+        store: &LogStore,
+        id: StoreID,
+    ) -> Option<(StoreID, usize, Result<Vec<RawLine>, String>)> {
+        let path = store.source_path(id.source_id())?;
+        let number = store.get_by_id(&id)?.line_number;
+        if self.index.as_ref().is_none_or(|index| index.path() != path) {
+            match RawLineIndex::build(&path) {
+                Ok(index) => self.index = Some(index),
+                Err(e) => {
+                    self.index = None;
+                    let message = if e.kind() == std::io::ErrorKind::Unsupported {
+                        tr!("Raw lines are not available for compressed files ({e})", e)
+                    } else {
+                        tr!("Cannot read {path}: {e}", path = path.display(), e)
+                    };
+                    return Some((id, number, Err(message)));
+                }
+            }
+        }
+        let index = self.index.as_ref()?;
+        let lines = index
+            .read_around(number, CONTEXT_LINES)
+            .map_err(|e| tr!("Cannot read {path}: {e}", path = path.display(), e));
+        Some((id, number, lines))
+    }
+}
@@ -0,0 +1,44 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Repaints requested by background work, limited by the FPS cap.
+//!
+//! Loader progress and worker results can arrive far more often than a
+//! screen refreshes. Instead of repainting for each, they ask for a repaint
+//! within one frame budget; egui merges the requests into one frame. Input
+//! is never delayed, as egui repaints for it on its own.
+
+use std::time::Duration;
+
+fn storage_id() -> egui::Id {
+    egui::Id::new("background_frame_budget")
+}
+
+/// Set the frame budget for background repaints from the FPS cap (0 = unlimited).
+pub fn set_max_fps(ctx: &egui::Context, max_fps: u32) {
+    let budget = std::num::NonZeroU32::new(max_fps)
+        .map_or(Duration::ZERO, |fps| Duration::from_secs(1) / fps.get());
+    ctx.data_mut(|d| d.insert_temp(storage_id(), budget));
+}
+
+/// Repaint for new background results within one frame budget, so that a
+/// burst of them shares one frame.
+pub fn request_repaint(ctx: &egui::Context) {
+    let budget: Duration = ctx.data(|d| d.get_temp(storage_id())).unwrap_or_default();
+    ctx.request_repaint_after(budget);
+}
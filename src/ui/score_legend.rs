@@ -0,0 +1,88 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Score color legend with the global dimming threshold, shown above the
+//! status bar.
+
+use crate::config::GlobalConfig;
+use crate::tr;
+use crate::ui::tabs::filter_tab::log_table::{dim_below_threshold, score_to_color};
+use egui::{Sense, Ui, Vec2};
+
+/// Width of the color strip in points
+const STRIP_WIDTH: f32 = 200.0;
+
+/// Render the score-to-color strip and the threshold slider.
+///
+/// Changes are written to `global_config` and persisted once the slider is
+/// released.
+pub fn render_score_legend(ui: &mut Ui, global_config: &mut GlobalConfig) {
+    ui.horizontal(|ui| {
+        ui.label(tr!("Anomaly score:"));
+        ui.label("0");
+        render_strip(ui, global_config.score_threshold);
+        ui.label("100");
+
+        ui.separator();
+
+        ui.label(tr!("Dim below:"));
+        let response = ui
+            .add(egui::Slider::new(&mut global_config.score_threshold, 0.0..=100.0).integer())
+            .on_hover_text(tr!(
+                "Lines scoring below this are dimmed in every tab; 0 dims nothing"
+            ));
+        if (response.changed() && !response.dragged()) || response.drag_stopped() {
+            let threshold = global_config.score_threshold;
+            *global_config = GlobalConfig::update(|c| c.score_threshold = threshold);
+        }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui
+                .small_button("✖")
+                .on_hover_text(tr!("Hide the score legend (View menu)"))
+                .clicked()
+            {
+                *global_config = GlobalConfig::update(|c| c.show_score_legend = false);
+            }
+        });
+    });
+}
+
+/// Paint the score gradient as the log table uses it, dimmed below `threshold`.
+fn render_strip(ui: &mut Ui, threshold: f64) {
+    let dark_mode = ui.visuals().dark_mode;
+    let height = ui.spacing().interact_size.y * 0.6;
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(STRIP_WIDTH, height), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let steps = 100_u8;
+    let step_width = rect.width() / f32::from(steps);
+    for step in 0..steps {
+        let score = f64::from(step);
+        let color = dim_below_threshold(score_to_color(score, dark_mode), score, threshold);
+        let left = f32::from(step).mul_add(step_width, rect.left());
+        let cell = egui::Rect::from_min_max(
+            egui::pos2(left, rect.top()),
+            egui::pos2(left + step_width + 0.5, rect.bottom()),
+        );
+        painter.rect_filled(cell, 0.0, color);
+    }
+    response.on_hover_text(tr!(
+        "Text color of a line by its anomaly score: grey lines are normal, \
+         yellow to red ones increasingly unusual (see Help → Anomaly Score Calculation)"
+    ));
+}
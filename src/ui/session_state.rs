@@ -21,14 +21,22 @@
 //! This module contains the state that is shared across all tabs in a session,
 //! including bookmarks, highlights, selection state, and filter history.
 
+use std::io;
+use std::path::Path;
 use std::sync::Arc;
 
 use chrono::{DateTime, Local};
 use egui::Color32;
 
+use crate::core::attachments;
+use crate::core::bookmark_template::expand_template;
 use crate::core::histogram_worker::HistogramWorkerHandle;
 use crate::core::log_store::StoreID;
+use crate::core::net_endpoint::Endpoint;
+use crate::core::session::Activity;
+use crate::core::template_counts::TemplateCountCache;
 use crate::core::{FilterWorkerHandle, LogStore, SearchRule};
+use crate::tr;
 use crate::ui::tabs::bookmarks_tab::BookmarkData;
 
 /// Shared state for a log viewing session.
@@ -48,9 +56,16 @@ pub struct SessionState {
     /// Handle to send histogram requests to the background worker
     pub histogram_worker: HistogramWorkerHandle,
 
+    /// Message template counts for the log table's `Count` column
+    pub template_counts: TemplateCountCache,
+
     /// Currently selected line index
     pub selected_line_index: Option<StoreID>,
 
+    /// Anchor and far end of a shift-click range selection; only counts
+    /// while the far end is still the selected line
+    pub range_selection: Option<(StoreID, StoreID)>,
+
     /// Whether the session has unsaved modifications
     pub modified: bool,
 
@@ -69,6 +84,25 @@ pub struct SessionState {
     /// Pending conversion request: filter data to convert to highlight
     pub pending_filter_to_highlight: Option<FilterToHighlightData>,
 
+    /// Pending request: open a filter tab matching any of these message templates
+    pub pending_template_filter: Option<Vec<String>>,
+
+    /// Pending request: open a filter tab with every line containing this correlation ID
+    pub pending_id_filter: Option<String>,
+
+    /// Pending request: open or focus a packet tab for this endpoint and
+    /// select the packet closest in time to the line naming it
+    pub pending_packet_filter: Option<(Endpoint, StoreID)>,
+
+    /// Pending request: open one filter tab per literal pattern
+    pub pending_split_filters: Option<Vec<String>>,
+
+    /// Pending request: zoom every filter's timeline to this period
+    pub pending_time_zoom: Option<(DateTime<Local>, DateTime<Local>)>,
+
+    /// Pending request: act on every filter tab of a group
+    pub pending_group_action: Option<FilterGroupAction>,
+
     /// Sender for showing toast notifications from background threads.
     ///
     /// Set by the app after session creation so background classification threads
@@ -88,6 +122,15 @@ pub struct FilterToHighlightData {
     pub show_in_histogram: bool,
 }
 
+/// Action on all filter tabs of a named group
+#[derive(Debug, Clone)]
+pub enum FilterGroupAction {
+    /// Show or hide the group's matches as highlights in other tabs
+    SetGloballyVisible { group: String, visible: bool },
+    /// Close every tab of the group
+    Close { group: String },
+}
+
 impl SessionState {
     /// Create a new session state with the given log store and worker handles.
     pub const fn new(
@@ -99,13 +142,21 @@ impl SessionState {
             store,
             filter_worker,
             histogram_worker,
+            template_counts: TemplateCountCache::new(),
             selected_line_index: None,
+            range_selection: None,
             modified: false,
             last_saved: None,
             filter_history: Vec::new(),
             highlights: Vec::new(),
             pending_highlight_to_filter: None,
             pending_filter_to_highlight: None,
+            pending_template_filter: None,
+            pending_id_filter: None,
+            pending_packet_filter: None,
+            pending_split_filters: None,
+            pending_time_zoom: None,
+            pending_group_action: None,
             toast_sender: None,
         }
     }
@@ -115,6 +166,12 @@ impl SessionState {
         if pattern.is_empty() {
             return;
         }
+        self.store.record_activity(
+            None,
+            Activity::FilterChanged {
+                pattern: pattern.clone(),
+            },
+        );
         // Remove if already exists to avoid duplicates
         self.filter_history.retain(|p| p != &pattern);
         // Add to front (most recent first)
@@ -125,6 +182,39 @@ impl SessionState {
         }
     }
 
+    /// Select `id` and extend the range selection to it, starting from the
+    /// anchor of the current range or else from the selected line
+    pub fn extend_selection_to(&mut self, id: StoreID) {
+        let anchor = self
+            .range_selection
+            .filter(|(_, end)| self.selected_line_index == Some(*end))
+            .map(|(anchor, _)| anchor)
+            .or(self.selected_line_index);
+        self.range_selection = anchor.map(|anchor| (anchor, id));
+        self.selected_line_index = Some(id);
+    }
+
+    /// First and last line of the range selection, in timestamp order
+    #[must_use]
+    pub fn selection_range(&self) -> Option<(StoreID, StoreID)> {
+        let (anchor, end) = self.range_selection?;
+        if self.selected_line_index != Some(end) || anchor == end {
+            return None;
+        }
+        if anchor.cmp(&end, &self.store).is_gt() {
+            Some((end, anchor))
+        } else {
+            Some((anchor, end))
+        }
+    }
+
+    /// Line number of `id` in its file, as shown in the activity log
+    fn line_number(&self, id: &StoreID) -> usize {
+        self.store
+            .get_by_id(id)
+            .map_or_else(|| id.line_index_within_source() + 1, |line| line.line_number)
+    }
+
     // ========================================================================
     // Bookmark Management (delegates to LogStore)
     // ========================================================================
@@ -144,10 +234,23 @@ impl SessionState {
         if self.store.has_bookmark(&line_index) {
             tracing::debug!("Removing bookmark at line {line_index:?}");
             self.store.remove_bookmark(&line_index);
+            self.store.record_activity(
+                Some(&line_index),
+                Activity::BookmarkRemoved {
+                    line_number: self.line_number(&line_index),
+                },
+            );
         } else {
             let bookmark_name = String::new();
             tracing::debug!("Adding bookmark with empty annotation");
-            self.store.set_bookmark(&line_index, bookmark_name);
+            self.store.set_bookmark(&line_index, bookmark_name.clone());
+            self.store.record_activity(
+                Some(&line_index),
+                Activity::BookmarkAdded {
+                    line_number: self.line_number(&line_index),
+                    name: bookmark_name,
+                },
+            );
         }
         self.modified = true;
     }
@@ -159,15 +262,155 @@ impl SessionState {
         }
     }
 
+    /// Bookmark the selected line with a name generated from `template`.
+    ///
+    /// Placeholders resolve to `message`, `source`, `line`, `time` or any
+    /// parsed field of the line (case-insensitive, e.g. `{level}`, `{tag}`,
+    /// `{apid}`). Lines that are already bookmarked keep their current name.
+    pub fn quick_bookmark_selected(&mut self, template: &str) {
+        let Some(id) = self.selected_line_index else {
+            return;
+        };
+        if self.store.has_bookmark(&id) {
+            return;
+        }
+        let Some(line) = self.store.get_by_id(&id) else {
+            return;
+        };
+        let fields = self.store.get_fields(&id);
+        let source = self.store.get_source_name(&id).unwrap_or_default();
+        let name = expand_template(template, |key| match key.to_lowercase().as_str() {
+            "message" | "msg" => Some(line.message.clone()),
+            "source" => Some(source.clone()),
+            "line" => Some(line.line_number.to_string()),
+            "time" | "timestamp" => Some(line.timestamp().format("%H:%M:%S%.3f").to_string()),
+            _ => fields
+                .iter()
+                .find(|(field, _)| field.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value.clone()),
+        });
+        tracing::debug!("Adding quick bookmark {name:?} at line {id:?}");
+        self.store.set_bookmark(&id, name.clone());
+        self.store.record_activity(
+            Some(&id),
+            Activity::BookmarkAdded {
+                line_number: line.line_number,
+                name,
+            },
+        );
+        self.modified = true;
+    }
+
+    /// Bookmark `id` under `name`, replacing any existing bookmark name
+    pub fn add_named_bookmark(&mut self, id: StoreID, name: String) {
+        tracing::debug!("Adding bookmark {name:?} at line {id:?}");
+        self.store.set_bookmark(&id, name.clone());
+        self.store.record_activity(
+            Some(&id),
+            Activity::BookmarkAdded {
+                line_number: self.line_number(&id),
+                name,
+            },
+        );
+        self.modified = true;
+    }
+
     /// Rename a bookmark
     pub fn rename_bookmark(&mut self, id: &StoreID, new_name: String) {
-        self.store.set_bookmark(id, new_name);
+        self.store.set_bookmark(id, new_name.clone());
+        self.store.record_activity(
+            Some(id),
+            Activity::BookmarkRenamed {
+                line_number: self.line_number(id),
+                name: new_name,
+            },
+        );
         self.modified = true;
     }
 
+    /// File the bookmark at `id` under `category`, keeping its annotation
+    pub fn set_bookmark_category(&mut self, id: &StoreID, category: String) {
+        self.store.set_bookmark_category(id, category);
+        self.modified = true;
+    }
+
+    /// Copy the file at `path` next to the session and attach it to the
+    /// bookmark at `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is too large or cannot be copied.
+    pub fn attach_file_to_bookmark(&mut self, id: &StoreID, path: &Path) -> io::Result<()> {
+        self.add_bookmark_attachment(id, |dir| attachments::attach_file(dir, path))
+    }
+
+    /// Store `bytes` next to the session as a file named like `name` and
+    /// attach it to the bookmark at `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is too large or cannot be written.
+    pub fn attach_bytes_to_bookmark(
+        &mut self,
+        id: &StoreID,
+        name: &str,
+        bytes: &[u8],
+    ) -> io::Result<()> {
+        self.add_bookmark_attachment(id, |dir| attachments::attach_bytes(dir, name, bytes))
+    }
+
+    fn add_bookmark_attachment(
+        &mut self,
+        id: &StoreID,
+        store_file: impl FnOnce(&Path) -> io::Result<String>,
+    ) -> io::Result<()> {
+        let (Some(bookmark), Some(dir)) = (
+            self.store.get_bookmark(id),
+            self.store.attachments_dir(id.source_id()),
+        ) else {
+            return Err(io::Error::other(tr!("The line is not bookmarked")));
+        };
+        let name = store_file(&dir)?;
+        let mut names = bookmark.attachments;
+        names.push(name);
+        self.store.set_bookmark_attachments(id, names);
+        self.modified = true;
+        Ok(())
+    }
+
+    /// Detach `name` from the bookmark at `id` and delete the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be deleted.
+    pub fn remove_bookmark_attachment(&mut self, id: &StoreID, name: &str) -> io::Result<()> {
+        let (Some(bookmark), Some(dir)) = (
+            self.store.get_bookmark(id),
+            self.store.attachments_dir(id.source_id()),
+        ) else {
+            return Ok(());
+        };
+        attachments::remove_attachment(&dir, name)?;
+        let names = bookmark
+            .attachments
+            .into_iter()
+            .filter(|attached| attached != name)
+            .collect();
+        self.store.set_bookmark_attachments(id, names);
+        self.modified = true;
+        Ok(())
+    }
+
     /// Remove a bookmark
     pub fn remove_bookmark(&mut self, id: &StoreID) {
-        self.store.remove_bookmark(id);
+        if self.store.remove_bookmark(id).is_some() {
+            self.store.record_activity(
+                Some(id),
+                Activity::BookmarkRemoved {
+                    line_number: self.line_number(id),
+                },
+            );
+        }
         self.modified = true;
     }
 }
@@ -0,0 +1,218 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Detail view of the selected bookmark: its attached screenshots and notes.
+
+use super::BookmarkData;
+use crate::core::attachments::is_image;
+use crate::tr;
+use crate::ui::session_state::SessionState;
+use chrono::Local;
+use egui::{RichText, Ui};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Height of image previews in the detail view
+const THUMBNAIL_HEIGHT: f32 = 140.0;
+
+/// Attachments of the selected bookmark, with decoded image previews cached
+#[derive(Default)]
+pub struct AttachmentPanel {
+    previews: HashMap<PathBuf, Result<egui::TextureHandle, String>>,
+}
+
+impl AttachmentPanel {
+    /// Render the attachments of `bookmark` and the buttons to add more
+    pub fn render(&mut self, ui: &mut Ui, data_state: &mut SessionState, bookmark: &BookmarkData) {
+        let store_id = bookmark.store_id;
+        let Some(dir) = data_state.store.attachments_dir(store_id.source_id()) else {
+            return;
+        };
+        let mut result = Ok(());
+
+        ui.horizontal(|ui| {
+            let title = if bookmark.name.is_empty() {
+                tr!("Attachments").to_owned()
+            } else {
+                tr!("Attachments of \"{name}\"", name = bookmark.name)
+            };
+            ui.label(RichText::new(title).strong());
+            if ui
+                .button(tr!("📎 Attach Files…"))
+                .on_hover_text(tr!("Copy files next to the session and attach them"))
+                .clicked()
+            {
+                if let Some(paths) = rfd::FileDialog::new()
+                    .set_title(tr!("Attach Files"))
+                    .pick_files()
+                {
+                    for path in paths {
+                        if let Err(e) = data_state.attach_file_to_bookmark(&store_id, &path) {
+                            result = Err(e);
+                        }
+                    }
+                }
+            }
+            if ui
+                .button(tr!("📋 Paste Image"))
+                .on_hover_text(tr!("Attach the image on the clipboard, e.g. a screenshot"))
+                .clicked()
+            {
+                let name = format!("screenshot-{}.png", Local::now().format("%Y%m%d-%H%M%S"));
+                if let Err(e) = clipboard_png()
+                    .and_then(|png| data_state.attach_bytes_to_bookmark(&store_id, &name, &png))
+                {
+                    result = Err(e);
+                }
+            }
+        });
+
+        if bookmark.attachments.is_empty() {
+            ui.weak(tr!(
+                "No attachments yet. Attach screenshots or notes that explain this line."
+            ));
+        }
+
+        let mut removed = None;
+        egui::ScrollArea::horizontal()
+            .id_salt("bookmark_attachments")
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                ui.horizontal_top(|ui| {
+                    for name in &bookmark.attachments {
+                        let path = dir.join(name);
+                        ui.vertical(|ui| {
+                            if is_image(name) {
+                                self.render_preview(ui, &path);
+                            } else {
+                                ui.label(RichText::new("📄").size(48.0));
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label(name);
+                                if ui
+                                    .small_button("↗")
+                                    .on_hover_text(tr!("Open with the default application"))
+                                    .clicked()
+                                {
+                                    if let Err(e) = open_with_default_app(&path) {
+                                        result = Err(e);
+                                    }
+                                }
+                                if ui
+                                    .small_button("🗑")
+                                    .on_hover_text(tr!("Delete the attachment"))
+                                    .clicked()
+                                {
+                                    removed = Some(name.clone());
+                                }
+                            });
+                        });
+                    }
+                });
+            });
+
+        if let Some(name) = removed {
+            self.previews.remove(&dir.join(&name));
+            if let Err(e) = data_state.remove_bookmark_attachment(&store_id, &name) {
+                result = Err(e);
+            }
+        }
+        if let Err(e) = result {
+            tracing::error!("Bookmark attachment failed: {e}");
+            if let Some(sender) = &data_state.toast_sender {
+                sender.send(tr!("Attachment failed: {e}", e));
+            }
+        }
+    }
+
+    /// Thumbnail of the image at `path`, full size on hover
+    fn render_preview(&mut self, ui: &mut Ui, path: &Path) {
+        let preview = self
+            .previews
+            .entry(path.to_path_buf())
+            .or_insert_with(|| load_preview(ui.ctx(), path));
+        match preview {
+            Ok(texture) => {
+                let size = texture.size_vec2();
+                let scale = (THUMBNAIL_HEIGHT / size.y).min(1.0);
+                let texture = egui::load::SizedTexture::from_handle(texture);
+                ui.add(egui::Image::new(texture).fit_to_exact_size(size * scale))
+                    .on_hover_ui(|ui| {
+                        ui.add(egui::Image::new(texture).max_size(egui::vec2(960.0, 720.0)));
+                    });
+            }
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+            }
+        }
+    }
+}
+
+/// Decode the PNG at `path` into a texture
+fn load_preview(ctx: &egui::Context, path: &Path) -> Result<egui::TextureHandle, String> {
+    let decoded = image::open(path)
+        .map_err(|e| tr!("Cannot show image: {e}", e))?
+        .to_rgba8();
+    let size = [decoded.width() as usize, decoded.height() as usize];
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, decoded.as_raw());
+    Ok(ctx.load_texture(
+        path.display().to_string(),
+        color_image,
+        egui::TextureOptions::LINEAR,
+    ))
+}
+
+/// The image on the clipboard, encoded as PNG
+fn clipboard_png() -> io::Result<Vec<u8>> {
+    let clip = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_image())
+        .map_err(|e| io::Error::other(tr!("No image on the clipboard: {e}", e)))?;
+    let rgba = u32::try_from(clip.width)
+        .ok()
+        .zip(u32::try_from(clip.height).ok())
+        .and_then(|(width, height)| {
+            image::RgbaImage::from_raw(width, height, clip.bytes.into_owned())
+        })
+        .ok_or_else(|| io::Error::other(tr!("The clipboard image is malformed")))?;
+    let mut png = Vec::new();
+    rgba.write_to(&mut io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(io::Error::other)?;
+    Ok(png)
+}
+
+/// Open `path` in the application the system associates with its type.
+fn open_with_default_app(path: &Path) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]).arg(path);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = std::process::Command::new("open");
+        command.arg(path);
+        command
+    } else {
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(path);
+        command
+    };
+    let mut child = command.spawn()?;
+    // Reap the launcher once it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -19,20 +19,28 @@
 use crate::core::log_store::LogLine;
 use crate::core::log_store::StoreID;
 use crate::core::LogStore;
+use crate::tr;
 use crate::ui::filter_highlight::FilterHighlight;
 use crate::ui::session_state::SessionState;
 use crate::ui::tabs::filter_tab::log_table::{
-    score_to_color, scrolled_to_row_color, selected_row_color,
+    dim_below_threshold, score_to_color, scrolled_to_row_color, selected_row_color,
 };
 use chrono::Local;
 use egui::{Color32, RichText, Ui};
 use egui_extras::{Column, TableBuilder};
+use std::collections::HashSet;
 
 /// Bookmark data
 #[derive(Debug, Clone)]
 pub struct BookmarkData {
     pub store_id: StoreID,
     pub name: String,
+    /// Category the bookmark was filed under; empty for none
+    pub category: String,
+    /// File names of the attachments, see [`crate::core::attachments`]
+    pub attachments: Vec<String>,
+    /// The bookmarked line could not be found again after the file changed
+    pub orphaned: bool,
 }
 
 /// Events emitted by the bookmark panel
@@ -57,12 +65,12 @@ impl BookmarkPanel {
         events: &mut Vec<BookmarkPanelEvent>,
     ) {
         response.context_menu(|ui| {
-            if ui.button("📑 Remove Bookmark").clicked() {
+            if ui.button(tr!("📑 Remove Bookmark")).clicked() {
                 events.push(BookmarkPanelEvent::BookmarkDeleted { store_id: line_idx });
                 ui.close();
             }
 
-            if ui.button("🎯 Jump to Line").clicked() {
+            if ui.button(tr!("🎯 Jump to Line")).clicked() {
                 events.push(BookmarkPanelEvent::BookmarkClicked { store_id: line_idx });
                 ui.close();
             }
@@ -77,12 +85,12 @@ impl BookmarkPanel {
 
             ui.separator();
 
-            if ui.button("📋 Copy Message").clicked() {
+            if ui.button(tr!("📋 Copy Message")).clicked() {
                 ui.ctx().copy_text(line.message.clone());
                 ui.close();
             }
 
-            if ui.button("📋 Copy Full Line").clicked() {
+            if ui.button(tr!("📋 Copy Full Line")).clicked() {
                 ui.ctx().copy_text(line.raw);
                 ui.close();
             }
@@ -96,6 +104,7 @@ impl BookmarkPanel {
         ui: &mut Ui,
         log_view_state: &SessionState,
         bookmarks: &[BookmarkData],
+        multi_selected: &HashSet<StoreID>,
         editing_bookmark: Option<&StoreID>,
         bookmark_name_input: &mut String,
         scroll_to_row: Option<usize>,
@@ -103,6 +112,7 @@ impl BookmarkPanel {
         all_filter_highlights: &[FilterHighlight],
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
     ) -> Vec<BookmarkPanelEvent> {
         let mut events = Vec::new();
 
@@ -121,6 +131,7 @@ impl BookmarkPanel {
                     ui,
                     log_view_state,
                     bookmarks,
+                    multi_selected,
                     editing_bookmark,
                     bookmark_name_input,
                     scroll_to_row,
@@ -128,6 +139,7 @@ impl BookmarkPanel {
                     all_filter_highlights,
                     color_by_ml_score,
                     grey_rare_ml_lines,
+                    score_threshold,
                     &mut events,
                 );
             });
@@ -138,8 +150,8 @@ impl BookmarkPanel {
     fn render_empty_state(ui: &mut Ui) {
         ui.vertical_centered(|ui| {
             ui.add_space(50.0);
-            ui.label("No bookmarks yet");
-            ui.label("Right-click on any line to bookmark it");
+            ui.label(tr!("No bookmarks yet"));
+            ui.label(tr!("Right-click on any line to bookmark it"));
         });
     }
 
@@ -147,6 +159,7 @@ impl BookmarkPanel {
         ui: &mut Ui,
         log_view_state: &SessionState,
         bookmarks: &[BookmarkData],
+        multi_selected: &HashSet<StoreID>,
         editing_bookmark: Option<&StoreID>,
         bookmark_name_input: &mut String,
         scroll_to_row: Option<usize>,
@@ -154,6 +167,7 @@ impl BookmarkPanel {
         all_filter_highlights: &[FilterHighlight],
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
         events: &mut Vec<BookmarkPanelEvent>,
     ) {
         let available_height = ui.available_height();
@@ -184,21 +198,21 @@ impl BookmarkPanel {
         table
             .header(header_height, |mut header| {
                 header.col(|ui| {
-                    ui.strong("Annotation");
+                    ui.strong(tr!("Annotation"));
                 });
                 header.col(|ui| {
-                    ui.strong("Source");
+                    ui.strong(tr!("Source"));
                 });
                 header.col(|ui| {
-                    ui.strong("Line");
+                    ui.strong(tr!("Line"));
                 });
                 header.col(|ui| {
                     let now = Local::now();
                     let offset = now.offset();
-                    ui.strong(format!("Timestamp (UTC{offset})"));
+                    ui.strong(tr!("Timestamp (UTC{offset})", offset));
                 });
                 header.col(|ui| {
-                    ui.strong("Message");
+                    ui.strong(tr!("Message"));
                 });
                 header.col(|ui| {
                     ui.strong("");
@@ -210,12 +224,14 @@ impl BookmarkPanel {
                         &mut row,
                         log_view_state,
                         bookmarks,
+                        multi_selected,
                         editing_bookmark,
                         bookmark_name_input,
                         closest_bookmark_index,
                         all_filter_highlights,
                         color_by_ml_score,
                         grey_rare_ml_lines,
+                        score_threshold,
                         events,
                         dark_mode,
                     );
@@ -227,12 +243,14 @@ impl BookmarkPanel {
         row: &mut egui_extras::TableRow<'_, '_>,
         log_view_state: &SessionState,
         bookmarks: &[BookmarkData],
+        multi_selected: &HashSet<StoreID>,
         editing_bookmark: Option<&StoreID>,
         bookmark_name_input: &mut String,
         closest_bookmark_index: Option<usize>,
         all_filter_highlights: &[FilterHighlight],
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
         events: &mut Vec<BookmarkPanelEvent>,
         dark_mode: bool,
     ) {
@@ -240,10 +258,11 @@ impl BookmarkPanel {
         let bookmark = &bookmarks[row_index];
         let store_id = &bookmark.store_id;
 
-        let is_selected = log_view_state
-            .selected_line_index
-            .as_ref()
-            .is_some_and(|s| s == store_id);
+        let is_selected = multi_selected.contains(store_id)
+            || log_view_state
+                .selected_line_index
+                .as_ref()
+                .is_some_and(|s| s == store_id);
 
         let is_closest = !is_selected
             && closest_bookmark_index.is_some_and(|idx| idx == row_index)
@@ -251,7 +270,7 @@ impl BookmarkPanel {
 
         let Some(line) = log_view_state.store.get_by_id(store_id) else {
             row.col(|ui| {
-                ui.label("Loading...");
+                ui.label(tr!("Loading..."));
             });
             row.col(|_| {});
             row.col(|_| {});
@@ -261,19 +280,20 @@ impl BookmarkPanel {
             return;
         };
 
-        let color = if color_by_ml_score {
-            if line.sidecar_scored {
-                if grey_rare_ml_lines && line.sidecar_score_is_rare {
-                    score_to_color(0.0, dark_mode)
-                } else {
-                    score_to_color(line.sidecar_anomaly_score, dark_mode)
-                }
+        let line_score = if color_by_ml_score {
+            if line.sidecar_scored && !(grey_rare_ml_lines && line.sidecar_score_is_rare) {
+                line.sidecar_anomaly_score
             } else {
-                score_to_color(0.0, dark_mode)
+                0.0
             }
         } else {
-            score_to_color(line.anomaly_score, dark_mode)
+            line.anomaly_score
         };
+        let color = dim_below_threshold(
+            score_to_color(line_score, dark_mode),
+            line_score,
+            score_threshold,
+        );
 
         let mut row_clicked = false;
 
@@ -432,7 +452,7 @@ impl BookmarkPanel {
             Self::paint_selection_background(ui, is_selected, is_closest, dark_mode);
 
             // Timestamp is already calibrated (includes source time offset)
-            let display_time = line.timestamp;
+            let display_time = line.timestamp();
 
             let timestamp_str = display_time.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
             ui.label(RichText::new(&timestamp_str).color(color));
@@ -471,12 +491,28 @@ impl BookmarkPanel {
                 Self::render_name_editor(ui, store_id, bookmark_name_input, events);
             } else {
                 let text = if bookmark.name.is_empty() {
-                    RichText::new("Double-click to annotate")
+                    RichText::new(tr!("Double-click to annotate"))
                         .color(ui.visuals().weak_text_color())
                         .italics()
                 } else {
                     RichText::new(&bookmark.name).color(color).strong()
                 };
+                if bookmark.orphaned {
+                    ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                        .on_hover_text(tr!(
+                            "The bookmarked line was not found after the file changed; \
+                         the bookmark is shown at its old line number"
+                        ));
+                }
+                if !bookmark.category.is_empty() {
+                    ui.label(RichText::new(format!("🏷 {}", bookmark.category)).weak());
+                }
+                if !bookmark.attachments.is_empty() {
+                    ui.label("📎").on_hover_text(tr!(
+                        "{count} attachment(s)",
+                        count = bookmark.attachments.len()
+                    ));
+                }
                 ui.label(text);
                 let response = ui.interact(
                     ui.max_rect(),
@@ -599,7 +635,7 @@ impl BookmarkPanel {
         row.col(|ui| {
             Self::paint_selection_background(ui, is_selected, is_closest, dark_mode);
 
-            if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+            if ui.small_button("🗑").on_hover_text(tr!("Delete")).clicked() {
                 events.push(BookmarkPanelEvent::BookmarkDeleted {
                     store_id: *store_id,
                 });
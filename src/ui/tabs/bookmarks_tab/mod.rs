@@ -16,14 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod attachment_panel;
 pub mod bookmark_panel;
 
+pub use attachment_panel::AttachmentPanel;
 pub use bookmark_panel::{BookmarkData, BookmarkPanel, BookmarkPanelEvent};
 
 use crate::{
     config::GlobalConfig,
-    core::{log_store::StoreID, SavedFilter},
+    core::{log_store::StoreID, redaction::Redactor, SavedFilter},
     input::ShortcutAction,
+    tr,
     ui::{
         filter_highlight::FilterHighlight,
         session_state::SessionState,
@@ -31,6 +34,7 @@ use crate::{
     },
 };
 use egui::Ui;
+use std::collections::{BTreeSet, HashSet};
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
@@ -43,6 +47,17 @@ pub struct BookmarksView {
     enter_pressed_this_frame: bool,
     last_selected_line: Option<StoreID>,
     closest_bookmark_index: Option<usize>,
+    /// Bookmarks picked with Ctrl/Shift+click for bulk operations
+    multi_selection: HashSet<StoreID>,
+    /// Bookmark a Shift+click range starts from
+    selection_anchor: Option<StoreID>,
+    /// Category applied to all selected bookmarks
+    bulk_category_input: String,
+    /// Detail view of the selected bookmark
+    attachment_panel: AttachmentPanel,
+    /// Only bookmarks whose annotation, category or message contains this
+    /// text are listed (case-insensitive)
+    search: String,
 }
 
 impl BookmarksView {
@@ -88,6 +103,7 @@ impl BookmarksView {
         ui: &mut Ui,
         session_state: &SessionState,
         bookmarks: &[BookmarkData],
+        multi_selected: &HashSet<StoreID>,
         editing_bookmark: Option<&StoreID>,
         bookmark_name_input: &mut String,
         scroll_to_row: Option<usize>,
@@ -95,11 +111,13 @@ impl BookmarksView {
         all_filter_highlights: &[FilterHighlight],
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
     ) -> Vec<BookmarkPanelEvent> {
         BookmarkPanel::render(
             ui,
             session_state,
             bookmarks,
+            multi_selected,
             editing_bookmark,
             bookmark_name_input,
             scroll_to_row,
@@ -107,6 +125,7 @@ impl BookmarksView {
             all_filter_highlights,
             color_by_ml_score,
             grey_rare_ml_lines,
+            score_threshold,
         )
     }
 
@@ -117,10 +136,124 @@ impl BookmarksView {
         }
     }
 
+    /// Update the multi-selection for a click on `store_id`.
+    ///
+    /// Ctrl+click toggles a single bookmark, Shift+click adds the range from
+    /// the last clicked bookmark, a plain click clears the selection.
+    fn update_multi_selection(
+        &mut self,
+        store_id: StoreID,
+        modifiers: egui::Modifiers,
+        bookmarks: &[BookmarkData],
+    ) {
+        if !modifiers.command && !modifiers.shift {
+            self.multi_selection.clear();
+            self.selection_anchor = Some(store_id);
+            return;
+        }
+        // The plainly selected bookmark becomes part of a new multi-selection
+        if self.multi_selection.is_empty() {
+            self.multi_selection.extend(self.selection_anchor);
+        }
+        if modifiers.shift {
+            let position = |id: StoreID| bookmarks.iter().position(|b| b.store_id == id);
+            let anchor = self.selection_anchor.unwrap_or(store_id);
+            if let (Some(from), Some(to)) = (position(anchor), position(store_id)) {
+                let range = from.min(to)..=from.max(to);
+                self.multi_selection
+                    .extend(bookmarks[range].iter().map(|b| b.store_id));
+            }
+        } else if !self.multi_selection.remove(&store_id) {
+            self.multi_selection.insert(store_id);
+        }
+        if !modifiers.shift {
+            self.selection_anchor = Some(store_id);
+        }
+    }
+
+    /// Bulk actions on the multi-selection: delete, categorize, filter by templates
+    fn render_bulk_actions(&mut self, ui: &mut Ui, data_state: &mut SessionState) {
+        let mut selected: Vec<StoreID> = self.multi_selection.iter().copied().collect();
+        selected.sort_by(|a, b| a.cmp(b, &data_state.store));
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("{count} selected", count = selected.len()));
+            if ui
+                .button(tr!("🗑 Delete"))
+                .on_hover_text(tr!("Delete all selected bookmarks"))
+                .clicked()
+            {
+                for id in &selected {
+                    data_state.remove_bookmark(id);
+                }
+                self.multi_selection.clear();
+            }
+
+            ui.add(
+                egui::TextEdit::singleline(&mut self.bulk_category_input)
+                    .desired_width(150.0)
+                    .hint_text(tr!("Category")),
+            );
+            if ui
+                .button(tr!("🏷 Categorize"))
+                .on_hover_text(tr!(
+                    "File all selected bookmarks under this category; their annotations stay"
+                ))
+                .clicked()
+            {
+                let category = self.bulk_category_input.trim();
+                for id in &selected {
+                    data_state.set_bookmark_category(id, category.to_string());
+                }
+            }
+
+            if ui
+                .button(tr!("🔍 Filter by Templates"))
+                .on_hover_text(tr!(
+                    "Open a filter tab matching every line with the same message \
+                     template as one of the selected bookmarks"
+                ))
+                .clicked()
+            {
+                let templates: BTreeSet<String> = selected
+                    .iter()
+                    .filter_map(|id| data_state.store.get_by_id(id))
+                    .map(|line| line.template_key())
+                    .collect();
+                if !templates.is_empty() {
+                    data_state.pending_template_filter = Some(templates.into_iter().collect());
+                }
+            }
+
+            if ui.button(tr!("Clear Selection")).clicked() {
+                self.multi_selection.clear();
+            }
+        });
+        ui.separator();
+    }
+
     fn sort_bookmarks_by_timestamp(bookmarks: &mut [BookmarkData], data_state: &SessionState) {
         bookmarks.sort_by(|b1, b2| b1.store_id.cmp(&b2.store_id, &data_state.store));
     }
 
+    /// Bookmarks matching the search, sorted by timestamp
+    fn visible_bookmarks(&self, data_state: &SessionState) -> Vec<BookmarkData> {
+        let mut bookmarks = data_state.get_all_bookmarks();
+        let query = self.search.trim().to_lowercase();
+        if !query.is_empty() {
+            bookmarks.retain(|bookmark| {
+                bookmark.name.to_lowercase().contains(&query)
+                    || bookmark.category.to_lowercase().contains(&query)
+                    || data_state
+                        .store
+                        .get_by_id(&bookmark.store_id)
+                        .is_some_and(|line| line.message.to_lowercase().contains(&query))
+            });
+        }
+        Self::sort_bookmarks_by_timestamp(&mut bookmarks, data_state);
+        bookmarks
+    }
+
     pub fn render_bookmarks(
         &mut self,
         ui: &mut Ui,
@@ -128,6 +261,7 @@ impl BookmarksView {
         all_filter_highlights: &[FilterHighlight],
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
     ) {
         // Check if Enter was pressed this frame (when not editing)
         if self.edited_store_id.is_none() {
@@ -136,9 +270,17 @@ impl BookmarksView {
             self.enter_pressed_this_frame = false;
         }
 
-        // Convert bookmarks to BookmarkData format
-        let mut bookmarks: Vec<BookmarkData> = data_state.get_all_bookmarks();
-        Self::sort_bookmarks_by_timestamp(&mut bookmarks, data_state);
+        let bookmarks = self.visible_bookmarks(data_state);
+        if bookmarks.is_empty() && !self.search.trim().is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(50.0);
+                ui.label(tr!(
+                    "No bookmarks match \"{search}\"",
+                    search = self.search.trim()
+                ));
+            });
+            return;
+        }
 
         // Calculate scroll position and closest bookmark for highlighting
         let (scroll_to_row, closest_bookmark_index) = self.calculate_scroll_and_closest(
@@ -147,11 +289,35 @@ impl BookmarksView {
             &data_state.store,
         );
 
+        // Forget selected bookmarks that were deleted elsewhere or are hidden
+        // by the search
+        let visible: HashSet<StoreID> = bookmarks.iter().map(|b| b.store_id).collect();
+        self.multi_selection.retain(|id| visible.contains(id));
+        if !self.multi_selection.is_empty() {
+            self.render_bulk_actions(ui, data_state);
+        }
+        let modifiers = ui.input(|i| i.modifiers);
+
+        // Detail view of a single selected bookmark
+        let detail = data_state
+            .selected_line_index
+            .filter(|_| self.multi_selection.is_empty())
+            .and_then(|id| bookmarks.iter().find(|b| b.store_id == id))
+            .cloned();
+        if let Some(bookmark) = detail {
+            egui::TopBottomPanel::bottom(ui.id().with("bookmark_details"))
+                .resizable(true)
+                .show_inside(ui, |ui| {
+                    self.attachment_panel.render(ui, data_state, &bookmark);
+                });
+        }
+
         // Render using BookmarksView
         let events = Self::render(
             ui,
             data_state,
             &bookmarks,
+            &self.multi_selection,
             self.edited_store_id.as_ref(),
             &mut self.bookmark_name_input,
             scroll_to_row,
@@ -159,12 +325,14 @@ impl BookmarksView {
             all_filter_highlights,
             color_by_ml_score,
             grey_rare_ml_lines,
+            score_threshold,
         );
 
         // Handle events
         for event in events {
             match event {
                 BookmarkPanelEvent::BookmarkClicked { store_id } => {
+                    self.update_multi_selection(store_id, modifiers, &bookmarks);
                     data_state.selected_line_index = Some(store_id);
                 }
                 BookmarkPanelEvent::BookmarkDeleted { store_id } => {
@@ -184,8 +352,13 @@ impl BookmarksView {
         }
     }
 
-    /// Export all bookmarks (sorted by timestamp) to a text file
-    fn export_bookmarks(data_state: &SessionState, path: &Path) -> Result<(), String> {
+    /// Export all bookmarks (sorted by timestamp) to a text file, passing every
+    /// line through the redaction rules
+    fn export_bookmarks(
+        data_state: &SessionState,
+        redactor: &Redactor,
+        path: &Path,
+    ) -> Result<(), String> {
         let mut bookmarks = data_state.get_all_bookmarks();
         bookmarks.sort_by(|b1, b2| b1.store_id.cmp(&b2.store_id, &data_state.store));
 
@@ -194,31 +367,29 @@ impl BookmarksView {
 
         for bookmark in &bookmarks {
             if let Some(line) = data_state.store.get_by_id(&bookmark.store_id) {
-                let ts = line.timestamp.to_rfc3339();
+                let ts = line.timestamp().to_rfc3339();
                 let msg = &line.message;
                 let name = &bookmark.name;
-                if name.is_empty() {
-                    writeln!(writer, "{ts}\t{msg}")
+                let text = if name.is_empty() {
+                    format!("{ts}\t{msg}")
                 } else {
-                    writeln!(writer, "{ts}\t{msg}\t[{name}]")
-                }
-                .map_err(|e| format!("Write error: {e}"))?;
+                    format!("{ts}\t{msg}\t[{name}]")
+                };
+                writeln!(writer, "{}", redactor.apply(&text))
+                    .map_err(|e| format!("Write error: {e}"))?;
             }
         }
         Ok(())
     }
 
     /// Move selection in bookmarks view
-    pub fn move_selection_in_bookmarks(delta: i32, data_state: &mut SessionState) {
-        let mut bookmarks = data_state.get_all_bookmarks();
+    pub fn move_selection_in_bookmarks(&self, delta: i32, data_state: &mut SessionState) {
+        // TODO: We shouldn't sort this every time - maybe sort by timestamp?
+        let bookmarks = self.visible_bookmarks(data_state);
         if bookmarks.is_empty() {
             return;
         }
 
-        // Get sorted list of bookmark store IDs
-        // TODO: We shouldn't sort this every time - maybe sort by timestamp?
-        Self::sort_bookmarks_by_timestamp(&mut bookmarks, data_state);
-
         let bookmark_ids: Vec<StoreID> = bookmarks.into_iter().map(|id| id.store_id).collect();
 
         // Find current position in bookmark list
@@ -242,8 +413,8 @@ impl BookmarksView {
     }
 
     /// Jump to the first bookmark (Vim-style gg)
-    pub fn jump_to_top_in_bookmarks(data_state: &mut SessionState) {
-        let bookmarks = data_state.get_all_bookmarks();
+    pub fn jump_to_top_in_bookmarks(&self, data_state: &mut SessionState) {
+        let bookmarks = self.visible_bookmarks(data_state);
         if bookmarks.is_empty() {
             return;
         }
@@ -255,8 +426,8 @@ impl BookmarksView {
     }
 
     /// Jump to the last bookmark (Vim-style G)
-    pub fn jump_to_bottom_in_bookmarks(data_state: &mut SessionState) {
-        let bookmarks = data_state.get_all_bookmarks();
+    pub fn jump_to_bottom_in_bookmarks(&self, data_state: &mut SessionState) {
+        let bookmarks = self.visible_bookmarks(data_state);
         if bookmarks.is_empty() {
             return;
         }
@@ -268,21 +439,21 @@ impl BookmarksView {
     }
 
     /// Move selection up by one page in bookmarks view
-    pub fn page_up_in_bookmarks(data_state: &mut SessionState) {
+    pub fn page_up_in_bookmarks(&self, data_state: &mut SessionState) {
         const PAGE_SIZE: i32 = 25;
-        Self::move_selection_in_bookmarks(-PAGE_SIZE, data_state);
+        self.move_selection_in_bookmarks(-PAGE_SIZE, data_state);
     }
 
     /// Move selection down by one page in bookmarks view
-    pub fn page_down_in_bookmarks(data_state: &mut SessionState) {
+    pub fn page_down_in_bookmarks(&self, data_state: &mut SessionState) {
         const PAGE_SIZE: i32 = 25;
-        Self::move_selection_in_bookmarks(PAGE_SIZE, data_state);
+        self.move_selection_in_bookmarks(PAGE_SIZE, data_state);
     }
 }
 
 impl LogCrabTab for BookmarksView {
     fn title(&mut self) -> egui::WidgetText {
-        "Bookmarks".into()
+        tr!("Bookmarks").into()
     }
 
     fn render(
@@ -293,46 +464,75 @@ impl LogCrabTab for BookmarksView {
         all_filter_highlights: &[FilterHighlight],
         _histogram_markers: &[HistogramMarker],
     ) {
-        // Add timeline toggle button and export button at the top
+        // Add timeline toggle button, export button and search box at the top
+        let mut search_entered = false;
         ui.horizontal(|ui| {
             if ui
                 .toggle_value(&mut global_config.show_bookmarks_in_timeline, "📊")
-                .on_hover_text("Show bookmarks as markers in timeline")
+                .on_hover_text(tr!("Show bookmarks as markers in timeline"))
                 .changed()
             {
                 let new_val = global_config.show_bookmarks_in_timeline;
-                match GlobalConfig::update(|c| c.show_bookmarks_in_timeline = new_val) {
-                    Ok(updated) => *global_config = updated,
-                    Err(e) => tracing::error!("Failed to save config: {e}"),
-                }
+                *global_config = GlobalConfig::update(|c| c.show_bookmarks_in_timeline = new_val);
             }
-            ui.label("Show in Timeline");
+            ui.label(tr!("Show in Timeline"));
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui
-                    .button("Export…")
-                    .on_hover_text("Export all bookmarks to file")
+                    .button(tr!("Export…"))
+                    .on_hover_text(tr!("Export all bookmarks to file"))
                     .clicked()
                 {
                     if let Some(path) = rfd::FileDialog::new()
-                        .set_title("Export Bookmarks")
+                        .set_title(tr!("Export Bookmarks"))
                         .add_filter("Text", &["txt"])
                         .set_file_name("bookmarks.txt")
                         .save_file()
                     {
-                        if let Err(e) = Self::export_bookmarks(data_state, &path) {
+                        let redactor = Redactor::new(&global_config.redaction_rules);
+                        if let Err(e) = redactor.and_then(|redactor| {
+                            Self::export_bookmarks(data_state, &redactor, &path)
+                        }) {
                             tracing::error!("Failed to export bookmarks: {e}");
                         } else {
                             tracing::info!("Bookmarks exported to {}", path.display());
                         }
                     }
                 }
+
+                let response = ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.search)
+                            .desired_width(200.0)
+                            .hint_text(tr!("🔍 Search bookmarks...")),
+                    )
+                    .on_hover_text(tr!(
+                        "Show only bookmarks whose annotation or message contains this text; \
+                         Enter selects the first one"
+                    ));
+                search_entered =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
             });
         });
 
         ui.separator();
 
-        self.render_bookmarks(ui, data_state, all_filter_highlights, global_config.color_by_ml_score, global_config.grey_rare_ml_lines);
+        self.render_bookmarks(
+            ui,
+            data_state,
+            all_filter_highlights,
+            global_config.color_by_ml_score,
+            global_config.grey_rare_ml_lines,
+            global_config.score_threshold,
+        );
+
+        if search_entered {
+            // The Enter belongs to the search box, not to renaming
+            self.enter_pressed_this_frame = false;
+            if let Some(first) = self.visible_bookmarks(data_state).first() {
+                data_state.selected_line_index = Some(first.store_id);
+            }
+        }
     }
 
     fn process_events(
@@ -350,27 +550,32 @@ impl LogCrabTab for BookmarksView {
 
         for action in actions {
             match action {
-                ShortcutAction::MoveDown => Self::move_selection_in_bookmarks(1, data_state),
-                ShortcutAction::MoveUp => Self::move_selection_in_bookmarks(-1, data_state),
+                ShortcutAction::MoveDown => self.move_selection_in_bookmarks(1, data_state),
+                ShortcutAction::MoveUp => self.move_selection_in_bookmarks(-1, data_state),
                 ShortcutAction::ToggleBookmark => {}
+                ShortcutAction::QuickBookmark => {}
                 ShortcutAction::JumpToTop => {
-                    Self::jump_to_top_in_bookmarks(data_state);
+                    self.jump_to_top_in_bookmarks(data_state);
                 }
                 ShortcutAction::JumpToBottom => {
-                    Self::jump_to_bottom_in_bookmarks(data_state);
+                    self.jump_to_bottom_in_bookmarks(data_state);
                 }
                 ShortcutAction::PageUp => {
-                    Self::page_up_in_bookmarks(data_state);
+                    self.page_up_in_bookmarks(data_state);
                 }
                 ShortcutAction::PageDown => {
-                    Self::page_down_in_bookmarks(data_state);
+                    self.page_down_in_bookmarks(data_state);
                 }
                 ShortcutAction::FocusSearch => {}
                 ShortcutAction::NewFilterTab => {}
                 ShortcutAction::NewBookmarksTab => {}
                 ShortcutAction::ReverseCycleTab => {}
                 ShortcutAction::OpenFile => {}
+                ShortcutAction::ReloadFiles => {}
                 ShortcutAction::RenameFilter => {}
+                ShortcutAction::NextSameTemplate => {}
+                ShortcutAction::PreviousSameTemplate => {}
+                ShortcutAction::ErrorsView => {}
                 ShortcutAction::CloseTab => {}
                 ShortcutAction::CycleTab => {}
                 ShortcutAction::FocusPaneLeft => {}
@@ -0,0 +1,193 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::hex_dump::hex_dump;
+use crate::core::log_store::{LogStore, StoreID};
+use crate::tr;
+use egui::{RichText, Ui};
+use egui_extras::{Column, TableBuilder};
+
+/// Events emitted by the detail panel
+#[derive(Debug, Clone)]
+pub enum DetailPanelEvent {
+    /// Replace the filter's search text with a pattern matching this value literally
+    FilterOnValue { value: String },
+}
+
+/// What the detail panel shows of a line that offers more than its fields
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum DetailView {
+    #[default]
+    Fields,
+    /// The pretty-printed original of a structured line
+    Original,
+    /// The captured bytes of a binary record
+    Hex,
+}
+
+/// Key/value view of the selected line's parsed fields
+pub struct DetailPanel;
+
+impl DetailPanel {
+    /// Render the field table for `selected` into a resizable bottom panel.
+    ///
+    /// Generic rows (source, line, timestamp, message) come first, followed by
+    /// the format-specific fields reported by [`LogStore::get_fields`]. Lines
+    /// with a [`LogStore::get_pretty_raw`] text or [`LogStore::get_payload`]
+    /// bytes can switch to showing that text or a hex dump of the bytes instead.
+    pub fn render(
+        ui: &mut Ui,
+        store: &LogStore,
+        selected: Option<StoreID>,
+        filter_id: usize,
+    ) -> Option<DetailPanelEvent> {
+        profiling::scope!("DetailPanel::render");
+
+        let mut event = None;
+        let panel_id = egui::Id::new(("detail_panel", filter_id));
+        egui::TopBottomPanel::bottom(panel_id)
+            .resizable(true)
+            .default_height(180.0)
+            .min_height(60.0)
+            .show_inside(ui, |ui| {
+                let Some(line_idx) = selected else {
+                    ui.weak(tr!("Select a line to see its parsed fields"));
+                    return;
+                };
+                let Some(line) = store.get_by_id(&line_idx) else {
+                    ui.weak(tr!("Selected line is no longer available"));
+                    return;
+                };
+
+                let mut rows = vec![
+                    (
+                        "Source".to_string(),
+                        store.get_source_name(&line_idx).unwrap_or_default(),
+                    ),
+                    ("Line".to_string(), line.line_number.to_string()),
+                    (
+                        "Timestamp".to_string(),
+                        line.timestamp().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+                    ),
+                    ("Message".to_string(), line.message),
+                ];
+                rows.extend(store.get_fields(&line_idx));
+
+                let pretty = store.get_pretty_raw(&line_idx);
+                let payload = store.get_payload(&line_idx);
+                if pretty.is_some() || payload.is_some() {
+                    let view_id = panel_id.with("view");
+                    let mut view: DetailView = ui.data(|d| d.get_temp(view_id).unwrap_or_default());
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut view, DetailView::Fields, tr!("Fields"));
+                        if pretty.is_some() {
+                            ui.selectable_value(
+                                &mut view,
+                                DetailView::Original,
+                                tr!("Original JSON"),
+                            );
+                        }
+                        if let Some(bytes) = &payload {
+                            ui.selectable_value(&mut view, DetailView::Hex, tr!("Hex Dump"))
+                                .on_hover_text(tr!("{count} bytes", count = bytes.len()));
+                        }
+                    });
+                    ui.data_mut(|d| d.insert_temp(view_id, view));
+                    // A view the line does not offer falls back to its fields
+                    match (view, pretty, payload) {
+                        (DetailView::Original, Some(pretty), _) => {
+                            Self::render_raw(ui, &pretty);
+                            return;
+                        }
+                        (DetailView::Hex, _, Some(bytes)) => {
+                            Self::render_raw(ui, &hex_dump(&bytes));
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+
+                event = Self::render_table(ui, &rows);
+            });
+        event
+    }
+
+    /// Pretty-printed original or hex dump of a line, selectable for copying
+    fn render_raw(ui: &mut Ui, pretty: &str) {
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.add(egui::Label::new(RichText::new(pretty).monospace()).selectable(true));
+            });
+    }
+
+    fn render_table(ui: &mut Ui, rows: &[(String, String)]) -> Option<DetailPanelEvent> {
+        let mut event = None;
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .auto_shrink([false, false])
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::initial(140.0).at_least(60.0).clip(true))
+            .column(Column::remainder().at_least(100.0).clip(true))
+            .column(Column::exact(50.0))
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong(tr!("Field"));
+                });
+                header.col(|ui| {
+                    ui.strong(tr!("Value"));
+                });
+                header.col(|_| {});
+            })
+            .body(|body| {
+                body.rows(18.0, rows.len(), |mut row| {
+                    let (key, value) = &rows[row.index()];
+                    row.col(|ui| {
+                        ui.label(RichText::new(key).weak());
+                    });
+                    row.col(|ui| {
+                        ui.add(
+                            egui::Label::new(RichText::new(value.replace('\n', " ↵ ")).monospace())
+                                .truncate(),
+                        )
+                        .on_hover_text(value);
+                    });
+                    row.col(|ui| {
+                        if ui
+                            .small_button("📋")
+                            .on_hover_text(tr!("Copy {key}", key))
+                            .clicked()
+                        {
+                            ui.ctx().copy_text(value.clone());
+                        }
+                        if ui
+                            .small_button("🔍")
+                            .on_hover_text(tr!("Filter on this value"))
+                            .clicked()
+                        {
+                            event = Some(DetailPanelEvent::FilterOnValue {
+                                value: value.clone(),
+                            });
+                        }
+                    });
+                });
+            });
+        event
+    }
+}
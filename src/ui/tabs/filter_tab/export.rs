@@ -1,13 +1,35 @@
-use crate::core::log_store::LogStore;
+use crate::core::log_store::{LogStore, StoreID};
+use crate::core::redaction::Redactor;
 use crate::ui::tabs::filter_tab::filter_state::FilterState;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
-/// Export filtered results to a file (timestamp and message columns)
+/// Exported text of a line (timestamp and message columns)
+fn export_line(store: &LogStore, id: &StoreID) -> Option<String> {
+    let line = store.get_by_id(id)?;
+    Some(format!(
+        "{}\t{}",
+        line.timestamp().to_rfc3339(),
+        line.message
+    ))
+}
+
+/// The first `count` lines an export of `filter` would contain, unredacted
+pub fn export_preview_lines(filter: &FilterState, store: &LogStore, count: usize) -> Vec<String> {
+    let filtered_indices = filter.search.get_filtered_indices_cached();
+    filtered_indices
+        .iter()
+        .filter_map(|id| export_line(store, id))
+        .take(count)
+        .collect()
+}
+
+/// Export filtered results to a file, passing every line through `redactor`
 pub fn export_filtered_results(
     filter: &FilterState,
     store: &LogStore,
+    redactor: &Redactor,
     path: &Path,
 ) -> Result<(), String> {
     let filtered_indices = filter.search.get_filtered_indices_cached();
@@ -15,10 +37,9 @@ pub fn export_filtered_results(
     let mut writer = BufWriter::new(file);
 
     for id in filtered_indices.iter() {
-        if let Some(line) = store.get_by_id(id) {
-            let ts = line.timestamp.to_rfc3339();
-            let msg = &line.message;
-            writeln!(writer, "{ts}\t{msg}").map_err(|e| format!("Write error: {e}"))?;
+        if let Some(line) = export_line(store, id) {
+            writeln!(writer, "{}", redactor.apply(&line))
+                .map_err(|e| format!("Write error: {e}"))?;
         }
     }
     Ok(())
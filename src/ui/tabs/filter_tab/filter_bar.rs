@@ -16,13 +16,23 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use egui::{Color32, Ui};
 
 use crate::{
     config::GlobalConfig,
-    core::LogStore,
+    core::{
+        column_filter::{source_label, SOURCE_COLUMN},
+        field_query::split_field_terms,
+        search_rule::{or_pattern, pasted_patterns},
+        search_state::TimeScope,
+        shell_command::{shell_command, GrepTool},
+        slow_filter::SlowPatternHint,
+        LogStore, SearchState,
+    },
+    tr,
     ui::{
         session_state::SessionState,
         tabs::filter_tab::{filter_state::FilterState, log_table::TimestampMode},
@@ -55,8 +65,20 @@ pub struct FilterBar {
     history_index: Option<usize>,
     /// Temporary storage for the text being edited before entering history mode
     pre_history_text: String,
+    /// A long pasted pattern list that could be split into separate tabs instead
+    split_offer: Option<SplitOffer>,
+}
+
+/// Patterns pasted into the search box, kept while splitting them into tabs is offered
+struct SplitOffer {
+    patterns: Vec<String>,
+    /// Search text before the paste, restored when the list is split
+    text_before: String,
 }
 
+/// Pasting more patterns than this offers one tab per pattern
+const SPLIT_OFFER_THRESHOLD: usize = 4;
+
 impl FilterBar {
     pub const fn new() -> Self {
         Self {
@@ -65,6 +87,7 @@ impl FilterBar {
             favorite_focus_requested: false,
             history_index: None,
             pre_history_text: String::new(),
+            split_offer: None,
         }
     }
 
@@ -72,16 +95,15 @@ impl FilterBar {
         let new_name = self.temp_favorite_name.clone();
         let search_text = filter.search.search_text.clone();
         let case_sensitive = filter.search.case_sensitive;
-        match GlobalConfig::update(|c| {
-            if let Some(fav) = c.favorite_filters.iter_mut().find(|f| {
-                f.search_text == search_text && f.case_sensitive == case_sensitive
-            }) {
+        *global_config = GlobalConfig::update(|c| {
+            if let Some(fav) = c
+                .favorite_filters
+                .iter_mut()
+                .find(|f| f.search_text == search_text && f.case_sensitive == case_sensitive)
+            {
                 fav.name.clone_from(&new_name);
             }
-        }) {
-            Ok(updated) => *global_config = updated,
-            Err(e) => tracing::error!("Failed to save config: {e}"),
-        }
+        });
     }
 
     /// Render the filter bar UI
@@ -99,7 +121,20 @@ impl FilterBar {
 
         let mut events = Vec::new();
 
+        if filter.filter_bar_collapsed {
+            Self::render_collapsed(ui, filter, log_view_state);
+            return events;
+        }
+
         ui.horizontal(|ui| {
+            if ui
+                .small_button("▼")
+                .on_hover_text(tr!("Collapse the filter bar"))
+                .clicked()
+            {
+                filter.filter_bar_collapsed = true;
+                log_view_state.modified = true;
+            }
             Self::render_edit_button(ui, &mut events);
             Self::render_globally_visible_toggle(ui, filter, log_view_state);
             Self::render_histogram_toggle(ui, filter, log_view_state);
@@ -110,26 +145,55 @@ impl FilterBar {
             Self::render_exclude_input(ui, filter, log_view_state);
             Self::render_case_checkbox(ui, filter, log_view_state);
             Self::render_validation_status(ui, filter);
+            render_slow_filter_warning(ui, &mut filter.search);
             Self::render_convert_to_highlight_button(ui, &mut events);
+            Self::render_scope_dropdown(ui, filter, &log_view_state.store);
+            Self::render_source_mask(ui, filter, log_view_state);
             Self::render_timestamp_mode_dropdown(ui, filter, &log_view_state.store);
+            Self::render_detail_panel_toggle(ui, filter);
+            Self::render_invisibles_toggle(ui, filter);
+            Self::render_compact_toggle(ui, filter, log_view_state);
+            Self::render_follow_toggle(ui, filter);
 
             // Export button for filtered results
             if ui
-                .button("Export…")
-                .on_hover_text("Export filtered results to file")
+                .button(tr!("Export…"))
+                .on_hover_text(tr!("Export filtered results to file"))
                 .clicked()
             {
                 events.push(FilterInternalEvent::ExportFiltered);
             }
+            Self::render_copy_command_menu(ui, filter, &log_view_state.store);
         });
 
         events
     }
 
+    /// One-line summary of a collapsed filter bar with a button to expand it
+    fn render_collapsed(ui: &mut Ui, filter: &mut FilterState, session_state: &mut SessionState) {
+        ui.horizontal(|ui| {
+            if ui
+                .small_button("▶")
+                .on_hover_text(tr!("Show the filter bar"))
+                .clicked()
+            {
+                filter.filter_bar_collapsed = false;
+                session_state.modified = true;
+            }
+            ui.colored_label(filter.color, "■");
+            let search = if filter.search.search_text.is_empty() {
+                tr!("(all lines)").to_string()
+            } else {
+                format!("🔍 {}", filter.search.search_text)
+            };
+            ui.add(egui::Label::new(egui::RichText::new(search).weak()).truncate());
+        });
+    }
+
     fn render_edit_button(ui: &mut Ui, events: &mut Vec<FilterInternalEvent>) {
         if ui
             .small_button("✏")
-            .on_hover_text("Edit filter name")
+            .on_hover_text(tr!("Edit filter name"))
             .clicked()
         {
             events.push(FilterInternalEvent::FilterNameEditRequested);
@@ -138,7 +202,7 @@ impl FilterBar {
 
     fn render_color_picker(ui: &mut Ui, filter: &mut FilterState) {
         ui.color_edit_button_srgba(&mut filter.color)
-            .on_hover_text("Choose highlight color for this filter");
+            .on_hover_text(tr!("Choose highlight color for this filter"));
     }
 
     fn render_favorite_toggle(
@@ -153,7 +217,7 @@ impl FilterBar {
             .find(|fav| fav.matches(filter));
         if ui
             .toggle_value(&mut current_favorite.is_some(), "⭐")
-            .on_hover_text("Toggle favorite filter")
+            .on_hover_text(tr!("Toggle favorite filter"))
             .clicked()
         {
             events.push(FilterInternalEvent::FavoriteToggled);
@@ -237,7 +301,7 @@ impl FilterBar {
         events: &mut Vec<FilterInternalEvent>,
     ) {
         let selected_text = current_favorite.map_or_else(
-            || "⭐ Favorites".to_string(),
+            || tr!("⭐ Favorites").to_string(),
             |fav| format!("⭐ {}", fav.display_name()),
         );
 
@@ -272,9 +336,14 @@ impl FilterBar {
         session_state: &mut SessionState,
     ) {
         let search_id = ui.id().with("search_input");
+        if ui.memory(|mem| mem.has_focus(search_id)) {
+            self.handle_list_paste(ui, filter, session_state);
+        }
         let search_response = ui.add(
             egui::TextEdit::singleline(&mut filter.search.search_text)
-                .hint_text("Enter regex pattern (e.g., ERROR|FATAL, \\d+\\.\\d+\\.\\d+\\.\\d+)")
+                .hint_text(tr!(
+                    "Enter regex pattern (e.g., ERROR|FATAL, \\d+\\.\\d+\\.\\d+\\.\\d+)"
+                ))
                 .desired_width(300.0)
                 .id(search_id),
         );
@@ -292,8 +361,73 @@ impl FilterBar {
 
         if search_response.changed() {
             self.history_index = None;
+            self.split_offer = None;
             session_state.modified = true;
         }
+
+        self.render_split_offer(ui, filter, session_state);
+    }
+
+    /// Turn a pasted multi-line list into one OR-combined pattern of its
+    /// (escaped) lines, instead of letting the text field join the lines.
+    fn handle_list_paste(
+        &mut self,
+        ui: &Ui,
+        filter: &mut FilterState,
+        session_state: &mut SessionState,
+    ) {
+        let pasted = ui.input_mut(|input| {
+            let position = input.events.iter().position(|event| {
+                matches!(event, egui::Event::Paste(text) if pasted_patterns(text).len() > 1)
+            })?;
+            if let egui::Event::Paste(text) = input.events.remove(position) {
+                Some(text)
+            } else {
+                None
+            }
+        });
+        let Some(pasted) = pasted else {
+            return;
+        };
+        let patterns = pasted_patterns(&pasted);
+        let text_before = filter.search.search_text.clone();
+        filter.search.search_text = or_pattern(&text_before, &patterns);
+        self.history_index = None;
+        session_state.modified = true;
+        self.split_offer = (patterns.len() > SPLIT_OFFER_THRESHOLD).then(|| SplitOffer {
+            patterns: patterns.into_iter().map(str::to_string).collect(),
+            text_before,
+        });
+    }
+
+    fn render_split_offer(
+        &mut self,
+        ui: &mut Ui,
+        filter: &mut FilterState,
+        session_state: &mut SessionState,
+    ) {
+        let Some(offer) = &self.split_offer else {
+            return;
+        };
+        if ui
+            .button(tr!("Split into {count} tabs", count = offer.patterns.len()))
+            .on_hover_text(tr!(
+                "Open one filter tab per pasted pattern instead of combining them here"
+            ))
+            .clicked()
+        {
+            if let Some(offer) = self.split_offer.take() {
+                filter.search.search_text = offer.text_before;
+                session_state.pending_split_filters = Some(offer.patterns);
+                session_state.modified = true;
+            }
+        } else if ui
+            .small_button("✖")
+            .on_hover_text(tr!("Keep the combined pattern"))
+            .clicked()
+        {
+            self.split_offer = None;
+        }
     }
 
     fn render_exclude_input(
@@ -303,7 +437,7 @@ impl FilterBar {
     ) {
         let exclude_response = ui.add(
             egui::TextEdit::singleline(&mut filter.search.exclude_text)
-                .hint_text("Exclude pattern (optional)")
+                .hint_text(tr!("Exclude pattern (optional)"))
                 .desired_width(200.0),
         );
 
@@ -391,7 +525,7 @@ impl FilterBar {
     ) {
         let toggle_response = ui
             .toggle_value(&mut filter.search.case_sensitive, "Aa")
-            .on_hover_text("Toggle case insensitive matching");
+            .on_hover_text(tr!("Toggle case insensitive matching"));
         if toggle_response.changed() {
             session_state.modified = true;
         }
@@ -404,7 +538,7 @@ impl FilterBar {
     ) {
         if ui
             .toggle_value(&mut filter.enabled, "👁")
-            .on_hover_text("Show highlights from this filter in all tabs")
+            .on_hover_text(tr!("Show highlights from this filter in all tabs"))
             .changed()
         {
             session_state.modified = true;
@@ -418,13 +552,100 @@ impl FilterBar {
     ) {
         if ui
             .toggle_value(&mut filter.show_in_histogram, "📊")
-            .on_hover_text("Show filter matches as vertical lines in histogram")
+            .on_hover_text(tr!("Show filter matches as vertical lines in histogram"))
+            .changed()
+        {
+            session_state.modified = true;
+        }
+    }
+
+    fn render_detail_panel_toggle(ui: &mut Ui, filter: &mut FilterState) {
+        ui.toggle_value(&mut filter.show_detail_panel, "🔎")
+            .on_hover_text(tr!("Show parsed fields of the selected line"));
+    }
+
+    fn render_invisibles_toggle(ui: &mut Ui, filter: &mut FilterState) {
+        ui.toggle_value(&mut filter.show_invisibles, "¶")
+            .on_hover_text(tr!(
+                "Show tabs, carriage returns, control characters and trailing whitespace"
+            ));
+    }
+
+    fn render_compact_toggle(
+        ui: &mut Ui,
+        filter: &mut FilterState,
+        session_state: &mut SessionState,
+    ) {
+        if ui
+            .toggle_value(&mut filter.compact, "≡")
+            .on_hover_text(tr!(
+                "Compact rows: smaller font and spacing to fit more lines"
+            ))
             .changed()
         {
             session_state.modified = true;
         }
     }
 
+    fn render_follow_toggle(ui: &mut Ui, filter: &mut FilterState) {
+        if ui
+            .toggle_value(&mut filter.follow, "⤓")
+            .on_hover_text(tr!(
+                "Follow: scroll along as new lines arrive. Scrolling up pauses, \
+                 scrolling back to the bottom resumes"
+            ))
+            .changed()
+        {
+            filter.follow_paused_at = None;
+            filter.follow_jump_pending = filter.follow;
+        }
+    }
+
+    /// Menu copying an `rg`/`grep` command that finds the filter's lines in
+    /// the raw files of the tab's text sources
+    fn render_copy_command_menu(ui: &mut Ui, filter: &FilterState, store: &Arc<LogStore>) {
+        let search = &filter.search;
+        ui.menu_button("⌨", |ui| {
+            let paths: Vec<PathBuf> = store
+                .get_source_ids_and_paths()
+                .into_iter()
+                .filter(|(source_id, path)| {
+                    !store.is_binary_source(*source_id)
+                        && !search
+                            .column_filter
+                            .is_hidden(SOURCE_COLUMN, &path.to_string_lossy())
+                })
+                .map(|(_, path)| path)
+                .collect();
+            for tool in GrepTool::ALL {
+                if ui
+                    .button(tr!("Copy as {tool} command", tool = tool.name()))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(shell_command(
+                        tool,
+                        &search.search_text,
+                        &search.exclude_text,
+                        search.case_sensitive,
+                        &paths,
+                    ));
+                    ui.close();
+                }
+            }
+            if !split_field_terms(&search.search_text).1.is_empty() {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    tr!("Field terms have no equivalent on raw lines and are left out"),
+                );
+            }
+        })
+        .response
+        .on_hover_text(tr!(
+            "Copy as shell command: reproduce this filter on the raw files, \
+             e.g. on a server. Time scopes and column filters are not carried over"
+        ));
+    }
+
     fn render_validation_status(ui: &mut Ui, filter: &FilterState) {
         // Check both include and exclude patterns
         let include_result = filter.search.get_regex();
@@ -435,24 +656,139 @@ impl FilterBar {
                 ui.colored_label(Color32::GREEN, "✓");
             }
             (Err(err), _) => {
-                ui.colored_label(Color32::RED, format!("❌ Include: {err}"));
+                ui.colored_label(Color32::RED, tr!("❌ Include: {err}", err));
             }
             (_, Err(err)) => {
-                ui.colored_label(Color32::RED, format!("❌ Exclude: {err}"));
+                ui.colored_label(Color32::RED, tr!("❌ Exclude: {err}", err));
             }
         }
     }
 
     fn render_convert_to_highlight_button(ui: &mut Ui, events: &mut Vec<FilterInternalEvent>) {
         if ui
-            .button("into Highlight")
-            .on_hover_text("Convert this filter to a highlight")
+            .button(tr!("into Highlight"))
+            .on_hover_text(tr!("Convert this filter to a highlight"))
             .clicked()
         {
             events.push(FilterInternalEvent::ConvertToHighlight);
         }
     }
 
+    /// Restrict the filter to the span between two consecutive bookmarks.
+    fn render_scope_dropdown(ui: &mut Ui, filter: &mut FilterState, store: &Arc<LogStore>) {
+        let selected_text = filter.search.time_scope.as_ref().map_or_else(
+            || "⌚ Whole log".to_string(),
+            |scope| format!("⌚ {}", scope.label),
+        );
+
+        egui::ComboBox::from_id_salt("time_scope_combo")
+            .selected_text(selected_text)
+            .width(160.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut filter.search.time_scope, None, tr!("⌚ Whole log"));
+
+                let mut bookmarks: Vec<_> = store
+                    .get_all_bookmarks()
+                    .into_iter()
+                    .filter_map(|b| {
+                        let ts = store.adjusted_timestamp(&b.store_id)?;
+                        let name = if b.name.is_empty() {
+                            tr!(
+                                "Line {line}",
+                                line = b.store_id.line_index_within_source() + 1
+                            )
+                        } else {
+                            b.name
+                        };
+                        Some((ts, name))
+                    })
+                    .collect();
+                bookmarks.sort_by_key(|(ts, _)| *ts);
+
+                if bookmarks.len() < 2 {
+                    ui.weak(tr!("Bookmark two lines to scope between them"));
+                    return;
+                }
+                for pair in bookmarks.windows(2) {
+                    let [(start, from), (end, to)] = pair else {
+                        continue;
+                    };
+                    let scope = TimeScope {
+                        label: format!("{from} → {to}"),
+                        start: *start,
+                        end: *end,
+                    };
+                    let label = scope.label.clone();
+                    ui.selectable_value(&mut filter.search.time_scope, Some(scope), label);
+                }
+            })
+            .response
+            .on_hover_text(tr!("Only search between two bookmarks"));
+    }
+
+    /// Checkbox list of the loaded sources, so a tab can show e.g. only the
+    /// app log while another shows only the kernel log.
+    ///
+    /// Shares the hidden set of the Source column dropdown.
+    fn render_source_mask(ui: &mut Ui, filter: &mut FilterState, session_state: &mut SessionState) {
+        let mut sources: Vec<String> = session_state
+            .store
+            .get_source_ids_and_paths()
+            .into_iter()
+            .map(|(_, path)| path.to_string_lossy().into_owned())
+            .collect();
+        if sources.len() < 2 {
+            return;
+        }
+        sources.sort_unstable();
+        sources.dedup();
+
+        let column_filter = &mut filter.search.column_filter;
+        let hidden = sources
+            .iter()
+            .filter(|path| column_filter.is_hidden(SOURCE_COLUMN, path))
+            .count();
+        let label = if hidden == 0 {
+            tr!("📂 All sources").to_string()
+        } else {
+            tr!(
+                "📂 {shown}/{total} sources",
+                shown = sources.len() - hidden,
+                total = sources.len()
+            )
+        };
+        let label = if hidden == 0 {
+            egui::RichText::new(label)
+        } else {
+            egui::RichText::new(label).color(ui.visuals().warn_fg_color)
+        };
+
+        let mut changed = false;
+        ui.menu_button(label, |ui| {
+            for path in &sources {
+                let mut shown = !column_filter.is_hidden(SOURCE_COLUMN, path);
+                if ui
+                    .checkbox(&mut shown, source_label(path))
+                    .on_hover_text(path)
+                    .changed()
+                {
+                    column_filter.set_hidden(SOURCE_COLUMN, path, !shown);
+                    changed = true;
+                }
+            }
+            ui.separator();
+            if ui.button(tr!("Show all")).clicked() {
+                column_filter.show_all(SOURCE_COLUMN);
+                changed = true;
+            }
+        })
+        .response
+        .on_hover_text(tr!("Choose which sources this tab shows"));
+        if changed {
+            session_state.modified = true;
+        }
+    }
+
     fn render_timestamp_mode_dropdown(
         ui: &mut Ui,
         filter: &mut FilterState,
@@ -475,20 +811,91 @@ impl FilterBar {
                 ui.selectable_value(
                     &mut filter.timestamp_mode,
                     TimestampMode::Absolute,
-                    "🕐 Absolute time",
+                    tr!("🕐 Absolute time"),
                 );
                 ui.selectable_value(
                     &mut filter.timestamp_mode,
                     TimestampMode::Delta,
-                    "Δ Delta time",
+                    tr!("Δ Delta time"),
                 );
                 if let Some(first_ts) = first_timestamp {
                     ui.selectable_value(
                         &mut filter.timestamp_mode,
                         TimestampMode::Relative(first_ts),
-                        "⏱ Relative time",
+                        tr!("⏱ Relative time"),
                     );
                 }
             });
     }
 }
+
+/// Warning icon shown when the last filter pass was slow.
+///
+/// Hovering shows the measured time and a suggestion for a cheaper pattern.
+/// A pass that hit the filter timeout is reported as aborted, with a button
+/// to try again.
+pub fn render_slow_filter_warning(ui: &mut Ui, search: &mut SearchState) {
+    let timing = search.last_timing();
+    if !timing.is_slow() {
+        return;
+    }
+    let seconds = format!("{:.1}", timing.elapsed.as_secs_f64());
+    let mut details = if timing.timed_out {
+        tr!("Filtering was stopped after {seconds} s.", seconds)
+    } else {
+        tr!("Filtering took {seconds} s.", seconds)
+    };
+    if timing.aborted_lines > 0 {
+        details.push('\n');
+        details.push_str(&tr!(
+            "{count} lines exceeded the per-line budget and were skipped.",
+            count = timing.aborted_lines
+        ));
+    }
+    details.push_str("\n\n");
+    details.push_str(slow_pattern_suggestion(SlowPatternHint::for_pattern(
+        &search.search_text,
+    )));
+    if timing.timed_out {
+        ui.colored_label(
+            ui.visuals().error_fg_color,
+            tr!("⛔ Filter aborted: pattern too slow"),
+        )
+        .on_hover_text(details);
+        if ui
+            .small_button(tr!("Retry"))
+            .on_hover_text(tr!(
+                "Run the filter again, e.g. after raising the timeout in View → Performance"
+            ))
+            .clicked()
+        {
+            search.retry();
+        }
+    } else {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {seconds} s"))
+            .on_hover_text(details);
+    }
+}
+
+fn slow_pattern_suggestion(hint: SlowPatternHint) -> &'static str {
+    match hint {
+        SlowPatternHint::NestedQuantifier => tr!(
+            "The pattern repeats a group that itself repeats, e.g. (a+)+. \
+             Remove the inner or outer quantifier."
+        ),
+        SlowPatternHint::StackedWildcards => tr!(
+            "Several .* can split a line in many ways. \
+             Replace them with more specific classes such as [^ ]*."
+        ),
+        SlowPatternHint::Backtracking => tr!(
+            "Lookarounds and backreferences disable the fast matcher. \
+             Try to express the filter without them, or use the exclude field."
+        ),
+        SlowPatternHint::LeadingWildcard => {
+            tr!("Filters match anywhere in the line, so a leading .* is not needed.")
+        }
+        SlowPatternHint::General => {
+            tr!("Use more specific literal text or anchor the pattern to make it faster.")
+        }
+    }
+}
@@ -16,12 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::anomaly::keyword::ERROR_KEYWORDS_PATTERN;
+use crate::core::column_filter::SOURCE_COLUMN;
 use crate::core::log_store::StoreID;
 use crate::core::{SavedFilter, SearchRule};
-use crate::ui::tabs::filter_tab::histogram::HistogramCache;
+use crate::ui::tabs::filter_tab::histogram::{HistogramCache, DEFAULT_HISTOGRAM_HEIGHT};
 use crate::ui::tabs::filter_tab::log_table::{ColumnWidths, TimestampMode};
 use egui::Color32;
 
+/// Tab name of the built-in [`FilterState::errors_view`].
+pub const ERRORS_VIEW_NAME: &str = "⚠ Errors & Fatals";
+
 /// Represents a single filter view with its own search criteria and cached results.
 ///
 /// Wraps a `SearchRule` (shared with highlights) and adds filter-tab-specific
@@ -44,6 +49,47 @@ pub struct FilterState {
 
     /// How the timestamp column displays time (absolute or delta).
     pub timestamp_mode: TimestampMode,
+
+    /// Whether the parsed-field detail panel is shown below the table
+    pub show_detail_panel: bool,
+
+    /// Whether tabs, carriage returns, control characters and trailing
+    /// whitespace are drawn as visible symbols in the message column
+    pub show_invisibles: bool,
+
+    /// Compact rows: smaller font, minimal spacing and no marker icons, so
+    /// more lines fit on screen
+    pub compact: bool,
+
+    /// Follow mode: keep the newest lines in view while followed files grow
+    pub follow: bool,
+
+    /// Row count at the moment the user scrolled away from the bottom in
+    /// follow mode; `None` while auto-scroll is active
+    pub follow_paused_at: Option<usize>,
+
+    /// Scroll to the last row on the next frame (follow mode was switched on,
+    /// or the "new lines" pill was clicked)
+    pub follow_jump_pending: bool,
+
+    /// Show an alert badge on the tab when more than this many lines match
+    pub alert_threshold: Option<usize>,
+
+    /// Named group the tab is organized under, shown as a colored title prefix
+    pub group: Option<String>,
+
+    /// Built-in "Errors & Fatals" view: its search is fixed, it is never
+    /// saved as a user filter and does not use up a palette slot.
+    pub is_errors_view: bool,
+
+    /// Height of the histogram bars (pixels)
+    pub histogram_height: f32,
+
+    /// Histogram hidden behind a one-line expander
+    pub histogram_collapsed: bool,
+
+    /// Filter bar reduced to a one-line summary
+    pub filter_bar_collapsed: bool,
 }
 
 impl FilterState {
@@ -57,9 +103,37 @@ impl FilterState {
             histogram_cache: HistogramCache::new(filter_id),
             column_widths: ColumnWidths::default(),
             timestamp_mode: TimestampMode::default(),
+            show_detail_panel: false,
+            show_invisibles: false,
+            compact: false,
+            follow: false,
+            follow_paused_at: None,
+            follow_jump_pending: false,
+            alert_threshold: None,
+            group: None,
+            is_errors_view: false,
+            histogram_height: DEFAULT_HISTOGRAM_HEIGHT,
+            histogram_collapsed: false,
+            filter_bar_collapsed: false,
         }
     }
 
+    /// The built-in "Errors & Fatals" view.
+    ///
+    /// Shows the lines [`crate::anomaly::keyword::is_error`] accepts, i.e. the
+    /// ones the keyword scorer rates highest: those with an error or fatal
+    /// level and those naming an error. Logcat-style `PID TID E|F` markers
+    /// also count, for lines whose format has no level of its own.
+    #[must_use]
+    pub fn errors_view() -> Self {
+        let mut state = Self::new(ERRORS_VIEW_NAME.to_string(), Color32::from_rgb(220, 50, 50));
+        state.search.search_text = format!(r"\b\d+\s+\d+\s+[EF]\s|(?i:{ERROR_KEYWORDS_PATTERN})");
+        state.search.case_sensitive = true;
+        state.search.or_errors = true;
+        state.is_errors_view = true;
+        state
+    }
+
     /// Get the unique filter ID
     pub const fn get_id(&self) -> usize {
         self.rule.id()
@@ -90,7 +164,11 @@ impl std::ops::DerefMut for FilterState {
 
 impl From<&SavedFilter> for FilterState {
     fn from(saved: &SavedFilter) -> Self {
-        let rule = SearchRule::from(saved);
+        let mut rule = SearchRule::from(saved);
+        rule.search.column_filter.hide_all(
+            SOURCE_COLUMN,
+            saved.hidden_sources.iter().map(String::as_str),
+        );
         let filter_id = rule.id();
         Self {
             rule,
@@ -99,12 +177,39 @@ impl From<&SavedFilter> for FilterState {
             histogram_cache: HistogramCache::new(filter_id),
             column_widths: ColumnWidths::default(),
             timestamp_mode: TimestampMode::default(),
+            show_detail_panel: false,
+            show_invisibles: false,
+            compact: saved.compact,
+            follow: false,
+            follow_paused_at: None,
+            follow_jump_pending: false,
+            alert_threshold: saved.alert_threshold,
+            group: saved.group.clone(),
+            is_errors_view: false,
+            histogram_height: saved.histogram_height.unwrap_or(DEFAULT_HISTOGRAM_HEIGHT),
+            histogram_collapsed: saved.histogram_collapsed,
+            filter_bar_collapsed: saved.filter_bar_collapsed,
         }
     }
 }
 
 impl From<&FilterState> for SavedFilter {
     fn from(filter: &FilterState) -> Self {
-        Self::from(&filter.rule)
+        Self {
+            alert_threshold: filter.alert_threshold,
+            group: filter.group.clone(),
+            compact: filter.compact,
+            histogram_height: (filter.histogram_height != DEFAULT_HISTOGRAM_HEIGHT)
+                .then_some(filter.histogram_height),
+            histogram_collapsed: filter.histogram_collapsed,
+            filter_bar_collapsed: filter.filter_bar_collapsed,
+            hidden_sources: filter
+                .search
+                .column_filter
+                .hidden_values(SOURCE_COLUMN)
+                .map(str::to_string)
+                .collect(),
+            ..Self::from(&filter.rule)
+        }
     }
 }
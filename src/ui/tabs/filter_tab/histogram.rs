@@ -20,11 +20,18 @@ use crate::core::histogram_worker::{
     AnomalyDistribution, HistogramCacheKey, HistogramData, HistogramRequest, HistogramResult,
     HistogramWorkerHandle, NUM_BUCKETS, SCORE_BUCKETS,
 };
-use crate::core::{log_store::StoreID, LogStore};
+use crate::core::run_history::PreviousRun;
+use crate::core::{
+    log_store::{StoreID, StoreVersion},
+    LogStore,
+};
+use crate::tr;
+use crate::ui::tabs::bookmarks_tab::BookmarkData;
 use crate::ui::tabs::filter_tab::filter_state::FilterState;
 use crate::ui::tabs::filter_tab::log_table;
 use chrono::{DateTime, Local, TimeDelta};
 use egui::{Color32, Pos2, Ui};
+use std::collections::HashMap;
 use std::sync::mpsc::{self, Receiver};
 use std::sync::Arc;
 use std::time::Duration;
@@ -49,6 +56,128 @@ fn timedelta_from_secs_f32(secs: f32) -> TimeDelta {
     }
 }
 
+/// Anomaly score threshold preselected when switching to anomaly-only bars
+const DEFAULT_ANOMALY_THRESHOLD: u8 = 70;
+
+/// Color of bookmark flags and timeline markers
+pub const BOOKMARK_COLOR: Color32 = Color32::from_rgb(255, 215, 0);
+
+/// Height of the bars until the user resizes them (pixels)
+pub const DEFAULT_HISTOGRAM_HEIGHT: f32 = 60.0;
+
+/// Range the bars can be resized to (pixels)
+const HISTOGRAM_HEIGHT_RANGE: std::ops::RangeInclusive<f32> = 30.0..=600.0;
+
+/// Height of the drag handle below the histogram (pixels)
+const RESIZE_HANDLE_HEIGHT: f32 = 6.0;
+
+/// Height of the bookmark strip above the bars (pixels)
+const BOOKMARK_STRIP_HEIGHT: f32 = 12.0;
+
+/// How close the pointer must be to a bookmark flag to hover or click it (pixels)
+const BOOKMARK_HIT_DISTANCE: f32 = 4.0;
+
+/// Minimum horizontal distance between two time ruler ticks (pixels)
+const RULER_MIN_TICK_SPACING_PX: f64 = 110.0;
+
+/// Candidate tick steps for the time ruler, in milliseconds.
+/// Every sub-day step divides 24h so ticks stay aligned to local midnight.
+const RULER_STEPS_MS: &[i64] = &[
+    1,
+    2,
+    5,
+    10,
+    20,
+    50,
+    100,
+    200,
+    500,
+    1_000,
+    2_000,
+    5_000,
+    10_000,
+    15_000,
+    30_000,
+    60_000,
+    2 * 60_000,
+    5 * 60_000,
+    10 * 60_000,
+    15 * 60_000,
+    30 * 60_000,
+    3_600_000,
+    2 * 3_600_000,
+    3 * 3_600_000,
+    6 * 3_600_000,
+    12 * 3_600_000,
+    86_400_000,
+];
+
+/// Compute tick timestamps for the visible range so that ticks are at least
+/// [`RULER_MIN_TICK_SPACING_PX`] apart on a ruler `width_px` wide.
+fn ruler_ticks(
+    view_start: DateTime<Local>,
+    view_end: DateTime<Local>,
+    width_px: f32,
+) -> Vec<DateTime<Local>> {
+    let total_ms = (view_end - view_start).num_milliseconds();
+    if total_ms <= 0 || width_px <= 0.0 {
+        return Vec::new();
+    }
+    let max_ticks = (f64::from(width_px) / RULER_MIN_TICK_SPACING_PX).max(1.0);
+    let min_step_ms = total_ms as f64 / max_ticks;
+
+    let Some(midnight) = view_start
+        .date_naive()
+        .and_time(chrono::NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+    else {
+        return Vec::new();
+    };
+
+    let mut ticks = Vec::new();
+    if let Some(&step_ms) = RULER_STEPS_MS.iter().find(|&&s| s as f64 >= min_step_ms) {
+        // Sub-day (or exactly one day) steps: align to multiples of the step since midnight
+        let offset_ms = (view_start - midnight).num_milliseconds();
+        let first = midnight + TimeDelta::milliseconds((offset_ms + step_ms - 1) / step_ms * step_ms);
+        let mut tick = first;
+        while tick <= view_end {
+            ticks.push(tick);
+            tick += TimeDelta::milliseconds(step_ms);
+        }
+    } else {
+        // Multi-day steps: walk calendar days so DST changes don't shift the ticks
+        let step_days = (min_step_ms / 86_400_000.0).ceil() as u64;
+        let mut date = view_start.date_naive();
+        while let Some(tick) = date
+            .and_time(chrono::NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+        {
+            if tick > view_end {
+                break;
+            }
+            if tick >= view_start {
+                ticks.push(tick);
+            }
+            let Some(next) = date.checked_add_days(chrono::Days::new(step_days)) else {
+                break;
+            };
+            date = next;
+        }
+    }
+    ticks
+}
+
+/// Per-bucket ratio `numerators[i] / denominators[i]`, `None` where the denominator is 0.
+fn bucket_ratios(numerators: &[usize], denominators: &[usize]) -> Vec<Option<f64>> {
+    numerators
+        .iter()
+        .zip(denominators)
+        .map(|(&num, &den)| (den > 0).then(|| num as f64 / den as f64))
+        .collect()
+}
+
 /// Minimum fraction of view width required for drag-to-zoom selection
 const MIN_DRAG_ZOOM_FRACTION: f32 = 0.005;
 
@@ -114,6 +243,67 @@ pub struct HistogramCache {
     data: Option<HistogramData>,
     /// Zoom state for the timeline
     pub zoom: HistogramZoomState,
+    /// Name of the marker plotted as `marker / this filter` ratio line
+    pub ratio_marker: Option<String>,
+    /// Anomaly-only mode: bars count only lines scoring at least this (0–100)
+    pub anomaly_threshold: Option<u8>,
+    /// Per-bucket line counts of the markers for the current view
+    marker_counts: MarkerCounts,
+}
+
+/// Store version and bucket layout the marker counts were computed for
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct MarkerCountsKey {
+    store_version: StoreVersion,
+    view_start_ms: i64,
+    view_bucket_size: Duration,
+    num_visible_buckets: usize,
+}
+
+/// Marker counts binned like the bars, recomputed only when the store, the
+/// view or a marker's lines change
+#[derive(Default)]
+struct MarkerCounts {
+    key: Option<MarkerCountsKey>,
+    /// Marker name -> the lines the counts were taken from and the counts
+    counts: HashMap<String, (Arc<Vec<StoreID>>, Vec<usize>)>,
+}
+
+impl MarkerCounts {
+    fn get(
+        &mut self,
+        store: &LogStore,
+        marker: &HistogramMarker,
+        view_start: DateTime<Local>,
+        view_bucket_size: Duration,
+        num_visible_buckets: usize,
+    ) -> &[usize] {
+        let key = MarkerCountsKey {
+            store_version: store.version(),
+            view_start_ms: view_start.timestamp_millis(),
+            view_bucket_size,
+            num_visible_buckets,
+        };
+        if self.key != Some(key) {
+            self.key = Some(key);
+            self.counts.clear();
+        }
+        let entry = self
+            .counts
+            .entry(marker.name.clone())
+            .or_insert_with(|| (Arc::clone(&marker.indices), Vec::new()));
+        if !Arc::ptr_eq(&entry.0, &marker.indices) || entry.1.len() != num_visible_buckets {
+            entry.0 = Arc::clone(&marker.indices);
+            entry.1 = Histogram::marker_bucket_counts(
+                store,
+                marker,
+                view_start,
+                view_bucket_size,
+                num_visible_buckets,
+            );
+        }
+        &entry.1
+    }
 }
 
 impl HistogramCache {
@@ -128,6 +318,9 @@ impl HistogramCache {
             pending_key: None,
             data: None,
             zoom: HistogramZoomState::default(),
+            ratio_marker: None,
+            anomaly_threshold: None,
+            marker_counts: MarkerCounts::default(),
         }
     }
 
@@ -173,6 +366,7 @@ impl HistogramCache {
             result_tx: self.result_tx.clone(),
             key: cache_key.clone(),
             color_by_ml_score: cache_key.color_by_ml_score,
+            min_anomaly_score: cache_key.min_anomaly_score,
         };
 
         worker.send_request(request);
@@ -196,6 +390,7 @@ impl Histogram {
         filter_state: &mut FilterState,
         worker: &HistogramWorkerHandle,
         color_by_ml_score: bool,
+        show_previous_run: bool,
     ) -> Option<HistogramClickEvent> {
         profiling::scope!("Histogram::render");
 
@@ -203,7 +398,7 @@ impl Histogram {
             return None;
         }
         if filtered_indices.is_empty() {
-            ui.label("No logs match the current filter");
+            ui.label(tr!("No logs match the current filter"));
             return None;
         }
 
@@ -227,53 +422,142 @@ impl Histogram {
             case_sensitive: indices_case,
             zoom_range_ms,
             color_by_ml_score,
+            min_anomaly_score: cache.anomaly_threshold,
         };
 
         // Poll for any completed results
         cache.poll_results();
 
         if !cache.is_valid(&cache_key) {
-            if cache.is_pending(&cache_key) {
-                ui.ctx().request_repaint(); // Keep polling
-            } else {
-                // Request new computation
+            // A pending computation wakes the UI itself once the worker replies
+            if !cache.is_pending(&cache_key) {
                 cache.request_computation(worker, store, filtered_indices, &cache_key, zoom_range);
             }
         }
 
         let is_recalculating = !cache.is_valid(&cache_key);
 
+        // The earlier run counts all its lines, so it is only comparable to
+        // bars that do too
+        let previous_run = if show_previous_run
+            && cache_key.search_str.is_empty()
+            && cache_key.exclude_str.is_empty()
+            && cache.anomaly_threshold.is_none()
+        {
+            store.previous_run()
+        } else {
+            None
+        };
+
         if let Some(data) = cache.data.clone() {
             // Cache is valid or stale; render it (spinner overlay shown if stale)
             Self::render_cached(
                 ui,
                 store,
                 &data,
+                filter_state.histogram_height,
                 filtered_indices,
                 selected_line_index,
                 markers,
                 &mut cache.zoom,
+                &mut cache.ratio_marker,
+                &mut cache.anomaly_threshold,
+                &mut cache.marker_counts,
+                previous_run.as_ref(),
                 is_recalculating,
             )
         } else {
             // No stale data available, show loading
             ui.horizontal(|ui| {
                 ui.spinner();
-                ui.label("Computing histogram...");
+                ui.label(tr!("Computing histogram..."));
             });
-            ui.ctx().request_repaint(); // Keep polling
             None
         }
     }
 
+    /// Stand-in for a collapsed histogram: one row with a button to expand it.
+    ///
+    /// Returns `true` once the user expanded it.
+    pub fn render_collapsed(ui: &mut Ui, filter_state: &mut FilterState) -> bool {
+        let mut expanded = false;
+        ui.horizontal(|ui| {
+            if ui
+                .small_button("▶")
+                .on_hover_text(tr!("Show the histogram"))
+                .clicked()
+            {
+                filter_state.histogram_collapsed = false;
+                expanded = true;
+            }
+            ui.weak(tr!("Histogram"));
+        });
+        expanded
+    }
+
+    /// Handle below the histogram: drag to resize, double-click or context
+    /// menu to collapse.
+    ///
+    /// Returns `true` when the size or collapsed state should be saved, i.e.
+    /// not on every frame of a drag.
+    pub fn render_resize_handle(ui: &mut Ui, filter_state: &mut FilterState) -> bool {
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width(), RESIZE_HANDLE_HEIGHT),
+            egui::Sense::click_and_drag(),
+        );
+        let response = response
+            .on_hover_cursor(egui::CursorIcon::ResizeVertical)
+            .on_hover_text(tr!(
+                "Drag to resize the histogram, double-click to collapse it"
+            ));
+        let stroke = if response.hovered() || response.dragged() {
+            ui.visuals().widgets.hovered.fg_stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        ui.painter().hline(rect.x_range(), rect.center().y, stroke);
+
+        if response.dragged() {
+            filter_state.histogram_height =
+                (filter_state.histogram_height + response.drag_delta().y).clamp(
+                    *HISTOGRAM_HEIGHT_RANGE.start(),
+                    *HISTOGRAM_HEIGHT_RANGE.end(),
+                );
+        }
+        let mut save = if response.double_clicked() {
+            filter_state.histogram_collapsed = true;
+            true
+        } else {
+            response.drag_stopped()
+        };
+        response.context_menu(|ui| {
+            if ui.button(tr!("Collapse histogram")).clicked() {
+                filter_state.histogram_collapsed = true;
+                save = true;
+                ui.close();
+            }
+            if ui.button(tr!("Reset height")).clicked() {
+                filter_state.histogram_height = DEFAULT_HISTOGRAM_HEIGHT;
+                save = true;
+                ui.close();
+            }
+        });
+        save
+    }
+
     fn render_cached(
         ui: &mut Ui,
         store: &LogStore,
         data: &HistogramData,
+        bars_height: f32,
         filtered_indices: &[StoreID],
         selected_line_index: Option<StoreID>,
         markers: &[HistogramMarker],
         zoom: &mut HistogramZoomState,
+        ratio_marker: &mut Option<String>,
+        anomaly_threshold: &mut Option<u8>,
+        marker_counts: &mut MarkerCounts,
+        previous_run: Option<&PreviousRun>,
         is_recalculating: bool,
     ) -> Option<HistogramClickEvent> {
         // The data already contains buckets computed for the current view range
@@ -288,9 +572,12 @@ impl Histogram {
         let dark_mode = ui.visuals().dark_mode;
         let bg_color = ui.visuals().extreme_bg_color;
 
+        let bookmark_event = Self::render_bookmark_strip(ui, store, view_start, view_end);
+
         let click_event = Self::render_histogram_bars(
             ui,
             data,
+            bars_height,
             filtered_indices,
             &data.buckets,
             &data.anomaly_buckets,
@@ -301,11 +588,17 @@ impl Histogram {
             dark_mode,
             bg_color,
             zoom,
+            ratio_marker,
+            anomaly_threshold,
+            marker_counts,
+            previous_run,
             view_start,
             view_end,
             is_recalculating,
         );
 
+        Self::draw_time_ruler(ui, view_start, view_end);
+
         Self::render_timeline_labels(
             ui,
             view_start,
@@ -317,7 +610,84 @@ impl Histogram {
             zoom.is_zoomed(),
         );
 
-        click_event
+        bookmark_event.or(click_event)
+    }
+
+    /// Draw a flag above the bars for every bookmark in the visible range.
+    ///
+    /// Hovering lists the names of the flags under the pointer; clicking
+    /// jumps to the closest one. Takes no space when there are no bookmarks.
+    fn render_bookmark_strip(
+        ui: &mut Ui,
+        store: &LogStore,
+        view_start: DateTime<Local>,
+        view_end: DateTime<Local>,
+    ) -> Option<HistogramClickEvent> {
+        profiling::scope!("Histogram::render_bookmark_strip");
+        let bookmarks = store.get_all_bookmarks();
+        if bookmarks.is_empty() {
+            return None;
+        }
+
+        let desired_size = egui::vec2(ui.available_width(), BOOKMARK_STRIP_HEIGHT);
+        let (response, painter) = ui.allocate_painter(desired_size, egui::Sense::click());
+        let rect = response.rect;
+
+        let start_ms = view_start.timestamp_millis();
+        let span_ms = (view_end - view_start).num_milliseconds().max(1) as f64;
+        let mut flags: Vec<(f32, BookmarkData)> = bookmarks
+            .into_iter()
+            .filter(|bookmark| !bookmark.orphaned)
+            .filter_map(|bookmark| {
+                let fraction =
+                    (store.adjusted_timestamp_ms(&bookmark.store_id)? - start_ms) as f64 / span_ms;
+                (0.0..=1.0).contains(&fraction).then(|| {
+                    (
+                        (fraction as f32).mul_add(rect.width(), rect.min.x),
+                        bookmark,
+                    )
+                })
+            })
+            .collect();
+        flags.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (x, _) in &flags {
+            painter.vline(*x, rect.y_range(), (1.0, BOOKMARK_COLOR));
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    egui::pos2(*x, rect.min.y),
+                    egui::pos2(*x + 6.0, rect.min.y + 3.0),
+                    egui::pos2(*x, rect.min.y + 6.0),
+                ],
+                BOOKMARK_COLOR,
+                egui::Stroke::NONE,
+            ));
+        }
+
+        let pointer_x = response.hover_pos()?.x;
+        let near: Vec<&(f32, BookmarkData)> = flags
+            .iter()
+            .filter(|(x, _)| (x - pointer_x).abs() <= BOOKMARK_HIT_DISTANCE)
+            .collect();
+        let closest = near
+            .iter()
+            .min_by(|a, b| (a.0 - pointer_x).abs().total_cmp(&(b.0 - pointer_x).abs()))
+            .map(|(_, bookmark)| bookmark.store_id)?;
+
+        let response = response.on_hover_ui_at_pointer(|ui| {
+            for (_, bookmark) in &near {
+                let name = if bookmark.name.is_empty() {
+                    tr!("(unnamed bookmark)")
+                } else {
+                    bookmark.name.as_str()
+                };
+                ui.colored_label(BOOKMARK_COLOR, format!("🔖 {name}"));
+            }
+            ui.weak(tr!("Click to jump"));
+        });
+        response.clicked().then_some(HistogramClickEvent {
+            line_index: closest,
+        })
     }
 
     fn calculate_selected_x_fraction(
@@ -328,7 +698,7 @@ impl Histogram {
     ) -> Option<f64> {
         let selected_line_index = selected_line_index?;
         let line = store.get_by_id(&selected_line_index)?;
-        let sel_ts = line.timestamp;
+        let sel_ts = line.timestamp();
         let elapsed = (sel_ts - start_time).as_seconds_f64();
         let total = (end_time - start_time).as_seconds_f64();
 
@@ -343,6 +713,7 @@ impl Histogram {
     fn render_histogram_bars(
         ui: &mut Ui,
         data: &HistogramData,
+        bars_height: f32,
         filtered_indices: &[StoreID],
         visible_buckets: &[usize],
         visible_anomaly_buckets: &[AnomalyDistribution],
@@ -353,12 +724,16 @@ impl Histogram {
         dark_mode: bool,
         bg_color: Color32,
         zoom: &mut HistogramZoomState,
+        ratio_marker: &mut Option<String>,
+        anomaly_threshold: &mut Option<u8>,
+        marker_counts: &mut MarkerCounts,
+        previous_run: Option<&PreviousRun>,
         view_start: DateTime<Local>,
         view_end: DateTime<Local>,
         is_recalculating: bool,
     ) -> Option<HistogramClickEvent> {
         profiling::scope!("Histogram::draw_bars");
-        let desired_size = egui::vec2(ui.available_width(), 60.0);
+        let desired_size = egui::vec2(ui.available_width(), bars_height);
         let (response, painter) = ui.allocate_painter(desired_size, egui::Sense::click_and_drag());
         let rect = response.rect;
 
@@ -380,6 +755,15 @@ impl Histogram {
             bar_width,
             dark_mode,
         );
+        if let Some(threshold) = anomaly_threshold {
+            painter.text(
+                rect.left_top() + egui::vec2(4.0, 2.0),
+                egui::Align2::LEFT_TOP,
+                tr!("anomalies ≥ {threshold}", threshold),
+                egui::FontId::proportional(10.0),
+                ui.visuals().weak_text_color(),
+            );
+        }
 
         // Calculate view bucket size for markers
         let view_duration = view_end - view_start;
@@ -387,6 +771,16 @@ impl Histogram {
             view_duration.num_milliseconds() as f64 / 1000.0 / num_visible_buckets.max(1) as f64,
         );
 
+        let ghost = previous_run.map(|run| {
+            let counts = run.resample(
+                view_start.timestamp_millis(),
+                view_bucket_size.as_secs_f64() * 1000.0,
+                num_visible_buckets,
+            );
+            Self::draw_ghost(ui, &painter, rect, &counts, max_count, bar_width, run);
+            counts
+        });
+
         Self::draw_markers(
             &painter,
             rect,
@@ -396,6 +790,32 @@ impl Histogram {
             markers,
             num_visible_buckets,
         );
+
+        // Drop the overlay when its marker went away (filter closed or hidden)
+        if ratio_marker
+            .as_ref()
+            .is_some_and(|name| !markers.iter().any(|m| &m.name == name))
+        {
+            *ratio_marker = None;
+        }
+        let ratios = ratio_marker
+            .as_ref()
+            .and_then(|name| markers.iter().find(|m| &m.name == name))
+            .map(|marker| {
+                let counts = marker_counts.get(
+                    store,
+                    marker,
+                    view_start,
+                    view_bucket_size,
+                    num_visible_buckets,
+                );
+                (marker, bucket_ratios(counts, visible_buckets))
+            });
+        if let Some((marker, ratios)) = &ratios {
+            Self::draw_ratio_line(&painter, rect, ratios, bar_width, marker);
+        }
+        Self::render_context_menu(&response, markers, ratio_marker, anomaly_threshold);
+
         Self::draw_selected_indicator(&painter, rect, selected_x_fraction);
 
         // Handle zoom interactions
@@ -443,6 +863,21 @@ impl Histogram {
             ui.ctx().request_repaint();
         }
 
+        if zoom.drag_start.is_none() && !ui.input(|i| i.modifiers.shift) {
+            Self::render_bucket_tooltip(
+                &response,
+                rect,
+                store,
+                visible_buckets,
+                visible_anomaly_buckets,
+                markers,
+                marker_counts,
+                ghost.as_deref(),
+                view_start,
+                view_bucket_size,
+            );
+        }
+
         Self::handle_marker_hover(
             ui,
             &response,
@@ -576,16 +1011,77 @@ impl Histogram {
             return None;
         }
 
-        // Show zoom hint on hover (only when not already zooming)
+        // Mark the position under the cursor (only when not already zooming)
         if response.hovered() && zoom.drag_start.is_none() {
-            response.clone().on_hover_text_at_pointer(
-                "Scroll to zoom • Shift+drag to select range • Double-click to reset",
-            );
+            if let Some(hover_pos) = response.hover_pos() {
+                painter.vline(
+                    hover_pos.x,
+                    rect.y_range(),
+                    (1.0, ui.visuals().weak_text_color()),
+                );
+            }
         }
 
         None
     }
 
+    /// Describe the bucket under the cursor: its time range, line count,
+    /// the matches of every filter shown in the histogram and the highest
+    /// anomaly score, followed by the zoom hint.
+    #[allow(clippy::too_many_arguments)]
+    fn render_bucket_tooltip(
+        response: &egui::Response,
+        rect: egui::Rect,
+        store: &LogStore,
+        buckets: &[usize],
+        anomaly_buckets: &[AnomalyDistribution],
+        markers: &[HistogramMarker],
+        marker_counts: &mut MarkerCounts,
+        ghost: Option<&[f64]>,
+        view_start: DateTime<Local>,
+        view_bucket_size: Duration,
+    ) {
+        let Some(hover_pos) = response.hover_pos() else {
+            return;
+        };
+        if buckets.is_empty() {
+            return;
+        }
+        let fraction = ((hover_pos.x - rect.min.x) / rect.width()).clamp(0.0, 1.0);
+        let bucket = ((fraction * buckets.len() as f32) as usize).min(buckets.len() - 1);
+        let bucket_start = view_start + view_bucket_size.mul_f64(bucket as f64);
+        let bucket_end = bucket_start + view_bucket_size;
+        let count = buckets[bucket];
+        let max_score = anomaly_buckets.get(bucket).map_or(0.0, |d| d.max_score);
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            ui.label(format!(
+                "{} – {}",
+                bucket_start.format("%Y-%m-%d %H:%M:%S%.3f"),
+                bucket_end.format("%H:%M:%S%.3f")
+            ));
+            ui.label(tr!("{count} lines", count));
+            for marker in markers {
+                let counts =
+                    marker_counts.get(store, marker, view_start, view_bucket_size, buckets.len());
+                let marker_count = counts[bucket];
+                ui.colored_label(marker.color, format!("■ {}: {marker_count}", marker.name));
+            }
+            if let Some(previous) = ghost.and_then(|ghost| ghost.get(bucket)) {
+                ui.weak(tr!(
+                    "Previous run: {count} lines",
+                    count = format!("{previous:.0}")
+                ));
+            }
+            if count > 0 {
+                ui.label(tr!("Max score: {score}", score = format!("{max_score:.0}")));
+            }
+            ui.weak(tr!(
+                "Scroll to zoom • Shift+drag to select range • Double-click to reset"
+            ));
+        });
+    }
+
     /// Handle scroll wheel zoom centered on cursor position
     fn handle_scroll_zoom(
         zoom: &mut HistogramZoomState,
@@ -740,6 +1236,166 @@ impl Histogram {
         }
     }
 
+    /// Outline the earlier run's line counts on the scale of the bars, with its
+    /// file name in the bottom left corner. Counts above the highest bar are
+    /// cut off at the top.
+    fn draw_ghost(
+        ui: &Ui,
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        counts: &[f64],
+        max_count: usize,
+        bar_width: f32,
+        run: &PreviousRun,
+    ) {
+        let color = ui.visuals().text_color().gamma_multiply(0.5);
+        let scale = rect.height() / max_count.max(1) as f32;
+        let mut outline = Vec::with_capacity(counts.len() * 2 + 2);
+        outline.push(egui::pos2(rect.min.x, rect.max.y));
+        for (i, &count) in counts.iter().enumerate() {
+            let y = (rect.max.y - count as f32 * scale).max(rect.min.y);
+            let x = (i as f32).mul_add(bar_width, rect.min.x);
+            outline.push(egui::pos2(x, y));
+            outline.push(egui::pos2(x + bar_width, y));
+        }
+        outline.push(egui::pos2(rect.max.x, rect.max.y));
+        painter.add(egui::Shape::line(outline, egui::Stroke::new(1.0, color)));
+
+        painter.text(
+            rect.left_bottom() + egui::vec2(4.0, -2.0),
+            egui::Align2::LEFT_BOTTOM,
+            tr!("previous run: {file}", file = run.summary.file_name),
+            egui::FontId::proportional(10.0),
+            color,
+        );
+    }
+
+    /// Count the marker's lines per visible bucket, binned like the worker bins bars.
+    fn marker_bucket_counts(
+        store: &LogStore,
+        marker: &HistogramMarker,
+        view_start: DateTime<Local>,
+        view_bucket_size: Duration,
+        num_visible_buckets: usize,
+    ) -> Vec<usize> {
+        profiling::scope!("Histogram::marker_bucket_counts");
+        let mut counts = vec![0usize; num_visible_buckets];
+        let bucket_secs = view_bucket_size.as_secs_f64();
+        if num_visible_buckets == 0 || bucket_secs <= 0.0 {
+            return counts;
+        }
+        let total_secs = bucket_secs * num_visible_buckets as f64;
+        let view_start_ms = view_start.timestamp_millis();
+        for line_idx in marker.indices.iter() {
+            let Some(line) = store.get_by_id(line_idx) else {
+                continue;
+            };
+            let elapsed = (line.timestamp_ms - view_start_ms) as f64 / 1000.0;
+            if elapsed < 0.0 || elapsed > total_secs {
+                continue;
+            }
+            let bucket = ((elapsed / bucket_secs) as usize).min(num_visible_buckets - 1);
+            counts[bucket] += 1;
+        }
+        counts
+    }
+
+    /// Draw the ratio overlay as a polyline scaled to its own maximum.
+    ///
+    /// Buckets without any line of this filter have no ratio and break the line.
+    fn draw_ratio_line(
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        ratios: &[Option<f64>],
+        bar_width: f32,
+        marker: &HistogramMarker,
+    ) {
+        let max_ratio = ratios.iter().flatten().copied().fold(0.0_f64, f64::max);
+        if max_ratio <= 0.0 {
+            return;
+        }
+        let stroke = egui::Stroke::new(2.0, marker.color);
+        let mut segment: Vec<Pos2> = Vec::new();
+        for (i, ratio) in ratios.iter().enumerate() {
+            if let Some(ratio) = ratio {
+                let x = (i as f32 + 0.5).mul_add(bar_width, rect.min.x);
+                let y = ((ratio / max_ratio) as f32).mul_add(-(rect.height() - 2.0), rect.max.y);
+                segment.push(egui::pos2(x, y));
+            } else if !segment.is_empty() {
+                Self::flush_ratio_segment(painter, &mut segment, stroke);
+            }
+        }
+        Self::flush_ratio_segment(painter, &mut segment, stroke);
+
+        painter.text(
+            rect.right_top() + egui::vec2(-4.0, 2.0),
+            egui::Align2::RIGHT_TOP,
+            tr!(
+                "{name} / this: max {ratio}",
+                name = marker.name,
+                ratio = format!("{max_ratio:.3}")
+            ),
+            egui::FontId::proportional(10.0),
+            marker.color,
+        );
+    }
+
+    fn flush_ratio_segment(painter: &egui::Painter, segment: &mut Vec<Pos2>, stroke: egui::Stroke) {
+        match segment.len() {
+            0 => {}
+            1 => {
+                painter.circle_filled(segment[0], 1.5, stroke.color);
+            }
+            _ => {
+                painter.add(egui::Shape::line(std::mem::take(segment), stroke));
+            }
+        }
+        segment.clear();
+    }
+
+    /// Right-click menu for choosing the ratio overlay and the anomaly-only mode
+    fn render_context_menu(
+        response: &egui::Response,
+        markers: &[HistogramMarker],
+        ratio_marker: &mut Option<String>,
+        anomaly_threshold: &mut Option<u8>,
+    ) {
+        response.context_menu(|ui| {
+            ui.label(tr!("Ratio overlay"));
+            ui.separator();
+            if ui.radio(ratio_marker.is_none(), tr!("None")).clicked() {
+                *ratio_marker = None;
+                ui.close();
+            }
+            if markers.is_empty() {
+                ui.weak(tr!("Enable \"Show in histogram\" on another filter\nor highlight to plot it against this one"));
+            }
+            for marker in markers {
+                let selected = ratio_marker.as_deref() == Some(marker.name.as_str());
+                if ui
+                    .radio(selected, tr!("{name} / this filter", name = marker.name))
+                    .clicked()
+                {
+                    *ratio_marker = Some(marker.name.clone());
+                    ui.close();
+                }
+            }
+
+            ui.separator();
+            let mut anomaly_only = anomaly_threshold.is_some();
+            if ui
+                .checkbox(&mut anomaly_only, tr!("Anomalies only"))
+                .on_hover_text(tr!("Count only lines whose anomaly score reaches the threshold"))
+                .changed()
+            {
+                *anomaly_threshold = anomaly_only.then_some(DEFAULT_ANOMALY_THRESHOLD);
+            }
+            if let Some(threshold) = anomaly_threshold {
+                ui.add(egui::Slider::new(threshold, 0..=100).text(tr!("min. score")));
+            }
+        });
+    }
+
     fn draw_markers(
         painter: &egui::Painter,
         rect: egui::Rect,
@@ -752,14 +1408,14 @@ impl Histogram {
         profiling::scope!("Histogram::draw_markers");
         let total_width = rect.width();
         let total_time = num_visible_buckets as u32 * view_bucket_size;
+        let view_start_ms = view_start.timestamp_millis();
 
         for marker in markers {
             for line_idx in marker.indices.iter() {
                 let Some(line) = store.get_by_id(line_idx) else {
                     continue;
                 };
-                let ts = line.timestamp;
-                let elapsed = ts - view_start;
+                let elapsed = chrono::Duration::milliseconds(line.timestamp_ms - view_start_ms);
 
                 // Skip markers outside visible range
                 if elapsed.num_milliseconds() < 0
@@ -799,6 +1455,7 @@ impl Histogram {
 
         let total_width = rect.width();
         let total_time = num_visible_buckets as u32 * view_bucket_size;
+        let view_start_ms = view_start.timestamp_millis();
         let hover_threshold = 3.0; // pixels
 
         let mut closest_match: Option<MarkerMatch> = None;
@@ -808,8 +1465,7 @@ impl Histogram {
                 let Some(line) = store.get_by_id(line_idx) else {
                     continue;
                 };
-                let ts = line.timestamp;
-                let elapsed = ts - view_start;
+                let elapsed = chrono::Duration::milliseconds(line.timestamp_ms - view_start_ms);
 
                 // Skip markers outside visible range
                 if elapsed.num_milliseconds() < 0
@@ -917,10 +1573,75 @@ impl Histogram {
         target_time: DateTime<Local>,
     ) -> Option<StoreID> {
         profiling::scope!("Histogram::find_closest_line_by_time");
-        let pos = store.find_closest_line_position_by_time(filtered_indices, target_time)?;
+        let pos = store
+            .find_closest_line_position_by_time(filtered_indices, target_time.timestamp_millis())?;
         Some(filtered_indices[pos])
     }
 
+    /// Draw a time axis below the bars with adaptive tick spacing.
+    ///
+    /// Tick labels include the date whenever it changes between ticks, and local
+    /// midnights are drawn as emphasized lines so multi-day captures stay readable.
+    fn draw_time_ruler(ui: &mut Ui, view_start: DateTime<Local>, view_end: DateTime<Local>) {
+        profiling::scope!("Histogram::draw_time_ruler");
+        let desired_size = egui::vec2(ui.available_width(), 16.0);
+        let (rect, _) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+
+        let total_secs = (view_end - view_start).as_seconds_f64();
+        if total_secs <= 0.0 || rect.width() <= 0.0 {
+            return;
+        }
+
+        let text_color = ui.visuals().text_color();
+        let weak_color = ui.visuals().weak_text_color();
+        let font = egui::FontId::monospace(10.0);
+
+        let mut prev_date = None;
+        for tick in ruler_ticks(view_start, view_end, rect.width()) {
+            let fraction = (tick - view_start).as_seconds_f64() / total_secs;
+            let x = (fraction as f32).mul_add(rect.width(), rect.min.x);
+            let is_midnight = tick.time() == chrono::NaiveTime::MIN;
+
+            let (tick_height, color) = if is_midnight {
+                (rect.height(), text_color)
+            } else {
+                (4.0, weak_color)
+            };
+            painter.vline(
+                x,
+                egui::Rangef::new(rect.min.y, rect.min.y + tick_height),
+                (1.0, color),
+            );
+
+            let step_secs = total_secs / f64::from(rect.width()) * RULER_MIN_TICK_SPACING_PX;
+            let date = tick.date_naive();
+            let time_format = if step_secs < 1.0 {
+                "%H:%M:%S%.3f"
+            } else if step_secs < 60.0 {
+                "%H:%M:%S"
+            } else {
+                "%H:%M"
+            };
+            let label = if is_midnight {
+                tick.format("%Y-%m-%d").to_string()
+            } else if prev_date == Some(date) {
+                tick.format(time_format).to_string()
+            } else {
+                format!("{} {}", tick.format("%m-%d"), tick.format(time_format))
+            };
+            prev_date = Some(date);
+
+            painter.text(
+                egui::pos2(x + 2.0, rect.min.y + 2.0),
+                egui::Align2::LEFT_TOP,
+                label,
+                font.clone(),
+                color,
+            );
+        }
+    }
+
     fn render_timeline_labels(
         ui: &mut Ui,
         view_start: chrono::DateTime<chrono::Local>,
@@ -945,10 +1666,15 @@ impl Histogram {
         };
 
         ui.horizontal(|ui| {
-            ui.label(format!(
-                "Timeline: {} → {}",
-                view_start.format("%H:%M:%S"),
-                view_end.format("%H:%M:%S")
+            let range_format = if view_start.date_naive() == view_end.date_naive() {
+                "%H:%M:%S"
+            } else {
+                "%Y-%m-%d %H:%M:%S"
+            };
+            ui.label(tr!(
+                "Timeline: {start} → {end}",
+                start = view_start.format(range_format),
+                end = view_end.format(range_format)
             ));
 
             if is_zoomed {
@@ -963,17 +1689,20 @@ impl Histogram {
 
                 ui.colored_label(
                     zoom_color,
-                    format!("🔍 {zoom_level:.1}x (double-click to reset)"),
+                    tr!(
+                        "🔍 {zoom}x (double-click to reset)",
+                        zoom = format!("{zoom_level:.1}")
+                    ),
                 );
             }
 
             if let Some(selected_line_index) = selected_line_index {
                 if let Some(line) = store.get_by_id(&selected_line_index) {
-                    let sel_ts = line.timestamp;
+                    let sel_ts = line.timestamp();
                     ui.separator();
                     ui.colored_label(
                         selected_color,
-                        format!("Selected: {}", sel_ts.format("%H:%M:%S%.3f")),
+                        tr!("Selected: {time}", time = sel_ts.format("%H:%M:%S%.3f")),
                     );
                 }
             }
@@ -16,20 +16,52 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use crate::{
+    config::{ColumnLayout, TimestampFormat, COUNT_COLUMN},
     core::{
+        column_filter::{source_label, SOURCE_COLUMN},
+        correlation_id::correlation_ids,
         log_store::{LogLine, StoreID},
-        LogStore,
+        net_endpoint::{endpoints, Endpoint},
+        session::TemplateVerdict,
+        table_copy::CopyFormat,
+        template_counts::TemplateCounts,
+        ColumnFilter, LogStore,
     },
+    filetype::{pcap::PcapFileType, HasSlug},
     parser::format_time_diff,
-    ui::{filter_highlight::FilterHighlight, tabs::filter_tab::filter_state::FilterState},
+    tr,
+    ui::{
+        filter_highlight::FilterHighlight,
+        tabs::filter_tab::{
+            filter_state::FilterState,
+            message_selection::{MessageSelection, NEWLINE_MARKER},
+        },
+    },
 };
 use chrono::{DateTime, Local};
 use egui::{Color32, RichText, Ui};
 use egui_extras::{Column, TableBuilder};
 
+/// Column names, used as keys of the persisted [`ColumnLayout`]
+const LINE_COLUMN: &str = "Line";
+const TIMESTAMP_COLUMN: &str = "Timestamp";
+const SCORE_COLUMN: &str = "Score";
+const ML_SCORE_COLUMN: &str = "ML Score";
+
+/// Columns that can be hidden; the message column is always shown.
+const HIDEABLE_COLUMNS: [&str; 6] = [
+    SOURCE_COLUMN,
+    LINE_COLUMN,
+    TIMESTAMP_COLUMN,
+    COUNT_COLUMN,
+    SCORE_COLUMN,
+    ML_SCORE_COLUMN,
+];
+
 /// Controls how the timestamp column displays time values.
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub enum TimestampMode {
@@ -52,9 +84,19 @@ pub enum LogTableEvent {
     LineClicked {
         line_index: StoreID,
     },
+    /// User shift-clicked a line to extend the range selection to it.
+    LineShiftClicked {
+        line_index: StoreID,
+    },
     BookmarkToggled {
         line_index: StoreID,
     },
+    /// User wants the first and last line of the range selection bookmarked.
+    BookmarkRange,
+    /// User wants the visible lines of the range selection on the clipboard.
+    CopyRange {
+        format: CopyFormat,
+    },
     /// User requested this line to be the delta-time reference (time zero).
     SetTimeZero {
         line_index: StoreID,
@@ -68,6 +110,52 @@ pub enum LogTableEvent {
     ExplainAttention {
         line_index: StoreID,
     },
+    /// User hid this line from all views.
+    SuppressLine {
+        line_index: StoreID,
+    },
+    /// User hid every line with the same message template as this one.
+    SuppressTemplate {
+        line_index: StoreID,
+    },
+    /// User judged the message template of this line, or forgot the verdict.
+    SetTemplateVerdict {
+        line_index: StoreID,
+        verdict: Option<TemplateVerdict>,
+    },
+    /// User split the line's source into a new segment starting at this line.
+    StartSegment {
+        line_index: StoreID,
+    },
+    /// User wants to go to the other line carrying this correlation ID.
+    JumpToPairedLine {
+        line_index: StoreID,
+        id: String,
+    },
+    /// User wants a filter tab with every line carrying this correlation ID.
+    ShowLinesWithId {
+        id: String,
+    },
+    /// User wants the captured packets of a network endpoint named in this line.
+    ShowMatchingPackets {
+        line_index: StoreID,
+        endpoint: Endpoint,
+    },
+    /// User resized or toggled a column; the layout to store for the format.
+    ColumnLayoutChanged {
+        layout: ColumnLayout,
+    },
+}
+
+/// Fade `color` when `score` is below the user's threshold
+/// (see [`crate::config::GlobalConfig::score_threshold`]).
+#[must_use]
+pub fn dim_below_threshold(color: Color32, score: f64, threshold: f64) -> Color32 {
+    if score < threshold {
+        color.gamma_multiply(0.35)
+    } else {
+        color
+    }
 }
 
 /// Convert anomaly score to color with continuous gradient
@@ -174,6 +262,27 @@ fn blend_colors(base: Color32, overlay: Color32, overlay_weight: f32) -> Color32
     Color32::from_rgb(r, g, b)
 }
 
+/// Replace characters that are invisible in the table with visible symbols.
+///
+/// Control characters become their Unicode "control picture" (`\t` → `␉`,
+/// `\r` → `␍`, ...), non-breaking spaces become `⍽` and trailing whitespace
+/// becomes `·`. Newlines are kept so the caller can still render them as `↵`.
+fn visualize_invisibles(text: &str) -> String {
+    let trailing_start = text.trim_end_matches([' ', '\u{a0}']).len();
+    text.char_indices()
+        .map(|(i, c)| match c {
+            '\n' => '\n',
+            '\u{7f}' => '␡',
+            c if c.is_ascii_control() => {
+                char::from_u32(0x2400 + u32::from(c)).unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+            '\u{a0}' => '⍽',
+            ' ' if i >= trailing_start => '·',
+            c => c,
+        })
+        .collect()
+}
+
 /// Compute the background color for a row based on selection state and bookmark status
 #[allow(clippy::fn_params_excessive_bools)]
 fn compute_row_background_color(
@@ -202,6 +311,52 @@ fn compute_row_background_color(
     }
 }
 
+/// Byte offset of the first occurrence of `word` in `text` that is not part
+/// of a longer alphanumeric token
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    if word.is_empty() {
+        return None;
+    }
+    let is_word_char = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    text.match_indices(word)
+        .map(|(start, _)| start)
+        .find(|&start| {
+            !is_word_char(text[..start].chars().next_back())
+                && !is_word_char(text[start + word.len()..].chars().next())
+        })
+}
+
+/// Recolor the text in `range` of `job`, leaving filter highlights untouched
+fn tint_range(job: &mut egui::text::LayoutJob, range: std::ops::Range<usize>, color: Color32) {
+    let sections = std::mem::take(&mut job.sections);
+    for section in sections {
+        let own = section.byte_range.clone();
+        let inner_start = range.start.clamp(own.start, own.end);
+        let inner_end = range.end.clamp(own.start, own.end);
+        for part in [
+            own.start..inner_start,
+            inner_start..inner_end,
+            inner_end..own.end,
+        ] {
+            if part.is_empty() {
+                continue;
+            }
+            let mut piece = section.clone();
+            if part.start != own.start {
+                piece.leading_space = 0.0;
+            }
+            if part.start == inner_start
+                && part.end == inner_end
+                && piece.format.background == Color32::TRANSPARENT
+            {
+                piece.format.color = color;
+            }
+            piece.byte_range = part;
+            job.sections.push(piece);
+        }
+    }
+}
+
 /// Reusable log table component
 pub struct LogTable;
 
@@ -213,16 +368,30 @@ impl LogTable {
         line_idx: StoreID,
         events: &mut Vec<LogTableEvent>,
         model_is_active: bool,
+        is_in_range: bool,
     ) {
         response.context_menu(|ui| {
-            if ui.button("📑 Toggle Bookmark").clicked() {
+            if ui.button(tr!("📑 Toggle Bookmark")).clicked() {
                 events.push(LogTableEvent::BookmarkToggled {
                     line_index: line_idx,
                 });
                 ui.close();
             }
 
-            if ui.button("🎯 Jump to Line").clicked() {
+            if is_in_range
+                && ui
+                    .button(tr!("📑 Bookmark Range..."))
+                    .on_hover_text(tr!(
+                        "Bookmark the first and last line of the shift-click selection \
+                         under one name"
+                    ))
+                    .clicked()
+            {
+                events.push(LogTableEvent::BookmarkRange);
+                ui.close();
+            }
+
+            if ui.button(tr!("🎯 Jump to Line")).clicked() {
                 events.push(LogTableEvent::LineClicked {
                     line_index: line_idx,
                 });
@@ -238,35 +407,212 @@ impl LogTable {
             store.render_typed_context_menu_items(&line_idx, ui);
 
             // Delta time zero marker
-            if ui.button("⏱ Set as time zero").clicked() {
+            if ui.button(tr!("⏱ Set as time zero")).clicked() {
                 events.push(LogTableEvent::SetTimeZero {
                     line_index: line_idx,
                 });
                 ui.close();
             }
 
+            if ui
+                .button(tr!("✂ Start segment here"))
+                .on_hover_text(tr!(
+                    "Split this file into a new segment, e.g. at a reboot; \
+                     segments are listed under File → Sources"
+                ))
+                .clicked()
+            {
+                events.push(LogTableEvent::StartSegment {
+                    line_index: line_idx,
+                });
+                ui.close();
+            }
+
             ui.separator();
 
-            if ui.button("📋 Copy Message").clicked() {
+            if let Some(timestamp) = store.adjusted_timestamp(&line_idx) {
+                ui.menu_button(tr!("📋 Copy Timestamp"), |ui| {
+                    for format in TimestampFormat::ALL {
+                        let value = format.format(timestamp);
+                        if ui.button(format!("{}: {value}", format.label())).clicked() {
+                            ui.ctx().copy_text(value);
+                            ui.close();
+                        }
+                    }
+                });
+            }
+
+            if let Some(selection) = MessageSelection::load(ui.ctx())
+                .filter(|s| s.line() == line_idx && !s.text().is_empty())
+            {
+                if ui.button(tr!("📋 Copy Selection")).clicked() {
+                    ui.ctx().copy_text(selection.text().to_string());
+                    ui.close();
+                }
+            }
+
+            if ui.button(tr!("📋 Copy Message")).clicked() {
                 ui.ctx().copy_text(line.message.clone());
                 ui.close();
             }
 
-            if ui.button("📋 Copy Full Line").clicked() {
+            if ui.button(tr!("📋 Copy Full Line")).clicked() {
                 ui.ctx().copy_text(line.raw);
                 ui.close();
             }
 
+            if is_in_range {
+                ui.menu_button(tr!("📋 Copy Selected Lines"), |ui| {
+                    for format in CopyFormat::ALL {
+                        if ui.button(format.label()).clicked() {
+                            events.push(LogTableEvent::CopyRange { format });
+                            ui.close();
+                        }
+                    }
+                });
+            }
+
+            if let Some(link) = store.permalink(&line_idx) {
+                if ui
+                    .button(tr!("🔗 Copy Permalink"))
+                    .on_hover_text(tr!("Copy a link to this line that can be opened with \
+                         View → Go to Permalink… wherever the same file is loaded"))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(link.to_string());
+                    ui.close();
+                }
+            }
+
+            let ids = correlation_ids(&line.message);
+            if !ids.is_empty() {
+                ui.separator();
+                for id in ids {
+                    ui.menu_button(format!("🔀 ID {id}"), |ui| {
+                        if ui
+                            .button(tr!("↔ Jump to Paired Line"))
+                            .on_hover_text(tr!(
+                                "Go to the other line with this ID, if exactly one within a \
+                                 minute has it"
+                            ))
+                            .clicked()
+                        {
+                            events.push(LogTableEvent::JumpToPairedLine {
+                                line_index: line_idx,
+                                id: id.to_string(),
+                            });
+                            ui.close();
+                        }
+                        if ui.button(tr!("🔍 Show All Lines with This ID")).clicked() {
+                            events.push(LogTableEvent::ShowLinesWithId { id: id.to_string() });
+                            ui.close();
+                        }
+                    });
+                }
+            }
+
+            let endpoints = endpoints(&line.message);
+            let pcap = <PcapFileType as HasSlug>::SLUG;
+            if !endpoints.is_empty()
+                && store.source_filetype_slug(line_idx.source_id()) != Some(pcap)
+                && !store.sources_with_filetype(pcap).is_empty()
+            {
+                ui.separator();
+                for endpoint in endpoints {
+                    if ui
+                        .button(tr!("📡 Show Packets of {endpoint}", endpoint))
+                        .on_hover_text(tr!(
+                            "Open a tab with the captured packets from or to this endpoint \
+                             and select the one closest in time to this line"
+                        ))
+                        .clicked()
+                    {
+                        events.push(LogTableEvent::ShowMatchingPackets {
+                            line_index: line_idx,
+                            endpoint,
+                        });
+                        ui.close();
+                    }
+                }
+            }
+
+            ui.separator();
+
+            if ui
+                .button(tr!("🚫 Suppress Line"))
+                .on_hover_text(tr!("Hide this line from all tabs and the histogram"))
+                .clicked()
+            {
+                events.push(LogTableEvent::SuppressLine {
+                    line_index: line_idx,
+                });
+                ui.close();
+            }
+
+            if ui
+                .button(tr!("🚫 Suppress Similar Lines"))
+                .on_hover_text(tr!("Hide every line with the same message template"))
+                .clicked()
+            {
+                events.push(LogTableEvent::SuppressTemplate {
+                    line_index: line_idx,
+                });
+                ui.close();
+            }
+
+            let mut set_verdict = |verdict| {
+                events.push(LogTableEvent::SetTemplateVerdict {
+                    line_index: line_idx,
+                    verdict,
+                });
+            };
+            if store.template_verdict(&line_idx).is_some() {
+                if ui
+                    .button(tr!("↺ Score Similar Lines Normally"))
+                    .on_hover_text(tr!(
+                        "Forget the verdict on this message template and use the \
+                         anomaly scorer's score again"
+                    ))
+                    .clicked()
+                {
+                    set_verdict(None);
+                    ui.close();
+                }
+            } else {
+                if ui
+                    .button(tr!("👌 Mark Similar Lines as Expected"))
+                    .on_hover_text(tr!(
+                        "Score every line with this message template as 0 from now on; \
+                         saved with the session"
+                    ))
+                    .clicked()
+                {
+                    set_verdict(Some(TemplateVerdict::Expected));
+                    ui.close();
+                }
+                if ui
+                    .button(tr!("📌 Mark Similar Lines as Important"))
+                    .on_hover_text(tr!(
+                        "Score every line with this message template as 100 from now on, \
+                         however often it occurs; saved with the session"
+                    ))
+                    .clicked()
+                {
+                    set_verdict(Some(TemplateVerdict::Important));
+                    ui.close();
+                }
+            }
+
             if model_is_active {
                 ui.separator();
-                if ui.button("✅ Mark as Benign").clicked() {
+                if ui.button(tr!("✅ Mark as Benign")).clicked() {
                     events.push(LogTableEvent::ClassifyLine {
                         line_index: line_idx,
                         label: crate::anomaly::sidecar_client::SampleLabel::Benign,
                     });
                     ui.close();
                 }
-                if ui.button("🚨 Mark as Anomalous").clicked() {
+                if ui.button(tr!("🚨 Mark as Anomalous")).clicked() {
                     events.push(LogTableEvent::ClassifyLine {
                         line_index: line_idx,
                         label: crate::anomaly::sidecar_client::SampleLabel::Anomalous,
@@ -274,7 +620,7 @@ impl LogTable {
                     ui.close();
                 }
                 if line.sidecar_scored {
-                    if ui.button("🔍 Show Attention").clicked() {
+                    if ui.button(tr!("🔍 Show Attention")).clicked() {
                         events.push(LogTableEvent::ExplainAttention {
                             line_index: line_idx,
                         });
@@ -293,8 +639,12 @@ pub struct ColumnWidths {
     pub line: f32,
     pub timestamp: f32,
     pub message: f32,
+    pub count: f32,
     pub score: f32,
     pub ml_score: f32,
+    /// Set while the user is resizing columns, cleared once the new widths
+    /// were written to the column layout.
+    pub resized: bool,
 }
 
 impl Default for ColumnWidths {
@@ -304,12 +654,25 @@ impl Default for ColumnWidths {
             line: 60.0,
             timestamp: 175.0,
             message: 0.0, // Will be calculated
+            count: 70.0,
             score: 70.0,
             ml_score: 90.0,
+            resized: false,
         }
     }
 }
 
+impl ColumnWidths {
+    /// Widths of the user-resizable columns (the message column fills the rest)
+    const fn resizable_widths(&self) -> [(&'static str, f32); 3] {
+        [
+            (SOURCE_COLUMN, self.source.round()),
+            (LINE_COLUMN, self.line.round()),
+            (TIMESTAMP_COLUMN, self.timestamp.round()),
+        ]
+    }
+}
+
 impl LogTable {
     /// Render a table of log lines
     ///
@@ -320,13 +683,19 @@ impl LogTable {
         store: &Arc<LogStore>,
         filter: &mut FilterState,
         selected_line_index: Option<StoreID>,
+        selected_range: Option<(StoreID, StoreID)>,
         bookmarked_lines: &std::collections::HashMap<StoreID, String>,
         scroll_to_row: Option<usize>,
         closest_row_index: Option<usize>,
         all_filter_highlights: &[FilterHighlight],
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
         model_is_active: bool,
+        layout: &ColumnLayout,
+        layout_key: &str,
+        timestamp_format: TimestampFormat,
+        template_counts: Option<&TemplateCounts>,
     ) -> Vec<LogTableEvent> {
         profiling::scope!("LogTable::render");
 
@@ -340,43 +709,167 @@ impl LogTable {
 
         let available_width = ui.available_width();
         let ctx = ui.ctx().clone();
-        egui::ScrollArea::horizontal()
+        let widths_before = filter.column_widths.resizable_widths();
+        let row_count = filtered_indices.len();
+        // A pending jump to the end of follow mode takes over from the selection
+        let scroll_to_row = if filter.follow && std::mem::take(&mut filter.follow_jump_pending) {
+            row_count
+                .checked_sub(1)
+                .map(|last| (last, egui::Align::BOTTOM))
+        } else {
+            scroll_to_row.map(|row| (row, egui::Align::Center))
+        };
+        let mut vertical_scroll = None;
+        let table_area = egui::ScrollArea::horizontal()
             .id_salt(format!("filtered_scroll_{filter_id}"))
             .auto_shrink([false, false])
             .show(ui, |ui| {
                 profiling::scope!("filtered_table");
                 ui.set_min_width(available_width);
+                if filter.compact {
+                    Self::apply_compact_style(ui.style_mut());
+                }
+                let row_height = Self::row_height(ui, filter.compact);
+
+                let table = Self::create_table(
+                    ui,
+                    scroll_to_row,
+                    filter.follow,
+                    &filter.column_widths,
+                    layout,
+                    layout_key,
+                );
 
-                let table = Self::create_table(ui, scroll_to_row, &filter.column_widths);
-
-                Self::render_table_with_header(
+                vertical_scroll = Some(Self::render_table_with_header(
                     table,
                     &ctx,
                     store,
                     &filtered_indices,
                     selected_line_index,
+                    selected_range,
                     bookmarked_lines,
                     closest_row_index,
                     all_filter_highlights,
                     &mut events,
                     dark_mode,
                     &mut filter.column_widths,
+                    &mut filter.rule.search.column_filter,
                     filter.timestamp_mode,
+                    timestamp_format,
+                    filter.show_invisibles,
                     color_by_ml_score,
                     grey_rare_ml_lines,
+                    score_threshold,
                     model_is_active,
-                );
+                    layout,
+                    row_height,
+                    filter.compact,
+                    template_counts,
+                ));
             });
 
+        if filter.follow {
+            if let Some(scroll) = vertical_scroll {
+                Self::update_follow(ui, filter, &scroll, row_count, table_area.inner_rect);
+            }
+        }
+
+        // Persist resized widths once the drag is over. Only the table that
+        // was resized writes, so several tabs of one format don't fight.
+        let widths = &mut filter.column_widths;
+        if widths.resizable_widths() != widths_before {
+            widths.resized = true;
+        }
+        if widths.resized && !ui.input(|i| i.pointer.any_down()) {
+            widths.resized = false;
+            let mut resized = layout.clone();
+            for (column, width) in widths.resizable_widths() {
+                if layout.is_visible(column) && width > 0.0 {
+                    resized.set_width(column, width);
+                }
+            }
+            if resized != *layout {
+                events.push(LogTableEvent::ColumnLayoutChanged { layout: resized });
+            }
+        }
+
         events
     }
 
     const MIN_MESSAGE_WIDTH: f32 = 100.0;
+    /// Row height of the default (comfortable) display mode
+    const COMFORTABLE_ROW_HEIGHT: f32 = 18.0;
+    /// Font size factor of compact rows
+    const COMPACT_FONT_SCALE: f32 = 0.85;
+
+    /// Pause follow mode when the user scrolled away from the bottom, resume
+    /// it once they are back there, and offer a jump to the end while paused.
+    fn update_follow(
+        ui: &mut Ui,
+        filter: &mut FilterState,
+        scroll: &egui::scroll_area::ScrollAreaOutput<()>,
+        row_count: usize,
+        table_rect: egui::Rect,
+    ) {
+        let at_bottom =
+            scroll.state.offset.y + scroll.inner_rect.height() >= scroll.content_size.y - 1.0;
+        match (filter.follow_paused_at, at_bottom) {
+            (None, false) => filter.follow_paused_at = Some(row_count),
+            (Some(_), true) => filter.follow_paused_at = None,
+            (None, true) | (Some(_), false) => {}
+        }
+
+        let new_lines = filter
+            .follow_paused_at
+            .map_or(0, |paused_at| row_count.saturating_sub(paused_at));
+        if new_lines == 0 {
+            return;
+        }
+        let pill = egui::Rect::from_center_size(
+            egui::pos2(table_rect.center().x, table_rect.bottom() - 24.0),
+            egui::vec2(140.0, 24.0),
+        );
+        let label = if new_lines == 1 {
+            tr!("1 new line ↓").to_string()
+        } else {
+            tr!("{count} new lines ↓", count = new_lines)
+        };
+        if ui
+            .put(pill, egui::Button::new(label).corner_radius(12.0))
+            .on_hover_text(tr!("Jump to the newest line and resume following"))
+            .clicked()
+        {
+            filter.follow_jump_pending = true;
+        }
+    }
+
+    /// Shrink fonts and spacing for compact rows
+    fn apply_compact_style(style: &mut egui::Style) {
+        for font in style.text_styles.values_mut() {
+            font.size *= Self::COMPACT_FONT_SCALE;
+        }
+        style.spacing.item_spacing.y = 1.0;
+        style.spacing.interact_size.y = 0.0;
+    }
+
+    /// Row height adapted to the current font, one pixel of spacing in compact mode
+    fn row_height(ui: &Ui, compact: bool) -> f32 {
+        if compact {
+            ui.text_style_height(&egui::TextStyle::Body)
+                .max(ui.text_style_height(&egui::TextStyle::Monospace))
+                + 1.0
+        } else {
+            Self::COMFORTABLE_ROW_HEIGHT
+        }
+    }
 
     fn create_table<'a>(
         ui: &'a mut Ui,
-        scroll_to_row: Option<usize>,
+        scroll_to_row: Option<(usize, egui::Align)>,
+        follow: bool,
         column_widths: &ColumnWidths,
+        layout: &ColumnLayout,
+        layout_key: &str,
     ) -> TableBuilder<'a> {
         let available_height = ui.available_height();
         let available_width = ui.available_width();
@@ -384,14 +877,31 @@ impl LogTable {
         let body_height = available_height - header_height - 1.0;
 
         // Calculate message column width: fill space not taken by other fixed columns
-        let other_cols_width = column_widths.source
-            + column_widths.line
-            + column_widths.timestamp
-            + column_widths.score
-            + column_widths.ml_score;
+        let other_cols_width: f32 = [
+            (SOURCE_COLUMN, column_widths.source),
+            (LINE_COLUMN, column_widths.line),
+            (TIMESTAMP_COLUMN, column_widths.timestamp),
+            (COUNT_COLUMN, column_widths.count),
+            (SCORE_COLUMN, column_widths.score),
+            (ML_SCORE_COLUMN, column_widths.ml_score),
+        ]
+        .into_iter()
+        .filter(|(column, _)| layout.is_visible(column))
+        .map(|(_, width)| width)
+        .sum();
         let remainder = (available_width - other_cols_width).max(Self::MIN_MESSAGE_WIDTH);
 
+        let defaults = ColumnWidths::default();
+        let resizable = |column: &str, default: f32| {
+            Column::initial(layout.width(column, default))
+                .resizable(true)
+                .clip(true)
+        };
+
+        // A separate table state per format, so switching formats applies that
+        // format's stored widths instead of the previous table's.
         let mut table = TableBuilder::new(ui)
+            .id_salt(("log_table", layout_key))
             .striped(true)
             .resizable(false)
             .sense(egui::Sense::click())
@@ -400,20 +910,34 @@ impl LogTable {
             .drag_to_scroll(false)
             .min_scrolled_height(body_height)
             .max_scroll_height(body_height)
-            .column(Column::initial(120.0).resizable(true).clip(true)) // Source
-            .column(Column::initial(60.0).resizable(true).clip(true)) // Line
-            .column(Column::initial(175.0).resizable(true).clip(true)) // Timestamp
-            .column(
-                Column::initial(remainder)
-                    .at_least(remainder)
-                    .resizable(true)
-                    .clip(true),
-            ) // Message
-            .column(Column::initial(column_widths.score).clip(true)) // Score
-            .column(Column::initial(column_widths.ml_score).clip(true)); // ML Score
-
-        if let Some(row_idx) = scroll_to_row {
-            table = table.scroll_to_row(row_idx, Some(egui::Align::Center));
+            .stick_to_bottom(follow);
+        if layout.is_visible(SOURCE_COLUMN) {
+            table = table.column(resizable(SOURCE_COLUMN, defaults.source));
+        }
+        if layout.is_visible(LINE_COLUMN) {
+            table = table.column(resizable(LINE_COLUMN, defaults.line));
+        }
+        if layout.is_visible(TIMESTAMP_COLUMN) {
+            table = table.column(resizable(TIMESTAMP_COLUMN, defaults.timestamp));
+        }
+        table = table.column(
+            Column::initial(remainder)
+                .at_least(remainder)
+                .resizable(true)
+                .clip(true),
+        ); // Message
+        if layout.is_visible(COUNT_COLUMN) {
+            table = table.column(Column::initial(column_widths.count).clip(true));
+        }
+        if layout.is_visible(SCORE_COLUMN) {
+            table = table.column(Column::initial(column_widths.score).clip(true));
+        }
+        if layout.is_visible(ML_SCORE_COLUMN) {
+            table = table.column(Column::initial(column_widths.ml_score).clip(true));
+        }
+
+        if let Some((row_idx, align)) = scroll_to_row {
+            table = table.scroll_to_row(row_idx, Some(align));
         }
 
         table
@@ -426,20 +950,38 @@ impl LogTable {
         store: &LogStore,
         filtered_indices: &[StoreID],
         selected_line_index: Option<StoreID>,
+        selected_range: Option<(StoreID, StoreID)>,
         bookmarked_lines: &std::collections::HashMap<StoreID, String>,
         closest_row_index: Option<usize>,
         all_filter_highlights: &[FilterHighlight],
         events: &mut Vec<LogTableEvent>,
         dark_mode: bool,
         column_widths: &mut ColumnWidths,
+        column_filter: &mut ColumnFilter,
         timestamp_mode: TimestampMode,
+        timestamp_format: TimestampFormat,
+        show_invisibles: bool,
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
         model_is_active: bool,
-    ) {
+        layout: &ColumnLayout,
+        row_height: f32,
+        compact: bool,
+        template_counts: Option<&TemplateCounts>,
+    ) -> egui::scroll_area::ScrollAreaOutput<()> {
         table
             .header(20.0, |mut header| {
-                Self::render_header(&mut header, column_widths, timestamp_mode);
+                Self::render_header(
+                    &mut header,
+                    store,
+                    column_widths,
+                    column_filter,
+                    timestamp_mode,
+                    timestamp_format,
+                    layout,
+                    events,
+                );
             })
             .body(|body| {
                 profiling::scope!("LogTable::body");
@@ -449,57 +991,195 @@ impl LogTable {
                     store,
                     filtered_indices,
                     selected_line_index,
+                    selected_range,
                     bookmarked_lines,
                     closest_row_index,
                     all_filter_highlights,
                     events,
                     dark_mode,
                     timestamp_mode,
+                    timestamp_format,
+                    show_invisibles,
                     color_by_ml_score,
                     grey_rare_ml_lines,
+                    score_threshold,
                     model_is_active,
+                    layout,
+                    row_height,
+                    compact,
+                    template_counts,
                 );
-            });
+            })
     }
 
     fn render_header(
         header: &mut egui_extras::TableRow,
+        store: &LogStore,
         column_widths: &mut ColumnWidths,
+        column_filter: &mut ColumnFilter,
         timestamp_mode: TimestampMode,
+        timestamp_format: TimestampFormat,
+        visible: &ColumnLayout,
+        events: &mut Vec<LogTableEvent>,
     ) {
-        header.col(|ui| {
-            column_widths.source = ui.available_width();
-            ui.strong("Source");
-        });
-        header.col(|ui| {
-            column_widths.line = ui.available_width();
-            ui.strong("Line");
-        });
-        header.col(|ui| {
-            column_widths.timestamp = ui.available_width();
-            let label = match timestamp_mode {
-                TimestampMode::Absolute => {
-                    let now = Local::now();
-                    let offset = now.offset();
-                    format!("Timestamp (UTC{offset})")
-                }
-                TimestampMode::Delta => "Δ Time".to_string(),
-                TimestampMode::Relative(_) => "⏱ Relative".to_string(),
-            };
-            ui.strong(label);
-        });
+        if visible.is_visible(SOURCE_COLUMN) {
+            header.col(|ui| {
+                column_widths.source = ui.available_width();
+                Self::render_column_filter_menu(ui, store, SOURCE_COLUMN, column_filter);
+            });
+        }
+        if visible.is_visible(LINE_COLUMN) {
+            header.col(|ui| {
+                column_widths.line = ui.available_width();
+                ui.strong(tr!("Line"));
+            });
+        }
+        if visible.is_visible(TIMESTAMP_COLUMN) {
+            header.col(|ui| {
+                column_widths.timestamp = ui.available_width();
+                let label = match timestamp_mode {
+                    TimestampMode::Absolute => match timestamp_format {
+                        TimestampFormat::Local => {
+                            let now = Local::now();
+                            let offset = now.offset();
+                            tr!("Timestamp (UTC{offset})", offset)
+                        }
+                        TimestampFormat::Utc => tr!("Timestamp (UTC)").to_string(),
+                        TimestampFormat::Iso8601 => tr!("Timestamp (ISO-8601)").to_string(),
+                        TimestampFormat::EpochMillis => tr!("Timestamp (epoch ms)").to_string(),
+                    },
+                    TimestampMode::Delta => tr!("Δ Time").to_string(),
+                    TimestampMode::Relative(_) => tr!("⏱ Relative").to_string(),
+                };
+                ui.strong(label);
+            });
+        }
         header.col(|ui| {
             column_widths.message = ui.available_width();
-            ui.strong("Message");
-        });
-        header.col(|ui| {
-            column_widths.score = ui.available_width();
-            ui.strong("Score");
-        });
-        header.col(|ui| {
-            column_widths.ml_score = ui.available_width();
-            ui.strong("ML Score");
+            ui.strong(tr!("Message"));
+            for column in store.facet_columns() {
+                // The source filter moves here while its own column is hidden
+                let in_own_column = column == SOURCE_COLUMN && visible.is_visible(SOURCE_COLUMN);
+                if !in_own_column {
+                    Self::render_column_filter_menu(ui, store, column, column_filter);
+                }
+            }
+            if let Some(layout) = Self::render_column_visibility_menu(ui, visible) {
+                events.push(LogTableEvent::ColumnLayoutChanged { layout });
+            }
         });
+        if visible.is_visible(COUNT_COLUMN) {
+            header.col(|ui| {
+                column_widths.count = ui.available_width();
+                ui.strong(tr!("Count")).on_hover_text(tr!(
+                    "How many lines of all sources share the line's message template"
+                ));
+            });
+        }
+        if visible.is_visible(SCORE_COLUMN) {
+            header.col(|ui| {
+                column_widths.score = ui.available_width();
+                ui.strong(tr!("Score"));
+            });
+        }
+        if visible.is_visible(ML_SCORE_COLUMN) {
+            header.col(|ui| {
+                column_widths.ml_score = ui.available_width();
+                ui.strong(tr!("ML Score"));
+            });
+        }
+    }
+
+    /// Dropdown for showing/hiding columns. The choice is stored per log format.
+    ///
+    /// Returns the changed layout if the user toggled a column.
+    fn render_column_visibility_menu(ui: &mut Ui, layout: &ColumnLayout) -> Option<ColumnLayout> {
+        let mut changed = None;
+        ui.menu_button(RichText::new("☰").strong(), |ui| {
+            for column in HIDEABLE_COLUMNS {
+                let mut shown = layout.is_visible(column);
+                if ui.checkbox(&mut shown, column).changed() {
+                    changed
+                        .get_or_insert_with(|| layout.clone())
+                        .set_visible(column, shown);
+                }
+            }
+            ui.separator();
+            if ui.button(tr!("Show All")).clicked() {
+                changed.get_or_insert_with(|| layout.clone()).show_all();
+                ui.close();
+            }
+            ui.separator();
+            let mut color_components = layout.color_components;
+            if ui
+                .checkbox(&mut color_components, tr!("Color by Component"))
+                .on_hover_text(tr!(
+                    "Give every tag, APID or process its own text color, so runs of lines \
+                     from one component stand out"
+                ))
+                .changed()
+            {
+                changed
+                    .get_or_insert_with(|| layout.clone())
+                    .color_components = color_components;
+            }
+        })
+        .response
+        .on_hover_text(tr!("Show or hide columns (remembered per log format)"));
+        changed
+    }
+
+    /// Excel-style dropdown listing the observed values of a discrete column
+    /// with their line counts and a visibility checkbox each.
+    fn render_column_filter_menu(
+        ui: &mut Ui,
+        store: &LogStore,
+        column: &str,
+        column_filter: &mut ColumnFilter,
+    ) {
+        let label = RichText::new(format!("{column} ⏷")).strong();
+        let label = if column_filter.is_active(column) {
+            label.color(ui.visuals().warn_fg_color)
+        } else {
+            label
+        };
+        ui.menu_button(label, |ui| {
+            let values = store.facet_values(column);
+            ui.horizontal(|ui| {
+                if ui.small_button(tr!("All")).clicked() {
+                    column_filter.show_all(column);
+                }
+                if ui.small_button(tr!("None")).clicked() {
+                    column_filter.hide_all(column, values.iter().map(|(v, _)| v.as_str()));
+                }
+            });
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for (value, count) in &values {
+                        let mut visible = !column_filter.is_hidden(column, value);
+                        let text = if value.is_empty() {
+                            tr!("(empty) ({count})", count)
+                        } else if column == SOURCE_COLUMN {
+                            format!("{} ({count})", source_label(value))
+                        } else {
+                            format!("{value} ({count})")
+                        };
+                        let checkbox = ui.checkbox(&mut visible, text);
+                        let checkbox = if column == SOURCE_COLUMN {
+                            checkbox.on_hover_text(value)
+                        } else {
+                            checkbox
+                        };
+                        if checkbox.changed() {
+                            column_filter.set_hidden(column, value, !visible);
+                        }
+                    }
+                });
+        })
+        .response
+        .on_hover_text(tr!("Filter by {column}", column));
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -509,15 +1189,23 @@ impl LogTable {
         store: &LogStore,
         filtered_indices: &[StoreID],
         selected_line_index: Option<StoreID>,
+        selected_range: Option<(StoreID, StoreID)>,
         bookmarked_lines: &std::collections::HashMap<StoreID, String>,
         closest_row_index: Option<usize>,
         all_filter_highlights: &[FilterHighlight],
         events: &mut Vec<LogTableEvent>,
         dark_mode: bool,
         timestamp_mode: TimestampMode,
+        timestamp_format: TimestampFormat,
+        show_invisibles: bool,
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
         model_is_active: bool,
+        layout: &ColumnLayout,
+        row_height: f32,
+        compact: bool,
+        template_counts: Option<&TemplateCounts>,
     ) {
         let visible_lines = filtered_indices.len();
 
@@ -531,7 +1219,7 @@ impl LogTable {
         // Track the previous row's display time for Delta mode (consecutive-line differences).
         let mut prev_row_timestamp: Option<DateTime<Local>> = None;
 
-        body.rows(18.0, visible_lines, |mut row| {
+        body.rows(row_height, visible_lines, |mut row| {
             let row_index = row.index();
 
             // Apply hover state from last frame (before any col() calls)
@@ -544,16 +1232,23 @@ impl LogTable {
                 store,
                 filtered_indices,
                 selected_line_index,
+                selected_range,
                 bookmarked_lines,
                 closest_row_index,
                 all_filter_highlights,
                 events,
                 dark_mode,
                 timestamp_mode,
+                timestamp_format,
+                show_invisibles,
                 prev_row_timestamp,
                 color_by_ml_score,
                 grey_rare_ml_lines,
+                score_threshold,
                 model_is_active,
+                layout,
+                compact,
+                template_counts,
             );
 
             prev_row_timestamp = store.adjusted_timestamp(&filtered_indices[row_index]);
@@ -578,52 +1273,70 @@ impl LogTable {
         store: &LogStore,
         filtered_indices: &[StoreID],
         selected_line_index: Option<StoreID>,
+        selected_range: Option<(StoreID, StoreID)>,
         bookmarked_lines: &std::collections::HashMap<StoreID, String>,
         closest_row_index: Option<usize>,
         all_filter_highlights: &[FilterHighlight],
         events: &mut Vec<LogTableEvent>,
         dark_mode: bool,
         timestamp_mode: TimestampMode,
+        timestamp_format: TimestampFormat,
+        show_invisibles: bool,
         prev_row_timestamp: Option<DateTime<Local>>,
         color_by_ml_score: bool,
         grey_rare_ml_lines: bool,
+        score_threshold: f64,
         model_is_active: bool,
+        layout: &ColumnLayout,
+        compact: bool,
+        template_counts: Option<&TemplateCounts>,
     ) -> Option<LogTableEvent> {
         let row_index = row.index();
         let line_idx = filtered_indices[row_index];
 
         // Handle stale indices gracefully (can happen briefly after source removal)
         let Some(line) = store.get_by_id(&line_idx) else {
-            // Render empty placeholder row
-            row.col(|_| {});
-            row.col(|_| {});
-            row.col(|_| {});
-            row.col(|_| {});
-            row.col(|_| {}); // Score column
-            row.col(|_| {}); // ML Score column
+            // Render empty placeholder row (message column + visible optional columns)
+            let hidden = HIDEABLE_COLUMNS
+                .iter()
+                .filter(|column| !layout.is_visible(column))
+                .count();
+            for _ in 0..=HIDEABLE_COLUMNS.len() - hidden {
+                row.col(|_| {});
+            }
             return None;
         };
 
         let is_selected = selected_line_index.as_ref() == Some(&line_idx);
+        let is_in_range = selected_range.is_some_and(|(first, last)| {
+            line_idx.cmp(&first, store).is_ge() && line_idx.cmp(&last, store).is_le()
+        });
         let is_bookmarked = bookmarked_lines.contains_key(&line_idx);
         // Check if this is the scrolled-to row when the selected line is not in filtered results
         let is_scrolled_to_closest = !is_selected
             && closest_row_index.is_some_and(|closest_row| closest_row == row_index)
             && selected_line_index.is_some();
-        let color = if color_by_ml_score {
-            if line.sidecar_scored {
-                if grey_rare_ml_lines && line.sidecar_score_is_rare {
-                    score_to_color(0.0, dark_mode)
-                } else {
-                    score_to_color(line.sidecar_anomaly_score, dark_mode)
-                }
+        let line_score = if color_by_ml_score {
+            if line.sidecar_scored && !(grey_rare_ml_lines && line.sidecar_score_is_rare) {
+                line.sidecar_anomaly_score
             } else {
-                score_to_color(0.0, dark_mode)
+                0.0
             }
         } else {
-            score_to_color(line.anomaly_score, dark_mode)
+            line.anomaly_score
         };
-        let source_name = store.get_source_name(&line_idx);
+        let color = dim_below_threshold(
+            score_to_color(line_score, dark_mode),
+            line_score,
+            score_threshold,
+        );
+        let source_name =
+            store
+                .get_source_name(&line_idx)
+                .map(|name| match store.segment_name(&line_idx) {
+                    Some(segment) => format!("{name} › {segment}"),
+                    None => name,
+                });
 
         let column_response = Self::render_all_columns(
             row,
@@ -631,7 +1344,8 @@ impl LogTable {
             &line,
             line_idx,
             is_selected,
-            is_scrolled_to_closest,
+            // Rows of the range selection share the subtle scrolled-to tint
+            is_scrolled_to_closest || is_in_range,
             is_bookmarked,
             color,
             source_name.as_deref(),
@@ -639,20 +1353,39 @@ impl LogTable {
             all_filter_highlights,
             dark_mode,
             timestamp_mode,
+            timestamp_format,
+            show_invisibles,
             prev_row_timestamp,
+            layout,
+            compact,
+            template_counts,
         );
 
         // Row-level interaction handling (union column and row responses)
         let merged = column_response.union(row.response());
         let row_clicked = merged.clicked();
         let row_middle_clicked = merged.middle_clicked();
+        if row_clicked || row_middle_clicked {
+            MessageSelection::clear(&merged.ctx);
+        }
 
-        Self::show_line_context_menu(&merged, store, line_idx, events, model_is_active);
+        Self::show_line_context_menu(
+            &merged,
+            store,
+            line_idx,
+            events,
+            model_is_active,
+            is_in_range,
+        );
 
         if row_middle_clicked {
             Some(LogTableEvent::BookmarkToggled {
                 line_index: line_idx,
             })
+        } else if row_clicked && merged.ctx.input(|i| i.modifiers.shift) {
+            Some(LogTableEvent::LineShiftClicked {
+                line_index: line_idx,
+            })
         } else if row_clicked {
             Some(LogTableEvent::LineClicked {
                 line_index: line_idx,
@@ -678,10 +1411,16 @@ impl LogTable {
         all_filter_highlights: &[FilterHighlight],
         dark_mode: bool,
         timestamp_mode: TimestampMode,
+        timestamp_format: TimestampFormat,
+        show_invisibles: bool,
         prev_row_timestamp: Option<DateTime<Local>>,
+        layout: &ColumnLayout,
+        compact: bool,
+        template_counts: Option<&TemplateCounts>,
     ) -> egui::Response {
-        let responses = [
-            Self::render_source_column(
+        let mut responses = Vec::with_capacity(HIDEABLE_COLUMNS.len() + 1);
+        if layout.is_visible(SOURCE_COLUMN) {
+            responses.push(Self::render_source_column(
                 row,
                 is_selected,
                 is_scrolled_to_closest,
@@ -689,8 +1428,10 @@ impl LogTable {
                 color,
                 source_name,
                 dark_mode,
-            ),
-            Self::render_line_column(
+            ));
+        }
+        if layout.is_visible(LINE_COLUMN) {
+            responses.push(Self::render_line_column(
                 row,
                 line,
                 is_selected,
@@ -701,8 +1442,11 @@ impl LogTable {
                     .get(&line_idx)
                     .map(std::string::String::as_str),
                 dark_mode,
-            ),
-            Self::render_timestamp_column(
+                !compact,
+            ));
+        }
+        if layout.is_visible(TIMESTAMP_COLUMN) {
+            responses.push(Self::render_timestamp_column(
                 row,
                 store,
                 line_idx,
@@ -712,19 +1456,40 @@ impl LogTable {
                 color,
                 dark_mode,
                 timestamp_mode,
+                timestamp_format,
                 prev_row_timestamp,
-            ),
-            Self::render_message_column(
+            ));
+        }
+        let component = if layout.color_components {
+            store.component(&line_idx)
+        } else {
+            None
+        };
+        responses.push(Self::render_message_column(
+            row,
+            line,
+            line_idx,
+            is_selected,
+            is_scrolled_to_closest,
+            is_bookmarked,
+            color,
+            all_filter_highlights,
+            dark_mode,
+            show_invisibles,
+            component.as_deref(),
+        ));
+        if layout.is_visible(COUNT_COLUMN) {
+            responses.push(Self::render_count_column(
                 row,
-                line,
+                template_counts.and_then(|counts| counts.count(&line_idx)),
                 is_selected,
                 is_scrolled_to_closest,
                 is_bookmarked,
-                color,
-                all_filter_highlights,
                 dark_mode,
-            ),
-            Self::render_score_column(
+            ));
+        }
+        if layout.is_visible(SCORE_COLUMN) {
+            responses.push(Self::render_score_column(
                 row,
                 line,
                 is_selected,
@@ -732,21 +1497,23 @@ impl LogTable {
                 is_bookmarked,
                 color,
                 dark_mode,
-            ),
-            Self::render_ml_score_column(
+            ));
+        }
+        if layout.is_visible(ML_SCORE_COLUMN) {
+            responses.push(Self::render_ml_score_column(
                 row,
                 line,
                 is_selected,
                 is_scrolled_to_closest,
                 is_bookmarked,
                 dark_mode,
-            ),
-        ];
+            ));
+        }
 
         responses
             .into_iter()
             .reduce(|a, b| a.union(b))
-            .expect("array is non-empty")
+            .expect("message column always renders")
     }
 
     #[allow(clippy::fn_params_excessive_bools)]
@@ -800,6 +1567,7 @@ impl LogTable {
         color: Color32,
         bookmark_name: Option<&str>,
         dark_mode: bool,
+        show_markers: bool,
     ) -> egui::Response {
         let mut response: Option<egui::Response> = None;
         row.col(|ui| {
@@ -813,8 +1581,13 @@ impl LogTable {
                     .rect_filled(ui.available_rect_before_wrap(), 0.0, bg_color);
             }
 
-            let bookmark_icon = if is_bookmarked { "★ " } else { "" };
-            let line_text = if is_selected {
+            // Selection and bookmarks stay visible through the row background
+            let bookmark_icon = if is_bookmarked && show_markers {
+                "★ "
+            } else {
+                ""
+            };
+            let line_text = if is_selected && show_markers {
                 format!("▶ {}{}", bookmark_icon, line.line_number)
             } else {
                 format!("{}{}", bookmark_icon, line.line_number)
@@ -832,7 +1605,7 @@ impl LogTable {
                 if let Some(name) = bookmark_name {
                     label_response
                         .clone()
-                        .on_hover_text(format!("📑 Bookmark: {name}"));
+                        .on_hover_text(tr!("📑 Bookmark: {name}", name));
                 }
             }
             response = Some(label_response);
@@ -853,6 +1626,7 @@ impl LogTable {
         color: Color32,
         dark_mode: bool,
         timestamp_mode: TimestampMode,
+        timestamp_format: TimestampFormat,
         prev_row_timestamp: Option<DateTime<Local>>,
     ) -> egui::Response {
         let mut response: Option<egui::Response> = None;
@@ -874,7 +1648,7 @@ impl LogTable {
             };
 
             let timestamp_str = match timestamp_mode {
-                TimestampMode::Absolute => display_time.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+                TimestampMode::Absolute => timestamp_format.format(display_time),
                 TimestampMode::Delta => prev_row_timestamp.map_or_else(
                     || "0.000s".to_string(),
                     |prev| format_time_diff(display_time.signed_duration_since(prev)),
@@ -897,12 +1671,15 @@ impl LogTable {
     fn render_message_column(
         row: &mut egui_extras::TableRow,
         line: &LogLine,
+        line_idx: StoreID,
         is_selected: bool,
         is_scrolled_to_closest: bool,
         is_bookmarked: bool,
         bg_color: Color32,
         all_filter_highlights: &[FilterHighlight],
         dark_mode: bool,
+        show_invisibles: bool,
+        component: Option<&str>,
     ) -> egui::Response {
         let mut response: Option<egui::Response> = None;
         row.col(|ui| {
@@ -916,12 +1693,26 @@ impl LogTable {
                     .rect_filled(ui.available_rect_before_wrap(), 0.0, bg_color);
             }
 
-            let job = FilterHighlight::highlight_text_with_filters(
-                &line.message.replace('\n', " ↵ "),
+            let text = if show_invisibles {
+                Cow::Owned(visualize_invisibles(&line.message))
+            } else {
+                Cow::Borrowed(line.message.as_str())
+            };
+            let mut job = FilterHighlight::highlight_text_with_filters(
+                &text.replace('\n', NEWLINE_MARKER),
                 bg_color,
                 all_filter_highlights,
                 dark_mode,
             );
+            if let Some(component) = component {
+                if let Some(start) = find_word(&job.text, component) {
+                    tint_range(
+                        &mut job,
+                        start..start + component.len(),
+                        crate::ui::component_color(component, dark_mode),
+                    );
+                }
+            }
 
             // Layout the text to check if it would be clipped
             let available_width = ui.available_width();
@@ -929,7 +1720,7 @@ impl LogTable {
             let text_width = galley.size().x;
             let is_clipped = text_width > available_width || line.message.contains('\n');
 
-            let label_response = ui.add(egui::Label::new(job).selectable(true).extend());
+            let label_response = MessageSelection::show(ui, line_idx, job);
 
             // Only show hover tooltip if text was clipped
             if is_clipped {
@@ -940,6 +1731,41 @@ impl LogTable {
         response.expect("column always renders")
     }
 
+    /// Occurrences of the line's message template; `None` while not yet counted
+    #[allow(clippy::fn_params_excessive_bools)]
+    fn render_count_column(
+        row: &mut egui_extras::TableRow,
+        count: Option<u32>,
+        is_selected: bool,
+        is_scrolled_to_closest: bool,
+        is_bookmarked: bool,
+        dark_mode: bool,
+    ) -> egui::Response {
+        let mut response: Option<egui::Response> = None;
+        row.col(|ui| {
+            if let Some(bg_color) = compute_row_background_color(
+                is_selected,
+                is_scrolled_to_closest,
+                is_bookmarked,
+                dark_mode,
+            ) {
+                ui.painter()
+                    .rect_filled(ui.available_rect_before_wrap(), 0.0, bg_color);
+            }
+
+            let text = match count {
+                // A template seen only once is worth a look
+                Some(1) => RichText::new("1")
+                    .strong()
+                    .color(ui.visuals().warn_fg_color),
+                Some(count) => RichText::new(count.to_string()),
+                None => RichText::new("…").weak(),
+            };
+            response = Some(ui.add(egui::Label::new(text).sense(egui::Sense::click())));
+        });
+        response.expect("column always renders")
+    }
+
     #[allow(clippy::fn_params_excessive_bools)]
     fn render_score_column(
         row: &mut egui_extras::TableRow,
@@ -1012,3 +1838,18 @@ impl LogTable {
         response.expect("column always renders")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visualize_invisibles() {
+        assert_eq!(visualize_invisibles("a\tb\r"), "a␉b␍");
+        assert_eq!(visualize_invisibles("del\u{7f}"), "del␡");
+        assert_eq!(visualize_invisibles("a\u{a0}b"), "a⍽b");
+        assert_eq!(visualize_invisibles("a b  "), "a b··");
+        assert_eq!(visualize_invisibles("a \u{a0} "), "a·⍽·");
+        assert_eq!(visualize_invisibles("one\ntwo"), "one\ntwo");
+    }
+}
@@ -0,0 +1,152 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Drag-to-select text inside a single message cell of the log table.
+//!
+//! egui's own label selection keeps the selected text private, so the log
+//! table tracks the selected character range itself. That lets Ctrl+C copy
+//! just the fragment and feeds it to the primary selection on Linux.
+
+use crate::core::log_store::StoreID;
+use crate::ui::primary_selection::set_primary_selection;
+use egui::text::{CCursor, LayoutJob};
+use egui::text_selection::{visuals::paint_text_selection, CCursorRange};
+use egui::{CursorIcon, Sense, Ui};
+
+/// Marker the message column uses for embedded newlines
+pub const NEWLINE_MARKER: &str = " ↵ ";
+
+/// Character range selected inside the message of one line
+#[derive(Clone, PartialEq)]
+pub struct MessageSelection {
+    line: StoreID,
+    anchor: CCursor,
+    cursor: CCursor,
+    /// The selected text, with newline markers turned back into newlines
+    text: String,
+}
+
+impl MessageSelection {
+    fn storage_id() -> egui::Id {
+        egui::Id::new("log_table_message_selection")
+    }
+
+    /// The current selection, if any.
+    #[must_use]
+    pub fn load(ctx: &egui::Context) -> Option<Self> {
+        ctx.data(|d| d.get_temp(Self::storage_id())).flatten()
+    }
+
+    fn store(ctx: &egui::Context, selection: Option<Self>) {
+        ctx.data_mut(|d| d.insert_temp(Self::storage_id(), selection));
+    }
+
+    /// Drop the selection (e.g. when a row is clicked).
+    pub fn clear(ctx: &egui::Context) {
+        Self::store(ctx, None);
+    }
+
+    /// The line this selection belongs to.
+    #[must_use]
+    pub const fn line(&self) -> StoreID {
+        self.line
+    }
+
+    fn range(&self) -> CCursorRange {
+        CCursorRange::two(self.anchor, self.cursor)
+    }
+
+    /// The selected text.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn update_text(&mut self, displayed: &str) {
+        let [start, end] = self.range().sorted_cursors();
+        self.text = displayed
+            .chars()
+            .skip(start.index)
+            .take(end.index - start.index)
+            .collect::<String>()
+            .replace(NEWLINE_MARKER, "\n");
+    }
+
+    /// Lay out, paint and handle drag selection for one message cell.
+    pub fn show(ui: &mut Ui, line: StoreID, job: LayoutJob) -> egui::Response {
+        let ctx = ui.ctx().clone();
+        let (pos, mut galley, response) = egui::Label::new(job)
+            .selectable(false)
+            .sense(Sense::click_and_drag())
+            .extend()
+            .layout_in_ui(ui);
+
+        let previous = Self::load(&ctx);
+        let mut selection = previous.clone();
+        if response.hovered() || response.dragged() {
+            ctx.set_cursor_icon(CursorIcon::Text);
+        }
+        if response.drag_started() {
+            // Ctrl+C copies the selection until another widget takes the focus
+            response.request_focus();
+            if let Some(origin) = ctx.input(|i| i.pointer.press_origin()) {
+                let anchor = galley.cursor_from_pos(origin - pos);
+                selection = Some(Self {
+                    line,
+                    anchor,
+                    cursor: anchor,
+                    text: String::new(),
+                });
+            }
+        }
+        if response.dragged() {
+            if let (Some(pointer), Some(current)) = (
+                response.interact_pointer_pos(),
+                selection.as_mut().filter(|s| s.line == line),
+            ) {
+                current.cursor = galley.cursor_from_pos(pointer - pos);
+                current.update_text(galley.text());
+            }
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            selection = None;
+        }
+
+        if let Some(current) = selection
+            .as_ref()
+            .filter(|s| s.line == line && !s.text.is_empty())
+        {
+            if response.drag_stopped() {
+                set_primary_selection(&current.text);
+            }
+            // A focused text field handles Ctrl+C itself
+            let owns_copy = response.has_focus()
+                || (response.hovered() && ctx.memory(|m| m.focused().is_none()));
+            if owns_copy && ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy))) {
+                ctx.copy_text(current.text.clone());
+            }
+            paint_text_selection(&mut galley, ui.visuals(), &current.range(), None);
+        }
+        if selection != previous {
+            Self::store(&ctx, selection);
+        }
+
+        ui.painter().galley(pos, galley, ui.visuals().text_color());
+        response
+    }
+}
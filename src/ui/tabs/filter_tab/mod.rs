@@ -16,28 +16,41 @@
 // You should have received a copy of the GNU General Public License
 // along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod detail_panel;
 pub mod filter_bar;
 pub mod filter_state;
 pub mod histogram;
 pub mod log_table;
+pub mod message_selection;
 
+pub use detail_panel::{DetailPanel, DetailPanelEvent};
 pub use filter_bar::{FilterBar, FilterInternalEvent};
 pub use histogram::{Histogram, HistogramMarker};
 pub use log_table::{LogTable, LogTableEvent};
 
-use crate::config::GlobalConfig;
-use crate::core::log_store::StoreID;
+use crate::config::{ColumnLayout, GlobalConfig, COUNT_COLUMN};
+use crate::core::chunk_index::ScanHints;
+use crate::core::correlation_id::{paired_occurrence, PAIR_WINDOW_MS};
+use crate::core::log_store::{LogStore, StoreID};
+use crate::core::redaction::{RedactionRule, Redactor};
+use crate::core::segment::Segment;
+use crate::core::table_copy::{copy_rows, CopiedRow};
 use crate::core::SavedFilter;
 use crate::input::ShortcutAction;
+use crate::tr;
 use crate::ui::filter_highlight::FilterHighlight;
 use crate::ui::session_state::{FilterToHighlightData, SessionState};
 use crate::ui::tabs::filter_tab::filter_state::FilterState;
 use crate::ui::tabs::filter_tab::log_table::TimestampMode;
 use crate::ui::tabs::LogCrabTab;
-use crate::ui::windows::ChangeFilternameWindow;
+use crate::ui::windows::export_redaction::PREVIEW_LINES;
+use crate::ui::windows::{BookmarkRangeWindow, ChangeFilternameWindow, ExportRedactionWindow};
+use chrono::{DateTime, Local};
 use egui::Ui;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Events that can be emitted by the filter view
 #[derive(Debug, Clone)]
@@ -48,6 +61,10 @@ pub enum FilterViewEvent {
     BookmarkToggled {
         store_id: StoreID,
     },
+    /// Extend the range selection to this line
+    SelectionExtended {
+        store_id: StoreID,
+    },
     FilterNameEditRequested,
     FavoriteToggled,
     /// Convert this filter to a highlight
@@ -59,6 +76,8 @@ pub struct FilterView {
     should_focus_search: bool,
     state: FilterState,
     change_filtername_window: Option<ChangeFilternameWindow>,
+    bookmark_range_window: Option<BookmarkRangeWindow>,
+    export_window: Option<ExportRedactionWindow>,
     filter_bar: FilterBar,
     /// Whether the attention panel window is visible.
     show_attention_panel: bool,
@@ -70,6 +89,15 @@ pub struct FilterView {
     attention_pending: bool,
     /// Set when the explain session's WebSocket closes unexpectedly.
     attention_error: Option<String>,
+    /// Include pattern, exclude pattern and case sensitivity as last seen;
+    /// `None` until the first render
+    watched_pattern: Option<(String, String, bool)>,
+    /// When `watched_pattern` last changed; `None` if unchanged since the tab opened
+    pattern_changed_at: Option<Instant>,
+    /// Bumped whenever an input of the hover summary changes
+    summary_revision: u64,
+    /// Hover summary without its elapsed-time line and the revision it was built for
+    summary_cache: RefCell<Option<(u64, String)>>,
 }
 
 impl FilterView {
@@ -78,15 +106,93 @@ impl FilterView {
             should_focus_search: false,
             state,
             change_filtername_window: None,
+            bookmark_range_window: None,
+            export_window: None,
             filter_bar: FilterBar::new(),
             show_attention_panel: false,
             attention_target: None,
             attention_result: None,
             attention_pending: false,
             attention_error: None,
+            watched_pattern: None,
+            pattern_changed_at: None,
+            summary_revision: 0,
+            summary_cache: RefCell::new(None),
         }
     }
 
+    fn pattern_of(state: &FilterState) -> (String, String, bool) {
+        (
+            state.search.search_text.clone(),
+            state.search.exclude_text.clone(),
+            state.search.case_sensitive,
+        )
+    }
+
+    /// Group, pattern, mode and match count lines of the hover summary
+    fn build_summary(&self) -> String {
+        let search = &self.state.search;
+        let mut lines = vec![if search.search_text.is_empty() {
+            tr!("Pattern: (everything)").to_string()
+        } else {
+            tr!("Pattern: {pattern}", pattern = search.search_text)
+        }];
+        if let Some(group) = &self.state.group {
+            lines.insert(0, tr!("Group: {group}", group));
+        }
+        if !search.exclude_text.is_empty() {
+            lines.push(tr!("Excluding: {pattern}", pattern = search.exclude_text));
+        }
+
+        let mut mode = vec![if search.case_sensitive {
+            tr!("case-sensitive")
+        } else {
+            tr!("case-insensitive")
+        }
+        .to_string()];
+        if search.hide_duplicates {
+            mode.push(tr!("duplicates hidden").to_string());
+        }
+        if !search.column_filter.is_empty() {
+            mode.push(tr!("column filters").to_string());
+        }
+        if let Some(scope) = &search.time_scope {
+            mode.push(tr!("within {scope}", scope = scope.label));
+        }
+        lines.push(tr!("Mode: {mode}", mode = mode.join(", ")));
+
+        lines.push(tr!(
+            "Matches: {count}",
+            count = search.get_filtered_indices_cached().len()
+        ));
+        lines.join("\n")
+    }
+
+    /// Lines of all sources carrying `id` within [`PAIR_WINDOW_MS`] of `line`,
+    /// in time order.
+    ///
+    /// Only the storage chunks around the line whose bloom may hold the ID are
+    /// scanned, so this stays cheap on large stores.
+    fn lines_with_id_near(store: &LogStore, line: StoreID, id: &str) -> Vec<StoreID> {
+        let Some(at) = store.adjusted_timestamp_ms(&line) else {
+            return Vec::new();
+        };
+        let window = (at - PAIR_WINDOW_MS, at + PAIR_WINDOW_MS);
+        let hints = ScanHints {
+            time_range: Some(window),
+            literal: Some(id.to_string()),
+        };
+        store
+            .get_matching_ids_within(&hints, |_, raw| raw.contains(id))
+            .into_iter()
+            .filter(|other| {
+                store
+                    .adjusted_timestamp_ms(other)
+                    .is_some_and(|ts| (window.0..=window.1).contains(&ts))
+            })
+            .collect()
+    }
+
     pub const fn focus_search_next_frame(&mut self) {
         self.should_focus_search = true;
     }
@@ -106,9 +212,20 @@ impl FilterView {
 
         let selected_line_index = log_view_state.selected_line_index;
         let mut events = Vec::new();
+        let pattern = Self::pattern_of(&self.state);
+        if let Some(seen) = &self.watched_pattern {
+            if *seen != pattern {
+                self.pattern_changed_at = Some(Instant::now());
+                self.summary_revision += 1;
+                self.watched_pattern = Some(pattern);
+            }
+        } else {
+            self.watched_pattern = Some(pattern);
+        }
         if self.state.search.check_filter_results() {
             // New filter results arrived - invalidate scroll tracking so we re-scroll
             self.state.last_rendered_selection = None;
+            self.summary_revision += 1;
         }
         self.state
             .search
@@ -117,8 +234,11 @@ impl FilterView {
             .search
             .ensure_cache_valid(&log_view_state.store, &log_view_state.filter_worker);
 
-        // Render filter bar
-        let filter_bar_events = {
+        // Render filter bar (the built-in errors view has a fixed search)
+        let filter_bar_events = if self.state.is_errors_view {
+            ui.label(tr!("Lines with an error/fatal log level or an error keyword of the anomaly scorer."));
+            Vec::new()
+        } else {
             profiling::scope!("render_filter_bar");
             self.filter_bar.render(
                 ui,
@@ -152,20 +272,11 @@ impl FilterView {
                     events.push(FilterViewEvent::ConvertToHighlight);
                 }
                 FilterInternalEvent::ExportFiltered => {
-                    if let Some(path) = rfd::FileDialog::new()
-                        .set_title("Export Filtered Results")
-                        .add_filter("Text", &["txt"])
-                        .set_file_name("filtered_results.txt")
-                        .save_file()
-                    {
-                        if let Err(e) =
-                            export_filtered_results(&self.state, &log_view_state.store, &path)
-                        {
-                            tracing::error!("Failed to export filtered results: {e}");
-                        } else {
-                            tracing::info!("Filtered results exported to {}", path.display());
-                        }
-                    }
+                    self.export_window = Some(ExportRedactionWindow::new(
+                        "Export Filtered Results".to_string(),
+                        global_config.redaction_rules.clone(),
+                        export_preview_lines(&self.state, &log_view_state.store, PREVIEW_LINES),
+                    ));
                 }
             }
         }
@@ -190,32 +301,59 @@ impl FilterView {
         };
 
         // Render histogram (using Arc<Vec> for cheap cloning)
-        let hist_event = {
-            profiling::scope!("render_histogram");
-            let indices = self.state.search.get_filtered_indices_cached();
-            Histogram::render(
-                ui,
-                store,
-                &indices,
-                selected_line_index,
-                histogram_markers,
-                &mut self.state,
-                &log_view_state.histogram_worker,
-                global_config.color_by_ml_score,
-            )
-        };
-        if let Some(hist_event) = hist_event {
-            events.push(FilterViewEvent::LineSelected {
-                store_id: hist_event.line_index,
-            });
+        if self.state.histogram_collapsed {
+            if Histogram::render_collapsed(ui, &mut self.state) {
+                log_view_state.modified = true;
+            }
+            ui.separator();
+        } else {
+            let hist_event = {
+                profiling::scope!("render_histogram");
+                let indices = self.state.search.get_filtered_indices_cached();
+                Histogram::render(
+                    ui,
+                    store,
+                    &indices,
+                    selected_line_index,
+                    histogram_markers,
+                    &mut self.state,
+                    &log_view_state.histogram_worker,
+                    global_config.color_by_ml_score,
+                    global_config.show_previous_run,
+                )
+            };
+            if let Some(hist_event) = hist_event {
+                events.push(FilterViewEvent::LineSelected {
+                    store_id: hist_event.line_index,
+                });
+            }
+            if Histogram::render_resize_handle(ui, &mut self.state) {
+                log_view_state.modified = true;
+            }
         }
 
-        ui.separator();
+        // Render detail panel (bottom) before the table so the table fills the rest
+        let detail_event = if self.state.show_detail_panel {
+            DetailPanel::render(ui, store, selected_line_index, self.state.get_id())
+        } else {
+            None
+        };
 
         // Render log table
         let closest_row_index = self.state.closest_row_index;
         let model_is_active = global_config.use_sidecar_scoring
             && global_config.selected_model.is_some();
+        let layout_key = store.column_layout_key();
+        let default_layout = ColumnLayout::default();
+        let layout = global_config
+            .column_layouts
+            .get(layout_key)
+            .unwrap_or(&default_layout);
+        let template_counts = if layout.is_visible(COUNT_COLUMN) {
+            log_view_state.template_counts.request(store, ui.ctx())
+        } else {
+            None
+        };
         let table_events = {
             profiling::scope!("render_log_table");
             LogTable::render(
@@ -223,24 +361,65 @@ impl FilterView {
                 store,
                 &mut self.state,
                 selected_line_index,
+                log_view_state.selection_range(),
                 bookmarked_lines,
                 scroll_to_row,
                 closest_row_index,
                 all_filter_highlights,
                 global_config.color_by_ml_score,
                 global_config.grey_rare_ml_lines,
+                global_config.score_threshold,
                 model_is_active,
+                layout,
+                layout_key,
+                global_config.timestamp_format,
+                template_counts.as_deref(),
             )
         };
-
         // Handle table events
         for event in table_events {
             match event {
                 LogTableEvent::LineClicked { line_index } => {
+                    log_view_state.range_selection = None;
                     events.push(FilterViewEvent::LineSelected {
                         store_id: line_index,
                     });
                 }
+                LogTableEvent::LineShiftClicked { line_index } => {
+                    events.push(FilterViewEvent::SelectionExtended {
+                        store_id: line_index,
+                    });
+                }
+                LogTableEvent::BookmarkRange => {
+                    self.bookmark_range_window = log_view_state
+                        .selection_range()
+                        .map(BookmarkRangeWindow::new);
+                }
+                LogTableEvent::CopyRange { format } => {
+                    if let Some((first, last)) = log_view_state.selection_range() {
+                        let indices = self.state.search.get_filtered_indices_cached();
+                        // Filtered lines are in store order, so the range is one slice
+                        let start = indices.partition_point(|id| id.cmp(&first, store).is_lt());
+                        let end = indices.partition_point(|id| id.cmp(&last, store).is_le());
+                        let rows: Vec<CopiedRow> = indices[start..end.max(start)]
+                            .iter()
+                            .filter_map(|id| {
+                                let line = store.get_by_id(id)?;
+                                Some(CopiedRow {
+                                    timestamp: store
+                                        .adjusted_timestamp(id)
+                                        .map(|ts| global_config.timestamp_format.format(ts))
+                                        .unwrap_or_default(),
+                                    level: store.level(id).unwrap_or_default(),
+                                    source: store.get_source_name(id).unwrap_or_default(),
+                                    message: line.message,
+                                    raw: line.raw,
+                                })
+                            })
+                            .collect();
+                        ui.ctx().copy_text(copy_rows(format, &rows));
+                    }
+                }
                 LogTableEvent::BookmarkToggled { line_index } => {
                     events.push(FilterViewEvent::BookmarkToggled {
                         store_id: line_index,
@@ -255,6 +434,59 @@ impl FilterView {
                         TimestampMode::Relative,
                     );
                 }
+                LogTableEvent::SuppressLine { line_index } => {
+                    store.suppress_line(&line_index);
+                    log_view_state.modified = true;
+                }
+                LogTableEvent::SuppressTemplate { line_index } => {
+                    store.suppress_template_of(&line_index);
+                    log_view_state.modified = true;
+                }
+                LogTableEvent::SetTemplateVerdict {
+                    line_index,
+                    verdict,
+                } => {
+                    store.set_template_verdict(&line_index, verdict);
+                    log_view_state.modified = true;
+                }
+                LogTableEvent::StartSegment { line_index } => {
+                    let source_id = line_index.source_id();
+                    let start_line = line_index.line_index_within_source();
+                    let mut segments = store.get_segments(source_id);
+                    // The lines before the first split form a segment of their own
+                    if segments.is_empty() && start_line > 0 {
+                        segments.push(Segment::new(tr!("Segment {n}", n = 1), 0));
+                    }
+                    let n = segments.len() + 1;
+                    segments.push(Segment::new(tr!("Segment {n}", n), start_line));
+                    store.set_segments(source_id, segments);
+                    log_view_state.modified = true;
+                }
+                LogTableEvent::JumpToPairedLine { line_index, id } => {
+                    let occurrences = Self::lines_with_id_near(store, line_index, &id);
+                    if let Some(paired) = paired_occurrence(&occurrences, line_index) {
+                        events.push(FilterViewEvent::LineSelected { store_id: paired });
+                    } else if let Some(ref sender) = log_view_state.toast_sender {
+                        sender.send(tr!(
+                            "ID {id} is not shared by exactly one other line within a minute",
+                            id
+                        ));
+                    }
+                }
+                LogTableEvent::ShowLinesWithId { id } => {
+                    log_view_state.pending_id_filter = Some(id);
+                }
+                LogTableEvent::ShowMatchingPackets {
+                    line_index,
+                    endpoint,
+                } => {
+                    log_view_state.pending_packet_filter = Some((endpoint, line_index));
+                }
+                LogTableEvent::ColumnLayoutChanged { layout } => {
+                    *global_config = GlobalConfig::update(|c| {
+                        c.column_layouts.insert(layout_key.to_string(), layout);
+                    });
+                }
                 LogTableEvent::ExplainAttention { line_index } => {
                     let source_id = line_index.source_id();
                     // Use the 0-based line index that matches line_id.line_number
@@ -268,7 +500,10 @@ impl FilterView {
                     } else {
                         // Session is closed or was never opened.
                         if let Some(ref sender) = log_view_state.toast_sender {
-                            sender.send("Attention not available: sidecar session is closed".to_string());
+                            sender.send(
+                                tr!("Attention not available: sidecar session is closed")
+                                    .to_string(),
+                            );
                         }
                     }
                 }
@@ -308,15 +543,17 @@ impl FilterView {
                                     "Classification submitted: {label} for line {classified_line_number}"
                                 );
                                 if let Some(ref sender) = toast_sender {
-                                    sender.send_success(format!(
-                                        "Submitted as {label} sample (line {classified_line_number})"
+                                    sender.send_success(tr!(
+                                        "Submitted as {label} sample (line {line})",
+                                        label,
+                                        line = classified_line_number
                                     ));
                                 }
                             }
                             Err(e) => {
                                 tracing::error!("Classification upload failed: {e}");
                                 if let Some(ref sender) = toast_sender {
-                                    sender.send(format!("Classification upload failed: {e}"));
+                                    sender.send(tr!("Classification upload failed: {e}", e));
                                 }
                             }
                         }
@@ -339,7 +576,7 @@ impl FilterView {
                     }
                     ExplainPollStatus::Dead => {
                         self.attention_pending = false;
-                        self.attention_error = Some("Sidecar connection lost".to_string());
+                        self.attention_error = Some(tr!("Sidecar connection lost").to_string());
                     }
                     _ => {}
                 }
@@ -357,6 +594,12 @@ impl FilterView {
                 self.attention_pending,                self.attention_error.as_deref(),            );
         }
 
+        if let Some(DetailPanelEvent::FilterOnValue { value }) = detail_event {
+            log_view_state.add_to_filter_history(self.state.search.search_text.clone());
+            self.state.search.search_text = fancy_regex::escape(&value).into_owned();
+            log_view_state.modified = true;
+        }
+
         events
     }
 
@@ -392,6 +635,9 @@ impl FilterView {
                 FilterViewEvent::LineSelected { store_id } => {
                     data_state.selected_line_index = Some(store_id);
                 }
+                FilterViewEvent::SelectionExtended { store_id } => {
+                    data_state.extend_selection_to(store_id);
+                }
                 FilterViewEvent::BookmarkToggled { store_id } => {
                     data_state.selected_line_index = Some(store_id);
                     data_state.toggle_bookmark(store_id);
@@ -407,7 +653,7 @@ impl FilterView {
                     let case_sensitive = self.state.search.case_sensitive;
 
                     // Save global config
-                    match GlobalConfig::update(|c| {
+                    *global_config = GlobalConfig::update(|c| {
                         let pos = c.favorite_filters.iter().position(|f| {
                             f.search_text == search_text && f.case_sensitive == case_sensitive
                         });
@@ -421,10 +667,7 @@ impl FilterView {
                             ));
                             tracing::info!("Added favorite: '{search_text}'");
                         }
-                    }) {
-                        Ok(updated) => *global_config = updated,
-                        Err(e) => tracing::error!("Failed to save config: {e}"),
-                    }
+                    });
                 }
                 FilterViewEvent::ConvertToHighlight => {
                     // Request conversion to highlight - LogView will handle it and close this tab
@@ -458,6 +701,67 @@ impl FilterView {
                 }
             }
         }
+
+        if let Some(ref mut window) = self.bookmark_range_window {
+            match window.render(ui.ctx()) {
+                Ok(Some(name)) => {
+                    let (first, last) = window.range;
+                    self.bookmark_range_window = None;
+                    let name = if name.is_empty() {
+                        tr!("Range").to_string()
+                    } else {
+                        name
+                    };
+                    data_state.add_named_bookmark(first, tr!("{name} (start)", name));
+                    data_state.add_named_bookmark(last, tr!("{name} (end)", name));
+                }
+                Ok(None) => {}
+                Err(()) => self.bookmark_range_window = None,
+            }
+        }
+
+        // Handle export redaction dialog
+        if let Some(ref mut window) = self.export_window {
+            match window.render(ui.ctx()) {
+                Ok(Some(rules)) => {
+                    self.export_window = None;
+                    self.export_with_rules(rules, data_state, global_config);
+                }
+                Ok(None) => {}
+                Err(()) => self.export_window = None,
+            }
+        }
+    }
+
+    /// Remember `rules` and export this tab's results through them
+    fn export_with_rules(
+        &self,
+        rules: Vec<RedactionRule>,
+        data_state: &SessionState,
+        global_config: &mut GlobalConfig,
+    ) {
+        let redactor = match Redactor::new(&rules) {
+            Ok(redactor) => redactor,
+            Err(e) => {
+                tracing::error!("Failed to export filtered results: {e}");
+                return;
+            }
+        };
+        *global_config = GlobalConfig::update(|c| c.redaction_rules = rules);
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Export Filtered Results"))
+            .add_filter("Text", &["txt"])
+            .set_file_name("filtered_results.txt")
+            .save_file()
+        {
+            if let Err(e) =
+                export_filtered_results(&self.state, &data_state.store, &redactor, &path)
+            {
+                tracing::error!("Failed to export filtered results: {e}");
+            } else {
+                tracing::info!("Filtered results exported to {}", path.display());
+            }
+        }
     }
 
     /// Move selection within a filtered view (only through matched indices)
@@ -478,6 +782,9 @@ impl FilterView {
                 } else {
                     (current_pos + delta as usize).min(indices.len().saturating_sub(1))
                 };
+                self.state
+                    .search
+                    .remember_row_position(indices[new_pos], new_pos);
                 indices[new_pos]
             });
     }
@@ -486,19 +793,18 @@ impl FilterView {
     pub fn jump_to_top_in_filter(&self, data_state: &mut SessionState) {
         let indices = self.state.search.get_filtered_indices_cached();
         if let Some(first_line_index) = indices.first().copied() {
+            self.state.search.remember_row_position(first_line_index, 0);
             data_state.selected_line_index = Some(first_line_index);
         }
     }
 
     /// Jump to the last line in a filtered view (Vim-style G)
     pub fn jump_to_bottom_in_filter(&self, data_state: &mut SessionState) {
-        if let Some(last_line_index) = self
-            .state
-            .search
-            .get_filtered_indices_cached()
-            .last()
-            .copied()
-        {
+        let indices = self.state.search.get_filtered_indices_cached();
+        if let Some(last_line_index) = indices.last().copied() {
+            self.state
+                .search
+                .remember_row_position(last_line_index, indices.len() - 1);
             data_state.selected_line_index = Some(last_line_index);
         }
     }
@@ -515,6 +821,46 @@ impl FilterView {
         const PAGE_SIZE: i32 = 25;
         self.move_selection_in_filter(PAGE_SIZE, data_state);
     }
+
+    /// Select the next (or previous) line in this filter's results whose
+    /// normalized template matches the selected line (Vim-style `*` / `#`).
+    ///
+    /// Does not wrap around; the selection stays put when there is no further
+    /// occurrence in that direction.
+    pub fn jump_to_same_template_in_filter(&self, forward: bool, data_state: &mut SessionState) {
+        profiling::function_scope!();
+        let Some(selected) = data_state.selected_line_index else {
+            return;
+        };
+        let Some(template) = data_state.store.get_by_id(&selected).map(|l| l.template_key())
+        else {
+            return;
+        };
+        let Some(current_pos) = self
+            .state
+            .search
+            .find_closest_row_position_in_cache(selected, &data_state.store)
+        else {
+            return;
+        };
+
+        let indices = self.state.search.get_filtered_indices_cached();
+        let is_same_template = |id: &&StoreID| {
+            **id != selected
+                && data_state
+                    .store
+                    .get_by_id(id)
+                    .is_some_and(|line| line.template_key() == template)
+        };
+        let found = if forward {
+            indices.iter().skip(current_pos + 1).find(is_same_template)
+        } else {
+            indices.iter().take(current_pos).rev().find(is_same_template)
+        };
+        if let Some(&id) = found {
+            data_state.selected_line_index = Some(id);
+        }
+    }
 }
 
 impl FilterView {
@@ -522,21 +868,10 @@ impl FilterView {
     ///
     /// Priority:
     /// 1. Use explicit name if set
-    /// 2. Otherwise use filter text (truncated) if present  
+    /// 2. Otherwise a title derived from the filter text (see [`crate::core::SearchRule::display_name`])
     /// 3. Otherwise show "everything"
     fn get_display_name(&self) -> String {
-        if !self.state.name.is_empty() {
-            self.state.name.clone()
-        } else if self.state.search.search_text.is_empty() {
-            "everything".to_string()
-        } else {
-            let filter_text = &self.state.search.search_text;
-            if filter_text.chars().count() > 10 {
-                format!("{}…", filter_text.chars().take(9).collect::<String>())
-            } else {
-                filter_text.clone()
-            }
-        }
+        self.state.display_name()
     }
 }
 
@@ -544,6 +879,19 @@ impl LogCrabTab for FilterView {
     fn title(&mut self) -> egui::WidgetText {
         let mut layout_job = egui::text::LayoutJob::default();
 
+        if let Some(group) = &self.state.group {
+            layout_job.append(
+                &format!(" {group} "),
+                0.0,
+                egui::TextFormat {
+                    color: egui::Color32::BLACK,
+                    background: crate::ui::group_color(group),
+                    ..Default::default()
+                },
+            );
+            layout_job.append(" ", 0.0, egui::TextFormat::default());
+        }
+
         layout_job.append(
             "■ ",
             0.0,
@@ -556,9 +904,43 @@ impl LogCrabTab for FilterView {
         let display_name = self.get_display_name();
         layout_job.append(&display_name, 0.0, egui::TextFormat::default());
 
+        if let Some(threshold) = self.state.alert_threshold {
+            let count = self.state.search.get_filtered_indices_cached().len();
+            if count > threshold {
+                layout_job.append(
+                    &format!(" {count} "),
+                    6.0,
+                    egui::TextFormat {
+                        color: egui::Color32::WHITE,
+                        background: egui::Color32::from_rgb(200, 40, 40),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
         layout_job.into()
     }
 
+    fn hover_summary(&self) -> Option<String> {
+        let mut cache = self.summary_cache.borrow_mut();
+        if cache
+            .as_ref()
+            .is_none_or(|(revision, _)| *revision != self.summary_revision)
+        {
+            *cache = Some((self.summary_revision, self.build_summary()));
+        }
+        let summary = cache.as_ref().map(|(_, summary)| summary.as_str())?;
+        let changed = self.pattern_changed_at.map_or_else(
+            || tr!("Pattern unchanged since the tab was opened").to_string(),
+            |at| {
+                let elapsed = format_elapsed(at.elapsed().as_secs());
+                tr!("Pattern changed {elapsed} ago", elapsed)
+            },
+        );
+        Some(format!("{summary}\n{changed}"))
+    }
+
     fn render(
         &mut self,
         ui: &mut egui::Ui,
@@ -622,6 +1004,7 @@ impl LogCrabTab for FilterView {
                     data_state.toggle_bookmark_for_selected();
                     should_save = true;
                 }
+                ShortcutAction::QuickBookmark => {}
                 ShortcutAction::JumpToTop => {
                     self.jump_to_top_in_filter(data_state);
                 }
@@ -635,7 +1018,9 @@ impl LogCrabTab for FilterView {
                     self.page_down_in_filter(data_state);
                 }
                 ShortcutAction::FocusSearch => {
-                    self.focus_search_next_frame();
+                    if !self.state.is_errors_view {
+                        self.focus_search_next_frame();
+                    }
                 }
                 ShortcutAction::NewFilterTab => {}
                 ShortcutAction::NewBookmarksTab => {}
@@ -643,21 +1028,30 @@ impl LogCrabTab for FilterView {
                 ShortcutAction::CycleTab => {}
                 ShortcutAction::ReverseCycleTab => {}
                 ShortcutAction::OpenFile => {}
+                ShortcutAction::ReloadFiles => {}
+                ShortcutAction::RenameFilter if self.state.is_errors_view => {}
                 ShortcutAction::RenameFilter => {
                     self.change_filtername_window =
                         Some(ChangeFilternameWindow::new(self.state.name.clone()));
                 }
+                ShortcutAction::NextSameTemplate => {
+                    self.jump_to_same_template_in_filter(true, data_state);
+                }
+                ShortcutAction::PreviousSameTemplate => {
+                    self.jump_to_same_template_in_filter(false, data_state);
+                }
                 ShortcutAction::FocusPaneLeft => {}
                 ShortcutAction::FocusPaneDown => {}
                 ShortcutAction::FocusPaneUp => {}
                 ShortcutAction::FocusPaneRight => {}
+                ShortcutAction::ErrorsView => {}
             }
         }
         should_save
     }
 
     fn try_into_stored_filter(&self) -> Option<SavedFilter> {
-        Some((&self.state).into())
+        (!self.state.is_errors_view).then(|| (&self.state).into())
     }
 
     fn get_filter_highlight(&self) -> Option<FilterHighlight> {
@@ -687,24 +1081,84 @@ impl LogCrabTab for FilterView {
         })
     }
 
-    fn context_menu(&mut self, ui: &mut egui::Ui) {
+    fn context_menu(&mut self, ui: &mut egui::Ui, data_state: &mut SessionState) {
         let icon = if self.state.enabled { "👁" } else { "🚫" };
         let text = if self.state.enabled {
-            "Hide in other tabs"
+            tr!("Hide in other tabs")
         } else {
-            "Show in other tabs"
+            tr!("Show in other tabs")
         };
 
         if ui.button(format!("{icon} {text}")).clicked() {
             self.state.enabled = !self.state.enabled;
+            data_state.modified = true;
             ui.close();
         }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let mut alert_enabled = self.state.alert_threshold.is_some();
+            if ui
+                .checkbox(&mut alert_enabled, tr!("🔔 Alert above"))
+                .on_hover_text(tr!("Show a red badge with the match count on the tab when more lines than this match"))
+                .changed()
+            {
+                self.state.alert_threshold = alert_enabled.then_some(0);
+                data_state.modified = true;
+            }
+            if let Some(threshold) = self.state.alert_threshold.as_mut() {
+                if ui
+                    .add(egui::DragValue::new(threshold).suffix(tr!(" matches")))
+                    .changed()
+                {
+                    data_state.modified = true;
+                }
+            }
+        });
     }
 
     fn get_uuid(&self) -> Option<usize> {
         Some(self.state.get_id())
     }
+
+    fn is_errors_view(&self) -> bool {
+        self.state.is_errors_view
+    }
+
+    fn match_count(&self) -> Option<usize> {
+        Some(self.state.search.get_filtered_indices_cached().len())
+    }
+
+    fn group(&self) -> Option<&str> {
+        self.state.group.as_deref()
+    }
+
+    fn set_group(&mut self, group: Option<String>) {
+        self.state.group = group;
+        self.summary_revision += 1;
+    }
+
+    fn resolve_source_names(&mut self, paths: &[String]) -> bool {
+        self.state.search.column_filter.resolve_source_names(paths)
+    }
+
+    fn set_globally_visible(&mut self, visible: bool) {
+        self.state.enabled = visible;
+    }
+
+    fn zoom_to_time_range(&mut self, start: DateTime<Local>, end: DateTime<Local>) {
+        self.state.histogram_cache.zoom.set_visible_range(start, end);
+    }
 }
 
 mod export;
-use export::export_filtered_results;
+use export::{export_filtered_results, export_preview_lines};
+
+/// Coarse human-readable duration, e.g. "5 s", "3 min", "2 h".
+fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs} s"),
+        60..3600 => format!("{} min", secs / 60),
+        _ => format!("{} h", secs / 3600),
+    }
+}
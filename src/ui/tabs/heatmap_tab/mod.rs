@@ -0,0 +1,424 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Calendar-style heatmap of error counts or peak anomaly scores.
+//!
+//! Each row is one hour (or day) that contains log lines; each column a
+//! minute (or quarter hour) within it. For logs spanning days this keeps
+//! daily and hourly structure visible where the linear histogram flattens
+//! it. Clicking a cell zooms every filter's histogram to that period.
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Timelike};
+use egui::{Color32, Rect, Sense, Ui, Vec2};
+use fancy_regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+
+use crate::anomaly::keyword::ERROR_KEYWORDS_PATTERN;
+use crate::config::GlobalConfig;
+use crate::core::log_store::{StoreID, StoreVersion};
+use crate::core::{LogStore, SavedFilter};
+use crate::input::ShortcutAction;
+use crate::tr;
+use crate::ui::filter_highlight::FilterHighlight;
+use crate::ui::session_state::SessionState;
+use crate::ui::tabs::filter_tab::HistogramMarker;
+use crate::ui::tabs::LogCrabTab;
+
+/// Width reserved for the row labels
+const LABEL_WIDTH: f32 = 120.0;
+/// Height of one heatmap row
+const ROW_HEIGHT: f32 = 14.0;
+
+/// What one heatmap row spans
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowSpan {
+    /// One hour per row, one minute per column
+    Hour,
+    /// One day per row, fifteen minutes per column
+    Day,
+}
+
+impl RowSpan {
+    const fn columns(self) -> usize {
+        match self {
+            Self::Hour => 60,
+            Self::Day => 96,
+        }
+    }
+
+    const fn cell_duration(self) -> Duration {
+        match self {
+            Self::Hour => Duration::minutes(1),
+            Self::Day => Duration::minutes(15),
+        }
+    }
+
+    /// Row start (local wall-clock time) and column of a timestamp
+    fn cell_of(self, ts: DateTime<Local>) -> (NaiveDateTime, usize) {
+        let naive = ts.naive_local();
+        let date = naive.date();
+        match self {
+            Self::Hour => (
+                date.and_hms_opt(naive.hour(), 0, 0)
+                    .expect("whole hour is a valid time"),
+                naive.minute() as usize,
+            ),
+            Self::Day => (
+                date.and_hms_opt(0, 0, 0).expect("midnight is a valid time"),
+                (naive.hour() * 4 + naive.minute() / 15) as usize,
+            ),
+        }
+    }
+
+    fn row_label(self, row: NaiveDateTime) -> String {
+        match self {
+            Self::Hour => row.format("%Y-%m-%d %H:00").to_string(),
+            Self::Day => row.format("%Y-%m-%d %a").to_string(),
+        }
+    }
+}
+
+/// Value a cell is coloured by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeatMetric {
+    Errors,
+    MaxAnomaly,
+}
+
+/// Aggregates of the lines falling into one cell
+#[derive(Debug, Clone, Copy, Default)]
+struct HeatCell {
+    lines: usize,
+    errors: usize,
+    max_anomaly: f64,
+    /// Earliest line in the cell, selected when the cell is clicked
+    first: Option<StoreID>,
+}
+
+impl HeatCell {
+    const fn value(&self, metric: HeatMetric) -> f64 {
+        match metric {
+            HeatMetric::Errors => self.errors as f64,
+            HeatMetric::MaxAnomaly => self.max_anomaly,
+        }
+    }
+}
+
+/// Inputs the computed grid depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HeatmapKey {
+    store_version: StoreVersion,
+    span: RowSpan,
+    use_ml_score: bool,
+}
+
+/// Computed heatmap grid: only rows that contain lines
+struct HeatmapData {
+    key: HeatmapKey,
+    rows: BTreeMap<NaiveDateTime, Vec<HeatCell>>,
+}
+
+impl HeatmapData {
+    fn compute(store: &LogStore, key: HeatmapKey) -> Self {
+        profiling::scope!("HeatmapData::compute");
+        let error_regex =
+            Regex::new(&format!("(?i){ERROR_KEYWORDS_PATTERN}")).expect("valid regex literal");
+        let errors: HashSet<StoreID> = store
+            .get_matching_ids(|message, _raw| error_regex.is_match(message).unwrap_or(false))
+            .into_iter()
+            .collect();
+
+        let mut rows: BTreeMap<NaiveDateTime, Vec<HeatCell>> = BTreeMap::new();
+        for id in store.get_matching_ids(|_, _| true) {
+            let Some(ts) = store.adjusted_timestamp(&id) else {
+                continue;
+            };
+            let (row, column) = key.span.cell_of(ts);
+            let cell = &mut rows
+                .entry(row)
+                .or_insert_with(|| vec![HeatCell::default(); key.span.columns()])[column];
+            cell.lines += 1;
+            if errors.contains(&id) {
+                cell.errors += 1;
+            }
+            let anomaly = if key.use_ml_score {
+                store.get_sidecar_score(id.source_id(), id.line_index_within_source())
+            } else {
+                store.get_score(id.source_id(), id.line_index_within_source())
+            };
+            cell.max_anomaly = cell.max_anomaly.max(anomaly);
+            cell.first.get_or_insert(id);
+        }
+        Self { key, rows }
+    }
+}
+
+/// Heatmap tab
+pub struct HeatmapView {
+    span: RowSpan,
+    metric: HeatMetric,
+    data: Option<HeatmapData>,
+    /// Channel of the computation in flight
+    pending: Option<Receiver<HeatmapData>>,
+}
+
+impl Default for HeatmapView {
+    fn default() -> Self {
+        Self {
+            span: RowSpan::Hour,
+            metric: HeatMetric::Errors,
+            data: None,
+            pending: None,
+        }
+    }
+}
+
+impl HeatmapView {
+    /// Collect a finished computation and start a new one if `key` changed.
+    ///
+    /// At most one computation runs at a time; while a file is still loading
+    /// the grid is refreshed each time the previous computation finishes.
+    fn request(&mut self, store: &Arc<LogStore>, key: HeatmapKey, ctx: &egui::Context) {
+        if let Some(rx) = &self.pending {
+            match rx.try_recv() {
+                Ok(data) => {
+                    self.data = Some(data);
+                    self.pending = None;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+        if self.data.as_ref().is_some_and(|d| d.key == key) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let store = Arc::clone(store);
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let data = HeatmapData::compute(&store, key);
+            // The tab may have been closed; nobody to deliver to then.
+            let _ = tx.send(data);
+            ctx.request_repaint();
+        });
+        self.pending = Some(rx);
+    }
+
+    /// Colour for `value` on a scale whose maximum is `max`
+    fn cell_color(value: f64, max: f64, has_lines: bool, visuals: &egui::Visuals) -> Color32 {
+        if !has_lines {
+            return visuals.faint_bg_color;
+        }
+        if value <= 0.0 || max <= 0.0 {
+            return visuals.widgets.inactive.bg_fill;
+        }
+        let t = (value / max).sqrt().clamp(0.0, 1.0) as f32;
+        let low = Color32::from_rgb(255, 220, 80);
+        let high = Color32::from_rgb(220, 30, 30);
+        low.lerp_to_gamma(high, t)
+    }
+
+    fn render_grid(&self, ui: &mut Ui, data: &HeatmapData, data_state: &mut SessionState) {
+        let span = data.key.span;
+        let max = data
+            .rows
+            .values()
+            .flatten()
+            .map(|cell| cell.value(self.metric))
+            .fold(0.0, f64::max);
+        let visuals = ui.visuals().clone();
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show_rows(ui, ROW_HEIGHT, data.rows.len(), |ui, range| {
+                for (row_start, cells) in data.rows.iter().skip(range.start).take(range.len()) {
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        ui.add_sized(
+                            [LABEL_WIDTH, ROW_HEIGHT],
+                            egui::Label::new(
+                                egui::RichText::new(span.row_label(*row_start)).monospace(),
+                            ),
+                        );
+                        let cell_width = (ui.available_width() / span.columns() as f32).max(2.0);
+                        let (rect, response) = ui.allocate_exact_size(
+                            Vec2::new(cell_width * span.columns() as f32, ROW_HEIGHT),
+                            Sense::click(),
+                        );
+                        let painter = ui.painter_at(rect);
+                        for (column, cell) in cells.iter().enumerate() {
+                            let cell_rect = Rect::from_min_size(
+                                rect.min + Vec2::new(column as f32 * cell_width, 0.0),
+                                Vec2::new(cell_width - 1.0, ROW_HEIGHT - 1.0),
+                            );
+                            painter.rect_filled(
+                                cell_rect,
+                                0.0,
+                                Self::cell_color(
+                                    cell.value(self.metric),
+                                    max,
+                                    cell.lines > 0,
+                                    &visuals,
+                                ),
+                            );
+                        }
+
+                        let Some(pos) = response.hover_pos() else {
+                            return;
+                        };
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let column =
+                            (((pos.x - rect.min.x) / cell_width) as usize).min(span.columns() - 1);
+                        let cell = cells[column];
+                        let offset = span.cell_duration()
+                            * i32::try_from(column).expect("column count fits in i32");
+                        let Some(start) =
+                            Local.from_local_datetime(&(*row_start + offset)).earliest()
+                        else {
+                            return;
+                        };
+                        let end = start + span.cell_duration();
+                        let response = response.on_hover_text(format!(
+                            "{} – {}\n{}",
+                            start.format("%Y-%m-%d %H:%M"),
+                            end.format("%H:%M"),
+                            tr!(
+                                "{lines} lines, {errors} errors, max anomaly {max}",
+                                lines = cell.lines,
+                                errors = cell.errors,
+                                max = format!("{:.0}", cell.max_anomaly),
+                            ),
+                        ));
+                        if response.clicked() && cell.lines > 0 {
+                            data_state.pending_time_zoom = Some((start, end));
+                            if let Some(first) = cell.first {
+                                data_state.selected_line_index = Some(first);
+                            }
+                        }
+                    });
+                }
+            });
+    }
+}
+
+impl LogCrabTab for HeatmapView {
+    fn title(&mut self) -> egui::WidgetText {
+        tr!("Heatmap").into()
+    }
+
+    fn render(
+        &mut self,
+        ui: &mut Ui,
+        data_state: &mut SessionState,
+        global_config: &mut GlobalConfig,
+        _all_filter_highlights: &[FilterHighlight],
+        _histogram_markers: &[HistogramMarker],
+    ) {
+        let key = HeatmapKey {
+            store_version: data_state.store.version(),
+            span: self.span,
+            use_ml_score: global_config.color_by_ml_score,
+        };
+        self.request(&data_state.store, key, ui.ctx());
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("Rows:"));
+            ui.selectable_value(&mut self.span, RowSpan::Hour, tr!("Hours"));
+            ui.selectable_value(&mut self.span, RowSpan::Day, tr!("Days"));
+            ui.separator();
+            ui.label(tr!("Color by:"));
+            ui.selectable_value(&mut self.metric, HeatMetric::Errors, tr!("Errors"));
+            ui.selectable_value(&mut self.metric, HeatMetric::MaxAnomaly, tr!("Max anomaly"));
+            if self.pending.is_some() {
+                ui.spinner();
+            }
+        });
+        ui.separator();
+
+        let Some(data) = self.data.as_ref() else {
+            ui.label(tr!("Computing…"));
+            return;
+        };
+        if data.rows.is_empty() {
+            ui.label(tr!("No lines to show."));
+            return;
+        }
+        self.render_grid(ui, data, data_state);
+    }
+
+    fn process_events(
+        &mut self,
+        _actions: &[ShortcutAction],
+        _data_state: &mut SessionState,
+    ) -> bool {
+        false
+    }
+
+    fn try_into_stored_filter(&self) -> Option<SavedFilter> {
+        None
+    }
+
+    fn get_filter_highlight(&self) -> Option<FilterHighlight> {
+        None
+    }
+
+    fn get_histogram_marker(&mut self) -> Option<HistogramMarker> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn local(h: u32, m: u32, s: u32) -> DateTime<Local> {
+        let naive = NaiveDate::from_ymd_opt(2026, 3, 4)
+            .and_then(|d| d.and_hms_opt(h, m, s))
+            .expect("valid time");
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .expect("unambiguous local time")
+    }
+
+    #[test]
+    fn test_hour_rows_have_minute_columns() {
+        let (row, column) = RowSpan::Hour.cell_of(local(13, 47, 59));
+        assert_eq!(row, local(13, 0, 0).naive_local());
+        assert_eq!(column, 47);
+        assert!(column < RowSpan::Hour.columns());
+    }
+
+    #[test]
+    fn test_day_rows_have_quarter_hour_columns() {
+        assert_eq!(
+            RowSpan::Day.cell_of(local(0, 0, 0)),
+            (local(0, 0, 0).naive_local(), 0)
+        );
+        let (row, column) = RowSpan::Day.cell_of(local(13, 47, 59));
+        assert_eq!(row, local(0, 0, 0).naive_local());
+        assert_eq!(column, 13 * 4 + 3);
+        assert_eq!(
+            RowSpan::Day.cell_of(local(23, 59, 59)).1,
+            RowSpan::Day.columns() - 1
+        );
+    }
+}
@@ -19,10 +19,13 @@
 use egui::{Color32, RichText, Ui};
 
 use crate::config::GlobalConfig;
+use crate::core::keyword_list::parse_keyword_list;
 use crate::core::{SavedFilter, SearchRule};
 use crate::input::ShortcutAction;
+use crate::tr;
 use crate::ui::filter_highlight::FilterHighlight;
 use crate::ui::session_state::SessionState;
+use crate::ui::tabs::filter_tab::filter_bar::render_slow_filter_warning;
 use crate::ui::tabs::filter_tab::HistogramMarker;
 use crate::ui::tabs::LogCrabTab;
 use crate::ui::DEFAULT_PALETTE;
@@ -36,6 +39,14 @@ pub struct HighlightsView {
     editing_name_index: Option<usize>,
     /// Whether we've already requested focus for the current edit session
     focus_requested: bool,
+    /// Row selected with the keyboard (j/k)
+    selected_index: Option<usize>,
+    /// Enter was pressed while no text field had focus (set during render)
+    enter_pressed_this_frame: bool,
+    /// 'c' was pressed while no text field had focus (set during render)
+    color_key_pressed_this_frame: bool,
+    /// Row whose color picker should open on the next render
+    open_color_picker: Option<usize>,
 }
 
 impl HighlightsView {
@@ -50,12 +61,61 @@ impl HighlightsView {
         (color, name)
     }
 
+    /// Ask for a keyword list file and append one highlight per pattern.
+    fn import_keyword_file(&mut self, data_state: &mut SessionState) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(tr!("Import Highlights"))
+            .add_filter("Keyword List", &["txt", "lst"])
+            .add_filter("All Files", &["*"])
+            .pick_file()
+        else {
+            return;
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::error!("Failed to read keyword list {}: {e}", path.display());
+                return;
+            }
+        };
+
+        let entries = parse_keyword_list(&text);
+        for entry in &entries {
+            let (palette_color, _) = self.next_color_and_name();
+            let mut rule =
+                SearchRule::new(entry.pattern.clone(), entry.color.unwrap_or(palette_color));
+            rule.search.search_text.clone_from(&entry.pattern);
+            data_state.highlights.push(rule);
+        }
+        if !entries.is_empty() {
+            data_state.modified = true;
+        }
+        tracing::info!(
+            "Imported {} highlights from {}",
+            entries.len(),
+            path.display()
+        );
+    }
+
+    /// Move the keyboard selection by `delta` rows, clamped to the list.
+    fn move_selection(&mut self, delta: isize, len: usize) {
+        if len == 0 {
+            self.selected_index = None;
+            return;
+        }
+        self.selected_index = Some(
+            self.selected_index
+                .map_or(0, |index| index.saturating_add_signed(delta).min(len - 1)),
+        );
+    }
+
     fn render_highlight_row(
         ui: &mut Ui,
         highlight: &mut SearchRule,
         index: usize,
         is_editing_name: bool,
         should_focus: bool,
+        open_color_picker: bool,
     ) -> Vec<HighlightRowAction> {
         let mut actions = Vec::new();
 
@@ -63,7 +123,7 @@ impl HighlightsView {
             // Enable/disable toggle
             if ui
                 .toggle_value(&mut highlight.enabled, "👁")
-                .on_hover_text("Enable/disable this highlight")
+                .on_hover_text(tr!("Enable/disable this highlight"))
                 .changed()
             {
                 actions.push(HighlightRowAction::Modified);
@@ -72,7 +132,7 @@ impl HighlightsView {
             // Histogram toggle
             if ui
                 .toggle_value(&mut highlight.show_in_histogram, "📊")
-                .on_hover_text("Show matches as markers in timeline")
+                .on_hover_text(tr!("Show matches as markers in timeline"))
                 .changed()
             {
                 actions.push(HighlightRowAction::Modified);
@@ -83,7 +143,7 @@ impl HighlightsView {
                 let response = ui.add(
                     egui::TextEdit::singleline(&mut highlight.name)
                         .desired_width(120.0)
-                        .hint_text("Name..."),
+                        .hint_text(tr!("Name...")),
                 );
                 // Request focus only on the first frame
                 if should_focus {
@@ -108,14 +168,17 @@ impl HighlightsView {
                 };
                 if ui
                     .add(egui::Label::new(name_display).sense(egui::Sense::click()))
-                    .on_hover_text("Click to edit name")
+                    .on_hover_text(tr!("Click to edit name"))
                     .clicked()
                 {
                     actions.push(HighlightRowAction::StartEditingName(index));
                 }
             }
 
-            // Color picker
+            // Color picker ('c' opens it for the selected row)
+            if open_color_picker {
+                egui::Popup::open_id(ui.ctx(), ui.auto_id_with("popup"));
+            }
             let mut color_arr = highlight.color.to_array();
             if ui
                 .color_edit_button_srgba_unmultiplied(&mut color_arr)
@@ -134,7 +197,7 @@ impl HighlightsView {
             let response = ui.add(
                 egui::TextEdit::singleline(&mut highlight.search.search_text)
                     .desired_width(300.0)
-                    .hint_text("Search pattern..."),
+                    .hint_text(tr!("Search pattern...")),
             );
             if response.changed() {
                 actions.push(HighlightRowAction::Modified);
@@ -144,7 +207,7 @@ impl HighlightsView {
             let exclude_response = ui.add(
                 egui::TextEdit::singleline(&mut highlight.search.exclude_text)
                     .desired_width(200.0)
-                    .hint_text("Exclude pattern (optional)"),
+                    .hint_text(tr!("Exclude pattern (optional)")),
             );
             if exclude_response.changed() {
                 actions.push(HighlightRowAction::Modified);
@@ -158,7 +221,7 @@ impl HighlightsView {
             };
             if ui
                 .toggle_value(&mut highlight.search.case_sensitive, case_label)
-                .on_hover_text("Case sensitive")
+                .on_hover_text(tr!("Case sensitive"))
                 .changed()
             {
                 actions.push(HighlightRowAction::Modified);
@@ -174,11 +237,12 @@ impl HighlightsView {
                 }
                 _ => {}
             }
+            render_slow_filter_warning(ui, &mut highlight.search);
 
             // Convert to filter button
             if ui
-                .button("into Filter")
-                .on_hover_text("Convert this highlight to a filter tab")
+                .button(tr!("into Filter"))
+                .on_hover_text(tr!("Convert this highlight to a filter tab"))
                 .clicked()
             {
                 actions.push(HighlightRowAction::ConvertToFilter(index));
@@ -187,7 +251,7 @@ impl HighlightsView {
             // Delete button
             if ui
                 .button(RichText::new("🗑").color(Color32::from_rgb(200, 80, 80)))
-                .on_hover_text("Delete this highlight")
+                .on_hover_text(tr!("Delete this highlight"))
                 .clicked()
             {
                 actions.push(HighlightRowAction::Delete(index));
@@ -213,7 +277,7 @@ enum HighlightRowAction {
 
 impl LogCrabTab for HighlightsView {
     fn title(&mut self) -> egui::WidgetText {
-        "🎨 Highlights".into()
+        tr!("🎨 Highlights").into()
     }
 
     fn render(
@@ -226,31 +290,64 @@ impl LogCrabTab for HighlightsView {
     ) {
         profiling::scope!("HighlightsView::render");
 
+        // Keys without a shortcut action, handled in process_events like the bookmarks tab
+        let keys_free = !ui.ctx().wants_keyboard_input();
+        self.enter_pressed_this_frame = keys_free
+            && self.editing_name_index.is_none()
+            && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        self.color_key_pressed_this_frame =
+            keys_free && ui.input(|i| i.modifiers.is_none() && i.key_pressed(egui::Key::C));
+
         ui.vertical(|ui| {
             ui.add_space(4.0);
 
             // Header
             ui.horizontal(|ui| {
-                if ui.button("➕ Add Highlight").clicked() {
+                if ui.button(tr!("➕ Add Highlight")).clicked() {
                     let (color, name) = self.next_color_and_name();
                     data_state.highlights.push(SearchRule::new(name, color));
                     data_state.modified = true;
                 }
+                if ui
+                    .button(tr!("📂 Import from File…"))
+                    .on_hover_text(tr!(
+                        "Create highlights from a keyword list: one pattern per line, \
+                         optionally followed by a tab and a #RRGGBB color"
+                    ))
+                    .clicked()
+                {
+                    self.import_keyword_file(data_state);
+                }
             });
 
             ui.separator();
 
             if data_state.highlights.is_empty() {
-                ui.label("No highlights configured. Click 'Add Highlight' to create one.");
+                ui.label(tr!(
+                    "No highlights configured. Click 'Add Highlight' to create one."
+                ));
             } else {
                 // Render each highlight row
                 let mut actions = Vec::new();
+                let open_color_picker = self.open_color_picker.take();
                 for (index, highlight) in data_state.highlights.iter_mut().enumerate() {
                     let is_editing = self.editing_name_index == Some(index);
                     let should_focus = is_editing && !self.focus_requested;
-                    let row_actions =
-                        Self::render_highlight_row(ui, highlight, index, is_editing, should_focus);
-                    actions.extend(row_actions);
+                    let fill = if self.selected_index == Some(index) {
+                        ui.visuals().selection.bg_fill.gamma_multiply(0.3)
+                    } else {
+                        Color32::TRANSPARENT
+                    };
+                    egui::Frame::new().fill(fill).show(ui, |ui| {
+                        actions.extend(Self::render_highlight_row(
+                            ui,
+                            highlight,
+                            index,
+                            is_editing,
+                            should_focus,
+                            open_color_picker == Some(index),
+                        ));
+                    });
                     ui.add_space(2.0);
                 }
 
@@ -277,8 +374,14 @@ impl LogCrabTab for HighlightsView {
                             }
                             data_state.highlights.remove(index);
                             data_state.modified = true;
+                            let len = data_state.highlights.len();
+                            self.selected_index = self
+                                .selected_index
+                                .filter(|_| len > 0)
+                                .map(|selected| selected.min(len - 1));
                         }
                         HighlightRowAction::StartEditingName(index) => {
+                            self.selected_index = Some(index);
                             self.editing_name_index = Some(index);
                             self.focus_requested = false; // Reset so we request focus on next frame
                         }
@@ -297,9 +400,60 @@ impl LogCrabTab for HighlightsView {
 
     fn process_events(
         &mut self,
-        _actions: &[ShortcutAction],
-        _data_state: &mut SessionState,
+        actions: &[ShortcutAction],
+        data_state: &mut SessionState,
     ) -> bool {
+        // Enter and 'c' were captured during render, where the UI context is available
+        if let Some(index) = self.selected_index {
+            if self.enter_pressed_this_frame && self.editing_name_index.is_none() {
+                self.editing_name_index = Some(index);
+                self.focus_requested = false;
+            }
+            if self.color_key_pressed_this_frame {
+                self.open_color_picker = Some(index);
+            }
+        }
+        self.enter_pressed_this_frame = false;
+        self.color_key_pressed_this_frame = false;
+
+        let len = data_state.highlights.len();
+        for action in actions {
+            match action {
+                ShortcutAction::MoveDown => self.move_selection(1, len),
+                ShortcutAction::MoveUp => self.move_selection(-1, len),
+                ShortcutAction::JumpToTop => self.move_selection(isize::MIN, len),
+                ShortcutAction::JumpToBottom => self.move_selection(isize::MAX, len),
+                ShortcutAction::ToggleBookmark => {
+                    // Space toggles the selected highlight on and off
+                    if let Some(highlight) = self
+                        .selected_index
+                        .and_then(|index| data_state.highlights.get_mut(index))
+                    {
+                        highlight.enabled = !highlight.enabled;
+                        data_state.modified = true;
+                    }
+                }
+                ShortcutAction::QuickBookmark => {}
+                ShortcutAction::PageUp => {}
+                ShortcutAction::PageDown => {}
+                ShortcutAction::FocusSearch => {}
+                ShortcutAction::NewFilterTab => {}
+                ShortcutAction::NewBookmarksTab => {}
+                ShortcutAction::CloseTab => {}
+                ShortcutAction::CycleTab => {}
+                ShortcutAction::ReverseCycleTab => {}
+                ShortcutAction::OpenFile => {}
+                ShortcutAction::ReloadFiles => {}
+                ShortcutAction::RenameFilter => {}
+                ShortcutAction::NextSameTemplate => {}
+                ShortcutAction::PreviousSameTemplate => {}
+                ShortcutAction::ErrorsView => {}
+                ShortcutAction::FocusPaneLeft => {}
+                ShortcutAction::FocusPaneDown => {}
+                ShortcutAction::FocusPaneUp => {}
+                ShortcutAction::FocusPaneRight => {}
+            }
+        }
         false
     }
 
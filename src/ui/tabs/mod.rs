@@ -18,11 +18,13 @@
 
 pub mod bookmarks_tab;
 pub mod filter_tab;
+pub mod heatmap_tab;
 pub mod highlights_tab;
 pub mod navigation;
 
 pub use bookmarks_tab::BookmarksView;
 pub use filter_tab::FilterView;
+pub use heatmap_tab::HeatmapView;
 pub use highlights_tab::HighlightsView;
 
 use egui_dock::TabViewer;
@@ -30,8 +32,9 @@ use egui_dock::TabViewer;
 use crate::config::GlobalConfig;
 use crate::core::SavedFilter;
 use crate::input::ShortcutAction;
+use crate::tr;
 use crate::ui::filter_highlight::FilterHighlight;
-use crate::ui::session_state::SessionState;
+use crate::ui::session_state::{FilterGroupAction, SessionState};
 use crate::ui::tabs::filter_tab::HistogramMarker;
 
 pub trait LogCrabTab {
@@ -49,13 +52,53 @@ pub trait LogCrabTab {
     fn try_into_stored_filter(&self) -> Option<SavedFilter>;
     fn get_filter_highlight(&self) -> Option<FilterHighlight>;
     fn get_histogram_marker(&mut self) -> Option<HistogramMarker>;
-    fn context_menu(&mut self, _ui: &mut egui::Ui) {
+    fn context_menu(&mut self, _ui: &mut egui::Ui, _data_state: &mut SessionState) {
         // Default implementation does nothing
     }
     /// Get the unique identifier for this tab (for filter tabs)
     fn get_uuid(&self) -> Option<usize> {
         None
     }
+    /// Tooltip text shown when hovering the tab button
+    fn hover_summary(&self) -> Option<String> {
+        None
+    }
+    /// Whether this is the built-in "Errors & Fatals" view
+    fn is_errors_view(&self) -> bool {
+        false
+    }
+    /// Number of lines matching this tab's filter (for filter tabs)
+    fn match_count(&self) -> Option<usize> {
+        None
+    }
+    /// Name of the group this tab is organized under (for filter tabs)
+    fn group(&self) -> Option<&str> {
+        None
+    }
+    /// Move this tab into `group`, or out of its group with `None`
+    fn set_group(&mut self, _group: Option<String>) {
+        // Default implementation does nothing
+    }
+    /// Map `Source` filter entries saved as file names to the paths of the
+    /// open sources (see [`ColumnFilter::resolve_source_names`]). Returns
+    /// `true` if the filter changed.
+    ///
+    /// [`ColumnFilter::resolve_source_names`]: crate::core::column_filter::ColumnFilter::resolve_source_names
+    fn resolve_source_names(&mut self, _paths: &[String]) -> bool {
+        false
+    }
+    /// Show or hide this tab's matches as highlights in other tabs
+    fn set_globally_visible(&mut self, _visible: bool) {
+        // Default implementation does nothing
+    }
+    /// Zoom this tab's timeline to `[start, end)` (e.g. from a heatmap click)
+    fn zoom_to_time_range(
+        &mut self,
+        _start: chrono::DateTime<chrono::Local>,
+        _end: chrono::DateTime<chrono::Local>,
+    ) {
+        // Default implementation does nothing
+    }
 }
 
 /// Pending tab addition request from the add button
@@ -64,6 +107,7 @@ pub enum PendingTabAdd {
     Filter,
     Bookmarks,
     Highlights,
+    Heatmap,
 }
 
 /// `TabViewer` implementation for dock system
@@ -73,6 +117,72 @@ pub struct LogCrabTabViewer<'a> {
     pub pending_tab_add: &'a mut Option<PendingTabAdd>,
     pub all_filter_highlights: &'a [FilterHighlight],
     pub histogram_markers: &'a [HistogramMarker],
+    /// Names of all filter tab groups, sorted
+    pub filter_groups: &'a [String],
+}
+
+impl LogCrabTabViewer<'_> {
+    /// Group assignment and group-wide actions for saved filter tabs
+    fn group_menu(&mut self, ui: &mut egui::Ui, tab: &mut Box<dyn LogCrabTab>) {
+        let current = tab.group().map(str::to_string);
+        ui.separator();
+        ui.menu_button(tr!("🗂 Group"), |ui| {
+            if ui.radio(current.is_none(), tr!("No group")).clicked() {
+                tab.set_group(None);
+                self.log_view.modified = true;
+                ui.close();
+            }
+            for group in self.filter_groups {
+                let selected = current.as_deref() == Some(group.as_str());
+                let label = egui::RichText::new(group).color(crate::ui::group_color(group));
+                if ui.radio(selected, label).clicked() {
+                    tab.set_group(Some(group.clone()));
+                    self.log_view.modified = true;
+                    ui.close();
+                }
+            }
+            ui.separator();
+            let id = ui.id().with("new_filter_group");
+            let mut name = ui.data_mut(|d| d.get_temp::<String>(id).unwrap_or_default());
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut name)
+                    .hint_text(tr!("New group…"))
+                    .desired_width(120.0),
+            );
+            let name = name.trim().to_string();
+            if response.lost_focus()
+                && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                && !name.is_empty()
+            {
+                tab.set_group(Some(name));
+                self.log_view.modified = true;
+                ui.data_mut(|d| d.remove::<String>(id));
+                ui.close();
+            } else {
+                ui.data_mut(|d| d.insert_temp(id, name));
+            }
+        });
+
+        let Some(group) = current else {
+            return;
+        };
+        for (visible, text) in [
+            (false, "🚫 Hide group in other tabs"),
+            (true, "👁 Show group in other tabs"),
+        ] {
+            if ui.button(text).clicked() {
+                self.log_view.pending_group_action = Some(FilterGroupAction::SetGloballyVisible {
+                    group: group.clone(),
+                    visible,
+                });
+                ui.close();
+            }
+        }
+        if ui.button(tr!("✖ Close group \"{group}\"", group)).clicked() {
+            self.log_view.pending_group_action = Some(FilterGroupAction::Close { group });
+            ui.close();
+        }
+    }
 }
 
 impl TabViewer for LogCrabTabViewer<'_> {
@@ -90,6 +200,15 @@ impl TabViewer for LogCrabTabViewer<'_> {
         )
     }
 
+    fn on_tab_button(&mut self, tab: &mut Self::Tab, response: &egui::Response) {
+        if !response.hovered() {
+            return;
+        }
+        if let Some(summary) = tab.hover_summary() {
+            response.clone().on_hover_text(summary);
+        }
+    }
+
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
         profiling::scope!("TabViewer::ui");
         tab.render(
@@ -108,7 +227,10 @@ impl TabViewer for LogCrabTabViewer<'_> {
         _surface: egui_dock::SurfaceIndex,
         _node: egui_dock::NodeIndex,
     ) {
-        tab.context_menu(ui);
+        tab.context_menu(ui, self.log_view);
+        if tab.try_into_stored_filter().is_some() {
+            self.group_menu(ui, tab);
+        }
     }
 
     fn scroll_bars(&self, _tab: &Self::Tab) -> [bool; 2] {
@@ -123,19 +245,24 @@ impl TabViewer for LogCrabTabViewer<'_> {
     ) {
         ui.set_min_width(150.0);
 
-        if ui.button("➕ Filter Tab").clicked() {
+        if ui.button(tr!("➕ Filter Tab")).clicked() {
             *self.pending_tab_add = Some(PendingTabAdd::Filter);
             ui.close();
         }
 
-        if ui.button("🎨 Highlights Tab").clicked() {
+        if ui.button(tr!("🎨 Highlights Tab")).clicked() {
             *self.pending_tab_add = Some(PendingTabAdd::Highlights);
             ui.close();
         }
 
-        if ui.button("⭐ Bookmarks Tab").clicked() {
+        if ui.button(tr!("⭐ Bookmarks Tab")).clicked() {
             *self.pending_tab_add = Some(PendingTabAdd::Bookmarks);
             ui.close();
         }
+
+        if ui.button(tr!("🗓 Heatmap Tab")).clicked() {
+            *self.pending_tab_add = Some(PendingTabAdd::Heatmap);
+            ui.close();
+        }
     }
 }
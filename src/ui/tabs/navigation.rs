@@ -1,5 +1,13 @@
 use crate::ui::PaneDirection;
 
+/// Find the leaf node whose area contains `pos`
+pub fn leaf_at<T>(tree: &egui_dock::Tree<T>, pos: egui::Pos2) -> Option<egui_dock::NodeIndex> {
+    (0..tree.len()).map(egui_dock::NodeIndex::from).find(|&idx| {
+        let node = &tree[idx];
+        node.is_leaf() && node.rect().is_some_and(|rect| rect.contains(pos))
+    })
+}
+
 /// Find a neighboring leaf node in the specified direction
 pub fn find_neighbor<T>(
     tree: &egui_dock::Tree<T>,
@@ -21,6 +21,7 @@
 //! The loader thread can own a `ProgressToastHandle` and update it directly.
 //! The `ToastManager` renders all active handles each frame.
 
+use crate::tr;
 use egui::{Align2, Color32, Margin};
 use egui_toast::{Toast, ToastKind, ToastOptions, ToastStyle, Toasts};
 use std::sync::{Arc, Mutex, RwLock};
@@ -148,7 +149,7 @@ impl ProgressToastHandle {
             state.progress = Some(progress);
             state.message = message.into();
         }
-        self.ctx.request_repaint();
+        crate::ui::repaint::request_repaint(&self.ctx);
     }
 
     /// Change the title (e.g., from "Loading" to "Scoring")
@@ -174,6 +175,14 @@ impl ProgressToastHandle {
         }
         self.ctx.request_repaint();
     }
+
+    /// Whether the operation behind this toast has dismissed it (finished or failed)
+    #[must_use]
+    pub fn is_dismissed(&self) -> bool {
+        self.state
+            .read()
+            .is_ok_and(|state| state.dismissed_at.is_some())
+    }
 }
 
 impl Drop for ProgressToastHandle {
@@ -247,7 +256,7 @@ impl ToastManager {
             kind: ToastKind::Error,
             options: ToastOptions::default().duration(None),
             style: ToastStyle {
-                close_button_text: "Got it".into(),
+                close_button_text: tr!("Got it").into(),
                 ..Default::default()
             },
         });
@@ -1,5 +1,7 @@
 // LogCrab - GPL-3.0-or-later
 
+use crate::tr;
+
 /// Version from Cargo.toml
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -8,7 +10,7 @@ const GIT_HASH: &str = env!("GIT_HASH");
 
 /// Render the About window
 pub fn render_about_window(ctx: &egui::Context, open: &mut bool) {
-    egui::Window::new("About LogCrab")
+    egui::Window::new(tr!("About LogCrab"))
         .collapsible(false)
         .resizable(false)
         .default_width(350.0)
@@ -20,22 +22,22 @@ pub fn render_about_window(ctx: &egui::Context, open: &mut bool) {
                 ui.heading("🦀 LogCrab");
                 ui.add_space(5.0);
 
-                ui.label("A polyscopic anomaly explorer");
+                ui.label(tr!("A polyscopic anomaly explorer"));
                 ui.add_space(15.0);
 
                 egui::Grid::new("about_grid")
                     .num_columns(2)
                     .spacing([20.0, 8.0])
                     .show(ui, |ui| {
-                        ui.label("Version:");
+                        ui.label(tr!("Version:"));
                         ui.label(egui::RichText::new(VERSION).strong());
                         ui.end_row();
 
-                        ui.label("Git:");
+                        ui.label(tr!("Git:"));
                         ui.label(egui::RichText::new(GIT_HASH).code());
                         ui.end_row();
 
-                        ui.label("License:");
+                        ui.label(tr!("License:"));
                         ui.label("GPL-3.0-or-later");
                         ui.end_row();
                     });
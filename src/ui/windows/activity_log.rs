@@ -0,0 +1,94 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Activity log window: the analysis actions recorded in the session.
+
+use std::fmt::Write;
+
+use crate::core::LogStore;
+use crate::tr;
+
+/// Render the activity log of `store`, newest entry last
+pub fn render_activity_log_window(ctx: &egui::Context, open: &mut bool, store: &LogStore) {
+    egui::Window::new(tr!("📜 Activity Log"))
+        .open(open)
+        .default_width(560.0)
+        .default_height(360.0)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let log = store.get_activity_log();
+
+            ui.horizontal(|ui| {
+                if !store.is_recording_activity() {
+                    ui.label(
+                        egui::RichText::new(tr!("Recording is off (View → Record Activity Log)"))
+                            .weak(),
+                    );
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add_enabled(!log.is_empty(), egui::Button::new(tr!("📋 Copy as Text")))
+                        .clicked()
+                    {
+                        let mut text = String::new();
+                        for (source, entry) in &log {
+                            let time = entry.time.format("%Y-%m-%d %H:%M:%S");
+                            let source = source
+                                .as_deref()
+                                .map(|source| format!("[{source}] "))
+                                .unwrap_or_default();
+                            let _ = writeln!(text, "{time}  {source}{}", entry.action);
+                        }
+                        ui.ctx().copy_text(text);
+                    }
+                });
+            });
+            ui.separator();
+
+            if log.is_empty() {
+                ui.label(tr!("No actions recorded yet."));
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("activity_log_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            for (source, entry) in &log {
+                                ui.label(
+                                    egui::RichText::new(
+                                        entry.time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                                    )
+                                    .monospace(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(source.as_deref().unwrap_or("")).weak(),
+                                );
+                                ui.label(entry.action.to_string());
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
@@ -0,0 +1,91 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Summary shown once a source finished scoring, pointing at its most
+//! anomalous lines so they are one click away instead of found by scrolling.
+
+use crate::core::log_store::StoreID;
+use crate::tr;
+use egui::{Align2, Id};
+
+/// Lines scoring above this are counted in the summary
+pub const SUMMARY_THRESHOLD: f64 = 90.0;
+
+/// High-scoring lines of a freshly scored source
+#[derive(Debug, Clone)]
+pub struct AnomalySummary {
+    pub source_id: u64,
+    pub source_name: String,
+    /// Whether the scores came from the ML sidecar
+    pub ml: bool,
+    /// Lines scoring above [`SUMMARY_THRESHOLD`], sorted by timestamp
+    pub lines: Vec<StoreID>,
+}
+
+/// What the user picked in an [`AnomalySummary`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalySummaryAction {
+    JumpToFirst,
+    OpenTab,
+    Dismiss,
+}
+
+impl AnomalySummary {
+    /// Render the summary in the top-right corner, stacked below the
+    /// summaries with a lower `slot`.
+    #[must_use]
+    pub fn render(&self, ctx: &egui::Context, slot: usize) -> Option<AnomalySummaryAction> {
+        let mut action = None;
+        egui::Window::new(tr!("Anomalies found"))
+            .id(Id::new(("anomaly_summary", self.source_id)))
+            .anchor(
+                Align2::RIGHT_TOP,
+                [-10.0, (slot as f32).mul_add(120.0, 40.0)],
+            )
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr!(
+                    "{count} lines of {source} scored above {threshold}",
+                    count = self.lines.len(),
+                    source = self.source_name,
+                    threshold = SUMMARY_THRESHOLD,
+                ));
+                if self.ml {
+                    ui.weak(tr!("ML sidecar scores"));
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("⤵ Jump to first")).clicked() {
+                        action = Some(AnomalySummaryAction::JumpToFirst);
+                    }
+                    if ui
+                        .button(tr!("🔍 Open anomalies tab"))
+                        .on_hover_text(tr!("Open a filter tab matching the message templates \
+                             of these lines"))
+                        .clicked()
+                    {
+                        action = Some(AnomalySummaryAction::OpenTab);
+                    }
+                    if ui.button(tr!("Dismiss")).clicked() {
+                        action = Some(AnomalySummaryAction::Dismiss);
+                    }
+                });
+            });
+        action
+    }
+}
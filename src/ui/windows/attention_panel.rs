@@ -18,6 +18,7 @@
 
 use crate::anomaly::sidecar_client::ExplainResult;
 use crate::core::log_store::{LogStore, StoreID};
+use crate::tr;
 use egui::{Color32, RichText, Ui};
 use egui_extras::{Column, TableBuilder};
 
@@ -40,7 +41,7 @@ pub fn render_attention_panel(
     is_pending: bool,
     session_error: Option<&str>,
 ) {
-    egui::Window::new("Attention Weights")
+    egui::Window::new(tr!("Attention Weights"))
         .collapsible(false)
         .resizable(true)
         .default_width(640.0)
@@ -64,13 +65,17 @@ fn render_content(
         if let Some(line) = store.get_by_id(&t) {
             let preview: String = line.message.chars().take(100).collect();
             ui.label(
-                RichText::new(format!("Line {}: {preview}", line.line_number))
-                    .strong()
-                    .monospace(),
+                RichText::new(tr!(
+                    "Line {line}: {preview}",
+                    line = line.line_number,
+                    preview
+                ))
+                .strong()
+                .monospace(),
             );
         }
     } else {
-        ui.label("Right-click a scored line → Show Attention");
+        ui.label(tr!("Right-click a scored line → Show Attention"));
         return;
     }
 
@@ -80,7 +85,7 @@ fn render_content(
     if is_pending {
         ui.horizontal(|ui| {
             ui.spinner();
-            ui.label("Computing attention…");
+            ui.label(tr!("Computing attention…"));
         });
         return;
     }
@@ -94,20 +99,22 @@ fn render_content(
     }
     // ── No result yet (panel just opened, before first click) ─────────────────
     let Some(result) = result else {
-        ui.label("Right-click a scored line → Show Attention");
+        ui.label(tr!("Right-click a scored line → Show Attention"));
         return;
     };
 
     // ── Target not in corpus ──────────────────────────────────────────────────
     if !result.target_in_corpus {
-        ui.label("⚠ This line was filtered out by the model's corpus filter — no attention available.");
+        ui.label(tr!(
+            "⚠ This line was filtered out by the model's corpus filter — no attention available."
+        ));
         return;
     }
 
     // ── Score info ────────────────────────────────────────────────────────────
     ui.horizontal(|ui| {
         if let Some(score) = result.target_score {
-            ui.label(format!("Loss: {score:.4}"));
+            ui.label(tr!("Loss: {score}", score = format!("{score:.4}")));
         }
         if result.target_is_unk {
             ui.label(
@@ -120,7 +127,7 @@ fn render_content(
     // ── Top predicted templates ───────────────────────────────────────────────
     if !result.top_templates.is_empty() {
         ui.separator();
-        ui.label(RichText::new("Top predicted templates").strong());
+        ui.label(RichText::new(tr!("Top predicted templates")).strong());
         ui.add_space(2.0);
 
         let max_prob = result
@@ -129,36 +136,44 @@ fn render_content(
             .map_or(1.0_f32, |e| e.probability)
             .max(f32::EPSILON);
 
-        ui.push_id("templates_table", |ui| { TableBuilder::new(ui)
-            .striped(true)
-            .resizable(true)
-            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-            .column(Column::initial(130.0).at_least(80.0))
-            .column(Column::remainder().at_least(100.0))
-            .header(20.0, |mut header| {
-                header.col(|ui| { ui.label(RichText::new("Prob").strong()); });
-                header.col(|ui| { ui.label(RichText::new("Template").strong()); });
-            })
-            .body(|mut body| {
-                for entry in &result.top_templates {
-                    body.row(18.0, |mut row| {
-                        row.col(|ui| {
-                            let normalized = entry.probability / max_prob;
-                            ui.add(
-                                egui::ProgressBar::new(normalized)
-                                    .desired_width(ui.available_width())
-                                    .text(format!("{:.1}%", entry.probability * 100.0)),
-                            );
-                        });
-                        row.col(|ui| {
-                            ui.add(
-                                egui::Label::new(RichText::new(entry.template.clone()).monospace())
+        ui.push_id("templates_table", |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::initial(130.0).at_least(80.0))
+                .column(Column::remainder().at_least(100.0))
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.label(RichText::new(tr!("Prob")).strong());
+                    });
+                    header.col(|ui| {
+                        ui.label(RichText::new(tr!("Template")).strong());
+                    });
+                })
+                .body(|mut body| {
+                    for entry in &result.top_templates {
+                        body.row(18.0, |mut row| {
+                            row.col(|ui| {
+                                let normalized = entry.probability / max_prob;
+                                ui.add(
+                                    egui::ProgressBar::new(normalized)
+                                        .desired_width(ui.available_width())
+                                        .text(format!("{:.1}%", entry.probability * 100.0)),
+                                );
+                            });
+                            row.col(|ui| {
+                                ui.add(
+                                    egui::Label::new(
+                                        RichText::new(entry.template.clone()).monospace(),
+                                    )
                                     .wrap_mode(egui::TextWrapMode::Truncate),
-                            );
+                                );
+                            });
                         });
-                    });
-                }
-            }); });
+                    }
+                });
+        });
     }
 
     if result.attention.is_empty() {
@@ -167,9 +182,9 @@ fn render_content(
 
     ui.separator();
     ui.label(
-        RichText::new(format!(
-            "Context lines by attention (top {})",
-            result.attention.len().min(MAX_ENTRIES)
+        RichText::new(tr!(
+            "Context lines by attention (top {count})",
+            count = result.attention.len().min(MAX_ENTRIES)
         ))
         .strong(),
     );
@@ -185,49 +200,59 @@ fn render_content(
         .map_or(1.0_f32, |e| e.weight)
         .max(f32::EPSILON);
 
-    ui.push_id("attention_table", |ui| { TableBuilder::new(ui)
-        .striped(true)
-        .resizable(true)
-        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-        .column(Column::auto().at_least(50.0))
-        .column(Column::initial(130.0).at_least(80.0))
-        .column(Column::remainder().at_least(100.0))
-        .header(20.0, |mut header| {
-            header.col(|ui| { ui.label(RichText::new("Line").strong()); });
-            header.col(|ui| { ui.label(RichText::new("Weight").strong()); });
-            header.col(|ui| { ui.label(RichText::new("Message").strong()); });
-        })
-        .body(|mut body| {
-            for entry in result.attention.iter().take(MAX_ENTRIES) {
-                body.row(18.0, |mut row| {
-                    let sid = StoreID::make(source_id, entry.line_number);
-                    let msg = store
-                        .get_by_id(&sid)
-                        .map(|l| l.message.clone())
-                        .unwrap_or_else(|| format!("<line {}>", entry.line_number));
-
-                    row.col(|ui| { ui.label(entry.line_number.to_string()); });
-
-                    // Attention bar
-                    row.col(|ui| {
-                        let normalized = entry.weight / max_weight;
-                        let fill = weight_color(normalized);
-                        ui.add(
-                            egui::ProgressBar::new(normalized)
-                                .desired_width(ui.available_width())
-                                .fill(fill),
-                        );
-                    });
+    ui.push_id("attention_table", |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::auto().at_least(50.0))
+            .column(Column::initial(130.0).at_least(80.0))
+            .column(Column::remainder().at_least(100.0))
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.label(RichText::new(tr!("Line")).strong());
+                });
+                header.col(|ui| {
+                    ui.label(RichText::new(tr!("Weight")).strong());
+                });
+                header.col(|ui| {
+                    ui.label(RichText::new(tr!("Message")).strong());
+                });
+            })
+            .body(|mut body| {
+                for entry in result.attention.iter().take(MAX_ENTRIES) {
+                    body.row(18.0, |mut row| {
+                        let sid = StoreID::make(source_id, entry.line_number);
+                        let msg = store
+                            .get_by_id(&sid)
+                            .map(|l| l.message)
+                            .unwrap_or_else(|| format!("<line {}>", entry.line_number));
+
+                        row.col(|ui| {
+                            ui.label(entry.line_number.to_string());
+                        });
+
+                        // Attention bar
+                        row.col(|ui| {
+                            let normalized = entry.weight / max_weight;
+                            let fill = weight_color(normalized);
+                            ui.add(
+                                egui::ProgressBar::new(normalized)
+                                    .desired_width(ui.available_width())
+                                    .fill(fill),
+                            );
+                        });
 
-                    row.col(|ui| {
-                        ui.add(
-                            egui::Label::new(RichText::new(msg).monospace())
-                                .wrap_mode(egui::TextWrapMode::Truncate),
-                        );
+                        row.col(|ui| {
+                            ui.add(
+                                egui::Label::new(RichText::new(msg).monospace())
+                                    .wrap_mode(egui::TextWrapMode::Truncate),
+                            );
+                        });
                     });
-                });
-            }
-        }); });
+                }
+            });
+    });
 }
 
 /// Map a normalized attention weight (0 → 1) to a color: gray → orange → red.
@@ -0,0 +1,73 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::log_store::StoreID;
+use crate::tr;
+
+/// Prompt for the name of a bookmark pair spanning a range selection
+pub struct BookmarkRangeWindow {
+    /// First and last line of the range, in timestamp order
+    pub range: (StoreID, StoreID),
+    name: String,
+    focus_requested: bool,
+}
+
+impl BookmarkRangeWindow {
+    #[must_use]
+    pub const fn new(range: (StoreID, StoreID)) -> Self {
+        Self {
+            range,
+            name: String::new(),
+            focus_requested: false,
+        }
+    }
+
+    /// Render the prompt
+    ///
+    /// Returns `Ok(Some(name))` once confirmed and `Ok(None)` while still open.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(())` if the prompt was cancelled.
+    pub fn render(&mut self, ctx: &egui::Context) -> Result<Option<String>, ()> {
+        let mut result = Ok(None);
+        egui::Window::new(tr!("Bookmark Range"))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr!("Name of the range:"));
+                let response = ui.text_edit_singleline(&mut self.name);
+                if !self.focus_requested {
+                    response.request_focus();
+                    self.focus_requested = true;
+                }
+
+                let enter_pressed = ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let escape_pressed = ui.input(|i| i.key_pressed(egui::Key::Escape));
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("Bookmark")).clicked() || enter_pressed {
+                        result = Ok(Some(self.name.trim().to_string()));
+                    }
+                    if ui.button(tr!("Cancel")).clicked() || escape_pressed {
+                        result = Err(());
+                    }
+                });
+            });
+        result
+    }
+}
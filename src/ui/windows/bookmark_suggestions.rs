@@ -0,0 +1,229 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Checklist of bookmark suggestions at the strongest anomaly clusters
+//! (see [`crate::core::bookmark_suggestion`]).
+
+use crate::core::bookmark_suggestion::{suggest_bookmarks, AnomalyCluster};
+use crate::core::log_store::StoreID;
+use crate::core::LogStore;
+use crate::tr;
+use egui_extras::{Column, TableBuilder};
+
+/// Characters of the peak line's message used in a bookmark name
+const NAME_MESSAGE_CHARS: usize = 60;
+
+/// What the user picked in the [`BookmarkSuggestionsWindow`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookmarkSuggestionsAction {
+    /// Select this line
+    Jump(StoreID),
+    /// Bookmark these lines under these names and close
+    Accept(Vec<(StoreID, String)>),
+    Close,
+}
+
+/// Suggested bookmarks the user accepts or rejects one by one
+pub struct BookmarkSuggestionsWindow {
+    /// Use ML sidecar scores instead of the heuristic scores
+    ml: bool,
+    /// Number of clusters suggested
+    limit: usize,
+    /// Lines at most this far apart share a cluster
+    gap_secs: u32,
+    clusters: Vec<AnomalyCluster>,
+    /// Per cluster, whether it gets bookmarked
+    accepted: Vec<bool>,
+}
+
+impl BookmarkSuggestionsWindow {
+    #[must_use]
+    pub fn new(store: &LogStore, ml: bool) -> Self {
+        let mut window = Self {
+            ml,
+            limit: 10,
+            gap_secs: 5,
+            clusters: Vec::new(),
+            accepted: Vec::new(),
+        };
+        window.recompute(store);
+        window
+    }
+
+    fn recompute(&mut self, store: &LogStore) {
+        self.clusters =
+            suggest_bookmarks(store, self.ml, i64::from(self.gap_secs) * 1000, self.limit);
+        self.accepted = vec![true; self.clusters.len()];
+    }
+
+    /// Render the checklist
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        store: &LogStore,
+    ) -> Option<BookmarkSuggestionsAction> {
+        let mut action = None;
+        let mut open = true;
+        egui::Window::new(tr!("Suggested Bookmarks"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(560.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label(tr!("Clusters:"));
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.limit).range(1..=100))
+                        .changed();
+                    ui.label(tr!("Group lines within (s):"));
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.gap_secs).range(0..=3600))
+                        .on_hover_text(tr!(
+                            "High-scoring lines at most this far apart form one cluster"
+                        ))
+                        .changed();
+                });
+                if changed {
+                    self.recompute(store);
+                }
+                if self.ml {
+                    ui.weak(tr!("ML sidecar scores"));
+                }
+                ui.separator();
+
+                if self.clusters.is_empty() {
+                    ui.label(tr!(
+                        "No lines scored {threshold} or more",
+                        threshold = crate::core::bookmark_suggestion::SUGGESTION_MIN_SCORE,
+                    ));
+                } else {
+                    action = self.render_table(ui, store);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let count = self.accepted.iter().filter(|&&a| a).count();
+                    if ui
+                        .add_enabled(
+                            count > 0,
+                            egui::Button::new(tr!("🔖 Bookmark {count} selected", count)),
+                        )
+                        .clicked()
+                    {
+                        action = Some(BookmarkSuggestionsAction::Accept(
+                            self.accepted_bookmarks(store),
+                        ));
+                    }
+                    if ui.button(tr!("Cancel")).clicked() {
+                        action = Some(BookmarkSuggestionsAction::Close);
+                    }
+                });
+            });
+        if !open {
+            action = Some(BookmarkSuggestionsAction::Close);
+        }
+        action
+    }
+
+    fn render_table(
+        &mut self,
+        ui: &mut egui::Ui,
+        store: &LogStore,
+    ) -> Option<BookmarkSuggestionsAction> {
+        let mut action = None;
+        TableBuilder::new(ui)
+            .striped(true)
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::remainder())
+            .max_scroll_height(320.0)
+            .header(20.0, |mut header| {
+                header.col(|_| {});
+                header.col(|ui| {
+                    ui.strong(tr!("Time"));
+                });
+                header.col(|ui| {
+                    ui.strong(tr!("Score"));
+                });
+                header.col(|ui| {
+                    ui.strong(tr!("Lines"));
+                });
+                header.col(|ui| {
+                    ui.strong(tr!("Message"));
+                });
+            })
+            .body(|mut body| {
+                for (cluster, accepted) in self.clusters.iter().zip(self.accepted.iter_mut()) {
+                    let line = store.get_by_id(&cluster.peak);
+                    body.row(18.0, |mut row| {
+                        row.col(|ui| {
+                            ui.checkbox(accepted, "");
+                        });
+                        row.col(|ui| {
+                            let time = line.as_ref().map_or_else(String::new, |line| {
+                                line.timestamp().format("%H:%M:%S%.3f").to_string()
+                            });
+                            if ui
+                                .link(time)
+                                .on_hover_text(tr!("Jump to the highest-scoring line"))
+                                .clicked()
+                            {
+                                action = Some(BookmarkSuggestionsAction::Jump(cluster.peak));
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{:.0}", cluster.peak_score));
+                        });
+                        row.col(|ui| {
+                            ui.label(cluster.lines.to_string());
+                        });
+                        row.col(|ui| {
+                            if let Some(line) = &line {
+                                ui.add(egui::Label::new(line.message.as_str()).truncate());
+                            }
+                        });
+                    });
+                }
+            });
+        action
+    }
+
+    /// Lines and names of the clusters left checked
+    fn accepted_bookmarks(&self, store: &LogStore) -> Vec<(StoreID, String)> {
+        self.clusters
+            .iter()
+            .zip(&self.accepted)
+            .filter(|(_, &accepted)| accepted)
+            .map(|(cluster, _)| {
+                let message: String = store
+                    .get_by_id(&cluster.peak)
+                    .map(|line| line.message.chars().take(NAME_MESSAGE_CHARS).collect())
+                    .unwrap_or_default();
+                let name = tr!(
+                    "Anomaly {score}: {message}",
+                    score = format!("{:.0}", cluster.peak_score),
+                    message
+                );
+                (cluster.peak, name)
+            })
+            .collect()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::tr;
+
 pub struct ChangeFilternameWindow {
     new_name: String,
     focus_requested: bool,
@@ -18,11 +20,11 @@ impl ChangeFilternameWindow {
     /// Err(()) if the operation was cancelled.
     pub fn render(&mut self, ui: &egui::Ui) -> Result<Option<String>, ()> {
         let mut result = Ok(None);
-        egui::Window::new("Rename Filter")
+        egui::Window::new(tr!("Rename Filter"))
             .collapsible(false)
             .resizable(false)
             .show(ui.ctx(), |ui| {
-                ui.label("Enter filter name:");
+                ui.label(tr!("Enter filter name:"));
                 let response = ui.text_edit_singleline(&mut self.new_name);
 
                 // Request focus on first frame only
@@ -40,8 +42,8 @@ impl ChangeFilternameWindow {
 
                 ui.horizontal(|ui| {
                     let should_save =
-                        ui.button("Save").clicked() || enter_pressed || enter_submitted;
-                    let should_cancel = ui.button("Cancel").clicked() || escape_pressed;
+                        ui.button(tr!("Save")).clicked() || enter_pressed || enter_submitted;
+                    let should_cancel = ui.button(tr!("Cancel")).clicked() || escape_pressed;
 
                     if should_save {
                         result = Ok(Some(self.new_name.clone()));
@@ -0,0 +1,176 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::redaction::{RedactionRule, Redactor};
+use crate::tr;
+
+/// Lines shown in the preview pane
+pub const PREVIEW_LINES: usize = 100;
+
+/// Export step that edits the redaction rules and previews their effect
+pub struct ExportRedactionWindow {
+    title: String,
+    rules: Vec<RedactionRule>,
+    /// First [`PREVIEW_LINES`] lines of the export, unredacted
+    preview: Vec<String>,
+    /// `rules` compiled; `None` until the first frame and after a rule was edited
+    redactor: Option<Result<Redactor, String>>,
+}
+
+impl ExportRedactionWindow {
+    #[must_use]
+    pub const fn new(title: String, rules: Vec<RedactionRule>, preview: Vec<String>) -> Self {
+        Self {
+            title,
+            rules,
+            preview,
+            redactor: None,
+        }
+    }
+
+    /// Render the export redaction window
+    ///
+    /// Returns `Ok(Some(rules))` once the user confirmed the export with these rules,
+    /// Ok(None) if the window is still open,
+    /// Err(()) if the export was cancelled.
+    pub fn render(&mut self, ctx: &egui::Context) -> Result<Option<Vec<RedactionRule>>, ()> {
+        let mut result = Ok(None);
+        let mut open = true;
+        egui::Window::new(&self.title)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(640.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(tr!(
+                    "Redaction rules (regex → replacement) applied to every exported line:"
+                ));
+                if self.render_rules(ui) {
+                    self.redactor = None;
+                }
+
+                ui.separator();
+                let redactor = self
+                    .redactor
+                    .get_or_insert_with(|| Redactor::new(&self.rules));
+                match redactor {
+                    Ok(redactor) => Self::render_preview(ui, &self.preview, redactor),
+                    Err(e) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                    }
+                }
+                let compiled = redactor.is_ok();
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(compiled, egui::Button::new(tr!("Export…")))
+                        .clicked()
+                    {
+                        result = Ok(Some(self.rules.clone()));
+                    }
+                    if ui.button(tr!("Cancel")).clicked() {
+                        result = Err(());
+                    }
+                });
+            });
+        if open {
+            result
+        } else {
+            Err(())
+        }
+    }
+
+    /// Edit the rule list; returns `true` if any rule changed
+    fn render_rules(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        let mut remove = None;
+        egui::Grid::new("redaction_rules")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, rule) in self.rules.iter_mut().enumerate() {
+                    changed |= ui.checkbox(&mut rule.enabled, "").changed();
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut rule.pattern)
+                                .hint_text(tr!("regex"))
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(260.0),
+                        )
+                        .changed();
+                    ui.label("→");
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut rule.replacement)
+                                .hint_text(tr!("replacement, $1 for groups"))
+                                .desired_width(140.0),
+                        )
+                        .changed();
+                    if ui
+                        .small_button("🗑")
+                        .on_hover_text(tr!("Remove rule"))
+                        .clicked()
+                    {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(index) = remove {
+            self.rules.remove(index);
+            changed = true;
+        }
+        if ui.button(tr!("➕ Add Rule")).clicked() {
+            self.rules
+                .push(RedactionRule::new(String::new(), "<redacted>"));
+            changed = true;
+        }
+        changed
+    }
+
+    fn render_preview(ui: &mut egui::Ui, preview: &[String], redactor: &Redactor) {
+        let redacted: Vec<_> = preview
+            .iter()
+            .map(|line| (redactor.apply(line), line))
+            .collect();
+        let changed = redacted
+            .iter()
+            .filter(|(after, before)| after.as_ref() != before.as_str())
+            .count();
+        ui.label(tr!(
+            "Preview of the first {count} lines ({changed} changed):",
+            count = preview.len(),
+            changed
+        ));
+        let changed_color = ui.visuals().warn_fg_color;
+        egui::ScrollArea::both()
+            .max_height(300.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for (after, before) in &redacted {
+                    let text = egui::RichText::new(after.as_ref()).monospace();
+                    if after.as_ref() == before.as_str() {
+                        ui.label(text);
+                    } else {
+                        ui.label(text.color(changed_color));
+                    }
+                }
+            });
+    }
+}
@@ -0,0 +1,252 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Ranked overview of every log in a folder, scored headlessly on
+//! background threads so the most suspicious file can be opened first.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+
+use crate::core::log_store::summarize_dispatch;
+use crate::core::rotated_logs::is_hidden_or_session;
+use crate::export::FileSummary;
+use crate::parser::format_time_diff;
+use crate::tr;
+
+/// Column the overview is ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    File,
+    MaxScore,
+    Errors,
+    Duration,
+}
+
+/// One file of the analyzed folder
+struct FolderEntry {
+    path: PathBuf,
+    /// `None` while the file is still being analyzed
+    result: Option<Result<FileSummary, String>>,
+}
+
+/// What the user picked in the [`FolderAnalysisWindow`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FolderAnalysisAction {
+    Open(PathBuf),
+    Close,
+}
+
+/// Window listing the logs of a folder ranked by anomaly score
+pub struct FolderAnalysisWindow {
+    dir: PathBuf,
+    entries: Vec<FolderEntry>,
+    receiver: Receiver<(usize, Result<FileSummary, String>)>,
+    sort: SortKey,
+}
+
+impl FolderAnalysisWindow {
+    /// List the files directly inside `dir` and start analyzing them, one
+    /// worker per available core.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dir` cannot be read.
+    pub fn start(dir: PathBuf, ctx: &egui::Context) -> std::io::Result<Self> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && !is_hidden_or_session(path))
+            .collect();
+        paths.sort();
+
+        let (sender, receiver) = mpsc::channel();
+        let paths = Arc::new(paths);
+        let next = Arc::new(AtomicUsize::new(0));
+        let workers = std::thread::available_parallelism()
+            .map_or(1, std::num::NonZero::get)
+            .min(paths.len());
+        for _ in 0..workers {
+            let (paths, next, sender, ctx) = (
+                Arc::clone(&paths),
+                Arc::clone(&next),
+                sender.clone(),
+                ctx.clone(),
+            );
+            std::thread::spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(idx) else {
+                    return;
+                };
+                let result = summarize_dispatch(path).map_err(|e| format!("{e:#}"));
+                if sender.send((idx, result)).is_err() {
+                    return;
+                }
+                ctx.request_repaint();
+            });
+        }
+
+        let entries = paths
+            .iter()
+            .map(|path| FolderEntry {
+                path: path.clone(),
+                result: None,
+            })
+            .collect();
+        Ok(Self {
+            dir,
+            entries,
+            receiver,
+            sort: SortKey::MaxScore,
+        })
+    }
+
+    /// Render the overview
+    ///
+    /// Returns the file the user chose to open, or `Close` once the window
+    /// was closed.
+    pub fn render(&mut self, ctx: &egui::Context) -> Option<FolderAnalysisAction> {
+        while let Ok((idx, result)) = self.receiver.try_recv() {
+            if let Some(entry) = self.entries.get_mut(idx) {
+                entry.result = Some(result);
+            }
+        }
+        self.sort_entries();
+
+        let mut action = None;
+        let mut open = true;
+        let done = self.entries.iter().filter(|e| e.result.is_some()).count();
+        egui::Window::new(tr!("Folder Analysis"))
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                ui.label(self.dir.display().to_string());
+                if done < self.entries.len() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr!(
+                            "Analyzed {done} of {total} files",
+                            done,
+                            total = self.entries.len()
+                        ));
+                    });
+                } else if self.entries.is_empty() {
+                    ui.weak(tr!("No files in this folder"));
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("folder_analysis_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            self.render_header(ui);
+                            for entry in &self.entries {
+                                if let Some(path) = render_entry(ui, entry) {
+                                    action = Some(FolderAnalysisAction::Open(path));
+                                }
+                            }
+                        });
+                });
+            });
+        if !open {
+            action = Some(FolderAnalysisAction::Close);
+        }
+        action
+    }
+
+    fn render_header(&mut self, ui: &mut egui::Ui) {
+        for (key, label) in [
+            (SortKey::File, tr!("File")),
+            (SortKey::MaxScore, tr!("Max Score")),
+            (SortKey::Errors, tr!("Errors")),
+            (SortKey::Duration, tr!("Duration")),
+        ] {
+            if ui.selectable_label(self.sort == key, label).clicked() {
+                self.sort = key;
+            }
+        }
+        ui.label("");
+        ui.end_row();
+    }
+
+    /// Order by the selected column, largest first, with pending and
+    /// failed files last.
+    fn sort_entries(&mut self) {
+        let sort = self.sort;
+        self.entries.sort_by(|a, b| {
+            let (a_ok, b_ok) = (ok_summary(a), ok_summary(b));
+            match (a_ok, b_ok) {
+                (Some(a_sum), Some(b_sum)) => match sort {
+                    SortKey::File => a.path.cmp(&b.path),
+                    SortKey::MaxScore => b_sum.max_score.total_cmp(&a_sum.max_score),
+                    SortKey::Errors => b_sum.errors.cmp(&a_sum.errors),
+                    SortKey::Duration => b_sum.duration_ms().cmp(&a_sum.duration_ms()),
+                },
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.path.cmp(&b.path),
+            }
+        });
+    }
+}
+
+fn ok_summary(entry: &FolderEntry) -> Option<&FileSummary> {
+    entry.result.as_ref().and_then(|r| r.as_ref().ok())
+}
+
+/// Render one row, returning its path if "Open" was clicked
+fn render_entry(ui: &mut egui::Ui, entry: &FolderEntry) -> Option<PathBuf> {
+    let name = entry.path.file_name().map_or_else(
+        || entry.path.display().to_string(),
+        |n| n.to_string_lossy().to_string(),
+    );
+    match &entry.result {
+        None => {
+            ui.label(name);
+            ui.spinner();
+            ui.label("");
+            ui.label("");
+        }
+        Some(Err(e)) => {
+            ui.label(name).on_hover_text(e);
+            ui.colored_label(ui.visuals().error_fg_color, tr!("Failed"))
+                .on_hover_text(e);
+            ui.label("");
+            ui.label("");
+        }
+        Some(Ok(summary)) => {
+            ui.label(name).on_hover_text(tr!(
+                "{filetype}, {lines} lines",
+                filetype = summary.filetype,
+                lines = summary.lines
+            ));
+            ui.label(format!("{:.0}", summary.max_score));
+            ui.label(summary.errors.to_string());
+            ui.label(summary.duration_ms().map_or_else(String::new, |ms| {
+                format_time_diff(chrono::Duration::milliseconds(ms))
+                    .trim_start_matches('+')
+                    .to_string()
+            }));
+        }
+    }
+    let clicked = ui.button(tr!("Open")).clicked();
+    ui.end_row();
+    clicked.then(|| entry.path.clone())
+}
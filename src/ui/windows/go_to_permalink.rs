@@ -0,0 +1,78 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::permalink::Permalink;
+use crate::tr;
+
+/// Dialog to paste a permalink copied from a log line and jump to it
+#[derive(Default)]
+pub struct GoToPermalinkWindow {
+    text: String,
+    focus_requested: bool,
+}
+
+impl GoToPermalinkWindow {
+    /// Render the go to permalink window
+    ///
+    /// Returns `Ok(Some(link))` once a valid permalink was submitted,
+    /// Ok(None) if the window is still open,
+    /// Err(()) if the operation was cancelled.
+    pub fn render(&mut self, ctx: &egui::Context) -> Result<Option<Permalink>, ()> {
+        let mut result = Ok(None);
+        egui::Window::new(tr!("Go to Permalink"))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr!("Paste a permalink copied from a log line:"));
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.text)
+                        .hint_text("logcrab:…")
+                        .desired_width(360.0),
+                );
+
+                // Request focus on first frame only
+                if !self.focus_requested {
+                    response.request_focus();
+                    self.focus_requested = true;
+                }
+
+                let link = Permalink::parse(&self.text);
+                if link.is_none() && !self.text.trim().is_empty() {
+                    ui.colored_label(ui.visuals().error_fg_color, tr!("Not a LogCrab permalink"));
+                }
+
+                let enter_pressed = ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let escape_pressed = ui.input(|i| i.key_pressed(egui::Key::Escape));
+
+                ui.horizontal(|ui| {
+                    let go = ui
+                        .add_enabled(link.is_some(), egui::Button::new(tr!("Go")))
+                        .clicked();
+                    if go || enter_pressed {
+                        if let Some(link) = link {
+                            result = Ok(Some(link));
+                        }
+                    }
+                    if ui.button(tr!("Cancel")).clicked() || escape_pressed {
+                        result = Err(());
+                    }
+                });
+            });
+        result
+    }
+}
@@ -1,13 +1,31 @@
 pub mod about;
+pub mod activity_log;
 pub mod anomaly_help;
+pub mod anomaly_summary;
 pub mod attention_panel;
+pub mod bookmark_range;
+pub mod bookmark_suggestions;
 pub mod change_filtername;
+pub mod export_redaction;
+pub mod folder_analysis;
+pub mod go_to_permalink;
+pub mod onboarding;
+pub mod scorer_settings;
 pub mod shortcuts;
 pub mod sidecar_settings;
 
 pub use about::render_about_window;
+pub use activity_log::render_activity_log_window;
 pub use anomaly_help::render_anomaly_explanation;
+pub use anomaly_summary::{AnomalySummary, AnomalySummaryAction};
 pub use attention_panel::render_attention_panel;
+pub use bookmark_range::BookmarkRangeWindow;
+pub use bookmark_suggestions::{BookmarkSuggestionsAction, BookmarkSuggestionsWindow};
 pub use change_filtername::ChangeFilternameWindow;
+pub use export_redaction::ExportRedactionWindow;
+pub use folder_analysis::{FolderAnalysisAction, FolderAnalysisWindow};
+pub use go_to_permalink::GoToPermalinkWindow;
+pub use onboarding::{OnboardingAction, OnboardingWindow};
+pub use scorer_settings::{ScorerSettingsAction, ScorerSettingsWindow};
 pub use shortcuts::render_shortcuts_window;
 pub use sidecar_settings::SidecarSettingsWindow;
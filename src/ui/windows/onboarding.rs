@@ -0,0 +1,151 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::input::{KeyboardBindings, ShortcutAction};
+use crate::tr;
+use std::path::PathBuf;
+
+/// Android logcat excerpt shipped with the binary so that first-time users can
+/// try filters, the histogram and bookmarks without looking for a log first
+const SAMPLE_LOG: &str = include_str!("../../../assets/sample.log");
+
+/// File name of the sample log when written to the temp directory
+const SAMPLE_LOG_FILENAME: &str = "logcrab-sample.log";
+
+/// What the user chose in the onboarding overlay
+pub enum OnboardingAction {
+    /// Open the bundled sample log
+    OpenSample,
+    /// Pick a log file of their own
+    OpenFile,
+    /// Close the overlay
+    Dismiss,
+}
+
+/// Overlay shown on first launch that points out the main UI elements
+#[derive(Default)]
+pub struct OnboardingWindow;
+
+impl OnboardingWindow {
+    /// Render the onboarding overlay
+    ///
+    /// Returns the user's choice once a button was pressed or the window was closed.
+    #[must_use]
+    pub fn render(
+        &self,
+        ctx: &egui::Context,
+        bindings: &KeyboardBindings,
+    ) -> Option<OnboardingAction> {
+        let mut action = None;
+        let mut open = true;
+
+        egui::Window::new(tr!("Getting Started"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(tr!("Welcome to LogCrab 🦀"));
+                });
+                ui.add_space(10.0);
+
+                let bookmark_key = bindings.get_shortcut(ShortcutAction::ToggleBookmark);
+                let bookmark_text = if bookmark_key.is_empty() {
+                    tr!(
+                        "Right-click a line to bookmark it. Bookmarks are saved next to the log \
+                         and listed in the bookmarks tab."
+                    )
+                    .to_string()
+                } else {
+                    tr!(
+                        "Press {key} (or right-click) to bookmark the selected line. \
+                         Bookmarks are saved next to the log and listed in the bookmarks tab.",
+                        key = bookmark_key
+                    )
+                };
+
+                highlight(
+                    ui,
+                    tr!("🔍 Filter bar"),
+                    tr!(
+                        "Type a regex above the log table to narrow the lines down. \
+                         Each filter lives in its own tab, so several views can sit side by side."
+                    ),
+                );
+                highlight(
+                    ui,
+                    tr!("📊 Histogram"),
+                    tr!(
+                        "Shows how the filtered lines are spread over time, colored by anomaly \
+                         score. Click it to jump to that moment, shift-drag to zoom in."
+                    ),
+                );
+                highlight(ui, tr!("🔖 Bookmarks"), &bookmark_text);
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr!("Open Sample Log"))
+                        .on_hover_text(tr!("A short Android logcat with a crash to explore"))
+                        .clicked()
+                    {
+                        action = Some(OnboardingAction::OpenSample);
+                    }
+                    if ui.button(tr!("Open Log File…")).clicked() {
+                        action = Some(OnboardingAction::OpenFile);
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(tr!("Got it")).clicked() {
+                            action = Some(OnboardingAction::Dismiss);
+                        }
+                    });
+                });
+            });
+
+        if open {
+            action
+        } else {
+            Some(OnboardingAction::Dismiss)
+        }
+    }
+}
+
+/// Render one highlighted UI element with a short explanation
+fn highlight(ui: &mut egui::Ui, title: &str, text: &str) {
+    egui::Frame::group(ui.style())
+        .stroke(egui::Stroke::new(1.5, ui.visuals().selection.stroke.color))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.label(egui::RichText::new(title).strong());
+            ui.label(text);
+        });
+    ui.add_space(4.0);
+}
+
+/// Write the bundled sample log to the temp directory and return its path
+///
+/// # Errors
+///
+/// Returns the I/O error if the file cannot be written.
+pub fn write_sample_log() -> std::io::Result<PathBuf> {
+    let path = std::env::temp_dir().join(SAMPLE_LOG_FILENAME);
+    std::fs::write(&path, SAMPLE_LOG)?;
+    Ok(path)
+}
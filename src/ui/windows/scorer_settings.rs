@@ -0,0 +1,141 @@
+// LogCrab - GPL-3.0-or-later
+// This file is part of LogCrab.
+//
+// Copyright (C) 2026 Daniel Freiermuth
+//
+// LogCrab is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// LogCrab is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with LogCrab.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::anomaly::ScorerWeights;
+use crate::tr;
+
+/// What the user did in the [`ScorerSettingsWindow`]
+pub enum ScorerSettingsAction {
+    /// Keep `weights` for future scoring, and score the loaded sources again
+    /// with them if `rescore` is set
+    Save {
+        weights: ScorerWeights,
+        rescore: bool,
+    },
+    Close,
+}
+
+/// Weights of the heuristic scorers and the temporal window, edited as a draft
+/// until saved
+pub struct ScorerSettingsWindow {
+    weights: ScorerWeights,
+}
+
+impl ScorerSettingsWindow {
+    #[must_use]
+    pub const fn new(weights: ScorerWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Render the window; `can_rescore` is whether sources are loaded.
+    ///
+    /// Returns `None` while the window stays open.
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        can_rescore: bool,
+    ) -> Option<ScorerSettingsAction> {
+        let mut action = None;
+        let mut open = true;
+        egui::Window::new(tr!("Anomaly Scorer Settings"))
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(tr!(
+                    "A line's score is the weighted average of the scorers below; \
+                     a weight of 0 turns a scorer off."
+                ));
+                ui.add_space(5.0);
+
+                let weights = &mut self.weights;
+                egui::Grid::new("scorer_weights")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (label, hint, weight) in [
+                            (
+                                tr!("Rarity"),
+                                tr!("Lines whose message template is seldom seen"),
+                                &mut weights.rarity,
+                            ),
+                            (
+                                tr!("Temporal"),
+                                tr!("Templates reappearing after a long gap, and bursts"),
+                                &mut weights.temporal,
+                            ),
+                            (
+                                tr!("Entropy"),
+                                tr!("Messages with unusually random content"),
+                                &mut weights.entropy,
+                            ),
+                            (
+                                tr!("Keyword"),
+                                tr!("Error, warning and failure keywords"),
+                                &mut weights.keyword,
+                            ),
+                            (
+                                tr!("Request"),
+                                tr!("HTTP 5xx responses and latency outliers"),
+                                &mut weights.request,
+                            ),
+                        ] {
+                            ui.label(label).on_hover_text(hint);
+                            ui.add(egui::DragValue::new(weight).range(0.0..=10.0).speed(0.1));
+                            ui.end_row();
+                        }
+                        ui.label(tr!("Temporal window"))
+                            .on_hover_text(tr!("Time span in which bursts of lines are counted"));
+                        ui.add(
+                            egui::DragValue::new(&mut weights.temporal_window_secs)
+                                .range(1..=3600)
+                                .suffix(" s"),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("Reset to Defaults")).clicked() {
+                        self.weights = ScorerWeights::DEFAULT;
+                    }
+                    if ui.button(tr!("Save")).clicked() {
+                        action = Some(ScorerSettingsAction::Save {
+                            weights: self.weights,
+                            rescore: false,
+                        });
+                    }
+                    if ui
+                        .add_enabled(can_rescore, egui::Button::new(tr!("Save and Rescore")))
+                        .on_hover_text(tr!(
+                            "Score the loaded files again with these weights in the background"
+                        ))
+                        .clicked()
+                    {
+                        action = Some(ScorerSettingsAction::Save {
+                            weights: self.weights,
+                            rescore: true,
+                        });
+                    }
+                });
+            });
+        if !open {
+            action = Some(ScorerSettingsAction::Close);
+        }
+        action
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::GlobalConfig;
 use crate::input::{KeyboardBindings, ShortcutAction};
+use crate::tr;
 
 /// Render the keyboard shortcuts configuration window
 pub fn render_shortcuts_window(
@@ -9,7 +10,7 @@ pub fn render_shortcuts_window(
     pending_rebind: &mut Option<ShortcutAction>,
     global_config: &mut GlobalConfig,
 ) {
-    egui::Window::new("⌨ Keyboard Shortcuts")
+    egui::Window::new(tr!("⌨ Keyboard Shortcuts"))
         .open(open)
         .default_width(480.0)
         .resizable(true)
@@ -21,7 +22,7 @@ pub fn render_shortcuts_window(
             ui.set_min_width(ui.available_width());
             ui.horizontal(|ui| {
                 ui.label(
-                    egui::RichText::new("⚙ Keyboard Bindings")
+                    egui::RichText::new(tr!("⚙ Keyboard Bindings"))
                         .strong()
                         .size(13.0)
                         .color(egui::Color32::from_rgb(100, 150, 255)),
@@ -29,7 +30,7 @@ pub fn render_shortcuts_window(
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if ui
-                        .button(egui::RichText::new("↺ Reset").size(10.0))
+                        .button(egui::RichText::new(tr!("↺ Reset")).size(10.0))
                         .clicked()
                     {
                         *shortcut_bindings = KeyboardBindings::default();
@@ -37,12 +38,9 @@ pub fn render_shortcuts_window(
                         // Save the reset bindings
                         shortcut_bindings.save_to_config(global_config);
                         let default_shortcuts = global_config.shortcuts.clone();
-                        match GlobalConfig::update(|c| {
+                        *global_config = GlobalConfig::update(|c| {
                             c.shortcuts = default_shortcuts;
-                        }) {
-                            Ok(updated) => *global_config = updated,
-                            Err(e) => tracing::error!("Failed to save config: {e}"),
-                        }
+                        });
                     }
                 });
             });
@@ -97,21 +95,21 @@ pub fn render_shortcuts_window(
                         if *pending_rebind == Some(*action) {
                             ui.colored_label(
                                 egui::Color32::from_rgb(255, 200, 100),
-                                egui::RichText::new("⌛ Press any key...").strong(),
+                                egui::RichText::new(tr!("⌛ Press any key...")).strong(),
                             );
-                            if ui.button("✖ Cancel").clicked() {
+                            if ui.button(tr!("✖ Cancel")).clicked() {
                                 *pending_rebind = None;
                             }
                         } else if is_rebindable {
                             if ui
-                                .button(egui::RichText::new("🔧 Rebind").size(11.0))
+                                .button(egui::RichText::new(tr!("🔧 Rebind")).size(11.0))
                                 .clicked()
                             {
                                 *pending_rebind = Some(*action);
                             }
                         } else {
                             ui.label(
-                                egui::RichText::new("(hardcoded)")
+                                egui::RichText::new(tr!("(hardcoded)"))
                                     .size(10.0)
                                     .color(ui.visuals().weak_text_color()),
                             );
@@ -120,6 +118,9 @@ pub fn render_shortcuts_window(
                 });
             }
 
+            ui.add_space(12.0);
+            ui.separator();
+            render_quick_bookmark_template(ui, global_config);
             ui.add_space(4.0);
         });
 
@@ -128,3 +129,38 @@ pub fn render_shortcuts_window(
         *pending_rebind = None;
     }
 }
+
+/// Editor for the name template used by the quick bookmark shortcut
+#[allow(clippy::literal_string_with_formatting_args)] // Template placeholders, not format args
+fn render_quick_bookmark_template(ui: &mut egui::Ui, global_config: &mut GlobalConfig) {
+    ui.label(
+        egui::RichText::new(tr!("🔖 Quick Bookmark Name"))
+            .strong()
+            .size(13.0)
+            .color(egui::Color32::from_rgb(100, 150, 255)),
+    );
+    ui.label(
+        egui::RichText::new(tr!(
+            "Placeholders: {message}, {source}, {line}, {time} or any parsed field such as \
+             {level}, {tag}, {apid}. Use {message:40} to keep only the first 40 characters."
+        ))
+        .size(10.0)
+        .color(ui.visuals().weak_text_color()),
+    );
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut global_config.quick_bookmark_template)
+                .desired_width(ui.available_width() - 70.0)
+                .font(egui::TextStyle::Monospace),
+        );
+        let reset = ui.button(tr!("↺ Reset")).clicked();
+        if reset {
+            global_config.quick_bookmark_template =
+                crate::core::bookmark_template::DEFAULT_QUICK_BOOKMARK_TEMPLATE.to_string();
+        }
+        if response.lost_focus() || reset {
+            let new_val = global_config.quick_bookmark_template.clone();
+            *global_config = GlobalConfig::update(|c| c.quick_bookmark_template = new_val);
+        }
+    });
+}
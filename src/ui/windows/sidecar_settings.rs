@@ -18,6 +18,7 @@
 
 use crate::anomaly::sidecar_client::{ModelInfo, SidecarClient};
 use crate::config::GlobalConfig;
+use crate::tr;
 use egui::{Color32, RichText, Ui};
 
 pub struct SidecarSettingsWindow {
@@ -60,26 +61,34 @@ impl SidecarSettingsWindow {
     pub fn render(&mut self, ui: &mut Ui, config: &mut GlobalConfig) -> bool {
         let mut changed = false;
 
-        ui.heading("Sidecar Settings");
+        ui.heading(tr!("Sidecar Settings"));
         ui.separator();
 
-        ui.label("Configure the LogBERT sidecar server for ML-based anomaly detection");
+        ui.label(tr!(
+            "Configure the LogBERT sidecar server for ML-based anomaly detection"
+        ));
         ui.add_space(10.0);
 
         // Server configuration
         ui.group(|ui| {
-            ui.label(RichText::new("Server Configuration").strong());
+            ui.label(RichText::new(tr!("Server Configuration")).strong());
             ui.add_space(5.0);
 
-            let host_changed = ui.horizontal(|ui| {
-                ui.label("Host:");
-                ui.text_edit_singleline(&mut self.temp_host)
-            }).inner.changed();
-
-            let port_changed = ui.horizontal(|ui| {
-                ui.label("Port:");
-                ui.text_edit_singleline(&mut self.temp_port)
-            }).inner.changed();
+            let host_changed = ui
+                .horizontal(|ui| {
+                    ui.label(tr!("Host:"));
+                    ui.text_edit_singleline(&mut self.temp_host)
+                })
+                .inner
+                .changed();
+
+            let port_changed = ui
+                .horizontal(|ui| {
+                    ui.label(tr!("Port:"));
+                    ui.text_edit_singleline(&mut self.temp_port)
+                })
+                .inner
+                .changed();
 
             if host_changed || port_changed {
                 if self.apply_settings(config) {
@@ -91,14 +100,14 @@ impl SidecarSettingsWindow {
 
             // Test connection button
             ui.horizontal(|ui| {
-                if ui.button("Test Connection").clicked() {
+                if ui.button(tr!("Test Connection")).clicked() {
                     self.test_connection();
                 }
 
                 match &self.connection_status {
                     ConnectionStatus::Unknown => {}
                     ConnectionStatus::Connected => {
-                        ui.colored_label(Color32::GREEN, "✓ Connected");
+                        ui.colored_label(Color32::GREEN, tr!("✓ Connected"));
 
                         if self.available_models.is_empty() && !self.models_loading {
                             self.load_models();
@@ -118,29 +127,29 @@ impl SidecarSettingsWindow {
             let prev_model = config.selected_model.clone();
 
             ui.group(|ui| {
-                ui.label(RichText::new("Model Selection").strong());
+                ui.label(RichText::new(tr!("Model Selection")).strong());
                 ui.add_space(5.0);
 
                 if self.models_loading {
                     ui.horizontal(|ui| {
                         ui.spinner();
-                        ui.label("Loading available models...");
+                        ui.label(tr!("Loading available models..."));
                     });
                 } else if let Some(error) = &self.models_error {
-                    ui.colored_label(Color32::RED, format!("Error loading models: {error}"));
-                    if ui.button("Retry").clicked() {
+                    ui.colored_label(Color32::RED, tr!("Error loading models: {error}", error));
+                    if ui.button(tr!("Retry")).clicked() {
                         self.load_models();
                     }
                 } else if self.available_models.is_empty() {
-                    ui.label("No models available");
+                    ui.label(tr!("No models available"));
                 } else {
-                    ui.label("Select model:");
+                    ui.label(tr!("Select model:"));
 
                     let current_id = config.selected_model.as_deref().unwrap_or("");
 
                     egui::ComboBox::from_label("")
                         .selected_text(if current_id.is_empty() {
-                            "Select a model...".to_string()
+                            tr!("Select a model...").to_string()
                         } else {
                             self.available_models
                                 .iter()
@@ -172,29 +181,43 @@ impl SidecarSettingsWindow {
                         });
 
                     // Details for the selected model
-                    if let Some(model) = config.selected_model.as_ref().and_then(|id| {
-                        self.available_models.iter().find(|m| &m.id == id)
-                    }) {
+                    if let Some(model) = config
+                        .selected_model
+                        .as_ref()
+                        .and_then(|id| self.available_models.iter().find(|m| &m.id == id))
+                    {
                         ui.add_space(5.0);
-                        ui.label(RichText::new("Model Details:").weak());
-                        ui.label(format!("Architecture: {}", model.architecture));
-                        ui.label(format!("Version: {}", model.version));
+                        ui.label(RichText::new(tr!("Model Details:")).weak());
+                        ui.label(tr!(
+                            "Architecture: {architecture}",
+                            architecture = model.architecture
+                        ));
+                        ui.label(tr!("Version: {version}", version = model.version));
 
                         let norm_versions = crate::core::log_store::all_normalization_versions();
                         let mismatches = normalization_mismatches(model, &norm_versions);
                         if mismatches.is_empty() {
-                            ui.colored_label(Color32::GREEN, "✓ Normalisation versions match");
+                            ui.colored_label(Color32::GREEN, tr!("✓ Normalisation versions match"));
                         } else {
                             ui.add_space(3.0);
-                            ui.colored_label(Color32::YELLOW, "⚠ Normalisation version mismatch:");
+                            ui.colored_label(
+                                Color32::YELLOW,
+                                tr!("⚠ Normalisation version mismatch:"),
+                            );
                             for (slug, trained_on, current) in &mismatches {
-                                ui.label(format!(
-                                    "  {slug}: trained on v{trained_on}, frontend is v{current}"
+                                ui.label(tr!(
+                                    "  {slug}: trained on v{trained_on}, frontend is v{current}",
+                                    slug,
+                                    trained_on,
+                                    current
                                 ));
                             }
-                            ui.label(RichText::new(
-                                "Scores may be less accurate for affected file types.",
-                            ).weak());
+                            ui.label(
+                                RichText::new(tr!(
+                                    "Scores may be less accurate for affected file types."
+                                ))
+                                .weak(),
+                            );
                         }
                     }
                 }
@@ -215,7 +238,7 @@ impl SidecarSettingsWindow {
         self.models_error = None;
         let Ok(port) = self.temp_port.parse::<u16>() else {
             self.connection_status =
-                ConnectionStatus::Failed("Invalid port number".to_string());
+                ConnectionStatus::Failed(tr!("Invalid port number").to_string());
             return;
         };
 
@@ -241,7 +264,7 @@ impl SidecarSettingsWindow {
         self.models_error = None;
 
         let Ok(port) = self.temp_port.parse::<u16>() else {
-            self.models_error = Some("Invalid port number".to_string());
+            self.models_error = Some(tr!("Invalid port number").to_string());
             self.models_loading = false;
             return;
         };